@@ -0,0 +1,372 @@
+//! The `build` subcommand, which builds the native library for each target platform
+//! before generating the bindings.
+//!
+//! Each runtime identifier is built with `cargo build --target <triple>`, and the
+//! resulting library is copied into the output directory using the layout that NuGet
+//! expects, i.e. `runtimes/<rid>/native/<library>`. With `--unity`, the libraries are
+//! instead copied into the `Plugins` folder for each platform.
+//!
+//! The bindings are then generated from the library built for the current platform,
+//! loading the library by the crate's library name, e.g. `mahjong` rather than
+//! `libmahjong`. The .NET runtime and Unity both add the platform's prefix and
+//! extension when resolving a `DllImport`, so the same bindings load the right library
+//! on every platform.
+
+use crate::{pack::NativeLib, Opt};
+use failure::Error;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct BuildOpt {
+    /// The runtime identifier of a platform to build the library for, e.g. `win-x64`,
+    /// `linux-x64`, `osx-x64`, or `osx-arm64`. Defaults to the current platform.
+    ///
+    /// Can be specified multiple times to build for multiple platforms. The Rust
+    /// target for each platform must be installed, e.g. with `rustup target add`.
+    #[structopt(long = "rid", number_of_values = 1)]
+    rids: Vec<String>,
+
+    /// The package containing the `cdylib` to build. Only required if the workspace
+    /// contains more than one `cdylib`.
+    #[structopt(short, long)]
+    package: Option<String>,
+
+    /// Path to the `Cargo.toml` of the package or workspace.
+    #[structopt(long, parse(from_os_str))]
+    manifest_path: Option<PathBuf>,
+
+    /// Build the libraries with the release profile.
+    #[structopt(long)]
+    release: bool,
+
+    /// Space-separated list of features to enable when building the libraries.
+    #[structopt(long)]
+    features: Option<String>,
+}
+
+/// A platform that the native library can be built for.
+struct Platform {
+    /// The .NET runtime identifier for the platform.
+    rid: &'static str,
+
+    /// The Rust target triple for the platform.
+    triple: &'static str,
+
+    /// The prefix and extension of dynamic libraries on the platform.
+    prefix: &'static str,
+    extension: &'static str,
+}
+
+const PLATFORMS: &[Platform] = &[
+    Platform {
+        rid: "win-x64",
+        triple: "x86_64-pc-windows-msvc",
+        prefix: "",
+        extension: "dll",
+    },
+    Platform {
+        rid: "win-x86",
+        triple: "i686-pc-windows-msvc",
+        prefix: "",
+        extension: "dll",
+    },
+    Platform {
+        rid: "win-arm64",
+        triple: "aarch64-pc-windows-msvc",
+        prefix: "",
+        extension: "dll",
+    },
+    Platform {
+        rid: "linux-x64",
+        triple: "x86_64-unknown-linux-gnu",
+        prefix: "lib",
+        extension: "so",
+    },
+    Platform {
+        rid: "linux-arm64",
+        triple: "aarch64-unknown-linux-gnu",
+        prefix: "lib",
+        extension: "so",
+    },
+    Platform {
+        rid: "osx-x64",
+        triple: "x86_64-apple-darwin",
+        prefix: "lib",
+        extension: "dylib",
+    },
+    Platform {
+        rid: "osx-arm64",
+        triple: "aarch64-apple-darwin",
+        prefix: "lib",
+        extension: "dylib",
+    },
+    Platform {
+        rid: "android-arm64",
+        triple: "aarch64-linux-android",
+        prefix: "lib",
+        extension: "so",
+    },
+    Platform {
+        rid: "android-arm",
+        triple: "armv7-linux-androideabi",
+        prefix: "lib",
+        extension: "so",
+    },
+    Platform {
+        rid: "android-x86",
+        triple: "i686-linux-android",
+        prefix: "lib",
+        extension: "so",
+    },
+];
+
+impl Platform {
+    fn from_rid(rid: &str) -> Result<&'static Platform, Error> {
+        PLATFORMS
+            .iter()
+            .find(|platform| platform.rid == rid)
+            .ok_or_else(|| {
+                let known = PLATFORMS
+                    .iter()
+                    .map(|platform| platform.rid)
+                    .collect::<Vec<_>>();
+                failure::format_err!(
+                    "Unknown runtime identifier `{}`, expected one of: {}",
+                    rid,
+                    known.join(", "),
+                )
+            })
+    }
+
+    /// Returns the platform that the CLI is running on, which is the only platform
+    /// that the libraries can be loaded on to read their exports.
+    fn host() -> Option<&'static Platform> {
+        let triple = match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+            ("windows", "x86") => "i686-pc-windows-msvc",
+            ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+            ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+            ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+            ("macos", "x86_64") => "x86_64-apple-darwin",
+            ("macos", "aarch64") => "aarch64-apple-darwin",
+            _ => return None,
+        };
+
+        PLATFORMS.iter().find(|platform| platform.triple == triple)
+    }
+
+    fn file_name(&self, lib_name: &str) -> String {
+        format!("{}{}.{}", self.prefix, lib_name, self.extension)
+    }
+}
+
+/// The parts of the output of `cargo metadata` that are needed to find the library.
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    target_directory: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    targets: Vec<Target>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    name: String,
+    crate_types: Vec<String>,
+}
+
+/// Builds the library for each platform and copies it into the output directory, then
+/// updates `opt` to generate the bindings from the library built for the current
+/// platform.
+pub fn build(build: &BuildOpt, opt: &mut Opt) -> Result<(), Error> {
+    let out_dir = opt.out_dir.clone().ok_or_else(|| {
+        failure::err_msg("An output directory must be specified with `--out-dir` for `build`")
+    })?;
+
+    let host = Platform::host()
+        .ok_or_else(|| failure::err_msg("`build` isn't supported on the current platform"))?;
+    let platforms = if build.rids.is_empty() {
+        vec![host]
+    } else {
+        build
+            .rids
+            .iter()
+            .map(|rid| Platform::from_rid(rid))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let metadata = cargo_metadata(build)?;
+    let (package, lib_name) = find_cdylib(&metadata, build.package.as_deref())?;
+    let profile = if build.release { "release" } else { "debug" };
+
+    let mut native_libs = Vec::new();
+    for platform in &platforms {
+        cargo_build(build, package, Some(platform.triple))?;
+
+        let built = metadata
+            .target_directory
+            .join(platform.triple)
+            .join(profile)
+            .join(platform.file_name(&lib_name));
+        native_libs.push(NativeLib {
+            rid: platform.rid.into(),
+            path: built,
+        });
+    }
+
+    // The exports are read by loading the library, which is only possible for the
+    // library built for the current platform. If that isn't one of the requested
+    // platforms, it's built separately without being added to the output.
+    let input = match native_libs.iter().find(|lib| lib.rid == host.rid) {
+        Some(lib) => lib.path.clone(),
+        None => {
+            cargo_build(build, package, None)?;
+            metadata
+                .target_directory
+                .join(profile)
+                .join(host.file_name(&lib_name))
+        }
+    };
+
+    // NOTE: Unity packages copy the libraries into their `Plugins` folders when the
+    // bindings are written, so they're only copied here for the NuGet layout.
+    if !opt.unity {
+        for lib in &mut native_libs {
+            lib.path = copy_runtime_lib(lib, &out_dir)?;
+        }
+    }
+
+    opt.input = vec![input];
+    opt.native_lib = native_libs;
+    if opt.dll_name.is_none() {
+        opt.dll_name = Some(lib_name);
+    }
+
+    Ok(())
+}
+
+fn cargo_metadata(build: &BuildOpt) -> Result<Metadata, Error> {
+    let mut command = Command::new("cargo");
+    command.args(&["metadata", "--format-version=1", "--no-deps"]);
+    if let Some(manifest_path) = &build.manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+
+    let output = command
+        .output()
+        .map_err(|err| failure::format_err!("Failed to run `cargo metadata`: {}", err))?;
+    if !output.status.success() {
+        return Err(failure::format_err!(
+            "`cargo metadata` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr),
+        ));
+    }
+
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// Finds the package containing the `cdylib` to build, returning the name of the
+/// package along with the name of the library.
+fn find_cdylib<'a>(
+    metadata: &'a Metadata,
+    package: Option<&str>,
+) -> Result<(&'a str, String), Error> {
+    let mut cdylibs = metadata
+        .packages
+        .iter()
+        .filter(|candidate| package.map_or(true, |package| candidate.name == package))
+        .flat_map(|package| {
+            package
+                .targets
+                .iter()
+                .filter(|target| target.crate_types.iter().any(|ty| ty == "cdylib"))
+                .map(move |target| (&*package.name, target))
+        })
+        .collect::<Vec<_>>();
+
+    match (cdylibs.len(), package) {
+        // NOTE: Cargo names the library after the target with any `-` replaced by `_`.
+        (1, _) => {
+            let (package, target) = cdylibs.remove(0);
+            Ok((package, target.name.replace('-', "_")))
+        }
+
+        (0, Some(package)) => Err(failure::format_err!(
+            "Package `{}` doesn't have a `cdylib` target",
+            package,
+        )),
+        (0, None) => Err(failure::err_msg(
+            "No package in the workspace has a `cdylib` target",
+        )),
+
+        _ => {
+            let names = cdylibs
+                .iter()
+                .map(|(package, _)| *package)
+                .collect::<Vec<_>>();
+            Err(failure::format_err!(
+                "Multiple packages have a `cdylib` target, specify one with `--package`: {}",
+                names.join(", "),
+            ))
+        }
+    }
+}
+
+fn cargo_build(build: &BuildOpt, package: &str, triple: Option<&str>) -> Result<(), Error> {
+    let mut command = Command::new("cargo");
+    command.arg("build").arg("--package").arg(package);
+    if let Some(manifest_path) = &build.manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+    if let Some(triple) = triple {
+        command.arg("--target").arg(triple);
+    }
+    if build.release {
+        command.arg("--release");
+    }
+    if let Some(features) = &build.features {
+        command.arg("--features").arg(features);
+    }
+
+    let status = command
+        .status()
+        .map_err(|err| failure::format_err!("Failed to run `cargo build`: {}", err))?;
+    if !status.success() {
+        return Err(failure::format_err!(
+            "`cargo build` for {} exited with {}",
+            triple.unwrap_or("the current platform"),
+            status,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Copies a library into the `runtimes/<rid>/native` folder of the output directory,
+/// returning the path of the copy.
+fn copy_runtime_lib(lib: &NativeLib, out_dir: &Path) -> Result<PathBuf, Error> {
+    let file_name = lib.path.file_name().ok_or_else(|| {
+        failure::format_err!("Native library path has no file name: {:?}", lib.path)
+    })?;
+
+    let native_dir = out_dir.join("runtimes").join(&lib.rid).join("native");
+    fs::create_dir_all(&native_dir)?;
+
+    let dest = native_dir.join(file_name);
+    fs::copy(&lib.path, &dest).map_err(|err| {
+        failure::format_err!("Failed to copy native library {:?}: {}", lib.path, err)
+    })?;
+
+    Ok(dest)
+}
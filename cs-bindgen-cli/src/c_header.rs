@@ -0,0 +1,681 @@
+//! C header backend, selected with `--target c`.
+//!
+//! Generates a header declaring every function exported from the built library,
+//! along with the raw types used to pass values across the FFI boundary. The header
+//! describes the same ABI that the C# bindings use, so hosts that can call into C
+//! (C++, Swift, etc.) can use the library directly:
+//!
+//! * Handle types are declared as opaque structs and passed by pointer.
+//! * Value types are declared as the raw struct that Rust converts to and from.
+//! * Built-in types (strings, collections, callbacks) use the shared raw types
+//!   declared at the top of the header, e.g. `RawVec`.
+//!
+//! Every binding function takes a trailing `RawVec* panic` argument. If the Rust code
+//! panics, the panic message is written to it as a UTF-8 string, which must be freed
+//! with `__cs_bindgen_drop_vec_u8`.
+//!
+//! NOTE: C has no namespaces, so types are declared with their Rust name and exported
+//! types with the same name in different modules will conflict.
+
+use crate::{
+    generate::{enum_values, primitive_repr, repr_for_schema, GeneratedFile},
+    Opt,
+};
+use cs_bindgen_shared::{
+    schematic::Schema, BindingStyle, ConstValue, Export, FnArg, NamedType, ReceiverStyle, Repr,
+    TypeName,
+};
+use heck::*;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    fmt::Write,
+};
+
+/// Generates the header file for the exports.
+pub fn generate(exports: &[Export], opt: &Opt) -> Vec<GeneratedFile> {
+    let name = opt
+        .input()
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .expect("Unable to get name of input file")
+        .to_string();
+
+    let types = exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::Named(export) => Some((&export.type_name, export)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let mut header = Header {
+        types,
+        declared: HashSet::new(),
+        definitions: String::new(),
+    };
+
+    let mut functions = String::new();
+    for element in VEC_ELEMENTS {
+        writeln!(
+            functions,
+            "void __cs_bindgen_drop_vec_{}(RawVec raw);\nRawVec __cs_bindgen_convert_vec_{}(RawSlice raw);",
+            element, element
+        )
+        .unwrap();
+    }
+    writeln!(functions).unwrap();
+
+    for export in exports {
+        header.declare_bindings(export, &mut functions);
+    }
+
+    let guard = format!("{}_H", name.to_shouty_snake_case());
+    let mut out = String::new();
+    writeln!(out, "// Generated by cs-bindgen. Do not edit.\n").unwrap();
+    writeln!(out, "#ifndef {}\n#define {}\n", guard, guard).unwrap();
+    writeln!(out, "{}", PRELUDE).unwrap();
+    writeln!(out, "{}", header.definitions).unwrap();
+    writeln!(out, "{}", functions).unwrap();
+    writeln!(
+        out,
+        "#ifdef __cplusplus\n}} // extern \"C\"\n#endif\n\n#endif // {}",
+        guard
+    )
+    .unwrap();
+
+    vec![GeneratedFile {
+        name: format!("{}.h", name),
+        contents: out,
+    }]
+}
+
+const PRELUDE: &str = "\
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+typedef struct RawVec {
+    const void* ptr;
+    uintptr_t len;
+    uintptr_t capacity;
+} RawVec;
+
+typedef struct RawSlice {
+    const void* ptr;
+    uintptr_t len;
+} RawSlice;
+
+typedef struct RawMap {
+    void* keys;
+    void* values;
+    uintptr_t len;
+} RawMap;
+
+typedef struct RawI128 {
+    uint64_t low;
+    uint64_t high;
+} RawI128;
+
+typedef struct RawU128 {
+    uint64_t low;
+    uint64_t high;
+} RawU128;
+
+typedef struct RawGuid {
+    uint8_t bytes[16];
+} RawGuid;
+
+typedef struct RawCallback {
+    const void* func;
+    void* context;
+} RawCallback;
+
+typedef struct RawCompletion {
+    const void* func;
+    void* context;
+} RawCompletion;
+
+typedef struct RawIter {
+    void* iter;
+    const void* next;
+    const void* drop;
+} RawIter;
+
+typedef struct RawManaged {
+    const void* const* vtable;
+    void* context;
+} RawManaged;
+
+uint32_t __cs_bindgen_abi_version(void);
+
+uint8_t* __cs_bindgen_alloc_buffer(uintptr_t size);
+void __cs_bindgen_free_buffer(uint8_t* ptr, uintptr_t size);
+uint8_t* __cs_bindgen_alloc(uintptr_t size, uintptr_t align);
+void __cs_bindgen_free(uint8_t* ptr, uintptr_t size, uintptr_t align);
+uint8_t __cs_bindgen_set_allocator(
+    uint8_t* (*alloc_fn)(uintptr_t size, uintptr_t align),
+    void (*free_fn)(uint8_t* ptr, uintptr_t size, uintptr_t align));
+
+RawVec __cs_bindgen_string_from_utf16(RawSlice raw);
+uintptr_t __cs_bindgen_string_utf16_len(RawSlice raw);
+void __cs_bindgen_string_write_utf16(RawSlice raw, uint16_t* dest, uintptr_t len);
+
+const void* __cs_bindgen_cancel_flag_new(void);
+void __cs_bindgen_cancel_flag_cancel(const void* flag);
+void __cs_bindgen_cancel_flag_drop(const void* flag);
+
+uint8_t __cs_bindgen_iter_next(RawIter raw, void* item, RawVec* panic);
+void __cs_bindgen_iter_drop(RawIter raw);
+";
+
+/// The element types with built-in functions for dropping and converting lists.
+const VEC_ELEMENTS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize", "f32", "f64", "bool",
+    "char",
+];
+
+struct Header<'a> {
+    types: HashMap<&'a TypeName, &'a NamedType>,
+
+    /// The names of the types that have already been written to `definitions`.
+    declared: HashSet<String>,
+
+    /// The type definitions, in an order where each type is defined before it's used
+    /// by value.
+    definitions: String,
+}
+
+impl<'a> Header<'a> {
+    fn declare_bindings(&mut self, export: &Export, out: &mut String) {
+        match export {
+            Export::Fn(export) => {
+                let mut args = self.quote_args(&export.inputs);
+                if export.is_async {
+                    args.push("RawCompletion __completion".into());
+                }
+                args.push(panic_arg());
+
+                let output = match &export.output {
+                    Some(output) if !export.is_async => self.c_type(output),
+                    _ => "void".into(),
+                };
+
+                write_docs(out, export.docs.as_deref());
+                write_fn(out, &output, &export.binding, &args);
+            }
+
+            Export::Method(export) => {
+                let mut args = self.quote_args(&export.inputs);
+                if export.receiver.is_some() {
+                    let self_ty = self.self_type(&export.self_type);
+                    let constness = match export.receiver {
+                        Some(ReceiverStyle::Ref) => "const ",
+                        _ => "",
+                    };
+                    args.insert(0, format!("{}{}* self", constness, self_ty));
+                }
+                args.push(panic_arg());
+
+                let output = match &export.output {
+                    Some(output) => self.c_type(output),
+                    None => "void".into(),
+                };
+
+                write_docs(out, export.docs.as_deref());
+                write_fn(out, &output, &export.binding, &args);
+            }
+
+            Export::Named(export) => self.declare_named_bindings(export, out),
+
+            Export::Trait(export) => {
+                for method in &export.methods {
+                    let mut args = self.quote_args(&method.inputs);
+                    args.insert(0, "void* self".into());
+                    args.push(panic_arg());
+
+                    let output = match &method.output {
+                        Some(output) => self.c_type(output),
+                        None => "void".into(),
+                    };
+                    write_fn(out, &output, &method.binding, &args);
+
+                    // The default implementation takes the raw managed object instead of
+                    // a trait object.
+                    if let Some(default_binding) = &method.default_binding {
+                        args[0] = "RawManaged self".into();
+                        write_fn(out, &output, default_binding, &args);
+                    }
+                }
+
+                // A `Box<dyn Trait>` returned from Rust is borrowed as a trait object in
+                // order to call its methods.
+                write_fn(
+                    out,
+                    "void*",
+                    &export.boxed_as_dyn_fn,
+                    &["void* handle".into()],
+                );
+                write_fn(out, "void", &export.drop_boxed_fn, &["void* handle".into()]);
+                writeln!(out).unwrap();
+            }
+
+            Export::TraitImpl(export) => {
+                let self_ty = self.self_type(&export.self_type);
+                write_fn(
+                    out,
+                    "void*",
+                    &export.as_dyn_fn,
+                    &[format!("{}* handle", self_ty)],
+                );
+            }
+
+            // Std trait impls are merged into their types when loading the exports.
+            Export::StdImpl(_) => {}
+
+            Export::Conversion(export) => {
+                let output = if export.fallible {
+                    self.c_type(&Repr::Option(Box::new(export.into.clone())))
+                } else {
+                    self.c_type(&export.into)
+                };
+                let args = [format!("{} value", self.c_type(&export.from)), panic_arg()];
+                write_fn(out, &output, &export.binding, &args);
+            }
+
+            Export::Operator(export) => {
+                let output = self.c_type(&export.output);
+                let mut args = vec![format!("{} lhs", self.c_type(&export.lhs))];
+                if let Some(rhs) = &export.rhs {
+                    args.push(format!("{} rhs", self.c_type(rhs)));
+                }
+                args.push(panic_arg());
+                write_fn(out, &output, &export.binding, &args);
+            }
+
+            Export::List(export) => {
+                let raw = self.c_type(&export.element);
+                write_fn(
+                    out,
+                    &raw,
+                    &export.index_fn,
+                    &["RawSlice slice".into(), "uintptr_t index".into()],
+                );
+                write_fn(
+                    out,
+                    "RawVec",
+                    &export.convert_list_fn,
+                    &["RawSlice raw".into()],
+                );
+                write_fn(out, "void", &export.drop_vec_fn, &["RawVec vec".into()]);
+                writeln!(out).unwrap();
+            }
+
+            Export::Const(export) => match &export.value {
+                Some(value) if export.self_type.is_none() => {
+                    let value = match value {
+                        ConstValue::Bool(value) => (*value as u8).to_string(),
+                        ConstValue::Int(value) => value.to_string(),
+                        ConstValue::UInt(value) => format!("{}u", value),
+                        ConstValue::Float(value) => format!("{:?}", value),
+                        ConstValue::String(value) => format!("{:?}", value),
+                    };
+                    writeln!(out, "#define {} {}\n", export.name, value).unwrap();
+                }
+
+                // Associated constants with a value are only known to the C# bindings.
+                Some(_) => {}
+
+                None => {
+                    let output = self.c_type(&export.repr);
+                    write_fn(out, &output, &export.binding, &[panic_arg()]);
+                }
+            },
+
+            // Aliases don't have any bindings, and the C types for the aliased types are
+            // declared where they're used.
+            Export::Alias(_) => {}
+        }
+    }
+
+    /// Declares the type definition for a named type, along with the helper functions
+    /// that are exported for the type.
+    fn declare_named_bindings(&mut self, export: &NamedType, out: &mut String) {
+        let raw = self.c_type(&Repr::Named(export.type_name.clone()));
+
+        if let BindingStyle::Handle = export.binding_style {
+            if let Some(drop_fn) = &export.drop_fn {
+                write_fn(out, "void", drop_fn, &[format!("{} self", raw)]);
+            }
+        } else {
+            write_fn(
+                out,
+                &raw,
+                &export.index_fn,
+                &["RawSlice slice".into(), "uintptr_t index".into()],
+            );
+            write_fn(
+                out,
+                "RawVec",
+                &export.convert_list_fn,
+                &["RawSlice raw".into()],
+            );
+            write_fn(out, "void", &export.drop_vec_fn, &["RawVec vec".into()]);
+            if let Some(size_fn) = &export.size_fn {
+                write_fn(out, "uintptr_t", size_fn, &[]);
+            }
+        }
+
+        let self_arg = format!("{} self", raw);
+        let other_arg = format!("{} other", raw);
+        let fns = [
+            (&export.default_fn, raw.as_str(), vec![]),
+            (&export.display_fn, "RawVec", vec![self_arg.clone()]),
+            (
+                &export.eq_fn,
+                "uint8_t",
+                vec![self_arg.clone(), other_arg.clone()],
+            ),
+            (&export.hash_fn, "uint64_t", vec![self_arg.clone()]),
+            (&export.cmp_fn, "int32_t", vec![self_arg.clone(), other_arg]),
+            (&export.clone_fn, raw.as_str(), vec![self_arg]),
+        ];
+        for (binding, output, mut args) in fns.iter().cloned() {
+            if let Some(binding) = binding {
+                args.push(panic_arg());
+                write_fn(out, output, binding, &args);
+            }
+        }
+
+        writeln!(out).unwrap();
+    }
+
+    fn quote_args(&mut self, inputs: &[FnArg]) -> Vec<String> {
+        inputs
+            .iter()
+            .map(|arg| format!("{} {}", self.c_type(&arg.repr), arg.name))
+            .collect()
+    }
+
+    /// Returns the type that a method's receiver points to, i.e. the opaque struct for
+    /// handle types and the raw struct otherwise.
+    fn self_type(&mut self, type_name: &TypeName) -> String {
+        let raw = self.c_type(&Repr::Named(type_name.clone()));
+        raw.trim_end_matches('*').to_string()
+    }
+
+    /// Returns the C type for the raw representation of `repr`, defining the type
+    /// first if necessary.
+    fn c_type(&mut self, repr: &Repr) -> String {
+        match repr {
+            Repr::Unit | Repr::Bool | Repr::U8 => "uint8_t".into(),
+            Repr::Char | Repr::U32 => "uint32_t".into(),
+            Repr::I8 => "int8_t".into(),
+            Repr::I16 => "int16_t".into(),
+            Repr::I32 => "int32_t".into(),
+            Repr::I64 => "int64_t".into(),
+            Repr::ISize => "intptr_t".into(),
+            Repr::U16 => "uint16_t".into(),
+            Repr::U64 => "uint64_t".into(),
+            Repr::USize => "uintptr_t".into(),
+            Repr::I128 => "RawI128".into(),
+            Repr::U128 => "RawU128".into(),
+            Repr::F32 => "float".into(),
+            Repr::F64 => "double".into(),
+
+            Repr::Named(type_name) => self.named_type(type_name),
+
+            Repr::Box(_) | Repr::Ref(_) | Repr::Dyn(_) | Repr::DynHandle(_) => "void*".into(),
+            Repr::RefMut(inner) => format!("{}*", self.c_type(inner)),
+            Repr::BoxDyn(_) => "RawManaged".into(),
+
+            Repr::Vec(_) | Repr::String => "RawVec".into(),
+            Repr::Slice(_) | Repr::Str => "RawSlice".into(),
+            Repr::Map { .. } => "RawMap".into(),
+            Repr::Iter(_) | Repr::Events(_) => "RawIter".into(),
+            Repr::Fn { .. } => "RawCallback".into(),
+            Repr::CancelFlag => "const void*".into(),
+
+            // Time types are passed as a number of 100 nanosecond ticks.
+            Repr::Duration | Repr::SystemTime => "int64_t".into(),
+            Repr::Date => "int32_t".into(),
+            Repr::Uuid => "RawGuid".into(),
+
+            Repr::Array { element, len } => {
+                let element_ty = self.c_type(element);
+                let ident = format!("RawArray_{}_{}", mangle(repr_name(element)), len);
+                self.define(&ident, format!("    {} elements[{}];\n", element_ty, len))
+            }
+
+            Repr::Tuple(elements) => {
+                let element_tys = elements
+                    .iter()
+                    .map(|element| self.c_type(element))
+                    .collect::<Vec<_>>();
+                let ident = format!(
+                    "RawTuple_{}",
+                    elements
+                        .iter()
+                        .map(|element| mangle(repr_name(element)))
+                        .collect::<Vec<_>>()
+                        .join("_")
+                );
+                let fields = element_tys
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ty)| format!("    {} element{};\n", ty, index))
+                    .collect();
+                self.define(&ident, fields)
+            }
+
+            Repr::Option(inner) => {
+                let inner_ty = self.c_type(inner);
+                let ident = format!("RawOption_{}", mangle(repr_name(inner)));
+                let fields = format!("    uint8_t is_some;\n    {} value;\n", inner_ty);
+                self.define(&ident, fields)
+            }
+
+            Repr::Result { ok, err } => {
+                let ok_ty = self.c_type(ok);
+                let err_ty = self.c_type(err);
+                let ident = format!(
+                    "RawResult_{}_{}",
+                    mangle(repr_name(ok)),
+                    mangle(repr_name(err))
+                );
+                let fields = format!(
+                    "    uint8_t is_ok;\n    union {{\n        {} ok;\n        {} err;\n    }} value;\n",
+                    ok_ty, err_ty
+                );
+                self.define(&ident, fields)
+            }
+        }
+    }
+
+    fn named_type(&mut self, type_name: &TypeName) -> String {
+        let export = *self
+            .types
+            .get(type_name)
+            .unwrap_or_else(|| panic!("No export found for named type {:?}", type_name));
+        let ident = type_name.name.to_string();
+
+        match &export.binding_style {
+            BindingStyle::Handle => {
+                if self.declared.insert(ident.clone()) {
+                    writeln!(self.definitions, "typedef struct {} {};\n", ident, ident).unwrap();
+                }
+                format!("{}*", ident)
+            }
+
+            BindingStyle::Marshal(..) => "RawVec".into(),
+            BindingStyle::Custom(custom) => self.c_type(&custom.raw),
+            BindingStyle::Transparent(transparent) => self.c_type(&transparent.inner),
+
+            BindingStyle::Value(Schema::Enum(schema)) if !schema.has_data() => {
+                if self.declared.contains(&ident) {
+                    return ident;
+                }
+
+                let discriminant = export
+                    .discriminant
+                    .clone()
+                    .or_else(|| schema.repr.map(primitive_repr))
+                    .unwrap_or(Repr::ISize);
+                let discriminant = self.c_type(&discriminant);
+
+                let variants = enum_values(schema)
+                    .into_iter()
+                    .map(|(name, value)| format!("    {}_{} = {},\n", ident, name, value))
+                    .collect::<String>();
+                writeln!(
+                    self.definitions,
+                    "typedef {} {};\n\nenum {{\n{}}};\n",
+                    discriminant, ident, variants
+                )
+                .unwrap();
+                self.declared.insert(ident.clone());
+                ident
+            }
+
+            BindingStyle::Value(Schema::Enum(schema)) => {
+                if self.declared.contains(&ident) {
+                    return ident;
+                }
+
+                // Data-carrying enums match `RawEnum<D, V>`: A pointer-sized
+                // discriminant, followed by a union of the variants that have fields.
+                let mut variants = String::new();
+                for variant in &schema.variants {
+                    let fields = variant.fields().collect::<Vec<_>>();
+                    if fields.is_empty() {
+                        continue;
+                    }
+
+                    writeln!(variants, "        struct {{").unwrap();
+                    for (index, field) in fields.iter().enumerate() {
+                        let ty = self.schema_type(&field.schema);
+                        writeln!(
+                            variants,
+                            "            {} {};",
+                            ty,
+                            field_name(field.name, index)
+                        )
+                        .unwrap();
+                    }
+                    writeln!(variants, "        }} {};", variant.name()).unwrap();
+                }
+
+                let fields = format!(
+                    "    intptr_t discriminant;\n    union {{\n{}    }} value;\n",
+                    variants
+                );
+                self.define(&ident, fields)
+            }
+
+            BindingStyle::Value(schema) => {
+                if self.declared.contains(&ident) {
+                    return ident;
+                }
+
+                let schema = schema
+                    .as_struct_like()
+                    .unwrap_or_else(|| panic!("Unsupported value type {:?}", type_name));
+
+                // NOTE: Flags are passed as their underlying integer, which is the only
+                // field of the struct.
+                if export.is_flags {
+                    let bits = self.schema_type(&schema.fields[0].schema);
+                    writeln!(self.definitions, "typedef {} {};\n", bits, ident).unwrap();
+                    self.declared.insert(ident.clone());
+                    return ident;
+                }
+
+                let fields = schema
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        let ty = self.schema_type(&field.schema);
+                        format!("    {} {};\n", ty, field_name(field.name, index))
+                    })
+                    .collect::<String>();
+                self.define(&ident, fields)
+            }
+        }
+    }
+
+    fn schema_type(&mut self, schema: &Schema) -> String {
+        let repr = repr_for_schema(schema)
+            .unwrap_or_else(|| panic!("Unsupported field type: {:?}", schema));
+        self.c_type(&repr)
+    }
+
+    /// Writes the definition of a struct with the specified fields, unless it has
+    /// already been defined.
+    ///
+    /// The types of the fields must be resolved before calling this, so that any
+    /// types they depend on are defined first.
+    fn define(&mut self, ident: &str, fields: String) -> String {
+        if self.declared.insert(ident.into()) {
+            writeln!(
+                self.definitions,
+                "typedef struct {} {{\n{}}} {};\n",
+                ident, fields, ident
+            )
+            .unwrap();
+        }
+
+        ident.into()
+    }
+}
+
+/// Returns a name for the repr that can be used in the names of generated types.
+fn repr_name(repr: &Repr) -> String {
+    match repr {
+        Repr::Named(type_name) => type_name.name.to_string(),
+        Repr::Array { element, len } => format!("{}x{}", repr_name(element), len),
+        Repr::Option(inner) => format!("Option_{}", repr_name(inner)),
+        Repr::Result { ok, err } => format!("Result_{}_{}", repr_name(ok), repr_name(err)),
+        Repr::Tuple(elements) => format!(
+            "Tuple_{}",
+            elements.iter().map(repr_name).collect::<Vec<_>>().join("_")
+        ),
+        _ => format!("{:?}", repr),
+    }
+}
+
+/// Strips any characters that aren't valid in a C identifier.
+fn mangle(name: String) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect()
+}
+
+fn panic_arg() -> String {
+    "RawVec* panic".into()
+}
+
+fn field_name(name: Option<&str>, index: usize) -> String {
+    match name {
+        Some(name) => name.into(),
+        None => format!("element{}", index),
+    }
+}
+
+fn write_fn(out: &mut String, output: &str, name: &str, args: &[String]) {
+    let args = if args.is_empty() {
+        "void".to_string()
+    } else {
+        args.join(", ")
+    };
+    writeln!(out, "{} {}({});", output, name, args).unwrap();
+}
+
+fn write_docs(out: &mut String, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        for line in docs.lines() {
+            writeln!(out, "//{}", line).unwrap();
+        }
+    }
+}
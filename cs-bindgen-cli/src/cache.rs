@@ -0,0 +1,134 @@
+//! Incremental generation when writing the bindings to an output directory.
+//!
+//! The output directory contains a cache file recording a hash of the inputs that the
+//! bindings were generated from, i.e. the export metadata, the generation options,
+//! and the version of cs-bindgen, along with a hash of each generated file. If the
+//! inputs haven't changed since the last run and the generated files haven't been
+//! modified, generating the bindings is skipped entirely.
+//!
+//! Otherwise the bindings are regenerated, but only the files whose contents changed
+//! are written. This preserves the timestamps of the other files, so build tools that
+//! watch the output directory (e.g. MSBuild or the Unity editor) don't recompile them.
+//! Files that were generated by the previous run but no longer are, e.g. for a type
+//! that was removed, are deleted.
+//!
+//! `--force` ignores the cache and rewrites every file.
+
+use crate::{generate::GeneratedFile, metadata::Metadata, Opt};
+use cs_bindgen_shared::Export;
+use failure::Error;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// The name of the cache file in the output directory.
+const CACHE_FILE_NAME: &str = ".cs-bindgen-cache.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    /// The hash of the inputs that the files were generated from.
+    inputs: u64,
+
+    /// The hash of each generated file, keyed by its path relative to the output
+    /// directory.
+    files: BTreeMap<String, u64>,
+}
+
+impl Cache {
+    /// Loads the cache from the output directory.
+    ///
+    /// A missing or unreadable cache is treated as empty, which regenerates all files.
+    fn load(out_dir: &Path) -> Self {
+        fs::read_to_string(out_dir.join(CACHE_FILE_NAME))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Hashes the inputs that determine the generated bindings.
+pub fn hash_inputs(exports: &[Export], opt: &Opt) -> u64 {
+    let metadata = Metadata::new(exports.to_vec()).to_json();
+    let options = format!("{:?}", opt);
+    hash(
+        [
+            env!("CARGO_PKG_VERSION").as_bytes(),
+            metadata.as_bytes(),
+            options.as_bytes(),
+        ]
+        .iter()
+        .flat_map(|bytes| bytes.iter().copied()),
+    )
+}
+
+/// Returns `true` if the files in the output directory were generated from the same
+/// inputs and haven't been modified since.
+pub fn is_up_to_date(out_dir: &Path, inputs: u64) -> bool {
+    let cache = Cache::load(out_dir);
+    cache.inputs == inputs
+        && !cache.files.is_empty()
+        && cache.files.iter().all(|(name, &file_hash)| {
+            fs::read(out_dir.join(name)).map_or(false, |contents| {
+                hash(contents.iter().copied()) == file_hash
+            })
+        })
+}
+
+/// Writes the generated files that have changed to the output directory, removes the
+/// files left over from the previous run, and updates the cache.
+///
+/// If `force` is set, all files are written regardless of whether they changed.
+pub fn write_files(
+    out_dir: &Path,
+    files: &[GeneratedFile],
+    inputs: u64,
+    force: bool,
+) -> Result<(), Error> {
+    let previous = Cache::load(out_dir);
+    let mut cache = Cache {
+        inputs,
+        files: BTreeMap::new(),
+    };
+
+    for file in files {
+        let path = out_dir.join(&file.name);
+        let unchanged =
+            fs::read(&path).map_or(false, |existing| existing == file.contents.as_bytes());
+        if force || !unchanged {
+            // NOTE: Some targets generate files in subdirectories, e.g. the Python
+            // package directory.
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &file.contents)?;
+        }
+
+        cache
+            .files
+            .insert(file.name.clone(), hash(file.contents.bytes()));
+    }
+
+    for name in previous.files.keys() {
+        if cache.files.contains_key(name) {
+            continue;
+        }
+
+        match fs::remove_file(out_dir.join(name)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+
+    fs::write(
+        out_dir.join(CACHE_FILE_NAME),
+        serde_json::to_string_pretty(&cache)?,
+    )?;
+
+    Ok(())
+}
+
+/// Hashes `bytes` with 64-bit FNV-1a, which is stable across runs and platforms.
+fn hash(bytes: impl Iterator<Item = u8>) -> u64 {
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
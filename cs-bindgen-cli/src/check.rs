@@ -0,0 +1,432 @@
+//! API compatibility checking between two sets of export declarations.
+//!
+//! Each export is identified by its kind and its Rust path, and two exports with the
+//! same identity are compared by their signature, i.e. everything about the export
+//! that affects the generated C# API. Docs and the names of the generated binding
+//! functions are ignored, since they don't affect code using the bindings.
+//!
+//! Removing or changing an export is a breaking change, whereas adding an export is
+//! not. Optional capabilities of an export (e.g. a type implementing `Display`, or a
+//! function supporting batched calls) are compared separately: Adding a capability
+//! only adds to the C# API, whereas removing one is a breaking change.
+
+use crate::{load_decl::load_declarations, metadata::Metadata};
+use cs_bindgen_shared::{Export, Repr, TypeName};
+use failure::Error;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, ffi::OsStr, fs, path::Path};
+
+/// The result of comparing two sets of export declarations.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl Report {
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    pub fn print(&self) {
+        for item in &self.added {
+            println!("+ {}", item);
+        }
+
+        for item in &self.removed {
+            println!("- {}", item);
+        }
+
+        for item in &self.changed {
+            println!("~ {}", item);
+        }
+
+        if self.is_breaking() {
+            println!(
+                "{} breaking change(s) found",
+                self.removed.len() + self.changed.len()
+            );
+        } else {
+            println!("No breaking changes found");
+        }
+    }
+}
+
+/// Compares the declarations in `old` and `new`, each of which is either a build
+/// artifact or a JSON document written by the `dump` subcommand.
+pub fn check(old: &Path, new: &Path) -> Result<Report, Error> {
+    Ok(compare(&load(old)?, &load(new)?))
+}
+
+/// Compares two sets of export declarations.
+fn compare(old: &[Export], new: &[Export]) -> Report {
+    let old = signatures(old);
+    let new = signatures(new);
+
+    let mut report = Report::default();
+    for (identity, signature) in &new {
+        let old_signature = match old.get(identity) {
+            Some(old_signature) => old_signature,
+            None => {
+                report.added.push(identity.clone());
+                continue;
+            }
+        };
+
+        if old_signature.value != signature.value {
+            report.changed.push(identity.clone());
+            continue;
+        }
+
+        for (capability, &enabled) in &signature.capabilities {
+            let was_enabled = old_signature
+                .capabilities
+                .get(capability)
+                .copied()
+                .unwrap_or(false);
+            if enabled && !was_enabled {
+                report.added.push(format!("{}: {}", identity, capability));
+            } else if was_enabled && !enabled {
+                report.removed.push(format!("{}: {}", identity, capability));
+            }
+        }
+    }
+
+    report.removed.extend(
+        old.keys()
+            .filter(|identity| !new.contains_key(*identity))
+            .cloned(),
+    );
+
+    report
+}
+
+fn load(path: &Path) -> Result<Vec<Export>, Error> {
+    if path.extension() == Some(OsStr::new("json")) {
+        let json = fs::read_to_string(path)
+            .map_err(|err| failure::format_err!("Failed to read {:?}: {}", path, err))?;
+        Ok(Metadata::from_json(&json)?.exports)
+    } else {
+        load_declarations(path)
+    }
+}
+
+/// The signature of an export.
+#[derive(Debug)]
+struct Signature {
+    /// The parts of the signature that have to match exactly.
+    value: Value,
+
+    /// Whether the export has each of its optional capabilities.
+    capabilities: BTreeMap<&'static str, bool>,
+}
+
+impl Signature {
+    fn new(value: Value) -> Self {
+        Signature {
+            value,
+            capabilities: BTreeMap::new(),
+        }
+    }
+
+    fn with(mut self, capability: &'static str, enabled: bool) -> Self {
+        self.capabilities.insert(capability, enabled);
+        self
+    }
+}
+
+/// Returns the signature of each export, keyed by the identity of the export.
+fn signatures(exports: &[Export]) -> BTreeMap<String, Signature> {
+    exports
+        .iter()
+        // The list helpers are generated wherever a list type is used, so they change
+        // along with the items that use them and aren't part of the API.
+        .filter(|export| !matches!(export, Export::List(_)))
+        .map(|export| match export {
+            Export::Fn(export) => (
+                format!("fn {}::{}", export.module, export.name),
+                Signature::new(json!({
+                    "rename": export.rename,
+                    "inputs": export.inputs,
+                    "output": export.output,
+                    "is_async": export.is_async,
+                }))
+                .with("batch", export.command.is_some()),
+            ),
+
+            Export::Method(export) => (
+                format!("method {}::{}", path(&export.self_type), export.name),
+                Signature::new(json!({
+                    "rename": export.rename,
+                    "receiver": export.receiver,
+                    "inputs": export.inputs,
+                    "output": export.output,
+                    "accessor": export.accessor,
+                    "is_constructor": export.is_constructor,
+                }))
+                .with("batch", export.command.is_some()),
+            ),
+
+            Export::Named(export) => (
+                format!("type {}", path(&export.type_name)),
+                Signature::new(json!({
+                    "binding_style": export.binding_style,
+                    "renamed_fields": export.renamed_fields,
+                    "discriminant": export.discriminant,
+                    "is_flags": export.is_flags,
+                }))
+                .with("default", export.default_fn.is_some())
+                .with("display", export.display_fn.is_some())
+                .with("eq", export.eq_fn.is_some())
+                .with("hash", export.hash_fn.is_some())
+                .with("cmp", export.cmp_fn.is_some())
+                .with("clone", export.clone_fn.is_some()),
+            ),
+
+            Export::Trait(export) => {
+                let methods = export
+                    .methods
+                    .iter()
+                    .map(|method| {
+                        json!({
+                            "name": method.name,
+                            "inputs": method.inputs,
+                            "output": method.output,
+                            "default": method.default_binding.is_some(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (
+                    format!("trait {}", path(&export.type_name)),
+                    Signature::new(json!({
+                        "methods": methods,
+                        "managed": export.managed,
+                    })),
+                )
+            }
+
+            Export::TraitImpl(export) => (
+                format!(
+                    "impl {} for {}",
+                    path(&export.trait_name),
+                    path(&export.self_type)
+                ),
+                Signature::new(Value::Null),
+            ),
+
+            Export::StdImpl(export) => (
+                format!("impl {:?} for {}", export.trait_, path(&export.self_type)),
+                Signature::new(Value::Null),
+            ),
+
+            Export::Conversion(export) => (
+                format!(
+                    "conversion {} -> {}",
+                    repr_path(&export.from),
+                    repr_path(&export.into)
+                ),
+                Signature::new(json!({
+                    "fallible": export.fallible,
+                })),
+            ),
+
+            Export::Operator(export) => (
+                match &export.rhs {
+                    Some(rhs) => format!(
+                        "impl {:?}<{}> for {}",
+                        export.trait_,
+                        repr_path(rhs),
+                        repr_path(&export.lhs)
+                    ),
+                    None => format!("impl {:?} for {}", export.trait_, repr_path(&export.lhs)),
+                },
+                Signature::new(json!({
+                    "output": export.output,
+                })),
+            ),
+
+            Export::Const(export) => {
+                let identity = match &export.self_type {
+                    Some(self_type) => format!("const {}::{}", path(self_type), export.name),
+                    None => format!("const {}::{}", export.module, export.name),
+                };
+                (
+                    identity,
+                    Signature::new(json!({
+                        "repr": export.repr,
+                        "value": export.value,
+                    })),
+                )
+            }
+
+            Export::Alias(export) => (
+                format!("alias {}::{}", export.module, export.name),
+                Signature::new(json!({
+                    "repr": export.repr,
+                })),
+            ),
+
+            Export::List(_) => unreachable!("List helpers are filtered out above"),
+        })
+        .collect()
+}
+
+fn path(type_name: &TypeName) -> String {
+    format!("{}::{}", type_name.module, type_name.name)
+}
+
+/// Returns the Rust path of a named type, or the debug representation of any other
+/// type.
+fn repr_path(repr: &Repr) -> String {
+    match repr {
+        Repr::Named(type_name) => path(type_name),
+        _ => format!("{:?}", repr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cs_bindgen_shared::{BindingStyle, Const, ConstValue, FnArg, Func, NamedType};
+
+    fn func(module: &'static str, name: &'static str, inputs: Vec<FnArg>) -> Export {
+        Export::Fn(Func {
+            name: name.into(),
+            module: module.into(),
+            binding: format!("__cs_bindgen_generated__{}", name).into(),
+            rename: None,
+            docs: None,
+            inputs,
+            output: None,
+            out_params: Vec::new(),
+            is_async: false,
+            command: None,
+        })
+    }
+
+    fn handle_type(name: &'static str, display: bool) -> Export {
+        Export::Named(NamedType {
+            type_name: TypeName::new(name, "mahjong::tile"),
+            binding_style: BindingStyle::Handle,
+            index_fn: "index".into(),
+            drop_vec_fn: "drop_vec".into(),
+            convert_list_fn: "convert_list".into(),
+            drop_fn: Some("drop".into()),
+            size_fn: None,
+            renamed_fields: Vec::new(),
+            docs: None,
+            variant_docs: Vec::new(),
+            discriminant: None,
+            is_non_exhaustive: false,
+            is_send: true,
+            is_sync: true,
+            is_flags: false,
+            default_fn: None,
+            display_fn: if display {
+                Some("display".into())
+            } else {
+                None
+            },
+            eq_fn: None,
+            hash_fn: None,
+            cmp_fn: None,
+            clone_fn: None,
+        })
+    }
+
+    #[test]
+    fn added_export_is_not_breaking() {
+        let old = vec![func("mahjong", "shuffle", Vec::new())];
+        let new = vec![
+            func("mahjong", "shuffle", Vec::new()),
+            func("mahjong", "deal", Vec::new()),
+        ];
+
+        let report = compare(&old, &new);
+        assert_eq!(vec!["fn mahjong::deal"], report.added);
+        assert!(!report.is_breaking());
+    }
+
+    #[test]
+    fn removed_export_is_breaking() {
+        let old = vec![
+            func("mahjong", "shuffle", Vec::new()),
+            func("mahjong", "deal", Vec::new()),
+        ];
+        let new = vec![func("mahjong", "shuffle", Vec::new())];
+
+        let report = compare(&old, &new);
+        assert_eq!(vec!["fn mahjong::deal"], report.removed);
+        assert!(report.is_breaking());
+    }
+
+    #[test]
+    fn changed_export_is_breaking() {
+        let old = vec![func("mahjong", "deal", Vec::new())];
+        let new = vec![func("mahjong", "deal", vec![FnArg::new("seed", Repr::U64)])];
+
+        let report = compare(&old, &new);
+        assert_eq!(vec!["fn mahjong::deal"], report.changed);
+        assert!(report.added.is_empty());
+        assert!(report.removed.is_empty());
+        assert!(report.is_breaking());
+    }
+
+    #[test]
+    fn added_capability_is_not_breaking() {
+        let old = vec![handle_type("Tile", false)];
+        let new = vec![handle_type("Tile", true)];
+
+        let report = compare(&old, &new);
+        assert_eq!(vec!["type mahjong::tile::Tile: display"], report.added);
+        assert!(report.changed.is_empty());
+        assert!(!report.is_breaking());
+    }
+
+    #[test]
+    fn removed_capability_is_breaking() {
+        let old = vec![handle_type("Tile", true)];
+        let new = vec![handle_type("Tile", false)];
+
+        let report = compare(&old, &new);
+        assert_eq!(vec!["type mahjong::tile::Tile: display"], report.removed);
+        assert!(report.changed.is_empty());
+        assert!(report.is_breaking());
+    }
+
+    #[test]
+    fn free_items_are_identified_by_module() {
+        let constant = |module: &'static str| {
+            Export::Const(Const {
+                name: "MAX_PLAYERS".into(),
+                module: module.into(),
+                binding: "__cs_bindgen_generated__MAX_PLAYERS".into(),
+                self_type: None,
+                repr: Repr::U8,
+                value: Some(ConstValue::UInt(4)),
+            })
+        };
+
+        let old = vec![
+            func("mahjong::hand", "score", Vec::new()),
+            constant("mahjong"),
+        ];
+        let new = vec![
+            func("mahjong::hand", "score", Vec::new()),
+            func("mahjong::yaku", "score", Vec::new()),
+            constant("mahjong"),
+            constant("mahjong::sanma"),
+        ];
+
+        let report = compare(&old, &new);
+        assert_eq!(
+            vec![
+                "const mahjong::sanma::MAX_PLAYERS",
+                "fn mahjong::yaku::score"
+            ],
+            report.added,
+        );
+        assert!(!report.is_breaking());
+    }
+}
@@ -0,0 +1,202 @@
+//! Loading of generation settings from a `cs-bindgen.toml` config file.
+//!
+//! Every setting in the config file has a corresponding command line option, and
+//! options specified on the command line take precedence over the config file. Flags
+//! can only be enabled from the command line, so a flag enabled in the config file
+//! can't be disabled by the command line.
+//!
+//! Settings for individual types are specified in a `[types."<path>"]` table, using
+//! the full Rust path of the type, e.g. `[types."my_crate::tile::Tile"]`.
+
+use crate::{pack::NativeLib, Opt, Target};
+use cs_bindgen_shared::TypeName;
+use failure::Error;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// The name of the config file that's loaded from the current directory if no config
+/// file is specified.
+pub const DEFAULT_CONFIG_FILE: &str = "cs-bindgen.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub output: Option<PathBuf>,
+    pub out_dir: Option<PathBuf>,
+    pub target: Option<String>,
+    pub target_framework: Option<String>,
+    pub class_name: Option<String>,
+    pub dll_name: Option<String>,
+    pub root_namespace: Option<String>,
+    pub thread_checks: bool,
+    pub trace: bool,
+    pub namespaces: bool,
+    pub nullable: bool,
+    pub enum_classes: bool,
+    pub unity: bool,
+    pub source_generator: bool,
+    pub blazor: bool,
+    pub pack: bool,
+    pub package_id: Option<String>,
+    pub package_version: Option<String>,
+    pub native_libs: Vec<String>,
+    pub types: BTreeMap<String, TypeConfig>,
+}
+
+/// Settings that override the global settings for a single type.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct TypeConfig {
+    pub thread_checks: Option<bool>,
+    pub enum_classes: Option<bool>,
+}
+
+impl Config {
+    /// Loads the config file specified with `--config`, or `cs-bindgen.toml` in the
+    /// current directory if it exists.
+    pub fn load(opt: &Opt) -> Result<Config, Error> {
+        let path = match &opt.config {
+            Some(path) => path.clone(),
+            None => {
+                let path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if !path.exists() {
+                    return Ok(Config::default());
+                }
+                path
+            }
+        };
+
+        let text = fs::read_to_string(&path)
+            .map_err(|err| failure::format_err!("Failed to read config {:?}: {}", path, err))?;
+        toml::from_str(&text)
+            .map_err(|err| failure::format_err!("Invalid config {:?}: {}", path, err))
+    }
+
+    /// Fills in any options that weren't specified on the command line.
+    pub fn apply(self, opt: &mut Opt) -> Result<(), Error> {
+        fn merge<T>(option: &mut Option<T>, config: Option<T>) {
+            if option.is_none() {
+                *option = config;
+            }
+        }
+
+        // NOTE: `--output` and `--out-dir` conflict, so the config file only provides
+        // an output location if neither was specified on the command line.
+        if opt.output.is_none() && opt.out_dir.is_none() {
+            opt.output = self.output;
+            opt.out_dir = self.out_dir;
+        }
+
+        if opt.target.is_none() {
+            opt.target = self.target.as_deref().map(str::parse).transpose()?;
+        }
+
+        merge(&mut opt.target_framework, self.target_framework);
+        merge(&mut opt.class_name, self.class_name);
+        merge(&mut opt.dll_name, self.dll_name);
+        merge(&mut opt.root_namespace, self.root_namespace);
+        merge(&mut opt.package_id, self.package_id);
+        merge(&mut opt.package_version, self.package_version);
+
+        opt.thread_checks |= self.thread_checks;
+        opt.trace |= self.trace;
+        opt.namespaces |= self.namespaces;
+        opt.nullable |= self.nullable;
+        opt.enum_classes |= self.enum_classes;
+        opt.unity |= self.unity;
+        opt.source_generator |= self.source_generator;
+        opt.blazor |= self.blazor;
+        opt.pack |= self.pack;
+
+        if opt.native_lib.is_empty() {
+            opt.native_lib = self
+                .native_libs
+                .iter()
+                .map(|lib| lib.parse::<NativeLib>())
+                .collect::<Result<_, _>>()?;
+        }
+
+        opt.type_configs = self.types;
+
+        if (opt.pack || opt.unity || opt.source_generator) && opt.out_dir.is_none() {
+            return Err(failure::err_msg(
+                "An output directory must be specified with `--out-dir` when generating a \
+                 package",
+            ));
+        }
+
+        if opt.blazor && opt.out_dir.is_none() {
+            return Err(failure::err_msg(
+                "An output directory must be specified with `--out-dir` for `--blazor`, \
+                 since the JavaScript module is written alongside the bindings",
+            ));
+        }
+
+        if opt.blazor && (opt.unity || opt.source_generator) {
+            return Err(failure::err_msg(
+                "`--blazor` can't be used with `--unity` or `--source-generator`",
+            ));
+        }
+
+        if opt.bench.is_some() {
+            if opt.output.is_none() && opt.out_dir.is_none() {
+                return Err(failure::err_msg(
+                    "`--bench` requires the bindings to be written with `--output` or \
+                     `--out-dir`, since the benchmark project compiles them",
+                ));
+            }
+
+            if opt.source_generator || opt.blazor {
+                return Err(failure::err_msg(
+                    "`--bench` can't be used with `--source-generator` or `--blazor`",
+                ));
+            }
+        }
+
+        if opt.pack && opt.unity {
+            return Err(failure::err_msg(
+                "`--pack` and `--unity` can't be used together",
+            ));
+        }
+
+        if opt.source_generator && (opt.pack || opt.unity) {
+            return Err(failure::err_msg(
+                "`--source-generator` can't be used with `--pack` or `--unity`",
+            ));
+        }
+
+        if opt.target() != Target::CSharp {
+            if opt.pack
+                || opt.unity
+                || opt.source_generator
+                || opt.blazor
+                || opt.tests.is_some()
+                || opt.bench.is_some()
+            {
+                return Err(failure::err_msg(
+                    "`--pack`, `--unity`, `--source-generator`, `--blazor`, `--tests`, and \
+                     `--bench` are only supported for the C# target",
+                ));
+            }
+
+            let multiple_files = matches!(opt.target(), Target::TypeScript | Target::Python);
+            if multiple_files && opt.out_dir.is_none() {
+                return Err(failure::err_msg(
+                    "An output directory must be specified with `--out-dir` for the \
+                     TypeScript and Python targets",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the settings for the specified type, if any were specified in the config
+/// file.
+pub fn type_config<'a>(
+    configs: &'a BTreeMap<String, TypeConfig>,
+    type_name: &TypeName,
+) -> Option<&'a TypeConfig> {
+    configs.get(&format!("{}::{}", type_name.module, type_name.name))
+}
@@ -1,43 +1,238 @@
+pub use self::{bench::generate_bench, test_suite::generate_tests};
+
 use self::{binding::*, class::*, enumeration::*, func::*};
-use crate::Opt;
+use crate::{
+    config::{self, TypeConfig},
+    load_decl::MetadataHash,
+    Opt,
+};
 use cs_bindgen_shared::{
-    schematic::{self, Primitive, Schema, TypeName},
-    BindingStyle, Export, NamedType, Repr,
+    boxed_type_name,
+    schematic::{self, Enum, Primitive, Schema, TypeName, Variant},
+    BindingStyle, Export, Method, NamedType, Repr,
 };
 use heck::*;
 use lazy_static::lazy_static;
 use proc_macro2::TokenStream;
 use quote::*;
-use std::{collections::HashMap, ffi::OsStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsStr,
+    ops::Deref,
+    path::Path,
+    slice,
+};
 use syn::Ident;
 
+mod alias;
+mod array;
+mod batch;
+mod bench;
 mod binding;
+mod blazor;
+mod borrow;
+mod boxed;
+mod callback;
 mod class;
+mod comparison;
+mod constant;
+mod conversion;
+mod custom;
+mod docs;
 mod enumeration;
+mod equality;
+mod event;
+mod flags;
+mod format;
 mod func;
+mod handshake;
+mod interface;
+mod iter;
+mod list;
+mod map;
+mod marshal;
+mod operator;
+mod option;
+mod overload;
+mod result;
 mod strukt;
+mod task;
+mod test_suite;
+mod trace;
+mod transparent;
+mod tuple;
+
+/// The definitions for all exported types, keyed by type name.
+///
+/// Also determines which C# namespace each type is declared in, since references to
+/// exported types need to use the fully-qualified name of the type.
+pub struct TypeMap<'a> {
+    types: HashMap<&'a TypeName, &'a NamedType>,
+
+    /// Whether types are placed in namespaces based on their module path, i.e. whether
+    /// `--namespaces` was specified.
+    namespaces: bool,
+
+    /// Whether optional reference types are annotated as nullable, i.e. whether
+    /// `--nullable` was specified.
+    nullable: bool,
+
+    /// Whether data-carrying enums are generated as a class hierarchy, i.e. whether
+    /// `--enum-classes` was specified.
+    enum_classes: bool,
+
+    /// Whether the bindings are generated for Unity, i.e. whether `--unity` was
+    /// specified.
+    unity: bool,
+
+    /// Whether the wrapper functions include tracing code, i.e. whether `--trace` was
+    /// specified.
+    trace: bool,
+
+    /// Whether dates are exposed as a `DateOnly`, which is only available in .NET 6 and
+    /// later. Otherwise dates are exposed as a `DateTime` at midnight.
+    date_only: bool,
+
+    /// The segments of the namespace specified with `--root-namespace`, which all
+    /// other namespaces are nested in.
+    root_namespace: Vec<Ident>,
+
+    /// The settings for individual types from the config file.
+    type_configs: &'a BTreeMap<String, TypeConfig>,
+}
+
+impl<'a> Deref for TypeMap<'a> {
+    type Target = HashMap<&'a TypeName, &'a NamedType>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.types
+    }
+}
+
+impl TypeMap<'_> {
+    /// Returns the segments of the C# namespace that the type is declared in.
+    ///
+    /// The namespace is based on the module path of the type, omitting the name of the
+    /// crate. Types declared in the crate root, or all types if namespaces are
+    /// disabled, are declared in the root namespace, which is the global namespace
+    /// unless `--root-namespace` was specified.
+    fn namespace(&self, type_name: &TypeName) -> Vec<Ident> {
+        let mut namespace = self.root_namespace.clone();
+        if self.namespaces {
+            namespace.extend(
+                type_name
+                    .module
+                    .split("::")
+                    .skip(1)
+                    .map(|segment| format_ident!("{}", segment.to_camel_case())),
+            );
+        }
+
+        namespace
+    }
+
+    /// Returns `true` if the specified data-carrying enum is generated as a class
+    /// hierarchy, taking the settings for the type in the config file into account.
+    pub fn uses_enum_classes(&self, type_name: &TypeName) -> bool {
+        config::type_config(self.type_configs, type_name)
+            .and_then(|config| config.enum_classes)
+            .unwrap_or(self.enum_classes)
+    }
+
+    /// Quotes a fully-qualified reference to `ident`, which is declared in the same
+    /// namespace as the specified type.
+    pub fn qualify(&self, type_name: &TypeName, ident: impl ToTokens) -> TokenStream {
+        let namespace = self.namespace(type_name);
+        quote! {
+            global::#( #namespace. )* #ident
+        }
+    }
+
+    /// Returns the name of the file that the declarations for the specified type are
+    /// written to with `--out-dir`.
+    ///
+    /// The file is named after the C# identifier of the type, prefixed with the type's
+    /// namespace so that types with the same name in different namespaces don't collide.
+    fn file_name(&self, type_name: &TypeName, ident: &Ident) -> String {
+        let mut segments = self
+            .namespace(type_name)
+            .iter()
+            .map(Ident::to_string)
+            .collect::<Vec<_>>();
+        segments.push(ident.to_string());
+        format!("{}.cs", segments.join("."))
+    }
+
+    /// Wraps the declarations for the specified type in the type's namespace.
+    pub fn wrap_namespace(&self, type_name: &TypeName, tokens: TokenStream) -> TokenStream {
+        let namespace = self.namespace(type_name);
+        if namespace.is_empty() {
+            return tokens;
+        }
 
-type TypeMap<'a> = HashMap<&'a TypeName, &'a NamedType>;
+        quote! {
+            namespace #( #namespace ).*
+            {
+                #tokens
+            }
+        }
+    }
+}
+
+/// The name of the file containing the bindings shared by all exported types.
+///
+/// Without `--out-dir`, this is the only generated file.
+const SHARED_FILE_NAME: &str = "Bindings.cs";
+
+/// A generated C# source file.
+pub struct GeneratedFile {
+    /// The name of the file, relative to the output directory.
+    pub name: String,
+    pub contents: String,
+}
 
 lazy_static! {
     static ref STRING_SCHEMA: Schema = schematic::describe::<String>();
 }
 
-pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, failure::Error> {
-    // TODO: Add a validation pass to detect any invalid types (e.g. 128 bit integers,
-    // `()` as an argument). This would remove the need to have graceful error handling
-    // around those cases.
+/// Returns the name of the native library that the bindings load.
+pub fn dll_name(opt: &Opt) -> &str {
+    opt.dll_name.as_deref().unwrap_or_else(|| input_name(opt))
+}
+
+/// Returns the identifier of the C# class containing the exported functions.
+fn class_ident(opt: &Opt) -> Ident {
+    match &opt.class_name {
+        Some(class_name) => format_ident!("{}", class_name),
+        None => format_ident!("{}", input_name(opt).to_camel_case()),
+    }
+}
 
-    let dll_name = opt
-        .input
+/// Returns the name of the native library that each input is loaded as, in the same
+/// order as the inputs.
+///
+/// `--dll-name` only applies to the first input, and the other libraries are loaded by
+/// the names of their input files.
+fn dll_names(opt: &Opt) -> Vec<&str> {
+    std::iter::once(dll_name(opt))
+        .chain(opt.inputs()[1..].iter().map(|input| file_name(input)))
+        .collect()
+}
+
+fn input_name(opt: &Opt) -> &str {
+    file_name(opt.input())
+}
+
+fn file_name(input: &Path) -> &str {
+    input
         .file_stem()
         .and_then(OsStr::to_str)
-        .expect("Unable to get name of wasm file");
-
-    let class_name = format_ident!("{}", dll_name.to_camel_case());
+        .expect("Unable to get name of input file")
+}
 
-    // Gather the definitions for all user-defined types so that the full export
-    // information can be retrieved when an export represents another exported type.
+/// Gathers the definitions for all user-defined types so that the full export
+/// information can be retrieved when an export represents another exported type.
+fn type_map<'a>(exports: &'a [Export], opt: &'a Opt) -> TypeMap<'a> {
     let types = exports
         .iter()
         .filter_map(|export| match export {
@@ -45,43 +240,234 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
             _ => None,
         })
         .collect::<HashMap<_, _>>();
+    TypeMap {
+        types,
+        namespaces: opt.namespaces,
+        nullable: opt.nullable,
+        enum_classes: opt.enum_classes,
+        unity: opt.unity,
+        trace: opt.trace,
+        date_only: !opt.unity
+            && dotnet_major_version(opt.target_framework())
+                .map(|major| major >= 6)
+                .unwrap_or(false),
+        root_namespace: opt
+            .root_namespace
+            .iter()
+            .flat_map(|namespace| namespace.split('.'))
+            .map(|segment| format_ident!("{}", segment))
+            .collect(),
+        type_configs: &opt.type_configs,
+    }
+}
+
+/// Generates the C# bindings for the exports.
+///
+/// `sources` contains the index of the input that each export was loaded from, which
+/// determines the native library that its raw bindings load. `metadata_hash` is the
+/// hash of the declarations exported by the first input, which the bindings check
+/// against the loaded library.
+pub fn generate_bindings(
+    mut exports: Vec<Export>,
+    sources: &[usize],
+    metadata_hash: &MetadataHash,
+    opt: &Opt,
+) -> Result<Vec<GeneratedFile>, failure::Error> {
+    // TODO: Add a validation pass to detect any invalid types (e.g. 128 bit integers,
+    // `()` as an argument). This would remove the need to have graceful error handling
+    // around those cases.
+
+    let dll_names = dll_names(opt);
+    let dll_name = dll_names[0];
+    let class_name = class_ident(opt);
+
+    // Resolve any members with colliding C# names first, since this can rename exports
+    // and turn constructors into static factory methods.
+    overload::resolve_names(&mut exports, &class_name.to_string(), opt)?;
+    let types = type_map(&exports, opt);
+
+    // Without namespaces, all types are declared in the global namespace, so types with
+    // the same name in different modules would collide. With namespaces, the crate name
+    // isn't part of the namespace, so types from different crates can still collide.
+    // The exports are checked in order so that the same collision is always reported.
+    let mut names = HashMap::new();
+    let type_names = exports.iter().filter_map(|export| match export {
+        Export::Named(export) => Some(&export.type_name),
+        _ => None,
+    });
+    for type_name in type_names {
+        let cs_name = (types.namespace(type_name), &*type_name.name);
+        if let Some(other) = names.insert(cs_name, type_name) {
+            let hint = if types.namespaces {
+                "rename one of them"
+            } else {
+                "use `--namespaces` to place them in separate namespaces or rename one of them"
+            };
+            return Err(failure::format_err!(
+                "Exported types `{}::{}` and `{}::{}` have the same name, {}",
+                other.module,
+                other.name,
+                type_name.module,
+                type_name.name,
+                hint,
+            ));
+        }
+    }
+
+    // Types used by the exports of one crate may be declared in another crate, so make
+    // sure that every referenced type was loaded from one of the inputs.
+    for export in &exports {
+        for repr in collect_reprs(slice::from_ref(export)) {
+            if let Repr::Named(type_name) = &repr {
+                if types.get(type_name).is_none() {
+                    return Err(failure::format_err!(
+                        "`{}::{}` is used by an exported item but isn't exported, export it with \
+                         `#[cs_bindgen]` or pass the library that exports it as another input",
+                        type_name.module,
+                        type_name.name,
+                    ));
+                }
+            }
+        }
+    }
+
+    // Gather the definitions for all exported traits so that trait impls can generate
+    // the implementations of the trait's methods.
+    let traits = exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::Trait(export) => Some((&export.type_name, export)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
 
     // Generate the raw bindings for all exported items.
     let raw_bindings = exports
         .iter()
-        .map(|item| quote_raw_binding(item, dll_name, &types))
+        .zip(sources)
+        .map(|(item, &source)| quote_raw_binding(item, dll_names[source], &types))
         .collect::<Vec<_>>();
 
+    // NOTE: Unity's scripting runtime only supports .NET Standard 2.0, regardless of the
+    // target framework specified.
+    let target_framework = if opt.unity {
+        "netstandard2.0"
+    } else {
+        opt.target_framework()
+    };
+    let global_aliases = alias::supports_global_using(target_framework);
+
     let mut fn_bindings = Vec::new();
     let mut binding_items = Vec::new();
+    let mut aliases = Vec::new();
+
+    // The declarations for each exported type and trait, along with the name of the type
+    // that they belong to. These are kept separate from the rest of the bindings so that
+    // each type can be written to its own file with `--out-dir`.
+    let mut type_items = Vec::<(&TypeName, TokenStream)>::new();
     for export in &exports {
         match export {
-            Export::Fn(export) => fn_bindings.push(quote_wrapper_fn(
-                &*export.name,
-                &*export.binding,
-                None,
-                &export.inputs,
-                export.output.as_ref(),
-                &types,
-            )),
+            Export::Fn(export) => {
+                let docs = docs::quote_doc_comment(export.docs.as_deref());
+                let wrapper = if export.is_async {
+                    task::quote_async_wrapper_fn(export, &types)
+                } else {
+                    quote_wrapper_fn(
+                        fn_ident(&export.name, export.rename.as_deref()),
+                        &*export.binding,
+                        None,
+                        &export.inputs,
+                        export.output.as_ref(),
+                        &export.out_params,
+                        &types,
+                    )
+                };
+
+                // Batched functions also get an overload for recording calls in a
+                // command buffer.
+                let record_fn = export.command.as_ref().map(|command| {
+                    batch::quote_record_fn(
+                        fn_ident(&export.name, export.rename.as_deref()),
+                        command,
+                        None,
+                        &export.inputs,
+                        &types,
+                    )
+                });
+
+                fn_bindings.push(quote! {
+                    #docs
+                    #wrapper
+                    #record_fn
+                });
+            }
 
             Export::Named(export) => match &export.binding_style {
-                BindingStyle::Handle => binding_items.push(class::quote_handle_type(export)),
+                BindingStyle::Handle => {
+                    let type_name = &export.type_name;
+                    let thread_checks = config::type_config(&opt.type_configs, type_name)
+                        .and_then(|config| config.thread_checks)
+                        .unwrap_or(opt.thread_checks);
+                    type_items.push((
+                        type_name,
+                        class::quote_handle_type(export, thread_checks, &types),
+                    ));
+                    type_items.extend(
+                        class::quote_default_constructor(export, &exports, &types)
+                            .into_iter()
+                            .chain(class::quote_to_string(export, &types))
+                            .chain(class::quote_equality(export, &types))
+                            .chain(class::quote_comparison(export, &types))
+                            .chain(class::quote_clone(export, &types))
+                            .map(|item| (type_name, item)),
+                    );
+                }
+
+                BindingStyle::Marshal(format, schema) => type_items.push((
+                    &export.type_name,
+                    marshal::quote_marshaled_type(export, *format, schema, &types),
+                )),
+
+                BindingStyle::Custom(custom) => type_items.push((
+                    &export.type_name,
+                    custom::quote_custom_type(export, custom, &types),
+                )),
+
+                // Transparent newtypes are exposed as their inner type unless they have a
+                // wrapper struct, so there's nothing else to generate for them.
+                BindingStyle::Transparent(transparent) => {
+                    if transparent.wrapper {
+                        type_items.push((
+                            &export.type_name,
+                            transparent::quote_wrapper_struct(export, transparent, &types),
+                        ));
+                    }
+                }
+
+                BindingStyle::Value(_) if export.is_flags => type_items.push((
+                    &export.type_name,
+                    flags::quote_flags(export, &exports, &types)?,
+                )),
 
                 BindingStyle::Value(schema) => match schema {
                     Schema::Struct(_)
                     | Schema::TupleStruct(_)
                     | Schema::UnitStruct(_)
-                    | Schema::NewtypeStruct(_) => binding_items.push(strukt::quote_struct(
-                        export,
-                        // NOTE: The unwrap here will not panic because all of the matched variants have
-                        // a struct-like representation. If it panics here, then it likely indicates a
-                        // bug in the schematic crate.
-                        schema.as_struct_like().unwrap(),
-                        &types,
+                    | Schema::NewtypeStruct(_) => type_items.push((
+                        &export.type_name,
+                        strukt::quote_struct(
+                            export,
+                            // NOTE: The unwrap here will not panic because all of the matched variants have
+                            // a struct-like representation. If it panics here, then it likely indicates a
+                            // bug in the schematic crate.
+                            schema.as_struct_like().unwrap(),
+                            &types,
+                        ),
                     )),
 
-                    Schema::Enum(schema) => binding_items.push(quote_enum(export, schema, &types)),
+                    Schema::Enum(schema) => {
+                        type_items.push((&export.type_name, quote_enum(export, schema, &types)))
+                    }
 
                     _ => {
                         return Err(failure::format_err!(
@@ -93,15 +479,138 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
                 },
             },
 
-            Export::Method(export) => binding_items.push(quote_method_binding(export, &types)),
+            // Property accessors are grouped into properties once all exports have been
+            // processed.
+            Export::Method(export) if export.accessor.is_some() => {}
+
+            Export::Method(export) => {
+                type_items.push((&export.self_type, quote_method_binding(export, &types)))
+            }
+
+            Export::Trait(export) => type_items.push((
+                &export.type_name,
+                interface::quote_interface(export, &types),
+            )),
+            Export::TraitImpl(export) => type_items.push((
+                &export.self_type,
+                interface::quote_trait_impl(export, &traits, &types),
+            )),
+
+            // Std trait impls are merged into their types when loading the exports.
+            Export::StdImpl(_) => {}
+
+            Export::Conversion(export) => {
+                type_items.push(conversion::quote_conversion(export, &types)?)
+            }
+            Export::Operator(export) => type_items.push(operator::quote_operator(export, &types)?),
+
+            // The list conversions are generated for each element type below.
+            Export::List(_) => {}
+
+            Export::Const(export) => match &export.self_type {
+                Some(self_type) => {
+                    type_items.push((self_type, constant::quote_assoc_const(export, &types)?))
+                }
+                None => fn_bindings.push(constant::quote_const(export, &types)?),
+            },
+
+            Export::Alias(export) => {
+                aliases.push(alias::quote_alias(export, global_aliases, &types)?)
+            }
+        }
+    }
+
+    type_items.extend(class::quote_properties(&exports, &types));
+
+    // Verify that the library matches the bindings and check the layout of blittable
+    // structs the first time any of the bindings are used, since a mismatch would
+    // otherwise silently corrupt values passed to Rust.
+    let handshake = handshake::quote_check(metadata_hash, dll_name);
+    let layout_checks = exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::Named(export) if strukt::is_blittable(export, &types) => {
+                Some(strukt::quote_layout_check(export, &types))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    binding_items.push(handshake::quote_raw_bindings(metadata_hash, dll_name));
+    binding_items.push(binding::wrap_bindings(quote! {
+        static __bindings()
+        {
+            #handshake
+            #( #layout_checks )*
+        }
+    }));
+
+    // Generate the raw types and conversions for any built-in generic types (e.g.
+    // `Option<T>` and `Result<T, E>`) used in the exported API. Since P/Invoke doesn't support generic
+    // structs, we need to generate a dedicated raw struct for each concrete type. Map types share
+    // a single raw struct, but still need dedicated conversion functions.
+    let generic_items = collect_reprs(&exports)
+        .iter()
+        .filter_map(|repr| match repr {
+            Repr::Option(inner) => Some(option::quote_raw_option(inner, &types)),
+            Repr::Box(inner) => Some(boxed::quote_raw_box(inner, &types)),
+            Repr::Map { key, value } => Some(map::quote_map_conversions(key, value, &types)),
+            Repr::Tuple(elements) => Some(tuple::quote_raw_tuple(elements, &types)),
+            Repr::Array { element, len } => Some(array::quote_raw_array(element, *len, &types)),
+            Repr::Result { ok, err } => Some(result::quote_raw_result(ok, err, &types)),
+            Repr::Iter(item) => Some(iter::quote_iter_conversion(item, &types)),
+            Repr::Events(event) => Some(event::quote_events_conversion(event, &types)),
+            Repr::Fn { inputs, output } => {
+                Some(callback::quote_raw_callback(inputs, output, &types))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Generate the completion functions for async functions, one for each output type.
+    let mut completion_reprs = Vec::new();
+    for export in &exports {
+        if let Export::Fn(export) = export {
+            let repr = task::completion_repr(export);
+            if export.is_async && !completion_reprs.contains(&repr) {
+                completion_reprs.push(repr);
+            }
+        }
+    }
+    let completions = completion_reprs
+        .iter()
+        .map(|repr| task::quote_raw_completion(repr, &types));
+
+    // Generate the conversions for borrowed slices returned from methods, one for each
+    // element type. Slices used as arguments are converted by the wrapper function
+    // instead, so they're not included.
+    let mut borrowed_elements = Vec::new();
+    for export in &exports {
+        if let Export::Method(Method {
+            output: Some(Repr::Slice(element)),
+            ..
+        }) = export
+        {
+            if !borrowed_elements.contains(&**element) {
+                borrowed_elements.push((**element).clone());
+            }
         }
     }
+    let borrowed_slices = borrowed_elements
+        .iter()
+        .map(|element| borrow::quote_slice_conversion(element, &types));
+
+    // Generate the conversions for lists of any element types that the exports declared
+    // list functions for.
+    let list_conversions = list::quote_list_conversions(&exports, sources, &dll_names, &types);
 
     // Wrap the raw bindings for exported functions/methods in the bindings class definition.
     let raw_bindings = binding::wrap_bindings(quote! {
         #( #raw_bindings )*
     });
 
+    let string_from_raw = quote_string_from_raw(target_framework);
+    let date_conversions = quote_date_conversions(&types);
+
     let built_in_bindings = binding::wrap_bindings(quote! {
         // Bindings to built-in helper functions.
         [DllImport(
@@ -250,6 +759,114 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
             CallingConvention = CallingConvention.Cdecl)]
         internal static extern RawVec __cs_bindgen_string_from_utf16(RawSlice raw);
 
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern UIntPtr __cs_bindgen_string_utf16_len(RawSlice raw);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_string_write_utf16(RawSlice raw, IntPtr dest, UIntPtr len);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr __cs_bindgen_alloc_buffer(UIntPtr size);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_free_buffer(IntPtr ptr, UIntPtr size);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr __cs_bindgen_alloc(UIntPtr size, UIntPtr align);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_free(IntPtr ptr, UIntPtr size, UIntPtr align);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern IntPtr __cs_bindgen_cancel_flag_new();
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_cancel_flag_cancel(IntPtr flag);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_cancel_flag_drop(IntPtr flag);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern byte __cs_bindgen_iter_next(RawIter raw, IntPtr item, ref RawVec panic);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_iter_drop(RawIter raw);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern UIntPtr __cs_bindgen_submit_commands(RawSlice buffer, ref RawVec panic);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern byte __cs_bindgen_set_log_handler(IntPtr handler, byte maxLevel);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void __cs_bindgen_set_error_hook(IntPtr hook);
+
+        [DllImport(
+            #dll_name,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern RawVec __cs_bindgen_live_handles();
+
+        // Throws an exception if the raw binding function reported a panic.
+        //
+        // If the panic was caused by an exception thrown by a C# object called from Rust,
+        // the original exception is rethrown instead.
+        internal static void __CheckPanic(RawVec panic)
+        {
+            if (panic.Ptr != IntPtr.Zero)
+            {
+                __FromRaw(panic, out string message);
+
+                var pending = __pendingException;
+                if (pending != null)
+                {
+                    __pendingException = null;
+                    ExceptionDispatchInfo.Capture(pending).Throw();
+                }
+
+                throw new RustPanicException(message);
+            }
+        }
+
+        // The exception thrown by the most recent call from Rust into a C# object, which
+        // Rust reports as a panic.
+        [ThreadStatic]
+        internal static Exception __pendingException;
+
+        // Releases a C# object passed to Rust as a `RawManaged`.
+        [MonoPInvokeCallback(typeof(__RawReleaseManaged))]
+        internal static void __ReleaseManaged(IntPtr context)
+        {
+            GCHandle.FromIntPtr(context).Free();
+        }
+
         // Overloads of `__FromRaw` for primitives and built-in types.
         internal static void __FromRaw(byte raw, out byte result) { result = raw; }
         internal static void __FromRaw(sbyte raw, out sbyte result) { result = raw; }
@@ -261,18 +878,42 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
         internal static void __FromRaw(ulong raw, out ulong result) { result = raw; }
         internal static void __FromRaw(float raw, out float result) { result = raw; }
         internal static void __FromRaw(double raw, out double result) { result = raw; }
+        internal static void __FromRaw(Guid raw, out Guid result) { result = raw; }
 
         internal static void __FromRaw(byte raw, out bool result)
         {
             result = raw != 0;
         }
 
-        internal static void __FromRaw(RawVec raw, out string result)
+        // Time types are passed as a number of ticks, with points in time measured from
+        // the Unix epoch.
+        internal static readonly DateTimeOffset __UnixEpoch =
+            new DateTimeOffset(1970, 1, 1, 0, 0, 0, TimeSpan.Zero);
+
+        internal static void __FromRaw(long raw, out TimeSpan result)
         {
-            result = Encoding.UTF8.GetString((byte*)raw.Ptr, (int)raw.Length);
-            __bindings.__cs_bindgen_drop_vec_u8(raw);
+            result = new TimeSpan(raw);
+        }
+
+        internal static void __FromRaw(long raw, out DateTimeOffset result)
+        {
+            result = __UnixEpoch.AddTicks(raw);
+        }
+
+        // 128-bit integers are passed as two 64-bit halves. The high half of a signed
+        // integer is reinterpreted as a `long` so that the sign is preserved.
+        internal static void __FromRaw(RawI128 raw, out System.Numerics.BigInteger result)
+        {
+            result = ((System.Numerics.BigInteger)unchecked((long)raw.High) << 64) | raw.Low;
         }
 
+        internal static void __FromRaw(RawU128 raw, out System.Numerics.BigInteger result)
+        {
+            result = ((System.Numerics.BigInteger)raw.High << 64) | raw.Low;
+        }
+
+        #string_from_raw
+
         internal static void __FromRaw(RawVec raw, out List<byte> result)
         {
             result = raw.ToPrimitiveList<byte>();
@@ -339,6 +980,21 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
             __bindings.__cs_bindgen_drop_vec_u8(raw);
         }
 
+        // Byte buffers are copied directly into a `byte[]`, rather than being converted
+        // one element at a time.
+        internal static void __FromRaw(RawVec raw, out byte[] result)
+        {
+            result = new byte[(int)raw.Length];
+            Marshal.Copy(raw.Ptr, result, 0, result.Length);
+            __bindings.__cs_bindgen_drop_vec_u8(raw);
+        }
+
+        internal static void __FromRaw(RawSlice raw, out byte[] result)
+        {
+            result = new byte[(int)raw.Length];
+            Marshal.Copy(raw.Ptr, result, 0, result.Length);
+        }
+
         // Overloads of `__IntoRaw` for primitives and built-in types.
         internal static void __IntoRaw(byte value, out byte result) { result = value; }
         internal static void __IntoRaw(sbyte value, out sbyte result) { result = value; }
@@ -350,38 +1006,89 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
         internal static void __IntoRaw(ulong value, out ulong result) { result = value; }
         internal static void __IntoRaw(float value, out float result) { result = value; }
         internal static void __IntoRaw(double value, out double result) { result = value; }
+        internal static void __IntoRaw(Guid value, out Guid result) { result = value; }
 
         internal static void __IntoRaw(bool value, out byte result)
         {
             result = value ? (byte)1 : (byte)0;
         }
 
-        internal static void __IntoRaw(string value, out RawVec result)
+        internal static void __IntoRaw(TimeSpan value, out long result)
         {
-            fixed (char* charPtr = value)
-            {
-                result = __cs_bindgen_string_from_utf16(new RawSlice((IntPtr)charPtr, value.Length));
-            }
+            result = value.Ticks;
         }
 
-        internal static void __IntoRaw(List<byte> value, out RawVec result)
+        internal static void __IntoRaw(DateTimeOffset value, out long result)
         {
-            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_u8);
+            result = (value - __UnixEpoch).Ticks;
         }
 
-        internal static void __IntoRaw(List<sbyte> value, out RawVec result)
-        {
-            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_i8);
-        }
+        #date_conversions
 
-        internal static void __IntoRaw(List<short> value, out RawVec result)
+        // Values that don't fit in the Rust type throw an `OverflowException`, the same
+        // as an explicit conversion to one of the built-in integer types.
+        internal static void __IntoRaw(System.Numerics.BigInteger value, out RawI128 result)
         {
-            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_i16);
+            if (value < RawI128.MinValue || value > RawI128.MaxValue)
+            {
+                throw new OverflowException("Value was either too large or too small for an i128");
+            }
+
+            result = new RawI128
+            {
+                Low = (ulong)(value & ulong.MaxValue),
+                High = unchecked((ulong)(long)(value >> 64)),
+            };
         }
 
-        internal static void __IntoRaw(List<ushort> value, out RawVec result)
+        internal static void __IntoRaw(System.Numerics.BigInteger value, out RawU128 result)
         {
-            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_u16);
+            if (value.Sign < 0 || value > RawU128.MaxValue)
+            {
+                throw new OverflowException("Value was either too large or too small for a u128");
+            }
+
+            result = new RawU128
+            {
+                Low = (ulong)(value & ulong.MaxValue),
+                High = (ulong)(value >> 64),
+            };
+        }
+
+        internal static void __IntoRaw(byte[] value, out RawVec result)
+        {
+            fixed (byte* ptr = value)
+            {
+                result = __cs_bindgen_convert_vec_u8(new RawSlice((IntPtr)ptr, value.Length));
+            }
+        }
+
+        internal static void __IntoRaw(string value, out RawVec result)
+        {
+            fixed (char* charPtr = value)
+            {
+                result = __cs_bindgen_string_from_utf16(new RawSlice((IntPtr)charPtr, value.Length));
+            }
+        }
+
+        internal static void __IntoRaw(List<byte> value, out RawVec result)
+        {
+            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_u8);
+        }
+
+        internal static void __IntoRaw(List<sbyte> value, out RawVec result)
+        {
+            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_i8);
+        }
+
+        internal static void __IntoRaw(List<short> value, out RawVec result)
+        {
+            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_i16);
+        }
+
+        internal static void __IntoRaw(List<ushort> value, out RawVec result)
+        {
+            result = RawVec.FromPrimitiveList(value, __cs_bindgen_convert_vec_u16);
         }
 
         internal static void __IntoRaw(List<int> value, out RawVec result)
@@ -423,24 +1130,444 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
         }
     });
 
-    let generated = quote! {
-        using System;
-        using System.Collections.Generic;
-        using System.Runtime.InteropServices;
-        using System.Text;
-
-        #built_in_bindings
-        #raw_bindings
+    // With `--out-dir`, the declarations for each type are written to their own file
+    // rather than being included in the shared bindings file.
+    let shared_type_items = if opt.out_dir.is_none() {
+        type_items.iter().map(|(_, item)| item).collect()
+    } else {
+        Vec::new()
+    };
 
+    let fn_class = quote! {
         public class #class_name
         {
             #( #fn_bindings )*
         }
+    };
+    let root_namespace = &types.root_namespace;
+    let fn_class = if root_namespace.is_empty() {
+        fn_class
+    } else {
+        quote! {
+            namespace #( #root_namespace ).*
+            {
+                #fn_class
+            }
+        }
+    };
+
+    // Allow the test assembly to use the internal conversion functions.
+    let internals_visible_to = opt.test_assembly.as_ref().map(|assembly| {
+        quote! {
+            [assembly: System.Runtime.CompilerServices.InternalsVisibleTo(#assembly)]
+        }
+    });
+
+    let trace_class = trace::quote_trace_class(&types);
+    let command_buffer_class = batch::quote_command_buffer_class();
+
+    let module_class = if opt.blazor {
+        blazor::quote_module_class(dll_name)
+    } else {
+        quote! {}
+    };
+
+    // NOTE: The aliases are declared first, since `using` directives have to come before
+    // any other declarations in the file.
+    let shared = quote! {
+        #( #aliases )*
+        #internals_visible_to
+        #module_class
+        #built_in_bindings
+        #raw_bindings
+        #list_conversions
+
+        #fn_class
 
+        #( #shared_type_items )*
         #( #binding_items )*
+        #( #generic_items )*
+        #( #completions )*
+        #( #borrowed_slices )*
+        #trace_class
+        #command_buffer_class
 
         internal delegate void FromRaw<R, T>(R raw, out T result);
 
+        // Exception types used to surface Rust errors in C#.
+        public class RustException : Exception
+        {
+            public RustException(string message) : base(message) { }
+        }
+
+        public class RustPanicException : RustException
+        {
+            public RustPanicException(string message) : base(message) { }
+        }
+
+        public class RustException<E> : RustException
+        {
+            public readonly E Error;
+
+            public RustException(E error) : base(error.ToString())
+            {
+                Error = error;
+            }
+        }
+
+        // Controls the thread that receives the callbacks that Rust makes outside of a
+        // call from C#, i.e. the records passed to `RustLogger` and the completion of
+        // the tasks returned by async functions. By default these are invoked on
+        // whichever thread Rust happens to be running on. Setting `Context` (e.g. to
+        // Unity's main thread context with `CaptureCurrentContext`) posts them to that
+        // context instead.
+        //
+        // Callbacks passed as arguments are always invoked directly, since Rust waits
+        // for their result, and reports to `RustErrorHook` are always invoked directly,
+        // since the process may abort once the hook returns. Event queues are polled
+        // from C#, so their events are raised on the thread that polls them.
+        public static class RustCallbacks
+        {
+            private static volatile SynchronizationContext _context;
+
+            public static SynchronizationContext Context
+            {
+                get { return _context; }
+                set { _context = value; }
+            }
+
+            // Dispatches callbacks to the synchronization context of the current thread,
+            // e.g. when called from Unity's main thread.
+            public static void CaptureCurrentContext()
+            {
+                _context = SynchronizationContext.Current;
+            }
+
+            internal static void Dispatch(Action callback)
+            {
+                var context = _context;
+                if (context == null || context == SynchronizationContext.Current)
+                {
+                    callback();
+                }
+                else
+                {
+                    context.Post(state => ((Action)state)(), callback);
+                }
+            }
+        }
+
+        // Log levels used by the `log` crate, in order of increasing verbosity.
+        public enum RustLogLevel : byte
+        {
+            Off = 0,
+            Error = 1,
+            Warn = 2,
+            Info = 3,
+            Debug = 4,
+            Trace = 5,
+        }
+
+        // Forwards records logged with the `log` crate to a C# delegate. The handler may
+        // be invoked from any thread that logs a record, and any exception thrown by the
+        // handler is discarded.
+        public static class RustLogger
+        {
+            public delegate void Handler(RustLogLevel level, string target, string message);
+
+            [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+            internal delegate void __RawLogHandler(byte level, RawVec target, RawVec message);
+
+            private static volatile Handler _handler;
+            private static readonly __RawLogHandler _log = Log;
+            private static readonly IntPtr _logPtr = Marshal.GetFunctionPointerForDelegate(_log);
+
+            // Registers the handler for log records, replacing any existing handler.
+            // Passing `null` disables logging. Returns `false` if the Rust code has
+            // already installed a different logger.
+            public static bool SetHandler(Handler handler, RustLogLevel maxLevel = RustLogLevel.Info)
+            {
+                _handler = handler;
+
+                var ptr = handler == null ? IntPtr.Zero : _logPtr;
+                return __bindings.__cs_bindgen_set_log_handler(ptr, (byte)maxLevel) != 0;
+            }
+
+            [MonoPInvokeCallback(typeof(__RawLogHandler))]
+            private static void Log(byte level, RawVec target, RawVec message)
+            {
+                __bindings.__FromRaw(target, out string targetString);
+                __bindings.__FromRaw(message, out string messageString);
+
+                var handler = _handler;
+                if (handler == null)
+                {
+                    return;
+                }
+
+                RustCallbacks.Dispatch(() =>
+                {
+                    try
+                    {
+                        handler((RustLogLevel)level, targetString, messageString);
+                    }
+                    catch (Exception)
+                    {
+                        // NOTE: Exceptions can't be propagated back through Rust, and the
+                        // `log` macros have no way to report a failure, so the exception
+                        // is dropped.
+                    }
+                });
+            }
+        }
+
+        // Receives every panic caught at the boundary between C# and Rust, along with its
+        // backtrace, in addition to the `RustPanicException` thrown for the panic. This
+        // includes panics that abort the process, so the handler is the only chance to
+        // report them. The handler may be invoked from any thread, and any exception
+        // thrown by the handler is discarded.
+        public static class RustErrorHook
+        {
+            public delegate void Handler(string message, string backtrace);
+
+            [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+            internal delegate void __RawErrorHook(RawVec message, RawVec backtrace);
+
+            private static volatile Handler _handler;
+            private static readonly __RawErrorHook _hook = Report;
+            private static readonly IntPtr _hookPtr = Marshal.GetFunctionPointerForDelegate(_hook);
+
+            // Registers the handler for panics, replacing any existing handler. Passing
+            // `null` unregisters the current handler.
+            public static void SetHandler(Handler handler)
+            {
+                _handler = handler;
+                __bindings.__cs_bindgen_set_error_hook(handler == null ? IntPtr.Zero : _hookPtr);
+            }
+
+            [MonoPInvokeCallback(typeof(__RawErrorHook))]
+            private static void Report(RawVec message, RawVec backtrace)
+            {
+                __bindings.__FromRaw(message, out string messageString);
+                __bindings.__FromRaw(backtrace, out string backtraceString);
+
+                try
+                {
+                    _handler?.Invoke(messageString, backtraceString);
+                }
+                catch (Exception)
+                {
+                    // NOTE: Exceptions can't be propagated back through Rust, so the
+                    // exception is dropped.
+                }
+            }
+        }
+
+        // Allocates memory that can be passed between C# and Rust in either direction.
+        // Memory returned by `Alloc` can be passed to Rust as the buffer of a `RawVec`
+        // with the same size and alignment, and Rust takes ownership of it. Memory
+        // allocated by Rust can be freed with `Free`, given its size and alignment.
+        public static class RustAllocator
+        {
+            public delegate IntPtr AllocHook(UIntPtr size, UIntPtr align);
+            public delegate void FreeHook(IntPtr ptr, UIntPtr size, UIntPtr align);
+
+            [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+            internal delegate IntPtr __RawAllocHook(UIntPtr size, UIntPtr align);
+
+            [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+            internal delegate void __RawFreeHook(IntPtr ptr, UIntPtr size, UIntPtr align);
+
+            private static readonly object _lock = new object();
+            private static AllocHook _alloc;
+            private static FreeHook _free;
+            private static readonly __RawAllocHook _allocHook = OnAlloc;
+            private static readonly __RawFreeHook _freeHook = OnFree;
+
+            // NOTE: This is declared here rather than with the other bindings so that
+            // registering the hooks doesn't run the static constructor of the bindings,
+            // which allocates.
+            [DllImport(
+                #dll_name,
+                CallingConvention = CallingConvention.Cdecl)]
+            private static extern byte __cs_bindgen_set_allocator(IntPtr allocFn, IntPtr freeFn);
+
+            public static IntPtr Alloc(int size, int align)
+            {
+                var ptr = __bindings.__cs_bindgen_alloc((UIntPtr)size, (UIntPtr)align);
+                if (ptr == IntPtr.Zero)
+                {
+                    throw new ArgumentException("Alignment must be a power of two", nameof(align));
+                }
+
+                return ptr;
+            }
+
+            public static void Free(IntPtr ptr, int size, int align)
+            {
+                __bindings.__cs_bindgen_free(ptr, (UIntPtr)size, (UIntPtr)align);
+            }
+
+            // Registers the functions that Rust uses to allocate memory, if the Rust
+            // library uses `cs_bindgen::alloc::HostAllocator` as its global allocator.
+            // This must be called before any other calls into Rust. Returns `false` if
+            // Rust has already allocated memory or the hooks were already registered, in
+            // which case Rust keeps using its own allocator.
+            public static bool SetHooks(AllocHook alloc, FreeHook free)
+            {
+                if (alloc == null)
+                {
+                    throw new ArgumentNullException(nameof(alloc));
+                }
+
+                if (free == null)
+                {
+                    throw new ArgumentNullException(nameof(free));
+                }
+
+                lock (_lock)
+                {
+                    if (_alloc != null)
+                    {
+                        return false;
+                    }
+
+                    _alloc = alloc;
+                    _free = free;
+                }
+
+                return __cs_bindgen_set_allocator(
+                    Marshal.GetFunctionPointerForDelegate(_allocHook),
+                    Marshal.GetFunctionPointerForDelegate(_freeHook)) != 0;
+            }
+
+            // NOTE: Exceptions can't be propagated back through the allocator, so they
+            // abort the process instead.
+            [MonoPInvokeCallback(typeof(__RawAllocHook))]
+            private static IntPtr OnAlloc(UIntPtr size, UIntPtr align)
+            {
+                try
+                {
+                    return _alloc(size, align);
+                }
+                catch (Exception e)
+                {
+                    Environment.FailFast("Rust allocator hook threw an exception", e);
+                    return IntPtr.Zero;
+                }
+            }
+
+            [MonoPInvokeCallback(typeof(__RawFreeHook))]
+            private static void OnFree(IntPtr ptr, UIntPtr size, UIntPtr align)
+            {
+                try
+                {
+                    _free(ptr, size, align);
+                }
+                catch (Exception e)
+                {
+                    Environment.FailFast("Rust allocator hook threw an exception", e);
+                }
+            }
+        }
+
+        // The number of handles of a single Rust type that have been passed to C#.
+        public struct RustHandleCount
+        {
+            public string TypeName;
+            public long Live;
+            public long Created;
+        }
+
+        // Diagnostics for tracking down leaked handles. Rust only counts handles if the
+        // `cs-bindgen` crate is built with the `diagnostics` feature, but creation stack
+        // traces can be captured regardless, e.g. in debug builds.
+        public static class RustDiagnostics
+        {
+            private sealed class TrackedHandle
+            {
+                public WeakReference<SafeHandle> Handle;
+                public string TypeName;
+                public System.Diagnostics.StackTrace Trace;
+            }
+
+            private static readonly List<TrackedHandle> _tracked = new List<TrackedHandle>();
+            private static int _pruneThreshold = 1024;
+
+            // When set, every handle object created afterwards records the stack trace of
+            // its creation, which is reported by `UndisposedHandles()`.
+            public static bool CaptureStackTraces { get; set; }
+
+            // Returns the number of live handles for each Rust type, as counted by Rust.
+            public static List<RustHandleCount> LiveHandles()
+            {
+                __bindings.__FromRaw(__bindings.__cs_bindgen_live_handles(), out string report);
+
+                var counts = new List<RustHandleCount>();
+                foreach (var line in report.Split(new[] { '\n' }, StringSplitOptions.RemoveEmptyEntries))
+                {
+                    var fields = line.Split('\t');
+                    counts.Add(new RustHandleCount
+                    {
+                        TypeName = fields[0],
+                        Live = long.Parse(fields[1]),
+                        Created = long.Parse(fields[2]),
+                    });
+                }
+
+                return counts;
+            }
+
+            // Returns the type name and creation stack trace of every handle object that
+            // was created while `CaptureStackTraces` was set and hasn't been disposed yet.
+            public static List<KeyValuePair<string, System.Diagnostics.StackTrace>> UndisposedHandles()
+            {
+                var result = new List<KeyValuePair<string, System.Diagnostics.StackTrace>>();
+                lock (_tracked)
+                {
+                    Prune();
+                    foreach (var tracked in _tracked)
+                    {
+                        result.Add(new KeyValuePair<string, System.Diagnostics.StackTrace>(tracked.TypeName, tracked.Trace));
+                    }
+                }
+
+                return result;
+            }
+
+            internal static void __Track(SafeHandle handle, string typeName)
+            {
+                if (!CaptureStackTraces)
+                {
+                    return;
+                }
+
+                // NOTE: The handle is only held weakly so that tracking it doesn't keep it
+                // from being finalized.
+                var tracked = new TrackedHandle
+                {
+                    Handle = new WeakReference<SafeHandle>(handle),
+                    TypeName = typeName,
+                    Trace = new System.Diagnostics.StackTrace(1, true),
+                };
+
+                lock (_tracked)
+                {
+                    _tracked.Add(tracked);
+                    if (_tracked.Count >= _pruneThreshold)
+                    {
+                        Prune();
+                        _pruneThreshold = Math.Max(1024, _tracked.Count * 2);
+                    }
+                }
+            }
+
+            private static void Prune()
+            {
+                _tracked.RemoveAll(tracked => !tracked.Handle.TryGetTarget(out SafeHandle handle) || handle.IsClosed);
+            }
+        }
+
         [StructLayout(LayoutKind.Sequential)]
         internal unsafe struct RawVec
         {
@@ -506,45 +1633,572 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
                 }
             }
 
-            public static RawVec FromList<T, R>(List<T> items, Func<T, R> convertElement, Func<RawSlice, RawVec> handleResult)
-                where R : unmanaged
+            public static RawVec FromList<T, R>(List<T> items, Func<T, R> convertElement, Func<RawSlice, RawVec> handleResult)
+                where R : unmanaged
+            {
+                // If the list is small enough, allocate the temporary list of raw items on the
+                // stack to avoid unnecessary heap allocation. We use 32 as a fairly arbitrary
+                // cutoff, with the hope that it's small enough to be unlikely to overflow the
+                // stack.
+                if (items.Count <= 32)
+                {
+                    R* rawItems = stackalloc R[items.Count];
+
+                    for (int index = 0; index < items.Count; index += 1)
+                    {
+                        rawItems[index] = convertElement(items[index]);
+                    }
+
+                    return handleResult(new RawSlice((IntPtr)rawItems, items.Count));
+                }
+                else
+                {
+                    var rawItems = new R[items.Count];
+                    for (int index = 0; index < items.Count; index += 1)
+                    {
+                        rawItems[index] = convertElement(items[index]);
+                    }
+
+                    fixed (R* ptr = rawItems)
+                    {
+                        return handleResult(new RawSlice((IntPtr)ptr, items.Count));
+                    }
+                }
+            }
+
+            public RawSlice AsSlice()
+            {
+                return new RawSlice(Ptr, Length);
+            }
+        }
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal unsafe struct RawMap
+        {
+            public IntPtr Keys;
+            public IntPtr Values;
+            public UIntPtr Length;
+
+            public Dictionary<K, V> ToDictionary<RK, K, RV, V>(
+                FromRaw<RK, K> keyFromRaw,
+                FromRaw<RV, V> valueFromRaw)
+            where RK: unmanaged
+            where RV: unmanaged
+            {
+                var length = (int)Length;
+                var keys = (RK*)Keys;
+                var values = (RV*)Values;
+                var result = new Dictionary<K, V>(length);
+
+                for (int index = 0; index < length; index += 1)
+                {
+                    keyFromRaw(keys[index], out K key);
+                    valueFromRaw(values[index], out V value);
+                    result.Add(key, value);
+                }
+
+                // The individual keys and values have been converted (and freed if
+                // necessary), but we still need to free the buffers that held them.
+                __bindings.__cs_bindgen_free_buffer(Keys, (UIntPtr)(length * sizeof(RK)));
+                __bindings.__cs_bindgen_free_buffer(Values, (UIntPtr)(length * sizeof(RV)));
+
+                return result;
+            }
+
+            public static RawMap FromDictionary<K, RK, V, RV>(
+                Dictionary<K, V> items,
+                Func<K, RK> convertKey,
+                Func<V, RV> convertValue)
+            where RK: unmanaged
+            where RV: unmanaged
+            {
+                // NOTE: The buffers are allocated by Rust so that ownership of them can be
+                // passed to Rust along with the map. Rust frees the buffers once it has
+                // converted the raw map.
+                var length = items.Count;
+                var keys = (RK*)__bindings.__cs_bindgen_alloc_buffer((UIntPtr)(length * sizeof(RK)));
+                var values = (RV*)__bindings.__cs_bindgen_alloc_buffer((UIntPtr)(length * sizeof(RV)));
+
+                var index = 0;
+                foreach (var entry in items)
+                {
+                    keys[index] = convertKey(entry.Key);
+                    values[index] = convertValue(entry.Value);
+                    index += 1;
+                }
+
+                return new RawMap
+                {
+                    Keys = (IntPtr)keys,
+                    Values = (IntPtr)values,
+                    Length = (UIntPtr)length,
+                };
+            }
+        }
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawI128
+        {
+            public static readonly System.Numerics.BigInteger MinValue = -(System.Numerics.BigInteger.One << 127);
+            public static readonly System.Numerics.BigInteger MaxValue = (System.Numerics.BigInteger.One << 127) - 1;
+
+            public ulong Low;
+            public ulong High;
+        }
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawU128
+        {
+            public static readonly System.Numerics.BigInteger MaxValue = (System.Numerics.BigInteger.One << 128) - 1;
+
+            public ulong Low;
+            public ulong High;
+        }
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawCallback
+        {
+            public IntPtr Func;
+            public IntPtr Context;
+
+            public RawCallback(IntPtr func, IntPtr context)
+            {
+                Func = func;
+                Context = context;
+            }
+        }
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawCompletion
+        {
+            public IntPtr Func;
+            public IntPtr Context;
+
+            public RawCompletion(IntPtr func, IntPtr context)
+            {
+                Func = func;
+                Context = context;
+            }
+        }
+
+        // Raw representation of a C# object implementing an exported trait. The vtable
+        // holds the release function followed by the trampolines for each method of the
+        // trait, and the context is a `GCHandle` to the object.
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawManaged
+        {
+            public IntPtr Vtable;
+            public IntPtr Context;
+
+            public RawManaged(IntPtr vtable, IntPtr context)
+            {
+                Vtable = vtable;
+                Context = context;
+            }
+        }
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void __RawReleaseManaged(IntPtr context);
+
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct RawIter
+        {
+            public IntPtr Iter;
+            public IntPtr Next;
+            public IntPtr Drop;
+        }
+
+        // Wraps an iterator returned from Rust. Rust iterators can only be traversed
+        // once, so the enumerable can only be enumerated once. The Rust iterator is
+        // dropped once the enumerator is disposed or reaches the end of the iterator.
+        //
+        // NOTE: If the enumerable is never enumerated, the Rust iterator is only dropped
+        // if the enumerable is explicitly disposed.
+        internal sealed class RustEnumerable<R, T> : IEnumerable<T>, IDisposable
+            where R : unmanaged
+        {
+            private RawIter _raw;
+            private readonly FromRaw<R, T> _convert;
+            private bool _taken;
+
+            public RustEnumerable(RawIter raw, FromRaw<R, T> convert)
+            {
+                _raw = raw;
+                _convert = convert;
+            }
+
+            public IEnumerator<T> GetEnumerator()
+            {
+                if (_taken)
+                {
+                    throw new InvalidOperationException("Iterators returned from Rust can only be enumerated once");
+                }
+
+                _taken = true;
+                return new RustEnumerator<R, T>(_raw, _convert);
+            }
+
+            System.Collections.IEnumerator System.Collections.IEnumerable.GetEnumerator()
+            {
+                return GetEnumerator();
+            }
+
+            public void Dispose()
+            {
+                if (!_taken)
+                {
+                    _taken = true;
+                    __bindings.__cs_bindgen_iter_drop(_raw);
+                }
+            }
+        }
+
+        // The base class for the handles owned by the classes of exported handle types.
+        //
+        // Views of values borrowed from an object hold on to the object's handle and
+        // record the handle's generation when they're created. The generation is
+        // incremented each time the object is mutably borrowed by Rust (i.e. by a method
+        // that takes `&mut self`), which may invalidate anything borrowed from it, so a
+        // view throws once the owner has been disposed or mutated.
+        internal abstract class RustHandle : SafeHandle
+        {
+            internal int Generation;
+
+            protected RustHandle(bool ownsHandle) : base(IntPtr.Zero, ownsHandle) { }
+
+            public override bool IsInvalid
+            {
+                get { return handle == IntPtr.Zero; }
+            }
+
+            internal void CheckBorrow(int generation)
+            {
+                if (IsClosed)
+                {
+                    throw new ObjectDisposedException(
+                        GetType().Name,
+                        "The object that owns the borrowed value has been disposed");
+                }
+
+                if (Generation != generation)
+                {
+                    throw new InvalidOperationException(
+                        "The object that owns the borrowed value has been modified since the value was borrowed");
+                }
+            }
+        }
+
+        // A view of a slice borrowed from a Rust value that's owned by a C# object. The
+        // view holds the `RustHandle` of the owner, which keeps the owner from being
+        // finalized while the view is reachable, and throws once the owner is disposed
+        // or mutated.
+        //
+        // NOTE: This class can't be marked `unsafe`, since C# doesn't allow iterators in
+        // an unsafe context.
+        internal sealed class BorrowedSlice<R, T> : IReadOnlyList<T>
+            where R : unmanaged
+        {
+            private readonly RawSlice _raw;
+            private readonly RustHandle _owner;
+            private readonly int _generation;
+            private readonly Func<RawSlice, UIntPtr, R> _index;
+            private readonly FromRaw<R, T> _convert;
+
+            public BorrowedSlice(
+                RawSlice raw,
+                RustHandle owner,
+                Func<RawSlice, UIntPtr, R> index,
+                FromRaw<R, T> convert)
+            {
+                _raw = raw;
+                _owner = owner;
+                _generation = owner.Generation;
+                _index = index;
+                _convert = convert;
+            }
+
+            public int Count
+            {
+                get
+                {
+                    _owner.CheckBorrow(_generation);
+                    return (int)_raw.Length;
+                }
+            }
+
+            public T this[int index]
+            {
+                get
+                {
+                    if (index < 0 || index >= Count)
+                    {
+                        throw new ArgumentOutOfRangeException(nameof(index));
+                    }
+
+                    _convert(_index(_raw, (UIntPtr)index), out T element);
+                    return element;
+                }
+            }
+
+            public IEnumerator<T> GetEnumerator()
+            {
+                for (int index = 0; index < Count; index += 1)
+                {
+                    yield return this[index];
+                }
+            }
+
+            System.Collections.IEnumerator System.Collections.IEnumerable.GetEnumerator()
+            {
+                return GetEnumerator();
+            }
+        }
+
+        internal sealed unsafe class RustEnumerator<R, T> : IEnumerator<T>
+            where R : unmanaged
+        {
+            private RawIter _raw;
+            private readonly FromRaw<R, T> _convert;
+            private bool _done;
+
+            public RustEnumerator(RawIter raw, FromRaw<R, T> convert)
+            {
+                _raw = raw;
+                _convert = convert;
+            }
+
+            public T Current { get; private set; }
+
+            object System.Collections.IEnumerator.Current
+            {
+                get { return Current; }
+            }
+
+            public bool MoveNext()
+            {
+                if (_done)
+                {
+                    return false;
+                }
+
+                R item;
+                RawVec panic = new RawVec();
+                var hasItem = __bindings.__cs_bindgen_iter_next(_raw, (IntPtr)(&item), ref panic);
+                __bindings.__CheckPanic(panic);
+
+                if (hasItem == 0)
+                {
+                    Dispose();
+                    return false;
+                }
+
+                _convert(item, out T value);
+                Current = value;
+                return true;
+            }
+
+            public void Reset()
+            {
+                throw new NotSupportedException("Iterators returned from Rust can't be reset");
+            }
+
+            public void Dispose()
+            {
+                if (!_done)
+                {
+                    _done = true;
+                    __bindings.__cs_bindgen_iter_drop(_raw);
+                }
+            }
+        }
+
+        // A queue of events sent from Rust. Pending events are only converted once they're
+        // pulled from the queue, either by `Poll()`, which raises `Received` for each
+        // event on the calling thread, or by `Drain()` and `TryNext()`.
+        public abstract class EventQueue<T> : IDisposable
+        {
+            internal EventQueue() { }
+
+            public event EventHandler<T> Received;
+
+            public abstract bool TryNext(out T value);
+
+            public abstract void Dispose();
+
+            public int Poll()
+            {
+                var count = 0;
+                while (TryNext(out T value))
+                {
+                    count += 1;
+                    Received?.Invoke(this, value);
+                }
+
+                return count;
+            }
+
+            public List<T> Drain()
+            {
+                var events = new List<T>();
+                while (TryNext(out T value))
+                {
+                    events.Add(value);
+                }
+
+                return events;
+            }
+        }
+
+        // Wraps an event queue returned from Rust. The queue is shared with Rust, so the
+        // Rust side of the queue is only released once the C# object is disposed or
+        // finalized.
+        internal sealed unsafe class RustEventQueue<R, T> : EventQueue<T>
+            where R : unmanaged
+        {
+            private RawIter _raw;
+            private readonly FromRaw<R, T> _convert;
+            private bool _disposed;
+
+            public RustEventQueue(RawIter raw, FromRaw<R, T> convert)
+            {
+                _raw = raw;
+                _convert = convert;
+            }
+
+            ~RustEventQueue()
+            {
+                Release();
+            }
+
+            public override bool TryNext(out T value)
+            {
+                if (_disposed)
+                {
+                    throw new ObjectDisposedException(GetType().Name);
+                }
+
+                R item;
+                RawVec panic = new RawVec();
+                var hasItem = __bindings.__cs_bindgen_iter_next(_raw, (IntPtr)(&item), ref panic);
+                __bindings.__CheckPanic(panic);
+
+                if (hasItem == 0)
+                {
+                    value = default(T);
+                    return false;
+                }
+
+                _convert(item, out value);
+                return true;
+            }
+
+            public override void Dispose()
+            {
+                Release();
+                GC.SuppressFinalize(this);
+            }
+
+            private void Release()
+            {
+                if (!_disposed)
+                {
+                    _disposed = true;
+                    __bindings.__cs_bindgen_iter_drop(_raw);
+                }
+            }
+        }
+
+        // Holds a delegate passed as a callback to Rust, along with any exception thrown
+        // by the delegate. A `GCHandle` to the context is passed to Rust as the context
+        // pointer for the callback, which keeps the delegate alive until the context is
+        // disposed.
+        internal sealed class CallbackContext : IDisposable
+        {
+            public readonly Delegate Callback;
+            public Exception Exception;
+
+            private GCHandle _handle;
+
+            public CallbackContext(Delegate callback)
+            {
+                Callback = callback;
+                _handle = GCHandle.Alloc(this);
+            }
+
+            public IntPtr Handle
+            {
+                get { return GCHandle.ToIntPtr(_handle); }
+            }
+
+            public static CallbackContext FromHandle(IntPtr handle)
+            {
+                return (CallbackContext)GCHandle.FromIntPtr(handle).Target;
+            }
+
+            // Rethrows the exception thrown by the callback, preserving the original
+            // stack trace.
+            public void Rethrow()
+            {
+                ExceptionDispatchInfo.Capture(Exception).Throw();
+            }
+
+            public void Dispose()
+            {
+                _handle.Free();
+            }
+        }
+
+        // Connects a `CancellationToken` passed to an exported function to the flag that
+        // Rust checks, for as long as the call is running. The token's callback may run
+        // on any thread, which is fine since the flag is atomic.
+        internal sealed class RustCancelFlag : IDisposable
+        {
+            private readonly IntPtr _flag;
+            private CancellationTokenRegistration _registration;
+
+            public RustCancelFlag(CancellationToken token)
+            {
+                _flag = __bindings.__cs_bindgen_cancel_flag_new();
+
+                // NOTE: If the token has already been cancelled, `Register` invokes the
+                // callback immediately, so Rust sees the flag as cancelled from the start.
+                _registration = token.Register(Cancel);
+            }
+
+            public IntPtr Ptr
             {
-                // If the list is small enough, allocate the temporary list of raw items on the
-                // stack to avoid unnecessary heap allocation. We use 32 as a fairly arbitrary
-                // cutoff, with the hope that it's small enough to be unlikely to overflow the
-                // stack.
-                if (items.Count <= 32)
-                {
-                    R* rawItems = stackalloc R[items.Count];
-
-                    for (int index = 0; index < items.Count; index += 1)
-                    {
-                        rawItems[index] = convertElement(items[index]);
-                    }
+                get { return _flag; }
+            }
 
-                    return handleResult(new RawSlice((IntPtr)rawItems, items.Count));
-                }
-                else
-                {
-                    var rawItems = new R[items.Count];
-                    for (int index = 0; index < items.Count; index += 1)
-                    {
-                        rawItems[index] = convertElement(items[index]);
-                    }
+            private void Cancel()
+            {
+                __bindings.__cs_bindgen_cancel_flag_cancel(_flag);
+            }
 
-                    fixed (R* ptr = rawItems)
-                    {
-                        return handleResult(new RawSlice((IntPtr)ptr, items.Count));
-                    }
-                }
+            // Releases the flag once an async function's task completes.
+            public void DisposeAfter(Task task)
+            {
+                task.ContinueWith(_ => Dispose(), TaskContinuationOptions.ExecuteSynchronously);
             }
 
-            public RawSlice AsSlice()
+            // NOTE: Disposing the registration waits for the callback to finish if it's
+            // currently running, so the flag is never used after it's released.
+            public void Dispose()
             {
-                return new RawSlice(Ptr, Length);
+                _registration.Dispose();
+                __bindings.__cs_bindgen_cancel_flag_drop(_flag);
             }
         }
 
+        // Marks callback trampolines so that they can be called from native code in AOT
+        // environments. IL2CPP recognizes the attribute by name, so we define our own
+        // rather than depending on Unity's `AOT.MonoPInvokeCallbackAttribute`.
+        [AttributeUsage(AttributeTargets.Method)]
+        internal sealed class MonoPInvokeCallbackAttribute : Attribute
+        {
+            public MonoPInvokeCallbackAttribute(Type type) { }
+        }
+
         [StructLayout(LayoutKind.Sequential)]
         internal unsafe struct RawSlice
         {
@@ -562,10 +2216,209 @@ pub fn generate_bindings(exports: Vec<Export>, opt: &Opt) -> Result<String, fail
                 Ptr = ptr;
                 Length = (UIntPtr)len;
             }
+
+            public R Get<R>(UIntPtr index) where R : unmanaged
+            {
+                return ((R*)Ptr)[(int)index];
+            }
+        }
+    };
+
+    // With `--blazor`, the raw bindings of every file are lowered to calls into the
+    // JavaScript module, which is generated once all of them are known.
+    let mut js_imports = Vec::new();
+    let mut lower = |tokens: TokenStream| {
+        if opt.blazor {
+            blazor::lower_imports(tokens, dll_name, &mut js_imports)
+        } else {
+            tokens
+        }
+    };
+
+    let mut files = vec![GeneratedFile {
+        name: SHARED_FILE_NAME.into(),
+        contents: finish_file(lower(shared), opt),
+    }];
+
+    if opt.out_dir.is_some() {
+        // Group the declarations by file name, using a sorted map so that the files are
+        // always generated in the same order.
+        let mut grouped = BTreeMap::<String, Vec<TokenStream>>::new();
+        for (type_name, item) in type_items {
+            let ident = if traits.contains_key(type_name) {
+                interface::interface_ident(type_name)
+            } else {
+                format_ident!("{}", &*type_name.name)
+            };
+            grouped
+                .entry(types.file_name(type_name, &ident))
+                .or_default()
+                .push(item);
+        }
+
+        files.extend(grouped.into_iter().map(|(name, items)| GeneratedFile {
+            name,
+            contents: finish_file(lower(quote! { #( #items )* }), opt),
+        }));
+    }
+
+    if opt.blazor {
+        files.push(GeneratedFile {
+            name: format!("{}.interop.js", dll_name),
+            contents: blazor::generate_js(dll_name, &js_imports),
+        });
+    }
+
+    Ok(files)
+}
+
+/// Adds the `using` directives to the declarations for a generated file and converts
+/// them to a string.
+fn finish_file(tokens: TokenStream, opt: &Opt) -> String {
+    let js_interop = if opt.blazor {
+        quote! { using System.Runtime.InteropServices.JavaScript; }
+    } else {
+        quote! {}
+    };
+
+    let generated = quote! {
+        using System;
+        using System.Collections.Generic;
+        using System.Runtime.ExceptionServices;
+        using System.Runtime.InteropServices;
+        #js_interop
+        using System.Text;
+        using System.Threading;
+        using System.Threading.Tasks;
+
+        #tokens
+    };
+
+    let mut generated = format::format(generated);
+
+    // NOTE: Preprocessor directives need to be on their own line, so the directive is
+    // added after the generated code has been converted to a string. Only annotations
+    // are enabled, since the generated code isn't written to avoid nullable warnings,
+    // and those warnings would otherwise be reported in projects that enable nullable
+    // reference types.
+    if opt.nullable {
+        generated.insert_str(0, "#nullable enable annotations\n");
+    }
+
+    generated
+}
+
+/// Generates the `__FromRaw` overload for converting a Rust string into a C# string.
+///
+/// Rust strings are UTF-8, whereas C# strings are UTF-16, so returning a string
+/// requires transcoding it. Which side should do the transcoding depends on the
+/// target runtime:
+///
+/// * .NET Core 3.0 and later have a vectorized UTF-8 decoder, so we decode the string
+///   directly from the Rust buffer with `Encoding.UTF8`.
+/// * Older runtimes (including Mono, which is used by Unity) don't have a vectorized
+///   decoder, so we have Rust write the UTF-16 data directly into a newly-allocated
+///   C# string instead. This hasn't been benchmarked against `Encoding.UTF8` on those
+///   runtimes, so it isn't known to be faster.
+fn quote_string_from_raw(target_framework: &str) -> TokenStream {
+    if has_fast_utf8_decoding(target_framework) {
+        quote! {
+            internal static void __FromRaw(RawVec raw, out string result)
+            {
+                result = Encoding.UTF8.GetString((byte*)raw.Ptr, (int)raw.Length);
+                __bindings.__cs_bindgen_drop_vec_u8(raw);
+            }
+        }
+    } else {
+        quote! {
+            internal static void __FromRaw(RawVec raw, out string result)
+            {
+                var utf8 = raw.AsSlice();
+                var length = (int)__cs_bindgen_string_utf16_len(utf8);
+
+                // NOTE: Strings are immutable in C#, so writing into the string's buffer
+                // is only valid because nothing else can have observed the string yet.
+                // `new string(char, int)` allocates a fresh string for any non-zero
+                // length (only literals and explicitly interned strings are shared), and
+                // `result` isn't visible to the caller until this method returns. Only
+                // `new string('\0', 0)` returns the shared empty string, which is why we
+                // skip the write for empty strings. `string.Create` would avoid the
+                // mutation entirely, but isn't available on most of the runtimes that
+                // take this path (.NET Standard 2.0, .NET Framework, and Mono).
+                result = new string('\0', length);
+                if (length > 0)
+                {
+                    fixed (char* dest = result)
+                    {
+                        __cs_bindgen_string_write_utf16(utf8, (IntPtr)dest, (UIntPtr)length);
+                    }
+                }
+
+                __bindings.__cs_bindgen_drop_vec_u8(raw);
+            }
+        }
+    }
+}
+
+/// Generates the `__FromRaw` and `__IntoRaw` overloads for dates, which are passed as
+/// the number of days since January 1st, 0001.
+///
+/// `DateOnly` uses the same day number as its raw representation. On older runtimes,
+/// dates are exposed as a `DateTime` at midnight instead, and any time of day is
+/// discarded when passing one to Rust.
+fn quote_date_conversions(types: &TypeMap) -> TokenStream {
+    if types.date_only {
+        quote! {
+            internal static void __FromRaw(int raw, out DateOnly result)
+            {
+                result = DateOnly.FromDayNumber(raw);
+            }
+
+            internal static void __IntoRaw(DateOnly value, out int result)
+            {
+                result = value.DayNumber;
+            }
         }
+    } else {
+        quote! {
+            internal static void __FromRaw(int raw, out DateTime result)
+            {
+                result = new DateTime(raw * TimeSpan.TicksPerDay);
+            }
+
+            internal static void __IntoRaw(DateTime value, out int result)
+            {
+                result = (int)(value.Ticks / TimeSpan.TicksPerDay);
+            }
+        }
+    }
+}
+
+/// Returns `true` if the target framework has a vectorized UTF-8 decoder, i.e. it's
+/// .NET Core 3.0 or later (including .NET 5 and later).
+fn has_fast_utf8_decoding(target_framework: &str) -> bool {
+    dotnet_major_version(target_framework)
+        .map(|major| major >= 3)
+        .unwrap_or(false)
+}
+
+/// Returns the major version of .NET Core (or .NET 5 and later) for a target framework
+/// moniker, or `None` if the target framework is .NET Standard or .NET Framework.
+fn dotnet_major_version(target_framework: &str) -> Option<u32> {
+    let version = if target_framework.starts_with("netcoreapp") {
+        &target_framework["netcoreapp".len()..]
+    } else if target_framework.starts_with("net") && target_framework.contains('.') {
+        // NOTE: This also matches `netstandardX.Y`, but the version number will fail to
+        // parse. .NET Framework monikers (e.g. `net472`) don't contain a `.`.
+        &target_framework["net".len()..]
+    } else {
+        return None;
     };
 
-    Ok(generated.to_string())
+    version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
 }
 
 /// Quotes the C# type corresponding to the given Rust primitive.
@@ -620,6 +2473,10 @@ fn quote_cs_type_for_repr(repr: &Repr, types: &TypeMap) -> TokenStream {
         Repr::U64 => quote! { ulong },
         Repr::USize => quote! { UIntPtr },
 
+        // NOTE: `BigInteger` is always fully-qualified, since `System.Numerics` isn't
+        // imported in order to avoid conflicts with its vector types.
+        Repr::I128 | Repr::U128 => quote! { System.Numerics.BigInteger },
+
         Repr::F32 => quote! { float },
         Repr::F64 => quote! { double },
 
@@ -633,28 +2490,74 @@ fn quote_cs_type_for_repr(repr: &Repr, types: &TypeMap) -> TokenStream {
             // naming convention from Rust structs.
             let ident = match &export.binding_style {
                 BindingStyle::Value(Schema::Enum(schema)) => {
-                    enumeration::quote_type_reference(schema)
+                    enumeration::quote_type_reference(schema, types)
+                }
+
+                // Types with custom conversions may be exposed as an existing C# type.
+                BindingStyle::Custom(custom) => {
+                    return custom::quote_cs_type(export, custom, types)
+                }
+
+                BindingStyle::Transparent(transparent) => {
+                    return transparent::quote_cs_type(export, transparent, types)
                 }
-                _ => format_ident!("{}", &*export.type_name.name).into_token_stream(),
+
+                _ => export.type_name.ident().into_token_stream(),
             };
 
-            // TODO: Take into account things like custom namespaces or renaming the type, once
-            // those are supported. For now, we manually prefix references to user-defined types
-            // with `global::` in order to avoid name collisions. Once we support custom
-            // namespaces, we'll want to use the correct namespace name instead.
-            quote! { global::#ident }
+            // NOTE: References to user-defined types are always fully-qualified in order to
+            // avoid name collisions with built-in types and types in other namespaces.
+            types.qualify(type_name, ident)
         }
 
+        // Byte buffers are exposed as a `byte[]` so that they can be copied in a single
+        // operation.
+        Repr::Vec(inner) | Repr::Slice(inner) if **inner == Repr::U8 => quote! { byte[] },
+
         Repr::Vec(inner) => quote_sequence_type(inner),
         Repr::Slice(inner) => quote_sequence_type(inner),
-        Repr::Array { element, .. } => quote_sequence_type(element),
+        Repr::Iter(item) => iter::quote_cs_type(item, types),
+        Repr::Events(event) => event::quote_cs_type(event, types),
+        Repr::Array { element, .. } => array::quote_cs_type(element, types),
 
         Repr::String | Repr::Str => quote! { string },
 
-        Repr::Option(_) => todo!("Support optional values"),
-        Repr::Result { .. } => todo!("Support results"),
+        Repr::Duration => quote! { TimeSpan },
+        Repr::SystemTime => quote! { DateTimeOffset },
+        Repr::Date if types.date_only => quote! { DateOnly },
+        Repr::Date => quote! { DateTime },
+        Repr::Uuid => quote! { Guid },
+
+        Repr::Map { key, value } => map::quote_cs_type(key, value, types),
+        Repr::Tuple(elements) => tuple::quote_cs_type(elements, types),
+
+        Repr::Option(inner) => option::quote_cs_type(inner, types),
+        Repr::Result { ok, .. } => result::quote_cs_type(ok, types),
+
+        Repr::Fn { inputs, output } => callback::quote_cs_type(inputs, output, types),
+        Repr::CancelFlag => quote! { CancellationToken },
+
+        // Owned trait objects returned from Rust are exposed through the trait's
+        // interface, same as borrowed trait objects.
+        Repr::Dyn(type_name) | Repr::DynHandle(type_name) => {
+            let ident = interface::interface_ident(type_name);
+            types.qualify(type_name, ident)
+        }
+
+        Repr::BoxDyn(type_name) => {
+            let ident = interface::base_class_ident(type_name);
+            types.qualify(type_name, ident)
+        }
+
+        // NOTE: References are only supported for handle types, which are passed by
+        // borrowing the handle, so they use the same C# type as the referenced type.
+        // Mutable references to other types are passed as `ref` parameters, which also
+        // use the referenced type.
+        Repr::Ref(inner) | Repr::RefMut(inner) => quote_cs_type_for_repr(inner, types),
 
-        Repr::Box(_) | Repr::Ref(_) => todo!("Support pointer types"),
+        // Boxes are an implementation detail of the Rust type, so boxed values use the
+        // same C# type as the boxed type.
+        Repr::Box(inner) => quote_cs_type_for_repr(inner, types),
     }
 }
 
@@ -677,17 +2580,25 @@ fn quote_cs_type_for_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
         // type for a data-carrying enum is an interface, and therefore has a different
         // naming convention from Rust structs.
         let ident = match &export.binding_style {
-            BindingStyle::Value(Schema::Enum(schema)) => enumeration::quote_type_reference(schema),
-            _ => format_ident!("{}", &*export.type_name.name).into_token_stream(),
+            BindingStyle::Value(Schema::Enum(schema)) => {
+                enumeration::quote_type_reference(schema, types)
+            }
+            BindingStyle::Transparent(transparent) => {
+                return transparent::quote_cs_type(export, transparent, types)
+            }
+            _ => export.type_name.ident().into_token_stream(),
         };
 
-        // TODO: Take into account things like custom namespaces or renaming the type, once
-        // those are supported. For now, we manually prefix references to user-defined types
-        // with `global::` in order to avoid name collisions. Once we support custom
-        // namespaces, we'll want to use the correct namespace name instead.
-        quote! { global::#ident }
+        // NOTE: References to user-defined types are always fully-qualified in order to
+        // avoid name collisions with built-in types and types in other namespaces.
+        types.qualify(type_name, ident)
     };
 
+    // Boxed fields use the same C# type as the boxed type.
+    if let Some(type_name) = boxed_type_name(schema) {
+        return named_type_reference(type_name, types);
+    }
+
     match schema {
         // NOTE: This is only valid in a return position, it's not valid to have a `void`
         // argument. An earlier validation pass has already rejected any such cases so we
@@ -729,42 +2640,494 @@ fn quote_cs_type_for_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
         | Schema::UnitStruct(_)
         | Schema::NewtypeStruct(_) => named_type_reference(schema.type_name().unwrap(), types),
 
-        // All sequence types are exposed in C# as a `List<T>`, since for all practical
-        // purposes that's the most efficient and flexible option.
-        Schema::Array(schema) => quote_sequence_type(&schema.element),
+        // Dynamically-sized sequence types are exposed in C# as a `List<T>`, since for
+        // all practical purposes that's the most efficient and flexible option.
+        // Byte buffers are exposed as a `byte[]`, matching the handling for `Repr`.
+        Schema::Slice(element) if is_byte_schema(element) => quote! { byte[] },
+        Schema::Seq(schema) if is_byte_schema(&schema.element) => quote! { byte[] },
+
         Schema::Slice(element) => quote_sequence_type(element),
         Schema::Seq(schema) => quote_sequence_type(&schema.element),
 
+        // Fixed-size arrays are exposed as a C# array of the same length.
+        Schema::Array(_) => {
+            let repr = repr_for_schema(schema)
+                .unwrap_or_else(|| panic!("Unsupported array type: {:?}", schema));
+            quote_cs_type_for_repr(&repr, types)
+        }
+
         // Map types are exposed in C# as a `Dictionary<K, V>`.
-        Schema::Map(schema) => {
-            let key = quote_cs_type_for_schema(&schema.key, types);
-            let value = quote_cs_type_for_schema(&schema.value, types);
-            quote! {
-                Dictionary<#key, #value>
-            }
+        Schema::Map(_) => {
+            let repr = repr_for_schema(schema)
+                .unwrap_or_else(|| panic!("Unsupported map type: {:?}", schema));
+            quote_cs_type_for_repr(&repr, types)
         }
 
         // Generate an unnamed tuple type for an exported Rust tuple. Conveniently this has
         // the same syntax in C# as in Rust, e.g. `(int, Foo, Bar)`. How nice!
-        Schema::Tuple(elements) => {
-            let element = elements
+        Schema::Tuple(_) => {
+            let repr = repr_for_schema(schema)
+                .unwrap_or_else(|| panic!("Unsupported tuple type: {:?}", schema));
+            quote_cs_type_for_repr(&repr, types)
+        }
+
+        // Optional types are handled the same as an `Option` in a function signature.
+        Schema::Option(_) => {
+            let repr = repr_for_schema(schema)
+                .unwrap_or_else(|| panic!("Unsupported optional type: {:?}", schema));
+            quote_cs_type_for_repr(&repr, types)
+        }
+
+        Schema::I128 | Schema::U128 => quote! { System.Numerics.BigInteger },
+    }
+}
+
+fn is_byte_schema(schema: &Schema) -> bool {
+    matches!(schema, Schema::U8)
+}
+
+/// Returns `true` if the C# type corresponding to `repr` is a value type.
+///
+/// This is needed in order to determine how to represent optional values, since
+/// C# value types have to be wrapped in a `Nullable<T>`, whereas reference types
+/// are already nullable.
+fn is_value_type(repr: &Repr, types: &TypeMap) -> bool {
+    match repr {
+        Repr::Bool
+        | Repr::Char
+        | Repr::I8
+        | Repr::I16
+        | Repr::I32
+        | Repr::I64
+        | Repr::ISize
+        | Repr::U8
+        | Repr::U16
+        | Repr::U32
+        | Repr::U64
+        | Repr::USize
+        | Repr::F32
+        | Repr::F64 => true,
+
+        // `BigInteger`, `TimeSpan`, `DateTimeOffset`, `DateOnly`, `DateTime`, `Guid`, and
+        // `CancellationToken` are all structs.
+        Repr::I128
+        | Repr::U128
+        | Repr::Duration
+        | Repr::SystemTime
+        | Repr::Date
+        | Repr::Uuid
+        | Repr::CancelFlag => true,
+
+        // Named types are value types if they're marshaled by value, except for
+        // data-carrying enums which are represented by an interface in C#.
+        Repr::Named(type_name) => {
+            let export = types
+                .get(type_name)
+                .unwrap_or_else(|| panic!("Could not resolve type reference: {:?}", type_name));
+
+            match &export.binding_style {
+                BindingStyle::Handle | BindingStyle::Marshal(..) => false,
+
+                // NOTE: We can't tell if the user-provided C# type is a value type, so
+                // it's assumed to be a class.
+                BindingStyle::Custom(_) => false,
+
+                // Wrapper structs are always value types, otherwise the newtype is
+                // exposed as its inner type.
+                BindingStyle::Transparent(transparent) => {
+                    transparent.wrapper || is_value_type(&transparent.inner, types)
+                }
+
+                BindingStyle::Value(Schema::Enum(schema)) => !schema.has_data(),
+                BindingStyle::Value(_) => true,
+            }
+        }
+
+        // Tuples are exposed as a `ValueTuple`.
+        Repr::Tuple(_) => true,
+
+        // Boxed values are exposed as the boxed type.
+        Repr::Box(inner) => is_value_type(inner, types),
+
+        _ => false,
+    }
+}
+
+/// Returns `true` if the raw representation of `repr` is zero-sized, i.e. if it's a
+/// struct exported by value that has no fields (or only zero-sized fields).
+///
+/// C# can't declare a zero-sized struct, so zero-sized values are marshaled as nothing:
+/// they're left out of raw structs and raw bindings, and the C# value is always the
+/// default value of its (empty) struct.
+pub fn is_zero_sized(repr: &Repr, types: &TypeMap) -> bool {
+    let export = match repr {
+        Repr::Named(type_name) => match types.get(type_name) {
+            Some(export) => export,
+            None => return false,
+        },
+        _ => return false,
+    };
+
+    match &export.binding_style {
+        BindingStyle::Value(schema) if !export.is_flags => schema
+            .as_struct_like()
+            .map(|schema| {
+                schema
+                    .fields
+                    .iter()
+                    .all(|field| is_zero_sized_schema(&field.schema, types))
+            })
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the raw representation of a field with the specified schema is
+/// zero-sized, see [`is_zero_sized`].
+///
+/// [`is_zero_sized`]: fn.is_zero_sized.html
+pub fn is_zero_sized_schema(schema: &Schema, types: &TypeMap) -> bool {
+    repr_for_schema(schema)
+        .map(|repr| is_zero_sized(&repr, types))
+        .unwrap_or(false)
+}
+
+/// Generates a string that uniquely identifies `repr`, suitable for use as part of
+/// a C# identifier.
+///
+/// This is used to generate names for the raw types corresponding to built-in
+/// generic types like `Option<T>`, since we need to generate a distinct type for
+/// each concrete instantiation of the generic type.
+fn mangle_repr(repr: &Repr) -> String {
+    match repr {
+        Repr::Unit => "unit".into(),
+        Repr::Bool => "bool".into(),
+        Repr::Char => "char".into(),
+
+        Repr::I8 => "i8".into(),
+        Repr::I16 => "i16".into(),
+        Repr::I32 => "i32".into(),
+        Repr::I64 => "i64".into(),
+        Repr::ISize => "isize".into(),
+
+        Repr::U8 => "u8".into(),
+        Repr::U16 => "u16".into(),
+        Repr::U32 => "u32".into(),
+        Repr::U64 => "u64".into(),
+        Repr::USize => "usize".into(),
+
+        Repr::I128 => "i128".into(),
+        Repr::U128 => "u128".into(),
+
+        Repr::F32 => "f32".into(),
+        Repr::F64 => "f64".into(),
+
+        Repr::Named(type_name) => type_name.mangled_name(),
+
+        Repr::Box(inner) => format!("Box_{}", mangle_repr(inner)),
+        Repr::Ref(inner) => format!("Ref_{}", mangle_repr(inner)),
+        Repr::RefMut(inner) => format!("RefMut_{}", mangle_repr(inner)),
+        Repr::Vec(inner) => format!("Vec_{}", mangle_repr(inner)),
+        Repr::Iter(item) => format!("Iter_{}", mangle_repr(item)),
+        Repr::Events(event) => format!("Events_{}", mangle_repr(event)),
+        Repr::Slice(inner) => format!("Slice_{}", mangle_repr(inner)),
+        Repr::Array { element, len } => format!("Array{}_{}", len, mangle_repr(element)),
+
+        Repr::Map { key, value } => format!("Map_{}_{}", mangle_repr(key), mangle_repr(value)),
+        Repr::Tuple(elements) => {
+            let elements = elements.iter().map(mangle_repr).collect::<Vec<_>>();
+            format!("Tuple{}_{}", elements.len(), elements.join("_"))
+        }
+
+        Repr::String => "String".into(),
+        Repr::Str => "str".into(),
+        Repr::Duration => "Duration".into(),
+        Repr::SystemTime => "SystemTime".into(),
+        Repr::Date => "Date".into(),
+        Repr::Uuid => "Uuid".into(),
+
+        Repr::Option(inner) => format!("Option_{}", mangle_repr(inner)),
+        Repr::Result { ok, err } => format!("Result_{}_{}", mangle_repr(ok), mangle_repr(err)),
+
+        Repr::Dyn(type_name) => format!("Dyn_{}", type_name.mangled_name()),
+        Repr::BoxDyn(type_name) => format!("BoxDyn_{}", type_name.mangled_name()),
+        Repr::DynHandle(type_name) => format!("DynHandle_{}", type_name.mangled_name()),
+
+        Repr::Fn { inputs, output } => {
+            let inputs = inputs.iter().map(mangle_repr).collect::<Vec<_>>();
+            format!(
+                "Fn{}_{}_{}",
+                inputs.len(),
+                inputs.join("_"),
+                mangle_repr(output)
+            )
+        }
+
+        Repr::CancelFlag => "CancelFlag".into(),
+    }
+}
+
+/// Returns the repr corresponding to the given type schema.
+///
+/// Exported functions describe their arguments with a `Repr`, whereas the fields
+/// of exported types are described with a `Schema`. This allows code generation
+/// for field types to reuse the repr-based logic where the two overlap. Returns
+/// `None` if the schema doesn't have a corresponding repr.
+pub fn repr_for_schema(schema: &Schema) -> Option<Repr> {
+    if let Some(type_name) = boxed_type_name(schema) {
+        return Some(Repr::Box(Box::new(Repr::Named(type_name.clone()))));
+    }
+
+    let repr = match schema {
+        Schema::Unit => Repr::Unit,
+        Schema::Bool => Repr::Bool,
+        Schema::Char => Repr::Char,
+
+        Schema::I8 => Repr::I8,
+        Schema::I16 => Repr::I16,
+        Schema::I32 => Repr::I32,
+        Schema::I64 => Repr::I64,
+        Schema::ISize => Repr::ISize,
+
+        Schema::U8 => Repr::U8,
+        Schema::U16 => Repr::U16,
+        Schema::U32 => Repr::U32,
+        Schema::U64 => Repr::U64,
+        Schema::USize => Repr::USize,
+
+        Schema::F32 => Repr::F32,
+        Schema::F64 => Repr::F64,
+
+        Schema::Str => Repr::Str,
+        Schema::String(_) => Repr::String,
+
+        // NOTE: The unwrap here is valid because all of the struct-like variants are
+        // guaranteed to have a type name. If this panics, that indicates a bug in the
+        // schematic crate.
+        Schema::Enum(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::UnitStruct(_)
+        | Schema::NewtypeStruct(_) => Repr::Named(schema.type_name().unwrap().clone()),
+
+        Schema::Slice(element) => Repr::Slice(Box::new(repr_for_schema(element)?)),
+        Schema::Seq(schema) => Repr::Vec(Box::new(repr_for_schema(&schema.element)?)),
+        Schema::Option(inner) => Repr::Option(Box::new(repr_for_schema(inner)?)),
+
+        Schema::Map(schema) => Repr::Map {
+            key: Box::new(repr_for_schema(&schema.key)?),
+            value: Box::new(repr_for_schema(&schema.value)?),
+        },
+
+        Schema::Tuple(elements) => Repr::Tuple(
+            elements
                 .iter()
-                .map(|schema| quote_cs_type_for_schema(schema, types));
-            quote! {
-                ( #( #element ),* )
+                .map(repr_for_schema)
+                .collect::<Option<Vec<_>>>()?,
+        ),
+
+        Schema::Array(schema) => Repr::Array {
+            element: Box::new(repr_for_schema(&schema.element)?),
+            len: schema.len,
+        },
+
+        Schema::I128 => Repr::I128,
+        Schema::U128 => Repr::U128,
+    };
+
+    Some(repr)
+}
+
+/// Returns the name and value of each variant of a C-like enum, for backends that
+/// need to spell out the implicit discriminants.
+pub fn enum_values(schema: &Enum) -> Vec<(String, i64)> {
+    let mut next = 0;
+    schema
+        .variants
+        .iter()
+        .map(|variant| match variant {
+            Variant::Unit { name, discriminant } => {
+                let value = discriminant
+                    .as_ref()
+                    .and_then(|discriminant| discriminant.to_string().parse().ok())
+                    .unwrap_or(next);
+                next = value + 1;
+                (name.to_string(), value)
+            }
+
+            _ => panic!("Simple enum can only have unit variants"),
+        })
+        .collect()
+}
+
+/// Returns the repr corresponding to the primitive type of an enum's discriminant.
+pub fn primitive_repr(primitive: Primitive) -> Repr {
+    match primitive {
+        Primitive::U8 => Repr::U8,
+        Primitive::U16 => Repr::U16,
+        Primitive::U32 => Repr::U32,
+        Primitive::U64 => Repr::U64,
+        Primitive::U128 => Repr::U128,
+        Primitive::Usize => Repr::USize,
+        Primitive::I8 => Repr::I8,
+        Primitive::I16 => Repr::I16,
+        Primitive::I32 => Repr::I32,
+        Primitive::I64 => Repr::I64,
+        Primitive::I128 => Repr::I128,
+        Primitive::Isize => Repr::ISize,
+    }
+}
+
+/// Collects every repr used in the exported items, including reprs nested inside
+/// other reprs (e.g. the element type of a `Vec`) and the fields of exported types.
+///
+/// Each repr appears at most once in the returned list, in the order that it was
+/// first encountered, so that any code generated from the list is deterministic.
+fn collect_reprs(exports: &[Export]) -> Vec<Repr> {
+    fn visit(repr: &Repr, reprs: &mut Vec<Repr>) {
+        if !reprs.contains(repr) {
+            reprs.push(repr.clone());
+        }
+
+        match repr {
+            Repr::Box(inner)
+            | Repr::Ref(inner)
+            | Repr::RefMut(inner)
+            | Repr::Vec(inner)
+            | Repr::Iter(inner)
+            | Repr::Events(inner)
+            | Repr::Slice(inner)
+            | Repr::Option(inner) => visit(inner, reprs),
+
+            Repr::Array { element, .. } => visit(element, reprs),
+
+            Repr::Result { ok, err } => {
+                visit(ok, reprs);
+                visit(err, reprs);
+            }
+
+            Repr::Map { key, value } => {
+                visit(key, reprs);
+                visit(value, reprs);
+            }
+
+            Repr::Tuple(elements) => {
+                for element in elements {
+                    visit(element, reprs);
+                }
+            }
+
+            Repr::Fn { inputs, output } => {
+                for input in inputs {
+                    visit(input, reprs);
+                }
+
+                visit(output, reprs);
             }
+
+            _ => {}
         }
+    }
 
-        // TODO: Add support for optional types. In order to do so, we'll need to determine
-        // if the type is a reference type or a value type. Reference types are inherently
-        // nullable, but value types need to be converted to `Nullable<T>` (or `T?` for
-        // short).
-        Schema::Option(_) => todo!("Generate nullable type reference"),
+    let mut reprs = Vec::new();
+    for export in exports {
+        match export {
+            Export::Fn(export) => {
+                for arg in &export.inputs {
+                    visit(&arg.repr, &mut reprs);
+                }
+
+                if let Some(output) = &export.output {
+                    visit(output, &mut reprs);
+                }
+            }
+
+            Export::Method(export) => {
+                for arg in &export.inputs {
+                    visit(&arg.repr, &mut reprs);
+                }
+
+                if let Some(output) = &export.output {
+                    visit(output, &mut reprs);
+                }
+            }
+
+            Export::Trait(export) => {
+                for method in &export.methods {
+                    for arg in &method.inputs {
+                        visit(&arg.repr, &mut reprs);
+                    }
+
+                    if let Some(output) = &method.output {
+                        visit(output, &mut reprs);
+                    }
+                }
+            }
 
-        Schema::I128 | Schema::U128 => {
-            unreachable!("Invalid argument types should have already been rejected");
+            Export::TraitImpl(_) | Export::StdImpl(_) | Export::List(_) => {}
+
+            Export::Conversion(export) => {
+                visit(&export.from, &mut reprs);
+                if export.fallible {
+                    visit(&Repr::Option(Box::new(export.into.clone())), &mut reprs);
+                } else {
+                    visit(&export.into, &mut reprs);
+                }
+            }
+
+            Export::Operator(export) => {
+                visit(&export.lhs, &mut reprs);
+                if let Some(rhs) = &export.rhs {
+                    visit(rhs, &mut reprs);
+                }
+                visit(&export.output, &mut reprs);
+            }
+
+            Export::Const(export) => visit(&export.repr, &mut reprs),
+
+            // Aliases are declared in terms of the C# type for the aliased type, so they
+            // don't need any conversions of their own.
+            Export::Alias(_) => {}
+
+            Export::Named(export) => {
+                let field_reprs: Vec<Repr> = match export.schema() {
+                    Some(Schema::Enum(schema)) => schema
+                        .variants
+                        .iter()
+                        .flat_map(|variant| variant.fields())
+                        .filter_map(|field| repr_for_schema(&field.schema))
+                        .collect(),
+
+                    Some(schema) => schema
+                        .as_struct_like()
+                        .map(|schema| {
+                            schema
+                                .fields
+                                .iter()
+                                .filter_map(|field| repr_for_schema(&field.schema))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+
+                    // Types with custom conversions are passed as their raw type, which
+                    // may need its own generated conversions.
+                    None => match &export.binding_style {
+                        BindingStyle::Custom(custom) => vec![custom.raw.clone()],
+                        BindingStyle::Transparent(transparent) => {
+                            vec![transparent.inner.clone()]
+                        }
+                        _ => Vec::new(),
+                    },
+                };
+
+                for repr in &field_reprs {
+                    visit(repr, &mut reprs);
+                }
+            }
         }
     }
+
+    reprs
 }
 
 #[extend::ext]
@@ -772,4 +3135,13 @@ impl TypeName {
     fn ident(&self) -> Ident {
         format_ident!("{}", self.name)
     }
+
+    /// Returns a name for the type that's unique across all modules, for use in the
+    /// identifiers of generated items that are specific to the type.
+    ///
+    /// The `::` separators in the module path aren't valid in C# identifiers, so they're
+    /// replaced with `__`.
+    fn mangled_name(&self) -> String {
+        format!("{}__{}", self.module.replace("::", "__"), self.name)
+    }
 }
@@ -0,0 +1,136 @@
+//! Code generation for exported type aliases.
+//!
+//! Aliases are generated as `using` alias directives in the shared bindings file, e.g.
+//! `type TileList = Vec<Tile>;` becomes:
+//!
+//! ```csharp
+//! using TileList = System.Collections.Generic.List<global::Tile>;
+//! ```
+//!
+//! `using` aliases are scoped to the file that declares them, so aliases are declared
+//! with `global using` when targeting .NET 6 or later, which makes them available to
+//! the entire project. When targeting older frameworks, the aliases are still declared
+//! in the generated file, but code using the bindings needs to declare them itself.
+//!
+//! The target of an alias can't refer to any of the other `using` directives, so the
+//! target type is always fully-qualified, and types that C# only has syntax for (e.g.
+//! `byte`, `int?`, or tuples) are written as the underlying framework type.
+
+use crate::generate::{self, TypeMap};
+use cs_bindgen_shared::{Alias, BindingStyle, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the `using` directive for an alias.
+pub fn quote_alias(
+    export: &Alias,
+    global: bool,
+    types: &TypeMap,
+) -> Result<TokenStream, failure::Error> {
+    let ident = format_ident!("{}", export.rename.as_deref().unwrap_or(&export.name));
+    let target = quote_alias_target(&export.repr, types).ok_or_else(|| {
+        failure::format_err!(
+            "Type alias {}::{} refers to {:?}, which can't be the target of a C# `using` alias",
+            export.module,
+            export.name,
+            export.repr,
+        )
+    })?;
+
+    let global = if global {
+        quote! { global }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #global using #ident = #target;
+    })
+}
+
+/// Returns `true` if the target framework supports `global using` directives, i.e. it's
+/// .NET 6 or later.
+pub fn supports_global_using(target_framework: &str) -> bool {
+    generate::dotnet_major_version(target_framework)
+        .map(|major| major >= 6)
+        .unwrap_or(false)
+}
+
+/// Quotes the fully-qualified C# type for `repr`, or returns `None` if the type can't be
+/// named in a `using` alias.
+fn quote_alias_target(repr: &Repr, types: &TypeMap) -> Option<TokenStream> {
+    let target = match repr {
+        Repr::Bool => quote! { System.Boolean },
+
+        Repr::I8 => quote! { System.SByte },
+        Repr::I16 => quote! { System.Int16 },
+        Repr::I32 => quote! { System.Int32 },
+        Repr::I64 => quote! { System.Int64 },
+        Repr::ISize => quote! { System.IntPtr },
+
+        Repr::U8 => quote! { System.Byte },
+        Repr::U16 => quote! { System.UInt16 },
+        Repr::U32 => quote! { System.UInt32 },
+        Repr::U64 => quote! { System.UInt64 },
+        Repr::USize => quote! { System.UIntPtr },
+
+        Repr::I128 | Repr::U128 => quote! { System.Numerics.BigInteger },
+
+        Repr::F32 => quote! { System.Single },
+        Repr::F64 => quote! { System.Double },
+
+        Repr::String | Repr::Str => quote! { System.String },
+
+        Repr::Duration => quote! { System.TimeSpan },
+        Repr::SystemTime => quote! { System.DateTimeOffset },
+        Repr::Date if types.date_only => quote! { System.DateOnly },
+        Repr::Date => quote! { System.DateTime },
+        Repr::Uuid => quote! { System.Guid },
+
+        // NOTE: Byte buffers are exposed as a `byte[]`, which can't be named in an alias.
+        Repr::Vec(inner) | Repr::Slice(inner) if **inner == Repr::U8 => return None,
+        Repr::Vec(inner) | Repr::Slice(inner) => {
+            let element = quote_alias_target(inner, types)?;
+            quote! { System.Collections.Generic.List<#element> }
+        }
+
+        Repr::Map { key, value } => {
+            let key = quote_alias_target(key, types)?;
+            let value = quote_alias_target(value, types)?;
+            quote! { System.Collections.Generic.Dictionary<#key, #value> }
+        }
+
+        Repr::Tuple(elements) if !elements.is_empty() && elements.len() <= 7 => {
+            let elements = elements
+                .iter()
+                .map(|element| quote_alias_target(element, types))
+                .collect::<Option<Vec<_>>>()?;
+            quote! { System.ValueTuple<#( #elements ),*> }
+        }
+
+        // NOTE: Nullable reference types can't be named in an alias, so optional
+        // references are aliased as the reference type.
+        Repr::Option(inner) => {
+            let inner_ty = quote_alias_target(inner, types)?;
+            if generate::is_value_type(inner, types) {
+                quote! { System.Nullable<#inner_ty> }
+            } else {
+                inner_ty
+            }
+        }
+
+        // Transparent newtypes without a wrapper are exposed as their inner type, so
+        // the inner type needs to be qualified as well. All other named types are
+        // already referred to by their fully-qualified name.
+        Repr::Named(type_name) => match &types.get(type_name)?.binding_style {
+            BindingStyle::Transparent(transparent) if !transparent.wrapper => {
+                return quote_alias_target(&transparent.inner, types)
+            }
+            _ => generate::quote_cs_type_for_repr(repr, types),
+        },
+
+        _ => return None,
+    };
+
+    Some(target)
+}
@@ -0,0 +1,158 @@
+//! Code generation for fixed-size arrays.
+//!
+//! On the Rust side, an array `[T; N]` is marshaled as a `[T::Abi; N]`, i.e. the
+//! raw elements are stored inline. The generated C# exposes arrays as a `T[]` of the
+//! same length, and throws an `ArgumentException` if an array of the wrong length is
+//! passed to Rust.
+//!
+//! We generate a dedicated raw struct for each array type used in the exported API.
+//! For primitive element types the raw struct uses a fixed-size buffer, otherwise
+//! it has one field per element.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+use syn::Ident;
+
+/// Returns the identifier of the raw struct generated for an array with the specified
+/// element type and length.
+pub fn raw_array_ident(element: &Repr, len: usize) -> Ident {
+    format_ident!("__RawArray__{}__{}", generate::mangle_repr(element), len)
+}
+
+/// Quotes the idiomatic C# type for an array of the specified element type.
+pub fn quote_cs_type(element: &Repr, types: &TypeMap) -> TokenStream {
+    let element = generate::quote_cs_type_for_repr(element, types);
+    quote! { #element[] }
+}
+
+/// Returns `true` if arrays of `element` can be stored in a C# fixed-size buffer.
+///
+/// C# only supports fixed-size buffers of the built-in numeric types.
+fn is_fixed_buffer_element(element: &Repr) -> bool {
+    match element {
+        Repr::Bool
+        | Repr::I8
+        | Repr::I16
+        | Repr::I32
+        | Repr::I64
+        | Repr::U8
+        | Repr::U16
+        | Repr::U32
+        | Repr::U64
+        | Repr::F32
+        | Repr::F64 => true,
+
+        _ => false,
+    }
+}
+
+/// Generates the raw struct and conversion functions for an array with the
+/// specified element type and length.
+pub fn quote_raw_array(element: &Repr, len: usize, types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_array_ident(element, len);
+    let cs_ty = quote_cs_type(element, types);
+    let element_ty = generate::quote_cs_type_for_repr(element, types);
+    let raw_element_ty = binding::raw_type_from_repr(element, types);
+
+    // NOTE: We need to use an unsuffixed literal, otherwise `quote` would generate
+    // e.g. `4usize`, which isn't valid C#.
+    let len_lit = Literal::usize_unsuffixed(len);
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    // Generate the struct definition and the expressions used to access the element
+    // at a given index. For fixed-size buffers we can index directly, otherwise we
+    // have to reference each field individually.
+    let (struct_def, from_raw_body, into_raw_body) = if is_fixed_buffer_element(element) {
+        let struct_def = quote! {
+            [StructLayout(LayoutKind.Sequential)]
+            internal unsafe struct #raw_ident
+            {
+                public fixed #raw_element_ty Elements[#len_lit];
+            }
+        };
+
+        let from_raw_body = quote! {
+            for (int index = 0; index < #len_lit; index += 1)
+            {
+                #from_raw(raw.Elements[index], out result[index]);
+            }
+        };
+
+        // NOTE: Fixed-size buffers can only be indexed through a fixed variable, so we
+        // build the raw array in a local before assigning it to `result`.
+        let into_raw_body = quote! {
+            var raw = new #raw_ident();
+            for (int index = 0; index < #len_lit; index += 1)
+            {
+                #into_raw(value[index], out raw.Elements[index]);
+            }
+
+            result = raw;
+        };
+
+        (struct_def, from_raw_body, into_raw_body)
+    } else {
+        let field = (0..len)
+            .map(|index| format_ident!("Element{}", index))
+            .collect::<Vec<_>>();
+        let index = (0..len).map(Literal::usize_unsuffixed);
+
+        let struct_def = quote! {
+            [StructLayout(LayoutKind.Sequential)]
+            internal struct #raw_ident
+            {
+                #(
+                    public #raw_element_ty #field;
+                )*
+            }
+        };
+
+        let from_raw_body = quote! {
+            #(
+                #from_raw(raw.#field, out result[#index]);
+            )*
+        };
+
+        let index = (0..len).map(Literal::usize_unsuffixed);
+        let into_raw_body = quote! {
+            result = new #raw_ident();
+            #(
+                #into_raw(value[#index], out result.#field);
+            )*
+        };
+
+        (struct_def, from_raw_body, into_raw_body)
+    };
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ident raw, out #cs_ty result)
+        {
+            result = new #element_ty[#len_lit];
+            #from_raw_body
+        }
+
+        internal static void #into_raw(#cs_ty value, out #raw_ident result)
+        {
+            if (value.Length != #len_lit)
+            {
+                throw new ArgumentException(
+                    "Expected an array of length " + #len_lit + ", got an array of length " + value.Length,
+                    nameof(value));
+            }
+
+            #into_raw_body
+        }
+    });
+
+    quote! {
+        // Generate the raw struct for the array type. This needs to match the layout
+        // of the raw array type on the Rust side.
+        #struct_def
+
+        #raw_conversions
+    }
+}
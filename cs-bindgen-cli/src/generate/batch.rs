@@ -0,0 +1,292 @@
+//! Code generation for functions exported with `#[cs_bindgen(batch)]`.
+//!
+//! Along with the regular wrapper, each batched function gets an overload that takes
+//! a `CommandBuffer` as its first argument. Rather than calling into Rust, the
+//! overload records the call in the buffer, and all of the recorded calls are run with
+//! a single call into Rust when the buffer is submitted:
+//!
+//! ```csharp
+//! var commands = new CommandBuffer();
+//! foreach (var discard in discards)
+//! {
+//!     table.PushDiscard(commands, discard.Seat, discard.Tile);
+//! }
+//! commands.Submit();
+//! ```
+//!
+//! Each command is written as the address of the command function exported for the
+//! Rust function, followed by the raw arguments to the function. The address is looked
+//! up the first time the function is recorded, and cached in the bindings class. See
+//! the `batch` module in `cs-bindgen` for the Rust side of the encoding.
+
+use crate::generate::{self, binding, docs, func, TypeMap};
+use cs_bindgen_shared::*;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Quotes the raw binding for the function that returns the command function for a
+/// batched function, along with the property that caches the returned address.
+pub fn quote_raw_command_binding(command: &str, dll_name: &str) -> TokenStream {
+    let raw_fn = binding::quote_raw_fn_binding(command, quote! { IntPtr }, quote! {}, dll_name);
+    let getter = format_ident!("{}", command);
+    let cached = format_ident!("{}_cache", command);
+    let ptr = command_ptr_ident(command);
+
+    // NOTE: The address is looked up the first time it's needed, rather than when the
+    // bindings class is initialized, so that the version handshake runs first.
+    quote! {
+        #raw_fn
+
+        private static IntPtr #cached;
+
+        internal static IntPtr #ptr
+        {
+            get
+            {
+                if (#cached == IntPtr.Zero)
+                {
+                    #cached = #getter();
+                }
+
+                return #cached;
+            }
+        }
+    }
+}
+
+/// Quotes the overload of a batched function that records a call in a command buffer.
+///
+/// `receiver` is the expression for the raw handle of the receiver for methods, which
+/// must borrow the handle. The handle is retained by the buffer until the buffer is
+/// submitted, so that disposing the object doesn't drop the value before the command
+/// has run.
+pub fn quote_record_fn(
+    name: Ident,
+    command: &str,
+    receiver: Option<TokenStream>,
+    inputs: &[FnArg],
+    types: &TypeMap,
+) -> TokenStream {
+    let raw_args = inputs
+        .iter()
+        .filter(|arg| !generate::is_zero_sized(&arg.repr, types))
+        .collect::<Vec<_>>();
+    for arg in &raw_args {
+        if !is_batch_arg(&arg.repr) {
+            panic!(
+                "Argument `{}` of batched function `{}` can't be recorded in a command buffer: {:?}",
+                arg.name, name, arg.repr,
+            );
+        }
+    }
+
+    let bindings = binding::bindings_class_ident();
+    let into_raw = binding::into_raw_fn_ident();
+    let command_ptr = command_ptr_ident(command);
+
+    let arg_name = raw_args.iter().map(|arg| func::quote_arg_ident(&arg.name));
+    let temp_arg_name = raw_args
+        .iter()
+        .map(|arg| format_ident!("__{}", arg.name))
+        .collect::<Vec<_>>();
+    let raw_ty = raw_args
+        .iter()
+        .map(|arg| binding::raw_type_from_repr(&arg.repr, types));
+
+    // The receiver's handle is checked before anything is written to the buffer, since
+    // accessing the handle throws if the object has been disposed.
+    let (static_, get_receiver, retain_receiver, write_receiver) = match receiver {
+        Some(receiver) => (
+            quote! {},
+            quote! { IntPtr __self = #receiver; },
+            quote! { commands.__Retain(this._safeHandle, this._owner); },
+            quote! { commands.__Write(__self); },
+        ),
+        None => (quote! { static }, quote! {}, quote! {}, quote! {}),
+    };
+
+    let args = Some(quote! { CommandBuffer commands })
+        .into_iter()
+        .chain(func::quote_optional_args(inputs, types));
+    let docs = docs::quote_doc_comment(Some(&format!(
+        "Records a call to `{}` in `commands`, which is run when the buffer is submitted.",
+        name,
+    )));
+
+    quote! {
+        #docs
+        public #static_ void #name(#( #args ),*)
+        {
+            if (commands == null)
+            {
+                throw new ArgumentNullException(nameof(commands));
+            }
+
+            #get_receiver
+            #( #bindings.#into_raw(#arg_name, out #raw_ty #temp_arg_name); )*
+
+            #retain_receiver
+            commands.__Begin(#bindings.#command_ptr);
+            #write_receiver
+            #( commands.__Write(#temp_arg_name); )*
+        }
+    }
+}
+
+/// Quotes the `CommandBuffer` class that batched calls are recorded in.
+pub fn quote_command_buffer_class() -> TokenStream {
+    let bindings = binding::bindings_class_ident();
+
+    quote! {
+        // Records calls to functions exported with `#[cs_bindgen(batch)]` so that they
+        // can be run with a single call into Rust.
+        //
+        // The recorded calls are run in order when the buffer is submitted. If one of
+        // them panics, the remaining calls are skipped and `Submit` throws a
+        // `RustPanicException`. The buffer is cleared either way, so it can be reused for
+        // the next batch of calls.
+        //
+        // Objects used as the receiver of a recorded call can still be disposed before
+        // the buffer is submitted, but must not be passed to Rust by value until then.
+        public sealed class CommandBuffer : IDisposable
+        {
+            private byte[] _buffer;
+            private int _length;
+            private int _count;
+
+            // The handles of the objects borrowed by the recorded calls, which are
+            // retained until the buffer is submitted or cleared.
+            private readonly List<SafeHandle> _handles = new List<SafeHandle>();
+
+            public CommandBuffer() : this(256) { }
+
+            public CommandBuffer(int capacity)
+            {
+                _buffer = new byte[Math.Max(capacity, 16)];
+            }
+
+            // The number of calls recorded since the buffer was last submitted or
+            // cleared.
+            public int Count
+            {
+                get { return _count; }
+            }
+
+            public unsafe void Submit()
+            {
+                if (_count == 0)
+                {
+                    return;
+                }
+
+                RawVec __panic = new RawVec();
+                try
+                {
+                    fixed (byte* ptr = _buffer)
+                    {
+                        #bindings.__cs_bindgen_submit_commands(
+                            new RawSlice((IntPtr)ptr, _length),
+                            ref __panic);
+                    }
+                }
+                finally
+                {
+                    Clear();
+                }
+
+                #bindings.__CheckPanic(__panic);
+            }
+
+            // Discards the recorded calls without running them.
+            public void Clear()
+            {
+                foreach (var handle in _handles)
+                {
+                    handle.DangerousRelease();
+                }
+
+                _handles.Clear();
+                _length = 0;
+                _count = 0;
+            }
+
+            public void Dispose()
+            {
+                Clear();
+            }
+
+            internal void __Begin(IntPtr command)
+            {
+                __Write(command);
+                _count += 1;
+            }
+
+            // Retains the handle of a borrowed object, along with the handle of the
+            // object that it borrows its value from, if any.
+            internal void __Retain(SafeHandle handle, SafeHandle owner)
+            {
+                __Retain(handle);
+                if (owner != null)
+                {
+                    __Retain(owner);
+                }
+            }
+
+            private void __Retain(SafeHandle handle)
+            {
+                bool success = false;
+                handle.DangerousAddRef(ref success);
+                if (success)
+                {
+                    _handles.Add(handle);
+                }
+            }
+
+            // NOTE: The values are copied into the buffer byte by byte, since the
+            // arguments for each call aren't aligned within the buffer.
+            internal unsafe void __Write<T>(T value) where T : unmanaged
+            {
+                int size = sizeof(T);
+                if (_length + size > _buffer.Length)
+                {
+                    Array.Resize(ref _buffer, Math.Max(_buffer.Length * 2, _length + size));
+                }
+
+                fixed (byte* ptr = &_buffer[_length])
+                {
+                    Buffer.MemoryCopy(&value, ptr, size, size);
+                }
+
+                _length += size;
+            }
+        }
+    }
+}
+
+/// Returns the identifier of the property that caches the address of the command
+/// function for a batched function.
+fn command_ptr_ident(command: &str) -> Ident {
+    format_ident!("{}_ptr", command)
+}
+
+/// Returns `true` if arguments of the specified type can be recorded in a command
+/// buffer, i.e. their raw representation is a plain value.
+fn is_batch_arg(repr: &Repr) -> bool {
+    matches!(
+        repr,
+        Repr::Bool
+            | Repr::I8
+            | Repr::I16
+            | Repr::I32
+            | Repr::I64
+            | Repr::ISize
+            | Repr::U8
+            | Repr::U16
+            | Repr::U32
+            | Repr::U64
+            | Repr::USize
+            | Repr::F32
+            | Repr::F64
+    )
+}
@@ -0,0 +1,718 @@
+//! Generation of paired marshaling benchmarks for the exported functions and types.
+//!
+//! Two benchmarks are generated from the same set of calls, so that the cost of the
+//! C# side of the bindings can be separated from the cost of the Rust side:
+//!
+//! * A criterion benchmark that calls the raw binding functions directly from Rust,
+//!   which measures the Rust shims in isolation.
+//! * A BenchmarkDotNet project that calls the same functions through the C# bindings,
+//!   which measures the full round trip from C#.
+//!
+//! The benchmarks need sample values for each argument, so only the calls that can be
+//! constructed without knowing anything about the user's types are included:
+//!
+//! * Each synchronous function whose arguments are all primitives, strings, vecs or
+//!   slices of primitives, exported types that implement `Default`, or references to
+//!   handle types that implement `Default`.
+//! * Each value struct that implements `Default`, which is returned from Rust.
+//! * Each handle type that implements `Default`, which is created and dropped.
+//!
+//! Functions that take a handle by value are skipped, since the handle would have to
+//! be recreated for every call.
+
+use crate::{
+    generate::{
+        class_ident, func, input_name, quote_cs_type_for_repr, type_map, GeneratedFile, TypeMap,
+        TypeNameExt,
+    },
+    pack::{self, escape_xml},
+    Opt,
+};
+use cs_bindgen_shared::{schematic::Schema, BindingStyle, Export, Func, NamedType, Repr};
+use heck::*;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+use std::{
+    env,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+/// The number of elements in the sample vecs and slices.
+const SAMPLE_LEN: usize = 64;
+
+/// The sample string, which is short enough to be representative of typical names.
+const SAMPLE_STRING: &str = "Riichi";
+
+/// The target framework of the benchmark project if `--target-framework` doesn't
+/// specify one that can run an executable.
+const DEFAULT_TARGET_FRAMEWORK: &str = "netcoreapp3.1";
+
+const BENCHMARK_DOTNET_VERSION: &str = "0.12.1";
+
+/// Generates the Rust benchmark, the C# benchmarks, and the project for the C#
+/// benchmarks.
+pub fn generate_bench(exports: &[Export], opt: &Opt) -> Vec<GeneratedFile> {
+    let types = type_map(exports, opt);
+    let benches = exports
+        .iter()
+        .filter_map(|export| Bench::new(export, &types))
+        .collect::<Vec<_>>();
+
+    let project_name = format!("{}.Benchmarks", pack::project_name(opt));
+    vec![
+        GeneratedFile {
+            name: "marshaling.rs".into(),
+            contents: generate_rust(&benches, exports, opt),
+        },
+        GeneratedFile {
+            name: "Benchmarks.cs".into(),
+            contents: generate_cs(&benches, &types, opt),
+        },
+        GeneratedFile {
+            name: format!("{}.csproj", project_name),
+            contents: generate_project(exports, opt),
+        },
+    ]
+}
+
+/// A single call that's benchmarked in both Rust and C#.
+enum Bench<'a> {
+    /// Invoke an exported function with sample arguments.
+    Fn {
+        export: &'a Func,
+        args: Vec<Sample>,
+        output: Option<String>,
+    },
+
+    /// Get the default value of a value struct.
+    Default(&'a NamedType),
+
+    /// Create the default value of a handle type and drop it.
+    CreateAndDrop(&'a NamedType),
+}
+
+impl<'a> Bench<'a> {
+    fn new(export: &'a Export, types: &TypeMap) -> Option<Self> {
+        match export {
+            Export::Fn(export) if !export.is_async => {
+                let args = export
+                    .inputs
+                    .iter()
+                    .map(|arg| Sample::new(&arg.repr, types))
+                    .collect::<Option<Vec<_>>>()?;
+
+                let output = match &export.output {
+                    Some(output) => Some(rust_output_type(output, types)?),
+                    None => None,
+                };
+
+                Some(Bench::Fn {
+                    export,
+                    args,
+                    output,
+                })
+            }
+
+            Export::Named(export) if export.default_fn.is_some() => match &export.binding_style {
+                BindingStyle::Handle => Some(Bench::CreateAndDrop(export)),
+                BindingStyle::Value(schema) if is_struct(schema) => Some(Bench::Default(export)),
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+
+    /// The name of the benchmark, which is the same in both languages so that the
+    /// results can be compared.
+    fn name(&self) -> String {
+        match self {
+            Bench::Fn { export, .. } => {
+                func::fn_ident(&export.name, export.rename.as_deref()).to_string()
+            }
+            Bench::Default(export) => format!("Default_{}", export.type_name.mangled_name()),
+            Bench::CreateAndDrop(export) => {
+                format!("CreateAndDrop_{}", export.type_name.mangled_name())
+            }
+        }
+    }
+}
+
+/// A sample value for an argument of a benchmarked function.
+struct Sample {
+    /// The Rust type of the argument.
+    rust_type: String,
+
+    /// The Rust expression that creates the sample, which is run outside of the
+    /// measurement.
+    rust_setup: String,
+
+    /// How the sample is passed to the binding function, with `{}` replaced by the
+    /// sample. Samples that are only borrowed are kept alive until the measurement is
+    /// done.
+    rust_borrow: Option<&'static str>,
+
+    cs_type: TokenStream,
+    cs_value: TokenStream,
+
+    /// Whether the C# sample has to be disposed once the benchmarks are done.
+    cs_disposable: bool,
+}
+
+impl Sample {
+    fn new(repr: &Repr, types: &TypeMap) -> Option<Self> {
+        let sample = match repr {
+            Repr::String | Repr::Str => Sample {
+                rust_type: if *repr == Repr::Str {
+                    "&'static str".into()
+                } else {
+                    "String".into()
+                },
+                rust_setup: format!("String::from({:?})", SAMPLE_STRING),
+                rust_borrow: if *repr == Repr::Str {
+                    Some("&*{}")
+                } else {
+                    None
+                },
+                cs_type: quote! { string },
+                cs_value: quote! { #SAMPLE_STRING },
+                cs_disposable: false,
+            },
+
+            Repr::Vec(element) | Repr::Slice(element) => {
+                let (rust_element, cs_element) = scalar_sample(element, types)?;
+                let rust_element_type = rust_scalar_type(element)?;
+                let is_slice = matches!(repr, Repr::Slice(_));
+                let cs_type = quote_cs_type_for_repr(repr, types);
+                let len = Literal::usize_unsuffixed(SAMPLE_LEN);
+                let cs_value = if **element == Repr::U8 {
+                    quote! { Enumerable.Repeat(#cs_element, #len).ToArray() }
+                } else {
+                    quote! { new #cs_type(Enumerable.Repeat(#cs_element, #len)) }
+                };
+
+                Sample {
+                    rust_type: if is_slice {
+                        format!("&'static [{}]", rust_element_type)
+                    } else {
+                        format!("Vec<{}>", rust_element_type)
+                    },
+                    rust_setup: format!("vec![{}; {}]", rust_element, SAMPLE_LEN),
+                    rust_borrow: if is_slice { Some("&{}[..]") } else { None },
+                    cs_type,
+                    cs_value,
+                    cs_disposable: false,
+                }
+            }
+
+            Repr::Named(type_name) => {
+                let export = types.get(type_name)?;
+                match &export.binding_style {
+                    BindingStyle::Value(schema)
+                        if export.default_fn.is_some() && is_struct(schema) =>
+                    {
+                        let cs_type = quote_cs_type_for_repr(repr, types);
+                        Sample {
+                            rust_type: rust_path(export),
+                            rust_setup: format!("<{} as Default>::default()", rust_path(export)),
+                            rust_borrow: None,
+                            cs_value: quote! { #cs_type.Default },
+                            cs_type,
+                            cs_disposable: false,
+                        }
+                    }
+
+                    _ => return None,
+                }
+            }
+
+            Repr::Ref(inner) => {
+                let export = match &**inner {
+                    Repr::Named(type_name) => types.get(type_name)?,
+                    _ => return None,
+                };
+
+                if !matches!(export.binding_style, BindingStyle::Handle)
+                    || export.default_fn.is_none()
+                {
+                    return None;
+                }
+
+                let cs_type = quote_cs_type_for_repr(repr, types);
+                Sample {
+                    rust_type: format!("&'static {}", rust_path(export)),
+                    rust_setup: format!("<{} as Default>::default()", rust_path(export)),
+                    rust_borrow: Some("&{}"),
+                    cs_value: quote! { new #cs_type() },
+                    cs_type,
+                    cs_disposable: true,
+                }
+            }
+
+            _ => {
+                let (rust_setup, cs_value) = scalar_sample(repr, types)?;
+                Sample {
+                    rust_type: rust_scalar_type(repr)?.into(),
+                    rust_setup,
+                    rust_borrow: None,
+                    cs_type: quote_cs_type_for_repr(repr, types),
+                    cs_value,
+                    cs_disposable: false,
+                }
+            }
+        };
+
+        Some(sample)
+    }
+}
+
+/// Returns the Rust and C# expressions for a sample of a primitive type.
+fn scalar_sample(repr: &Repr, types: &TypeMap) -> Option<(String, TokenStream)> {
+    match repr {
+        Repr::Bool => Some(("true".into(), quote! { true })),
+        _ => {
+            let rust_type = rust_scalar_type(repr)?;
+            let cs_type = quote_cs_type_for_repr(repr, types);
+            Some((format!("1{}", rust_type), quote! { (#cs_type)1 }))
+        }
+    }
+}
+
+fn rust_scalar_type(repr: &Repr) -> Option<&'static str> {
+    let ty = match repr {
+        Repr::Bool => "bool",
+        Repr::I8 => "i8",
+        Repr::I16 => "i16",
+        Repr::I32 => "i32",
+        Repr::I64 => "i64",
+        Repr::ISize => "isize",
+        Repr::U8 => "u8",
+        Repr::U16 => "u16",
+        Repr::U32 => "u32",
+        Repr::U64 => "u64",
+        Repr::USize => "usize",
+        Repr::F32 => "f32",
+        Repr::F64 => "f64",
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+/// Returns the Rust type of a return value, if it's an owned type that can be named
+/// from outside of the crate.
+fn rust_output_type(repr: &Repr, types: &TypeMap) -> Option<String> {
+    match repr {
+        Repr::String => Some("String".into()),
+        Repr::Vec(element) => Some(format!("Vec<{}>", rust_output_type(element, types)?)),
+        Repr::Option(inner) => Some(format!("Option<{}>", rust_output_type(inner, types)?)),
+        Repr::Named(type_name) => types.get(type_name).map(|export| rust_path(export)),
+        _ => rust_scalar_type(repr).map(Into::into),
+    }
+}
+
+/// Returns the full path to the exported type, which includes the name of the crate.
+fn rust_path(export: &NamedType) -> String {
+    format!("{}::{}", export.type_name.module, export.type_name.name)
+}
+
+fn is_struct(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Struct(_)
+            | Schema::TupleStruct(_)
+            | Schema::UnitStruct(_)
+            | Schema::NewtypeStruct(_)
+    )
+}
+
+/// Returns the name of the crate that the bindings were generated for.
+///
+/// Prefers the module path of an exported type, since the name of the library may
+/// have a platform-specific prefix.
+fn crate_name(exports: &[Export], opt: &Opt) -> String {
+    let module = exports.iter().find_map(|export| match export {
+        Export::Named(export) => export.type_name.module.split("::").next(),
+        _ => None,
+    });
+
+    match module {
+        Some(module) => module.into(),
+        None => {
+            let name = input_name(opt);
+            let is_dll = opt.input().extension().map_or(false, |ext| ext == "dll");
+            match name.strip_prefix("lib") {
+                Some(stripped) if !is_dll => stripped.into(),
+                _ => name.into(),
+            }
+        }
+    }
+}
+
+fn generate_rust(benches: &[Bench], exports: &[Export], opt: &Opt) -> String {
+    let crate_name = crate_name(exports, opt);
+
+    let mut externs = String::new();
+    let mut bench_fns = String::new();
+    for bench in benches {
+        let name = bench.name();
+        match bench {
+            Bench::Fn {
+                export,
+                args,
+                output,
+            } => {
+                let params = args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, arg)| format!("arg{}: <{} as Abi>::Abi, ", index, arg.rust_type))
+                    .collect::<String>();
+                let return_decl = match output {
+                    Some(output) => format!(" -> <{} as Abi>::Abi", output),
+                    None => String::new(),
+                };
+                writeln!(
+                    externs,
+                    "    fn {}({}__panic: *mut RawString){};",
+                    export.binding, params, return_decl,
+                )
+                .unwrap();
+
+                let setup = args
+                    .iter()
+                    .map(|arg| format!("{}, ", arg.rust_setup))
+                    .collect::<String>();
+                let bindings = (0..args.len())
+                    .map(|index| format!("arg{}, ", index))
+                    .collect::<String>();
+                let call_args = args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, arg)| {
+                        let arg_name = format!("arg{}", index);
+                        let arg = match arg.rust_borrow {
+                            Some(borrow) => borrow.replace("{}", &arg_name),
+                            None => arg_name,
+                        };
+                        format!("Abi::into_abi({}), ", arg)
+                    })
+                    .collect::<String>();
+
+                // NOTE: Borrowed samples and the return value are returned from the
+                // routine so that they're dropped outside of the measurement.
+                let kept_args = args
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, arg)| arg.rust_borrow.is_some())
+                    .map(|(index, _)| format!("arg{}, ", index))
+                    .collect::<String>();
+                let (call, result) = match output {
+                    Some(output) => (
+                        format!("let result = {}({}&mut panic);", export.binding, call_args),
+                        format!("<{} as Abi>::from_abi(result)", output),
+                    ),
+                    None => (
+                        format!("{}({}&mut panic);", export.binding, call_args),
+                        "()".into(),
+                    ),
+                };
+
+                write!(
+                    bench_fns,
+                    r#"
+    c.bench_function("{name}", |b| {{
+        b.iter_batched(
+            || ({setup}),
+            |({bindings})| unsafe {{
+                let mut panic = empty_panic();
+                {call}
+                check_panic(panic);
+                ({result}, {kept_args})
+            }},
+            BatchSize::SmallInput,
+        )
+    }});
+"#,
+                    name = name,
+                    setup = setup,
+                    bindings = bindings,
+                    call = call,
+                    result = result,
+                    kept_args = kept_args,
+                )
+                .unwrap();
+            }
+
+            Bench::Default(export) | Bench::CreateAndDrop(export) => {
+                let default_fn = export.default_fn.as_ref().unwrap();
+                let path = rust_path(export);
+                writeln!(
+                    externs,
+                    "    fn {}(__panic: *mut RawString) -> <{} as Abi>::Abi;",
+                    default_fn, path,
+                )
+                .unwrap();
+
+                write!(
+                    bench_fns,
+                    r#"
+    c.bench_function("{name}", |b| {{
+        b.iter(|| unsafe {{
+            let mut panic = empty_panic();
+            let result = {default_fn}(&mut panic);
+            check_panic(panic);
+            <{path} as Abi>::from_abi(result)
+        }})
+    }});
+"#,
+                    name = name,
+                    default_fn = default_fn,
+                    path = path,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    format!(
+        r#"//! Marshaling benchmarks for the raw bindings of `{crate_name}`, generated by
+//! cs-bindgen.
+//!
+//! These call the binding functions directly, so they measure the Rust side of each
+//! call in isolation. Compare the results with the C# benchmarks generated alongside
+//! this file to see how much of each call is spent in the C# bindings.
+//!
+//! To run the benchmarks, add this file to the `benches` directory of `{crate_name}`,
+//! add `criterion` as a dev-dependency, and declare the benchmark with
+//! `harness = false`.
+
+#![allow(improper_ctypes)]
+
+extern crate {crate_name};
+
+use criterion::{{criterion_group, criterion_main, BatchSize, Criterion}};
+use cs_bindgen::abi::{{Abi, RawString}};
+use std::ptr;
+
+extern "C" {{
+{externs}}}
+
+fn empty_panic() -> RawString {{
+    RawString {{
+        ptr: ptr::null(),
+        len: 0,
+        capacity: 0,
+    }}
+}}
+
+fn check_panic(panic: RawString) {{
+    if !panic.ptr.is_null() {{
+        panic!("Binding panicked: {{}}", unsafe {{ panic.into_string() }});
+    }}
+}}
+
+fn marshaling(c: &mut Criterion) {{{bench_fns}}}
+
+criterion_group!(benches, marshaling);
+criterion_main!(benches);
+"#,
+        crate_name = crate_name,
+        externs = externs,
+        bench_fns = bench_fns,
+    )
+}
+
+fn generate_cs(benches: &[Bench], types: &TypeMap, opt: &Opt) -> String {
+    let class_name = class_ident(opt);
+    let bench_class = format_ident!("{}Benchmarks", class_name);
+
+    // NOTE: The class containing the exported functions is declared in the root
+    // namespace, if there is one.
+    let root_namespace = &types.root_namespace;
+    let qualified_class = quote! { global::#( #root_namespace. )* #class_name };
+
+    let mut fields = Vec::new();
+    let mut disposable_fields = Vec::new();
+    let bench_methods = benches
+        .iter()
+        .map(|bench| {
+            let bench_ident = format_ident!("{}", bench.name());
+            match bench {
+                Bench::Fn { export, args, .. } => {
+                    let fn_ident = func::fn_ident(&export.name, export.rename.as_deref());
+                    let arg_fields = export
+                        .inputs
+                        .iter()
+                        .zip(args)
+                        .map(|(input, sample)| {
+                            let field =
+                                format_ident!("_{}{}", fn_ident, input.name.to_camel_case());
+                            let Sample {
+                                cs_type, cs_value, ..
+                            } = sample;
+                            fields.push(quote! {
+                                private readonly #cs_type #field = #cs_value;
+                            });
+                            if sample.cs_disposable {
+                                disposable_fields.push(field.clone());
+                            }
+
+                            field
+                        })
+                        .collect::<Vec<_>>();
+
+                    match &export.output {
+                        Some(output) => {
+                            let return_ty = quote_cs_type_for_repr(output, types);
+                            quote! {
+                                [Benchmark]
+                                public #return_ty #bench_ident()
+                                {
+                                    return #qualified_class.#fn_ident(#( #arg_fields ),*);
+                                }
+                            }
+                        }
+
+                        None => quote! {
+                            [Benchmark]
+                            public void #bench_ident()
+                            {
+                                #qualified_class.#fn_ident(#( #arg_fields ),*);
+                            }
+                        },
+                    }
+                }
+
+                Bench::Default(export) => {
+                    let ty = types.qualify(&export.type_name, export.type_name.ident());
+                    quote! {
+                        [Benchmark]
+                        public #ty #bench_ident()
+                        {
+                            return #ty.Default;
+                        }
+                    }
+                }
+
+                Bench::CreateAndDrop(export) => {
+                    let ty = types.qualify(&export.type_name, export.type_name.ident());
+                    quote! {
+                        [Benchmark]
+                        public void #bench_ident()
+                        {
+                            using (var value = new #ty()) {}
+                        }
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let generated = quote! {
+        using System;
+        using System.Collections.Generic;
+        using System.Linq;
+        using BenchmarkDotNet.Attributes;
+        using BenchmarkDotNet.Running;
+
+        public class #bench_class
+        {
+            #( #fields )*
+
+            #( #bench_methods )*
+
+            [GlobalCleanup]
+            public void Cleanup()
+            {
+                #( #disposable_fields.Dispose(); )*
+            }
+        }
+
+        public static class Program
+        {
+            public static void Main(string[] args)
+            {
+                BenchmarkSwitcher.FromAssembly(typeof(Program).Assembly).Run(args);
+            }
+        }
+    };
+
+    super::format::format(generated)
+}
+
+/// Generates the `.csproj` for the C# benchmarks, which compiles the generated
+/// bindings into the benchmark executable and copies the native libraries next to it.
+fn generate_project(exports: &[Export], opt: &Opt) -> String {
+    let target_framework = opt
+        .target_framework
+        .as_deref()
+        .filter(|framework| !framework.starts_with("netstandard"))
+        .unwrap_or(DEFAULT_TARGET_FRAMEWORK);
+
+    // NOTE: The paths in the project are relative to the benchmark directory, so
+    // absolute paths are used to reference the bindings and native libraries.
+    let bindings = match (&opt.out_dir, &opt.output) {
+        (Some(out_dir), _) => absolute(out_dir).join("*.cs"),
+        (None, Some(output)) => absolute(output),
+        (None, None) => unreachable!("`--bench` requires `--output` or `--out-dir`"),
+    };
+
+    let package_refs = pack::package_refs(exports)
+        .iter()
+        .map(|(name, version)| {
+            format!(
+                "    <PackageReference Include=\"{}\" Version=\"{}\" />\n",
+                name, version
+            )
+        })
+        .collect::<String>();
+
+    let native_libs = opt
+        .inputs()
+        .iter()
+        .map(|input| {
+            format!(
+                "    <None Include=\"{}\" CopyToOutputDirectory=\"PreserveNewest\" />\n",
+                escape_xml(&absolute(input).to_string_lossy()),
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>{target_framework}</TargetFramework>
+    <AllowUnsafeBlocks>true</AllowUnsafeBlocks>
+    <LangVersion>{lang_version}</LangVersion>
+    <Optimize>true</Optimize>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <PackageReference Include="BenchmarkDotNet" Version="{benchmark_dotnet_version}" />
+{package_refs}  </ItemGroup>
+
+  <ItemGroup>
+    <Compile Include="{bindings}" />
+{native_libs}  </ItemGroup>
+
+</Project>
+"#,
+        target_framework = escape_xml(target_framework),
+        lang_version = if opt.nullable { "8.0" } else { "7.3" },
+        benchmark_dotnet_version = BENCHMARK_DOTNET_VERSION,
+        package_refs = package_refs,
+        bindings = escape_xml(&bindings.to_string_lossy()),
+        native_libs = native_libs,
+    )
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        env::current_dir()
+            .expect("Unable to get current directory")
+            .join(path)
+    })
+}
@@ -10,23 +10,20 @@
 //! function, using the `[DllImport]` attribute to load the corresponding function
 //! from the Rust dylib.
 
-use crate::generate::{self, class, enumeration, strukt, TypeMap, STRING_SCHEMA};
+use crate::generate::{
+    self, batch, boxed, class, constant, conversion, enumeration, flags, interface, operator,
+    option, result, strukt, tuple, TypeMap, TypeNameExt, STRING_SCHEMA,
+};
 use cs_bindgen_shared::{
+    boxed_type_name,
     schematic::{Field, Schema, TypeName},
-    BindingStyle, Export, FnArg, Repr,
+    BindingStyle, Export, FnArg, NamedType, Repr,
 };
 use proc_macro2::TokenStream;
 use quote::*;
+use std::borrow::Cow;
 use syn::{punctuated::Punctuated, token::Comma, Ident};
 
-// TODO: For the below functions that generate identifiers based on a type name, we
-// should use the fully-qualified `TypeName` instead of just a `&str` name. Right
-// now, if two types with the same name in different modules are exported, the
-// generated bindings will collide. We can avoid this by taking the module name into
-// account when generating the idents. This will require some additional mangling
-// logic, since the module paths include `::` characters, which aren't valid in C#
-// identifiers.
-
 /// Returns the identifier of the generating bindings class.
 pub fn bindings_class_ident() -> Ident {
     format_ident!("__bindings")
@@ -55,8 +52,12 @@ pub fn into_raw_fn_ident() -> Ident {
 /// convert the C# representation of the type to-and-from the raw representation.
 /// This function provides the canonical way to generate the name of the raw type
 /// corresponding to any given exported Rust type.
+///
+/// Raw types are always declared in the global namespace, so the name is based on
+/// the mangled name of the type in order to avoid collisions between types with the
+/// same name in different modules.
 pub fn raw_ident(type_name: &TypeName) -> Ident {
-    format_ident!("__{}__Raw", type_name.name)
+    format_ident!("__{}__Raw", type_name.mangled_name())
 }
 
 pub fn wrap_bindings(tokens: TokenStream) -> TokenStream {
@@ -71,19 +72,39 @@ pub fn wrap_bindings(tokens: TokenStream) -> TokenStream {
 pub fn quote_raw_binding(export: &Export, dll_name: &str, types: &TypeMap) -> TokenStream {
     match export {
         Export::Fn(export) => {
-            let args = quote_binding_args(&export.inputs, types);
+            let mut args = quote_binding_args(&export.inputs, types);
+
+            // Async functions take the completion callback instead of returning a value.
+            if export.is_async {
+                args.push(quote! { RawCompletion __completion });
+            }
+            args.push(quote_panic_arg());
+
             let return_ty = match &export.output {
-                Some(output) => raw_type_from_repr(output, types),
-                None => quote! { void },
+                Some(output) if !export.is_async && !generate::is_zero_sized(output, types) => {
+                    raw_type_from_repr(output, types)
+                }
+                _ => quote! { void },
             };
 
-            quote_raw_fn_binding(&export.binding, return_ty, args.to_token_stream(), dll_name)
+            let binding =
+                quote_raw_fn_binding(&export.binding, return_ty, args.to_token_stream(), dll_name);
+            let command = export
+                .command
+                .as_ref()
+                .map(|command| batch::quote_raw_command_binding(command, dll_name));
+            quote! {
+                #binding
+                #command
+            }
         }
 
         Export::Method(export) => {
             let return_ty = match &export.output {
-                Some(output) => raw_type_from_repr(output, types),
-                None => quote! { void },
+                Some(output) if !generate::is_zero_sized(output, types) => {
+                    raw_type_from_repr(output, types)
+                }
+                _ => quote! { void },
             };
 
             // TODO: Unify input handling for raw bindings. It shouldn't be necessary to
@@ -95,75 +116,244 @@ pub fn quote_raw_binding(export: &Export, dll_name: &str, types: &TypeMap) -> To
                 let handle_type = class::quote_handle_ptr();
                 args.insert(0, quote! { #handle_type self });
             }
-
-            quote_raw_fn_binding(&export.binding, return_ty, args.to_token_stream(), dll_name)
+            args.push(quote_panic_arg());
+
+            let binding =
+                quote_raw_fn_binding(&export.binding, return_ty, args.to_token_stream(), dll_name);
+            let command = export
+                .command
+                .as_ref()
+                .map(|command| batch::quote_raw_command_binding(command, dll_name));
+            quote! {
+                #binding
+                #command
+            }
         }
 
-        // Generate the binding for the destructor for any named types that are marshaled
-        // as handles.
-        Export::Named(export) => match &export.binding_style {
-            BindingStyle::Handle => class::quote_drop_fn(&export, dll_name),
-
-            BindingStyle::Value(schema) => {
-                let index_fn = quote_raw_fn_binding(
-                    &export.index_fn,
-                    raw_type_from_schema(schema, types),
-                    quote! { RawSlice slice, UIntPtr index },
-                    dll_name,
-                );
+        // Trait methods take the raw trait object as the receiver, and each trait impl
+        // exports a function for converting a handle into a trait object.
+        Export::Trait(export) => interface::quote_method_bindings(export, dll_name, types),
+        Export::TraitImpl(export) => interface::quote_as_dyn_binding(export, dll_name),
+
+        // The bindings for std trait impls are declared with their type, since the impls
+        // are merged into their types when loading the exports.
+        Export::StdImpl(_) => quote! {},
+
+        Export::Conversion(export) => conversion::quote_raw_binding(export, dll_name, types),
+        Export::Operator(export) => operator::quote_raw_binding(export, dll_name, types),
+
+        // Each list element type may be exported more than once, so their bindings are
+        // generated along with the list conversions once all of the exports are known.
+        Export::List(_) => quote! {},
+
+        Export::Const(export) => constant::quote_raw_binding(export, dll_name, types),
+
+        // Aliases don't have any bindings.
+        Export::Alias(_) => quote! {},
+
+        // Named types export a number of helper functions, as well as bindings for any
+        // standard traits implemented by the type.
+        Export::Named(export) => {
+            let raw_ty = raw_type_from_repr(&Repr::Named(export.type_name.clone()), types);
+            let panic_arg = quote_panic_arg();
 
-                let convert_list_fn = quote_raw_fn_binding(
-                    &export.convert_list_fn,
+            let default_fn = export.default_fn.as_ref().map(|default_fn| {
+                quote_raw_fn_binding(default_fn, raw_ty.clone(), panic_arg.clone(), dll_name)
+            });
+
+            let display_fn = export.display_fn.as_ref().map(|display_fn| {
+                quote_raw_fn_binding(
+                    display_fn,
                     quote! { RawVec },
-                    quote! { RawSlice raw },
+                    quote! { #raw_ty self, #panic_arg },
                     dll_name,
-                );
-
-                let drop_vec_fn = quote_raw_fn_binding(
-                    &export.drop_vec_fn,
-                    quote! { void },
-                    quote! { RawVec vec },
+                )
+            });
+
+            let eq_fn = export.eq_fn.as_ref().map(|eq_fn| {
+                quote_raw_fn_binding(
+                    eq_fn,
+                    quote! { byte },
+                    quote! { #raw_ty self, #raw_ty other, #panic_arg },
                     dll_name,
-                );
-
-                let from_raw = from_raw_fn_ident();
-                let into_raw = into_raw_fn_ident();
-                let ty = generate::quote_cs_type_for_schema(schema, types);
-                let raw_repr = raw_type_from_schema(schema, types);
-                let index_fn_name = format_ident!("{}", &*export.index_fn);
-                let drop_vec_fn_name = format_ident!("{}", &*export.drop_vec_fn);
-                let convert_list_fn_name = format_ident!("{}", &*export.convert_list_fn);
-
-                let list_from_raw = quote! {
-                    internal static void #from_raw(RawVec raw, out List<#ty> result)
-                    {
-                        result = raw.ToList<#raw_repr, #ty>(#index_fn_name, #from_raw);
-                        #drop_vec_fn_name(raw);
-                    }
-                };
-
-                let list_into_raw = quote! {
-                    internal static void #into_raw(List<#ty> items, out RawVec result)
-                    {
-                        result = RawVec.FromList(
-                            items,
-                            item => {
-                                #into_raw(item, out #raw_repr raw);
-                                return raw;
-                            },
-                            #convert_list_fn_name);
-                    }
-                };
-
-                quote! {
-                    #index_fn
-                    #convert_list_fn
-                    #drop_vec_fn
-                    #list_from_raw
-                    #list_into_raw
-                }
+                )
+            });
+
+            let hash_fn = export.hash_fn.as_ref().map(|hash_fn| {
+                quote_raw_fn_binding(
+                    hash_fn,
+                    quote! { ulong },
+                    quote! { #raw_ty self, #panic_arg },
+                    dll_name,
+                )
+            });
+
+            let cmp_fn = export.cmp_fn.as_ref().map(|cmp_fn| {
+                quote_raw_fn_binding(
+                    cmp_fn,
+                    quote! { int },
+                    quote! { #raw_ty self, #raw_ty other, #panic_arg },
+                    dll_name,
+                )
+            });
+
+            let clone_fn = export.clone_fn.as_ref().map(|clone_fn| {
+                quote_raw_fn_binding(
+                    clone_fn,
+                    raw_ty.clone(),
+                    quote! { #raw_ty self, #panic_arg },
+                    dll_name,
+                )
+            });
+
+            let bindings = quote_named_type_bindings(export, dll_name, types);
+            quote! {
+                #bindings
+                #default_fn
+                #display_fn
+                #eq_fn
+                #hash_fn
+                #cmp_fn
+                #clone_fn
             }
-        },
+        }
+    }
+}
+
+fn quote_named_type_bindings(export: &NamedType, dll_name: &str, types: &TypeMap) -> TokenStream {
+    match &export.binding_style {
+        // Generate the binding for the destructor for any named types that are marshaled
+        // as handles. Lists of handles use the same helper bindings as other types, except
+        // that the index function moves each element into a new handle.
+        BindingStyle::Handle => {
+            let drop_fn = class::quote_drop_fn(&export, dll_name);
+            let repr = Repr::Named(export.type_name.clone());
+            let list_bindings = quote_list_bindings(
+                export,
+                generate::quote_cs_type_for_repr(&repr, types),
+                class::quote_handle_ptr(),
+                dll_name,
+                types,
+            );
+
+            quote! {
+                #drop_fn
+                #list_bindings
+            }
+        }
+
+        // Marshaled types have the same helper bindings as value types, since the
+        // serialized buffer is treated as the raw value.
+        BindingStyle::Value(schema) | BindingStyle::Marshal(_, schema) => quote_list_bindings(
+            export,
+            generate::quote_cs_type_for_schema(schema, types),
+            raw_type_from_schema(schema, types),
+            dll_name,
+            types,
+        ),
+
+        // Types with custom conversions don't have a schema, so their types are
+        // determined from their repr instead.
+        BindingStyle::Custom(_) => {
+            let repr = Repr::Named(export.type_name.clone());
+            quote_list_bindings(
+                export,
+                generate::quote_cs_type_for_repr(&repr, types),
+                raw_type_from_repr(&repr, types),
+                dll_name,
+                types,
+            )
+        }
+
+        // Only wrapper structs have their own C# type, otherwise lists of the newtype
+        // aren't supported.
+        BindingStyle::Transparent(transparent) if transparent.wrapper => {
+            let repr = Repr::Named(export.type_name.clone());
+            quote_list_bindings(
+                export,
+                generate::quote_cs_type_for_repr(&repr, types),
+                raw_type_from_repr(&repr, types),
+                dll_name,
+                types,
+            )
+        }
+
+        BindingStyle::Transparent(_) => quote! {},
+    }
+}
+
+/// Generates the helper bindings for passing lists of an exported type, along with the
+/// list conversions.
+fn quote_list_bindings(
+    export: &NamedType,
+    ty: TokenStream,
+    raw_repr: TokenStream,
+    dll_name: &str,
+    types: &TypeMap,
+) -> TokenStream {
+    let index_fn = quote_raw_fn_binding(
+        &export.index_fn,
+        raw_repr.clone(),
+        quote! { RawSlice slice, UIntPtr index },
+        dll_name,
+    );
+
+    let convert_list_fn = quote_raw_fn_binding(
+        &export.convert_list_fn,
+        quote! { RawVec },
+        quote! { RawSlice raw },
+        dll_name,
+    );
+
+    let drop_vec_fn = quote_raw_fn_binding(
+        &export.drop_vec_fn,
+        quote! { void },
+        quote! { RawVec vec },
+        dll_name,
+    );
+
+    let from_raw = from_raw_fn_ident();
+    let into_raw = into_raw_fn_ident();
+    let index_fn_name = format_ident!("{}", &*export.index_fn);
+    let drop_vec_fn_name = format_ident!("{}", &*export.drop_vec_fn);
+    let convert_list_fn_name = format_ident!("{}", &*export.convert_list_fn);
+
+    let list_from_raw = quote! {
+        internal static void #from_raw(RawVec raw, out List<#ty> result)
+        {
+            result = raw.ToList<#raw_repr, #ty>(#index_fn_name, #from_raw);
+            #drop_vec_fn_name(raw);
+        }
+    };
+
+    let list_into_raw = quote! {
+        internal static void #into_raw(List<#ty> items, out RawVec result)
+        {
+            result = RawVec.FromList(
+                items,
+                item => {
+                    #into_raw(item, out #raw_repr raw);
+                    return raw;
+                },
+                #convert_list_fn_name);
+        }
+    };
+
+    // Only blittable structs check their layout against the Rust type.
+    let size_fn = match &export.size_fn {
+        Some(size_fn) if strukt::is_blittable(export, types) => {
+            quote_raw_fn_binding(size_fn, quote! { UIntPtr }, quote! {}, dll_name)
+        }
+        _ => quote! {},
+    };
+
+    quote! {
+        #index_fn
+        #convert_list_fn
+        #drop_vec_fn
+        #size_fn
+        #list_from_raw
+        #list_into_raw
     }
 }
 
@@ -172,12 +362,20 @@ pub fn quote_raw_binding(export: &Export, dll_name: &str, types: &TypeMap) -> To
 /// The raw representation is what is used in FFI calls:
 ///
 /// * For primitive numeric types we use the corresponding C# numeric type.
+/// * For 128-bit integers we use `RawI128` and `RawU128`.
 /// * For `String` and `Vec` we use `RawVec`.
+/// * For `Duration` and `SystemTime` we use a `long` number of ticks.
+/// * For dates we use an `int` number of days.
+/// * For `Uuid` we use `Guid`, since the raw layout matches.
 /// * For `str` and slices use `RawSlice`.
+/// * For callbacks we use `RawCallback`.
+/// * For trait objects we use `IntPtr`.
 /// * For named types we look up the export definition to determine what raw repr to
 ///   use:
 ///   * Handle types are represented as a raw pointer (`IntPtr`, specifically).
 ///   * Value types have a corresponding raw struct.
+///   * Marshaled types are passed as their serialized buffer (`RawVec`).
+///   * Types with custom conversions use the raw representation of their raw type.
 ///   * C-like enums that are passed by value use the numeric type of their
 ///     discriminant.
 ///
@@ -201,6 +399,9 @@ pub fn raw_type_from_repr(repr: &Repr, types: &TypeMap) -> TokenStream {
         Repr::U64 => quote! { ulong },
         Repr::USize => quote! { UIntPtr },
 
+        Repr::I128 => quote! { RawI128 },
+        Repr::U128 => quote! { RawU128 },
+
         Repr::F32 => quote! { float },
         Repr::F64 => quote! { double },
 
@@ -212,26 +413,94 @@ pub fn raw_type_from_repr(repr: &Repr, types: &TypeMap) -> TokenStream {
             match &export.binding_style {
                 BindingStyle::Handle => class::quote_handle_ptr(),
                 BindingStyle::Value(schema) => raw_type_from_schema(schema, types),
+                BindingStyle::Marshal(..) => quote! { RawVec },
+                BindingStyle::Custom(custom) => raw_type_from_repr(&custom.raw, types),
+                BindingStyle::Transparent(transparent) => {
+                    raw_type_from_repr(&transparent.inner, types)
+                }
             }
         }
 
-        // Pointer types are all marshalled as `IntPtr`.
-        Repr::Box(_) | Repr::Ref(_) => quote! { IntPtr },
+        // References are marshalled as `IntPtr`.
+        Repr::Ref(_) => quote! { IntPtr },
+
+        // Boxes have a generated raw struct for each boxed type.
+        Repr::Box(inner) => {
+            let ident = boxed::raw_box_ident(inner);
+            quote! { global::#ident }
+        }
+
+        // Mutable references point to the raw value in the caller's stack frame, so that
+        // Rust can write the modified value back.
+        Repr::RefMut(inner) => {
+            let inner = raw_type_from_repr(inner, types);
+            quote! { #inner* }
+        }
 
         Repr::Vec(_) => quote! { RawVec },
+        // Event queues are pulled from in the same way as iterators.
+        Repr::Iter(_) | Repr::Events(_) => quote! { RawIter },
         Repr::Slice(_) => quote! { RawSlice },
 
         Repr::String => quote! { RawVec },
+
+        // Time types are passed as a number of ticks.
+        Repr::Duration | Repr::SystemTime => quote! { long },
+        Repr::Date => quote! { int },
+
+        // The raw representation of a `Uuid` has the same layout as a `Guid`.
+        Repr::Uuid => quote! { Guid },
         Repr::Str => quote! { RawSlice },
 
-        Repr::Array { .. } => todo!("Support arrays"),
-        Repr::Option(_) => todo!("Support optional types"),
-        Repr::Result { .. } => todo!("Support `Result`"),
+        // Arrays have a generated raw struct for each combination of element type and
+        // length.
+        Repr::Array { element, len } => {
+            let ident = array::raw_array_ident(element, *len);
+            quote! { global::#ident }
+        }
+
+        // All map types share the same raw representation.
+        Repr::Map { .. } => quote! { RawMap },
+
+        // Tuples have a generated raw struct for each combination of element types.
+        Repr::Tuple(elements) => {
+            let ident = tuple::raw_tuple_ident(elements);
+            quote! { global::#ident }
+        }
+
+        // Optional values have a generated raw struct for each type of optional value.
+        Repr::Option(inner) => {
+            let ident = option::raw_option_ident(inner);
+            quote! { global::#ident }
+        }
+
+        // Results have a generated raw struct for each combination of success and error
+        // types.
+        Repr::Result { ok, err } => {
+            let ident = result::raw_result_ident(ok, err);
+            quote! { global::#ident }
+        }
+
+        // Trait objects are passed as a pointer to the boxed trait object, and owned trait
+        // objects returned from Rust are passed as a handle to the `Box<dyn Trait>`.
+        Repr::Dyn(_) | Repr::DynHandle(_) => quote! { IntPtr },
+        Repr::BoxDyn(_) => quote! { RawManaged },
+
+        // All callbacks share the same raw representation.
+        Repr::Fn { .. } => quote! { RawCallback },
+
+        // Cancellation flags are passed as a pointer to the shared flag.
+        Repr::CancelFlag => quote! { IntPtr },
     }
 }
 
 /// Generates the appropriate raw type name for the given type schema.
 pub fn raw_type_from_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
+    if let Some(type_name) = boxed_type_name(schema) {
+        let ident = boxed::raw_box_ident(&Repr::Named(type_name.clone()));
+        return quote! { global::#ident };
+    }
+
     match schema {
         Schema::Unit => quote! { byte },
         Schema::Bool => quote! { byte },
@@ -272,18 +541,21 @@ pub fn raw_type_from_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
                 .get(&schema.name)
                 .unwrap_or_else(|| panic!("No export found for named type {:?}", &schema.name));
 
-            // There are three possible raw representations for an exported enum:
+            // There are four possible raw representations for an exported enum:
             //
             // * Enums that are marshalled as handles are represented as the raw handle pointer
             //   type (`IntPtr`).
+            // * Enums that are serialized are represented as the serialized buffer.
             // * Data-carrying enums have an associate struct that represents its raw type.
             // * C-like enums are marshalled directly as an integer value.
             if matches!(export.binding_style, BindingStyle::Handle) {
                 class::quote_handle_ptr()
+            } else if matches!(export.binding_style, BindingStyle::Marshal(..)) {
+                quote! { RawVec }
             } else if schema.has_data() {
                 named_type_raw_reference(&schema.name)
             } else {
-                enumeration::quote_discriminant_type(schema)
+                enumeration::quote_discriminant_type(export, schema, types)
             }
         }
 
@@ -300,16 +572,24 @@ pub fn raw_type_from_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
                 .get(type_name)
                 .unwrap_or_else(|| panic!("No export found for named type {:?}", type_name));
 
-            // Determine the raw representation based on the marshaling style.
-            if matches!(export.binding_style, BindingStyle::Handle) {
+            // Determine the raw representation based on the marshaling style. Flags are
+            // marshaled as their underlying integer value, and transparent newtypes as
+            // their inner value.
+            if let BindingStyle::Transparent(transparent) = &export.binding_style {
+                raw_type_from_repr(&transparent.inner, types)
+            } else if matches!(export.binding_style, BindingStyle::Handle) {
                 class::quote_handle_ptr()
+            } else if matches!(export.binding_style, BindingStyle::Marshal(..)) {
+                quote! { RawVec }
+            } else if export.is_flags {
+                flags::quote_bits_type(export)
+            } else if strukt::is_blittable(export, types) {
+                generate::quote_cs_type_for_schema(schema, types)
             } else {
                 named_type_raw_reference(type_name)
             }
         }
 
-        Schema::Array(_) => todo!("Support passing fixed-size arrays"),
-
         Schema::Slice(_) => quote! { RawSlice },
 
         Schema::Seq(schema) => {
@@ -320,28 +600,39 @@ pub fn raw_type_from_schema(schema: &Schema, types: &TypeMap) -> TokenStream {
             }
         }
 
-        // TODO: Add support for collection types.
-        Schema::Option(_) | Schema::Tuple(_) | Schema::Map { .. } => {
-            todo!("Generate argument binding")
+        Schema::Array(_) | Schema::Option(_) | Schema::Map(_) | Schema::Tuple(_) => {
+            let repr = generate::repr_for_schema(schema)
+                .unwrap_or_else(|| panic!("Unsupported field type: {:?}", schema));
+            raw_type_from_repr(&repr, types)
         }
 
-        Schema::I128 | Schema::U128 => {
-            unreachable!("Invalid types should have already been handled")
-        }
+        Schema::I128 => quote! { RawI128 },
+        Schema::U128 => quote! { RawU128 },
     }
 }
 
 /// Generates the field definitions for the raw struct representation of an exported
 /// Rust type.
-pub fn raw_struct_fields(fields: &[Field<'_>], types: &TypeMap) -> TokenStream {
-    let field_name = fields
+pub fn raw_struct_fields(
+    fields: &[Field<'_>],
+    renamed: &[Cow<'static, str>],
+    types: &TypeMap,
+) -> TokenStream {
+    // NOTE: Zero-sized fields take up no space in the Rust struct, so they're left out
+    // of the raw struct entirely.
+    let fields = fields
         .iter()
         .enumerate()
-        .map(|(index, field)| strukt::field_ident(field.name, index));
+        .filter(|(_, field)| !generate::is_zero_sized_schema(&field.schema, types))
+        .collect::<Vec<_>>();
+
+    let field_name = fields
+        .iter()
+        .map(|(index, field)| strukt::field_ident(field.name, *index, renamed));
 
     let field_ty = fields
         .iter()
-        .map(|field| raw_type_from_schema(&field.schema, types));
+        .map(|(_, field)| raw_type_from_schema(&field.schema, types));
 
     quote! {
         #(
@@ -350,18 +641,93 @@ pub fn raw_struct_fields(fields: &[Field<'_>], types: &TypeMap) -> TokenStream {
     }
 }
 
-fn quote_binding_args<'a>(inputs: &[FnArg], types: &TypeMap<'_>) -> Punctuated<TokenStream, Comma> {
+/// Generates the statements that initialize the fields of a C# type from the raw
+/// struct `raw`, for use in a constructor of the C# type.
+///
+/// Zero-sized fields aren't included in the raw struct, so they're initialized with
+/// their default value instead.
+pub fn quote_fields_from_raw(
+    fields: &[Field<'_>],
+    renamed: &[Cow<'static, str>],
+    types: &TypeMap,
+) -> TokenStream {
+    let bindings = bindings_class_ident();
+    let from_raw = from_raw_fn_ident();
+
+    let statements = fields.iter().enumerate().map(|(index, field)| {
+        let ident = strukt::field_ident(field.name, index, renamed);
+        if generate::is_zero_sized_schema(&field.schema, types) {
+            quote! { this.#ident = default; }
+        } else {
+            quote! { #bindings.#from_raw(raw.#ident, out this.#ident); }
+        }
+    });
+
+    quote! { #( #statements )* }
+}
+
+/// Generates the statements that initialize the fields of a raw struct from the C#
+/// value `source`, for use in a constructor of the raw struct.
+///
+/// Zero-sized fields aren't included in the raw struct, so they're skipped.
+pub fn quote_fields_into_raw(
+    fields: &[Field<'_>],
+    renamed: &[Cow<'static, str>],
+    source: &TokenStream,
+    types: &TypeMap,
+) -> TokenStream {
+    let bindings = bindings_class_ident();
+    let into_raw = into_raw_fn_ident();
+
+    let statements = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !generate::is_zero_sized_schema(&field.schema, types))
+        .map(|(index, field)| {
+            let ident = strukt::field_ident(field.name, index, renamed);
+            quote! { #bindings.#into_raw(#source.#ident, out this.#ident); }
+        });
+
+    quote! { #( #statements )* }
+}
+
+pub fn quote_binding_args<'a>(
+    inputs: &[FnArg],
+    types: &TypeMap<'_>,
+) -> Punctuated<TokenStream, Comma> {
+    // NOTE: Zero-sized arguments are ignored by the C ABI, so they're left out of the
+    // binding.
     inputs
         .iter()
+        .filter(|arg| !generate::is_zero_sized(&arg.repr, types))
         .map(|arg| {
-            let ident = format_ident!("{}", &*arg.name);
+            // NOTE: The names of the raw arguments are only for readability, but they still
+            // need to be escaped if they happen to be a C# keyword.
+            let ident = generate::func::quote_arg_ident(&arg.name);
             let ty = raw_type_from_repr(&arg.repr, types);
             quote! { #ty #ident }
         })
         .collect()
 }
 
-fn quote_raw_fn_binding(
+/// Returns the identifier of the variable used to receive panic messages from the
+/// raw binding functions.
+pub fn panic_ident() -> Ident {
+    format_ident!("__panic")
+}
+
+/// Quotes the trailing argument to the raw binding for an exported function or
+/// method.
+///
+/// If the Rust function panics, the panic message is written to this argument
+/// instead of unwinding across the FFI boundary. See `quote_wrapper_body` for the
+/// corresponding logic for checking the panic message.
+pub fn quote_panic_arg() -> TokenStream {
+    let panic = panic_ident();
+    quote! { ref RawVec #panic }
+}
+
+pub fn quote_raw_fn_binding(
     entry_point: &str,
     return_ty: TokenStream,
     args: TokenStream,
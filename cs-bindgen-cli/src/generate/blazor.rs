@@ -0,0 +1,443 @@
+//! Bindings for Blazor WebAssembly, selected with `--blazor`.
+//!
+//! Blazor apps run in the browser, where the library can't be loaded as a native
+//! library. Instead, the bindings call the Wasm build of the library through a
+//! generated JavaScript module, using .NET 7's `[JSImport]` interop. The bindings are
+//! generated as usual and then each `[DllImport]` declaration is lowered to a method
+//! with the same signature, so the rest of the generated code (and the public API) is
+//! unchanged:
+//!
+//! * Functions that only pass numbers, e.g. primitives and handles, call a
+//!   `[JSImport]` binding to a function in the JavaScript module, which forwards the
+//!   call to the Wasm module. Panics are reported by the JavaScript function throwing
+//!   an error, which is converted back into a `RustPanicException`.
+//! * Any other function throws a `PlatformNotSupportedException`. The Wasm module has
+//!   its own linear memory, so values that are passed through memory (strings,
+//!   collections, structs) can't be shared with .NET, and neither can C# function
+//!   pointers (callbacks, logging, and allocator hooks).
+//!
+//! The JavaScript module is written to the output directory as `<dll>.interop.js`,
+//! and has to be loaded with `RustModule.InitializeAsync` before any of the bindings
+//! are used.
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, TokenStream, TokenTree};
+use quote::*;
+use std::fmt::Write;
+
+/// The prefix of the message of the JavaScript error thrown when Rust panics, which
+/// distinguishes panics from other JavaScript errors.
+const PANIC_PREFIX: &str = "Rust panic: ";
+
+/// Runtime functions that exchange pointers to .NET memory or C# function pointers,
+/// neither of which can be passed to the Wasm module.
+const HOST_ONLY_FNS: &[&str] = &[
+    "__cs_bindgen_alloc_buffer",
+    "__cs_bindgen_free_buffer",
+    "__cs_bindgen_alloc",
+    "__cs_bindgen_free",
+    "__cs_bindgen_set_allocator",
+    "__cs_bindgen_set_log_handler",
+    "__cs_bindgen_set_error_hook",
+];
+
+/// A raw binding that was lowered to a `[JSImport]`, which the JavaScript module needs
+/// to export.
+pub struct JsImport {
+    /// The name of the exported Wasm function.
+    entry_point: String,
+
+    /// Whether the binding takes the panic message as its last argument.
+    catches_panic: bool,
+}
+
+/// How a raw argument or return value is passed through `[JSImport]`, which only
+/// supports some of the numeric types.
+#[derive(Clone, Copy)]
+enum Marshal {
+    /// The type is supported as-is.
+    Direct,
+
+    /// Other 32-bit and smaller integers are passed as an `int`, which the Wasm function
+    /// reinterprets as the original type.
+    Int,
+
+    /// 64-bit integers are passed as a `BigInt`, which is what Wasm uses for `i64`.
+    BigInt,
+
+    /// `UIntPtr` is passed as an `IntPtr`.
+    Pointer,
+}
+
+impl Marshal {
+    fn for_type(ty: &str) -> Option<Marshal> {
+        match ty {
+            "byte" | "short" | "int" | "float" | "double" | "IntPtr" => Some(Marshal::Direct),
+            "sbyte" | "ushort" | "uint" => Some(Marshal::Int),
+            "long" | "ulong" => Some(Marshal::BigInt),
+            "UIntPtr" => Some(Marshal::Pointer),
+            _ => None,
+        }
+    }
+
+    fn quote_js_type(self, ty: &Ident) -> TokenStream {
+        match self {
+            Marshal::Direct => ty.to_token_stream(),
+            Marshal::Int => quote! { int },
+            Marshal::BigInt => quote! { long },
+            Marshal::Pointer => quote! { IntPtr },
+        }
+    }
+
+    fn quote_attribute(self, target: TokenStream) -> TokenStream {
+        match self {
+            Marshal::BigInt => quote! { [#target JSMarshalAs<JSType.BigInt>] },
+            _ => quote! {},
+        }
+    }
+
+    /// Converts a raw value into the type passed to JavaScript.
+    fn quote_into_js(self, value: TokenStream) -> TokenStream {
+        match self {
+            Marshal::Direct => value,
+            Marshal::Int => quote! { unchecked((int)#value) },
+            Marshal::BigInt => quote! { unchecked((long)#value) },
+            Marshal::Pointer => quote! { (IntPtr)(void*)#value },
+        }
+    }
+
+    /// Converts a value returned from JavaScript back into the raw type.
+    fn quote_from_js(self, ty: &Ident, value: TokenStream) -> TokenStream {
+        match self {
+            Marshal::Direct => value,
+            Marshal::Int | Marshal::BigInt => quote! { unchecked((#ty)#value) },
+            Marshal::Pointer => quote! { (UIntPtr)(void*)#value },
+        }
+    }
+}
+
+/// Generates the public class that loads the JavaScript module, and the helpers used
+/// by the lowered bindings to report panics.
+pub fn quote_module_class(dll_name: &str) -> TokenStream {
+    quote! {
+        public static partial class RustModule
+        {
+            private const string ModuleName = #dll_name;
+            private const string PanicPrefix = #PANIC_PREFIX;
+
+            [JSImport("init", ModuleName)]
+            private static partial Task __init(string wasmUrl);
+
+            // Loads the JavaScript module from `moduleUrl` and instantiates the Wasm
+            // module, which defaults to the `.wasm` file next to the JavaScript module.
+            // Must complete before any of the bindings are used.
+            public static async Task InitializeAsync(string moduleUrl, string wasmUrl = null)
+            {
+                await JSHost.ImportAsync(ModuleName, moduleUrl);
+                await __init(wasmUrl);
+            }
+
+            internal static bool IsPanic(JSException e)
+            {
+                return e.Message.StartsWith(PanicPrefix, StringComparison.Ordinal);
+            }
+
+            internal static RustPanicException ToPanic(JSException e)
+            {
+                return new RustPanicException(e.Message.Substring(PanicPrefix.Length));
+            }
+        }
+    }
+}
+
+/// Replaces each `[DllImport]` declaration in `tokens` with a method that calls the
+/// JavaScript module, or that throws if the function can't be called from JavaScript.
+///
+/// The functions that the JavaScript module needs to export are added to `imports`.
+pub fn lower_imports(
+    tokens: TokenStream,
+    dll_name: &str,
+    imports: &mut Vec<JsImport>,
+) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut lowered = TokenStream::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        if let Some((declaration, len)) = parse_import(&tokens[index..]) {
+            lowered.extend(lower_import(&declaration, dll_name, imports));
+            index += len;
+            continue;
+        }
+
+        match &tokens[index] {
+            TokenTree::Group(group) => {
+                let mut lowered_group = Group::new(
+                    group.delimiter(),
+                    lower_imports(group.stream(), dll_name, imports),
+                );
+                lowered_group.set_span(group.span());
+                lowered.extend(Some(TokenTree::Group(lowered_group)));
+            }
+
+            token => lowered.extend(Some(token.clone())),
+        }
+
+        index += 1;
+    }
+
+    lowered
+}
+
+/// A `[DllImport]` declaration, e.g.
+/// `[DllImport(...)] internal static extern int foo(int a, ref RawVec __panic);`.
+struct Import {
+    entry_point: String,
+    modifiers: Vec<TokenTree>,
+    return_ty: Vec<TokenTree>,
+    name: Ident,
+    params: Vec<Vec<TokenTree>>,
+}
+
+/// Parses the `[DllImport]` declaration at the start of `tokens`, returning the
+/// declaration and the number of tokens it spans.
+fn parse_import(tokens: &[TokenTree]) -> Option<(Import, usize)> {
+    let attribute = match tokens.first()? {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => group,
+        _ => return None,
+    };
+    let attribute = attribute.stream().into_iter().collect::<Vec<_>>();
+    match attribute.first()? {
+        TokenTree::Ident(ident) if ident == "DllImport" => {}
+        _ => return None,
+    }
+
+    // The modifiers end with `extern`, and the name is the identifier before the
+    // parameter list.
+    let extern_index = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident == "extern"))?;
+    let params_index = tokens.iter().position(
+        |token| matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis),
+    )?;
+    let name = match &tokens[params_index - 1] {
+        TokenTree::Ident(ident) => ident.clone(),
+        _ => return None,
+    };
+    let params = match &tokens[params_index] {
+        TokenTree::Group(group) => split_params(group.stream()),
+        _ => unreachable!(),
+    };
+
+    let entry_point = entry_point(&attribute).unwrap_or_else(|| name.to_string());
+    let import = Import {
+        entry_point,
+        modifiers: tokens[1..extern_index].to_vec(),
+        return_ty: tokens[extern_index + 1..params_index - 1].to_vec(),
+        name,
+        params,
+    };
+
+    // Skip the trailing semicolon.
+    Some((import, params_index + 2))
+}
+
+/// Returns the value of the `EntryPoint` argument of the attribute, if specified.
+fn entry_point(attribute: &[TokenTree]) -> Option<String> {
+    let args = match attribute.get(1)? {
+        TokenTree::Group(group) => group.stream().into_iter().collect::<Vec<_>>(),
+        _ => return None,
+    };
+    let index = args
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident == "EntryPoint"))?;
+    match args.get(index + 2)? {
+        TokenTree::Literal(literal) => Some(literal.to_string().trim_matches('"').into()),
+        _ => None,
+    }
+}
+
+fn split_params(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut params = vec![Vec::new()];
+    for token in stream {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => params.push(Vec::new()),
+            _ => params.last_mut().unwrap().push(token),
+        }
+    }
+
+    params.retain(|param| !param.is_empty());
+    params
+}
+
+fn lower_import(import: &Import, dll_name: &str, imports: &mut Vec<JsImport>) -> TokenStream {
+    let modifiers = &import.modifiers;
+    let return_ty = &import.return_ty;
+    let name = &import.name;
+    let params = import
+        .params
+        .iter()
+        .map(|param| param.iter().cloned().collect::<TokenStream>());
+    let declaration = quote! { #( #modifiers )* #( #return_ty )* #name(#( #params ),*) };
+
+    let signature = if HOST_ONLY_FNS.contains(&&*import.entry_point) {
+        None
+    } else {
+        js_signature(import)
+    };
+    let (args, catches_panic) = match signature {
+        Some(signature) => signature,
+        None => {
+            let message = format!(
+                "`{}` can't be called from Blazor WebAssembly, since its arguments or \
+                 return value can't be passed to the Wasm module",
+                import.entry_point,
+            );
+            return quote! {
+                #declaration
+                {
+                    throw new PlatformNotSupportedException(#message);
+                }
+            };
+        }
+    };
+
+    if !imports
+        .iter()
+        .any(|other| other.entry_point == import.entry_point)
+    {
+        imports.push(JsImport {
+            entry_point: import.entry_point.clone(),
+            catches_panic,
+        });
+    }
+
+    let js_fn = format_ident!("__js_{}", name);
+    let js_params = args.iter().map(|(ty, name, marshal)| {
+        let attribute = marshal.quote_attribute(quote! {});
+        let js_ty = marshal.quote_js_type(ty);
+        quote! { #attribute #js_ty #name }
+    });
+    let js_args = args
+        .iter()
+        .map(|(_, name, marshal)| marshal.quote_into_js(name.to_token_stream()));
+    let call = quote! { #js_fn(#( #js_args ),*) };
+
+    let (js_return_ty, return_attribute, body) = match return_ty.as_slice() {
+        [TokenTree::Ident(ty)] if ty == "void" => (quote! { void }, quote! {}, quote! { #call; }),
+        [TokenTree::Ident(ty)] => {
+            let marshal = Marshal::for_type(&ty.to_string()).unwrap();
+            let value = marshal.quote_from_js(ty, call);
+            (
+                marshal.quote_js_type(ty),
+                marshal.quote_attribute(quote! { return: }),
+                quote! { return #value; },
+            )
+        }
+        _ => unreachable!(),
+    };
+
+    let entry_point = Literal::string(&import.entry_point);
+    quote! {
+        [JSImport(#entry_point, #dll_name)]
+        #return_attribute
+        private static partial #js_return_ty #js_fn(#( #js_params ),*);
+
+        #declaration
+        {
+            try
+            {
+                #body
+            }
+            catch (JSException e) when (RustModule.IsPanic(e))
+            {
+                throw RustModule.ToPanic(e);
+            }
+        }
+    }
+}
+
+/// Returns the arguments that are passed to JavaScript, and whether the function takes
+/// the panic message as its last argument, or `None` if the function can't be called
+/// from JavaScript.
+fn js_signature(import: &Import) -> Option<(Vec<(Ident, Ident, Marshal)>, bool)> {
+    match import.return_ty.as_slice() {
+        [TokenTree::Ident(ty)] if ty == "void" || Marshal::for_type(&ty.to_string()).is_some() => {}
+        _ => return None,
+    }
+
+    let mut args = Vec::new();
+    let mut catches_panic = false;
+    for (index, param) in import.params.iter().enumerate() {
+        match param.as_slice() {
+            // The panic message is always the last argument. It's reported by throwing
+            // instead, so it's not passed to JavaScript.
+            [TokenTree::Ident(modifier), TokenTree::Ident(ty), TokenTree::Ident(_)]
+                if modifier == "ref" && ty == "RawVec" && index == import.params.len() - 1 =>
+            {
+                catches_panic = true;
+            }
+
+            [TokenTree::Ident(ty), TokenTree::Ident(name)] => {
+                let marshal = Marshal::for_type(&ty.to_string())?;
+                args.push((ty.clone(), name.clone(), marshal));
+            }
+
+            _ => return None,
+        }
+    }
+
+    Some((args, catches_panic))
+}
+
+/// Generates the JavaScript module that forwards the `[JSImport]` bindings to the Wasm
+/// module.
+pub fn generate_js(dll_name: &str, imports: &[JsImport]) -> String {
+    let mut js = JS_PRELUDE
+        .replace("{wasm_file}", &format!("{}.wasm", dll_name))
+        .replace("{panic_prefix}", PANIC_PREFIX);
+    for import in imports {
+        let call = if import.catches_panic {
+            format!("call(wasm.{}, ...args)", import.entry_point)
+        } else {
+            format!("wasm.{}(...args)", import.entry_point)
+        };
+        writeln!(
+            js,
+            "export function {}(...args) {{\n    return {};\n}}\n",
+            import.entry_point, call
+        )
+        .unwrap();
+    }
+
+    js
+}
+
+const JS_PRELUDE: &str = "\
+let wasm;
+const decoder = new TextDecoder();
+
+export async function init(wasmUrl) {
+    const url = wasmUrl ?? new URL('{wasm_file}', import.meta.url);
+    const { instance } = await WebAssembly.instantiateStreaming(fetch(url), {});
+    wasm = instance.exports;
+}
+
+// Invokes a raw binding function, throwing an error if it panics.
+function call(binding, ...args) {
+    // The panic message is written to a `RawVec<u8>`, which is 3 pointers in size.
+    const panic = wasm.__cs_bindgen_alloc_buffer(12);
+    new Uint32Array(wasm.memory.buffer, panic, 3).fill(0);
+    try {
+        const result = binding(...args, panic);
+        const [ptr, len] = new Uint32Array(wasm.memory.buffer, panic, 2);
+        if (ptr !== 0) {
+            const message = decoder.decode(new Uint8Array(wasm.memory.buffer, ptr, len));
+            wasm.__cs_bindgen_drop_vec_u8(panic);
+            throw new Error('{panic_prefix}' + message);
+        }
+        return result;
+    } finally {
+        wasm.__cs_bindgen_free_buffer(panic, 12);
+    }
+}
+
+";
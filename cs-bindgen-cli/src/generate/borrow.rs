@@ -0,0 +1,144 @@
+//! Code generation for borrowed return values.
+//!
+//! Methods can return references that borrow from `self`, i.e. a `&T` for a handle
+//! type or a `&[T]`. Rather than copying the borrowed value, C# gets a view of the
+//! value that holds on to the handle of the object that owns it:
+//!
+//! * A `&T` for a handle type is returned as an instance of the C# class for `T`
+//!   that doesn't own its handle, so disposing it doesn't drop the Rust value.
+//! * A `&[T]` is returned as an `IReadOnlyList<T>` that reads the elements from the
+//!   Rust slice as they're accessed, using a single generic `BorrowedSlice<R, T>`
+//!   class. We only need to generate the conversion that creates the view for each
+//!   element type.
+//!
+//! Holding the owner's handle keeps the owner from being finalized while the view is
+//! reachable, and the view checks the handle before each access so that it's
+//! invalidated once the owner is disposed. Calling a `&mut self` method on the owner
+//! also invalidates its views, since the borrowed value may have been moved or
+//! dropped, which is tracked by the generation of the owner's `RustHandle`.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::{schematic::Schema, BindingStyle, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns `true` if `output` is a borrowed return value.
+///
+/// Free functions can only return `'static` slices, which don't borrow from anything,
+/// so they're copied into a regular collection instead of being returned as a view.
+pub fn is_borrowed(output: &Repr, is_method: bool) -> bool {
+    match output {
+        Repr::Ref(_) => true,
+        Repr::Slice(_) => is_method,
+        _ => false,
+    }
+}
+
+/// Returns the name of the conversion functions used to create views of borrowed
+/// values.
+pub fn from_borrowed_fn_ident() -> Ident {
+    format_ident!("__FromBorrowed")
+}
+
+/// Quotes the expression for the handle that owns a value borrowed from `this`.
+pub fn quote_owner() -> TokenStream {
+    quote! { this._owningHandle }
+}
+
+/// Quotes the C# type for a borrowed slice with the specified element type.
+pub fn quote_cs_type(element: &Repr, types: &TypeMap) -> TokenStream {
+    let element = generate::quote_cs_type_for_repr(element, types);
+    quote! { IReadOnlyList<#element> }
+}
+
+/// Generates the conversion function for a borrowed slice with the specified element
+/// type.
+///
+/// The elements are converted from the Rust slice each time they're accessed, so only
+/// elements whose raw representation doesn't own any data are supported. Primitives
+/// are read directly from the slice, and types marshaled by value are read with the
+/// type's index function.
+pub fn quote_slice_conversion(element: &Repr, types: &TypeMap) -> TokenStream {
+    let cs_ty = quote_cs_type(element, types);
+    let element_ty = generate::quote_cs_type_for_repr(element, types);
+    let raw_element_ty = binding::raw_type_from_repr(element, types);
+    let from_raw = binding::from_raw_fn_ident();
+    let from_borrowed = from_borrowed_fn_ident();
+
+    let index_fn = match element {
+        Repr::Bool
+        | Repr::Char
+        | Repr::I8
+        | Repr::I16
+        | Repr::I32
+        | Repr::I64
+        | Repr::ISize
+        | Repr::U8
+        | Repr::U16
+        | Repr::U32
+        | Repr::U64
+        | Repr::USize
+        | Repr::F32
+        | Repr::F64 => quote! {
+            (slice, index) => slice.Get<#raw_element_ty>(index)
+        },
+
+        Repr::Named(type_name) => {
+            let export = types
+                .get(type_name)
+                .unwrap_or_else(|| panic!("Could not resolve type reference: {:?}", type_name));
+
+            // NOTE: Data-carrying enums aren't required to implement `Copy`, so their raw
+            // representation may own data that would be freed when the element is
+            // converted.
+            match &export.binding_style {
+                BindingStyle::Value(Schema::Enum(schema)) if schema.has_data() => panic!(
+                    "Borrowed slices of data-carrying enums are not supported: {:?}",
+                    type_name,
+                ),
+
+                BindingStyle::Value(_) => {
+                    let index_fn = format_ident!("{}", &*export.index_fn);
+                    quote! { #index_fn }
+                }
+
+                BindingStyle::Handle => panic!(
+                    "Borrowed slices of handle types are not supported: {:?}",
+                    type_name,
+                ),
+
+                // NOTE: The raw representation of a marshaled type is a newly-allocated
+                // buffer, so the elements can't be read without copying them.
+                BindingStyle::Marshal(..) => panic!(
+                    "Borrowed slices of marshaled types are not supported: {:?}",
+                    type_name,
+                ),
+
+                // NOTE: Custom conversions take ownership of the raw value, so the
+                // elements can't be read in place.
+                BindingStyle::Custom(_) => panic!(
+                    "Borrowed slices of types with custom conversions are not supported: {:?}",
+                    type_name,
+                ),
+
+                BindingStyle::Transparent(_) => panic!(
+                    "Borrowed slices of transparent newtypes are not supported: {:?}",
+                    type_name,
+                ),
+            }
+        }
+
+        _ => panic!(
+            "Borrowed slices are not supported for element type {:?}",
+            element,
+        ),
+    };
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_borrowed(RawSlice raw, RustHandle owner, out #cs_ty result)
+        {
+            result = new BorrowedSlice<#raw_element_ty, #element_ty>(raw, owner, #index_fn, #from_raw);
+        }
+    })
+}
@@ -0,0 +1,65 @@
+//! Code generation for boxed values.
+//!
+//! On the Rust side, a `Box<T>` is marshaled as a pointer to a buffer holding the raw
+//! value of the `T`, which gives recursive types (e.g. an enum with a `Box<Self>`
+//! field) a finite raw representation. The buffer is allocated with
+//! `__cs_bindgen_alloc_buffer`, and whichever side receives the box frees it with
+//! `__cs_bindgen_free_buffer` after moving the raw value out of it.
+//!
+//! The box itself is an implementation detail of the Rust type, so boxed values are
+//! exposed in C# as the boxed type. Since P/Invoke doesn't support generic structs, we
+//! generate a dedicated raw struct for each boxed type used in the exported API, along
+//! with the `__FromRaw` and `__IntoRaw` overloads that move the raw value in and out of
+//! the buffer. Having a distinct raw struct (rather than passing an `IntPtr`) keeps
+//! these overloads from conflicting with the ones for handle types.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the identifier of the raw struct generated for a `Box` containing values of
+/// the specified type.
+pub fn raw_box_ident(inner: &Repr) -> Ident {
+    format_ident!("__RawBox__{}", generate::mangle_repr(inner))
+}
+
+/// Generates the raw struct and conversion functions for a `Box` containing the
+/// specified type.
+pub fn quote_raw_box(inner: &Repr, types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_box_ident(inner);
+    let raw_value_ty = binding::raw_type_from_repr(inner, types);
+    let cs_ty = generate::quote_cs_type_for_repr(inner, types);
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ident raw, out #cs_ty result)
+        {
+            var value = *(#raw_value_ty*)raw.Ptr;
+            __cs_bindgen_free_buffer(raw.Ptr, (UIntPtr)sizeof(#raw_value_ty));
+            #from_raw(value, out result);
+        }
+
+        internal static void #into_raw(#cs_ty value, out #raw_ident result)
+        {
+            var ptr = __cs_bindgen_alloc_buffer((UIntPtr)sizeof(#raw_value_ty));
+            #into_raw(value, out *(#raw_value_ty*)ptr);
+            result = new #raw_ident { Ptr = ptr };
+        }
+    });
+
+    quote! {
+        // Generate the raw struct for the boxed type. This needs to match the pointer to
+        // the raw value on the Rust side.
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct #raw_ident
+        {
+            public IntPtr Ptr;
+        }
+
+        #raw_conversions
+    }
+}
@@ -0,0 +1,159 @@
+//! Code generation for callback arguments.
+//!
+//! Exported functions can take callbacks as arguments (e.g. `impl Fn(i32) -> bool`),
+//! which are exposed in C# as an `Action<..>` or `Func<..>` delegate. The delegate
+//! is passed to Rust as a `RawCallback`, which is a function pointer paired with a
+//! context pointer:
+//!
+//! * The function pointer points to a static "trampoline" function generated for
+//!   each callback signature. The trampoline converts the raw arguments into their
+//!   C# representation, invokes the delegate, and converts the return value back
+//!   into its raw representation.
+//! * The context pointer is a `GCHandle` to a `CallbackContext` object that holds
+//!   the delegate. The handle keeps the delegate alive for the duration of the call,
+//!   and is freed once the call returns.
+//!
+//! Using a static trampoline (rather than getting a function pointer directly for
+//! the user's delegate) means that the function pointer is valid for the lifetime
+//! of the program, and allows the trampolines to be marked with
+//! `[MonoPInvokeCallback]` so that they work in AOT-compiled environments like
+//! Unity's IL2CPP.
+//!
+//! If the delegate throws an exception, the trampoline stores the exception in the
+//! callback context and reports the failure to Rust, which panics. The generated
+//! wrapper function then rethrows the original exception instead of the panic.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the suffix used to generate the names of the items generated for a
+/// callback signature.
+fn mangle_signature(inputs: &[Repr], output: &Repr) -> String {
+    inputs
+        .iter()
+        .chain(Some(output))
+        .map(generate::mangle_repr)
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+/// Returns the identifier of the raw delegate type generated for a callback with the
+/// specified signature.
+pub fn raw_callback_ident(inputs: &[Repr], output: &Repr) -> Ident {
+    format_ident!("__RawCallback__{}", mangle_signature(inputs, output))
+}
+
+/// Returns the identifier of the field holding the function pointer for the
+/// trampoline generated for the specified callback signature.
+pub fn callback_ptr_ident(inputs: &[Repr], output: &Repr) -> Ident {
+    format_ident!("__CallbackPtr__{}", mangle_signature(inputs, output))
+}
+
+/// Quotes the idiomatic C# delegate type for a callback with the specified signature.
+pub fn quote_cs_type(inputs: &[Repr], output: &Repr, types: &TypeMap) -> TokenStream {
+    let input_ty = inputs
+        .iter()
+        .map(|input| generate::quote_cs_type_for_repr(input, types))
+        .collect::<Vec<_>>();
+
+    if *output == Repr::Unit {
+        if input_ty.is_empty() {
+            quote! { Action }
+        } else {
+            quote! { Action<#( #input_ty ),*> }
+        }
+    } else {
+        let output_ty = generate::quote_cs_type_for_repr(output, types);
+        quote! { Func<#( #input_ty, )* #output_ty> }
+    }
+}
+
+/// Generates the raw delegate type and trampoline function for a callback with the
+/// specified signature.
+pub fn quote_raw_callback(inputs: &[Repr], output: &Repr, types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_callback_ident(inputs, output);
+    let ptr_ident = callback_ptr_ident(inputs, output);
+    let delegate_ident = format_ident!("__Callback__{}", mangle_signature(inputs, output));
+    let invoke_ident = format_ident!("__Invoke__{}", mangle_signature(inputs, output));
+    let cs_ty = quote_cs_type(inputs, output, types);
+    let raw_output_ty = binding::raw_type_from_repr(output, types);
+
+    let raw_arg = (0..inputs.len())
+        .map(|index| format_ident!("arg{}", index))
+        .collect::<Vec<_>>();
+    let value = (0..inputs.len())
+        .map(|index| format_ident!("value{}", index))
+        .collect::<Vec<_>>();
+    let input_ty = inputs
+        .iter()
+        .map(|input| generate::quote_cs_type_for_repr(input, types));
+
+    // Build the full parameter list for the trampoline, which includes the context
+    // pointer and the out parameter for reporting exceptions.
+    let mut params = vec![quote! { IntPtr context }];
+    params.extend(inputs.iter().zip(&raw_arg).map(|(input, raw_arg)| {
+        let raw_ty = binding::raw_type_from_repr(input, types);
+        quote! { #raw_ty #raw_arg }
+    }));
+    params.push(quote! { out byte threw });
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    // NOTE: The unit type doesn't have an `__IntoRaw` overload, so we directly return
+    // the raw unit value in that case.
+    let invoke = if *output == Repr::Unit {
+        quote! {
+            callback(#( #value ),*);
+            return 0;
+        }
+    } else {
+        quote! {
+            var result = callback(#( #value ),*);
+            #into_raw(result, out #raw_output_ty rawResult);
+            return rawResult;
+        }
+    };
+
+    let trampoline = binding::wrap_bindings(quote! {
+        // NOTE: The delegate is stored in a static field so that it's never garbage
+        // collected, which keeps the function pointer valid.
+        internal static readonly #raw_ident #delegate_ident = #invoke_ident;
+        internal static readonly IntPtr #ptr_ident = Marshal.GetFunctionPointerForDelegate(#delegate_ident);
+
+        [MonoPInvokeCallback(typeof(#raw_ident))]
+        internal static #raw_output_ty #invoke_ident(#( #params ),*)
+        {
+            threw = 0;
+            var callbackContext = CallbackContext.FromHandle(context);
+
+            try
+            {
+                #(
+                    #from_raw(#raw_arg, out #input_ty #value);
+                )*
+
+                var callback = (#cs_ty)callbackContext.Callback;
+                #invoke
+            }
+            catch (Exception e)
+            {
+                callbackContext.Exception = e;
+                threw = 1;
+                return default(#raw_output_ty);
+            }
+        }
+    });
+
+    quote! {
+        // Generate the raw delegate type for the callback signature. This needs to match
+        // the function signature expected by `RawCallback` on the Rust side.
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate #raw_output_ty #raw_ident(#( #params ),*);
+
+        #trampoline
+    }
+}
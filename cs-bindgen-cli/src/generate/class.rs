@@ -1,13 +1,23 @@
 //! Code generation for exported named types that are marshaled as handles.
 
-use crate::generate::{binding, func, TypeMap, TypeNameExt};
-use cs_bindgen_shared::{BindingStyle, Method, NamedType, Repr};
+use crate::generate::{
+    self, batch, binding, borrow, comparison, docs, equality, func, TypeMap, TypeNameExt,
+};
+use cs_bindgen_shared::{
+    schematic::{Schema, TypeName},
+    BindingStyle, Export, FnArg, Method, NamedType, ReceiverStyle, Repr,
+};
+use heck::*;
 use proc_macro2::TokenStream;
 use quote::*;
+use syn::Ident;
 
 pub fn quote_drop_fn(export: &NamedType, dll_name: &str) -> TokenStream {
-    let binding_ident = format_ident!("__cs_bindgen_drop__{}", export.type_name.name);
-    let entry_point = binding_ident.to_string();
+    let binding_ident = drop_fn_ident(export);
+    let entry_point = export
+        .drop_fn
+        .as_ref()
+        .expect("Handle type must have a drop function");
     quote! {
         [DllImport(
             #dll_name,
@@ -17,53 +27,355 @@ pub fn quote_drop_fn(export: &NamedType, dll_name: &str) -> TokenStream {
     }
 }
 
+/// Returns the name of the raw binding for the drop function of a handle type.
+///
+/// NOTE: The name is based on the C# name of the type, which may not match the name
+/// of the exported function if the type was renamed. The raw binding sets its entry
+/// point to the exported function, so the two don't need to match.
+fn drop_fn_ident(export: &NamedType) -> Ident {
+    format_ident!("__cs_bindgen_drop__{}", export.type_name.mangled_name())
+}
+
 /// Quotes the pointer type used for handles, i.e. `IntPtr`.
 pub fn quote_handle_ptr() -> TokenStream {
     quote! { IntPtr }
 }
 
-pub fn quote_handle_type(export: &NamedType) -> TokenStream {
+/// Generates the C# class for a type that's marshaled as a handle.
+///
+/// The raw pointer is owned by a `SafeHandle` subclass, which guarantees that the drop
+/// function is called exactly once: Either when the object is disposed, or by the
+/// finalizer if the object is never disposed. Passing the object to Rust by value
+/// transfers ownership of the pointer, after which the object is treated as disposed.
+///
+/// If `thread_checks` is set and the type isn't `Sync`, the object also records the
+/// thread that created it and throws if the handle is accessed from any other thread.
+///
+/// Objects created for a `&T` returned from a method borrow the value owned by another
+/// object. See [the `borrow` module](../borrow/index.html) for more.
+pub fn quote_handle_type(export: &NamedType, thread_checks: bool, types: &TypeMap) -> TokenStream {
     let ident = export.type_name.ident();
-    let drop_fn = format_ident!("__cs_bindgen_drop__{}", export.type_name.name);
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let drop_fn = drop_fn_ident(export);
     let raw_repr = quote_handle_ptr();
 
     let from_raw = binding::from_raw_fn_ident();
     let into_raw = binding::into_raw_fn_ident();
 
+    let (owner_thread, check_thread) = if thread_checks && !export.is_sync {
+        let message = format!(
+            "`{}` isn't thread-safe and can only be used from the thread that created it",
+            export.type_name.name,
+        );
+        (
+            Some(quote! {
+                private readonly int _ownerThread = Environment.CurrentManagedThreadId;
+            }),
+            Some(quote! {
+                if (Environment.CurrentManagedThreadId != _ownerThread)
+                {
+                    throw new InvalidOperationException(#message);
+                }
+            }),
+        )
+    } else {
+        (None, None)
+    };
+
+    let from_borrowed = borrow::from_borrowed_fn_ident();
+    let borrowed_message = format!(
+        "Can't take ownership of a borrowed `{}`",
+        export.type_name.name,
+    );
     let raw_conversions = binding::wrap_bindings(quote! {
-        internal static void #from_raw(#raw_repr raw, out #ident result)
+        internal static void #from_raw(#raw_repr raw, out #qualified_ident result)
         {
-            result = new #ident(raw);
+            result = new #qualified_ident(raw);
         }
 
-        internal static void #into_raw(#ident value, out #raw_repr result)
+        internal static void #from_borrowed(#raw_repr raw, RustHandle owner, out #qualified_ident result)
         {
-            result = value._handle;
+            result = new #qualified_ident(raw, owner);
         }
-    });
 
-    quote! {
-        public unsafe partial class #ident : IDisposable
+        internal static void #into_raw(#qualified_ident value, out #raw_repr result)
         {
-            internal IntPtr _handle;
+            result = value._TakeHandle();
+        }
+    });
 
-            internal #ident(#raw_repr raw)
+    let borrowed_mut_message = format!("Can't mutate a borrowed `{}`", export.type_name.name);
+
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+    let type_name = export.type_name.name.to_string();
+
+    let class = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            public unsafe partial class #ident : IDisposable
             {
-                _handle = raw;
+                internal RustHandle _safeHandle;
+                #owner_thread
+
+                // The handle of the object that owns the value, if this object borrows
+                // the value from another object, along with the generation of the handle
+                // when the value was borrowed.
+                internal RustHandle _owner;
+                private int _ownerGeneration;
+
+                // NOTE: Accessing the raw pointer after the object has been disposed (or
+                // after ownership has been transferred to Rust) throws instead of passing a
+                // dangling pointer to Rust. Borrowed objects are also invalidated once the
+                // object that owns the value is disposed or mutated.
+                internal IntPtr _handle
+                {
+                    get
+                    {
+                        if (_safeHandle == null || _safeHandle.IsClosed)
+                        {
+                            throw new ObjectDisposedException(GetType().Name);
+                        }
+
+                        if (_owner != null)
+                        {
+                            _owner.CheckBorrow(_ownerGeneration);
+                        }
+
+                        #check_thread
+
+                        return _safeHandle.DangerousGetHandle();
+                    }
+
+                    set
+                    {
+                        _safeHandle = new __Handle(value, true);
+                    }
+                }
+
+                // Borrowed objects are created from a `&T`, so they can't be used to call
+                // methods that take `&mut self`. Mutating the value may invalidate any
+                // values borrowed from it, so this also invalidates any existing views.
+                internal IntPtr _handleMut
+                {
+                    get
+                    {
+                        if (_owner != null)
+                        {
+                            throw new InvalidOperationException(#borrowed_mut_message);
+                        }
+
+                        var raw = _handle;
+                        _safeHandle.Generation += 1;
+                        return raw;
+                    }
+                }
+
+                // Transfers ownership of the raw pointer to Rust, after which the object
+                // is treated as disposed. Used when the object is passed by value, including
+                // as the receiver of a method that takes `self`.
+                internal IntPtr _TakeHandle()
+                {
+                    if (_owner != null)
+                    {
+                        throw new InvalidOperationException(#borrowed_message);
+                    }
+
+                    var raw = _handle;
+                    _safeHandle.SetHandleAsInvalid();
+                    return raw;
+                }
+
+                // The handle that keeps the value alive, which is held by any values
+                // borrowed from this object.
+                internal RustHandle _owningHandle
+                {
+                    get { return _owner ?? _safeHandle; }
+                }
+
+                internal #ident(#raw_repr raw)
+                {
+                    _handle = raw;
+                    RustDiagnostics.__Track(_safeHandle, #type_name);
+                }
+
+                internal #ident(#raw_repr raw, RustHandle owner)
+                {
+                    _safeHandle = new __Handle(raw, false);
+                    _owner = owner;
+                    _ownerGeneration = owner.Generation;
+                }
+
+                public void Dispose()
+                {
+                    if (_safeHandle != null)
+                    {
+                        _safeHandle.Dispose();
+                    }
+                }
+
+                internal sealed class __Handle : RustHandle
+                {
+                    internal __Handle(#raw_repr raw, bool ownsHandle) : base(ownsHandle)
+                    {
+                        SetHandle(raw);
+                    }
+
+                    protected override bool ReleaseHandle()
+                    {
+                        __bindings.#drop_fn(handle);
+                        return true;
+                    }
+                }
             }
+        },
+    );
+
+    quote! {
+        #class
 
-            public void Dispose()
+        #raw_conversions
+    }
+}
+
+/// Generates a parameterless constructor for a handle type that implements `Default`.
+///
+/// If the type already has a parameterless constructor (e.g. `fn new() -> Self`), no
+/// additional constructor is generated since the two would conflict.
+pub fn quote_default_constructor(
+    export: &NamedType,
+    exports: &[Export],
+    types: &TypeMap,
+) -> Option<TokenStream> {
+    let default_fn = export.default_fn.as_ref()?;
+
+    let has_parameterless_constructor = exports.iter().any(|item| match item {
+        Export::Method(method) => {
+            method.self_type == export.type_name
+                && method.is_constructor
+                && method.inputs.is_empty()
+        }
+        _ => false,
+    });
+    if has_parameterless_constructor {
+        return None;
+    }
+
+    let ident = export.type_name.ident();
+    let body = func::quote_wrapper_body(
+        default_fn,
+        None,
+        &[],
+        None,
+        Some(&quote! { this._handle }),
+        types,
+    );
+
+    Some(types.wrap_namespace(
+        &export.type_name,
+        quote! {
+        partial class #ident
+        {
+            public #ident()
             {
-                if (_handle != IntPtr.Zero)
+                unsafe
                 {
-                    __bindings.#drop_fn(_handle);
-                    _handle = IntPtr.Zero;
+                    #body
                 }
             }
         }
+        },
+    ))
+}
 
-        #raw_conversions
-    }
+/// Generates an override of `ToString` for a handle type that implements `Display`.
+pub fn quote_to_string(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let display_fn = export.display_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    let block = func::quote_wrapper_block(
+        display_fn,
+        Some(quote! { this._handle }),
+        &[],
+        Some(&Repr::String),
+        types,
+    );
+
+    Some(types.wrap_namespace(
+        &export.type_name,
+        quote! {
+        partial class #ident
+        {
+            public override string ToString()
+            #block
+        }
+        },
+    ))
+}
+
+/// Generates the equality members for a handle type that implements `PartialEq`.
+pub fn quote_equality(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let interface = equality::quote_interface(export)?;
+    let members = equality::quote_members(export, types)?;
+    let ident = export.type_name.ident();
+
+    Some(types.wrap_namespace(
+        &export.type_name,
+        quote! {
+        partial class #ident : #interface
+        {
+            #members
+        }
+        },
+    ))
+}
+
+/// Generates the comparison members for a handle type that implements `PartialOrd`.
+pub fn quote_comparison(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let interface = comparison::quote_interface(export)?;
+    let members = comparison::quote_members(export, types)?;
+    let ident = export.type_name.ident();
+
+    Some(types.wrap_namespace(
+        &export.type_name,
+        quote! {
+        partial class #ident : #interface
+        {
+            #members
+        }
+        },
+    ))
+}
+
+/// Generates a `Clone` method for a handle type that implements `Clone`.
+///
+/// The clone is a separate Rust value with its own handle, so it needs to be disposed
+/// independently of the original.
+pub fn quote_clone(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let clone_fn = export.clone_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    let block = func::quote_wrapper_block(
+        clone_fn,
+        Some(quote! { this._handle }),
+        &[],
+        Some(&Repr::Named(export.type_name.clone())),
+        types,
+    );
+
+    Some(types.wrap_namespace(
+        &export.type_name,
+        quote! {
+        partial class #ident : ICloneable
+        {
+            public #ident Clone()
+            #block
+
+            object ICloneable.Clone()
+            {
+                return Clone();
+            }
+        }
+        },
+    ))
 }
 
 pub fn quote_method_binding(item: &Method, types: &TypeMap) -> TokenStream {
@@ -74,24 +386,18 @@ pub fn quote_method_binding(item: &Method, types: &TypeMap) -> TokenStream {
     // Determine the name of the generated wrapper class based on the self type.
     let class_ident = item.self_type.ident();
 
-    // Use a heuristic to determine if the method should be treated as a constructor.
-    //
-    // TODO: Also support an explicit attribute to specify that a method should (or
-    // should not) be treated as a constructor.
-    let is_constructor =
-        item.receiver.is_none() && item.output == Some(Repr::Named(item.self_type.clone()));
-
     // Generate the right type of function for the exported method. There are three options:
     //
     // * A constructor.
     // * A non-static method.
     // * A static method.
-    let wrapper_fn = if is_constructor {
-        let args = func::quote_args(&item.inputs, types);
+    let wrapper_fn = if item.is_constructor {
+        let args = func::quote_optional_args(&item.inputs, types);
         let body = func::quote_wrapper_body(
             &item.binding,
             None,
             &item.inputs,
+            None,
             Some(&quote! { this._handle }),
             types,
         );
@@ -104,42 +410,88 @@ pub fn quote_method_binding(item: &Method, types: &TypeMap) -> TokenStream {
                 }
             }
         }
-    } else if let Some(_style) = &item.receiver {
-        // TODO: Correctly handle `self` receivers. `&self` and `&mut self` are handled
-        // correctly by passing the handle pointer directly, but in order to handle
-        // `self` we'll need some concept of "consuming" the handle. Likely this will
-        // meaning setting the handle to `null` after calling the function.
+    } else if let Some(chained) = quote_chained_method(item, self_type_export, types) {
+        chained
+    } else if let Some(style) = &item.receiver {
+        // `&self` and `&mut self` borrow the handle, whereas `self` transfers ownership
+        // of the value to Rust, which consumes the object.
+        let receiver = match style {
+            ReceiverStyle::Ref => quote! { this._handle },
+            ReceiverStyle::RefMut => quote! { this._handleMut },
+            ReceiverStyle::Move => quote! { this._TakeHandle() },
+        };
         func::quote_wrapper_fn(
-            &*item.name,
+            func::fn_ident(&item.name, item.rename.as_deref()),
             &*item.binding,
-            Some(quote! { this._handle }),
+            Some(receiver),
             &item.inputs,
             item.output.as_ref(),
+            &item.out_params,
             types,
         )
     } else {
         func::quote_wrapper_fn(
-            &*item.name,
+            func::fn_ident(&item.name, item.rename.as_deref()),
             &*item.binding,
             None,
             &item.inputs,
             item.output.as_ref(),
+            &item.out_params,
             types,
         )
     };
 
+    // Batched methods also get an overload for recording calls in a command buffer,
+    // which borrows the receiver the same way as the regular wrapper.
+    let record_fn = item.command.as_ref().map(|command| {
+        let receiver = item.receiver.as_ref().map(|style| match style {
+            ReceiverStyle::Ref => quote! { this._handle },
+            ReceiverStyle::RefMut => quote! { this._handleMut },
+            ReceiverStyle::Move => panic!(
+                "Batched method {}::{} can't take `self` by value",
+                item.self_type.name, item.name,
+            ),
+        });
+        batch::quote_record_fn(
+            func::fn_ident(&item.name, item.rename.as_deref()),
+            command,
+            receiver,
+            &item.inputs,
+            types,
+        )
+    });
+    let wrapper_fn = quote! {
+        #wrapper_fn
+        #record_fn
+    };
+
+    let docs = docs::quote_doc_comment(item.docs.as_deref());
+
     // Determine how to generate the method based on what type of item the self type is.
     match &self_type_export.binding_style {
         // For any type that's marshaled by handle we extend the generated class with a
         // partial class containing the method.
-        BindingStyle::Handle => {
+        BindingStyle::Handle => types.wrap_namespace(
+            &item.self_type,
             quote! {
                 partial class #class_ident
                 {
+                    #docs
                     #wrapper_fn
                 }
-            }
-        }
+            },
+        ),
+
+        // Static methods don't take the value, so they can be declared on any type
+        // regardless of how it's marshaled.
+        _ if item.receiver.is_none() && !item.is_constructor => quote_static_method(
+            self_type_export,
+            quote! {
+                #docs
+                #wrapper_fn
+            },
+            types,
+        ),
 
         // * For structs exported by value, we generate a partial struct containing the
         //   method.
@@ -147,6 +499,345 @@ pub fn quote_method_binding(item: &Method, types: &TypeMap) -> TokenStream {
         //   containing the method.
         // * For a C-like enum exported by value, we generate a partial static class with
         //   an extension method.
-        BindingStyle::Value(_) => todo!("Support methods on non-handle types"),
+        BindingStyle::Value(_)
+        | BindingStyle::Marshal(..)
+        | BindingStyle::Custom(_)
+        | BindingStyle::Transparent(_) => {
+            todo!("Support methods on non-handle types")
+        }
+    }
+}
+
+/// Declares a static method on a type that isn't marshaled as a handle.
+///
+/// Structs and marshaled types are extended with a partial declaration, as are
+/// data-carrying enums, whose wrapper class contains the variants. C-like enums, flags,
+/// and types that don't have a C# declaration of their own (i.e. transparent newtypes
+/// without a wrapper and types with custom conversions) can't contain methods, so the
+/// method is declared in a static `{Name}Extensions` class instead.
+fn quote_static_method(export: &NamedType, method: TokenStream, types: &TypeMap) -> TokenStream {
+    let ident = export.type_name.ident();
+    let declaration = match &export.binding_style {
+        BindingStyle::Value(Schema::Enum(schema)) if !export.is_flags && schema.has_data() => {
+            if types.uses_enum_classes(&export.type_name) {
+                quote! { partial class #ident }
+            } else {
+                quote! { static partial class #ident }
+            }
+        }
+
+        BindingStyle::Value(Schema::Enum(_)) => quote_extensions_class(export),
+        BindingStyle::Value(_) if export.is_flags => quote_extensions_class(export),
+        BindingStyle::Value(_) => quote! { partial struct #ident },
+        BindingStyle::Marshal(..) => quote! { partial class #ident },
+        BindingStyle::Transparent(transparent) if transparent.wrapper => {
+            quote! { partial struct #ident }
+        }
+        BindingStyle::Transparent(_) | BindingStyle::Custom(_) => quote_extensions_class(export),
+        BindingStyle::Handle => unreachable!("Handle types declare static methods directly"),
+    };
+
+    types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #declaration
+            {
+                #method
+            }
+        },
+    )
+}
+
+/// Quotes the declaration of the static class containing the methods of a type that
+/// can't contain methods itself.
+fn quote_extensions_class(export: &NamedType) -> TokenStream {
+    let ident = format_ident!("{}Extensions", &*export.type_name.name);
+    quote! { public static partial class #ident }
+}
+
+/// Generates a chainable method for a builder-style method on a handle type, i.e. one
+/// that returns `Self` from `self`, or `&Self`/`&mut Self` from `&self`/`&mut self`.
+///
+/// Methods that take `self` consume the handle, and the object takes ownership of the
+/// returned value in its place. This way the object stays valid after the call, and
+/// `builder.WithA(a).WithB(b)` doesn't leave any intermediate objects to be disposed.
+///
+/// Methods that return a reference usually return the receiver itself, in which case
+/// the object is returned rather than creating a second object that borrows from it.
+/// Any other reference is returned as a borrowed object as usual.
+///
+/// Returns `None` if the method isn't builder-style.
+fn quote_chained_method(
+    item: &Method,
+    self_type_export: &NamedType,
+    types: &TypeMap,
+) -> Option<TokenStream> {
+    if self_type_export.binding_style != BindingStyle::Handle {
+        return None;
+    }
+
+    let class_ident = item.self_type.ident();
+    let returns_self = |repr: &Repr| matches!(repr, Repr::Named(name) if *name == item.self_type);
+    let returns_self_ref = |repr: &Repr| matches!(repr, Repr::Ref(inner) if returns_self(inner));
+
+    let bindings = binding::bindings_class_ident();
+    let from_borrowed = borrow::from_borrowed_fn_ident();
+    let owner = borrow::quote_owner();
+    let return_borrowed = quote! {
+        if (__raw_result == _handle)
+        {
+            return this;
+        }
+
+        #bindings.#from_borrowed(__raw_result, #owner, out #class_ident __result);
+        return __result;
+    };
+
+    let (receiver, update) = match (item.receiver.as_ref()?, item.output.as_ref()?) {
+        (ReceiverStyle::Move, output) if returns_self(output) => {
+            let type_name = item.self_type.name.to_string();
+            (
+                quote! { this._TakeHandle() },
+                quote! {
+                    _handle = __raw_result;
+                    RustDiagnostics.__Track(_safeHandle, #type_name);
+                    return this;
+                },
+            )
+        }
+
+        (ReceiverStyle::Ref, output) if returns_self_ref(output) => {
+            (quote! { this._handle }, return_borrowed)
+        }
+
+        (ReceiverStyle::RefMut, output) if returns_self_ref(output) => {
+            (quote! { this._handleMut }, return_borrowed)
+        }
+
+        _ => return None,
+    };
+
+    let name = func::fn_ident(&item.name, item.rename.as_deref());
+    let args = func::quote_optional_args(&item.inputs, types);
+    let raw_repr = quote_handle_ptr();
+    let body = func::quote_wrapper_body(
+        &item.binding,
+        Some(receiver),
+        &item.inputs,
+        None,
+        Some(&quote! { __raw_result }),
+        types,
+    );
+
+    Some(quote! {
+        public #class_ident #name(#( #args ),*)
+        {
+            unsafe
+            {
+                #raw_repr __raw_result;
+                #body
+                #update
+            }
+        }
+    })
+}
+
+/// Generates the C# properties and indexers for all methods exported as accessors.
+///
+/// The getter and setter for a property are exported as separate methods, so they're
+/// first grouped by self type and property name in order to generate a single
+/// property with both accessors. Indexers are grouped by self type alone, since a
+/// type can only have a single indexer.
+pub fn quote_properties<'a>(
+    exports: &'a [Export],
+    types: &TypeMap,
+) -> Vec<(&'a TypeName, TokenStream)> {
+    let mut properties = Vec::<(&Method, Option<&Method>, Option<&Method>)>::new();
+    for export in exports {
+        let (method, accessor) = match export {
+            Export::Method(method) => match &method.accessor {
+                Some(accessor) => (method, accessor),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        let index = properties.iter().position(|(first, _, _)| {
+            first.self_type == method.self_type
+                && first.accessor.as_ref().unwrap().property_name() == accessor.property_name()
+        });
+        let index = index.unwrap_or_else(|| {
+            properties.push((method, None, None));
+            properties.len() - 1
+        });
+
+        let (_, getter, setter) = &mut properties[index];
+        if accessor.is_getter() {
+            *getter = Some(method);
+        } else {
+            *setter = Some(method);
+        }
+    }
+
+    properties
+        .into_iter()
+        .map(|(first, getter, setter)| {
+            (
+                &first.self_type,
+                quote_property(first, getter, setter, types),
+            )
+        })
+        .collect()
+}
+
+fn quote_property(
+    first: &Method,
+    getter: Option<&Method>,
+    setter: Option<&Method>,
+    types: &TypeMap,
+) -> TokenStream {
+    let self_type_export = types
+        .get(&first.self_type)
+        .unwrap_or_else(|| panic!("No export found for type name {:?}", first.self_type));
+
+    let class_ident = first.self_type.ident();
+    let name = first.accessor.as_ref().unwrap().property_name();
+    let display_name = name.unwrap_or("this[]");
+
+    // NOTE: The value for the setter is always the last argument, since it follows the
+    // index for indexers.
+    let getter_repr = getter.map(|getter| getter.output.as_ref().unwrap());
+    let setter_repr = setter.map(|setter| &setter.inputs.last().unwrap().repr);
+    if let (Some(getter_repr), Some(setter_repr)) = (getter_repr, setter_repr) {
+        assert_eq!(
+            getter_repr, setter_repr,
+            "Getter and setter for property {}.{} have different types",
+            first.self_type.name, display_name,
+        );
+    }
+
+    let ty = func::quote_return_type(getter_repr.or(setter_repr), types);
+
+    // Determine the declaration for the property, as well as the arguments used to pass
+    // the index for indexers.
+    let (declaration, index_args, prologue) = match name {
+        Some(name) => {
+            let property_ident = format_ident!("{}", name.to_camel_case());
+            (quote! { #property_ident }, Vec::new(), None)
+        }
+
+        None => {
+            let getter_index = getter.map(|getter| &getter.inputs[0].repr);
+            let setter_index = setter.map(|setter| &setter.inputs[0].repr);
+            if let (Some(getter_index), Some(setter_index)) = (getter_index, setter_index) {
+                assert_eq!(
+                    getter_index, setter_index,
+                    "Getter and setter for indexer on {} have different index types",
+                    first.self_type.name,
+                );
+            }
+
+            // NOTE: Collections in C# are indexed with `int`, so `usize` indices are
+            // exposed as `int` rather than `UIntPtr` and converted before being passed to
+            // Rust.
+            match getter_index.or(setter_index).unwrap() {
+                Repr::USize => (
+                    quote! { this[int index] },
+                    vec![FnArg::new("position", Repr::USize)],
+                    Some(quote! {
+                        if (index < 0)
+                        {
+                            throw new ArgumentOutOfRangeException(nameof(index));
+                        }
+
+                        var position = new UIntPtr((ulong)index);
+                    }),
+                ),
+
+                index_repr => {
+                    let index_ty = generate::quote_cs_type_for_repr(index_repr, types);
+                    (
+                        quote! { this[#index_ty index] },
+                        vec![FnArg::new("index", index_repr.clone())],
+                        None,
+                    )
+                }
+            }
+        }
+    };
+
+    let get = getter.map(|getter| {
+        let block = func::quote_wrapper_block(
+            &getter.binding,
+            Some(quote! { this._handle }),
+            &index_args,
+            getter.output.as_ref(),
+            types,
+        );
+        quote! {
+            get
+            {
+                #prologue
+                #block
+            }
+        }
+    });
+
+    // NOTE: The setter's argument is renamed to `value` in order to match the implicit
+    // parameter in C# property setters.
+    let set = setter.map(|setter| {
+        let mut args = index_args.clone();
+        args.push(FnArg::new(
+            "value",
+            setter.inputs.last().unwrap().repr.clone(),
+        ));
+        let block = func::quote_wrapper_block(
+            &setter.binding,
+            Some(quote! { this._handleMut }),
+            &args,
+            None,
+            types,
+        );
+        quote! {
+            set
+            {
+                #prologue
+                #block
+            }
+        }
+    });
+
+    // NOTE: The getter and setter are documented separately in Rust, so the property
+    // uses the docs for the getter if it has any.
+    let docs = getter
+        .and_then(|getter| getter.docs.as_deref())
+        .or_else(|| setter.and_then(|setter| setter.docs.as_deref()));
+    let docs = docs::quote_doc_comment(docs);
+
+    match &self_type_export.binding_style {
+        BindingStyle::Handle => types.wrap_namespace(
+            &first.self_type,
+            quote! {
+                partial class #class_ident
+                {
+                    #docs
+                    public #ty #declaration
+                    {
+                        #get
+                        #set
+                    }
+                }
+            },
+        ),
+
+        // NOTE: `#[cs_bindgen]` rejects accessors on types that aren't handles, so this
+        // can only happen if the declarations were produced some other way.
+        BindingStyle::Value(_)
+        | BindingStyle::Marshal(..)
+        | BindingStyle::Custom(_)
+        | BindingStyle::Transparent(_) => panic!(
+            "Property {}.{} is declared on a type that isn't exported as a handle, which isn't supported",
+            first.self_type.name, display_name,
+        ),
     }
 }
@@ -0,0 +1,92 @@
+//! Code generation for types that implement `PartialOrd`.
+//!
+//! Ordering is exposed in C# by implementing `IComparable<T>` and overloading the `<`,
+//! `<=`, `>`, and `>=` operators. As with equality, all comparisons call back into
+//! Rust so that custom `PartialOrd` impls behave the same in C#.
+
+use crate::generate::{func, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{BindingStyle, FnArg, NamedType, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the interface implemented by types that implement `PartialOrd`, if any.
+pub fn quote_interface(export: &NamedType) -> Option<TokenStream> {
+    export.cmp_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    Some(quote! { IComparable<#ident> })
+}
+
+/// Quotes the comparison members for a type that implements `PartialOrd`.
+pub fn quote_members(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let cmp_fn = export.cmp_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    let repr = Repr::Named(export.type_name.clone());
+
+    let compare_to = match &export.binding_style {
+        // NOTE: By convention, any instance compares greater than `null`.
+        BindingStyle::Handle => {
+            let other = FnArg::new("other", Repr::Ref(Box::new(repr)));
+            let block = func::quote_wrapper_block(
+                cmp_fn,
+                Some(quote! { this._handle }),
+                &[other],
+                Some(&Repr::I32),
+                types,
+            );
+
+            quote! {
+                public int CompareTo(#ident other)
+                {
+                    if (ReferenceEquals(other, null))
+                    {
+                        return 1;
+                    }
+
+                    #block
+                }
+            }
+        }
+
+        BindingStyle::Value(_)
+        | BindingStyle::Marshal(..)
+        | BindingStyle::Custom(_)
+        | BindingStyle::Transparent(_) => {
+            let args = [FnArg::new("self", repr.clone()), FnArg::new("other", repr)];
+            let block = func::quote_wrapper_block(cmp_fn, None, &args, Some(&Repr::I32), types);
+
+            quote! {
+                public int CompareTo(#ident other)
+                {
+                    var self = this;
+                    #block
+                }
+            }
+        }
+    };
+
+    // NOTE: The operators go through `Comparer<T>.Default` so that `null` handles are
+    // ordered before all other values rather than throwing.
+    Some(quote! {
+        #compare_to
+
+        public static bool operator <(#ident left, #ident right)
+        {
+            return Comparer<#ident>.Default.Compare(left, right) < 0;
+        }
+
+        public static bool operator <=(#ident left, #ident right)
+        {
+            return Comparer<#ident>.Default.Compare(left, right) <= 0;
+        }
+
+        public static bool operator >(#ident left, #ident right)
+        {
+            return Comparer<#ident>.Default.Compare(left, right) > 0;
+        }
+
+        public static bool operator >=(#ident left, #ident right)
+        {
+            return Comparer<#ident>.Default.Compare(left, right) >= 0;
+        }
+    })
+}
@@ -0,0 +1,207 @@
+//! Code generation for exported `const` and `static` items.
+//!
+//! Constants with a primitive or string value are generated as a C# `const`, since
+//! their value is known when the bindings are generated. Any other constant is
+//! generated as a `static readonly` field, which is initialized by calling into Rust
+//! the first time the containing class is used.
+//!
+//! Associated constants are generated as members of the C# type for their self type,
+//! using a partial declaration of the class or struct. The associated constants of
+//! flags types are instead generated as the members of the enum.
+
+use crate::generate::{binding, flags, func, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{schematic::Schema, BindingStyle, Const, ConstValue, Repr};
+use heck::*;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+use std::{convert::TryFrom, fmt::Display};
+
+/// Quotes the raw binding for a constant, if the constant isn't generated as a
+/// literal.
+pub fn quote_raw_binding(export: &Const, dll_name: &str, types: &TypeMap) -> TokenStream {
+    if export.value.is_some() {
+        return quote! {};
+    }
+
+    binding::quote_raw_fn_binding(
+        &export.binding,
+        binding::raw_type_from_repr(&export.repr, types),
+        binding::quote_panic_arg(),
+        dll_name,
+    )
+}
+
+/// Quotes the C# declaration for a constant.
+pub fn quote_const(export: &Const, types: &TypeMap) -> Result<TokenStream, failure::Error> {
+    let ident = format_ident!("{}", export.name.to_camel_case());
+
+    let value = match &export.value {
+        Some(value) => value,
+
+        None => {
+            let ty = func::quote_method_return_type(Some(&export.repr), false, types);
+            let init_ident = format_ident!("__Init{}", ident);
+            let block =
+                func::quote_wrapper_block(&export.binding, None, &[], Some(&export.repr), types);
+
+            return Ok(quote! {
+                public static readonly #ty #ident = #init_ident();
+
+                private static #ty #init_ident()
+                #block
+            });
+        }
+    };
+
+    let (ty, literal) = match (&export.repr, value) {
+        (Repr::Bool, ConstValue::Bool(value)) => (quote! { bool }, quote! { #value }),
+
+        // NOTE: `IntPtr` and `UIntPtr` can't be used as constants, so pointer-sized
+        // constants are exposed as `int` (matching how sizes are represented in C#) as
+        // long as the value fits.
+        (Repr::ISize, ConstValue::Int(value)) => {
+            let value = to_int(&export.name, *value)?;
+            (
+                quote! { int },
+                Literal::i32_unsuffixed(value).into_token_stream(),
+            )
+        }
+        (Repr::USize, ConstValue::UInt(value)) => {
+            let value = to_int(&export.name, *value)?;
+            (
+                quote! { int },
+                Literal::i32_unsuffixed(value).into_token_stream(),
+            )
+        }
+
+        (repr, ConstValue::Int(value)) => (
+            func::quote_return_type(Some(repr), types),
+            Literal::i64_unsuffixed(*value).into_token_stream(),
+        ),
+        (repr, ConstValue::UInt(value)) => (
+            func::quote_return_type(Some(repr), types),
+            Literal::u64_unsuffixed(*value).into_token_stream(),
+        ),
+
+        (Repr::F32, ConstValue::Float(value)) => (quote! { float }, quote_float(*value, "float")),
+        (Repr::F64, ConstValue::Float(value)) => (quote! { double }, quote_float(*value, "double")),
+
+        (_, ConstValue::String(value)) => (quote! { string }, quote! { #value }),
+
+        (repr, value) => {
+            return Err(failure::format_err!(
+                "Invalid value for constant {} of type {:?}: {:?}",
+                export.name,
+                repr,
+                value,
+            ))
+        }
+    };
+
+    // NOTE: Constants of a flags type have an integer value, which needs to be cast to
+    // the enum type.
+    let literal = match &export.repr {
+        Repr::Named(_) => quote! { (#ty)(#literal) },
+        _ => literal,
+    };
+
+    Ok(quote! {
+        public const #ty #ident = #literal;
+    })
+}
+
+/// Quotes the C# declaration for an associated constant as part of a partial
+/// declaration of its self type.
+///
+/// Only handle types and structs can have associated constants, since exported enums
+/// don't have a C# type that can contain them.
+pub fn quote_assoc_const(export: &Const, types: &TypeMap) -> Result<TokenStream, failure::Error> {
+    let self_type = export
+        .self_type
+        .as_ref()
+        .expect("Associated constant must have a self type");
+    let self_export = types
+        .get(self_type)
+        .ok_or_else(|| failure::format_err!("No export found for type name {:?}", self_type))?;
+
+    // The associated constants of a flags type are generated as the members of its enum,
+    // which can't contain any other constants.
+    if self_export.is_flags {
+        if flags::is_flag(export, self_type) {
+            return Ok(quote! {});
+        }
+
+        return Err(failure::format_err!(
+            "Associated constant {} is declared on flags type {:?}, but only constants of \
+             the flags type itself are supported",
+            export.name,
+            self_type,
+        ));
+    }
+
+    let ident = self_type.ident();
+    let member = quote_const(export, types)?;
+
+    // NOTE: Marshaled types are generated as a partial class, so the constant can be
+    // added to the class the same way as for handle types.
+    let declaration = match &self_export.binding_style {
+        BindingStyle::Handle | BindingStyle::Marshal(..) => quote! {
+            partial class #ident
+            {
+                #member
+            }
+        },
+
+        BindingStyle::Value(schema) if !matches!(schema, Schema::Enum(_)) => quote! {
+            partial struct #ident
+            {
+                #member
+            }
+        },
+
+        BindingStyle::Value(_) | BindingStyle::Custom(_) | BindingStyle::Transparent(_) => {
+            return Err(failure::format_err!(
+                "Associated constant {} is declared on {:?}, but associated constants are only \
+             supported on structs and handle types",
+                export.name,
+                self_type,
+            ))
+        }
+    };
+
+    Ok(types.wrap_namespace(self_type, declaration))
+}
+
+/// Quotes a floating point literal, using the named constants on the C# type for
+/// values that can't be written as a literal.
+pub fn quote_float(value: f64, ty: &str) -> TokenStream {
+    let ty = format_ident!("{}", ty);
+    if value.is_nan() {
+        quote! { #ty.NaN }
+    } else if value == f64::INFINITY {
+        quote! { #ty.PositiveInfinity }
+    } else if value == f64::NEG_INFINITY {
+        quote! { #ty.NegativeInfinity }
+    } else if ty == "float" {
+        // NOTE: C# won't implicitly convert a `double` literal to `float`, so the
+        // literal needs the `F` suffix.
+        format!("{:?}F", value as f32).parse().unwrap()
+    } else {
+        format!("{:?}", value).parse().unwrap()
+    }
+}
+
+/// Converts the value of a pointer-sized constant into an `int`.
+fn to_int<T>(name: &str, value: T) -> Result<i32, failure::Error>
+where
+    T: Copy + Display,
+    i32: TryFrom<T>,
+{
+    i32::try_from(value).map_err(|_| {
+        failure::format_err!(
+            "Value of constant {} doesn't fit in a C# `int`: {}",
+            name,
+            value,
+        )
+    })
+}
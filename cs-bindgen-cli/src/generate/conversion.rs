@@ -0,0 +1,151 @@
+//! Code generation for conversions declared with `From` and `TryFrom` impls.
+//!
+//! Conversions are exposed in C# as user-defined conversion operators. C# requires the
+//! operator to be declared on either the source or the target type, so it's added to
+//! the target type if that's an exported struct or class, and otherwise to the source
+//! type, e.g. both `impl From<u8> for Tile` and `impl From<Tile> for u8` are declared
+//! on `Tile`.
+//!
+//! `From` impls generate an `implicit` operator, unless the source type is a handle.
+//! Since the conversion takes ownership of the value, converting a handle disposes
+//! the C# object, which shouldn't happen without an explicit cast. `TryFrom` impls
+//! always generate an `explicit` operator, which throws an `InvalidCastException` if
+//! the conversion fails, e.g. `(Tile)tileId`.
+
+use crate::generate::{binding, func, option, quote_cs_type_for_repr, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{
+    schematic::{Schema, TypeName},
+    BindingStyle, Conversion, FnArg, NamedType, Repr,
+};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the raw binding for a conversion.
+pub fn quote_raw_binding(export: &Conversion, dll_name: &str, types: &TypeMap) -> TokenStream {
+    let from_ty = binding::raw_type_from_repr(&export.from, types);
+    let return_ty = binding::raw_type_from_repr(&binding_output(export), types);
+    let panic_arg = binding::quote_panic_arg();
+
+    binding::quote_raw_fn_binding(
+        &export.binding,
+        return_ty,
+        quote! { #from_ty value, #panic_arg },
+        dll_name,
+    )
+}
+
+/// Quotes the conversion operator for a conversion, returning the name of the type
+/// that declares the operator along with the partial declaration of the type.
+pub fn quote_conversion<'a>(
+    export: &'a Conversion,
+    types: &'a TypeMap,
+) -> Result<(&'a TypeName, TokenStream), failure::Error> {
+    let owner = [&export.into, &export.from]
+        .iter()
+        .copied()
+        .find_map(|repr| match repr {
+            Repr::Named(type_name)
+                if types
+                    .get(type_name)
+                    .map_or(false, |named| can_declare_operators(named)) =>
+            {
+                Some(type_name)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            failure::format_err!(
+                "Can't generate a conversion operator from {:?} to {:?}, since neither type is \
+                 an exported struct or class",
+                export.from,
+                export.into,
+            )
+        })?;
+
+    let from_ty = quote_cs_type_for_repr(&export.from, types);
+    let into_ty = quote_cs_type_for_repr(&export.into, types);
+    let args = [FnArg::new("value", export.from.clone())];
+    let output = binding_output(export);
+    let block = func::quote_wrapper_block(&export.binding, None, &args, Some(&output), types);
+
+    let operator = if export.fallible {
+        let helper = format_ident!("{}", &*export.binding);
+        let option_ty = option::quote_cs_type(&export.into, types);
+
+        quote! {
+            private static #option_ty #helper(#from_ty value)
+            #block
+
+            public static explicit operator #into_ty(#from_ty value)
+            {
+                var result = #helper(value);
+                if (result == null)
+                {
+                    throw new InvalidCastException(
+                        "Can't convert " + value + " to " + typeof(#into_ty).Name);
+                }
+
+                return (#into_ty)result;
+            }
+        }
+    } else {
+        let kind = if is_handle(&export.from, types) {
+            quote! { explicit }
+        } else {
+            quote! { implicit }
+        };
+
+        quote! {
+            public static #kind operator #into_ty(#from_ty value)
+            #block
+        }
+    };
+
+    let ident = owner.ident();
+    let declaration = match &types[owner].binding_style {
+        BindingStyle::Handle | BindingStyle::Marshal(..) => quote! {
+            partial class #ident
+            {
+                #operator
+            }
+        },
+
+        _ => quote! {
+            partial struct #ident
+            {
+                #operator
+            }
+        },
+    };
+
+    Ok((owner, types.wrap_namespace(owner, declaration)))
+}
+
+/// Returns the output of the binding function for a conversion.
+fn binding_output(export: &Conversion) -> Repr {
+    if export.fallible {
+        Repr::Option(Box::new(export.into.clone()))
+    } else {
+        export.into.clone()
+    }
+}
+
+/// Returns `true` if the C# type for an exported type can declare operators, including
+/// conversion operators, i.e. it's generated as a class or struct.
+pub fn can_declare_operators(export: &NamedType) -> bool {
+    match &export.binding_style {
+        BindingStyle::Handle | BindingStyle::Marshal(..) => true,
+        BindingStyle::Value(schema) => !export.is_flags && !matches!(schema, Schema::Enum(_)),
+        BindingStyle::Custom(_) | BindingStyle::Transparent(_) => false,
+    }
+}
+
+fn is_handle(repr: &Repr, types: &TypeMap) -> bool {
+    match repr {
+        Repr::Named(type_name) => types
+            .get(type_name)
+            .map(|named| named.binding_style == BindingStyle::Handle)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
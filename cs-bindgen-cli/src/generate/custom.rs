@@ -0,0 +1,71 @@
+//! Code generation for types exported with custom conversion functions.
+//!
+//! These types are passed across the FFI boundary as their raw type, which is
+//! converted to the C# type by a user-provided converter class. The converter class
+//! must have a static `FromRaw` method that takes the C# type for the raw value and
+//! returns the C# type, and a static `IntoRaw` method for the opposite conversion:
+//!
+//! ```csharp
+//! public static class GameIdConverter
+//! {
+//!     public static Guid FromRaw(string raw) => Guid.ParseExact(raw, "N");
+//!     public static string IntoRaw(Guid value) => value.ToString("N");
+//! }
+//! ```
+//!
+//! If no C# type is specified with `cs_type`, the user must also provide a C# type
+//! with the same name and namespace as the generated type would have.
+//!
+//! NOTE: The generator can't tell whether the C# type is a value type, so it's always
+//! treated as a reference type. This means that optional values of types with custom
+//! conversions are only supported if the C# type is a class.
+
+use crate::generate::{self, binding, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{CustomMarshal, NamedType};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the C# type that a type with custom conversion functions is exposed as.
+pub fn quote_cs_type(export: &NamedType, custom: &CustomMarshal, types: &TypeMap) -> TokenStream {
+    match &custom.cs_type {
+        Some(cs_type) => parse_type_name(cs_type),
+        None => types.qualify(&export.type_name, export.type_name.ident()),
+    }
+}
+
+/// Generates the `__FromRaw` and `__IntoRaw` overloads for a type with custom
+/// conversion functions.
+///
+/// The raw value is first converted to the C# type for the raw type, which is then
+/// passed to the converter class.
+pub fn quote_custom_type(
+    export: &NamedType,
+    custom: &CustomMarshal,
+    types: &TypeMap,
+) -> TokenStream {
+    let ty = quote_cs_type(export, custom, types);
+    let converter = parse_type_name(&custom.converter);
+    let raw_ty = binding::raw_type_from_repr(&custom.raw, types);
+    let intermediate_ty = generate::quote_cs_type_for_repr(&custom.raw, types);
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ty raw, out #ty result)
+        {
+            #from_raw(raw, out #intermediate_ty value);
+            result = #converter.FromRaw(value);
+        }
+
+        internal static void #into_raw(#ty value, out #raw_ty result)
+        {
+            #into_raw(#converter.IntoRaw(value), out result);
+        }
+    })
+}
+
+/// Parses a C# type name specified in the arguments to `#[cs_bindgen]`.
+fn parse_type_name(name: &str) -> TokenStream {
+    name.parse()
+        .unwrap_or_else(|err| panic!("Invalid C# type name {:?}: {}", name, err))
+}
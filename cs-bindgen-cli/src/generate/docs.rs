@@ -0,0 +1,71 @@
+//! Code generation for XML doc comments.
+//!
+//! The bindings are generated as a stream of tokens, which can't contain comments.
+//! Doc comments are instead generated as a placeholder identifier followed by a
+//! string literal containing the comment text, which the formatter expands into a
+//! C# doc comment when writing out the bindings.
+//!
+//! The text of the comment is escaped as XML before being put in the string literal,
+//! using numeric character references for everything other than a conservative set
+//! of characters. This ensures that the literal never contains any characters that
+//! would be escaped when the literal is converted to a string, and that the comment
+//! text can't terminate the comment or inject any XML tags into it.
+
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::*;
+
+/// The identifier used to mark the location of a doc comment in the generated code.
+const DOC_PLACEHOLDER: &str = "__cs_bindgen_doc__";
+
+/// The escaped representation of a newline in the comment text.
+const ESCAPED_NEWLINE: &str = "&#10;";
+
+/// Quotes a placeholder for a doc comment with the specified text.
+///
+/// Returns an empty token stream if there is no doc comment.
+pub fn quote_doc_comment(docs: Option<&str>) -> TokenStream {
+    let docs = match docs {
+        Some(docs) => docs,
+        None => return quote! {},
+    };
+
+    let placeholder = format_ident!("{}", DOC_PLACEHOLDER);
+    let text = Literal::string(&escape_xml(docs));
+    quote! {
+        #placeholder #text
+    }
+}
+
+/// Returns `true` if `ident` marks the location of a doc comment.
+pub fn is_doc_placeholder(ident: &Ident) -> bool {
+    ident == DOC_PLACEHOLDER
+}
+
+/// Returns the lines of the C# doc comment for the string literal that follows a
+/// placeholder.
+pub fn doc_comment_lines(text: &Literal) -> Vec<String> {
+    // NOTE: The escaped text never contains a `"` or a `\`, so the literal is always
+    // the text wrapped in quotes.
+    let text = text.to_string();
+    let text = &text[1..text.len() - 1];
+
+    let mut lines = vec!["/// <summary>".to_string()];
+    lines.extend(
+        text.split(ESCAPED_NEWLINE)
+            .map(|line| format!("/// {}", line)),
+    );
+    lines.push("/// </summary>".into());
+    lines
+}
+
+/// Escapes the text of a doc comment so that it can be embedded in a string literal.
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c.to_string(),
+            ' ' | '.' | ',' | ':' | ';' | '!' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '-'
+            | '_' | '=' | '+' | '~' | '@' | '$' | '%' | '^' | '|' | '#' | '`' => c.to_string(),
+            c => format!("&#{};", c as u32),
+        })
+        .collect()
+}
@@ -1,11 +1,23 @@
 //! Code generation for exported enum types that are marshaled by value.
 
-use crate::generate::{binding, quote_primitive_type, strukt, TypeMap, TypeNameExt};
-use cs_bindgen_shared::{schematic::Enum, schematic::Variant, BindingStyle, NamedType, TypeName};
+use crate::generate::{self, binding, docs, quote_primitive_type, strukt, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{
+    schematic::{Enum, Field, Variant},
+    BindingStyle, NamedType, Repr, TypeName,
+};
 use proc_macro2::{Literal, TokenStream};
 use quote::*;
+use std::borrow::Cow;
 use syn::Ident;
 
+/// The name of the enum member that represents unrecognized values of a
+/// `#[non_exhaustive]` C-like enum.
+const UNKNOWN_VALUE: &str = "Unknown";
+
+/// The name of the variant that represents unrecognized variants of a
+/// `#[non_exhaustive]` data-carrying enum.
+const UNKNOWN_VARIANT: &str = "UnknownVariant";
+
 pub fn quote_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
     assert!(
         matches!(export.binding_style, BindingStyle::Value(..)),
@@ -13,14 +25,16 @@ pub fn quote_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenSt
         export.type_name,
     );
 
+    let schema = &*with_unknown_variant(export, schema);
+
     // Determine if we're dealing with a simple (C-like) enum or one with fields.
     let generated = if schema.has_data() {
         quote_complex_enum(export, schema, types)
     } else {
-        quote_simple_enum(export, schema)
+        quote_simple_enum(export, schema, types)
     };
 
-    let repr = quote_type_reference(schema);
+    let repr = types.qualify(&export.type_name, quote_type_reference(schema, types));
     let from_raw = binding::from_raw_fn_ident();
     let into_raw = binding::into_raw_fn_ident();
 
@@ -28,8 +42,8 @@ pub fn quote_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenSt
     // binding style is by-value.
     let raw_repr = binding::raw_type_from_schema(&export.schema().unwrap(), types);
 
-    let from_raw_impl = from_raw_impl(export, schema);
-    let into_raw_impl = into_raw_impl(export, schema);
+    let from_raw_impl = from_raw_impl(export, schema, types);
+    let into_raw_impl = into_raw_impl(export, schema, types);
     let raw_conversions = binding::wrap_bindings(quote! {
         internal static void #from_raw(#raw_repr raw, out #repr result)
         {
@@ -48,8 +62,38 @@ pub fn quote_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenSt
     }
 }
 
-pub fn quote_type_reference(schema: &Enum) -> TokenStream {
-    if schema.has_data() {
+/// Adds the variant that represents unrecognized variants to the schema of a
+/// `#[non_exhaustive]` data-carrying enum, so that it's generated like any other unit
+/// variant.
+///
+/// A newer version of the native library may add variants to a non-exhaustive enum,
+/// which are converted to the unknown variant instead of failing. The unknown variant
+/// is always the last variant, and doesn't have a discriminant on the Rust side.
+fn with_unknown_variant<'a>(export: &NamedType, schema: &'a Enum) -> Cow<'a, Enum> {
+    if !export.is_non_exhaustive || !schema.has_data() {
+        return Cow::Borrowed(schema);
+    }
+
+    let mut schema = schema.clone();
+    schema.variants.push(Variant::Unit {
+        name: UNKNOWN_VARIANT.into(),
+        discriminant: None,
+    });
+    Cow::Owned(schema)
+}
+
+/// Returns the variants of the enum that exist on the Rust side, i.e. excluding the
+/// unknown variant of a `#[non_exhaustive]` data-carrying enum.
+fn rust_variants<'a>(export: &NamedType, schema: &'a Enum) -> &'a [Variant] {
+    if export.is_non_exhaustive && schema.has_data() {
+        &schema.variants[..schema.variants.len() - 1]
+    } else {
+        &schema.variants
+    }
+}
+
+pub fn quote_type_reference(schema: &Enum, types: &TypeMap) -> TokenStream {
+    if schema.has_data() && !types.uses_enum_classes(&schema.name) {
         format_ident!("I{}", &*schema.name.name).into_token_stream()
     } else {
         format_ident!("{}", &*schema.name.name).into_token_stream()
@@ -59,32 +103,76 @@ pub fn quote_type_reference(schema: &Enum) -> TokenStream {
 /// Quotes the appropriate discriminant type for the specified enum type.
 ///
 /// The generated type is the type use to represent the raw discriminant when
-/// communicating with Rust. On the C# side, C-like enums are always represented as
-/// `int` under the hood, and complex enums don't have a specific discriminant since
-/// they are represented using an interface.
-pub fn quote_discriminant_type(schema: &Enum) -> TokenStream {
-    schema
-        .repr
-        .map(quote_primitive_type)
-        .unwrap_or_else(|| quote! { IntPtr })
+/// communicating with Rust. For C-like enums this is the type specified with
+/// `#[repr(...)]`, and otherwise defaults to `isize`. Complex enums don't have a
+/// specific discriminant since they are represented using an interface.
+pub fn quote_discriminant_type(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
+    match &export.discriminant {
+        Some(repr) => binding::raw_type_from_repr(repr, types),
+        None => schema
+            .repr
+            .map(quote_primitive_type)
+            .unwrap_or_else(|| quote! { IntPtr }),
+    }
+}
+
+/// Quotes the base type declaration for a C-like enum, if the enum has a fixed-size
+/// discriminant type.
+///
+/// C# enums can't use `IntPtr` as their underlying type, so enums with a
+/// pointer-sized discriminant use the default underlying type (`int`) and are cast to
+/// the discriminant type when marshaled.
+fn quote_underlying_type(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    match &export.discriminant {
+        Some(Repr::ISize) | Some(Repr::USize) | None => None,
+        Some(repr) => {
+            let ty = binding::raw_type_from_repr(repr, types);
+            Some(quote! { : #ty })
+        }
+    }
 }
 
-fn from_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
+fn from_raw_impl(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
     // For C-like enums, the conversion is just casting the raw discriminant value to
     // the C# enum type.
+    //
+    // Unrecognized values of non-exhaustive enums are converted to the unknown value,
+    // so that C# code doesn't have to handle values that aren't declared in the enum.
     if !schema.has_data() {
-        let cs_repr = quote_type_reference(schema);
-        return quote! { result = (#cs_repr)raw; };
+        let cs_repr = types.qualify(&export.type_name, quote_type_reference(schema, types));
+        if !export.is_non_exhaustive {
+            return quote! { result = (#cs_repr)raw; };
+        }
+
+        let unknown = format_ident!("{}", UNKNOWN_VALUE);
+        let variants = schema
+            .variants
+            .iter()
+            .map(|variant| format_ident!("{}", variant.name()));
+        return quote! {
+            switch ((#cs_repr)raw)
+            {
+                #(
+                    case #cs_repr.#variants:
+                )*
+                    result = (#cs_repr)raw;
+                    break;
+
+                default:
+                    result = #cs_repr.#unknown;
+                    break;
+            }
+        };
     }
 
-    let discriminants = schema
-        .variants
+    let variants = rust_variants(export, schema);
+    let discriminants = variants
         .iter()
         .enumerate()
         .map(|(index, _)| Literal::usize_unsuffixed(index));
 
-    let convert_variants = schema.variants.iter().map(|variant| {
-        let cs_repr = variant_struct_type_ref(export, variant);
+    let convert_variants = variants.iter().map(|variant| {
+        let cs_repr = variant_struct_type_ref(export, variant, types);
 
         if variant.is_empty() {
             quote! {
@@ -98,6 +186,13 @@ fn from_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
         }
     });
 
+    let default = if export.is_non_exhaustive {
+        let unknown = variant_struct_type_ref(export, schema.variants.last().unwrap(), types);
+        quote! { result = new #unknown(); break; }
+    } else {
+        quote! { throw new Exception("Invalid discriminant " + raw.Discriminant); }
+    };
+
     quote! {
         switch (raw.Discriminant.ToInt64())
         {
@@ -108,17 +203,38 @@ fn from_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
                 } break;
             )*
 
-            default: throw new Exception("Invalid discriminant " + raw.Discriminant);
+            default: #default
         }
     }
 }
 
-fn into_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
+fn into_raw_impl(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
     // For C-like enums, the conversion is just casting the C# enum value to the
     // appropriate discriminant type.
+    //
+    // The unknown value of a non-exhaustive enum doesn't correspond to any value on the
+    // Rust side, so it can't be passed to Rust.
     if !schema.has_data() {
-        let discriminant_ty = quote_discriminant_type(schema);
+        let discriminant_ty = quote_discriminant_type(export, schema, types);
+        let check_unknown = if export.is_non_exhaustive {
+            let cs_repr = types.qualify(&export.type_name, quote_type_reference(schema, types));
+            let unknown = format_ident!("{}", UNKNOWN_VALUE);
+            let message = format!(
+                "{}.{} can't be passed to Rust",
+                export.type_name.name, UNKNOWN_VALUE,
+            );
+            quote! {
+                if (value == #cs_repr.#unknown)
+                {
+                    throw new ArgumentException(#message);
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         return quote! {
+            #check_unknown
             result = (#discriminant_ty)value;
         };
     }
@@ -126,30 +242,30 @@ fn into_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
     let raw_struct_ty = binding::raw_ident(&export.type_name);
     let union_ty = union_struct_name(&export.type_name);
 
-    let variant_name = schema
-        .variants
+    // NOTE: The unknown variant of a non-exhaustive enum isn't matched, so it falls
+    // through to the default case and can't be passed to Rust.
+    let variants = rust_variants(export, schema);
+    let variant_name = variants
         .iter()
         .map(|variant| format_ident!("{}", &variant.name()));
 
-    let variant_type = schema
-        .variants
+    let variant_type = variants
         .iter()
-        .map(|variant| variant_struct_type_ref(export, variant));
+        .map(|variant| variant_struct_type_ref(export, variant, types));
 
-    let discriminant = schema
-        .variants
+    let discriminant = variants
         .iter()
         .enumerate()
         .map(|(index, _)| Literal::usize_unsuffixed(index));
 
-    let convert_union_field = schema.variants.iter().map(|variant| {
+    let convert_union_field = variants.iter().map(|variant| {
         // Empty variants aren't represented in the union, so leave the constructor body
         // empty.
         if variant.is_empty() {
             quote! {}
         } else {
             let variant_name = format_ident!("{}", variant.name());
-            let raw_variant_type = raw_variant_struct_type_ref(export, variant);
+            let raw_variant_type = raw_variant_struct_type_ref(export, variant, types);
             quote! {
                 #variant_name = new #raw_variant_type(#variant_name)
             }
@@ -174,9 +290,9 @@ fn into_raw_impl(export: &NamedType, schema: &Enum) -> TokenStream {
     }
 }
 
-fn quote_simple_enum(export: &NamedType, schema: &Enum) -> TokenStream {
+fn quote_simple_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
     let ident = export.type_name.ident();
-    let variants = schema.variants.iter().map(|variant| {
+    let variants = schema.variants.iter().enumerate().map(|(index, variant)| {
         let (name, discriminant) = match variant {
             Variant::Unit { name, discriminant } => (name, discriminant),
 
@@ -193,16 +309,47 @@ fn quote_simple_enum(export: &NamedType, schema: &Enum) -> TokenStream {
             None => TokenStream::new(),
         };
 
+        let docs = quote_variant_docs(export, index);
         quote! {
+            #docs
             #variant_ident #discriminant
         }
     });
 
-    quote! {
-        public enum #ident {
-            #( #variants ),*
-        }
-    }
+    // NOTE: The unknown value needs a value that's distinct from the other variants,
+    // since the bindings compare against it to reject it when it's passed to Rust.
+    let unknown = if export.is_non_exhaustive {
+        let unknown = format_ident!("{}", UNKNOWN_VALUE);
+        let value = generate::enum_values(schema)
+            .iter()
+            .map(|(_, value)| value + 1)
+            .max()
+            .unwrap_or(0);
+        let value = Literal::i64_unsuffixed(value);
+        let docs = docs::quote_doc_comment(Some(
+            "A value that isn't declared in the bindings, e.g. a variant that was added to \
+             the enum in a newer version of the native library.",
+        ));
+        Some(quote! {
+            #docs
+            #unknown = #value
+        })
+    } else {
+        None
+    };
+
+    let underlying_type = quote_underlying_type(export, types);
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+    types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            public enum #ident #underlying_type {
+                #( #variants, )*
+                #unknown
+            }
+        },
+    )
 }
 
 fn quote_complex_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> TokenStream {
@@ -220,64 +367,113 @@ fn quote_complex_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> Tok
     let union_fields = schema.variants.iter().filter_map(|variant| match variant {
         Variant::Unit { .. } => None,
         _ => {
-            let binding_ty = raw_variant_struct_name(&export.type_name, variant.name());
+            let binding_ty = raw_variant_struct_type_ref(export, variant, types);
             let name = format_ident!("{}", variant.name());
 
             Some(quote! {
-                #wrapper_class.#binding_ty #name
+                #binding_ty #name
             })
         }
     });
 
+    // Generate the parameters for the `Match` methods and the methods of the visitor
+    // interface, which have one entry for each variant of the enum.
+    let qualified_wrapper = types.qualify(&export.type_name, &wrapper_class);
+    let visitor = format_ident!("I{}Visitor", export.type_name.name);
+    let qualified_visitor = types.qualify(&export.type_name, &visitor);
+    let variant_ref = schema
+        .variants
+        .iter()
+        .map(|variant| variant_struct_type_ref(export, variant, types))
+        .collect::<Vec<_>>();
+    let match_param = schema
+        .variants
+        .iter()
+        .map(|variant| format_ident!("on{}", variant.name()))
+        .collect::<Vec<_>>();
+    let visit_fn = schema
+        .variants
+        .iter()
+        .map(|variant| format_ident!("Visit{}", variant.name()))
+        .collect::<Vec<_>>();
+
     // Generate the struct declarations for each variant of the enum. We generate two
     // structs for each variant:
     //
     // * The public struct that acts as the C# representation of the variant.
     // * The raw representation which is kept internal and used as a field of the raw
     //   union for the enum.
-    let variant_structs = schema.variants.iter().map(|variant| {
+    let variant_structs = schema.variants.iter().enumerate().map(|(index, variant)| {
         let ident = variant_struct_name(variant);
         let raw_ident = raw_variant_struct_name(&export.type_name, variant.name());
 
         let fields = variant.fields().collect::<Vec<_>>();
 
-        let struct_fields = strukt::struct_fields(&fields, types);
+        let struct_fields = strukt::struct_fields(&fields, &[], types);
 
         // Generate a basic constructor for the user-facing struct, but only if the
         // struct has fields since we're not allowed to generate an explicit parameter-
         // less constructor for structs in C#.
         let struct_constructor = if !variant.is_empty() {
-            strukt::struct_constructor(&ident, &fields, types)
+            strukt::struct_constructor(&ident, &fields, &[], types)
         } else {
             quote! {}
         };
 
-        let field_ident = fields
-            .iter()
-            .enumerate()
-            .map(|(index, field)| strukt::field_ident(field.name, index))
-            .collect::<Vec<_>>();
-
-        let bindings = binding::bindings_class_ident();
-        let from_raw_fn = binding::from_raw_fn_ident();
-        let into_raw_fn = binding::into_raw_fn_ident();
-
-        let raw_fields = binding::raw_struct_fields(&fields, types);
+        let fields_from_raw = binding::quote_fields_from_raw(&fields, &[], types);
+        let fields_into_raw =
+            binding::quote_fields_into_raw(&fields, &[], &quote! { value }, types);
+
+        let raw_fields = binding::raw_struct_fields(&fields, &[], types);
+        let docs = quote_variant_docs(export, index);
+        let variant_match_param = &match_param[index];
+        let variant_visit_fn = &visit_fn[index];
+
+        // With `--enum-classes` each variant is a sealed class deriving from the base
+        // class of the enum, which also needs the members that records get for free.
+        let (declaration, modifier, class_members) = if types.uses_enum_classes(&export.type_name) {
+            (
+                quote! { public sealed class #ident : #qualified_wrapper },
+                quote! { public override },
+                quote_variant_class_members(&ident, &fields, types),
+            )
+        } else {
+            (
+                quote! { public struct #ident : #interface },
+                quote! { public },
+                quote! {},
+            )
+        };
 
         quote! {
-            // Generate the C# struct for the variant.
-            public struct #ident : #interface
+            // Generate the C# type for the variant.
+            #docs
+            #declaration
             {
                 #struct_fields
                 #struct_constructor
+                #class_members
 
                 // Generate an internal constructor for creating an instance of the variant struct
                 // from its raw representation.
                 internal #ident(#raw_ident raw)
                 {
-                    #(
-                        #bindings.#from_raw_fn(raw.#field_ident, out this.#field_ident);
-                    )*
+                    #fields_from_raw
+                }
+
+                #modifier T Match<T>(#( Func<#variant_ref, T> #match_param ),*)
+                {
+                    return #variant_match_param(this);
+                }
+
+                #modifier void Match(#( Action<#variant_ref> #match_param ),*)
+                {
+                    #variant_match_param(this);
+                }
+
+                #modifier T Accept<T>(#qualified_visitor<T> visitor)
+                {
+                    return visitor.#variant_visit_fn(this);
                 }
             }
 
@@ -291,9 +487,7 @@ fn quote_complex_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> Tok
                 // its raw representation.
                 public #raw_ident(#ident value)
                 {
-                    #(
-                        #bindings.#into_raw_fn(value.#field_ident, out this.#field_ident);
-                    )*
+                    #fields_into_raw
                 }
             }
         }
@@ -302,16 +496,65 @@ fn quote_complex_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> Tok
     let raw_struct = binding::raw_ident(&export.type_name);
     let union_struct = union_struct_name(&export.type_name);
 
-    quote! {
-        // Generate an interface for the enum.
-        public interface #interface { }
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
 
-        // Generate wrapper class in order to namespace the variants.
-        public static class #wrapper_class
-        {
-            // Generate the struct declarations for each variant of the enum.
-            #( #variant_structs )*
+    let enum_declaration = if types.uses_enum_classes(&export.type_name) {
+        quote! {
+            // Generate an abstract base class for the enum, with the variants nested inside
+            // it. The base class can only be constructed by the variants, which ensures that
+            // the variants are the only possible subclasses.
+            #docs
+            public abstract partial class #wrapper_class
+            {
+                private #wrapper_class() { }
+
+                public abstract T Match<T>(#( Func<#variant_ref, T> #match_param ),*);
+                public abstract void Match(#( Action<#variant_ref> #match_param ),*);
+                public abstract T Accept<T>(#qualified_visitor<T> visitor);
+
+                // Generate the class declarations for each variant of the enum.
+                #( #variant_structs )*
+            }
         }
+    } else {
+        quote! {
+            // Generate an interface for the enum, with methods for exhaustively matching on
+            // the variants.
+            #docs
+            public interface #interface
+            {
+                T Match<T>(#( Func<#variant_ref, T> #match_param ),*);
+                void Match(#( Action<#variant_ref> #match_param ),*);
+                T Accept<T>(#qualified_visitor<T> visitor);
+            }
+
+            // Generate wrapper class in order to namespace the variants.
+            #docs
+            public static partial class #wrapper_class
+            {
+                // Generate the struct declarations for each variant of the enum.
+                #( #variant_structs )*
+            }
+        }
+    };
+
+    let declarations = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #enum_declaration
+
+            // Generate a visitor interface with one method for each variant.
+            public interface #visitor<T>
+            {
+                #(
+                    T #visit_fn(#variant_ref value);
+                )*
+            }
+        },
+    );
+
+    quote! {
+        #declarations
 
         // Generate the raw struct, which contains the discriminant and a union of all the
         // possible variants. This needs to match the `RawEnum<D, V>` type on the Rust side.
@@ -352,6 +595,70 @@ fn quote_complex_enum(export: &NamedType, schema: &Enum, types: &TypeMap) -> Tok
     }
 }
 
+/// Quotes the additional members of a variant class generated with `--enum-classes`.
+///
+/// Variant structs get a parameterless constructor, value equality, and positional
+/// deconstruction from C#, so the equivalent members are generated for variant classes.
+fn quote_variant_class_members(
+    ident: &Ident,
+    fields: &[Field<'_>],
+    types: &TypeMap,
+) -> TokenStream {
+    let field_ident = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| strukt::field_ident(field.name, index, &[]))
+        .collect::<Vec<_>>();
+    let arg_ident = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| strukt::arg_ident(field.name, index))
+        .collect::<Vec<_>>();
+    let field_ty = fields
+        .iter()
+        .map(|field| generate::quote_cs_type_for_schema(&field.schema, types))
+        .collect::<Vec<_>>();
+
+    // NOTE: Variants with fields already have a basic constructor, and C# doesn't
+    // allow deconstructing into zero values.
+    let constructor_or_deconstruct = if fields.is_empty() {
+        quote! {
+            public #ident() { }
+        }
+    } else {
+        quote! {
+            public void Deconstruct(#( out #field_ty #arg_ident ),*)
+            {
+                #(
+                    #arg_ident = this.#field_ident;
+                )*
+            }
+        }
+    };
+
+    quote! {
+        #constructor_or_deconstruct
+
+        public override bool Equals(object obj)
+        {
+            return obj is #ident other
+                #( && EqualityComparer<#field_ty>.Default.Equals(this.#field_ident, other.#field_ident) )*;
+        }
+
+        public override int GetHashCode()
+        {
+            unchecked
+            {
+                int hash = 17;
+                #(
+                    hash = hash * 31 + EqualityComparer<#field_ty>.Default.GetHashCode(this.#field_ident);
+                )*
+                return hash;
+            }
+        }
+    }
+}
+
 /// Returns the name of the wrapper class generated for for the specified exported type.
 fn wrapper_class_name(export: &NamedType) -> Ident {
     format_ident!("{}", &*export.type_name.name)
@@ -370,12 +677,13 @@ fn variant_struct_name(variant: &Variant) -> Ident {
 }
 
 /// Generates a type reference to the C# type for the specified enum variant.
-fn variant_struct_type_ref(export: &NamedType, variant: &Variant) -> TokenStream {
+fn variant_struct_type_ref(export: &NamedType, variant: &Variant, types: &TypeMap) -> TokenStream {
     let wrapper_class = wrapper_class_name(export);
     let variant_struct_name = variant_struct_name(variant);
-    quote! {
-        global::#wrapper_class.#variant_struct_name
-    }
+    types.qualify(
+        &export.type_name,
+        quote! { #wrapper_class.#variant_struct_name },
+    )
 }
 
 fn raw_variant_struct_name(type_name: &TypeName, variant_name: &str) -> Ident {
@@ -383,14 +691,37 @@ fn raw_variant_struct_name(type_name: &TypeName, variant_name: &str) -> Ident {
     format_ident!("{}__{}", raw_name, variant_name)
 }
 
-fn raw_variant_struct_type_ref(export: &NamedType, variant: &Variant) -> TokenStream {
+fn raw_variant_struct_type_ref(
+    export: &NamedType,
+    variant: &Variant,
+    types: &TypeMap,
+) -> TokenStream {
     let wrapper_class = wrapper_class_name(export);
     let raw_variant_struct_name = raw_variant_struct_name(&export.type_name, &variant.name());
-    quote! {
-        global::#wrapper_class.#raw_variant_struct_name
-    }
+    types.qualify(
+        &export.type_name,
+        quote! { #wrapper_class.#raw_variant_struct_name },
+    )
 }
 
 fn union_struct_name(type_name: &TypeName) -> Ident {
-    format_ident!("{}_Data_Raw", type_name.name)
+    format_ident!("{}_Data_Raw", type_name.mangled_name())
+}
+
+/// Quotes the doc comment for the variant at `index`, if it has one.
+fn quote_variant_docs(export: &NamedType, index: usize) -> TokenStream {
+    // NOTE: The unknown variant of a non-exhaustive enum is the only variant that
+    // doesn't exist in Rust, so it's the only one without an entry.
+    if export.is_non_exhaustive && index == export.variant_docs.len() {
+        return docs::quote_doc_comment(Some(
+            "A variant that isn't declared in the bindings, e.g. a variant that was added \
+             to the enum in a newer version of the native library.",
+        ));
+    }
+
+    let docs = export
+        .variant_docs
+        .get(index)
+        .and_then(|docs| docs.as_deref());
+    docs::quote_doc_comment(docs)
 }
@@ -0,0 +1,151 @@
+//! Code generation for types that implement `PartialEq` and `Hash`.
+//!
+//! Equality is exposed in C# by implementing `IEquatable<T>`, overriding `Equals` and
+//! `GetHashCode`, and overloading the `==` and `!=` operators. All comparisons call
+//! back into Rust so that custom `PartialEq` impls behave the same in C#, for both
+//! handle types and types marshaled by value.
+
+use crate::generate::{func, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{BindingStyle, FnArg, NamedType, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the interface implemented by types that implement `PartialEq`, if any.
+pub fn quote_interface(export: &NamedType) -> Option<TokenStream> {
+    export.eq_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    Some(quote! { IEquatable<#ident> })
+}
+
+/// Quotes the equality members for a type that implements `PartialEq`.
+///
+/// If the type doesn't also implement `Hash`, `GetHashCode` returns a constant value.
+/// This is slow when the type is used as a dictionary key, but is consistent with
+/// `Equals`, which a hash based on the C# representation wouldn't be.
+pub fn quote_members(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let eq_fn = export.eq_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    let repr = Repr::Named(export.type_name.clone());
+
+    let equals = match &export.binding_style {
+        BindingStyle::Handle => {
+            let other = FnArg::new("other", Repr::Ref(Box::new(repr.clone())));
+            let block = func::quote_wrapper_block(
+                eq_fn,
+                Some(quote! { this._handle }),
+                &[other],
+                Some(&Repr::Bool),
+                types,
+            );
+
+            quote! {
+                public bool Equals(#ident other)
+                {
+                    if (ReferenceEquals(other, null))
+                    {
+                        return false;
+                    }
+
+                    #block
+                }
+            }
+        }
+
+        // NOTE: The struct is passed to Rust by value, so we pass a copy of `this` as a
+        // regular argument in order for it to be converted to its raw representation.
+        BindingStyle::Value(_)
+        | BindingStyle::Marshal(..)
+        | BindingStyle::Custom(_)
+        | BindingStyle::Transparent(_) => {
+            let args = [
+                FnArg::new("self", repr.clone()),
+                FnArg::new("other", repr.clone()),
+            ];
+            let block = func::quote_wrapper_block(eq_fn, None, &args, Some(&Repr::Bool), types);
+
+            quote! {
+                public bool Equals(#ident other)
+                {
+                    var self = this;
+                    #block
+                }
+            }
+        }
+    };
+
+    let hash_code = match &export.hash_fn {
+        Some(hash_fn) => {
+            let (receiver, args, self_decl) = match &export.binding_style {
+                BindingStyle::Handle => (Some(quote! { this._handle }), vec![], None),
+                BindingStyle::Value(_)
+                | BindingStyle::Marshal(..)
+                | BindingStyle::Custom(_)
+                | BindingStyle::Transparent(_) => (
+                    None,
+                    vec![FnArg::new("self", repr.clone())],
+                    Some(quote! { var self = this; }),
+                ),
+            };
+            let block =
+                func::quote_wrapper_block(hash_fn, receiver, &args, Some(&Repr::U64), types);
+
+            quote! {
+                public override int GetHashCode()
+                {
+                    return __RustHash().GetHashCode();
+                }
+
+                private ulong __RustHash()
+                {
+                    #self_decl
+                    #block
+                }
+            }
+        }
+
+        None => quote! {
+            public override int GetHashCode()
+            {
+                return 0;
+            }
+        },
+    };
+
+    // Handle and marshaled types are reference types, so the operators need to handle
+    // `null`.
+    let op_equals = match &export.binding_style {
+        BindingStyle::Handle | BindingStyle::Marshal(..) => quote! {
+            if (ReferenceEquals(left, null))
+            {
+                return ReferenceEquals(right, null);
+            }
+
+            return left.Equals(right);
+        },
+
+        BindingStyle::Value(_) | BindingStyle::Custom(_) | BindingStyle::Transparent(_) => quote! {
+            return left.Equals(right);
+        },
+    };
+
+    Some(quote! {
+        #equals
+
+        public override bool Equals(object obj)
+        {
+            return obj is #ident other && Equals(other);
+        }
+
+        #hash_code
+
+        public static bool operator ==(#ident left, #ident right)
+        {
+            #op_equals
+        }
+
+        public static bool operator !=(#ident left, #ident right)
+        {
+            return !(left == right);
+        }
+    })
+}
@@ -0,0 +1,36 @@
+//! Code generation for event queues.
+//!
+//! On the Rust side, a `cs_bindgen::event::EventQueue<T>` is marshaled as a
+//! `RawIter` that yields the events that are currently pending, so C# pulls events
+//! from the queue using the same built-in functions as for iterators. The queue is
+//! exposed in C# as the public `EventQueue<T>` class, which raises its `Received`
+//! event for each pending event when `Poll()` is called. As with iterators, the
+//! generic `RustEventQueue<R, T>` class implements the queue for every event type, so
+//! we only need to generate the `__FromRaw` overload for each concrete event type.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the idiomatic C# type for an event queue with the specified event type.
+pub fn quote_cs_type(event: &Repr, types: &TypeMap) -> TokenStream {
+    let event = generate::quote_cs_type_for_repr(event, types);
+    quote! { EventQueue<#event> }
+}
+
+/// Generates the conversion function for an event queue with the specified event
+/// type.
+pub fn quote_events_conversion(event: &Repr, types: &TypeMap) -> TokenStream {
+    let cs_ty = quote_cs_type(event, types);
+    let event_ty = generate::quote_cs_type_for_repr(event, types);
+    let raw_event_ty = binding::raw_type_from_repr(event, types);
+    let from_raw = binding::from_raw_fn_ident();
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_raw(RawIter raw, out #cs_ty result)
+        {
+            result = new RustEventQueue<#raw_event_ty, #event_ty>(raw, #from_raw);
+        }
+    })
+}
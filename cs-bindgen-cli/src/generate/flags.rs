@@ -0,0 +1,131 @@
+//! Code generation for types exported with `#[cs_bindgen(flags)]`.
+//!
+//! Flags types are generated as a C# enum with the `[Flags]` attribute, using the
+//! integer type of the flags as the underlying type of the enum. The associated
+//! constants of the type become the members of the enum, including any constants that
+//! combine multiple flags. Values are marshaled as the raw integer value, so the
+//! conversions are just casts.
+
+use crate::generate::{binding, docs, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{
+    schematic::{Schema, TypeName},
+    Const, ConstValue, Export, NamedType, Repr,
+};
+use heck::*;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+
+pub fn quote_flags(
+    export: &NamedType,
+    exports: &[Export],
+    types: &TypeMap,
+) -> Result<TokenStream, failure::Error> {
+    let ident = export.type_name.ident();
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let bits_ty = quote_bits_type(export);
+
+    let mut has_zero = false;
+    let mut members = Vec::new();
+    for member in exports.iter().filter_map(|item| match item {
+        Export::Const(item) if is_flag(item, &export.type_name) => Some(item),
+        _ => None,
+    }) {
+        let member_ident = format_ident!("{}", member.name.to_camel_case());
+        let value = match &member.value {
+            Some(ConstValue::Int(value)) => {
+                has_zero |= *value == 0;
+                Literal::i64_unsuffixed(*value)
+            }
+            Some(ConstValue::UInt(value)) => {
+                has_zero |= *value == 0;
+                Literal::u64_unsuffixed(*value)
+            }
+
+            value => {
+                return Err(failure::format_err!(
+                    "Invalid value for flag {} of {:?}: {:?}",
+                    member.name,
+                    export.type_name,
+                    value,
+                ))
+            }
+        };
+
+        members.push(quote! { #member_ident = #value });
+    }
+
+    // Include a member for the empty set of flags, since C# code conventionally checks
+    // against `None` rather than comparing to `0`.
+    if !has_zero {
+        members.insert(0, quote! { None = 0 });
+    }
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#bits_ty raw, out #qualified_ident result)
+        {
+            result = (#qualified_ident)raw;
+        }
+
+        internal static void #into_raw(#qualified_ident value, out #bits_ty result)
+        {
+            result = (#bits_ty)value;
+        }
+    });
+
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+    let declaration = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            [Flags]
+            public enum #ident : #bits_ty
+            {
+                #( #members ),*
+            }
+        },
+    );
+
+    Ok(quote! {
+        #declaration
+        #raw_conversions
+    })
+}
+
+/// Returns `true` if `export` is one of the flags of the flags type `type_name`, i.e.
+/// it's an associated constant of the flags type with the flags type as its type.
+pub fn is_flag(export: &Const, type_name: &TypeName) -> bool {
+    export.self_type.as_ref() == Some(type_name)
+        && matches!(&export.repr, Repr::Named(repr) if repr == type_name)
+}
+
+/// Quotes the integer type used to store the bits of a flags type.
+///
+/// This is both the underlying type of the C# enum and the raw type used when
+/// marshaling the flags.
+pub fn quote_bits_type(export: &NamedType) -> TokenStream {
+    // NOTE: The macro only allows flags types with a single integer field, so a
+    // different schema here indicates a bug in the macro.
+    let field = export
+        .schema()
+        .and_then(Schema::as_struct_like)
+        .and_then(|schema| schema.fields.into_iter().next())
+        .unwrap_or_else(|| panic!("Flags type {:?} has no bits field", export.type_name));
+
+    match &field.schema {
+        Schema::I8 => quote! { sbyte },
+        Schema::I16 => quote! { short },
+        Schema::I32 => quote! { int },
+        Schema::I64 => quote! { long },
+        Schema::U8 => quote! { byte },
+        Schema::U16 => quote! { ushort },
+        Schema::U32 => quote! { uint },
+        Schema::U64 => quote! { ulong },
+
+        schema => panic!(
+            "Invalid bits type for flags type {:?}: {:?}",
+            export.type_name, schema,
+        ),
+    }
+}
@@ -0,0 +1,721 @@
+//! A lightweight pretty-printer for the generated C# code.
+//!
+//! The bindings are generated as a stream of tokens, and converting the tokens
+//! directly to a string puts everything on a single line. The formatter instead walks
+//! the token stream and lays out the code in the usual C# style: One statement per
+//! line, braces on their own lines, four spaces of indentation per block, and a blank
+//! line between members of a type.
+//!
+//! The formatter only has the tokens to go on, so the spacing around a few ambiguous
+//! tokens (generic brackets, pointer and nullable types, and casts) is decided with
+//! heuristics that work for the patterns used in the generated code. The output only
+//! depends on the input tokens, so generating the bindings from the same declarations
+//! always produces the same output.
+
+use crate::generate::{docs, trace};
+use proc_macro2::{Delimiter, Literal, Spacing, TokenStream, TokenTree};
+use std::collections::HashSet;
+
+/// The indentation for each level of nesting.
+const INDENT: &str = "    ";
+
+/// Keywords that are followed by a space before an opening parenthesis.
+const PAREN_KEYWORDS: &[&str] = &[
+    "if", "for", "foreach", "while", "switch", "catch", "using", "fixed", "lock", "return", "in",
+    "when", "await", "throw",
+];
+
+/// Keywords that can be followed by an operand, e.g. `return -1`.
+const OPERAND_KEYWORDS: &[&str] = &["return", "in", "case", "await", "throw"];
+
+/// Keywords that start a type or namespace declaration.
+const DECLARATION_KEYWORDS: &[&str] = &["class", "struct", "interface", "namespace"];
+
+/// Built-in C# types, which can be followed by `*` in a pointer type or `?` in a
+/// nullable type.
+const BUILTIN_TYPES: &[&str] = &[
+    "bool", "byte", "sbyte", "short", "ushort", "int", "uint", "long", "ulong", "char", "float",
+    "double", "void", "string", "object",
+];
+
+/// Formats the generated code.
+pub fn format(tokens: TokenStream) -> String {
+    let mut formatter = Formatter {
+        out: String::new(),
+        indent: 0,
+        prev: Prev::LineStart,
+        line: Vec::new(),
+        pending_blank: false,
+    };
+    formatter.write_tokens(tokens, Block::Declarations);
+    formatter.newline();
+    formatter.out
+}
+
+/// The kind of block that a sequence of tokens is contained in, which determines where
+/// lines are broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    /// The top level of the file, or the body of a type or namespace.
+    Declarations,
+
+    /// The body of an enum, where each variant is on its own line.
+    Enum,
+
+    /// Any other braced block, e.g. a method body.
+    Statements,
+
+    /// The contents of parentheses or brackets.
+    Inline,
+}
+
+/// A summary of the previously written token, used to determine spacing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Prev {
+    LineStart,
+    Open,
+    Ident(String),
+    Literal,
+    Punct {
+        ch: char,
+        joint: bool,
+        kind: PunctKind,
+    },
+    CloseParen {
+        cast: bool,
+    },
+    CloseBracket,
+    CloseBrace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PunctKind {
+    Binary,
+    Unary,
+    PathSeparator,
+    GenericOpen,
+    GenericClose,
+    Pointer,
+    Nullable,
+}
+
+struct Formatter {
+    out: String,
+    indent: usize,
+    prev: Prev,
+
+    /// The identifiers written on the current line.
+    line: Vec<String>,
+
+    /// Whether a blank line should be written before the next token.
+    pending_blank: bool,
+}
+
+impl Formatter {
+    fn write_tokens(&mut self, tokens: TokenStream, block: Block) {
+        let tokens = flatten(tokens);
+        let generics = find_generics(&tokens);
+
+        let mut index = 0;
+        while index < tokens.len() {
+            let next = tokens.get(index + 1);
+
+            // NOTE: Directives are handled before writing any pending blank line, since
+            // they determine where the blank line goes.
+            if let (TokenTree::Ident(ident), Some(TokenTree::Literal(text))) =
+                (&tokens[index], next)
+            {
+                if trace::is_directive_placeholder(ident) {
+                    self.write_directive(text);
+                    index += 2;
+                    continue;
+                }
+            }
+
+            if self.pending_blank && self.prev == Prev::LineStart {
+                self.out.push('\n');
+            }
+            self.pending_blank = false;
+
+            match &tokens[index] {
+                TokenTree::Ident(ident) if docs::is_doc_placeholder(ident) => {
+                    if let Some(TokenTree::Literal(text)) = next {
+                        self.write_doc_comment(text, block);
+                        index += 2;
+                        continue;
+                    }
+
+                    self.write_word(ident.to_string());
+                }
+
+                TokenTree::Ident(ident) => {
+                    let ident = ident.to_string();
+                    self.write_word(ident.clone());
+                    self.line.push(ident.clone());
+                    self.prev = Prev::Ident(ident);
+                }
+
+                TokenTree::Literal(literal) => {
+                    self.write_word(literal.to_string());
+                    self.prev = Prev::Literal;
+                }
+
+                TokenTree::Punct(_) => self.write_punct(index, &tokens, &generics, block),
+
+                TokenTree::Group(group) => match group.delimiter() {
+                    Delimiter::Brace => self.write_braces(group.stream(), next, block),
+                    Delimiter::Parenthesis => {
+                        let cast = self.is_cast(&group.stream(), next);
+                        self.write_parens(group.stream(), cast);
+                    }
+                    Delimiter::Bracket => self.write_brackets(group.stream(), block),
+                    Delimiter::None => unreachable!("Undelimited groups are flattened"),
+                },
+            }
+
+            index += 1;
+        }
+    }
+
+    fn write_word(&mut self, word: String) {
+        let space = match &self.prev {
+            Prev::LineStart | Prev::Open => false,
+            Prev::Punct { ch: '.', .. } => false,
+            Prev::Punct { kind, .. } => !no_space_after(*kind),
+            Prev::CloseParen { cast } => !cast,
+            _ => true,
+        };
+
+        self.write(&word, space);
+    }
+
+    fn write_punct(
+        &mut self,
+        index: usize,
+        tokens: &[TokenTree],
+        generics: &HashSet<usize>,
+        block: Block,
+    ) {
+        let punct = match &tokens[index] {
+            TokenTree::Punct(punct) => punct,
+            _ => unreachable!(),
+        };
+        let ch = punct.as_char();
+        let joint = punct.spacing() == Spacing::Joint;
+        let next = tokens.get(index + 1);
+        let after_next = tokens.get(index + 2);
+
+        let kind = if generics.contains(&index) {
+            if ch == '<' {
+                PunctKind::GenericOpen
+            } else {
+                PunctKind::GenericClose
+            }
+        } else if ch == ':'
+            && matches!(
+                self.prev,
+                Prev::Punct {
+                    ch: ':',
+                    joint: true,
+                    ..
+                }
+            )
+        {
+            PunctKind::PathSeparator
+        } else if ch == '*' && self.follows_type() && is_declarator(next, after_next) {
+            PunctKind::Pointer
+        } else if ch == '?' && self.follows_type() && is_declarator(next, after_next) {
+            PunctKind::Nullable
+        } else if (ch == '!' || ch == '~') && !joint {
+            PunctKind::Unary
+        } else if ch == '@' {
+            // A verbatim identifier, e.g. `@event`.
+            PunctKind::Unary
+        } else if "-+&*".contains(ch) && self.expects_operand() {
+            PunctKind::Unary
+        } else {
+            PunctKind::Binary
+        };
+
+        let is_postfix = (ch == '+' || ch == '-')
+            && joint
+            && matches!(self.prev, Prev::Ident(_))
+            && is_punct(next, ch)
+            && is_end_of_expression(after_next);
+        let is_case_label = ch == ':' && self.is_case_label(block);
+
+        let space = match &self.prev {
+            Prev::LineStart | Prev::Open => false,
+            _ if kind != PunctKind::Binary && kind != PunctKind::Unary => false,
+            Prev::Punct { joint: true, .. } => false,
+            Prev::Punct { ch: '.', .. } => false,
+            Prev::Punct { kind, .. } if no_space_after(*kind) => false,
+            _ if ch == ',' || ch == ';' || ch == '.' => false,
+            _ if ch == ':' && joint && is_punct(next, ':') => false,
+            _ => !is_postfix && !is_case_label,
+        };
+
+        self.write(&ch.to_string(), space);
+        self.prev = Prev::Punct { ch, joint, kind };
+
+        match ch {
+            ';' if block != Block::Inline => {
+                // Separate the `using` directives from the declarations that follow.
+                let is_using = self.line.first().map(String::as_str) == Some("using");
+                self.newline();
+                if block == Block::Declarations && is_using && !is_ident(next, "using") {
+                    self.pending_blank = true;
+                }
+            }
+            ',' if block == Block::Enum => self.newline(),
+            ':' if is_case_label => self.newline(),
+            _ => {}
+        }
+    }
+
+    fn write_braces(&mut self, tokens: TokenStream, next: Option<&TokenTree>, outer: Block) {
+        if tokens.is_empty() {
+            let space = self.prev != Prev::LineStart;
+            self.write("{ }", space);
+        } else {
+            let block = if self.line.iter().any(|word| word == "enum") {
+                Block::Enum
+            } else if self
+                .line
+                .iter()
+                .any(|word| DECLARATION_KEYWORDS.contains(&word.as_str()))
+            {
+                Block::Declarations
+            } else {
+                Block::Statements
+            };
+
+            self.newline();
+            self.write("{", false);
+            self.newline();
+
+            self.indent += 1;
+            self.write_tokens(tokens, block);
+            self.newline();
+            self.indent -= 1;
+
+            self.pending_blank = false;
+            self.write("}", false);
+        }
+
+        self.prev = Prev::CloseBrace;
+
+        // Braces that are part of an expression are followed by the rest of the
+        // expression, e.g. the `;` after an initializer.
+        if !is_punct(next, ';') && !is_punct(next, ',') && !is_punct(next, '.') {
+            if next.is_some() || outer != Block::Inline {
+                self.newline();
+                self.pending_blank = outer == Block::Declarations;
+            }
+        }
+    }
+
+    fn write_parens(&mut self, tokens: TokenStream, cast: bool) {
+        let space = match &self.prev {
+            Prev::LineStart | Prev::Open => false,
+            Prev::Ident(word) => PAREN_KEYWORDS.contains(&word.as_str()),
+            Prev::Punct { ch: '.', .. } => false,
+            Prev::Punct { joint: true, .. } => false,
+            Prev::Punct {
+                kind: PunctKind::GenericClose,
+                ..
+            } => false,
+            Prev::Punct { kind, .. } => !no_space_after(*kind),
+            Prev::CloseParen { cast } => !cast,
+            Prev::CloseBracket => false,
+            _ => true,
+        };
+
+        self.write("(", space);
+        self.prev = Prev::Open;
+        self.write_tokens(tokens, Block::Inline);
+        self.write(")", false);
+        self.prev = Prev::CloseParen { cast };
+    }
+
+    fn write_brackets(&mut self, tokens: TokenStream, block: Block) {
+        let is_attribute = self.prev == Prev::LineStart && block != Block::Inline;
+        let space = match &self.prev {
+            Prev::LineStart | Prev::Open => false,
+            Prev::Ident(_) | Prev::CloseParen { .. } | Prev::CloseBracket => false,
+            Prev::Punct { kind, .. } => *kind == PunctKind::Binary,
+            _ => true,
+        };
+
+        self.write("[", space);
+        self.prev = Prev::Open;
+        self.write_tokens(tokens, Block::Inline);
+        self.write("]", false);
+        self.prev = Prev::CloseBracket;
+
+        if is_attribute {
+            self.newline();
+        }
+    }
+
+    fn write_doc_comment(&mut self, text: &Literal, block: Block) {
+        self.newline();
+
+        // Separate the member from the one before it, unless it's the first member.
+        if block == Block::Declarations
+            && !self.out.is_empty()
+            && !self.out.ends_with("{\n")
+            && !self.out.ends_with("\n\n")
+        {
+            self.out.push('\n');
+        }
+
+        for line in docs::doc_comment_lines(text) {
+            self.write(line.trim_end(), false);
+            self.newline();
+        }
+    }
+
+    /// Writes a preprocessor directive on its own line, without any indentation.
+    fn write_directive(&mut self, text: &Literal) {
+        let text = trace::directive_text(text);
+        self.newline();
+
+        // Keep the blank line between declarations outside of the conditional section,
+        // i.e. before an `#if` and after an `#endif`.
+        if self.pending_blank && !text.starts_with("#endif") {
+            self.out.push('\n');
+            self.pending_blank = false;
+        }
+
+        self.out.push_str(&text);
+        self.newline();
+    }
+
+    /// Writes `text` at the current position, preceded by the indentation if at the
+    /// start of a line.
+    fn write(&mut self, text: &str, space: bool) {
+        if self.prev == Prev::LineStart {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+        } else if space {
+            self.out.push(' ');
+        }
+
+        self.out.push_str(text);
+    }
+
+    fn newline(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+
+        self.prev = Prev::LineStart;
+        self.line.clear();
+    }
+
+    /// Returns `true` if the previous token could be the end of a type, e.g. `int` or
+    /// `List<int>`.
+    fn follows_type(&self) -> bool {
+        match &self.prev {
+            Prev::Ident(word) => BUILTIN_TYPES.contains(&word.as_str()) || is_type_name(word),
+            Prev::Punct { kind, .. } => *kind == PunctKind::GenericClose,
+            Prev::CloseBracket => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the next token would be the start of an operand, i.e. an
+    /// operator at this position is a unary operator.
+    fn expects_operand(&self) -> bool {
+        match &self.prev {
+            Prev::LineStart | Prev::Open => true,
+            Prev::Ident(word) => OPERAND_KEYWORDS.contains(&word.as_str()),
+            Prev::Punct { kind, .. } => *kind == PunctKind::Binary || *kind == PunctKind::Unary,
+            _ => false,
+        }
+    }
+
+    fn is_case_label(&self, block: Block) -> bool {
+        block == Block::Statements
+            && match self.line.first().map(String::as_str) {
+                Some("case") | Some("default") => true,
+                _ => false,
+            }
+    }
+
+    /// Returns `true` if a parenthesized group at this position is a cast, i.e. it
+    /// isn't a call or a control flow condition, it only contains a type, and it's
+    /// followed by an operand.
+    fn is_cast(&self, tokens: &TokenStream, next: Option<&TokenTree>) -> bool {
+        let in_operand_position = match &self.prev {
+            Prev::Open => true,
+            Prev::Ident(word) => word == "return",
+            Prev::Punct { kind, .. } => *kind == PunctKind::Binary,
+            _ => false,
+        };
+
+        let is_type = !tokens.is_empty()
+            && flatten(tokens.clone()).iter().all(|token| match token {
+                TokenTree::Ident(_) => true,
+                TokenTree::Punct(punct) => ".*<>?,:".contains(punct.as_char()),
+                TokenTree::Group(group) => group.delimiter() == Delimiter::Bracket,
+                TokenTree::Literal(_) => false,
+            });
+
+        let followed_by_operand = match next {
+            Some(TokenTree::Ident(_)) | Some(TokenTree::Literal(_)) => true,
+            Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+            _ => false,
+        };
+
+        in_operand_position && is_type && followed_by_operand
+    }
+}
+
+fn no_space_after(kind: PunctKind) -> bool {
+    match kind {
+        PunctKind::Unary | PunctKind::PathSeparator | PunctKind::GenericOpen => true,
+        _ => false,
+    }
+}
+
+/// Expands any groups without delimiters, which are only used to preserve precedence
+/// in macro expansions.
+fn flatten(tokens: TokenStream) -> Vec<TokenTree> {
+    let mut result = Vec::new();
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                result.extend(flatten(group.stream()));
+            }
+            token => result.push(token),
+        }
+    }
+    result
+}
+
+/// Returns the indices of the `<` and `>` tokens that are generic brackets, rather
+/// than comparisons.
+///
+/// A `<` following a type name starts a generic argument list if it's closed by a
+/// matching `>` with only types in between.
+fn find_generics(tokens: &[TokenTree]) -> HashSet<usize> {
+    let mut generics = HashSet::new();
+    for start in 0..tokens.len() {
+        let follows_type = match (start.checked_sub(1).map(|i| &tokens[i]), &tokens[start]) {
+            (Some(TokenTree::Ident(ident)), TokenTree::Punct(punct)) => {
+                punct.as_char() == '<'
+                    && punct.spacing() == Spacing::Alone
+                    && is_type_name(&ident.to_string())
+            }
+            _ => false,
+        };
+        if !follows_type {
+            continue;
+        }
+
+        let mut depth = 0;
+        let mut brackets = Vec::new();
+        for (index, token) in tokens.iter().enumerate().skip(start) {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '<' => {
+                    depth += 1;
+                    brackets.push(index);
+                }
+
+                TokenTree::Punct(punct) if punct.as_char() == '>' => {
+                    depth -= 1;
+                    brackets.push(index);
+                    if depth == 0 {
+                        generics.extend(brackets.drain(..));
+                        break;
+                    }
+                }
+
+                TokenTree::Punct(punct) if ".,?*:".contains(punct.as_char()) => {}
+                TokenTree::Ident(_) => {}
+                TokenTree::Group(group) if group.delimiter() != Delimiter::Brace => {}
+                _ => break,
+            }
+        }
+    }
+
+    generics
+}
+
+/// Returns `true` if the identifier looks like the name of a type, i.e. it's in
+/// `PascalCase` or it's one of the generated raw types.
+fn is_type_name(ident: &str) -> bool {
+    ident
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_ascii_uppercase() || c == '_')
+}
+
+/// Returns `true` if the tokens following a `*` or `?` are consistent with it being
+/// part of a type, e.g. the name of a declaration or the end of a type argument list.
+fn is_declarator(next: Option<&TokenTree>, after_next: Option<&TokenTree>) -> bool {
+    match next {
+        None => true,
+        Some(TokenTree::Punct(punct)) => ">,*?".contains(punct.as_char()),
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Bracket,
+        Some(TokenTree::Ident(_)) => match after_next {
+            None => true,
+            Some(TokenTree::Punct(punct)) => ";=,".contains(punct.as_char()),
+            Some(TokenTree::Group(group)) => group.delimiter() != Delimiter::Bracket,
+            _ => false,
+        },
+        Some(TokenTree::Literal(_)) => false,
+    }
+}
+
+fn is_end_of_expression(token: Option<&TokenTree>) -> bool {
+    match token {
+        None => true,
+        Some(TokenTree::Punct(punct)) => ";,".contains(punct.as_char()),
+        _ => false,
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    match token {
+        Some(TokenTree::Punct(punct)) => punct.as_char() == ch,
+        _ => false,
+    }
+}
+
+fn is_ident(token: Option<&TokenTree>, word: &str) -> bool {
+    match token {
+        Some(TokenTree::Ident(ident)) => ident == word,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn generics() {
+        let tokens = quote! {
+            List<int> values = new List<int>();
+            Dictionary<string, List<int>> lookup;
+        };
+
+        assert_eq!(
+            "List<int> values = new List<int>();\n\
+             Dictionary<string, List<int>> lookup;\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn pointer_and_nullable_types() {
+        let tokens = quote! {
+            byte* ptr;
+            RawSlice* slices;
+            int? count;
+            int area = width * height;
+            int offset = -1;
+        };
+
+        assert_eq!(
+            "byte* ptr;\n\
+             RawSlice* slices;\n\
+             int? count;\n\
+             int area = width * height;\n\
+             int offset = -1;\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn casts() {
+        let tokens = quote! {
+            IntPtr handle = (IntPtr)ptr;
+            byte* bytes = (byte*)handle.ToPointer();
+            int sum = (a + b) * c;
+        };
+
+        assert_eq!(
+            "IntPtr handle = (IntPtr)ptr;\n\
+             byte* bytes = (byte*)handle.ToPointer();\n\
+             int sum = (a + b) * c;\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn enums() {
+        let tokens = quote! {
+            public enum Wind { East = 0, South = 1, West = 2, North = 3 }
+        };
+
+        assert_eq!(
+            "public enum Wind\n\
+             {\n    \
+                 East = 0,\n    \
+                 South = 1,\n    \
+                 West = 2,\n    \
+                 North = 3\n\
+             }\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn preprocessor_directives() {
+        let start = trace::quote_directive("#if DEBUG");
+        let end = trace::quote_directive("#endif");
+        let tokens = quote! {
+            class Hand
+            {
+                void Draw() { }
+                #start
+                void Log() { }
+                #end
+            }
+        };
+
+        assert_eq!(
+            "class Hand\n\
+             {\n    \
+                 void Draw() { }\n\
+             \n\
+             #if DEBUG\n    \
+                 void Log() { }\n\
+             #endif\n\
+             }\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn directives_in_statements() {
+        let start = trace::quote_directive("#if DEBUG");
+        let end = trace::quote_directive("#endif");
+        let tokens = quote! {
+            public void Discard() { #start Log(); #end Invoke(); }
+        };
+
+        assert_eq!(
+            "public void Discard()\n\
+             {\n\
+             #if DEBUG\n    \
+                 Log();\n\
+             #endif\n    \
+                 Invoke();\n\
+             }\n",
+            format(tokens),
+        );
+    }
+
+    #[test]
+    fn verbatim_identifiers() {
+        let tokens = quote! { Dispatch(@event, count); };
+        assert_eq!("Dispatch(@event, count);\n", format(tokens));
+    }
+}
@@ -1,35 +1,168 @@
 //! Code generation for exported functions and methods.
 
 use super::quote_cs_type_for_repr;
-use crate::generate::{binding, TypeMap};
+use crate::generate::{self, binding, borrow, callback, constant, result, trace, TypeMap};
 use cs_bindgen_shared::*;
 use heck::*;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, Punct, Spacing, TokenStream};
 use quote::*;
+use std::borrow::Cow;
+use syn::Ident;
 
+/// Generates the C# wrapper function for an exported function or method.
+///
+/// `out_params` names the C# `out` parameters that the trailing elements of a returned
+/// tuple are assigned to, if any.
 pub fn quote_wrapper_fn<'a>(
-    name: &str,
+    name: Ident,
     binding: &str,
     receiver: Option<TokenStream>,
     inputs: &[FnArg],
     output: Option<&Repr>,
+    out_params: &[Cow<'static, str>],
     types: &'a TypeMap,
 ) -> TokenStream {
-    // Determine the name of the wrapper function. The original function name is
-    // going to be in `snake_case`, so we need to convert it to `CamelCase` to keep
-    // with C# naming conventions.
-    let name = format_ident!("{}", name.to_camel_case());
+    // Determine if the function should be static or not based on whether or not it has
+    // a receiver.
+    let static_ = if receiver.is_some() {
+        TokenStream::default()
+    } else {
+        quote! { static }
+    };
 
-    let return_ty = match output {
-        Some(output) => quote_cs_type_for_repr(&output, types),
-        None => quote! { void },
+    let (return_ty, out_args, block) = match output {
+        Some(Repr::Tuple(elements)) if !out_params.is_empty() => {
+            quote_out_wrapper(binding, receiver, inputs, elements, out_params, types)
+        }
+
+        _ => (
+            quote_method_return_type(output, receiver.is_some(), types),
+            Vec::new(),
+            quote_wrapper_block(binding, receiver, inputs, output, types),
+        ),
     };
 
+    // NOTE: C# requires optional parameters to come last, so they can't be combined
+    // with `out` parameters.
+    let args = if out_args.is_empty() {
+        quote_optional_args(inputs, types).collect::<Vec<_>>()
+    } else {
+        quote_args(inputs, types).chain(out_args).collect()
+    };
+
+    quote! {
+        public #static_ #return_ty #name(#( #args ),*)
+        #block
+    }
+}
+
+/// Generates the return type, `out` parameters, and body of a wrapper function that
+/// splits the returned tuple into `out` parameters.
+///
+/// The whole tuple is converted as usual, after which the trailing elements are
+/// assigned to the `out` parameters. The remaining elements are returned directly if
+/// there's only one, or as a smaller tuple otherwise.
+fn quote_out_wrapper(
+    binding: &str,
+    receiver: Option<TokenStream>,
+    inputs: &[FnArg],
+    elements: &[Repr],
+    out_params: &[Cow<'static, str>],
+    types: &TypeMap,
+) -> (TokenStream, Vec<TokenStream>, TokenStream) {
+    let split = elements.len() - out_params.len();
+    let (returned, out_elements) = elements.split_at(split);
+
+    let tuple = Repr::Tuple(elements.to_vec());
+    let tuple_ty = quote_cs_type_for_repr(&tuple, types);
+    let raw_tuple_ty = binding::raw_type_from_repr(&tuple, types);
+
+    let out_ident = out_params
+        .iter()
+        .map(|name| quote_arg_ident(name))
+        .collect::<Vec<_>>();
+    let out_args = out_elements
+        .iter()
+        .zip(&out_ident)
+        .map(|(element, ident)| {
+            let ty = quote_cs_type_for_repr(element, types);
+            quote! { out #ty #ident }
+        })
+        .collect();
+
+    // NOTE: C# tuple fields are 1-indexed.
+    let out_field = (split + 1..=elements.len()).map(|index| format_ident!("Item{}", index));
+    let (return_ty, ret_expr) = match returned {
+        [] => (quote! { void }, quote! {}),
+        [element] => (
+            quote_cs_type_for_repr(element, types),
+            quote! { return __result.Item1; },
+        ),
+        _ => {
+            let field = (1..=split).map(|index| format_ident!("Item{}", index));
+            (
+                quote_cs_type_for_repr(&Repr::Tuple(returned.to_vec()), types),
+                quote! { return ( #( __result.#field ),* ); },
+            )
+        }
+    };
+
+    let binding_class = binding::bindings_class_ident();
+    let from_raw = binding::from_raw_fn_ident();
+    let ret = quote! { __raw_result };
+    let body = quote_wrapper_body(binding, receiver, inputs, None, Some(&ret), types);
+
+    let block = quote! {
+        {
+            unsafe {
+                #raw_tuple_ty #ret;
+                #body
+                #binding_class.#from_raw(#ret, out #tuple_ty __result);
+                #( #out_ident = __result.#out_field; )*
+                #ret_expr
+            }
+        }
+    };
+    let block = trace::quote_traced_block(binding, inputs, block, types);
+
+    (return_ty, out_args, block)
+}
+
+/// Returns the C# name of an exported function or method.
+///
+/// The original function name is going to be in `snake_case`, so we need to convert
+/// it to `CamelCase` to keep with C# naming conventions. Renamed functions use their
+/// new name as-is.
+pub fn fn_ident(name: &str, rename: Option<&str>) -> Ident {
+    match rename {
+        Some(rename) => format_ident!("{}", rename),
+        None => format_ident!("{}", name.to_camel_case()),
+    }
+}
+
+/// Generates the full body of a C# wrapper function, including the conversion of
+/// the return value.
+///
+/// This is separate from `quote_wrapper_fn` so that it can be used for functions
+/// with a different declaration, e.g. explicit interface implementations.
+pub fn quote_wrapper_block(
+    binding: &str,
+    receiver: Option<TokenStream>,
+    inputs: &[FnArg],
+    output: Option<&Repr>,
+    types: &TypeMap,
+) -> TokenStream {
+    let return_ty = quote_method_return_type(output, receiver.is_some(), types);
+
     // Generate the declaration for the output variable and return expression. We need
     // to treat `void` returns as a special case, since C# won't let you declare values
     // with type `void` (*sigh*).
+    //
+    // Zero-sized return values aren't returned by the raw binding, so the wrapper
+    // returns the default value instead.
+    let raw_output = output.filter(|output| !generate::is_zero_sized(output, types));
     let ret = quote! { __raw_result };
-    let ret_decl = match output {
+    let ret_decl = match raw_output {
         Some(schema) => {
             let raw_return_ty = binding::raw_type_from_repr(schema, types);
             quote! { #raw_return_ty #ret; }
@@ -42,6 +175,31 @@ pub fn quote_wrapper_fn<'a>(
     let from_raw = binding::from_raw_fn_ident();
 
     let ret_expr = match output {
+        Some(_) if raw_output.is_none() => quote! {
+            return default(#return_ty);
+        },
+
+        Some(output) if result::is_unit_result(output) => quote! {
+            #binding_class.#from_raw(#ret, out var _);
+        },
+
+        // Borrowed values are only valid while the object they borrow from is alive, so
+        // the conversion needs the handle of the owning object.
+        Some(output) if borrow::is_borrowed(output, receiver.is_some()) => {
+            assert!(
+                receiver.is_some(),
+                "Borrowed return values are only supported for methods: {}",
+                binding,
+            );
+
+            let from_borrowed = borrow::from_borrowed_fn_ident();
+            let owner = borrow::quote_owner();
+            quote! {
+                #binding_class.#from_borrowed(#ret, #owner, out #return_ty __result);
+                return __result;
+            }
+        }
+
         Some(_) => quote! {
             #binding_class.#from_raw(#ret, out #return_ty __result);
             return __result;
@@ -50,19 +208,16 @@ pub fn quote_wrapper_fn<'a>(
         None => quote! {},
     };
 
-    // Determine if the function should be static or not based on whether or not it has
-    // a receiver.
-    let static_ = if receiver.is_some() {
-        TokenStream::default()
-    } else {
-        quote! { static }
-    };
+    let body = quote_wrapper_body(
+        binding,
+        receiver,
+        &inputs,
+        None,
+        raw_output.map(|_| &ret),
+        types,
+    );
 
-    let args = quote_args(inputs, types);
-    let body = quote_wrapper_body(binding, receiver, &inputs, output.map(|_| &ret), types);
-
-    quote! {
-        public #static_ #return_ty #name(#( #args ),*)
+    let block = quote! {
         {
             unsafe {
                 #ret_decl
@@ -70,36 +225,161 @@ pub fn quote_wrapper_fn<'a>(
                 #ret_expr
             }
         }
+    };
+
+    trace::quote_traced_block(binding, inputs, block, types)
+}
+
+/// Quotes the C# return type for a function with the specified output.
+pub fn quote_return_type(output: Option<&Repr>, types: &TypeMap) -> TokenStream {
+    // NOTE: Functions returning `Result<(), E>` are treated as returning `void`, since
+    // the result only determines whether or not an exception is thrown.
+    match output {
+        Some(output) if result::is_unit_result(output) => quote! { void },
+        Some(Repr::Slice(element)) => borrow::quote_cs_type(element, types),
+        Some(output) => quote_cs_type_for_repr(&output, types),
+        None => quote! { void },
+    }
+}
+
+/// Quotes the C# return type for a function with the specified output, taking into
+/// account whether the function is a method.
+///
+/// Slices returned from methods borrow from `self` and are returned as a view, while
+/// slices returned from free functions are copied into the regular C# type.
+pub fn quote_method_return_type(
+    output: Option<&Repr>,
+    is_method: bool,
+    types: &TypeMap,
+) -> TokenStream {
+    match output {
+        Some(slice @ Repr::Slice(_)) if !is_method => quote_cs_type_for_repr(slice, types),
+        _ => quote_return_type(output, types),
     }
 }
 
+/// Generates the body of a C# wrapper function, converting the arguments and invoking
+/// the raw binding.
+///
+/// `completion` is the completion callback passed to the binding for async functions,
+/// which is passed after the regular arguments.
 pub fn quote_wrapper_body<'a>(
     binding_name: &str,
     receiver: Option<TokenStream>,
     args: &[FnArg],
+    completion: Option<TokenStream>,
     output: Option<&TokenStream>,
     types: &TypeMap,
 ) -> TokenStream {
-    let arg_name = args
-        .iter()
-        .map(|arg| format_ident!("{}", arg.name.to_mixed_case()));
-    let temp_arg_name = args.iter().map(|arg| format_ident!("__{}", arg.name));
-    let raw_ty = args
+    // NOTE: Zero-sized arguments aren't passed to the raw binding, so they're ignored
+    // entirely.
+    let raw_args = args
         .iter()
-        .map(|arg| binding::raw_type_from_repr(&arg.repr, types));
+        .filter(|arg| !generate::is_zero_sized(&arg.repr, types))
+        .collect::<Vec<_>>();
+
+    let arg_name = raw_args.iter().map(|arg| &arg.name);
+    let temp_arg_name = raw_args.iter().map(|arg| format_ident!("__{}", arg.name));
+    let is_async = completion.is_some();
 
     let bindings = binding::bindings_class_ident();
     let into_raw = binding::into_raw_fn_ident();
 
+    // Generate the conversion of each argument into its raw representation. Slices and
+    // string slices are pinned by `fold_fixed_blocks`, so we can pass a pointer to the
+    // pinned data directly instead of copying it.
+    let convert_arg = raw_args
+        .iter()
+        .zip(arg_name)
+        .zip(temp_arg_name.clone())
+        .map(|((arg, arg_name), temp_arg_name)| {
+            let raw_ty = binding::raw_type_from_repr(&arg.repr, types);
+            let arg_ident = quote_arg_ident(arg_name);
+            match &arg.repr {
+                Repr::Slice(_) | Repr::Str => {
+                    let fixed_ident = format_ident!("__fixed_{}", arg_name.to_mixed_case());
+                    quote! {
+                        #raw_ty #temp_arg_name = new #raw_ty((IntPtr)#fixed_ident, #arg_ident.Length);
+                    }
+                }
+
+                // Callbacks are wrapped in a `CallbackContext` by `fold_fixed_blocks`, so we
+                // pass the handle to the context along with the trampoline for the callback.
+                Repr::Fn { inputs, output } => {
+                    let context_ident = format_ident!("__context_{}", arg_name.to_mixed_case());
+                    let ptr_ident = callback::callback_ptr_ident(inputs, output);
+                    quote! {
+                        #raw_ty #temp_arg_name = new #raw_ty(#bindings.#ptr_ident, #context_ident.Handle);
+                    }
+                }
+
+                // Cancellation tokens are connected to a `RustCancelFlag` by
+                // `fold_fixed_blocks`, and Rust takes its own reference to the flag.
+                Repr::CancelFlag => {
+                    let cancel_ident = format_ident!("__cancel_{}", arg_name.to_mixed_case());
+                    quote! {
+                        #raw_ty #temp_arg_name = #cancel_ident.Ptr;
+                    }
+                }
+
+                // References to handle types borrow the handle, rather than transferring
+                // ownership of the value to Rust.
+                Repr::Ref(_) => quote! {
+                    #raw_ty #temp_arg_name = #arg_ident._handle;
+                },
+
+                // Mutable references are converted into a local raw value, and a pointer to
+                // the local is passed to Rust. The modified value is converted back after
+                // the call, see `write_back` below.
+                Repr::RefMut(inner) => {
+                    let raw_ty = binding::raw_type_from_repr(inner, types);
+                    quote! {
+                        #bindings.#into_raw(#arg_ident, out #raw_ty #temp_arg_name);
+                    }
+                }
+
+                _ => quote! {
+                    #bindings.#into_raw(#arg_ident, out #raw_ty #temp_arg_name);
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // The receiver and any borrowed arguments are only referenced by their raw handle
+    // once the call starts, so the GC is free to finalize them (dropping the Rust value)
+    // while Rust is still using them. Keeping them reachable until the call returns
+    // prevents this.
+    let keep_alive = receiver
+        .as_ref()
+        .map(|_| quote! { this })
+        .into_iter()
+        .chain(
+            raw_args
+                .iter()
+                .filter(|arg| matches!(arg.repr, Repr::Ref(_)))
+                .map(|arg| quote_arg_ident(&arg.name)),
+        )
+        .collect::<Vec<_>>();
+
     // Build the list of arguments to the wrapper function and insert the receiver at
     // the beginning of the list of arguments if necessary.
-    let mut invoke_arg = temp_arg_name
-        .clone()
-        .map(|name| name.into_token_stream())
+    let mut invoke_arg = raw_args
+        .iter()
+        .zip(temp_arg_name.clone())
+        .map(|(arg, name)| match &arg.repr {
+            Repr::RefMut(_) => quote! { &#name },
+            _ => name.into_token_stream(),
+        })
         .collect::<Vec<_>>();
     if let Some(receiver) = receiver {
         invoke_arg.insert(0, receiver);
     }
+    invoke_arg.extend(completion);
+
+    // Pass the panic message variable as the last argument so that we can check if
+    // the Rust function panicked.
+    let panic = binding::panic_ident();
+    invoke_arg.push(quote! { ref #panic });
 
     let raw_fn = format_ident!("{}", binding_name);
 
@@ -111,38 +391,257 @@ pub fn quote_wrapper_body<'a>(
         None => quote! {},
     };
 
+    // Write the modified values of any mutable references back to the caller. This
+    // happens after checking for a panic, since Rust doesn't write the value back if the
+    // function panics.
+    let from_raw = binding::from_raw_fn_ident();
+    let write_back = raw_args
+        .iter()
+        .zip(temp_arg_name)
+        .filter(|(arg, _)| matches!(arg.repr, Repr::RefMut(_)))
+        .map(|(arg, temp_arg_name)| {
+            let arg_name = quote_arg_ident(&arg.name);
+            quote! {
+                #bindings.#from_raw(#temp_arg_name, out #arg_name);
+            }
+        });
+
     let body = quote! {
-        #(
-            #bindings.#into_raw(#arg_name, out #raw_ty #temp_arg_name);
-        )*
+        #( #convert_arg )*
 
+        RawVec #panic = new RawVec();
         #out_equals #invoke;
+        #( GC.KeepAlive(#keep_alive); )*
+        #bindings.__CheckPanic(#panic);
+        #( #write_back )*
     };
+    let body = fold_fixed_blocks(body, args, is_async, types);
+
+    // Check any reference-typed arguments for `null` before doing anything else, since
+    // `null` can't be converted into a valid Rust value. This needs to happen before
+    // the `fixed` blocks, since pinning a `null` string or array silently produces a
+    // null pointer.
+    let null_checks = args
+        .iter()
+        .filter(|arg| is_reference_arg(&arg.repr, types))
+        .map(|arg| {
+            let arg_name = quote_arg_ident(&arg.name);
+            quote! {
+                if (#arg_name == null)
+                {
+                    throw new ArgumentNullException(nameof(#arg_name));
+                }
+            }
+        });
+
+    quote! {
+        #( #null_checks )*
+        #body
+    }
+}
+
+/// Returns `true` if arguments of the specified type are passed as a C# reference
+/// type that isn't allowed to be `null`.
+///
+/// Optional values are allowed to be `null`, and slices are passed as a
+/// `ReadOnlySpan<T>`, which is a value type, unless generating bindings for Unity.
+fn is_reference_arg(repr: &Repr, types: &TypeMap) -> bool {
+    match repr {
+        Repr::Slice(_) => types.unity,
+        Repr::Option(_) => false,
+        Repr::RefMut(inner) => is_reference_arg(inner, types),
+
+        // The C# type for a type with custom conversions may be a value type, which
+        // can't be compared to `null`, so null checks are left to the converter.
+        Repr::Named(type_name)
+            if matches!(
+                types.get(type_name).map(|export| &export.binding_style),
+                Some(BindingStyle::Custom(_))
+            ) =>
+        {
+            false
+        }
 
-    fold_fixed_blocks(body, args)
+        // Transparent newtypes without a wrapper struct are passed as their inner type.
+        Repr::Named(type_name) => match types.get(type_name).map(|export| &export.binding_style) {
+            Some(BindingStyle::Transparent(transparent)) if !transparent.wrapper => {
+                is_reference_arg(&transparent.inner, types)
+            }
+            _ => !generate::is_value_type(repr, types),
+        },
+
+        _ => !generate::is_value_type(repr, types),
+    }
 }
 
-fn fold_fixed_blocks<'a>(base_invoke: TokenStream, args: &[FnArg]) -> TokenStream {
+/// Wraps the body of a wrapper function in the blocks that keep arguments alive for
+/// the duration of the call.
+///
+/// For async functions, anything that needs to outlive the call itself lives until
+/// the task completes instead, which relies on the `__completion` declared by
+/// `task::quote_async_wrapper_fn`.
+fn fold_fixed_blocks<'a>(
+    base_invoke: TokenStream,
+    args: &[FnArg],
+    is_async: bool,
+    types: &TypeMap,
+) -> TokenStream {
+    let bindings = binding::bindings_class_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
     // Wrap the body of the function in `fixed` blocks for any parameters that need to
-    // be passed as pointers to Rust (just strings for now). We use `Iterator::fold` to
-    // generate a series of nested `fixed` blocks. This is very smart code and won't be
-    // hard to maintain at all, I'm sure.
+    // be passed as pointers to Rust (strings and slices for now). We use
+    // `Iterator::fold` to generate a series of nested `fixed` blocks. This is very smart
+    // code and won't be hard to maintain at all, I'm sure.
     args.iter().fold(base_invoke, |body, arg| {
-        if arg.repr == Repr::String {
-            let arg_ident = format_ident!("{}", arg.name.to_mixed_case());
-            let fixed_ident = format_ident!("__fixed_{}", arg_ident);
-            quote! {
+        let arg_name = arg.name.to_mixed_case();
+        let arg_ident = quote_arg_ident(&arg.name);
+        let fixed_ident = format_ident!("__fixed_{}", arg_name);
+        match &arg.repr {
+            Repr::String | Repr::Str => quote! {
                 fixed (char* #fixed_ident = #arg_ident)
                 {
                     #body
                 }
+            },
+
+            // Slices of primitives can be pinned and passed to Rust directly.
+            Repr::Slice(element) if is_primitive(element) => {
+                let element_ty = binding::raw_type_from_repr(element, types);
+                quote! {
+                    fixed (#element_ty* #fixed_ident = #arg_ident)
+                    {
+                        #body
+                    }
+                }
+            }
+
+            // For any other slice we first convert the elements into a temporary array of
+            // raw elements, then pin the temporary array. Rust takes ownership of the raw
+            // elements.
+            Repr::Slice(element) => {
+                assert_slice_element_supported(element, types);
+
+                let raw_element_ty = binding::raw_type_from_repr(element, types);
+                let raw_items_ident = format_ident!("__raw_items_{}", arg_name);
+                quote! {
+                    var #raw_items_ident = new #raw_element_ty[#arg_ident.Length];
+                    for (int __index = 0; __index < #arg_ident.Length; __index += 1)
+                    {
+                        #bindings.#into_raw(#arg_ident[__index], out #raw_items_ident[__index]);
+                    }
+
+                    fixed (#raw_element_ty* #fixed_ident = #raw_items_ident)
+                    {
+                        #body
+                    }
+                }
+            }
+
+            // Callbacks are kept alive for the duration of the call by a `CallbackContext`.
+            // If the callback throws, Rust reports it as a panic, in which case we rethrow
+            // the original exception instead. The `throw` after `Rethrow` is unreachable,
+            // but lets the compiler know that control doesn't leave the `catch` block.
+            Repr::Fn { .. } => {
+                let context_ident = format_ident!("__context_{}", arg_name);
+                quote! {
+                    var #context_ident = new CallbackContext(#arg_ident);
+                    try
+                    {
+                        #body
+                    }
+                    catch (RustPanicException) when (#context_ident.Exception != null)
+                    {
+                        #context_ident.Rethrow();
+                        throw;
+                    }
+                    finally
+                    {
+                        #context_ident.Dispose();
+                    }
+                }
+            }
+
+            // Cancellation tokens trip a Rust-side flag for as long as the function is
+            // running. An async function keeps running after the call returns, so the
+            // flag is only released once its task completes.
+            Repr::CancelFlag if is_async => {
+                let cancel_ident = format_ident!("__cancel_{}", arg_name);
+                quote! {
+                    var #cancel_ident = new RustCancelFlag(#arg_ident);
+                    try
+                    {
+                        #body
+                    }
+                    catch
+                    {
+                        #cancel_ident.Dispose();
+                        throw;
+                    }
+
+                    #cancel_ident.DisposeAfter(__completion.Task);
+                }
             }
-        } else {
-            body
+
+            Repr::CancelFlag => {
+                let cancel_ident = format_ident!("__cancel_{}", arg_name);
+                quote! {
+                    var #cancel_ident = new RustCancelFlag(#arg_ident);
+                    try
+                    {
+                        #body
+                    }
+                    finally
+                    {
+                        #cancel_ident.Dispose();
+                    }
+                }
+            }
+
+            _ => body,
         }
     })
 }
 
+/// Returns `true` if `repr` is a primitive numeric type, i.e. a type whose raw
+/// representation is the same in Rust and C#.
+fn is_primitive(repr: &Repr) -> bool {
+    match repr {
+        Repr::I8
+        | Repr::I16
+        | Repr::I32
+        | Repr::I64
+        | Repr::ISize
+        | Repr::U8
+        | Repr::U16
+        | Repr::U32
+        | Repr::U64
+        | Repr::USize
+        | Repr::F32
+        | Repr::F64 => true,
+
+        _ => false,
+    }
+}
+
+/// Panics if slices of `element` can't be passed as an argument.
+///
+/// The elements of a slice argument are moved into Rust, which isn't valid for
+/// handle types since the C# object retains ownership of the handle.
+fn assert_slice_element_supported(element: &Repr, types: &TypeMap) {
+    if let Repr::Named(type_name) = element {
+        let export = types
+            .get(type_name)
+            .unwrap_or_else(|| panic!("Could not resolve type reference: {:?}", type_name));
+        if let BindingStyle::Handle = export.binding_style {
+            panic!(
+                "Slices of handle types are not supported as arguments, use a `Vec` instead: {:?}",
+                type_name
+            );
+        }
+    }
+}
+
 /// Generates the argument declarations for a C# wrapper function.
 ///
 /// Attempts to use the most idiomatic C# type that corresponds to the original type.
@@ -151,8 +650,127 @@ pub fn quote_args<'a>(
     types: &'a TypeMap<'_>,
 ) -> impl Iterator<Item = TokenStream> + 'a {
     args.iter().map(move |arg| {
-        let ident = format_ident!("{}", arg.name.to_mixed_case());
-        let ty = quote_cs_type_for_repr(&arg.repr, types);
+        let ident = quote_arg_ident(&arg.name);
+        let ty = quote_arg_type(&arg.repr, types);
         quote! { #ty #ident }
     })
 }
+
+/// Generates the argument declarations for a C# wrapper function, declaring trailing
+/// arguments as optional parameters.
+///
+/// Arguments declared with `#[cs_bindgen(default = ...)]` use their default value,
+/// and `Option` arguments default to `null`. C# requires optional parameters to come
+/// after all required parameters, so only the arguments after the last required
+/// argument are optional, e.g. an `Option` argument followed by a required argument
+/// must still be passed explicitly.
+pub fn quote_optional_args<'a>(
+    args: &'a [FnArg],
+    types: &'a TypeMap<'_>,
+) -> impl Iterator<Item = TokenStream> + 'a {
+    let first_optional = args
+        .iter()
+        .rposition(|arg| arg.default.is_none() && !matches!(arg.repr, Repr::Option(_)))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    quote_args(args, types)
+        .zip(args)
+        .enumerate()
+        .map(move |(index, (decl, arg))| {
+            if index < first_optional {
+                return decl;
+            }
+
+            let default = match &arg.default {
+                Some(default) => quote_default_value(arg, &arg.repr, default),
+                None => quote! { null },
+            };
+            quote! { #decl = #default }
+        })
+}
+
+/// Quotes the literal for the default value of an argument with type `repr`.
+fn quote_default_value(arg: &FnArg, repr: &Repr, value: &ConstValue) -> TokenStream {
+    match (repr, value) {
+        (Repr::Option(inner), _) => quote_default_value(arg, inner, value),
+
+        (Repr::Bool, ConstValue::Bool(value)) => quote! { #value },
+
+        (Repr::F32, ConstValue::Float(value)) => constant::quote_float(*value, "float"),
+        (Repr::F64, ConstValue::Float(value)) => constant::quote_float(*value, "double"),
+        (Repr::F32, ConstValue::Int(value)) => constant::quote_float(*value as f64, "float"),
+        (Repr::F64, ConstValue::Int(value)) => constant::quote_float(*value as f64, "double"),
+
+        // NOTE: `IntPtr` and `UIntPtr` parameters can't have a constant default value,
+        // so pointer-sized arguments are excluded.
+        (repr, ConstValue::Int(value)) if is_fixed_size_integer(repr) => {
+            Literal::i64_unsuffixed(*value).into_token_stream()
+        }
+        (repr, ConstValue::UInt(value)) if is_fixed_size_integer(repr) => {
+            Literal::u64_unsuffixed(*value).into_token_stream()
+        }
+
+        (Repr::String, ConstValue::String(value)) | (Repr::Str, ConstValue::String(value)) => {
+            quote! { #value }
+        }
+
+        _ => panic!(
+            "Invalid default value for argument `{}` of type {:?}: {:?}",
+            arg.name, arg.repr, value,
+        ),
+    }
+}
+
+fn is_fixed_size_integer(repr: &Repr) -> bool {
+    matches!(
+        repr,
+        Repr::I8 | Repr::I16 | Repr::I32 | Repr::I64 | Repr::U8 | Repr::U16 | Repr::U32 | Repr::U64
+    )
+}
+
+/// Quotes the C# type of a parameter, including the `ref` modifier for parameters that
+/// are passed by reference.
+pub fn quote_arg_type(repr: &Repr, types: &TypeMap) -> TokenStream {
+    match repr {
+        // Slice arguments are exposed as a `ReadOnlySpan<T>`, which allows callers to pass
+        // an array or any other contiguous memory without copying it first. Unity doesn't
+        // provide `ReadOnlySpan<T>`, so slices are exposed as arrays instead, which
+        // support the same operations.
+        Repr::Slice(element) if types.unity => {
+            let element = quote_cs_type_for_repr(element, types);
+            quote! { #element[] }
+        }
+
+        Repr::Slice(element) => {
+            let element = quote_cs_type_for_repr(element, types);
+            quote! { ReadOnlySpan<#element> }
+        }
+
+        // Mutable references are exposed as `ref` parameters, so that the modified
+        // value is visible to the caller.
+        Repr::RefMut(inner) => {
+            let inner = quote_cs_type_for_repr(inner, types);
+            quote! { ref #inner }
+        }
+
+        _ => quote_cs_type_for_repr(repr, types),
+    }
+}
+
+/// Quotes the C# identifier for an argument.
+///
+/// Argument names are converted to `mixedCase`, which can collide with a C# keyword
+/// (e.g. an argument named `event`), so keywords are escaped with `@`.
+pub fn quote_arg_ident(name: &str) -> TokenStream {
+    let name = name.to_mixed_case();
+    let ident = format_ident!("{}", name);
+    if is_cs_keyword(&name) {
+        // NOTE: The `@` must be joint so that it's printed without a space between it
+        // and the identifier.
+        let at = Punct::new('@', Spacing::Joint);
+        quote! { #at #ident }
+    } else {
+        ident.into_token_stream()
+    }
+}
@@ -0,0 +1,94 @@
+//! The check that the native library matches the generated bindings.
+//!
+//! Loading a library that was built from a different version of the Rust code than the
+//! bindings were generated from would otherwise silently corrupt memory, since the raw
+//! bindings would disagree with the library about the layout of values and the
+//! signatures of functions. To catch this, the bindings check the library the first
+//! time that any of them are used:
+//!
+//! * The runtime's ABI version must match the version that the CLI was built with.
+//! * The hash of the declarations returned by the library's describe functions must
+//!   match the hash of the declarations that the bindings were generated from.
+//!
+//! If either check fails, the static constructor of the bindings class throws, so
+//! every use of the bindings fails with a `TypeInitializationException` describing the
+//! mismatch.
+//!
+//! When the bindings are generated from several inputs, only the declarations of the
+//! first input are hashed, since that's the library the bindings load. Declarations
+//! that are only exported by the other inputs aren't checked, so those inputs must be
+//! rebuilt along with the first one.
+
+use crate::{generate::binding, load_decl::MetadataHash};
+use cs_bindgen_shared::{ABI_VERSION, METADATA_HASH_SEED};
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+
+/// Generates the raw bindings for the runtime functions used by the check, and for
+/// each of the library's describe functions.
+pub fn quote_raw_bindings(metadata: &MetadataHash, dll_name: &str) -> TokenStream {
+    let abi_version_fn = binding::quote_raw_fn_binding(
+        "__cs_bindgen_abi_version",
+        quote! { uint },
+        quote! {},
+        dll_name,
+    );
+
+    let hash_fn = binding::quote_raw_fn_binding(
+        "__cs_bindgen_hash_declaration",
+        quote! { ulong },
+        quote! { ulong hash, IntPtr declaration },
+        dll_name,
+    );
+
+    let describe_fns = metadata.describe_fns.iter().map(|describe_fn| {
+        binding::quote_raw_fn_binding(describe_fn, quote! { IntPtr }, quote! {}, dll_name)
+    });
+
+    binding::wrap_bindings(quote! {
+        #abi_version_fn
+        #hash_fn
+        #( #describe_fns )*
+    })
+}
+
+/// Generates the statements that check the library, which are run by the static
+/// constructor of the bindings class.
+pub fn quote_check(metadata: &MetadataHash, dll_name: &str) -> TokenStream {
+    let abi_version = Literal::u32_unsuffixed(ABI_VERSION);
+    let seed = Literal::u64_unsuffixed(METADATA_HASH_SEED);
+    let expected_hash = Literal::u64_unsuffixed(metadata.hash);
+    let describe_fns = metadata
+        .describe_fns
+        .iter()
+        .map(|describe_fn| format_ident!("{}", describe_fn));
+
+    let version_message = format!(
+        "{} was built with a different version of cs-bindgen than the bindings (expected ABI \
+         version {}, found ",
+        dll_name, ABI_VERSION,
+    );
+    let hash_message = format!(
+        "The exports of {} don't match the bindings, regenerate the bindings from the \
+         current build of the library (only the exports of the first input that the \
+         bindings were generated from are checked)",
+        dll_name,
+    );
+
+    quote! {
+        var abiVersion = __cs_bindgen_abi_version();
+        if (abiVersion != #abi_version)
+        {
+            throw new InvalidOperationException(#version_message + abiVersion + ")");
+        }
+
+        ulong metadataHash = #seed;
+        #(
+            metadataHash = __cs_bindgen_hash_declaration(metadataHash, #describe_fns());
+        )*
+        if (metadataHash != #expected_hash)
+        {
+            throw new InvalidOperationException(#hash_message);
+        }
+    }
+}
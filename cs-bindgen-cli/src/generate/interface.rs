@@ -0,0 +1,584 @@
+//! Code generation for exported traits.
+//!
+//! An exported trait is generated as a public C# interface containing the methods
+//! of the trait. The C# class for each exported type that implements the trait also
+//! implements the interface.
+//!
+//! The methods of the trait are exported from Rust once for the trait, taking a
+//! trait object as the receiver, so the class needs to be able to convert itself into
+//! a `&dyn Trait`. Each implementing class explicitly implements an internal
+//! `__ITrait__Dyn` interface, which converts the object's handle into the raw
+//! representation of the trait object. The same conversion is used when passing an
+//! object implementing the interface as an argument to Rust.
+//!
+//! The interface methods are implemented explicitly, so that they don't conflict
+//! with any inherent methods of the same name.
+//!
+//! Traits that support it can also be implemented in C# by deriving from a generated
+//! abstract base class (e.g. `BotBase` for a `Bot` trait). Objects deriving from the
+//! base class are passed to Rust as a `Box<dyn Trait>`, represented by a `RawManaged`
+//! holding a `GCHandle` to the object and a pointer to a vtable of trampolines that
+//! invoke the object's methods. The vtable is allocated once for each trait. Provided
+//! methods of the trait are virtual, and by default call into Rust to invoke the
+//! trait's default implementation.
+//!
+//! A `Box<dyn Trait>` returned from Rust is exposed through the trait's interface. The
+//! object is an instance of an internal class that owns a handle to the box, which
+//! implements the interface by borrowing the boxed trait object for each call. The
+//! handle is dropped when the object is disposed (through `IDisposable`) or finalized.
+
+use crate::generate::{self, binding, func, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{schematic::TypeName, BindingStyle, Repr, Trait, TraitImpl};
+use heck::*;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+use std::collections::HashMap;
+use syn::Ident;
+
+/// Returns the identifier of the public interface generated for an exported trait.
+pub fn interface_ident(type_name: &TypeName) -> Ident {
+    format_ident!("I{}", type_name.name)
+}
+
+/// Returns the identifier of the abstract base class generated for C# implementations
+/// of an exported trait.
+pub fn base_class_ident(type_name: &TypeName) -> Ident {
+    format_ident!("{}Base", type_name.name)
+}
+
+/// Returns the identifier of the field holding the vtable for C# implementations of
+/// an exported trait.
+fn vtable_ident(type_name: &TypeName) -> Ident {
+    format_ident!("__Vtable__{}", type_name.mangled_name())
+}
+
+/// Returns the identifier of the internal interface used to convert objects into
+/// trait objects for the specified trait.
+fn dyn_interface_ident(type_name: &TypeName) -> Ident {
+    format_ident!("__I{}__Dyn", type_name.mangled_name())
+}
+
+/// Returns the identifier of the internal class for owned trait objects returned from
+/// Rust.
+fn boxed_class_ident(type_name: &TypeName) -> Ident {
+    format_ident!("__{}__Boxed", type_name.name)
+}
+
+/// Returns the identifiers of the raw bindings for borrowing and dropping an owned
+/// trait object.
+fn boxed_binding_idents(export: &Trait) -> (Ident, Ident) {
+    (
+        format_ident!("{}", &*export.boxed_as_dyn_fn),
+        format_ident!("{}", &*export.drop_boxed_fn),
+    )
+}
+
+/// Generates the interface declaration and raw conversion for an exported trait.
+pub fn quote_interface(export: &Trait, types: &TypeMap) -> TokenStream {
+    let ident = interface_ident(&export.type_name);
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let dyn_ident = dyn_interface_ident(&export.type_name);
+
+    let methods = export.methods.iter().map(|method| {
+        let name = format_ident!("{}", method.name.to_camel_case());
+        let return_ty = func::quote_return_type(method.output.as_ref(), types);
+        let args = func::quote_args(&method.inputs, types);
+        quote! {
+            #return_ty #name(#( #args ),*);
+        }
+    });
+
+    let into_raw = binding::into_raw_fn_ident();
+    let raw_ty = raw_dyn_ty();
+    let error_message = format!(
+        "Only exported Rust types can be passed as `{}` to Rust",
+        ident
+    );
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #into_raw(#qualified_ident value, out #raw_ty result)
+        {
+            if (value == null)
+            {
+                throw new ArgumentNullException(nameof(value));
+            }
+
+            var traitObject = value as #dyn_ident;
+            if (traitObject == null)
+            {
+                throw new ArgumentException(#error_message, nameof(value));
+            }
+
+            result = traitObject.__AsDyn();
+        }
+    });
+
+    let base_class = if export.managed {
+        quote_base_class(export, types)
+    } else {
+        quote! {}
+    };
+
+    let boxed_class = quote_boxed_class(export, types);
+
+    let interface = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            public interface #ident
+            {
+                #( #methods )*
+            }
+        },
+    );
+
+    quote! {
+        #interface
+
+        internal interface #dyn_ident
+        {
+            #raw_ty __AsDyn();
+        }
+
+        #raw_conversions
+        #base_class
+        #boxed_class
+    }
+}
+
+/// Generates the class for owned trait objects returned from Rust, along with its raw
+/// conversion.
+fn quote_boxed_class(export: &Trait, types: &TypeMap) -> TokenStream {
+    let ident = boxed_class_ident(&export.type_name);
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let interface_ident = types.qualify(&export.type_name, interface_ident(&export.type_name));
+    let dyn_ident = dyn_interface_ident(&export.type_name);
+    let (as_dyn_fn, drop_fn) = boxed_binding_idents(export);
+    let raw_ty = raw_dyn_ty();
+    let from_raw = binding::from_raw_fn_ident();
+
+    let methods = export.methods.iter().map(|method| {
+        let name = format_ident!("{}", method.name.to_camel_case());
+        let return_ty = func::quote_return_type(method.output.as_ref(), types);
+        let args = func::quote_args(&method.inputs, types);
+        let block = func::quote_wrapper_block(
+            &method.binding,
+            Some(quote! { ((#dyn_ident)this).__AsDyn() }),
+            &method.inputs,
+            method.output.as_ref(),
+            types,
+        );
+
+        quote! {
+            #return_ty #interface_ident.#name(#( #args ),*)
+            #block
+        }
+    });
+
+    let class = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            internal sealed class #ident : #interface_ident, #dyn_ident, IDisposable
+            {
+                private readonly __Handle _safeHandle;
+
+                internal #ident(#raw_ty raw)
+                {
+                    _safeHandle = new __Handle(raw);
+                }
+
+                // NOTE: Accessing the raw pointer after the object has been disposed
+                // throws instead of passing a dangling pointer to Rust.
+                private #raw_ty _handle
+                {
+                    get
+                    {
+                        if (_safeHandle.IsClosed)
+                        {
+                            throw new ObjectDisposedException(GetType().Name);
+                        }
+
+                        return _safeHandle.DangerousGetHandle();
+                    }
+                }
+
+                #raw_ty #dyn_ident.__AsDyn()
+                {
+                    return __bindings.#as_dyn_fn(_handle);
+                }
+
+                #( #methods )*
+
+                public void Dispose()
+                {
+                    _safeHandle.Dispose();
+                }
+
+                private sealed class __Handle : SafeHandle
+                {
+                    internal __Handle(#raw_ty raw) : base(IntPtr.Zero, true)
+                    {
+                        SetHandle(raw);
+                    }
+
+                    public override bool IsInvalid
+                    {
+                        get { return handle == IntPtr.Zero; }
+                    }
+
+                    protected override bool ReleaseHandle()
+                    {
+                        __bindings.#drop_fn(handle);
+                        return true;
+                    }
+                }
+            }
+        },
+    );
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ty raw, out #interface_ident result)
+        {
+            result = new #qualified_ident(raw);
+        }
+    });
+
+    quote! {
+        #class
+        #raw_conversions
+    }
+}
+
+/// Generates the abstract base class for C# implementations of an exported trait,
+/// along with its vtable and raw conversion.
+fn quote_base_class(export: &Trait, types: &TypeMap) -> TokenStream {
+    let trait_name = export.type_name.mangled_name();
+    let ident = base_class_ident(&export.type_name);
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let interface_ident = types.qualify(&export.type_name, interface_ident(&export.type_name));
+    let vtable_ident = vtable_ident(&export.type_name);
+    let create_vtable_ident = format_ident!("__CreateVtable__{}", trait_name);
+    let release_ident = format_ident!("__Release__{}", trait_name);
+    let bindings = binding::bindings_class_ident();
+
+    // Required methods are abstract, and provided methods invoke the default
+    // implementation in Rust. The object is passed to Rust with a temporary `GCHandle`,
+    // which is freed once the call returns.
+    let methods = export.methods.iter().map(|method| {
+        let name = format_ident!("{}", method.name.to_camel_case());
+        let return_ty = func::quote_return_type(method.output.as_ref(), types);
+        let args = func::quote_args(&method.inputs, types);
+
+        match &method.default_binding {
+            Some(default_binding) => {
+                let block = func::quote_wrapper_block(
+                    default_binding,
+                    Some(quote! {
+                        new RawManaged(#bindings.#vtable_ident, GCHandle.ToIntPtr(__handle))
+                    }),
+                    &method.inputs,
+                    method.output.as_ref(),
+                    types,
+                );
+
+                quote! {
+                    public virtual #return_ty #name(#( #args ),*)
+                    {
+                        var __handle = GCHandle.Alloc(this);
+                        try
+                        #block
+                        finally
+                        {
+                            __handle.Free();
+                        }
+                    }
+                }
+            }
+
+            None => quote! {
+                public abstract #return_ty #name(#( #args ),*);
+            },
+        }
+    });
+
+    // Generate the raw delegate type and trampoline for each method.
+    let mut delegate_types = Vec::new();
+    let mut trampolines = Vec::new();
+    let mut delegate_fields = Vec::new();
+    for method in &export.methods {
+        let raw_ident = format_ident!("__RawMethod__{}__{}", trait_name, &*method.name);
+        let delegate_ident = format_ident!("__Method__{}__{}", trait_name, &*method.name);
+        let invoke_ident = format_ident!("__InvokeMethod__{}__{}", trait_name, &*method.name);
+        let name = format_ident!("{}", method.name.to_camel_case());
+
+        // NOTE: Methods that don't return a value return the raw unit value, matching
+        // `RawCallback` on the Rust side.
+        let output = method.output.clone().unwrap_or(Repr::Unit);
+        let raw_output_ty = binding::raw_type_from_repr(&output, types);
+
+        let mut params = vec![quote! { IntPtr context }];
+        let mut convert_args = Vec::new();
+        let mut values = Vec::new();
+        for (index, arg) in method.inputs.iter().enumerate() {
+            let raw_arg = format_ident!("arg{}", index);
+            let value = format_ident!("value{}", index);
+            let raw_ty = binding::raw_type_from_repr(&arg.repr, types);
+            let arg_ty = generate::quote_cs_type_for_repr(&arg.repr, types);
+            let from_raw = binding::from_raw_fn_ident();
+
+            params.push(quote! { #raw_ty #raw_arg });
+            convert_args.push(quote! { #from_raw(#raw_arg, out #arg_ty #value); });
+            values.push(value);
+        }
+        params.push(quote! { out byte threw });
+
+        let into_raw = binding::into_raw_fn_ident();
+        let invoke = if output == Repr::Unit {
+            quote! {
+                self.#name(#( #values ),*);
+                return 0;
+            }
+        } else {
+            quote! {
+                var result = self.#name(#( #values ),*);
+                #into_raw(result, out #raw_output_ty rawResult);
+                return rawResult;
+            }
+        };
+
+        delegate_types.push(quote! {
+            [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+            internal delegate #raw_output_ty #raw_ident(#( #params ),*);
+        });
+
+        delegate_fields.push(quote! {
+            internal static readonly #raw_ident #delegate_ident = #invoke_ident;
+        });
+
+        trampolines.push(quote! {
+            [MonoPInvokeCallback(typeof(#raw_ident))]
+            internal static #raw_output_ty #invoke_ident(#( #params ),*)
+            {
+                threw = 0;
+
+                try
+                {
+                    #( #convert_args )*
+
+                    var self = (#qualified_ident)GCHandle.FromIntPtr(context).Target;
+                    #invoke
+                }
+                catch (Exception e)
+                {
+                    __pendingException = e;
+                    threw = 1;
+                    return default(#raw_output_ty);
+                }
+            }
+        });
+    }
+
+    // NOTE: We need to use unsuffixed literals, otherwise `quote` would generate e.g.
+    // `4usize`, which isn't valid C#.
+    let vtable_len = Literal::usize_unsuffixed(export.methods.len() + 1);
+    let vtable_entries = export.methods.iter().enumerate().map(|(index, method)| {
+        let index = Literal::usize_unsuffixed(index + 1);
+        let delegate_ident = format_ident!("__Method__{}__{}", trait_name, &*method.name);
+        quote! {
+            vtable[#index] = Marshal.GetFunctionPointerForDelegate(#delegate_ident);
+        }
+    });
+
+    let into_raw = binding::into_raw_fn_ident();
+    let vtable = binding::wrap_bindings(quote! {
+        // NOTE: The delegates are stored in static fields so that they're never garbage
+        // collected, which keeps the function pointers in the vtable valid. They're
+        // declared before the vtable so that they're initialized first.
+        internal static readonly __RawReleaseManaged #release_ident = __ReleaseManaged;
+        #( #delegate_fields )*
+        internal static readonly IntPtr #vtable_ident = #create_vtable_ident();
+
+        private static IntPtr #create_vtable_ident()
+        {
+            var vtable = (IntPtr*)Marshal.AllocHGlobal(IntPtr.Size * #vtable_len);
+            vtable[0] = Marshal.GetFunctionPointerForDelegate(#release_ident);
+            #( #vtable_entries )*
+            return (IntPtr)vtable;
+        }
+
+        #( #trampolines )*
+
+        internal static void #into_raw(#qualified_ident value, out RawManaged result)
+        {
+            if (value == null)
+            {
+                throw new ArgumentNullException(nameof(value));
+            }
+
+            result = new RawManaged(#vtable_ident, GCHandle.ToIntPtr(GCHandle.Alloc(value)));
+        }
+    });
+
+    let base_class = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            public abstract class #ident : #interface_ident
+            {
+                #( #methods )*
+            }
+        },
+    );
+
+    quote! {
+        #base_class
+
+        #( #delegate_types )*
+
+        #vtable
+    }
+}
+
+/// Generates the interface implementations for an exported trait impl.
+///
+/// # Panics
+///
+/// Panics if the trait wasn't exported, or if the implementing type isn't marshaled
+/// as a handle, since only handle types can be converted into a trait object.
+pub fn quote_trait_impl(
+    export: &TraitImpl,
+    traits: &HashMap<&TypeName, &Trait>,
+    types: &TypeMap,
+) -> TokenStream {
+    let trait_ = traits
+        .get(&export.trait_name)
+        .unwrap_or_else(|| panic!("No export found for trait {:?}", export.trait_name));
+    let self_type = types
+        .get(&export.self_type)
+        .unwrap_or_else(|| panic!("No export found for type name {:?}", export.self_type));
+    if !matches!(self_type.binding_style, BindingStyle::Handle) {
+        panic!(
+            "Only types marshaled as handles can implement exported traits: {:?}",
+            export.self_type
+        );
+    }
+
+    let class_ident = export.self_type.ident();
+    let ident = types.qualify(&export.trait_name, interface_ident(&export.trait_name));
+    let dyn_ident = dyn_interface_ident(&export.trait_name);
+    let as_dyn_fn = format_ident!("{}", &*export.as_dyn_fn);
+    let raw_ty = raw_dyn_ty();
+
+    let methods = trait_.methods.iter().map(|method| {
+        let name = format_ident!("{}", method.name.to_camel_case());
+        let return_ty = func::quote_return_type(method.output.as_ref(), types);
+        let args = func::quote_args(&method.inputs, types);
+        let block = func::quote_wrapper_block(
+            &method.binding,
+            Some(quote! { ((#dyn_ident)this).__AsDyn() }),
+            &method.inputs,
+            method.output.as_ref(),
+            types,
+        );
+
+        quote! {
+            #return_ty #ident.#name(#( #args ),*)
+            #block
+        }
+    });
+
+    types.wrap_namespace(
+        &export.self_type,
+        quote! {
+            partial class #class_ident : #ident, #dyn_ident
+            {
+                #raw_ty #dyn_ident.__AsDyn()
+                {
+                    return __bindings.#as_dyn_fn(_handle);
+                }
+
+                #( #methods )*
+            }
+        },
+    )
+}
+
+/// Generates the raw bindings for the methods of an exported trait, along with the
+/// bindings for owned trait objects returned from Rust.
+pub fn quote_method_bindings(export: &Trait, dll_name: &str, types: &TypeMap) -> TokenStream {
+    let raw_ty = raw_dyn_ty();
+    let bindings = export.methods.iter().map(|method| {
+        let mut args = binding::quote_binding_args(&method.inputs, types);
+        args.insert(0, quote! { #raw_ty self });
+        args.push(binding::quote_panic_arg());
+
+        let return_ty = match &method.output {
+            Some(output) => binding::raw_type_from_repr(output, types),
+            None => quote! { void },
+        };
+
+        let binding = binding::quote_raw_fn_binding(
+            &method.binding,
+            return_ty.clone(),
+            args.to_token_stream(),
+            dll_name,
+        );
+
+        // The binding for the default implementation takes the raw C# object instead of
+        // a trait object.
+        let default_binding = method.default_binding.as_ref().map(|default_binding| {
+            let mut args = args.clone();
+            args[0] = quote! { RawManaged self };
+            binding::quote_raw_fn_binding(
+                default_binding,
+                return_ty,
+                args.to_token_stream(),
+                dll_name,
+            )
+        });
+
+        quote! {
+            #binding
+            #default_binding
+        }
+    });
+
+    let (as_dyn_fn, drop_fn) = boxed_binding_idents(export);
+    let boxed_as_dyn_entry_point = &*export.boxed_as_dyn_fn;
+    let drop_boxed_entry_point = &*export.drop_boxed_fn;
+
+    quote! {
+        #( #bindings )*
+
+        [DllImport(
+            #dll_name,
+            EntryPoint = #boxed_as_dyn_entry_point,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern #raw_ty #as_dyn_fn(IntPtr handle);
+
+        [DllImport(
+            #dll_name,
+            EntryPoint = #drop_boxed_entry_point,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern void #drop_fn(IntPtr handle);
+    }
+}
+
+/// Generates the raw binding for the function that converts a handle into a trait
+/// object.
+pub fn quote_as_dyn_binding(export: &TraitImpl, dll_name: &str) -> TokenStream {
+    let entry_point = &*export.as_dyn_fn;
+    let fn_name = format_ident!("{}", entry_point);
+    let raw_ty = raw_dyn_ty();
+    quote! {
+        [DllImport(
+            #dll_name,
+            EntryPoint = #entry_point,
+            CallingConvention = CallingConvention.Cdecl)]
+        internal static extern #raw_ty #fn_name(IntPtr handle);
+    }
+}
+
+/// Quotes the raw type of a trait object, which is a pointer to the boxed trait
+/// object.
+fn raw_dyn_ty() -> TokenStream {
+    quote! { IntPtr }
+}
@@ -0,0 +1,34 @@
+//! Code generation for iterators.
+//!
+//! On the Rust side, an `impl Iterator<Item = T>` return value is boxed and
+//! marshaled as a `RawIter`, which C# advances with the built-in
+//! `__cs_bindgen_iter_next` function. Since the item conversion is the only part
+//! that depends on the item type, a single generic `RustEnumerable<R, T>` class is
+//! used on the C# side. We only need to generate the `__FromRaw` overload that
+//! wraps the raw iterator for each concrete item type.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the idiomatic C# type for an iterator with the specified item type.
+pub fn quote_cs_type(item: &Repr, types: &TypeMap) -> TokenStream {
+    let item = generate::quote_cs_type_for_repr(item, types);
+    quote! { IEnumerable<#item> }
+}
+
+/// Generates the conversion function for an iterator with the specified item type.
+pub fn quote_iter_conversion(item: &Repr, types: &TypeMap) -> TokenStream {
+    let cs_ty = quote_cs_type(item, types);
+    let item_ty = generate::quote_cs_type_for_repr(item, types);
+    let raw_item_ty = binding::raw_type_from_repr(item, types);
+    let from_raw = binding::from_raw_fn_ident();
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_raw(RawIter raw, out #cs_ty result)
+        {
+            result = new RustEnumerable<#raw_item_ty, #item_ty>(raw, #from_raw);
+        }
+    })
+}
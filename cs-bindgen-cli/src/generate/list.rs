@@ -0,0 +1,152 @@
+//! Conversions for lists whose elements aren't primitives or exported types.
+//!
+//! Primitives use the list functions exported by the runtime, and exported types
+//! generate the conversions for lists of themselves along with their other helper
+//! bindings. Any other element type, e.g. the `Vec<String>` in a `Vec<Vec<String>>`,
+//! uses the functions that the items using the list exported for it. The same element
+//! type may be exported by several items, in which case the first one is used.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::{BindingStyle, Export, ListElement, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Generates the raw bindings and the `List<T>` conversions for each element type
+/// exported with [`Export::List`].
+///
+/// `dll_names` are the names of the libraries that the exports were loaded from,
+/// indexed by the `sources` of the exports.
+pub fn quote_list_conversions(
+    exports: &[Export],
+    sources: &[usize],
+    dll_names: &[&str],
+    types: &TypeMap,
+) -> TokenStream {
+    let mut elements = Vec::<&Repr>::new();
+    let mut conversions = Vec::new();
+    for (export, &source) in exports.iter().zip(sources) {
+        let export = match export {
+            Export::List(export) => export,
+            _ => continue,
+        };
+
+        if is_builtin(&export.element) || elements.contains(&&export.element) {
+            continue;
+        }
+
+        elements.push(&export.element);
+        conversions.push(quote_element_conversions(export, dll_names[source], types));
+    }
+
+    binding::wrap_bindings(quote! {
+        #( #conversions )*
+    })
+}
+
+fn quote_element_conversions(export: &ListElement, dll_name: &str, types: &TypeMap) -> TokenStream {
+    // NOTE: The elements of a nested list are borrowed from the outer list, but lists of
+    // handles move each element out of the vec, so the elements would be dropped twice.
+    if let Repr::Vec(inner) | Repr::Slice(inner) = &export.element {
+        if is_handle(inner, types) {
+            panic!(
+                "Nested lists of handle types are not supported: {:?}",
+                export.element,
+            );
+        }
+    }
+
+    // NOTE: The elements of a list are borrowed from the vec, but a boxed trait object
+    // has to be moved into its own handle.
+    if let Repr::DynHandle(type_name) = &export.element {
+        panic!(
+            "Lists of boxed trait objects are not supported: {:?}",
+            type_name,
+        );
+    }
+
+    let ty = generate::quote_cs_type_for_repr(&export.element, types);
+    let raw_repr = binding::raw_type_from_repr(&export.element, types);
+
+    let index_fn = binding::quote_raw_fn_binding(
+        &export.index_fn,
+        raw_repr.clone(),
+        quote! { RawSlice slice, UIntPtr index },
+        dll_name,
+    );
+
+    let convert_list_fn = binding::quote_raw_fn_binding(
+        &export.convert_list_fn,
+        quote! { RawVec },
+        quote! { RawSlice raw },
+        dll_name,
+    );
+
+    let drop_vec_fn = binding::quote_raw_fn_binding(
+        &export.drop_vec_fn,
+        quote! { void },
+        quote! { RawVec vec },
+        dll_name,
+    );
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+    let index_fn_name = format_ident!("{}", &*export.index_fn);
+    let drop_vec_fn_name = format_ident!("{}", &*export.drop_vec_fn);
+    let convert_list_fn_name = format_ident!("{}", &*export.convert_list_fn);
+
+    quote! {
+        #index_fn
+        #convert_list_fn
+        #drop_vec_fn
+
+        internal static void #from_raw(RawVec raw, out List<#ty> result)
+        {
+            result = raw.ToList<#raw_repr, #ty>(#index_fn_name, #from_raw);
+            #drop_vec_fn_name(raw);
+        }
+
+        internal static void #into_raw(List<#ty> items, out RawVec result)
+        {
+            result = RawVec.FromList(
+                items,
+                item => {
+                    #into_raw(item, out #raw_repr raw);
+                    return raw;
+                },
+                #convert_list_fn_name);
+        }
+    }
+}
+
+fn is_handle(repr: &Repr, types: &TypeMap) -> bool {
+    match repr {
+        Repr::Named(type_name) => types
+            .get(type_name)
+            .map_or(false, |export| export.binding_style == BindingStyle::Handle),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the conversions for lists of `element` are already generated,
+/// either as part of the built-in bindings or along with an exported type.
+fn is_builtin(element: &Repr) -> bool {
+    match element {
+        Repr::Bool
+        | Repr::Char
+        | Repr::I8
+        | Repr::I16
+        | Repr::I32
+        | Repr::I64
+        | Repr::ISize
+        | Repr::U8
+        | Repr::U16
+        | Repr::U32
+        | Repr::U64
+        | Repr::USize
+        | Repr::F32
+        | Repr::F64
+        | Repr::Named(_) => true,
+
+        _ => false,
+    }
+}
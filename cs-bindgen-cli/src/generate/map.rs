@@ -0,0 +1,56 @@
+//! Code generation for map types.
+//!
+//! On the Rust side, map types like `HashMap<K, V>` and `BTreeMap<K, V>` are
+//! marshaled as a `RawMap<K::Abi, V::Abi>`, which is a pair of parallel buffers
+//! containing the raw keys and values. Since the buffers are just pointers, a single
+//! non-generic `RawMap` struct is used on the C# side. We only need to generate the
+//! `__FromRaw` and `__IntoRaw` overloads for converting each concrete map type to
+//! and from a `Dictionary<K, V>`.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the idiomatic C# type for a map with the specified key and value types.
+pub fn quote_cs_type(key: &Repr, value: &Repr, types: &TypeMap) -> TokenStream {
+    let key = generate::quote_cs_type_for_repr(key, types);
+    let value = generate::quote_cs_type_for_repr(value, types);
+    quote! { Dictionary<#key, #value> }
+}
+
+/// Generates the conversion functions for a map with the specified key and value
+/// types.
+pub fn quote_map_conversions(key: &Repr, value: &Repr, types: &TypeMap) -> TokenStream {
+    let cs_ty = quote_cs_type(key, value, types);
+    let key_ty = generate::quote_cs_type_for_repr(key, types);
+    let value_ty = generate::quote_cs_type_for_repr(value, types);
+    let raw_key_ty = binding::raw_type_from_repr(key, types);
+    let raw_value_ty = binding::raw_type_from_repr(value, types);
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_raw(RawMap raw, out #cs_ty result)
+        {
+            result = raw.ToDictionary<#raw_key_ty, #key_ty, #raw_value_ty, #value_ty>(
+                #from_raw,
+                #from_raw);
+        }
+
+        internal static void #into_raw(#cs_ty value, out RawMap result)
+        {
+            result = RawMap.FromDictionary<#key_ty, #raw_key_ty, #value_ty, #raw_value_ty>(
+                value,
+                key => {
+                    #into_raw(key, out #raw_key_ty raw);
+                    return raw;
+                },
+                value => {
+                    #into_raw(value, out #raw_value_ty raw);
+                    return raw;
+                });
+        }
+    })
+}
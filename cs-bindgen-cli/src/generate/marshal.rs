@@ -0,0 +1,294 @@
+//! Code generation for types exported with `#[cs_bindgen(marshal = "...")]`.
+//!
+//! Marshaled types are passed across the FFI boundary as a buffer containing the
+//! serialized value, which is deserialized into a C# class using the serializer for
+//! the chosen format, so projects using the bindings need to reference the package
+//! for that serializer:
+//!
+//! * JSON uses `System.Text.Json`. Properties are matched to the serialized fields by
+//!   name, using `[JsonPropertyName]`.
+//! * MessagePack uses MessagePack-CSharp (the `MessagePack` package). `rmp-serde`
+//!   serializes structs as an array of field values, so properties are matched to
+//!   the serialized fields by index, using `[Key]`.
+//!
+//! For structs with named fields we generate the class, with a property for each
+//! field. The serialized representation must match the Rust field names and order,
+//! so the fields must not be renamed or skipped with `#[serde(...)]`. The class is
+//! `partial`, so
+//! additional members can be added to it. For all other types (e.g. enums), there's
+//! no obvious C# equivalent of the serialized representation, so the class must be
+//! provided by the user, using the same name and namespace as the generated class
+//! would have.
+
+use crate::generate::{binding, docs, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{
+    boxed_type_name,
+    schematic::{Schema, StructLike},
+    BindingStyle, MarshalFormat, NamedType,
+};
+use heck::CamelCase;
+use proc_macro2::{Literal, TokenStream};
+use quote::*;
+
+/// Generates the class and raw conversions for a marshaled type.
+pub fn quote_marshaled_type(
+    export: &NamedType,
+    format: MarshalFormat,
+    schema: &Schema,
+    types: &TypeMap,
+) -> TokenStream {
+    let class = match schema {
+        Schema::Struct(_) => {
+            // NOTE: The unwrap here will not panic because named structs always have a
+            // struct-like representation.
+            quote_class(export, format, schema.as_struct_like().unwrap(), types)
+        }
+
+        _ => quote! {},
+    };
+
+    let conversions = quote_conversions(export, format, types);
+
+    quote! {
+        #class
+        #conversions
+    }
+}
+
+/// Generates the C# class that a marshaled struct is deserialized into.
+fn quote_class(
+    export: &NamedType,
+    format: MarshalFormat,
+    schema: StructLike<'_>,
+    types: &TypeMap,
+) -> TokenStream {
+    let ident = export.type_name.ident();
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+
+    let class_attribute = match format {
+        MarshalFormat::Json => quote! {},
+        MarshalFormat::MessagePack => quote! { [MessagePack.MessagePackObject] },
+    };
+
+    let properties = schema.fields.iter().enumerate().map(|(index, field)| {
+        // NOTE: The unwrap here will not panic because the fields of a struct with
+        // named fields always have a name.
+        let name = field.name.unwrap();
+        let property_ident = format_ident!("{}", name.to_camel_case());
+        let ty = quote_property_type(&field.schema, format, types);
+
+        let attribute = match format {
+            MarshalFormat::Json => quote! {
+                [System.Text.Json.Serialization.JsonPropertyName(#name)]
+            },
+
+            MarshalFormat::MessagePack => {
+                let index = Literal::usize_unsuffixed(index);
+                quote! { [MessagePack.Key(#index)] }
+            }
+        };
+
+        quote! {
+            #attribute
+            public #ty #property_ident { get; set; }
+        }
+    });
+
+    types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            #class_attribute
+            public partial class #ident
+            {
+                #( #properties )*
+            }
+        },
+    )
+}
+
+/// Generates the `__FromRaw` and `__IntoRaw` overloads for a marshaled type.
+///
+/// The serialized buffer is always allocated by Rust, so it's dropped once it has
+/// been deserialized. Values passed to Rust are serialized into a `byte[]`, which is
+/// then copied into a buffer owned by Rust.
+///
+/// NOTE: `System.Text.Json` can read directly from the Rust buffer, but
+/// MessagePack-CSharp can't deserialize from unmanaged memory without a custom
+/// `MemoryManager`, so the buffer is copied into a `byte[]` first.
+fn quote_conversions(export: &NamedType, format: MarshalFormat, types: &TypeMap) -> TokenStream {
+    let ty = types.qualify(&export.type_name, export.type_name.ident());
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    let (deserialize, serialize) = match format {
+        MarshalFormat::Json => (
+            quote! {
+                result = System.Text.Json.JsonSerializer.Deserialize<#ty>(
+                    new ReadOnlySpan<byte>((void*)raw.Ptr, (int)raw.Length));
+                __cs_bindgen_drop_vec_u8(raw);
+            },
+            quote! {
+                System.Text.Json.JsonSerializer.SerializeToUtf8Bytes(value)
+            },
+        ),
+
+        MarshalFormat::MessagePack => (
+            quote! {
+                #from_raw(raw, out byte[] bytes);
+                result = MessagePack.MessagePackSerializer.Deserialize<#ty>(bytes);
+            },
+            quote! {
+                MessagePack.MessagePackSerializer.Serialize(value)
+            },
+        ),
+    };
+
+    binding::wrap_bindings(quote! {
+        internal static void #from_raw(RawVec raw, out #ty result)
+        {
+            #deserialize
+        }
+
+        internal static void #into_raw(#ty value, out RawVec result)
+        {
+            #into_raw(#serialize, out result);
+        }
+    })
+}
+
+/// Quotes the C# type of a property of a marshaled class.
+///
+/// The C# serializers only support a subset of C# types, and serde doesn't always
+/// serialize values the same way that the C# serializers expect, so this is separate
+/// from the regular mapping of Rust types to C# types:
+///
+/// * `char` is serialized as a string.
+/// * Sequences are deserialized into a `List<T>`, including byte buffers, which
+///   the C# serializers would otherwise expect to be base64-encoded or binary data.
+/// * Maps in JSON must have string keys.
+/// * Named types must be marshaled with the same format.
+fn quote_property_type(schema: &Schema, format: MarshalFormat, types: &TypeMap) -> TokenStream {
+    match schema {
+        Schema::Bool => quote! { bool },
+
+        Schema::I8 => quote! { sbyte },
+        Schema::I16 => quote! { short },
+        Schema::I32 => quote! { int },
+        Schema::I64 | Schema::ISize => quote! { long },
+
+        Schema::U8 => quote! { byte },
+        Schema::U16 => quote! { ushort },
+        Schema::U32 => quote! { uint },
+        Schema::U64 | Schema::USize => quote! { ulong },
+
+        Schema::F32 => quote! { float },
+        Schema::F64 => quote! { double },
+
+        Schema::Char | Schema::Str | Schema::String(_) => quote! { string },
+
+        Schema::Option(inner) => {
+            let inner_ty = quote_property_type(inner, format, types);
+            if is_value_type(inner) {
+                quote! { #inner_ty? }
+            } else {
+                inner_ty
+            }
+        }
+
+        Schema::Slice(element) => {
+            let element = quote_property_type(element, format, types);
+            quote! { List<#element> }
+        }
+
+        Schema::Seq(schema) => {
+            let element = quote_property_type(&schema.element, format, types);
+            quote! { List<#element> }
+        }
+
+        Schema::Array(schema) => {
+            let element = quote_property_type(&schema.element, format, types);
+            quote! { List<#element> }
+        }
+
+        Schema::Map(schema) => {
+            if format == MarshalFormat::Json
+                && !matches!(&schema.key, Schema::Str | Schema::String(_))
+            {
+                panic!(
+                    "Only maps with string keys are supported in types marshaled as JSON: {:?}",
+                    schema,
+                );
+            }
+
+            let key = quote_property_type(&schema.key, format, types);
+            let value = quote_property_type(&schema.value, format, types);
+            quote! { Dictionary<#key, #value> }
+        }
+
+        Schema::Enum(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::UnitStruct(_)
+        | Schema::NewtypeStruct(_) => {
+            // Boxed fields are serialized the same as the boxed value.
+            //
+            // NOTE: The unwrap here is valid because all of the struct-like variants are
+            // guaranteed to have a type name. If this panics, that indicates a bug in the
+            // schematic crate.
+            let type_name = boxed_type_name(schema).unwrap_or_else(|| schema.type_name().unwrap());
+            let export = types
+                .get(type_name)
+                .unwrap_or_else(|| panic!("Could not resolve type reference: {:?}", type_name));
+
+            match &export.binding_style {
+                BindingStyle::Marshal(field_format, _) if *field_format == format => {
+                    types.qualify(type_name, export.type_name.ident())
+                }
+
+                _ => panic!(
+                    "Types used in the fields of a type marshaled as {} must also be marshaled \
+                     as {}: {:?}",
+                    format_name(format),
+                    format_name(format),
+                    type_name,
+                ),
+            }
+        }
+
+        Schema::Unit | Schema::Tuple(_) | Schema::I128 | Schema::U128 => panic!(
+            "Unsupported field type for a type marshaled as {}: {:?}",
+            format_name(format),
+            schema,
+        ),
+    }
+}
+
+/// Returns the name of `format` for use in error messages.
+fn format_name(format: MarshalFormat) -> &'static str {
+    match format {
+        MarshalFormat::Json => "JSON",
+        MarshalFormat::MessagePack => "MessagePack",
+    }
+}
+
+/// Returns `true` if the C# type for a property with the specified schema is a
+/// value type, i.e. it needs to be wrapped in a `Nullable<T>` when it's optional.
+fn is_value_type(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Bool
+            | Schema::I8
+            | Schema::I16
+            | Schema::I32
+            | Schema::I64
+            | Schema::ISize
+            | Schema::U8
+            | Schema::U16
+            | Schema::U32
+            | Schema::U64
+            | Schema::USize
+            | Schema::F32
+            | Schema::F64
+    )
+}
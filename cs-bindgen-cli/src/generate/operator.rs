@@ -0,0 +1,151 @@
+//! Code generation for impls of the operator traits in `std::ops`.
+//!
+//! Operators are exposed in C# as operator overloads that call into Rust, so types like
+//! a points newtype behave the same on both sides of the boundary. As with conversion
+//! operators, C# requires one of the operands to be the type that declares the
+//! operator, so it's declared on the left-hand operand if that's an exported struct or
+//! class, and otherwise on the right-hand operand, e.g. for `impl Mul<Points> for i32`.
+//! C# provides the compound assignment operators (e.g. `+=`) automatically.
+//!
+//! `Not` is generated as the `!` operator if it returns a `bool`, and as the `~`
+//! operator otherwise.
+//!
+//! The operands are passed to Rust by value, which would dispose the C# object for a
+//! handle, so operators with a handle operand aren't supported.
+
+use crate::generate::{binding, conversion, func, quote_cs_type_for_repr, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{schematic::TypeName, BindingStyle, FnArg, Operator, OperatorTrait, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the raw binding for an operator.
+pub fn quote_raw_binding(export: &Operator, dll_name: &str, types: &TypeMap) -> TokenStream {
+    let args = operands(export)
+        .into_iter()
+        .map(|arg| {
+            let ty = binding::raw_type_from_repr(&arg.repr, types);
+            let ident = format_ident!("{}", &*arg.name);
+            quote! { #ty #ident }
+        })
+        .chain(Some(binding::quote_panic_arg()));
+
+    binding::quote_raw_fn_binding(
+        &export.binding,
+        binding::raw_type_from_repr(&export.output, types),
+        quote! { #( #args ),* },
+        dll_name,
+    )
+}
+
+/// Quotes the operator overload for an operator, returning the name of the type that
+/// declares the operator along with the partial declaration of the type.
+pub fn quote_operator<'a>(
+    export: &'a Operator,
+    types: &'a TypeMap,
+) -> Result<(&'a TypeName, TokenStream), failure::Error> {
+    let reprs = Some(&export.lhs)
+        .into_iter()
+        .chain(export.rhs.as_ref())
+        .collect::<Vec<_>>();
+    if let Some(handle) = reprs.iter().find_map(|repr| handle_type(repr, types)) {
+        return Err(failure::format_err!(
+            "Can't generate the `{:?}` operator for {:?}, since operators can't take handle \
+             types by value",
+            export.trait_,
+            handle,
+        ));
+    }
+
+    let owner = reprs
+        .iter()
+        .copied()
+        .find_map(|repr| match repr {
+            Repr::Named(type_name)
+                if types
+                    .get(type_name)
+                    .map_or(false, |named| conversion::can_declare_operators(named)) =>
+            {
+                Some(type_name)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            failure::format_err!(
+                "Can't generate the `{:?}` operator for {:?}, since neither operand is an \
+                 exported struct or class",
+                export.trait_,
+                export.lhs,
+            )
+        })?;
+
+    let symbol = match export.trait_ {
+        OperatorTrait::Add => quote! { + },
+        OperatorTrait::Sub | OperatorTrait::Neg => quote! { - },
+        OperatorTrait::Mul => quote! { * },
+        OperatorTrait::Div => quote! { / },
+        OperatorTrait::Rem => quote! { % },
+        OperatorTrait::BitAnd => quote! { & },
+        OperatorTrait::BitOr => quote! { | },
+        OperatorTrait::BitXor => quote! { ^ },
+        OperatorTrait::Not if export.output == Repr::Bool => quote! { ! },
+        OperatorTrait::Not => quote! { ~ },
+    };
+
+    let args = operands(export);
+    let params = args.iter().map(|arg| {
+        let ty = quote_cs_type_for_repr(&arg.repr, types);
+        let ident = format_ident!("{}", &*arg.name);
+        quote! { #ty #ident }
+    });
+    let return_ty = func::quote_method_return_type(Some(&export.output), false, types);
+    let block =
+        func::quote_wrapper_block(&export.binding, None, &args, Some(&export.output), types);
+
+    let operator = quote! {
+        public static #return_ty operator #symbol(#( #params ),*)
+        #block
+    };
+
+    let ident = owner.ident();
+    let declaration = match &types[owner].binding_style {
+        BindingStyle::Handle | BindingStyle::Marshal(..) => quote! {
+            partial class #ident
+            {
+                #operator
+            }
+        },
+
+        _ => quote! {
+            partial struct #ident
+            {
+                #operator
+            }
+        },
+    };
+
+    Ok((owner, types.wrap_namespace(owner, declaration)))
+}
+
+/// Returns the arguments to the binding function for an operator.
+fn operands(export: &Operator) -> Vec<FnArg> {
+    let mut args = vec![FnArg::new("lhs", export.lhs.clone())];
+    if let Some(rhs) = &export.rhs {
+        args.push(FnArg::new("rhs", rhs.clone()));
+    }
+
+    args
+}
+
+/// Returns the name of the type if `repr` is a handle type.
+fn handle_type<'a>(repr: &'a Repr, types: &TypeMap) -> Option<&'a TypeName> {
+    match repr {
+        Repr::Named(type_name)
+            if types
+                .get(type_name)
+                .map_or(false, |named| named.binding_style == BindingStyle::Handle) =>
+        {
+            Some(type_name)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,91 @@
+//! Code generation for optional values.
+//!
+//! On the Rust side, an `Option<T>` is marshaled as a `RawOption<T::Abi>`, which is
+//! a presence flag paired with the raw value. Since P/Invoke doesn't support generic
+//! structs, we generate a dedicated raw struct for each optional type used in the
+//! exported API, along with the `__FromRaw` and `__IntoRaw` overloads for
+//! converting to and from the idiomatic C# type:
+//!
+//! * Optional value types (primitives, structs, C-like enums) become `Nullable<T>`.
+//! * Optional reference types (strings, lists, classes, interfaces) use `null`, and
+//!   are annotated as nullable (e.g. `string?`) if nullable annotations are enabled.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the identifier of the raw struct generated for an `Option` containing
+/// values of the specified type.
+pub fn raw_option_ident(inner: &Repr) -> Ident {
+    format_ident!("__RawOption__{}", generate::mangle_repr(inner))
+}
+
+/// Quotes the idiomatic C# type for an `Option` containing the specified type.
+pub fn quote_cs_type(inner: &Repr, types: &TypeMap) -> TokenStream {
+    let inner_ty = generate::quote_cs_type_for_repr(inner, types);
+    if generate::is_value_type(inner, types) || types.nullable {
+        quote! { #inner_ty? }
+    } else {
+        inner_ty
+    }
+}
+
+/// Generates the raw struct and conversion functions for an `Option` containing
+/// the specified type.
+pub fn quote_raw_option(inner: &Repr, types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_option_ident(inner);
+    let raw_value_ty = binding::raw_type_from_repr(inner, types);
+    let cs_ty = quote_cs_type(inner, types);
+    let inner_cs_ty = generate::quote_cs_type_for_repr(inner, types);
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    // `Nullable<T>` needs to be explicitly unwrapped, whereas reference types can be
+    // passed along directly once we've checked for `null`.
+    let (is_present, unwrap_value) = if generate::is_value_type(inner, types) {
+        (quote! { value.HasValue }, quote! { value.Value })
+    } else {
+        (quote! { value != null }, quote! { value })
+    };
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ident raw, out #cs_ty result)
+        {
+            if (raw.IsSome != 0)
+            {
+                #from_raw(raw.Value, out #inner_cs_ty value);
+                result = value;
+            }
+            else
+            {
+                result = null;
+            }
+        }
+
+        internal static void #into_raw(#cs_ty value, out #raw_ident result)
+        {
+            result = new #raw_ident();
+            if (#is_present)
+            {
+                result.IsSome = 1;
+                #into_raw(#unwrap_value, out result.Value);
+            }
+        }
+    });
+
+    quote! {
+        // Generate the raw struct for the optional type. This needs to match the
+        // `RawOption<T>` type on the Rust side.
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct #raw_ident
+        {
+            public byte IsSome;
+            public #raw_value_ty Value;
+        }
+
+        #raw_conversions
+    }
+}
@@ -0,0 +1,358 @@
+//! Resolution of exported members that end up with the same C# name.
+//!
+//! Functions and methods are named by converting their Rust names to `PascalCase`, so
+//! different Rust names can produce the same C# name (e.g. `tile_1` and `tile1`), and
+//! methods can also collide with the members generated for their type (e.g. a
+//! `to_string` method on a type that implements `Display`). Colliding names are
+//! resolved before generating any code:
+//!
+//! * Members with the same name but different parameter types are generated as C#
+//!   overloads and keep their names. Return types and `static` don't distinguish
+//!   overloads in C#, so they're ignored.
+//! * Otherwise, the member whose Rust name sorts first keeps the name, and the others
+//!   are suffixed with `2`, `3`, etc. in the order of their Rust names, skipping any
+//!   names that are already taken.
+//! * Constructors can't be renamed, so if two constructors have the same parameter
+//!   types, the one whose Rust name sorts first stays a constructor and the others are
+//!   generated as static factory methods named after their Rust functions.
+//! * Members generated by cs-bindgen (e.g. `Dispose` and `ToString`), properties,
+//!   constants, and members renamed with `#[cs_bindgen(rename = "...")]` are never
+//!   suffixed. If two of them collide, generating the bindings fails so that one of
+//!   them can be renamed.
+//!
+//! Properties, constants, and the variants of data-carrying enums can't be overloaded,
+//! and C# doesn't allow members with the same name as their type, so methods with any
+//! of those names are always suffixed.
+
+use crate::{
+    generate::{func, quote_cs_type_for_repr, type_map, TypeMap, TypeNameExt},
+    Opt,
+};
+use cs_bindgen_shared::{schematic::Schema, BindingStyle, Export, FnArg, NamedType, Repr};
+use failure::Error;
+use heck::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
+
+/// A member of a C# class that takes part in name resolution.
+struct Member {
+    /// The C# name of the member.
+    name: String,
+
+    /// The C# types of the member's parameters, or `None` if the member can't be
+    /// overloaded, e.g. a property.
+    params: Option<Vec<String>>,
+
+    /// The index of the function or method in the exports, if the member can be
+    /// suffixed.
+    export: Option<usize>,
+
+    /// The Rust name of the member, used to order the members and in errors.
+    rust_name: String,
+}
+
+impl Member {
+    fn conflicts_with(&self, other: &Member) -> bool {
+        self.name == other.name
+            && match (&self.params, &other.params) {
+                (Some(params), Some(other)) => params == other,
+                _ => true,
+            }
+    }
+}
+
+/// The members of a single C# class.
+#[derive(Default)]
+struct Scope {
+    /// The members that always keep their names.
+    fixed: Vec<Member>,
+
+    /// The members that are suffixed if they collide with another member.
+    renamable: Vec<Member>,
+}
+
+impl Scope {
+    fn push(&mut self, member: Member) {
+        if member.export.is_some() {
+            self.renamable.push(member);
+        } else {
+            self.fixed.push(member);
+        }
+    }
+}
+
+/// Renames exported functions and methods whose C# names would collide, and turns
+/// constructors with the same parameter types into static factory methods, as
+/// described in the module docs.
+pub fn resolve_names(exports: &mut [Export], class_name: &str, opt: &Opt) -> Result<(), Error> {
+    let (factories, renames) = {
+        let types = type_map(exports, opt);
+        let factories = duplicate_constructors(exports, &types);
+
+        // The free functions and constants are declared in the static class, and the
+        // members of each type are declared in the type's class.
+        let mut scopes = BTreeMap::<String, Scope>::new();
+        scopes
+            .entry(class_name.into())
+            .or_default()
+            .push(fixed(class_name, None, class_name));
+        for export in exports.iter() {
+            if let Export::Named(export) = export {
+                let scope = scopes.entry(export.type_name.mangled_name()).or_default();
+                for member in generated_members(export, &types) {
+                    scope.push(member);
+                }
+            }
+        }
+
+        for (index, export) in exports.iter().enumerate() {
+            let (scope, member) = match export {
+                Export::Fn(export) => (
+                    class_name.to_string(),
+                    member(
+                        &export.name,
+                        export.rename.as_deref(),
+                        &export.inputs,
+                        index,
+                        &types,
+                    ),
+                ),
+
+                Export::Method(method) => {
+                    let member = match &method.accessor {
+                        Some(accessor) => match accessor.property_name() {
+                            Some(name) => fixed(&name.to_camel_case(), None, &method.name),
+
+                            // Indexers don't have a name.
+                            None => continue,
+                        },
+
+                        None if method.is_constructor && !factories.contains(&index) => continue,
+
+                        None => member(
+                            &method.name,
+                            method.rename.as_deref(),
+                            &method.inputs,
+                            index,
+                            &types,
+                        ),
+                    };
+                    (method.self_type.mangled_name(), member)
+                }
+
+                Export::Const(export) => {
+                    let scope = match &export.self_type {
+                        Some(self_type) => self_type.mangled_name(),
+                        None => class_name.to_string(),
+                    };
+                    (
+                        scope,
+                        fixed(&export.name.to_camel_case(), None, &export.name),
+                    )
+                }
+
+                _ => continue,
+            };
+
+            if let Some(scope) = scopes.get_mut(&scope) {
+                scope.push(member);
+            }
+        }
+
+        let mut renames = Vec::new();
+        for (name, scope) in scopes {
+            renames.extend(resolve_scope(&name, scope)?);
+        }
+
+        (factories, renames)
+    };
+
+    for index in factories {
+        if let Export::Method(method) = &mut exports[index] {
+            method.is_constructor = false;
+        }
+    }
+
+    for (index, name) in renames {
+        match &mut exports[index] {
+            Export::Fn(export) => export.rename = Some(name.into()),
+            Export::Method(method) => method.rename = Some(name.into()),
+            _ => unreachable!("Only functions and methods can be renamed"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the indices of the constructors that have the same parameter types as
+/// another constructor for the same type, other than the first one.
+fn duplicate_constructors(exports: &[Export], types: &TypeMap) -> BTreeSet<usize> {
+    let mut constructors = exports
+        .iter()
+        .enumerate()
+        .filter_map(|(index, export)| match export {
+            Export::Method(method) if method.is_constructor => Some((
+                method.self_type.mangled_name(),
+                param_types(&method.inputs, types),
+                method.name.to_string(),
+                index,
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    constructors.sort();
+
+    constructors
+        .windows(2)
+        .filter(|pair| pair[0].0 == pair[1].0 && pair[0].1 == pair[1].1)
+        .map(|pair| pair[1].3)
+        .collect()
+}
+
+/// Determines the names of the members of a class.
+///
+/// Returns the index and new name of each export that had to be suffixed.
+fn resolve_scope(scope: &str, members: Scope) -> Result<Vec<(usize, String)>, Error> {
+    let Scope {
+        fixed,
+        mut renamable,
+    } = members;
+
+    let mut resolved = Vec::<Member>::new();
+    for member in fixed {
+        if let Some(other) = resolved.iter().find(|other| member.conflicts_with(other)) {
+            return Err(failure::format_err!(
+                "`{}` and `{}` both generate a member named `{}` in `{}`, rename one of \
+                 them with `#[cs_bindgen(rename = \"...\")]`",
+                other.rust_name,
+                member.rust_name,
+                member.name,
+                scope,
+            ));
+        }
+
+        resolved.push(member);
+    }
+
+    let mut renames = Vec::new();
+    renamable.sort_by(|left, right| left.rust_name.cmp(&right.rust_name));
+    for mut member in renamable {
+        let base = member.name.clone();
+        let mut suffix = 1;
+        while resolved.iter().any(|other| member.conflicts_with(other)) {
+            suffix += 1;
+            member.name = format!("{}{}", base, suffix);
+        }
+
+        if suffix > 1 {
+            renames.push((member.export.unwrap(), member.name.clone()));
+        }
+
+        resolved.push(member);
+    }
+
+    Ok(renames)
+}
+
+/// Creates the member for an exported function or method.
+///
+/// Explicitly renamed members are fixed, since the user chose the name.
+fn member(
+    name: &str,
+    rename: Option<&str>,
+    inputs: &[FnArg],
+    index: usize,
+    types: &TypeMap,
+) -> Member {
+    Member {
+        name: func::fn_ident(name, rename).to_string(),
+        params: Some(param_types(inputs, types)),
+        export: if rename.is_some() { None } else { Some(index) },
+        rust_name: name.into(),
+    }
+}
+
+/// Creates a member that always keeps its name.
+fn fixed(name: &str, params: Option<Vec<String>>, rust_name: &str) -> Member {
+    Member {
+        name: name.into(),
+        params,
+        export: None,
+        rust_name: rust_name.into(),
+    }
+}
+
+/// Returns the members that are generated for a type.
+///
+/// Types that aren't handles only have static methods, which can't collide with the
+/// generated instance members. The variants of a data-carrying enum are nested in the
+/// class for the enum though, so static methods can collide with them.
+fn generated_members(export: &NamedType, types: &TypeMap) -> Vec<Member> {
+    let ident = export.type_name.ident().to_string();
+
+    // C# doesn't allow members with the same name as their type.
+    let type_member = fixed(&ident, None, &ident);
+    match &export.binding_style {
+        BindingStyle::Handle => {}
+
+        BindingStyle::Value(Schema::Enum(schema)) if schema.has_data() => {
+            return iter::once(type_member)
+                .chain(schema.variants.iter().map(|variant| {
+                    fixed(
+                        variant.name(),
+                        None,
+                        &format!("{}::{}", ident, variant.name()),
+                    )
+                }))
+                .collect();
+        }
+
+        _ => return vec![type_member],
+    }
+
+    let self_type =
+        quote_cs_type_for_repr(&Repr::Named(export.type_name.clone()), types).to_string();
+    let generated = |name: &str, params: &[&str]| {
+        fixed(
+            name,
+            Some(params.iter().map(|param| param.to_string()).collect()),
+            &format!("{}.{}", ident, name),
+        )
+    };
+
+    let mut members = vec![type_member, generated("Dispose", &[])];
+
+    if export.clone_fn.is_some() {
+        members.push(generated("Clone", &[]));
+    }
+
+    if export.display_fn.is_some() {
+        members.push(generated("ToString", &[]));
+    }
+
+    if export.eq_fn.is_some() {
+        members.push(generated("Equals", &[&self_type]));
+        members.push(generated("Equals", &["object"]));
+    }
+
+    if export.eq_fn.is_some() || export.hash_fn.is_some() {
+        members.push(generated("GetHashCode", &[]));
+    }
+
+    if export.cmp_fn.is_some() {
+        members.push(generated("CompareTo", &[&self_type]));
+    }
+
+    members
+}
+
+/// Returns the C# types of the parameters, which determine whether two members with
+/// the same name are valid overloads.
+fn param_types(inputs: &[FnArg], types: &TypeMap) -> Vec<String> {
+    inputs
+        .iter()
+        .map(|arg| func::quote_arg_type(&arg.repr, types).to_string())
+        .collect()
+}
@@ -0,0 +1,118 @@
+//! Code generation for fallible return values.
+//!
+//! On the Rust side, a `Result<T, E>` is marshaled as a `RawResult<T::Abi, E::Abi>`,
+//! which is a success flag paired with a union of the raw success and error values.
+//! The generated C# returns the `Ok` value directly and throws the `Err` value as a
+//! `RustException<E>`.
+//!
+//! As with `Option<T>`, we generate a dedicated raw struct for each result type
+//! used in the exported API since P/Invoke doesn't support generic structs.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the identifier of the raw struct generated for the specified result type.
+pub fn raw_result_ident(ok: &Repr, err: &Repr) -> Ident {
+    format_ident!(
+        "__RawResult__{}__{}",
+        generate::mangle_repr(ok),
+        generate::mangle_repr(err),
+    )
+}
+
+fn raw_result_value_ident(ok: &Repr, err: &Repr) -> Ident {
+    format_ident!(
+        "__RawResultValue__{}__{}",
+        generate::mangle_repr(ok),
+        generate::mangle_repr(err),
+    )
+}
+
+/// Returns `true` if `repr` is a `Result` with no meaningful success value, i.e.
+/// `Result<(), E>`.
+///
+/// C# functions returning such a result are generated as `void` functions that
+/// only throw on error.
+pub fn is_unit_result(repr: &Repr) -> bool {
+    match repr {
+        Repr::Result { ok, .. } => **ok == Repr::Unit,
+        _ => false,
+    }
+}
+
+/// Quotes the C# type that a result is converted to, i.e. the type of the `Ok` value.
+///
+/// For `Result<(), E>`, the raw unit value is used since C# doesn't allow values
+/// of type `void`. In practice the converted value is discarded, see
+/// [`is_unit_result`].
+///
+/// [`is_unit_result`]: fn.is_unit_result.html
+pub fn quote_cs_type(ok: &Repr, types: &TypeMap) -> TokenStream {
+    if *ok == Repr::Unit {
+        binding::raw_type_from_repr(ok, types)
+    } else {
+        generate::quote_cs_type_for_repr(ok, types)
+    }
+}
+
+/// Generates the raw struct and conversion function for the specified result type.
+///
+/// Results are only supported as return values, so only the `__FromRaw` conversion
+/// is generated.
+pub fn quote_raw_result(ok: &Repr, err: &Repr, types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_result_ident(ok, err);
+    let raw_value_ident = raw_result_value_ident(ok, err);
+    let raw_ok_ty = binding::raw_type_from_repr(ok, types);
+    let raw_err_ty = binding::raw_type_from_repr(err, types);
+    let ok_ty = quote_cs_type(ok, types);
+    let err_ty = generate::quote_cs_type_for_repr(err, types);
+
+    let from_raw = binding::from_raw_fn_ident();
+
+    // NOTE: The unit type doesn't have a `__FromRaw` overload, so we directly use the
+    // raw value in that case.
+    let convert_ok = if *ok == Repr::Unit {
+        quote! { result = raw.Value.Ok; }
+    } else {
+        quote! { #from_raw(raw.Value.Ok, out result); }
+    };
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ident raw, out #ok_ty result)
+        {
+            if (raw.IsOk == 0)
+            {
+                #from_raw(raw.Value.Err, out #err_ty error);
+                throw new RustException<#err_ty>(error);
+            }
+
+            #convert_ok
+        }
+    });
+
+    quote! {
+        // Generate the raw struct for the result type. This needs to match the
+        // `RawResult<T, E>` type on the Rust side.
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct #raw_ident
+        {
+            public byte IsOk;
+            public #raw_value_ident Value;
+        }
+
+        [StructLayout(LayoutKind.Explicit)]
+        internal struct #raw_value_ident
+        {
+            [FieldOffset(0)]
+            public #raw_ok_ty Ok;
+
+            [FieldOffset(0)]
+            public #raw_err_ty Err;
+        }
+
+        #raw_conversions
+    }
+}
@@ -1,13 +1,15 @@
 //! Code generation for exported struct types.
 
-use crate::generate::{self, binding, TypeMap, TypeNameExt};
+use crate::generate::{self, binding, comparison, docs, equality, func, TypeMap, TypeNameExt};
 use cs_bindgen_shared::{
-    schematic::{Field, StructLike},
-    BindingStyle, NamedType,
+    boxed_type_name,
+    schematic::{Field, Schema, StructLike},
+    BindingStyle, FnArg, NamedType, Repr,
 };
 use heck::CamelCase;
 use proc_macro2::TokenStream;
 use quote::*;
+use std::borrow::Cow;
 use syn::Ident;
 
 pub fn quote_struct(export: &NamedType, schema: StructLike<'_>, types: &TypeMap) -> TokenStream {
@@ -18,59 +20,119 @@ pub fn quote_struct(export: &NamedType, schema: StructLike<'_>, types: &TypeMap)
     );
 
     let ident = export.type_name.ident();
+    let qualified_ident = types.qualify(&export.type_name, &ident);
     let raw_ident = binding::raw_ident(&export.type_name);
 
-    let field_ident = schema
-        .fields
-        .iter()
-        .enumerate()
-        .map(|(index, field)| field_ident(field.name, index))
-        .collect::<Vec<_>>();
+    let struct_fields = struct_fields(&schema.fields, &export.renamed_fields, types);
+    let basic_constructor =
+        struct_constructor(&ident, &schema.fields, &export.renamed_fields, types);
+    let default_property = quote_default_property(export, types);
+    let to_string = quote_to_string(export, types);
+    let equality = equality::quote_members(export, types);
+    let comparison = comparison::quote_members(export, types);
 
-    let struct_fields = struct_fields(&schema.fields, types);
-    let basic_constructor = struct_constructor(&ident, &schema.fields, types);
-    let raw_fields = binding::raw_struct_fields(&schema.fields, types);
+    let interfaces = equality::quote_interface(export)
+        .into_iter()
+        .chain(comparison::quote_interface(export))
+        .collect::<Vec<_>>();
+    let base_list = if interfaces.is_empty() {
+        None
+    } else {
+        Some(quote! { : #( #interfaces ),* })
+    };
+    let raw_fields = binding::raw_struct_fields(&schema.fields, &export.renamed_fields, types);
 
-    let bindings = binding::bindings_class_ident();
     let from_raw = binding::from_raw_fn_ident();
     let into_raw = binding::into_raw_fn_ident();
 
+    // Blittable structs are passed to Rust directly, so they don't need a raw struct and
+    // the conversions just copy the value.
+    if is_blittable(export, types) {
+        let raw_conversions = binding::wrap_bindings(quote! {
+            internal static void #from_raw(#qualified_ident raw, out #qualified_ident result)
+            {
+                result = raw;
+            }
+
+            internal static void #into_raw(#qualified_ident self, out #qualified_ident result)
+            {
+                result = self;
+            }
+        });
+
+        let declaration = quote_declaration(
+            export,
+            quote! { [StructLayout(LayoutKind.Sequential)] },
+            quote! {
+                #struct_fields
+                #basic_constructor
+                #default_property
+                #to_string
+                #equality
+                #comparison
+            },
+            &base_list,
+            types,
+        );
+
+        return quote! {
+            #declaration
+            #raw_conversions
+        };
+    }
+
+    let fields_from_raw =
+        binding::quote_fields_from_raw(&schema.fields, &export.renamed_fields, types);
+    let fields_into_raw = binding::quote_fields_into_raw(
+        &schema.fields,
+        &export.renamed_fields,
+        &quote! { self },
+        types,
+    );
+
     let raw_conversions = binding::wrap_bindings(quote! {
-        internal static void #from_raw(#raw_ident raw, out #ident result)
+        internal static void #from_raw(#raw_ident raw, out #qualified_ident result)
         {
-            result = new #ident(raw);
+            result = new #qualified_ident(raw);
         }
 
-        internal static void #into_raw(#ident self, out #raw_ident result)
+        internal static void #into_raw(#qualified_ident self, out #raw_ident result)
         {
             result = new #raw_ident(self);
         }
     });
 
-    quote! {
-        public struct #ident
-        {
+    let declaration = quote_declaration(
+        export,
+        quote! {},
+        quote! {
             #struct_fields
             #basic_constructor
+            #default_property
+            #to_string
+            #equality
+            #comparison
 
             // Generate a constructor that can initialize the struct from its raw version.
             internal #ident(#raw_ident raw)
             {
-                #(
-                    #bindings.#from_raw(raw.#field_ident, out this.#field_ident);
-                )*
+                #fields_from_raw
             }
-        }
+        },
+        &base_list,
+        types,
+    );
+
+    quote! {
+        #declaration
 
         internal struct #raw_ident
         {
             #raw_fields
 
-            internal #raw_ident(#ident self)
+            internal #raw_ident(#qualified_ident self)
             {
-                #(
-                    #bindings.#into_raw(self.#field_ident, out this.#field_ident);
-                )*
+                #fields_into_raw
             }
         }
 
@@ -78,13 +140,181 @@ pub fn quote_struct(export: &NamedType, schema: StructLike<'_>, types: &TypeMap)
     }
 }
 
+/// Quotes the declaration of the public C# struct for an exported type, with the
+/// specified attributes and members.
+fn quote_declaration(
+    export: &NamedType,
+    attributes: TokenStream,
+    members: TokenStream,
+    base_list: &Option<TokenStream>,
+    types: &TypeMap,
+) -> TokenStream {
+    let ident = export.type_name.ident();
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+
+    // NOTE: The struct is declared `partial` so that associated constants can be added
+    // to it separately.
+    types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            #attributes
+            public partial struct #ident #base_list
+            {
+                #members
+            }
+        },
+    )
+}
+
+/// Quotes a check that the size of a blittable struct in C# matches the size of the
+/// Rust type, throwing an exception if they don't match.
+pub fn quote_layout_check(export: &NamedType, types: &TypeMap) -> TokenStream {
+    let ty = types.qualify(&export.type_name, export.type_name.ident());
+    let size_fn = export
+        .size_fn
+        .as_ref()
+        .expect("Struct marshaled by value must have a size function");
+    let size_fn = format_ident!("{}", &**size_fn);
+    let message = format!(
+        "Layout of {} in C# doesn't match its Rust definition",
+        export.type_name.name,
+    );
+
+    quote! {
+        if ((ulong)Marshal.SizeOf<#ty>() != #size_fn().ToUInt64())
+        {
+            throw new InvalidOperationException(#message);
+        }
+    }
+}
+
+/// Returns `true` if the C# struct for `export` has the same layout as its raw
+/// representation, i.e. every field is a fixed-size number or another blittable type.
+///
+/// Blittable structs are passed to Rust directly instead of being converted to a raw
+/// struct. Empty structs are never blittable, since C# structs can't be zero-sized.
+pub fn is_blittable(export: &NamedType, types: &TypeMap) -> bool {
+    if export.is_flags {
+        return false;
+    }
+
+    match export.schema().and_then(Schema::as_struct_like) {
+        Some(schema) => {
+            !schema.fields.is_empty()
+                && schema
+                    .fields
+                    .iter()
+                    .all(|field| is_blittable_field(&field.schema, types))
+        }
+        None => false,
+    }
+}
+
+/// Returns `true` if a field with the specified schema has the same layout in C# as
+/// its raw representation.
+fn is_blittable_field(schema: &Schema, types: &TypeMap) -> bool {
+    if boxed_type_name(schema).is_some() {
+        return false;
+    }
+
+    match schema {
+        Schema::I8
+        | Schema::I16
+        | Schema::I32
+        | Schema::I64
+        | Schema::U8
+        | Schema::U16
+        | Schema::U32
+        | Schema::U64
+        | Schema::F32
+        | Schema::F64 => true,
+
+        Schema::Enum(_)
+        | Schema::Struct(_)
+        | Schema::TupleStruct(_)
+        | Schema::UnitStruct(_)
+        | Schema::NewtypeStruct(_) => {
+            // NOTE: The unwrap here is valid because all of the matched variants have a
+            // type name.
+            let type_name = schema.type_name().unwrap();
+            let export = types
+                .get(type_name)
+                .unwrap_or_else(|| panic!("No export found for named type {:?}", type_name));
+
+            // C-like enums are only blittable if the C# enum has the same underlying type
+            // as the discriminant, which isn't the case for pointer-sized discriminants.
+            match export.schema() {
+                Some(Schema::Enum(schema)) => {
+                    !schema.has_data()
+                        && matches!(
+                            &export.discriminant,
+                            Some(repr) if !matches!(repr, Repr::ISize | Repr::USize)
+                        )
+                }
+                Some(_) => export.is_flags || is_blittable(export, types),
+                None => false,
+            }
+        }
+
+        _ => false,
+    }
+}
+
+/// Quotes the static `Default` property for a struct that implements `Default`.
+///
+/// C# structs can't declare a parameterless constructor, so the Rust default value is
+/// exposed as a property instead. Note that `default(T)` in C# still zero-initializes
+/// the struct, which may differ from the Rust default.
+fn quote_default_property(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let default_fn = export.default_fn.as_ref()?;
+    let ident = export.type_name.ident();
+    let block = func::quote_wrapper_block(
+        default_fn,
+        None,
+        &[],
+        Some(&Repr::Named(export.type_name.clone())),
+        types,
+    );
+
+    Some(quote! {
+        public static #ident Default
+        {
+            get #block
+        }
+    })
+}
+
+/// Quotes an override of `ToString` for a struct that implements `Display`.
+fn quote_to_string(export: &NamedType, types: &TypeMap) -> Option<TokenStream> {
+    let display_fn = export.display_fn.as_ref()?;
+
+    // NOTE: The struct is passed to Rust by value, so we pass a copy of `this` as a
+    // regular argument in order for it to be converted to its raw representation.
+    let self_arg = FnArg::new("self", Repr::Named(export.type_name.clone()));
+    let block =
+        func::quote_wrapper_block(display_fn, None, &[self_arg], Some(&Repr::String), types);
+
+    Some(quote! {
+        public override string ToString()
+        {
+            var self = this;
+            #block
+        }
+    })
+}
+
 /// Quotes the field declarations for the generated C# struct corresponding to an
 /// exported Rust type.
-pub fn struct_fields(fields: &[Field<'_>], types: &TypeMap) -> TokenStream {
+pub fn struct_fields(
+    fields: &[Field<'_>],
+    renamed: &[Cow<'static, str>],
+    types: &TypeMap,
+) -> TokenStream {
     let field_ident = fields
         .iter()
         .enumerate()
-        .map(|(index, field)| field_ident(field.name, index));
+        .map(|(index, field)| field_ident(field.name, index, renamed));
 
     let field_ty = fields
         .iter()
@@ -101,11 +331,16 @@ pub fn struct_fields(fields: &[Field<'_>], types: &TypeMap) -> TokenStream {
 ///
 /// The basic constructor has a parameter for each field in the struct, and directly
 /// assigns each field.
-pub fn struct_constructor(ident: &Ident, fields: &[Field<'_>], types: &TypeMap) -> TokenStream {
+pub fn struct_constructor(
+    ident: &Ident,
+    fields: &[Field<'_>],
+    renamed: &[Cow<'static, str>],
+    types: &TypeMap,
+) -> TokenStream {
     let field_ident = fields
         .iter()
         .enumerate()
-        .map(|(index, field)| field_ident(field.name, index));
+        .map(|(index, field)| field_ident(field.name, index, renamed));
 
     let arg_ident = fields
         .iter()
@@ -129,12 +364,18 @@ pub fn struct_constructor(ident: &Ident, fields: &[Field<'_>], types: &TypeMap)
 
 /// Converts the specified field name into a C#-appropriate ident, or generates an
 /// ident based on the index of the field if the field is unnamed.
-pub fn field_ident(name: Option<&str>, index: usize) -> Ident {
-    name.map(|name| format_ident!("{}", name.to_camel_case()))
-        .unwrap_or_else(|| format_ident!("Element{}", index))
+///
+/// Fields listed in `renamed` already have their C# name, and are used as-is.
+pub fn field_ident(name: Option<&str>, index: usize, renamed: &[Cow<'static, str>]) -> Ident {
+    match name {
+        Some(name) if renamed.iter().any(|renamed| renamed == name) => format_ident!("{}", name),
+        Some(name) => format_ident!("{}", name.to_camel_case()),
+        None => format_ident!("Element{}", index),
+    }
 }
 
-fn arg_ident(name: Option<&str>, index: usize) -> Ident {
+/// Returns the name of the constructor argument for the specified field.
+pub fn arg_ident(name: Option<&str>, index: usize) -> Ident {
     name.map(|name| format_ident!("{}", name))
         .unwrap_or_else(|| format_ident!("element_{}", index))
 }
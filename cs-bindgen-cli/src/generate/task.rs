@@ -0,0 +1,179 @@
+//! Code generation for async functions.
+//!
+//! Exported `async fn`s are exposed in C# as functions returning a `Task` or
+//! `Task<T>`. The wrapper function creates a `TaskCompletionSource` and passes it to
+//! Rust as a `RawCompletion`, which is a function pointer paired with a context
+//! pointer:
+//!
+//! * The function pointer points to a static completion function generated for each
+//!   output type. The completion function converts the raw result and completes the
+//!   task, or fails the task with a `RustPanicException` if the future panicked.
+//! * The context pointer is a `GCHandle` to the `TaskCompletionSource`, which keeps it
+//!   alive until the future completes. The handle is freed by the completion function.
+//!
+//! Rust runs the future on a background thread, so the completion function is invoked
+//! from that thread. The task is created with `RunContinuationsAsynchronously` so that
+//! continuations don't run on the Rust executor thread.
+
+use crate::generate::{self, binding, func, result, TypeMap};
+use cs_bindgen_shared::{Func, Repr};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the output repr used to generate the completion function for an async
+/// function.
+///
+/// Async functions that don't return a value complete with the raw unit value.
+pub fn completion_repr(export: &Func) -> Repr {
+    export.output.clone().unwrap_or(Repr::Unit)
+}
+
+/// Returns the identifier of the field holding the function pointer for the
+/// completion function for the specified output type.
+fn completion_ptr_ident(output: &Repr) -> Ident {
+    format_ident!("__CompletionPtr__{}", generate::mangle_repr(output))
+}
+
+/// Returns `true` if the task for the specified output type doesn't have a result.
+fn is_void(output: &Repr) -> bool {
+    *output == Repr::Unit || result::is_unit_result(output)
+}
+
+/// Quotes the type argument of the `TaskCompletionSource` for the specified output
+/// type.
+///
+/// There's no non-generic `TaskCompletionSource` in .NET Standard 2.0, so tasks
+/// without a result use a `TaskCompletionSource<byte>` and are exposed as a `Task`.
+fn quote_result_type(output: &Repr, types: &TypeMap) -> TokenStream {
+    if is_void(output) {
+        quote! { byte }
+    } else {
+        func::quote_return_type(Some(output), types)
+    }
+}
+
+/// Generates the C# wrapper function for an exported async function.
+pub fn quote_async_wrapper_fn(export: &Func, types: &TypeMap) -> TokenStream {
+    let name = func::fn_ident(&export.name, export.rename.as_deref());
+    let output = completion_repr(export);
+    let result_ty = quote_result_type(&output, types);
+    let task_ty = if is_void(&output) {
+        quote! { Task }
+    } else {
+        quote! { Task<#result_ty> }
+    };
+
+    let bindings = binding::bindings_class_ident();
+    let ptr_ident = completion_ptr_ident(&output);
+    let args = func::quote_optional_args(&export.inputs, types);
+    let body = func::quote_wrapper_body(
+        &export.binding,
+        None,
+        &export.inputs,
+        Some(quote! {
+            new RawCompletion(#bindings.#ptr_ident, GCHandle.ToIntPtr(__completionHandle))
+        }),
+        None,
+        types,
+    );
+
+    quote! {
+        public static #task_ty #name(#( #args ),*)
+        {
+            var __completion = new TaskCompletionSource<#result_ty>(
+                TaskCreationOptions.RunContinuationsAsynchronously);
+            var __completionHandle = GCHandle.Alloc(__completion);
+
+            try
+            {
+                unsafe
+                {
+                    #body
+                }
+            }
+            catch
+            {
+                // Rust only invokes the completion function once the future has been
+                // spawned, so we need to free the handle if the call failed.
+                __completionHandle.Free();
+                throw;
+            }
+
+            return __completion.Task;
+        }
+    }
+}
+
+/// Generates the raw delegate type and completion function for async functions with
+/// the specified output type.
+pub fn quote_raw_completion(output: &Repr, types: &TypeMap) -> TokenStream {
+    let mangled = generate::mangle_repr(output);
+    let raw_ident = format_ident!("__RawCompletion__{}", mangled);
+    let delegate_ident = format_ident!("__Completion__{}", mangled);
+    let complete_ident = format_ident!("__Complete__{}", mangled);
+    let ptr_ident = completion_ptr_ident(output);
+
+    let raw_ty = binding::raw_type_from_repr(output, types);
+    let result_ty = quote_result_type(output, types);
+    let from_raw = binding::from_raw_fn_ident();
+
+    // NOTE: The unit type doesn't have a `__FromRaw` overload, so we complete the task
+    // directly in that case. Results still need to be converted in order to throw the
+    // error, if any. The result is always converted on the thread that completed the
+    // future, since the raw result is only valid until the completion returns, and only
+    // completing the task is dispatched.
+    let set_result = if *output == Repr::Unit {
+        quote! { RustCallbacks.Dispatch(() => completion.SetResult(0)); }
+    } else if result::is_unit_result(output) {
+        quote! {
+            #from_raw(result, out var _);
+            RustCallbacks.Dispatch(() => completion.SetResult(0));
+        }
+    } else {
+        quote! {
+            #from_raw(result, out #result_ty value);
+            RustCallbacks.Dispatch(() => completion.SetResult(value));
+        }
+    };
+
+    let complete = binding::wrap_bindings(quote! {
+        // NOTE: The delegate is stored in a static field so that it's never garbage
+        // collected, which keeps the function pointer valid.
+        internal static readonly #raw_ident #delegate_ident = #complete_ident;
+        internal static readonly IntPtr #ptr_ident = Marshal.GetFunctionPointerForDelegate(#delegate_ident);
+
+        [MonoPInvokeCallback(typeof(#raw_ident))]
+        internal static void #complete_ident(IntPtr context, #raw_ty result, RawVec panic)
+        {
+            var handle = GCHandle.FromIntPtr(context);
+            var completion = (TaskCompletionSource<#result_ty>)handle.Target;
+            handle.Free();
+
+            if (panic.Ptr != IntPtr.Zero)
+            {
+                #from_raw(panic, out string message);
+                RustCallbacks.Dispatch(() => completion.SetException(new RustPanicException(message)));
+                return;
+            }
+
+            try
+            {
+                #set_result
+            }
+            catch (Exception e)
+            {
+                RustCallbacks.Dispatch(() => completion.SetException(e));
+            }
+        }
+    });
+
+    quote! {
+        // Generate the raw delegate type for the completion function. This needs to match
+        // the function signature expected by `RawCompletion` on the Rust side.
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void #raw_ident(IntPtr context, #raw_ty result, RawVec panic);
+
+        #complete
+    }
+}
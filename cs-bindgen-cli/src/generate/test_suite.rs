@@ -0,0 +1,143 @@
+//! Generation of an xUnit test suite that exercises the generated bindings against
+//! the native library.
+//!
+//! The tests are meant to catch marshaling regressions (struct layout, ownership of
+//! raw values, string encoding) without having to write tests by hand for every
+//! exported type:
+//!
+//! * Each value type that can be constructed without knowing anything about its fields
+//!   is converted to its raw representation and back. Structs use their `Default`
+//!   value and are compared with `Equals` if they implement `PartialEq`, C-like enums
+//!   round trip every variant, and flags round trip the empty set of flags.
+//! * Each synchronous function that takes no arguments is invoked once, which verifies
+//!   that the binding resolves against the native library and doesn't panic.
+//!
+//! The tests use the internal conversion functions, so the test project needs to be
+//! able to see the internals of the bindings, e.g. with `--test-assembly`.
+
+use crate::{
+    generate::{binding, class_ident, format, func, type_map, TypeNameExt},
+    Opt,
+};
+use cs_bindgen_shared::{schematic::Schema, BindingStyle, Export};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Generates the contents of the test file for the specified exports.
+pub fn generate_tests(exports: &[Export], opt: &Opt) -> String {
+    let types = type_map(exports, opt);
+    let class_name = class_ident(opt);
+    let test_class = format_ident!("{}Tests", class_name);
+
+    // NOTE: The class containing the exported functions is declared in the root
+    // namespace, if there is one.
+    let root_namespace = &types.root_namespace;
+    let qualified_class = quote! { global::#( #root_namespace. )* #class_name };
+
+    let bindings = binding::bindings_class_ident();
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    let tests = exports.iter().filter_map(|export| -> Option<TokenStream> {
+        match export {
+            Export::Named(export) => {
+                let schema = match &export.binding_style {
+                    BindingStyle::Value(schema) => schema,
+                    _ => return None,
+                };
+
+                let ty = types.qualify(&export.type_name, export.type_name.ident());
+                let test_ident = format_ident!("RoundTrip_{}", export.type_name.mangled_name());
+                let body = match schema {
+                    _ if export.is_flags => quote! {
+                        var value = default(#ty);
+                        #bindings.#into_raw(value, out var raw);
+                        #bindings.#from_raw(raw, out #ty result);
+                        Assert.Equal(value, result);
+                    },
+
+                    Schema::Enum(schema) if !schema.has_data() => {
+                        // NOTE: The unknown value of a non-exhaustive enum can't be
+                        // passed to Rust.
+                        let skip_unknown = if export.is_non_exhaustive {
+                            quote! {
+                                if (value == #ty.Unknown)
+                                {
+                                    continue;
+                                }
+                            }
+                        } else {
+                            quote! {}
+                        };
+
+                        quote! {
+                        foreach (#ty value in Enum.GetValues(typeof(#ty)))
+                        {
+                            #skip_unknown
+                            #bindings.#into_raw(value, out var raw);
+                            #bindings.#from_raw(raw, out #ty result);
+                            Assert.Equal(value, result);
+                        }
+                        }
+                    }
+
+                    Schema::Struct(_)
+                    | Schema::TupleStruct(_)
+                    | Schema::UnitStruct(_)
+                    | Schema::NewtypeStruct(_)
+                        if export.default_fn.is_some() =>
+                    {
+                        let check = if export.eq_fn.is_some() {
+                            quote! { Assert.Equal(value, result); }
+                        } else {
+                            quote! {}
+                        };
+
+                        quote! {
+                            var value = #ty.Default;
+                            #bindings.#into_raw(value, out var raw);
+                            #bindings.#from_raw(raw, out #ty result);
+                            #check
+                        }
+                    }
+
+                    _ => return None,
+                };
+
+                Some(quote! {
+                    [Fact]
+                    public void #test_ident()
+                    {
+                        #body
+                    }
+                })
+            }
+
+            Export::Fn(export) if !export.is_async && export.inputs.is_empty() => {
+                let fn_ident = func::fn_ident(&export.name, export.rename.as_deref());
+                let test_ident = format_ident!("Invoke_{}", fn_ident);
+                Some(quote! {
+                    [Fact]
+                    public void #test_ident()
+                    {
+                        #qualified_class.#fn_ident();
+                    }
+                })
+            }
+
+            _ => None,
+        }
+    });
+
+    let generated = quote! {
+        using System;
+        using Xunit;
+
+        public class #test_class
+        {
+            #( #tests )*
+        }
+    };
+
+    format::format(generated)
+}
@@ -0,0 +1,242 @@
+//! Code generation for call tracing, enabled with `--trace`.
+//!
+//! With `--trace`, the body of each wrapper function is surrounded by code that
+//! records the name of the raw binding, a summary of the arguments, and the time spent
+//! in the call, and passes the record to the handler registered with
+//! `RustTrace.SetHandler`. The tracing code is only compiled if `CS_BINDGEN_TRACE` is
+//! defined, so the same bindings can be used in release builds without any overhead:
+//!
+//! ```csharp
+//! {
+//! #if CS_BINDGEN_TRACE
+//!     var __traceArgs = RustTrace.IsEnabled ? "tiles=" + RustTrace.Summarize(tiles) : null;
+//!     var __traceStart = RustTrace.Timestamp();
+//!     try
+//! #endif
+//!     {
+//!         // Convert the arguments and call the raw binding as usual.
+//!     }
+//! #if CS_BINDGEN_TRACE
+//!     finally
+//!     {
+//!         RustTrace.Exit("__cs_bindgen_generated__shanten", __traceArgs, __traceStart);
+//!     }
+//! #endif
+//! }
+//! ```
+//!
+//! Records are named after the raw binding, which is also the name used by the
+//! Rust side of the bindings when the `trace` feature of `cs-bindgen` is enabled, so
+//! the records from both sides can be matched up.
+//!
+//! The token stream can't contain preprocessor directives, so directives are
+//! generated as a placeholder identifier followed by a string literal containing the
+//! directive, which the formatter writes out on its own line.
+
+use crate::generate::{func, TypeMap};
+use cs_bindgen_shared::{FnArg, Repr};
+use heck::*;
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::*;
+
+/// The identifier used to mark the location of a preprocessor directive in the
+/// generated code.
+const DIRECTIVE_PLACEHOLDER: &str = "__cs_bindgen_directive__";
+
+/// The symbol that enables the tracing code.
+const TRACE_SYMBOL: &str = "CS_BINDGEN_TRACE";
+
+/// Returns `true` if `ident` marks the location of a preprocessor directive.
+pub fn is_directive_placeholder(ident: &Ident) -> bool {
+    ident == DIRECTIVE_PLACEHOLDER
+}
+
+/// Returns the text of the directive for the string literal that follows a
+/// placeholder.
+pub fn directive_text(text: &Literal) -> String {
+    // NOTE: Directives never contain a `"` or a `\`, so the literal is always the text
+    // wrapped in quotes.
+    let text = text.to_string();
+    text[1..text.len() - 1].into()
+}
+
+/// Quotes a preprocessor directive, which is written on its own line when the code
+/// is formatted.
+pub fn quote_directive(directive: &str) -> TokenStream {
+    let placeholder = format_ident!("{}", DIRECTIVE_PLACEHOLDER);
+    let text = Literal::string(directive);
+    quote! { #placeholder #text }
+}
+
+fn quote_if_traced() -> TokenStream {
+    quote_directive(&format!("#if {}", TRACE_SYMBOL))
+}
+
+fn quote_endif() -> TokenStream {
+    quote_directive("#endif")
+}
+
+/// Wraps the body of a wrapper function in the tracing code, if tracing is enabled.
+///
+/// `inputs` are the arguments of the wrapper function, which are summarized before the
+/// call since the call may consume them, e.g. by disposing a handle that's passed by
+/// value.
+pub fn quote_traced_block(
+    binding: &str,
+    inputs: &[FnArg],
+    block: TokenStream,
+    types: &TypeMap,
+) -> TokenStream {
+    if !types.trace {
+        return block;
+    }
+
+    // The summary of each argument is labeled with its name and separated from the
+    // previous one, e.g. `seat=2, name="East"`.
+    let summaries = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, arg)| {
+            let name = arg.name.to_mixed_case();
+            let separator = if index == 0 { "" } else { ", " };
+            let label = Literal::string(&format!("{}{}=", separator, name));
+            let ident = func::quote_arg_ident(&arg.name);
+
+            // NOTE: Spans can't be boxed, so slice arguments are summarized by their
+            // length. Arrays used for slices in Unity are summarized the same way.
+            match &arg.repr {
+                Repr::Slice(_) => quote! { #label + #ident.Length + " items" },
+                _ => quote! { #label + RustTrace.Summarize(#ident) },
+            }
+        })
+        .collect::<Vec<_>>();
+    let args = if summaries.is_empty() {
+        quote! { "" }
+    } else {
+        quote! { #( #summaries )+* }
+    };
+
+    let if_traced = quote_if_traced();
+    let endif = quote_endif();
+
+    quote! {
+        {
+            #if_traced
+            var __traceArgs = RustTrace.IsEnabled ? #args : null;
+            var __traceStart = RustTrace.Timestamp();
+            try
+            #endif
+            #block
+            #if_traced
+            finally
+            {
+                RustTrace.Exit(#binding, __traceArgs, __traceStart);
+            }
+            #endif
+        }
+    }
+}
+
+/// Quotes the `RustTrace` class, which receives the trace records, if tracing is
+/// enabled.
+pub fn quote_trace_class(types: &TypeMap) -> TokenStream {
+    if !types.trace {
+        return quote! {};
+    }
+
+    let if_traced = quote_if_traced();
+    let endif = quote_endif();
+
+    quote! {
+        #if_traced
+        // Receives a record for each call made through the bindings, with the name of
+        // the raw binding, a summary of the arguments, and the time spent in the call.
+        // The handler is invoked on the calling thread once the call returns or throws,
+        // and any exception thrown by the handler is discarded.
+        public static class RustTrace
+        {
+            public delegate void Handler(string binding, string args, TimeSpan elapsed);
+
+            // The longest summary of a single argument, after which it's truncated.
+            private const int MaxSummaryLength = 64;
+
+            private static volatile Handler _handler;
+
+            internal static bool IsEnabled
+            {
+                get { return _handler != null; }
+            }
+
+            // Registers the handler for trace records, replacing any existing handler.
+            // Passing `null` disables tracing.
+            public static void SetHandler(Handler handler)
+            {
+                _handler = handler;
+            }
+
+            internal static long Timestamp()
+            {
+                return System.Diagnostics.Stopwatch.GetTimestamp();
+            }
+
+            internal static void Exit(string binding, string args, long start)
+            {
+                var handler = _handler;
+                if (handler == null || args == null)
+                {
+                    return;
+                }
+
+                var ticks = (System.Diagnostics.Stopwatch.GetTimestamp() - start)
+                    * TimeSpan.TicksPerSecond
+                    / System.Diagnostics.Stopwatch.Frequency;
+
+                try
+                {
+                    handler(binding, args, TimeSpan.FromTicks(ticks));
+                }
+                catch (Exception)
+                {
+                    // NOTE: Tracing shouldn't change the behavior of the call, so the
+                    // exception is dropped.
+                }
+            }
+
+            // Summarizes an argument for a trace record. Strings are quoted, collections
+            // are summarized by their length, and handles by their type, since their
+            // `ToString` may call back into Rust.
+            internal static string Summarize(object value)
+            {
+                string summary;
+                if (value == null)
+                {
+                    summary = "null";
+                }
+                else if (value is string text)
+                {
+                    summary = "\"" + text + "\"";
+                }
+                else if (value is System.Collections.ICollection collection)
+                {
+                    summary = collection.Count + " items";
+                }
+                else if (value is IDisposable)
+                {
+                    summary = value.GetType().Name;
+                }
+                else
+                {
+                    summary = value.ToString();
+                }
+
+                if (summary.Length > MaxSummaryLength)
+                {
+                    summary = summary.Substring(0, MaxSummaryLength) + "...";
+                }
+
+                return summary;
+            }
+        }
+        #endif
+    }
+}
@@ -0,0 +1,124 @@
+//! Code generation for transparent newtypes.
+//!
+//! Newtype structs exported with `#[cs_bindgen(transparent)]` or
+//! `#[repr(transparent)]` are passed across the FFI boundary as the value of their
+//! only field. By default they're exposed in C# as the C# type for that field, so no
+//! bindings are generated for the type itself. If the type was also exported with
+//! `wrapper`, a minimal readonly struct that wraps the inner value is generated
+//! instead, so that the newtype stays distinct from its inner type in C#:
+//!
+//! ```csharp
+//! public readonly partial struct PlayerName : IEquatable<PlayerName>
+//! {
+//!     public readonly string Value;
+//!
+//!     public PlayerName(string value)
+//!     {
+//!         Value = value;
+//!     }
+//! }
+//! ```
+//!
+//! NOTE: Lists of transparent newtypes are only supported for wrapper structs, since
+//! otherwise the list would have the same C# type as a list of the inner type.
+
+use crate::generate::{self, binding, docs, TypeMap, TypeNameExt};
+use cs_bindgen_shared::{NamedType, Transparent};
+use proc_macro2::TokenStream;
+use quote::*;
+
+/// Quotes the C# type that a transparent newtype is exposed as.
+pub fn quote_cs_type(
+    export: &NamedType,
+    transparent: &Transparent,
+    types: &TypeMap,
+) -> TokenStream {
+    if transparent.wrapper {
+        types.qualify(&export.type_name, export.type_name.ident())
+    } else {
+        generate::quote_cs_type_for_repr(&transparent.inner, types)
+    }
+}
+
+/// Generates the wrapper struct for a transparent newtype exported with `wrapper`,
+/// along with the `__FromRaw` and `__IntoRaw` overloads for the wrapper.
+///
+/// The conversions delegate to the conversions for the inner type, so the raw
+/// representation is identical to that of the inner type.
+pub fn quote_wrapper_struct(
+    export: &NamedType,
+    transparent: &Transparent,
+    types: &TypeMap,
+) -> TokenStream {
+    let ident = export.type_name.ident();
+    let qualified_ident = types.qualify(&export.type_name, &ident);
+    let inner_ty = generate::quote_cs_type_for_repr(&transparent.inner, types);
+    let raw_ty = binding::raw_type_from_repr(&transparent.inner, types);
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    let docs = docs::quote_doc_comment(export.docs.as_deref());
+    let declaration = types.wrap_namespace(
+        &export.type_name,
+        quote! {
+            #docs
+            public readonly partial struct #ident : IEquatable<#ident>
+            {
+                public readonly #inner_ty Value;
+
+                public #ident(#inner_ty value)
+                {
+                    Value = value;
+                }
+
+                public bool Equals(#ident other)
+                {
+                    return EqualityComparer<#inner_ty>.Default.Equals(Value, other.Value);
+                }
+
+                public override bool Equals(object obj)
+                {
+                    return obj is #ident other && Equals(other);
+                }
+
+                public override int GetHashCode()
+                {
+                    return EqualityComparer<#inner_ty>.Default.GetHashCode(Value);
+                }
+
+                public override string ToString()
+                {
+                    return Convert.ToString(Value);
+                }
+
+                public static bool operator ==(#ident left, #ident right)
+                {
+                    return left.Equals(right);
+                }
+
+                public static bool operator !=(#ident left, #ident right)
+                {
+                    return !left.Equals(right);
+                }
+            }
+        },
+    );
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ty raw, out #qualified_ident result)
+        {
+            #from_raw(raw, out #inner_ty value);
+            result = new #qualified_ident(value);
+        }
+
+        internal static void #into_raw(#qualified_ident value, out #raw_ty result)
+        {
+            #into_raw(value.Value, out result);
+        }
+    });
+
+    quote! {
+        #declaration
+        #raw_conversions
+    }
+}
@@ -0,0 +1,91 @@
+//! Code generation for tuples.
+//!
+//! On the Rust side, a tuple is marshaled as a `RawTupleN` struct with one field per
+//! element. The generated C# exposes tuples as a `ValueTuple`, e.g. a Rust
+//! `(i32, String)` becomes a C# `(int, string)`. As with `Option<T>`, we generate a
+//! dedicated raw struct for each tuple type used in the exported API.
+
+use crate::generate::{self, binding, TypeMap};
+use cs_bindgen_shared::Repr;
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::Ident;
+
+/// Returns the identifier of the raw struct generated for a tuple with the specified
+/// element types.
+pub fn raw_tuple_ident(elements: &[Repr]) -> Ident {
+    let elements = elements
+        .iter()
+        .map(generate::mangle_repr)
+        .collect::<Vec<_>>();
+    format_ident!("__RawTuple__{}", elements.join("__"))
+}
+
+/// Quotes the idiomatic C# type for a tuple with the specified element types.
+pub fn quote_cs_type(elements: &[Repr], types: &TypeMap) -> TokenStream {
+    let element_ty = elements
+        .iter()
+        .map(|element| generate::quote_cs_type_for_repr(element, types));
+    quote! { ( #( #element_ty ),* ) }
+}
+
+/// Generates the raw struct and conversion functions for a tuple with the specified
+/// element types.
+pub fn quote_raw_tuple(elements: &[Repr], types: &TypeMap) -> TokenStream {
+    let raw_ident = raw_tuple_ident(elements);
+    let cs_ty = quote_cs_type(elements, types);
+
+    let raw_field = (0..elements.len())
+        .map(|index| format_ident!("Element{}", index))
+        .collect::<Vec<_>>();
+    let raw_field_ty = elements
+        .iter()
+        .map(|element| binding::raw_type_from_repr(element, types))
+        .collect::<Vec<_>>();
+    let element_ty = elements
+        .iter()
+        .map(|element| generate::quote_cs_type_for_repr(element, types));
+    let element_var = (0..elements.len())
+        .map(|index| format_ident!("element{}", index))
+        .collect::<Vec<_>>();
+
+    // NOTE: C# tuple fields are 1-indexed, whereas the raw fields are 0-indexed to
+    // match the Rust side.
+    let item_field = (1..=elements.len()).map(|index| format_ident!("Item{}", index));
+
+    let from_raw = binding::from_raw_fn_ident();
+    let into_raw = binding::into_raw_fn_ident();
+
+    let raw_conversions = binding::wrap_bindings(quote! {
+        internal static void #from_raw(#raw_ident raw, out #cs_ty result)
+        {
+            #(
+                #from_raw(raw.#raw_field, out #element_ty #element_var);
+            )*
+
+            result = ( #( #element_var ),* );
+        }
+
+        internal static void #into_raw(#cs_ty value, out #raw_ident result)
+        {
+            result = new #raw_ident();
+            #(
+                #into_raw(value.#item_field, out result.#raw_field);
+            )*
+        }
+    });
+
+    quote! {
+        // Generate the raw struct for the tuple type. This needs to match the
+        // corresponding `RawTupleN` type on the Rust side.
+        [StructLayout(LayoutKind.Sequential)]
+        internal struct #raw_ident
+        {
+            #(
+                public #raw_field_ty #raw_field;
+            )*
+        }
+
+        #raw_conversions
+    }
+}
@@ -1,18 +1,200 @@
-use crate::Opt;
-use cs_bindgen_shared::Export;
+use cs_bindgen_shared::{BindingStyle, Export, StdTrait, METADATA_HASH_SEED};
 use failure::Error;
+use libloading::{Library, Symbol};
+use object::{BinaryFormat, Object};
 use parity_wasm::elements::ExportEntry;
-use std::str;
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    slice, str,
+};
 use wasmi::{ExternVal, ImportsBuilder, Module, ModuleInstance, NopExternals};
 
 static DECL_PTR_FN_PREFIX: &str = "__cs_bindgen_describe__";
 
+/// Loads the export declarations from the specified input file.
+///
+/// Wasm modules are loaded with an interpreter, and any other input is assumed to be
+/// a native dynamic library (`.dll`, `.so`, or `.dylib`) built for the host platform,
+/// which is loaded directly into the process.
+///
+/// The declarations are sorted by the name of their descriptor function, so that the
+/// order doesn't depend on the order of the symbols in the input file. Impls of
+/// standard library traits are merged into the declarations of their types, so the
+/// returned declarations never contain an `Export::StdImpl`.
+pub fn load_declarations(input: &Path) -> Result<Vec<Export>, Error> {
+    let (exports, _) = load_declarations_with_hash(input)?;
+    Ok(exports)
+}
+
+/// The declarations exported by a library, which the generated bindings check against
+/// the library that they load at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataHash {
+    /// The names of the library's describe functions, in the order that they're
+    /// hashed.
+    pub describe_fns: Vec<String>,
+
+    /// The hash of the declarations returned by the describe functions.
+    pub hash: u64,
+}
+
+/// Loads the export declarations from the specified input file, along with the hash of
+/// the declarations as they were returned by the library's describe functions.
+fn load_declarations_with_hash(input: &Path) -> Result<(Vec<Export>, MetadataHash), Error> {
+    let declarations = if input.extension() == Some(OsStr::new("wasm")) {
+        load_wasm_declarations(input)?
+    } else {
+        load_native_declarations(input)?
+    };
+
+    let mut metadata = MetadataHash {
+        describe_fns: Vec::with_capacity(declarations.len()),
+        hash: METADATA_HASH_SEED,
+    };
+    let mut exports = Vec::with_capacity(declarations.len());
+    for (describe_fn, json) in declarations {
+        metadata.hash = cs_bindgen_shared::hash_metadata(metadata.hash, json.as_bytes());
+        metadata.describe_fns.push(describe_fn);
+        exports.push(serde_json::from_str(&json)?);
+    }
+
+    Ok((merge_std_impls(exports)?, metadata))
+}
+
+/// Loads and merges the export declarations from several input files, e.g. a crate and
+/// a companion crate that uses its types, so that a single set of bindings can be
+/// generated for all of them.
+///
+/// Returns the merged declarations, the index of the input that each declaration was
+/// loaded from, and the hash of the declarations exported by the first input. The
+/// generated bindings only load the first input's library, so only its declarations
+/// are checked at runtime (see the `handshake` module).
+///
+/// A library that links another exporting crate also exports that crate's
+/// declarations, so declarations that were already loaded from an earlier input are
+/// skipped. If two inputs export different declarations for the same type or binding
+/// function, an error is returned.
+pub fn load_all_declarations(
+    inputs: &[PathBuf],
+) -> Result<(Vec<Export>, Vec<usize>, MetadataHash), Error> {
+    let mut exports = Vec::new();
+    let mut sources = Vec::new();
+    let mut metadata = MetadataHash::default();
+    let mut loaded = HashMap::<String, (usize, String)>::new();
+    for (index, input) in inputs.iter().enumerate() {
+        let (declarations, hash) = load_declarations_with_hash(input)?;
+        if index == 0 {
+            metadata = hash;
+        }
+
+        for export in declarations {
+            let key = export_key(&export);
+            let json = serde_json::to_string(&export)?;
+            if let Some((source, other)) = loaded.get(&key) {
+                if *other == json {
+                    continue;
+                }
+
+                return Err(failure::format_err!(
+                    "`{}` is exported by both {:?} and {:?} with different declarations, rebuild \
+                     them against the same version of the crate that declares it or rename one of them",
+                    key,
+                    inputs[*source],
+                    input,
+                ));
+            }
+
+            loaded.insert(key, (index, json));
+            exports.push(export);
+            sources.push(index);
+        }
+    }
+
+    Ok((exports, sources, metadata))
+}
+
+/// Returns the name that identifies an export across all of the inputs, which is either
+/// the path of the exported type or trait, or the name of the export's binding function.
+fn export_key(export: &Export) -> String {
+    match export {
+        Export::Fn(export) => export.binding.to_string(),
+        Export::Method(export) => export.binding.to_string(),
+        Export::Const(export) => export.binding.to_string(),
+        Export::StdImpl(export) => export.binding.to_string(),
+        Export::Conversion(export) => export.binding.to_string(),
+        Export::Operator(export) => export.binding.to_string(),
+        Export::TraitImpl(export) => export.as_dyn_fn.to_string(),
+        Export::List(export) => export.index_fn.to_string(),
+        Export::Named(export) => format!("{}::{}", export.type_name.module, export.type_name.name),
+        Export::Trait(export) => format!("{}::{}", export.type_name.module, export.type_name.name),
+        Export::Alias(export) => format!("{}::{}", export.module, export.name),
+    }
+}
+
+/// Merges the impls of standard library traits into the declarations of their types,
+/// as if the trait had been specified on the type itself.
+fn merge_std_impls(exports: Vec<Export>) -> Result<Vec<Export>, Error> {
+    let (impls, mut exports): (Vec<_>, Vec<_>) = exports
+        .into_iter()
+        .partition(|export| matches!(export, Export::StdImpl(_)));
+
+    for export in impls {
+        let std_impl = match export {
+            Export::StdImpl(std_impl) => std_impl,
+            _ => unreachable!(),
+        };
+
+        let named = exports
+            .iter_mut()
+            .find_map(|export| match export {
+                Export::Named(named) if named.type_name == std_impl.self_type => Some(named),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                failure::format_err!(
+                    "`{}::{}` implements `{:?}` with `#[cs_bindgen]`, but the type isn't exported",
+                    std_impl.self_type.module,
+                    std_impl.self_type.name,
+                    std_impl.trait_,
+                )
+            })?;
+
+        let binding_fn = match std_impl.trait_ {
+            StdTrait::Default => &mut named.default_fn,
+            StdTrait::Display => &mut named.display_fn,
+            StdTrait::PartialEq => &mut named.eq_fn,
+            StdTrait::Hash => &mut named.hash_fn,
+            StdTrait::PartialOrd => &mut named.cmp_fn,
+
+            // `Clone` is only exported for handle types, since types marshaled by value
+            // are already copied when passed to C#.
+            StdTrait::Clone if named.binding_style == BindingStyle::Handle => &mut named.clone_fn,
+            StdTrait::Clone => continue,
+        };
+
+        // NOTE: If the trait was also specified on the type, the binding generated for
+        // the type is kept. In particular, clones of shared handles share the value
+        // rather than using the type's `Clone` impl.
+        if binding_fn.is_none() {
+            *binding_fn = Some(std_impl.binding);
+        }
+    }
+
+    Ok(exports)
+}
+
 /// Loads the specified Wasm module and extracts the export declarations.
-pub fn load_declarations(opt: &Opt) -> Result<Vec<Export>, Error> {
+///
+/// Returns the name of each describe function along with the JSON declaration that it
+/// returned, sorted by the name of the function.
+fn load_wasm_declarations(path: &Path) -> Result<Vec<(String, String)>, Error> {
     // Load the WASM module from the specified file.
-    let module = parity_wasm::deserialize_file(&opt.input)?;
+    let module = parity_wasm::deserialize_file(path)?;
 
-    let descriptor_fns = module
+    let mut descriptor_fns = module
         .export_section()
         .ok_or(failure::err_msg("No exports found in Wasm module"))?
         .entries()
@@ -21,6 +203,7 @@ pub fn load_declarations(opt: &Opt) -> Result<Vec<Export>, Error> {
         .filter(|name| name.starts_with(DECL_PTR_FN_PREFIX))
         .map(Into::into)
         .collect::<Vec<String>>();
+    descriptor_fns.sort();
 
     // Instantiate a module with empty imports and
     // assert that there is no `start` function.
@@ -35,7 +218,7 @@ pub fn load_declarations(opt: &Opt) -> Result<Vec<Export>, Error> {
         .ok_or(failure::err_msg("No memory export found in Wasm module"))?;
 
     // Find any exported declarations and extract the declaration data from the module.
-    let mut exports = Vec::new();
+    let mut declarations = Vec::new();
     for func in descriptor_fns {
         let result_string_addr = instance
             .invoke_export(&func, &[], &mut NopExternals)?
@@ -50,11 +233,74 @@ pub fn load_declarations(opt: &Opt) -> Result<Vec<Export>, Error> {
         // Get the JSON string returned by the descriptor function.
         let json_bytes = memory.get(str_ptr, str_len as usize)?;
         let json = str::from_utf8(&json_bytes)?;
+        declarations.push((func, json.to_owned()));
+    }
+
+    Ok(declarations)
+}
 
-        // Deserialize the export and add it to the list.
-        let export = serde_json::from_str(json)?;
-        exports.push(export);
+/// The layout of the `RawString` returned by the descriptor functions, which matches
+/// `cs_bindgen::abi::RawString` on the host platform.
+#[repr(C)]
+struct RawString {
+    ptr: *const u8,
+    len: usize,
+    _capacity: usize,
+}
+
+/// Loads the specified native library and extracts the export declarations.
+///
+/// The names of the descriptor functions are read from the library's symbol table,
+/// since a loaded library can't be queried for the symbols that it exports. Returns
+/// the name of each describe function along with the JSON declaration that it
+/// returned, sorted by the name of the function.
+fn load_native_declarations(path: &Path) -> Result<Vec<(String, String)>, Error> {
+    let data =
+        fs::read(path).map_err(|err| failure::format_err!("Failed to read {:?}: {}", path, err))?;
+    let file = object::File::parse(&data)
+        .map_err(|err| failure::format_err!("Failed to parse {:?}: {}", path, err))?;
+
+    // NOTE: Symbols in Mach-O files are prefixed with an underscore, which isn't part
+    // of the name used to look up the symbol.
+    let prefix_len = if file.format() == BinaryFormat::MachO {
+        1
+    } else {
+        0
+    };
+    let mut descriptor_fns = file
+        .exports()
+        .map_err(|err| failure::format_err!("Failed to read exports of {:?}: {}", path, err))?
+        .iter()
+        .filter_map(|export| str::from_utf8(export.name()).ok())
+        .filter_map(|name| name.get(prefix_len..))
+        .filter(|name| name.starts_with(DECL_PTR_FN_PREFIX))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    descriptor_fns.sort();
+
+    if descriptor_fns.is_empty() {
+        return Err(failure::format_err!(
+            "No exported declarations found in {:?}",
+            path
+        ));
     }
 
-    Ok(exports)
+    let library = Library::new(path)
+        .map_err(|err| failure::format_err!("Failed to load {:?}: {}", path, err))?;
+
+    let mut declarations = Vec::new();
+    for func in descriptor_fns {
+        // SAFETY: The descriptor functions are generated by `#[cs_bindgen]` and all
+        // have this signature. The returned string is intentionally leaked, since it was
+        // allocated by the library's allocator and the library is unloaded on exit.
+        let json = unsafe {
+            let describe: Symbol<unsafe extern "C" fn() -> *const RawString> =
+                library.get(func.as_bytes())?;
+            let raw = &*describe();
+            str::from_utf8(slice::from_raw_parts(raw.ptr, raw.len))?.to_owned()
+        };
+        declarations.push((func, json));
+    }
+
+    Ok(declarations)
 }
@@ -1,16 +1,144 @@
-use crate::load_decl::load_declarations;
-use std::{fs, fs::File, io::prelude::*, path::PathBuf, process};
-use structopt::*;
+use crate::{
+    config::{Config, TypeConfig},
+    load_decl::load_all_declarations,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    fs::File,
+    io::prelude::*,
+    mem,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+};
+use structopt::{clap::AppSettings, *};
 
+mod build;
+mod c_header;
+mod cache;
+mod check;
+mod config;
 mod generate;
 mod load_decl;
+mod metadata;
+mod pack;
+mod python;
+mod source_generator;
+mod ts;
+mod unity;
 
 fn main() {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
 
-    let result = load_declarations(&opt).and_then(|decls| generate::generate_bindings(decls, &opt));
-    let generated = match result {
-        Ok(decls) => decls,
+    // The `build` subcommand builds the input before generating the bindings as usual,
+    // so it's run once the rest of the options have been loaded.
+    let build = match opt.command.take() {
+        Some(Command::Build(build)) => Some(build),
+        Some(command) => {
+            let result = match command {
+                Command::Dump { input, output } => metadata::dump(&input, output.as_deref()),
+                Command::Check { old, new } => check::check(&old, &new).and_then(|report| {
+                    report.print();
+                    if report.is_breaking() {
+                        return Err(failure::err_msg("The API has breaking changes"));
+                    }
+
+                    Ok(())
+                }),
+                Command::Build(_) => unreachable!(),
+            };
+
+            if let Err(err) = result {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+
+            return;
+        }
+        None => None,
+    };
+
+    if let Err(err) = Config::load(&opt).and_then(|config| config.apply(&mut opt)) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+
+    if let Some(build) = &build {
+        if let Err(err) = build::build(build, &mut opt) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+
+    // NOTE: `--force` doesn't affect the generated code, so it's cleared before the
+    // options are hashed for the cache.
+    let force = mem::replace(&mut opt.force, false);
+
+    // NOTE: The other targets load a single library, so they can't reference exports
+    // from other inputs.
+    if opt.inputs().len() > 1
+        && (opt.target() != Target::CSharp || opt.source_generator || opt.blazor)
+    {
+        eprintln!("Multiple inputs are only supported when generating C# bindings without `--source-generator` or `--blazor`");
+        process::exit(1);
+    }
+
+    let result = load_all_declarations(opt.inputs()).and_then(|(decls, sources, metadata_hash)| {
+        // NOTE: The project is generated first since generating the bindings consumes
+        // the declarations.
+        let project = if opt.pack {
+            Some(pack::generate_project(&decls, &opt))
+        } else {
+            None
+        };
+
+        if let Some(tests_path) = &opt.tests {
+            write_file(tests_path, &generate::generate_tests(&decls, &opt));
+        }
+
+        if let Some(bench_dir) = &opt.bench {
+            fs::create_dir_all(bench_dir).expect("Failed to create benchmark directory");
+            for file in generate::generate_bench(&decls, &opt) {
+                write_file(&bench_dir.join(&file.name), &file.contents);
+            }
+        }
+
+        // NOTE: The metadata is serialized before generating the bindings, since
+        // generating the bindings consumes the declarations.
+        let metadata = if opt.source_generator {
+            Some(metadata::Metadata::new(decls.clone()).to_json())
+        } else {
+            None
+        };
+
+        // Skip generating the bindings if the output directory already contains the
+        // bindings for the same inputs.
+        let inputs = cache::hash_inputs(&decls, &opt);
+        if let Some(out_dir) = &opt.out_dir {
+            if !force && cache::is_up_to_date(out_dir, inputs) {
+                return Ok((None, project, inputs));
+            }
+        }
+
+        let files = match opt.target() {
+            Target::CSharp => generate::generate_bindings(decls, &sources, &metadata_hash, &opt)?,
+            Target::TypeScript => ts::generate(&decls, &opt),
+            Target::Python => python::generate(&decls, &opt),
+            Target::C => c_header::generate(&decls, &opt),
+        };
+
+        // The source generator embeds the bindings, so it's written instead of the
+        // bindings themselves.
+        let files = match &metadata {
+            Some(metadata) => source_generator::generate_project(&files, metadata, &opt),
+            None => files,
+        };
+
+        Ok((Some(files), project, inputs))
+    });
+    let (files, project, inputs) = match result {
+        Ok(files) => files,
         Err(err) => {
             // TODO: Provide suggestions for what users can do to resolve the issue.
             eprintln!("{}", err);
@@ -18,7 +146,53 @@ fn main() {
         }
     };
 
-    match opt.output {
+    // Write each generated file to the output directory.
+    if let Some(out_dir) = &opt.out_dir {
+        fs::create_dir_all(out_dir).expect("Failed to create output directory");
+        match &files {
+            Some(files) => {
+                if let Err(err) = cache::write_files(out_dir, files, inputs, force) {
+                    eprintln!("{}", err);
+                    process::exit(1);
+                }
+            }
+
+            None => eprintln!("Bindings are up to date"),
+        }
+
+        if opt.unity {
+            let name = pack::project_name(&opt);
+            write_file(
+                &out_dir.join(format!("{}.asmdef", name)),
+                &unity::generate_asmdef(&name),
+            );
+
+            if let Err(err) = unity::copy_plugins(&opt, out_dir) {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
+
+        if let Some(project) = project {
+            let project_path = out_dir.join(format!("{}.csproj", pack::project_name(&opt)));
+            write_file(&project_path, &project);
+
+            if opt.dotnet_pack {
+                if let Err(err) = pack::dotnet_pack(&project_path) {
+                    eprintln!("{}", err);
+                    process::exit(1);
+                }
+            }
+        }
+
+        return;
+    }
+
+    // NOTE: Without an output directory all bindings are generated as a single file,
+    // and they're always generated since there's no cache.
+    let files = files.expect("Bindings are always generated without an output directory");
+    let generated = &files[0].contents;
+    match &opt.output {
         // If no output file was specified, print to stdout.
         None => println!("{}", generated),
 
@@ -29,19 +203,317 @@ fn main() {
                 fs::create_dir_all(parent).expect("Failed to create directory for output");
             }
 
-            let mut file = File::create(&out_path).expect("Failed to open output file");
-            file.write_all(generated.as_bytes())
-                .expect("Failed to write to output file");
+            write_file(out_path, generated);
         }
     }
 }
 
+fn write_file(path: &Path, contents: &str) {
+    let mut file = File::create(path).expect("Failed to open output file");
+    file.write_all(contents.as_bytes())
+        .expect("Failed to write to output file");
+}
+
 #[derive(Debug, StructOpt)]
-#[structopt(name = "cs-bindgen")]
+#[structopt(name = "cs-bindgen", setting = AppSettings::SubcommandsNegateReqs)]
 pub struct Opt {
-    #[structopt(parse(from_os_str))]
-    input: PathBuf,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+
+    /// The Wasm module or native library (`.dll`, `.so`, or `.dylib`) to load the
+    /// exported declarations from.
+    ///
+    /// Multiple native libraries can be specified to generate a single set of C#
+    /// bindings for crates that use each other's types, e.g. a crate and a companion
+    /// crate built on top of it. Each binding loads the library it was exported from,
+    /// and names that default to the name of the input file are based on the first
+    /// input. `--dll-name` only applies to the first input.
+    #[structopt(parse(from_os_str), required = true)]
+    input: Vec<PathBuf>,
 
-    #[structopt(short, long, parse(from_os_str))]
+    /// The config file to load settings from. Defaults to `cs-bindgen.toml` in the
+    /// current directory, if it exists.
+    ///
+    /// Options specified on the command line take precedence over the config file.
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    #[structopt(short, long, parse(from_os_str), conflicts_with = "out-dir")]
     output: Option<PathBuf>,
+
+    /// Write the bindings to a directory, with the declarations for each exported type
+    /// in a separate file named after the type, plus a shared `Bindings.cs`.
+    ///
+    /// File names are stable across runs, which keeps diffs of the generated code
+    /// reviewable when it's checked in, e.g. as part of a Unity project.
+    #[structopt(long, parse(from_os_str))]
+    out_dir: Option<PathBuf>,
+
+    /// Regenerate and rewrite all of the bindings in the output directory, even if the
+    /// export metadata and options haven't changed since they were last generated.
+    ///
+    /// By default, generation is skipped if the output directory is up to date, and
+    /// only files whose contents changed are rewritten.
+    #[structopt(long)]
+    force: bool,
+
+    /// The language to generate bindings for: `cs`, `ts`, `python`, or `c`. Defaults
+    /// to `cs`.
+    ///
+    /// The `ts` target generates TypeScript declarations and a JavaScript wrapper for
+    /// a Wasm module, and the `python` target generates a Python package that loads
+    /// the native library with `ctypes`. Both require `--out-dir`. The `c` target
+    /// generates a C header declaring the raw bindings. Options that only apply to C#
+    /// are ignored.
+    #[structopt(long)]
+    target: Option<Target>,
+
+    /// Generate a `.csproj` in the output directory for packaging the bindings as a
+    /// NuGet package.
+    ///
+    /// The project targets the framework specified by `--target-framework`. Requires
+    /// `--out-dir`.
+    #[structopt(long)]
+    pack: bool,
+
+    /// The ID of the generated NuGet package. Defaults to the name of the input file.
+    #[structopt(long)]
+    package_id: Option<String>,
+
+    /// The version of the generated NuGet package. Defaults to `0.1.0`.
+    #[structopt(long)]
+    package_version: Option<String>,
+
+    /// A native library to include in the NuGet package as a runtime asset, or in the
+    /// `Plugins` folder of the Unity package, specified as `<rid>=<path>`, e.g.
+    /// `win-x64=target/release/my_crate.dll`.
+    ///
+    /// Can be specified multiple times to include the library for multiple platforms.
+    #[structopt(long, number_of_values = 1)]
+    native_lib: Vec<pack::NativeLib>,
+
+    /// Run `dotnet pack` on the generated project.
+    #[structopt(long)]
+    dotnet_pack: bool,
+
+    /// Generate the bindings as a Unity package in the output directory, with an
+    /// assembly definition for the bindings and the native libraries arranged under
+    /// `Plugins` for each platform.
+    ///
+    /// The bindings only use APIs that are available under IL2CPP and .NET Standard 2.0,
+    /// so `--target-framework` is ignored. Requires `--out-dir`.
+    #[structopt(long, conflicts_with = "pack")]
+    unity: bool,
+
+    /// Generate the bindings for a Blazor WebAssembly app, calling the Wasm build of
+    /// the library through `[JSImport]` and a generated JavaScript module, instead of
+    /// loading a native library with `[DllImport]`.
+    ///
+    /// The JavaScript module is written to the output directory as `<dll>.interop.js`,
+    /// and must be loaded with `RustModule.InitializeAsync` before the bindings are
+    /// used. Only functions whose arguments and return value are numbers or handles can
+    /// be called, and other functions throw a `PlatformNotSupportedException`.
+    /// Requires `--out-dir` and .NET 7 or later.
+    #[structopt(long, conflicts_with_all = &["unity", "source-generator"])]
+    blazor: bool,
+
+    /// Generate a Roslyn source generator in the output directory that adds the
+    /// bindings to a C# project at compile time, instead of writing out the bindings.
+    ///
+    /// The source generator checks the export metadata of the native library, provided
+    /// as an additional file, and reports an error if it doesn't match the metadata the
+    /// bindings were generated from. Requires `--out-dir`.
+    #[structopt(long, conflicts_with_all = &["pack", "unity"])]
+    source_generator: bool,
+
+    /// The target framework moniker of the C# project that will use the generated
+    /// bindings, e.g. `netstandard2.0` or `netcoreapp3.1`.
+    ///
+    /// Used to select the fastest implementation of some conversions for the target
+    /// runtime. Defaults to `netstandard2.0`.
+    #[structopt(long)]
+    target_framework: Option<String>,
+
+    /// The name of the C# class containing the exported functions. Defaults to the
+    /// name of the input file.
+    #[structopt(long)]
+    class_name: Option<String>,
+
+    /// The name of the native library that the bindings load. Defaults to the name of
+    /// the input file.
+    #[structopt(long)]
+    dll_name: Option<String>,
+
+    /// The C# namespace that all exported types and functions are declared in, e.g.
+    /// `Mahjong.Native`.
+    ///
+    /// With `--namespaces`, the namespace for each type's module is nested inside the
+    /// root namespace.
+    #[structopt(long)]
+    root_namespace: Option<String>,
+
+    /// Generate runtime checks that handles for types that aren't `Sync` are only used
+    /// from the thread that created them.
+    ///
+    /// Violations throw an `InvalidOperationException` instead of racing on the Rust
+    /// value. This adds a small cost to every method call, so it's disabled by default.
+    #[structopt(long)]
+    thread_checks: bool,
+
+    /// Generate tracing code in each wrapper function that reports the name of the
+    /// call, a summary of its arguments, and the time spent in the call to the handler
+    /// registered with `RustTrace.SetHandler`.
+    ///
+    /// The tracing code is only compiled if the `CS_BINDGEN_TRACE` symbol is defined,
+    /// e.g. in debug builds. Enable the `trace` feature of `cs-bindgen` to log the
+    /// matching calls on the Rust side.
+    #[structopt(long)]
+    trace: bool,
+
+    /// Place each generated type in a C# namespace based on its Rust module path, e.g.
+    /// a type declared in `my_crate::tile::suit` is placed in the `Tile.Suit`
+    /// namespace.
+    ///
+    /// Without this, all types are declared in the global namespace, which means that
+    /// exported types with the same name in different modules can't be exported.
+    #[structopt(long)]
+    namespaces: bool,
+
+    /// Annotate the generated code for nullable reference types, e.g. optional strings
+    /// are exposed as `string?`.
+    ///
+    /// Nullable reference types require C# 8 or later, so this is disabled by default.
+    #[structopt(long)]
+    nullable: bool,
+
+    /// Generate data-carrying enums as an abstract base class with a sealed class for
+    /// each variant, instead of an interface implemented by a struct for each variant.
+    ///
+    /// This prevents other types from implementing the enum's interface, and supports
+    /// deconstructing variants into their fields.
+    #[structopt(long)]
+    enum_classes: bool,
+
+    /// Generate an xUnit test file at the specified path that round trips each value
+    /// type through its raw representation, and invokes each function that takes no
+    /// arguments.
+    #[structopt(long, parse(from_os_str))]
+    tests: Option<PathBuf>,
+
+    /// The name of the assembly containing the generated tests, if it's not the same
+    /// assembly as the bindings.
+    ///
+    /// The tests use internal conversion functions, so the bindings declare the test
+    /// assembly as a friend assembly.
+    #[structopt(long)]
+    test_assembly: Option<String>,
+
+    /// Generate marshaling benchmarks in the specified directory: a criterion benchmark
+    /// that calls the Rust bindings directly, and a BenchmarkDotNet project that makes
+    /// the same calls through the C# bindings.
+    ///
+    /// The benchmarks cover the functions and types that can be called with sample
+    /// values, i.e. functions taking strings, primitives, and vecs of primitives, and
+    /// types that implement `Default`.
+    #[structopt(long, parse(from_os_str))]
+    bench: Option<PathBuf>,
+
+    /// Settings for individual types, which can only be specified in the config file.
+    #[structopt(skip)]
+    type_configs: BTreeMap<String, TypeConfig>,
+}
+
+impl Opt {
+    /// Returns the first input file, which the default names are based on.
+    fn input(&self) -> &Path {
+        // NOTE: The input is only optional when a subcommand is used.
+        self.input.first().expect("No input file specified")
+    }
+
+    fn inputs(&self) -> &[PathBuf] {
+        &self.input
+    }
+
+    fn target(&self) -> Target {
+        self.target.unwrap_or(Target::CSharp)
+    }
+
+    fn target_framework(&self) -> &str {
+        self.target_framework.as_deref().unwrap_or("netstandard2.0")
+    }
+
+    fn package_version(&self) -> &str {
+        self.package_version.as_deref().unwrap_or("0.1.0")
+    }
+}
+
+/// The language that bindings are generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    CSharp,
+    TypeScript,
+    Python,
+    C,
+}
+
+impl FromStr for Target {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cs" | "csharp" => Ok(Target::CSharp),
+            "ts" | "typescript" => Ok(Target::TypeScript),
+            "python" | "py" => Ok(Target::Python),
+            "c" => Ok(Target::C),
+            _ => Err(failure::format_err!(
+                "Unknown target {:?}, expected `cs`, `ts`, `python`, or `c`",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Print the exported declarations as a JSON document, without generating any C#
+    /// code.
+    ///
+    /// The document can be used by other tools to build on the same declarations that
+    /// the C# bindings are generated from.
+    Dump {
+        /// The Wasm module or native library to load the exported declarations from.
+        #[structopt(parse(from_os_str))]
+        input: PathBuf,
+
+        /// The file to write the document to. Prints to stdout if not specified.
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare the exported declarations of two builds and report added, removed, and
+    /// changed items.
+    ///
+    /// Either build can be a Wasm module, a native library, or a JSON document written
+    /// by `dump`, e.g. a baseline checked into the repository. Exits with an error if
+    /// any items were removed or changed, since those are breaking changes.
+    Check {
+        /// The previous build, or a baseline JSON document.
+        #[structopt(parse(from_os_str))]
+        old: PathBuf,
+
+        /// The new build.
+        #[structopt(parse(from_os_str))]
+        new: PathBuf,
+    },
+
+    /// Build the native library for each target platform, collect the libraries in the
+    /// output directory, and generate the bindings for them.
+    ///
+    /// The libraries are copied into `runtimes/<rid>/native`, the layout used by NuGet
+    /// packages, or into the `Plugins` folder for each platform with `--unity`. The
+    /// bindings load the library by the crate's library name, which resolves to the
+    /// right file on every platform. Options for generating the bindings are specified
+    /// before the subcommand, e.g. `cs-bindgen --out-dir Bindings --pack build --rid
+    /// win-x64 --rid linux-x64`.
+    Build(build::BuildOpt),
 }
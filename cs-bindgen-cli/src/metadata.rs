@@ -0,0 +1,68 @@
+//! The JSON document describing the exported declarations, as emitted by the `dump`
+//! subcommand.
+//!
+//! The document wraps the declarations in an object with a format version, so that
+//! tools consuming the document can detect incompatible changes to its structure.
+//! The declarations themselves are serialized as-is.
+
+use crate::load_decl::load_declarations;
+use cs_bindgen_shared::Export;
+use failure::Error;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The current version of the document format.
+///
+/// This must be incremented whenever a change to the declaration types would change
+/// the serialized document in a way that breaks existing consumers.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Metadata {
+    pub format_version: u32,
+    pub exports: Vec<Export>,
+}
+
+impl Metadata {
+    pub fn new(exports: Vec<Export>) -> Self {
+        Metadata {
+            format_version: FORMAT_VERSION,
+            exports,
+        }
+    }
+
+    /// Parses a document previously written by the `dump` subcommand.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let metadata = serde_json::from_str::<Metadata>(json)?;
+        if metadata.format_version != FORMAT_VERSION {
+            return Err(failure::format_err!(
+                "Unsupported metadata format version {}, expected version {}",
+                metadata.format_version,
+                FORMAT_VERSION,
+            ));
+        }
+
+        Ok(metadata)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize metadata")
+    }
+}
+
+/// Loads the declarations from `input` and writes them to `output` as JSON, or to
+/// stdout if no output file is specified.
+pub fn dump(input: &Path, output: Option<&Path>) -> Result<(), Error> {
+    let json = Metadata::new(load_declarations(input)?).to_json();
+    match output {
+        None => println!("{}", json),
+        Some(output) => {
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(output, json)?;
+        }
+    }
+
+    Ok(())
+}
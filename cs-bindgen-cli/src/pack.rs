@@ -0,0 +1,167 @@
+//! Generation of a `.csproj` for packaging the generated bindings as a NuGet package.
+//!
+//! The project includes the generated source files along with the native library for
+//! each runtime, which NuGet places under `runtimes/<rid>/native` so that the .NET
+//! runtime loads the right library for the current platform.
+
+use crate::Opt;
+use cs_bindgen_shared::{BindingStyle, Export, MarshalFormat};
+use failure::Error;
+use heck::*;
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// A native library to include in the package, specified as `<rid>=<path>`.
+#[derive(Debug, Clone)]
+pub struct NativeLib {
+    /// The runtime identifier of the platform the library was built for, e.g. `win-x64`.
+    pub rid: String,
+    pub path: PathBuf,
+}
+
+impl FromStr for NativeLib {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(rid), Some(path)) if !rid.is_empty() && !path.is_empty() => Ok(NativeLib {
+                rid: rid.into(),
+                path: path.into(),
+            }),
+
+            _ => Err(failure::format_err!(
+                "Invalid native library `{}`, expected `<rid>=<path>`, e.g. \
+                 `win-x64=target/release/my_crate.dll`",
+                s,
+            )),
+        }
+    }
+}
+
+/// Returns the name of the generated project file, which is also used as the default
+/// package ID.
+///
+/// Uses the name of the class containing the exported functions, which defaults to the
+/// name of the input file.
+pub fn project_name(opt: &Opt) -> String {
+    if let Some(class_name) = &opt.class_name {
+        return class_name.clone();
+    }
+
+    opt.input()
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .expect("Unable to get name of input file")
+        .to_camel_case()
+}
+
+/// Generates the contents of the `.csproj` for the bindings.
+pub fn generate_project(exports: &[Export], opt: &Opt) -> String {
+    let package_id = opt.package_id.clone().unwrap_or_else(|| project_name(opt));
+
+    // Nullable annotations require C# 8, otherwise we stick to the oldest version that
+    // the generated code supports so that the package works with older toolchains.
+    let lang_version = if opt.nullable { "8.0" } else { "7.3" };
+
+    let package_refs = package_refs(exports)
+        .iter()
+        .map(|(name, version)| {
+            format!(
+                "    <PackageReference Include=\"{}\" Version=\"{}\" />\n",
+                name, version
+            )
+        })
+        .collect::<String>();
+
+    let native_libs = opt
+        .native_lib
+        .iter()
+        .map(|lib| {
+            let path = lib.path.canonicalize().unwrap_or_else(|_| lib.path.clone());
+            format!(
+                "    <None Include=\"{}\" Pack=\"true\" PackagePath=\"runtimes/{}/native\" />\n",
+                escape_xml(&path.to_string_lossy()),
+                escape_xml(&lib.rid),
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>{target_framework}</TargetFramework>
+    <AllowUnsafeBlocks>true</AllowUnsafeBlocks>
+    <LangVersion>{lang_version}</LangVersion>
+    <PackageId>{package_id}</PackageId>
+    <Version>{version}</Version>
+  </PropertyGroup>
+
+  <ItemGroup>
+{package_refs}  </ItemGroup>
+
+  <ItemGroup>
+{native_libs}  </ItemGroup>
+
+</Project>
+"#,
+        target_framework = escape_xml(opt.target_framework()),
+        lang_version = lang_version,
+        package_id = escape_xml(&package_id),
+        version = escape_xml(opt.package_version()),
+        package_refs = package_refs,
+        native_libs = native_libs,
+    )
+}
+
+/// Returns the name and version of each package that the bindings depend on.
+///
+/// Only references the serialization libraries if there are types that use them.
+pub fn package_refs(exports: &[Export]) -> Vec<(&'static str, &'static str)> {
+    let mut package_refs = vec![("System.Memory", "4.5.4")];
+    let uses_format = |expected| {
+        exports.iter().any(|export| match export {
+            Export::Named(export) => match &export.binding_style {
+                BindingStyle::Marshal(format, _) => *format == expected,
+                _ => false,
+            },
+            _ => false,
+        })
+    };
+    if uses_format(MarshalFormat::Json) {
+        package_refs.push(("System.Text.Json", "4.7.2"));
+    }
+    if uses_format(MarshalFormat::MessagePack) {
+        package_refs.push(("MessagePack", "2.1.152"));
+    }
+
+    package_refs
+}
+
+/// Runs `dotnet pack` on the generated project.
+pub fn dotnet_pack(project_path: &Path) -> Result<(), Error> {
+    let status = Command::new("dotnet")
+        .arg("pack")
+        .arg(project_path)
+        .arg("--configuration=Release")
+        .status()
+        .map_err(|err| failure::format_err!("Failed to run `dotnet pack`: {}", err))?;
+
+    if !status.success() {
+        return Err(failure::format_err!("`dotnet pack` exited with {}", status));
+    }
+
+    Ok(())
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
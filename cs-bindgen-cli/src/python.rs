@@ -0,0 +1,691 @@
+//! Python backend, selected with `--target python`.
+//!
+//! Generates a Python package that loads the native library with `ctypes` and wraps
+//! the raw binding functions, following the same marshaling conventions as the C#
+//! bindings:
+//!
+//! * Handle types are wrapped in a class that owns the handle and drops it when the
+//!   object is closed or garbage collected.
+//! * Structs marshaled by value are exposed as dataclasses, and are converted to and
+//!   from a `ctypes.Structure` with the same layout as the raw struct.
+//! * C-like enums are exposed as an `IntEnum`.
+//! * Strings are converted to and from UTF-8, and panics are raised as `RustPanic`.
+//!
+//! Other types (collections, data-carrying enums, serialized types, callbacks) aren't
+//! supported yet. Functions that use them are still generated, but raise
+//! `NotImplementedError` when called.
+
+use crate::{
+    generate::{dll_name, enum_values, primitive_repr, repr_for_schema, GeneratedFile},
+    Opt,
+};
+use cs_bindgen_shared::{
+    schematic::{Schema, StructLike},
+    BindingStyle, ConstValue, Export, FnArg, NamedType, ReceiverStyle, Repr, TypeName,
+};
+use heck::*;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+/// Generates the `__init__.py` for the Python package.
+pub fn generate(exports: &[Export], opt: &Opt) -> Vec<GeneratedFile> {
+    let dll_name = dll_name(opt);
+    let types = exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::Named(export) => Some((&export.type_name, export)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let generator = Generator { types };
+
+    let mut out = PRELUDE.replace("{dll_name}", dll_name);
+
+    // NOTE: The raw structs have to be declared before any struct that contains them,
+    // since ctypes requires the type of each field to be complete.
+    let mut declared = HashSet::new();
+    for export in exports {
+        if let Export::Named(export) = export {
+            generator.declare_value_type(export, &mut declared, &mut out);
+        }
+    }
+
+    for export in exports {
+        match export {
+            Export::Fn(export) => {
+                let body = if export.is_async {
+                    None
+                } else {
+                    generator.quote_call(
+                        &export.binding,
+                        None,
+                        &export.inputs,
+                        export.output.as_ref(),
+                        &mut out,
+                    )
+                };
+
+                write_fn(
+                    &mut out,
+                    "",
+                    &export.name,
+                    None,
+                    &generator.quote_params(&export.inputs),
+                    &generator.py_return_type(export.output.as_ref()),
+                    export.docs.as_deref(),
+                    body,
+                );
+            }
+
+            Export::Named(export) if matches!(export.binding_style, BindingStyle::Handle) => {
+                generator.declare_class(export, exports, &mut out);
+            }
+
+            Export::Const(export) if export.self_type.is_none() => {
+                if let Some(value) = &export.value {
+                    let value = match value {
+                        ConstValue::Bool(true) => "True".into(),
+                        ConstValue::Bool(false) => "False".into(),
+                        ConstValue::Int(value) => value.to_string(),
+                        ConstValue::UInt(value) => value.to_string(),
+                        ConstValue::Float(value) => value.to_string(),
+                        ConstValue::String(value) => format!("{:?}", value),
+                    };
+                    writeln!(out, "{} = {}\n", export.name.to_shouty_snake_case(), value).unwrap();
+                }
+            }
+
+            // Value types were declared above, and trait objects, trait impls, and
+            // associated constants aren't supported yet.
+            _ => {}
+        }
+    }
+
+    vec![GeneratedFile {
+        name: format!("{}/__init__.py", dll_name.to_snake_case()),
+        contents: out,
+    }]
+}
+
+const PRELUDE: &str = r#"# Generated by cs-bindgen. Do not edit.
+
+import ctypes
+import enum
+import os
+import sys
+from dataclasses import dataclass
+
+
+def _load_library():
+    if sys.platform == "win32":
+        file_name = "{dll_name}.dll"
+    elif sys.platform == "darwin":
+        file_name = "lib{dll_name}.dylib"
+    else:
+        file_name = "lib{dll_name}.so"
+
+    # Prefer a copy of the library in the package directory, and otherwise fall back
+    # to the platform's library search path.
+    path = os.path.join(os.path.dirname(os.path.abspath(__file__)), file_name)
+    return ctypes.CDLL(path if os.path.exists(path) else file_name)
+
+
+_lib = _load_library()
+
+
+class RustPanic(Exception):
+    """Raised when the Rust code panics."""
+
+
+class _RawVec(ctypes.Structure):
+    _fields_ = [
+        ("ptr", ctypes.c_void_p),
+        ("len", ctypes.c_size_t),
+        ("capacity", ctypes.c_size_t),
+    ]
+
+
+class _RawSlice(ctypes.Structure):
+    _fields_ = [("ptr", ctypes.c_void_p), ("len", ctypes.c_size_t)]
+
+
+_drop_vec_u8 = _lib["__cs_bindgen_drop_vec_u8"]
+_drop_vec_u8.argtypes = [_RawVec]
+_drop_vec_u8.restype = None
+
+_convert_vec_u8 = _lib["__cs_bindgen_convert_vec_u8"]
+_convert_vec_u8.argtypes = [_RawSlice]
+_convert_vec_u8.restype = _RawVec
+
+
+def _call(binding, *args):
+    panic = _RawVec()
+    result = binding(*args, ctypes.byref(panic))
+    if panic.ptr:
+        message = ctypes.string_at(panic.ptr, panic.len).decode("utf-8")
+        _drop_vec_u8(panic)
+        raise RustPanic(message)
+    return result
+
+
+def _str_into_raw(value):
+    data = value.encode("utf-8")
+    buffer = ctypes.create_string_buffer(data, len(data))
+    raw = _RawSlice(ctypes.cast(buffer, ctypes.c_void_p), len(data))
+
+    # Keep the buffer alive for as long as the slice is in use.
+    raw._buffer = buffer
+    return raw
+
+
+def _string_into_raw(value):
+    # Rust takes ownership of the string, so it has to be copied into a buffer
+    # allocated by Rust.
+    return _convert_vec_u8(_str_into_raw(value))
+
+
+def _string_from_raw(raw):
+    try:
+        return ctypes.string_at(raw.ptr, raw.len).decode("utf-8")
+    finally:
+        _drop_vec_u8(raw)
+
+
+"#;
+
+struct Generator<'a> {
+    types: HashMap<&'a TypeName, &'a NamedType>,
+}
+
+impl<'a> Generator<'a> {
+    /// Declares the Python type for a value type, along with the raw struct and
+    /// conversion functions if the type is a struct. Any value types used by the
+    /// type's fields are declared first.
+    fn declare_value_type(
+        &self,
+        export: &NamedType,
+        declared: &mut HashSet<TypeName>,
+        out: &mut String,
+    ) {
+        if !declared.insert(export.type_name.clone()) {
+            return;
+        }
+
+        let ident = &*export.type_name.name;
+        let schema = match &export.binding_style {
+            BindingStyle::Value(schema) if !export.is_flags => schema,
+            _ => return,
+        };
+
+        if let Schema::Enum(schema) = schema {
+            if !schema.has_data() {
+                writeln!(out, "class {}(enum.IntEnum):", ident).unwrap();
+                write_docs(out, "    ", export.docs.as_deref());
+                for (name, value) in enum_values(schema) {
+                    writeln!(out, "    {} = {}", name.to_shouty_snake_case(), value).unwrap();
+                }
+                writeln!(out, "\n").unwrap();
+            }
+
+            return;
+        }
+
+        let schema = match schema.as_struct_like() {
+            Some(schema) if self.is_struct_supported(&schema) => schema,
+            _ => return,
+        };
+
+        for field in &schema.fields {
+            if let Some(Repr::Named(type_name)) = repr_for_schema(&field.schema) {
+                if let Some(field_export) = self.types.get(&type_name) {
+                    self.declare_value_type(field_export, declared, out);
+                }
+            }
+        }
+
+        let fields = schema
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let name = field_name(field.name, index);
+                let repr = repr_for_schema(&field.schema).unwrap();
+                (name, repr)
+            })
+            .collect::<Vec<_>>();
+
+        let raw_ident = raw_ident(&export.type_name);
+
+        writeln!(out, "@dataclass\nclass {}:", ident).unwrap();
+        write_docs(out, "    ", export.docs.as_deref());
+        for (name, repr) in &fields {
+            writeln!(out, "    {}: {}", name, self.py_type(repr)).unwrap();
+        }
+        if fields.is_empty() {
+            writeln!(out, "    pass").unwrap();
+        }
+
+        writeln!(out, "\n\nclass {}(ctypes.Structure):", raw_ident).unwrap();
+        writeln!(out, "    _fields_ = [").unwrap();
+        for (name, repr) in &fields {
+            writeln!(
+                out,
+                "        ({:?}, {}),",
+                name,
+                self.raw_type(repr).unwrap()
+            )
+            .unwrap();
+        }
+        writeln!(out, "    ]\n\n").unwrap();
+
+        let into_raw = fields
+            .iter()
+            .map(|(name, repr)| self.into_raw(repr, &format!("value.{}", name)).unwrap())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            out,
+            "def {}_into_raw(value):\n    return {}({})\n\n",
+            raw_ident, raw_ident, into_raw
+        )
+        .unwrap();
+
+        let from_raw = fields
+            .iter()
+            .map(|(name, repr)| self.from_raw(repr, &format!("raw.{}", name)).unwrap())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            out,
+            "def {}_from_raw(raw):\n    return {}({})\n\n",
+            raw_ident, ident, from_raw
+        )
+        .unwrap();
+    }
+
+    /// Declares the wrapper class for a handle type, including its methods.
+    fn declare_class(&self, export: &NamedType, exports: &[Export], out: &mut String) {
+        let ident = &*export.type_name.name;
+        let drop_fn = export
+            .drop_fn
+            .as_ref()
+            .expect("Handle type must have a drop function");
+        let drop_ident = binding_ident(drop_fn);
+        writeln!(
+            out,
+            "{} = _lib[{:?}]\n{}.argtypes = [ctypes.c_void_p]\n{}.restype = None\n\n",
+            drop_ident, drop_fn, drop_ident, drop_ident
+        )
+        .unwrap();
+
+        let mut body = String::new();
+        for method in exports.iter().filter_map(|item| match item {
+            Export::Method(method) if method.self_type == export.type_name => Some(method),
+            _ => None,
+        }) {
+            let receiver = match method.receiver {
+                Some(ReceiverStyle::Ref) | Some(ReceiverStyle::RefMut) => Some("self._ptr"),
+
+                // NOTE: Passing the handle by value transfers ownership to Rust, so the
+                // handle is taken to prevent it from being dropped a second time.
+                Some(ReceiverStyle::Move) => Some("self._take()"),
+                None => None,
+            };
+
+            let call = self.quote_call(
+                &method.binding,
+                receiver,
+                &method.inputs,
+                method.output.as_ref(),
+                out,
+            );
+
+            write_fn(
+                &mut body,
+                "    ",
+                &method.name,
+                method.receiver.map(|_| "self"),
+                &self.quote_params(&method.inputs),
+                &self.py_return_type(method.output.as_ref()),
+                method.docs.as_deref(),
+                call,
+            );
+        }
+
+        writeln!(out, "class {}:", ident).unwrap();
+        write_docs(out, "    ", export.docs.as_deref());
+        writeln!(
+            out,
+            r#"    def __init__(self, ptr):
+        self._ptr = ptr
+
+    def __del__(self):
+        self.close()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *args):
+        self.close()
+
+    def close(self):
+        """Drops the underlying Rust value."""
+        if self._ptr is not None:
+            {}(self._ptr)
+            self._ptr = None
+
+    def _take(self):
+        if self._ptr is None:
+            raise ValueError("{} has already been closed")
+        ptr, self._ptr = self._ptr, None
+        return ptr
+"#,
+            drop_ident, ident
+        )
+        .unwrap();
+        writeln!(out, "{}", body).unwrap();
+    }
+
+    /// Declares the argument and return types of the raw binding, and returns the
+    /// expression that invokes it. Returns `None` if any of the types aren't
+    /// supported.
+    ///
+    /// The argument types are written to `out` before the function that uses them.
+    fn quote_call(
+        &self,
+        binding: &str,
+        receiver: Option<&str>,
+        inputs: &[FnArg],
+        output: Option<&Repr>,
+        out: &mut String,
+    ) -> Option<String> {
+        let mut arg_types = Vec::new();
+        let mut args = Vec::new();
+        if let Some(receiver) = receiver {
+            arg_types.push("ctypes.c_void_p".to_string());
+            args.push(receiver.to_string());
+        }
+
+        for arg in inputs {
+            arg_types.push(self.raw_type(&arg.repr)?);
+            args.push(self.into_raw(&arg.repr, &arg.name)?);
+        }
+        arg_types.push("ctypes.POINTER(_RawVec)".into());
+
+        // NOTE: Borrowed strings can't be returned to Python, since the string would
+        // be freed before Python could copy it.
+        let (restype, result) = match output {
+            None => ("None".to_string(), None),
+            Some(Repr::Str) => return None,
+            Some(output) => (
+                self.raw_type(output)?,
+                Some(self.from_raw(output, "result")?),
+            ),
+        };
+
+        let ident = binding_ident(binding);
+        writeln!(
+            out,
+            "{} = _lib[{:?}]\n{}.argtypes = [{}]\n{}.restype = {}\n",
+            ident,
+            binding,
+            ident,
+            arg_types.join(", "),
+            ident,
+            restype
+        )
+        .unwrap();
+
+        args.insert(0, ident);
+        let call = format!("_call({})", args.join(", "));
+
+        Some(match result {
+            Some(result) => format!("result = {}\n    return {}", call, result),
+            None => call,
+        })
+    }
+
+    fn quote_params(&self, inputs: &[FnArg]) -> Vec<String> {
+        inputs
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name, self.py_type(&arg.repr)))
+            .collect()
+    }
+
+    fn py_return_type(&self, output: Option<&Repr>) -> String {
+        output.map_or("None".into(), |output| self.py_type(output))
+    }
+
+    /// Returns the type annotation for a Python value of the specified type.
+    fn py_type(&self, repr: &Repr) -> String {
+        match self.resolve(repr) {
+            Repr::Unit => "None".into(),
+            Repr::Bool => "bool".into(),
+            Repr::Char | Repr::String | Repr::Str => "str".into(),
+            Repr::F32 | Repr::F64 => "float".into(),
+            repr if is_integer(repr) => "int".into(),
+            Repr::Named(type_name) => format!("\"{}\"", type_name.name),
+            _ => "object".into(),
+        }
+    }
+
+    /// Returns the ctypes type for the raw representation of the specified type, or
+    /// `None` if the type isn't supported.
+    fn raw_type(&self, repr: &Repr) -> Option<String> {
+        let ty = match self.resolve(repr) {
+            Repr::Unit | Repr::Bool | Repr::U8 => "ctypes.c_uint8",
+            Repr::Char | Repr::U32 => "ctypes.c_uint32",
+            Repr::I8 => "ctypes.c_int8",
+            Repr::I16 => "ctypes.c_int16",
+            Repr::I32 => "ctypes.c_int32",
+            Repr::I64 => "ctypes.c_int64",
+            Repr::ISize => "ctypes.c_ssize_t",
+            Repr::U16 => "ctypes.c_uint16",
+            Repr::U64 => "ctypes.c_uint64",
+            Repr::USize => "ctypes.c_size_t",
+            Repr::F32 => "ctypes.c_float",
+            Repr::F64 => "ctypes.c_double",
+            Repr::String => "_RawVec",
+            Repr::Str => "_RawSlice",
+
+            Repr::Named(type_name) => {
+                let export = self.types.get(type_name)?;
+                return match &export.binding_style {
+                    BindingStyle::Handle => Some("ctypes.c_void_p".into()),
+                    BindingStyle::Value(Schema::Enum(schema)) if !schema.has_data() => {
+                        let discriminant = export
+                            .discriminant
+                            .clone()
+                            .or_else(|| schema.repr.map(primitive_repr))
+                            .unwrap_or(Repr::ISize);
+                        self.raw_type(&discriminant)
+                    }
+                    BindingStyle::Value(schema) if !export.is_flags => {
+                        if self.is_struct_supported(&schema.as_struct_like()?) {
+                            Some(raw_ident(type_name))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                };
+            }
+
+            _ => return None,
+        };
+
+        Some(ty.into())
+    }
+
+    /// Returns the expression converting the Python value `expr` to its raw
+    /// representation.
+    fn into_raw(&self, repr: &Repr, expr: &str) -> Option<String> {
+        Some(match self.resolve(repr) {
+            Repr::Bool => format!("1 if {} else 0", expr),
+            Repr::Char => format!("ord({})", expr),
+            Repr::String => format!("_string_into_raw({})", expr),
+            Repr::Str => format!("_str_into_raw({})", expr),
+            Repr::F32 | Repr::F64 => expr.into(),
+            repr if is_integer(repr) => expr.into(),
+
+            Repr::Named(type_name) => match &self.types.get(type_name)?.binding_style {
+                BindingStyle::Handle => format!("{}._ptr", expr),
+                BindingStyle::Value(Schema::Enum(_)) => format!("int({})", expr),
+                _ => format!("{}_into_raw({})", raw_ident(type_name), expr),
+            },
+
+            _ => return None,
+        })
+    }
+
+    /// Returns the expression converting the raw value `expr` to its Python
+    /// representation.
+    fn from_raw(&self, repr: &Repr, expr: &str) -> Option<String> {
+        Some(match self.resolve(repr) {
+            Repr::Unit => "None".into(),
+            Repr::Bool => format!("{} != 0", expr),
+            Repr::Char => format!("chr({})", expr),
+            Repr::String => format!("_string_from_raw({})", expr),
+            Repr::F32 | Repr::F64 => expr.into(),
+            repr if is_integer(repr) => expr.into(),
+
+            Repr::Named(type_name) => match &self.types.get(type_name)?.binding_style {
+                BindingStyle::Handle | BindingStyle::Value(Schema::Enum(_)) => {
+                    format!("{}({})", type_name.name, expr)
+                }
+                _ => format!("{}_from_raw({})", raw_ident(type_name), expr),
+            },
+
+            _ => return None,
+        })
+    }
+
+    /// Resolves transparent types to their inner type, since they're marshaled as
+    /// the inner type.
+    fn resolve<'r>(&'r self, repr: &'r Repr) -> &'r Repr {
+        match repr {
+            Repr::Named(type_name) => match self.types.get(type_name) {
+                Some(NamedType {
+                    binding_style: BindingStyle::Transparent(transparent),
+                    ..
+                }) => self.resolve(&transparent.inner),
+                _ => repr,
+            },
+            _ => repr,
+        }
+    }
+
+    /// Returns `true` if every field of the struct has a supported raw type.
+    ///
+    /// Borrowed strings aren't supported in struct fields, since there's no way to
+    /// free them once the struct is returned to Python.
+    fn is_struct_supported(&self, schema: &StructLike<'_>) -> bool {
+        schema
+            .fields
+            .iter()
+            .all(|field| match repr_for_schema(&field.schema) {
+                Some(Repr::Str) | None => false,
+                Some(repr) => self.raw_type(&repr).is_some(),
+            })
+    }
+}
+
+/// Writes a Python function, or a function that raises `NotImplementedError` if the
+/// body is `None`.
+#[allow(clippy::too_many_arguments)]
+fn write_fn(
+    out: &mut String,
+    indent: &str,
+    name: &str,
+    receiver: Option<&str>,
+    params: &[String],
+    return_type: &str,
+    docs: Option<&str>,
+    body: Option<String>,
+) {
+    let mut all_params = receiver.into_iter().map(String::from).collect::<Vec<_>>();
+    all_params.extend(params.iter().cloned());
+
+    if receiver.is_none() && !indent.is_empty() {
+        writeln!(out, "{}@staticmethod", indent).unwrap();
+    }
+    writeln!(
+        out,
+        "{}def {}({}) -> {}:",
+        indent,
+        name,
+        all_params.join(", "),
+        return_type
+    )
+    .unwrap();
+    write_docs(out, &format!("{}    ", indent), docs);
+
+    let body = body.unwrap_or_else(|| {
+        format!(
+            "raise NotImplementedError(\"{} isn't supported by the Python bindings\")",
+            name
+        )
+    });
+    for line in body.lines() {
+        writeln!(out, "{}    {}", indent, line.trim_start()).unwrap();
+    }
+    writeln!(out, "\n").unwrap();
+}
+
+fn write_docs(out: &mut String, indent: &str, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        writeln!(out, "{}\"\"\"", indent).unwrap();
+        for line in docs.lines() {
+            writeln!(
+                out,
+                "{}{}",
+                indent,
+                line.trim().replace("\"\"\"", "\\\"\\\"\\\"")
+            )
+            .unwrap();
+        }
+        writeln!(out, "{}\"\"\"", indent).unwrap();
+    }
+}
+
+fn is_integer(repr: &Repr) -> bool {
+    matches!(
+        repr,
+        Repr::I8
+            | Repr::I16
+            | Repr::I32
+            | Repr::I64
+            | Repr::ISize
+            | Repr::U8
+            | Repr::U16
+            | Repr::U32
+            | Repr::U64
+            | Repr::USize
+    )
+}
+
+/// Returns the name of the module-level variable holding the raw binding.
+///
+/// The names of the binding functions start with `__`, which Python would mangle if
+/// they were referenced from within a class.
+fn binding_ident(binding: &str) -> String {
+    format!("_{}", binding.trim_start_matches('_'))
+}
+
+/// Returns the name of the raw struct for a type, which is unique across modules.
+fn raw_ident(type_name: &TypeName) -> String {
+    format!(
+        "_{}__{}__Raw",
+        type_name.module.replace("::", "__"),
+        type_name.name
+    )
+}
+
+fn field_name(name: Option<&str>, index: usize) -> String {
+    match name {
+        Some(name) => name.into(),
+        None => format!("field{}", index),
+    }
+}
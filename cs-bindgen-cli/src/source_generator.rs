@@ -0,0 +1,204 @@
+//! Generation of a Roslyn source generator that adds the bindings to a C# project at
+//! compile time, instead of the bindings being written out as source files.
+//!
+//! The generated bindings are embedded in the source generator, along with a hash of
+//! the export metadata that they were generated from. The project using the bindings
+//! provides the metadata for the native library that it references as an additional
+//! file, e.g. by running `cs-bindgen dump` as part of its build. The source generator
+//! only adds the bindings if the metadata matches, and otherwise reports an error, so
+//! the bindings can't get out of sync with the native library.
+//!
+//! The output directory contains:
+//!
+//! * `<Name>.Generator.csproj` and `BindingsGenerator.cs`, the source generator.
+//! * `<input>.metadata.json`, the metadata that the bindings were generated from.
+//! * `<Name>.Generator.props`, which the project using the bindings imports to
+//!   reference the source generator and the metadata file.
+
+use crate::{generate::GeneratedFile, pack, Opt};
+use std::{ffi::OsStr, fmt::Write};
+
+/// Generates the source generator project for the generated bindings.
+///
+/// `metadata` is the JSON document describing the exports, as written by the `dump`
+/// subcommand.
+pub fn generate_project(
+    bindings: &[GeneratedFile],
+    metadata: &str,
+    opt: &Opt,
+) -> Vec<GeneratedFile> {
+    let name = pack::project_name(opt);
+    let generator_name = format!("{}.Generator", name);
+    let metadata_file_name = format!(
+        "{}.metadata.json",
+        opt.input()
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .expect("Unable to get name of input file")
+    );
+
+    let mut sources = String::new();
+    for file in bindings {
+        writeln!(
+            sources,
+            "        context.AddSource({:?}, SourceText.From({}, Encoding.UTF8));",
+            file.name,
+            verbatim_string(&file.contents),
+        )
+        .unwrap();
+    }
+
+    let generator = format!(
+        r#"// Generated by cs-bindgen. Do not edit.
+
+using System.IO;
+using System.Text;
+using Microsoft.CodeAnalysis;
+using Microsoft.CodeAnalysis.Text;
+
+[Generator]
+public class BindingsGenerator : ISourceGenerator
+{{
+    private const string MetadataFileName = {metadata_file_name:?};
+    private const ulong MetadataHash = 0x{hash:016x}UL;
+
+    private static readonly DiagnosticDescriptor MissingMetadata = new DiagnosticDescriptor(
+        "CSB0001",
+        "Missing export metadata",
+        "No additional file named '{{0}}' was found. Add the metadata for the native library with `cs-bindgen dump`.",
+        "cs-bindgen",
+        DiagnosticSeverity.Error,
+        isEnabledByDefault: true);
+
+    private static readonly DiagnosticDescriptor StaleMetadata = new DiagnosticDescriptor(
+        "CSB0002",
+        "Bindings don't match the native library",
+        "The export metadata in '{{0}}' doesn't match the metadata that the bindings were generated from. Regenerate the bindings with cs-bindgen.",
+        "cs-bindgen",
+        DiagnosticSeverity.Error,
+        isEnabledByDefault: true);
+
+    public void Initialize(GeneratorInitializationContext context) {{ }}
+
+    public void Execute(GeneratorExecutionContext context)
+    {{
+        AdditionalText metadata = null;
+        foreach (var file in context.AdditionalFiles)
+        {{
+            if (Path.GetFileName(file.Path) == MetadataFileName)
+            {{
+                metadata = file;
+                break;
+            }}
+        }}
+
+        if (metadata == null)
+        {{
+            context.ReportDiagnostic(Diagnostic.Create(MissingMetadata, Location.None, MetadataFileName));
+            return;
+        }}
+
+        var text = metadata.GetText(context.CancellationToken)?.ToString() ?? "";
+        if (Hash(text) != MetadataHash)
+        {{
+            context.ReportDiagnostic(Diagnostic.Create(StaleMetadata, Location.None, metadata.Path));
+            return;
+        }}
+
+{sources}    }}
+
+    // FNV-1a hash of the UTF-8 text, ignoring carriage returns so that the hash doesn't
+    // depend on how line endings were checked out.
+    private static ulong Hash(string text)
+    {{
+        ulong hash = 14695981039346656037UL;
+        foreach (var b in Encoding.UTF8.GetBytes(text))
+        {{
+            if (b == (byte)'\r')
+            {{
+                continue;
+            }}
+
+            hash ^= b;
+            hash *= 1099511628211UL;
+        }}
+
+        return hash;
+    }}
+}}
+"#,
+        metadata_file_name = metadata_file_name,
+        hash = hash_metadata(metadata),
+        sources = sources,
+    );
+
+    let project = r#"<Project Sdk="Microsoft.NET.Sdk">
+
+  <PropertyGroup>
+    <TargetFramework>netstandard2.0</TargetFramework>
+    <IsRoslynComponent>true</IsRoslynComponent>
+    <EnforceExtendedAnalyzerRules>true</EnforceExtendedAnalyzerRules>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <PackageReference Include="Microsoft.CodeAnalysis.CSharp" Version="3.8.0" PrivateAssets="all" />
+  </ItemGroup>
+
+</Project>
+"#
+    .to_string();
+
+    let props = format!(
+        r#"<Project>
+
+  <PropertyGroup>
+    <AllowUnsafeBlocks>true</AllowUnsafeBlocks>
+  </PropertyGroup>
+
+  <ItemGroup>
+    <ProjectReference Include="$(MSBuildThisFileDirectory){generator_name}.csproj" OutputItemType="Analyzer" ReferenceOutputAssembly="false" />
+    <AdditionalFiles Include="$(MSBuildThisFileDirectory){metadata_file_name}" Condition="'@(AdditionalFiles->WithMetadataValue('Filename', '{metadata_stem}'))' == ''" />
+  </ItemGroup>
+
+</Project>
+"#,
+        generator_name = pack::escape_xml(&generator_name),
+        metadata_file_name = pack::escape_xml(&metadata_file_name),
+        metadata_stem = pack::escape_xml(metadata_file_name.trim_end_matches(".json")),
+    );
+
+    vec![
+        GeneratedFile {
+            name: format!("{}.csproj", generator_name),
+            contents: project,
+        },
+        GeneratedFile {
+            name: "BindingsGenerator.cs".into(),
+            contents: generator,
+        },
+        GeneratedFile {
+            name: format!("{}.props", generator_name),
+            contents: props,
+        },
+        GeneratedFile {
+            name: metadata_file_name,
+            contents: metadata.into(),
+        },
+    ]
+}
+
+/// Hashes the metadata document with 64-bit FNV-1a, matching the hash computed by the
+/// generated source generator.
+fn hash_metadata(metadata: &str) -> u64 {
+    metadata
+        .bytes()
+        .filter(|&byte| byte != b'\r')
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Quotes `text` as a C# verbatim string literal.
+fn verbatim_string(text: &str) -> String {
+    format!("@\"{}\"", text.replace('"', "\"\""))
+}
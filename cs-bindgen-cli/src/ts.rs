@@ -0,0 +1,566 @@
+//! TypeScript backend, selected with `--target ts`.
+//!
+//! Generates two files from the same export declarations as the C# bindings:
+//!
+//! * `<name>.d.ts` declares the exported functions and types. Structs are declared as
+//!   interfaces, C-like enums as enums, data-carrying enums as a union of objects
+//!   tagged with the name of the variant, and handle types as classes.
+//! * `<name>.js` loads the Wasm module and wraps the raw binding functions.
+//!
+//! The JS wrapper calls the raw bindings directly, so it only supports functions whose
+//! arguments and return value are passed to Wasm as plain numbers, i.e. primitives and
+//! handles. Other functions (including async functions and methods that take `self` by
+//! value) are omitted from both files, so that the declarations only describe
+//! functions that can actually be called. Types that are marshaled through linear
+//! memory (strings, collections, structs) are still declared with their full types, so
+//! that the declarations can be used with other wrappers, such as ones generated by
+//! wasm-bindgen.
+
+use crate::{
+    generate::{enum_values, repr_for_schema, GeneratedFile},
+    Opt,
+};
+use cs_bindgen_shared::{
+    schematic::{Field, Schema},
+    BindingStyle, ConstValue, Export, FnArg, Method, NamedType, ReceiverStyle, Repr, TypeName,
+};
+use heck::*;
+use std::{collections::HashMap, ffi::OsStr, fmt::Write};
+
+/// Generates the TypeScript declarations and JavaScript wrapper for the exports.
+pub fn generate(exports: &[Export], opt: &Opt) -> Vec<GeneratedFile> {
+    let name = opt
+        .input()
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .expect("Unable to get name of input file")
+        .to_string();
+
+    let types = exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::Named(export) => Some((&export.type_name, export)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut dts = String::new();
+    let mut js = String::from(JS_PRELUDE);
+    writeln!(dts, "{}", DTS_PRELUDE).unwrap();
+
+    for export in exports {
+        match export {
+            // NOTE: Async functions return a promise, which the wrapper can't create
+            // from the raw binding.
+            Export::Fn(export) if export.is_async => {}
+
+            Export::Fn(export) => {
+                let body = match quote_js_call(
+                    &export.binding,
+                    None,
+                    &export.inputs,
+                    export.output.as_ref(),
+                    &types,
+                ) {
+                    Some(body) => body,
+                    None => continue,
+                };
+
+                let ident = fn_ident(&export.name, export.rename.as_deref());
+                let output = export.output.as_ref().map_or("void".into(), ts_type);
+                write_docs(&mut dts, "", export.docs.as_deref());
+                writeln!(
+                    dts,
+                    "export function {}({}): {};",
+                    ident,
+                    ts_args(&export.inputs),
+                    output,
+                )
+                .unwrap();
+                write_js_fn(&mut js, "export function ", &ident, &export.inputs, &body);
+            }
+
+            Export::Named(export) => {
+                write_docs(&mut dts, "", export.docs.as_deref());
+                let declaration = declare_type(export, exports, &types, &mut js);
+                writeln!(dts, "{}", declaration).unwrap();
+            }
+
+            Export::Trait(export) => {
+                writeln!(dts, "export interface I{} {{", export.type_name.name).unwrap();
+                for method in &export.methods {
+                    let output = method.output.as_ref().map_or("void".into(), ts_type);
+                    writeln!(
+                        dts,
+                        "    {}({}): {};",
+                        method.name.to_mixed_case(),
+                        ts_args(&method.inputs),
+                        output,
+                    )
+                    .unwrap();
+                }
+                writeln!(dts, "}}").unwrap();
+            }
+
+            Export::Const(export) if export.self_type.is_none() => {
+                let ident = export.name.to_shouty_snake_case();
+                writeln!(dts, "export const {}: {};", ident, ts_type(&export.repr)).unwrap();
+
+                let value = match &export.value {
+                    Some(ConstValue::Bool(value)) => value.to_string(),
+                    Some(ConstValue::Int(value)) => value.to_string(),
+                    Some(ConstValue::UInt(value)) => value.to_string(),
+                    Some(ConstValue::Float(value)) => value.to_string(),
+                    Some(ConstValue::String(value)) => format!("{:?}", value),
+                    None => "undefined".into(),
+                };
+                writeln!(js, "export const {} = {};\n", ident, value).unwrap();
+            }
+
+            Export::Alias(export) => {
+                write_docs(&mut dts, "", export.docs.as_deref());
+                let ident = export.rename.as_deref().unwrap_or(&export.name);
+                writeln!(dts, "export type {} = {};", ident, ts_type(&export.repr)).unwrap();
+            }
+
+            // Methods are declared as part of the class for their self type, and trait
+            // impls, conversions, operators, and associated constants aren't supported yet.
+            Export::Method(_)
+            | Export::TraitImpl(_)
+            | Export::Conversion(_)
+            | Export::Operator(_)
+            | Export::Const(_) => {}
+
+            // Std trait impls are merged into their types when loading the exports.
+            Export::StdImpl(_) => {}
+
+            // The TypeScript bindings don't use the list helpers.
+            Export::List(_) => {}
+        }
+    }
+
+    vec![
+        GeneratedFile {
+            name: format!("{}.d.ts", name),
+            contents: dts,
+        },
+        GeneratedFile {
+            name: format!("{}.js", name),
+            contents: js,
+        },
+    ]
+}
+
+const DTS_PRELUDE: &str = "\
+/** Loads the Wasm module. Must be called before any other function. */
+export function init(input: RequestInfo | URL | Response | BufferSource | WebAssembly.Module): Promise<void>;
+
+/** The error thrown when Rust code panics. */
+export class RustPanicError extends Error {}
+";
+
+const JS_PRELUDE: &str = "\
+let wasm;
+const decoder = new TextDecoder();
+
+export async function init(input) {
+    if (typeof input === 'string' || input instanceof URL || input instanceof Request) {
+        input = fetch(input);
+    }
+    input = await input;
+    if (input instanceof Response) {
+        input = await input.arrayBuffer();
+    }
+
+    const result = await WebAssembly.instantiate(input, {});
+    wasm = (result.instance || result).exports;
+}
+
+export class RustPanicError extends Error {}
+
+// Invokes a raw binding function, throwing a `RustPanicError` if it panics.
+function call(binding, ...args) {
+    // The panic message is written to a `RawVec<u8>`, which is 3 pointers in size.
+    const panic = wasm.__cs_bindgen_alloc_buffer(12);
+    new Uint32Array(wasm.memory.buffer, panic, 3).fill(0);
+    try {
+        const result = binding(...args, panic);
+        const [ptr, len] = new Uint32Array(wasm.memory.buffer, panic, 2);
+        if (ptr !== 0) {
+            const message = decoder.decode(new Uint8Array(wasm.memory.buffer, ptr, len));
+            wasm.__cs_bindgen_drop_vec_u8(panic);
+            throw new RustPanicError(message);
+        }
+        return result;
+    } finally {
+        wasm.__cs_bindgen_free_buffer(panic, 12);
+    }
+}
+
+";
+
+/// Returns the declaration for an exported type, and writes any runtime values that
+/// are needed for the type to `js`.
+fn declare_type(
+    export: &NamedType,
+    exports: &[Export],
+    types: &HashMap<&TypeName, &NamedType>,
+    js: &mut String,
+) -> String {
+    let ident = &*export.type_name.name;
+    match &export.binding_style {
+        BindingStyle::Handle => declare_class(export, exports, types, js),
+
+        BindingStyle::Value(Schema::Enum(schema)) if !schema.has_data() => {
+            let variants = enum_values(schema);
+            let body = variants
+                .iter()
+                .map(|(name, value)| format!("    {} = {},\n", name, value))
+                .collect::<String>();
+            let values = variants
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                js,
+                "export const {} = Object.freeze({{ {} }});\n",
+                ident, values
+            )
+            .unwrap();
+            format!("export enum {} {{\n{}}}", ident, body)
+        }
+
+        BindingStyle::Value(Schema::Enum(schema)) => {
+            let variants = schema
+                .variants
+                .iter()
+                .map(|variant| {
+                    let fields = variant
+                        .fields()
+                        .map(|field| {
+                            format!(" {}: {};", field_name(&field), schema_type(&field.schema))
+                        })
+                        .collect::<String>();
+                    format!("{{ type: {:?};{} }}", variant.name(), fields)
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            format!("export type {} = {};", ident, variants)
+        }
+
+        BindingStyle::Value(schema) | BindingStyle::Marshal(_, schema) => {
+            match schema.as_struct_like() {
+                Some(schema)
+                    if schema.fields.iter().all(|field| field.name.is_none())
+                        && !schema.fields.is_empty() =>
+                {
+                    let elements = schema
+                        .fields
+                        .iter()
+                        .map(|field| schema_type(&field.schema))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("export type {} = [{}];", ident, elements)
+                }
+
+                Some(schema) => {
+                    let fields = schema
+                        .fields
+                        .iter()
+                        .map(|field| {
+                            format!(
+                                "    {}: {};\n",
+                                field_name(field),
+                                schema_type(&field.schema)
+                            )
+                        })
+                        .collect::<String>();
+                    format!("export interface {} {{\n{}}}", ident, fields)
+                }
+
+                None => format!("export type {} = unknown;", ident),
+            }
+        }
+
+        BindingStyle::Transparent(transparent) => {
+            format!("export type {} = {};", ident, ts_type(&transparent.inner))
+        }
+
+        BindingStyle::Custom(_) => format!("export type {} = unknown;", ident),
+    }
+}
+
+/// Declares the class for a handle type, including its methods, and writes the
+/// implementation of the class to `js`.
+fn declare_class(
+    export: &NamedType,
+    exports: &[Export],
+    types: &HashMap<&TypeName, &NamedType>,
+    js: &mut String,
+) -> String {
+    let ident = &*export.type_name.name;
+    let methods = exports
+        .iter()
+        .filter_map(|item| match item {
+            Export::Method(method) if method.self_type == export.type_name => Some(method),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut dts = format!(
+        "export class {} {{\n    private constructor();\n    free(): void;\n",
+        ident
+    );
+    writeln!(js, "export class {} {{", ident).unwrap();
+    writeln!(
+        js,
+        "    constructor(ptr) {{\n        this.__ptr = ptr;\n    }}\n"
+    )
+    .unwrap();
+    match &export.drop_fn {
+        Some(drop_fn) => writeln!(
+            js,
+            "    free() {{\n        wasm.{}(this.__ptr);\n        this.__ptr = 0;\n    }}\n",
+            drop_fn
+        )
+        .unwrap(),
+        None => writeln!(js, "    free() {{}}\n").unwrap(),
+    }
+
+    for method in methods {
+        write_method(&mut dts, js, method, types);
+    }
+
+    dts.push('}');
+    writeln!(js, "}}\n").unwrap();
+    dts
+}
+
+/// Declares a method and writes its implementation, unless the wrapper doesn't
+/// support the method.
+fn write_method(
+    dts: &mut String,
+    js: &mut String,
+    method: &Method,
+    types: &HashMap<&TypeName, &NamedType>,
+) {
+    let receiver = match method.receiver {
+        Some(ReceiverStyle::Ref) | Some(ReceiverStyle::RefMut) => Some("this.__ptr"),
+
+        // NOTE: Methods that take `self` by value consume the handle, so the wrapper
+        // can't support them without tracking ownership.
+        Some(ReceiverStyle::Move) => return,
+
+        None => None,
+    };
+
+    let body = match quote_js_call(
+        &method.binding,
+        receiver,
+        &method.inputs,
+        method.output.as_ref(),
+        types,
+    ) {
+        Some(body) => body,
+        None => return,
+    };
+
+    let ident = fn_ident(&method.name, method.rename.as_deref());
+    let is_static = method.receiver.is_none();
+    let output = method.output.as_ref().map_or("void".into(), ts_type);
+
+    write_docs(dts, "    ", method.docs.as_deref());
+    writeln!(
+        dts,
+        "    {}{}({}): {};",
+        if is_static { "static " } else { "" },
+        ident,
+        ts_args(&method.inputs),
+        output,
+    )
+    .unwrap();
+
+    let prefix = if is_static { "    static " } else { "    " };
+    write_js_fn(js, prefix, &ident, &method.inputs, &body);
+}
+
+/// Writes a JavaScript function that returns the result of `body`.
+fn write_js_fn(js: &mut String, prefix: &str, ident: &str, inputs: &[FnArg], body: &str) {
+    let args = inputs
+        .iter()
+        .map(|arg| arg.name.to_mixed_case())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let indent = if prefix.starts_with(' ') { "    " } else { "" };
+    writeln!(
+        js,
+        "{}{}({}) {{\n{}    return {};\n{}}}\n",
+        prefix, ident, args, indent, body, indent
+    )
+    .unwrap();
+}
+
+/// Returns the expression that invokes the raw binding, or `None` if the function's
+/// arguments or return value can't be passed to Wasm directly.
+fn quote_js_call(
+    binding: &str,
+    receiver: Option<&str>,
+    inputs: &[FnArg],
+    output: Option<&Repr>,
+    types: &HashMap<&TypeName, &NamedType>,
+) -> Option<String> {
+    let mut args = vec![format!("wasm.{}", binding)];
+    args.extend(receiver.map(String::from));
+    for arg in inputs {
+        let name = arg.name.to_mixed_case();
+        args.push(match &arg.repr {
+            Repr::Bool => format!("{} ? 1 : 0", name),
+            repr if is_number(repr) => name,
+            Repr::Named(type_name) if is_handle(type_name, types) => format!("{}.__ptr", name),
+            _ => return None,
+        });
+    }
+
+    let call = format!("call({})", args.join(", "));
+    Some(match output {
+        None => call,
+        Some(Repr::Bool) => format!("{} !== 0", call),
+
+        // NOTE: Wasm integers are signed, so unsigned 32-bit values need to be converted
+        // back to an unsigned number.
+        Some(Repr::U32) | Some(Repr::USize) => format!("{} >>> 0", call),
+        Some(Repr::U64) => format!("BigInt.asUintN(64, {})", call),
+        Some(repr) if is_number(repr) => call,
+        Some(Repr::Named(type_name)) if is_handle(type_name, types) => {
+            format!("new {}({})", type_name.name, call)
+        }
+        Some(_) => return None,
+    })
+}
+
+/// Returns `true` if the type is passed to Wasm as a single number.
+fn is_number(repr: &Repr) -> bool {
+    matches!(
+        repr,
+        Repr::I8
+            | Repr::I16
+            | Repr::I32
+            | Repr::I64
+            | Repr::ISize
+            | Repr::U8
+            | Repr::U16
+            | Repr::U32
+            | Repr::U64
+            | Repr::USize
+            | Repr::F32
+            | Repr::F64
+    )
+}
+
+fn is_handle(type_name: &TypeName, types: &HashMap<&TypeName, &NamedType>) -> bool {
+    matches!(
+        types.get(type_name).map(|export| &export.binding_style),
+        Some(BindingStyle::Handle)
+    )
+}
+
+fn ts_args(inputs: &[FnArg]) -> String {
+    inputs
+        .iter()
+        .map(|arg| format!("{}: {}", arg.name.to_mixed_case(), ts_type(&arg.repr)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the TypeScript type corresponding to `repr`.
+fn ts_type(repr: &Repr) -> String {
+    match repr {
+        Repr::Unit => "void".into(),
+        Repr::Bool => "boolean".into(),
+        Repr::Char | Repr::String | Repr::Str | Repr::Uuid => "string".into(),
+
+        // NOTE: 64-bit integers are passed to Wasm as a `BigInt`.
+        Repr::I64 | Repr::U64 | Repr::I128 | Repr::U128 => "bigint".into(),
+        repr if is_number(repr) => "number".into(),
+
+        Repr::Named(type_name)
+        | Repr::Dyn(type_name)
+        | Repr::BoxDyn(type_name)
+        | Repr::DynHandle(type_name) => type_name.name.to_string(),
+
+        Repr::Box(inner) | Repr::Ref(inner) | Repr::RefMut(inner) => ts_type(inner),
+
+        Repr::Vec(inner) | Repr::Slice(inner) if **inner == Repr::U8 => "Uint8Array".into(),
+        Repr::Vec(inner) | Repr::Slice(inner) | Repr::Array { element: inner, .. } => {
+            format!("{}[]", ts_type(inner))
+        }
+
+        Repr::Tuple(elements) => format!(
+            "[{}]",
+            elements.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+        ),
+
+        Repr::Map { key, value } => format!("Map<{}, {}>", ts_type(key), ts_type(value)),
+
+        // Durations are expressed in milliseconds, matching the units used by `Date`.
+        Repr::Duration => "number".into(),
+        Repr::SystemTime | Repr::Date => "Date".into(),
+
+        Repr::Option(inner) => format!("{} | null", ts_type(inner)),
+
+        // Errors are thrown, so only the success type is returned.
+        Repr::Result { ok, .. } => ts_type(ok),
+
+        Repr::Iter(item) => format!("Iterable<{}>", ts_type(item)),
+        Repr::Events(event) => format!("AsyncIterable<{}>", ts_type(event)),
+
+        Repr::Fn { inputs, output } => {
+            let args = inputs
+                .iter()
+                .enumerate()
+                .map(|(index, input)| format!("arg{}: {}", index, ts_type(input)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({}) => {}", args, ts_type(output))
+        }
+
+        Repr::CancelFlag => "AbortSignal".into(),
+
+        // Handled by the number case above, but the compiler can't tell.
+        _ => unreachable!("Unhandled repr {:?}", repr),
+    }
+}
+
+/// Returns the TypeScript type corresponding to `schema`, which describes a field of
+/// an exported type.
+fn schema_type(schema: &Schema) -> String {
+    match repr_for_schema(schema) {
+        Some(repr) => ts_type(&repr),
+        None => "unknown".into(),
+    }
+}
+
+fn field_name(field: &Field) -> String {
+    field
+        .name
+        .map(|name| name.to_mixed_case())
+        .unwrap_or_else(|| "value".into())
+}
+
+fn fn_ident(name: &str, rename: Option<&str>) -> String {
+    match rename {
+        Some(rename) => rename.into(),
+        None => name.to_mixed_case(),
+    }
+}
+
+fn write_docs(out: &mut String, indent: &str, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        writeln!(out, "{}/**", indent).unwrap();
+        for line in docs.lines() {
+            writeln!(out, "{} * {}", indent, line.replace("*/", "* /")).unwrap();
+        }
+        writeln!(out, "{} */", indent).unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+//! Output layout for dropping the generated bindings into a Unity project.
+//!
+//! The bindings are placed in their own assembly definition so that they can be
+//! compiled with unsafe code enabled without enabling it for the rest of the project,
+//! and the native libraries are placed in the `Plugins` folder that Unity uses for
+//! the corresponding platform.
+
+use crate::{pack::NativeLib, Opt};
+use failure::Error;
+use std::{fs, path::Path};
+
+/// Generates the contents of the `.asmdef` for the bindings.
+pub fn generate_asmdef(name: &str) -> String {
+    format!(
+        r#"{{
+    "name": "{}",
+    "references": [],
+    "includePlatforms": [],
+    "excludePlatforms": [],
+    "allowUnsafeCode": true,
+    "autoReferenced": true
+}}
+"#,
+        name
+    )
+}
+
+/// Copies each native library into the `Plugins` folder for its platform.
+pub fn copy_plugins(opt: &Opt, out_dir: &Path) -> Result<(), Error> {
+    for lib in &opt.native_lib {
+        let file_name = lib.path.file_name().ok_or_else(|| {
+            failure::format_err!("Native library path has no file name: {:?}", lib.path)
+        })?;
+
+        let plugin_dir = out_dir.join("Plugins").join(plugin_folder(lib));
+        fs::create_dir_all(&plugin_dir)?;
+        fs::copy(&lib.path, plugin_dir.join(file_name)).map_err(|err| {
+            failure::format_err!("Failed to copy native library {:?}: {}", lib.path, err)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Returns the folder under `Plugins` that Unity loads the native library from for the
+/// library's runtime identifier.
+///
+/// Unknown runtime identifiers are placed in a folder named after the identifier, in
+/// which case the platform settings for the plugin need to be configured manually.
+fn plugin_folder(lib: &NativeLib) -> &str {
+    match &*lib.rid {
+        "win-x64" | "linux-x64" => "x86_64",
+        "win-x86" => "x86",
+        "osx-x64" | "osx-arm64" | "osx" => "macOS",
+        "android-arm64" => "Android/libs/arm64-v8a",
+        "android-arm" => "Android/libs/armeabi-v7a",
+        "android-x86" => "Android/libs/x86",
+        "ios-arm64" | "ios" => "iOS",
+        rid => rid,
+    }
+}
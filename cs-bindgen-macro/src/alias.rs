@@ -0,0 +1,45 @@
+//! Bindings for exported type aliases.
+//!
+//! Aliases don't generate any bindings, since values of the alias are passed as the
+//! aliased type. Only a describe function is generated, so that the generated code
+//! can refer to the aliased type by the name of the alias.
+
+use crate::{attr::*, quote_docs, quote_rename};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// Generates the describe function for a top-level `type` alias.
+pub fn quote_alias_item(item: ItemType, attr: &Attributes) -> syn::Result<TokenStream> {
+    // NOTE: Generic aliases can't be referred to without their type arguments, and C#
+    // aliases can't be generic, so there's no way to export them.
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "Generic type aliases are not supported with `#[cs_bindgen]`",
+        ));
+    }
+
+    let ident = &item.ident;
+    let ty = &item.ty;
+    let name = ident.to_string();
+    let describe_ident = format_describe_ident!(ident);
+    let rename = quote_rename(attr.rename.as_ref());
+    let docs = quote_docs(doc_comment(&item.attrs).as_deref());
+
+    Ok(quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::Alias {
+                name: #name.into(),
+                module: module_path!().into(),
+                rename: #rename,
+                docs: #docs,
+                repr: <#ty as cs_bindgen::abi::Abi>::repr(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+    })
+}
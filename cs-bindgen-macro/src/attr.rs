@@ -0,0 +1,541 @@
+//! Parsing for the arguments to the `#[cs_bindgen(...)]` attribute.
+
+use crate::{generic::TypeArg, marshal::MarshalFormat};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::Comma,
+    *,
+};
+
+/// The options specified in the arguments of a `#[cs_bindgen(...)]` attribute.
+#[derive(Default, Clone)]
+pub struct Attributes {
+    /// The concrete type arguments listed in any `instantiate(...)` arguments, in the
+    /// order they were declared.
+    pub instantiate: Vec<TypeArg>,
+
+    /// The name to use for the item in C#, i.e. `rename = "..."`.
+    pub rename: Option<LitStr>,
+
+    /// Set if the field or method should be excluded from the bindings, i.e. `skip`.
+    pub skip: Option<Ident>,
+
+    /// Set if the method is the getter for a property, i.e. `getter`.
+    pub getter: Option<Ident>,
+
+    /// Set if the method is the setter for a property, i.e. `setter`.
+    pub setter: Option<Ident>,
+
+    /// Set if the method is the getter for an indexer, i.e. `index`.
+    pub index: Option<Ident>,
+
+    /// Set if the method is the setter for an indexer, i.e. `index_mut`.
+    pub index_mut: Option<Ident>,
+
+    /// Set if the method should be exposed as a C# constructor, i.e. `constructor`.
+    pub constructor: Option<Ident>,
+
+    /// The names of the C# `out` parameters for the trailing elements of a returned
+    /// tuple, i.e. `out(...)`.
+    pub out: Vec<Ident>,
+
+    /// Set if calls to the function can be recorded into a C# `CommandBuffer`, i.e.
+    /// `batch`.
+    pub batch: Option<Ident>,
+
+    /// The format used to pass the error of a returned `Result` to C#, i.e.
+    /// `error = "..."`. Only `"display"` is supported, which passes the error as its
+    /// `Display` string.
+    pub error: Option<LitStr>,
+
+    /// Set if the struct implements `Default` without deriving it, i.e. `default`.
+    pub default: Option<Ident>,
+
+    /// Set if the struct implements `Display`, i.e. `display`.
+    pub display: Option<Ident>,
+
+    /// Set if the struct implements `PartialEq` without deriving it, i.e. `eq`.
+    pub eq: Option<Ident>,
+
+    /// Set if the struct implements `Hash` without deriving it, i.e. `hash`.
+    pub hash: Option<Ident>,
+
+    /// Set if the struct implements `PartialOrd` without deriving it, i.e. `ord`.
+    pub ord: Option<Ident>,
+
+    /// Set if the struct implements `Clone` without deriving it, i.e. `clone`.
+    pub clone: Option<Ident>,
+
+    /// Set if the struct is shared between C# objects through an `Arc`, i.e. `shared`.
+    pub shared: Option<Ident>,
+
+    /// Set if the allocations for the struct's handles are reused, i.e. `pool`.
+    pub pool: Option<Ident>,
+
+    /// Set if the struct is a set of bit flags exported as a C# `[Flags]` enum, i.e.
+    /// `flags`.
+    pub flags: Option<Ident>,
+
+    /// Set if the newtype struct is passed as its inner value, i.e. `transparent`.
+    pub transparent: Option<Ident>,
+
+    /// Set if a C# wrapper struct is generated for a transparent newtype, i.e.
+    /// `wrapper`.
+    pub wrapper: Option<Ident>,
+
+    /// The format used to serialize the type when it's passed to C#, i.e.
+    /// `marshal = "..."`.
+    pub marshal: Option<LitStr>,
+
+    /// The type that values are converted to before being passed to C#, i.e.
+    /// `raw = "..."`.
+    pub raw: Option<LitStr>,
+
+    /// The function that converts a value into its raw type, i.e.
+    /// `into_raw_with = "..."`.
+    pub into_raw_with: Option<LitStr>,
+
+    /// The function that converts a raw value back into the type, i.e.
+    /// `from_raw_with = "..."`.
+    pub from_raw_with: Option<LitStr>,
+
+    /// The C# class that converts between the raw type and the C# type, i.e.
+    /// `converter = "..."`.
+    pub converter: Option<LitStr>,
+
+    /// The C# type that the type is exposed as, i.e. `cs_type = "..."`.
+    pub cs_type: Option<LitStr>,
+}
+
+impl Attributes {
+    /// Parses and merges the arguments of all `#[cs_bindgen]` attributes in `attrs`.
+    ///
+    /// This is used for items nested within another exported item (e.g. methods in an
+    /// `impl` block), where the attributes are processed by the outer item.
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Attributes::default();
+        for attr in attrs.iter().filter(|attr| is_cs_bindgen_attr(attr)) {
+            if attr.tokens.is_empty() {
+                continue;
+            }
+
+            let Attributes {
+                instantiate,
+                rename,
+                skip,
+                getter,
+                setter,
+                index,
+                index_mut,
+                constructor,
+                out,
+                batch,
+                error,
+                default,
+                display,
+                eq,
+                hash,
+                ord,
+                clone,
+                shared,
+                pool,
+                flags,
+                transparent,
+                wrapper,
+                marshal,
+                raw,
+                into_raw_with,
+                from_raw_with,
+                converter,
+                cs_type,
+            } = attr.parse_args()?;
+            result.instantiate.extend(instantiate);
+            result.rename = result.rename.or(rename);
+            result.skip = result.skip.or(skip);
+            result.getter = result.getter.or(getter);
+            result.setter = result.setter.or(setter);
+            result.index = result.index.or(index);
+            result.index_mut = result.index_mut.or(index_mut);
+            result.constructor = result.constructor.or(constructor);
+            result.out.extend(out);
+            result.batch = result.batch.or(batch);
+            result.error = result.error.or(error);
+            result.default = result.default.or(default);
+            result.display = result.display.or(display);
+            result.eq = result.eq.or(eq);
+            result.hash = result.hash.or(hash);
+            result.ord = result.ord.or(ord);
+            result.clone = result.clone.or(clone);
+            result.shared = result.shared.or(shared);
+            result.pool = result.pool.or(pool);
+            result.flags = result.flags.or(flags);
+            result.transparent = result.transparent.or(transparent);
+            result.wrapper = result.wrapper.or(wrapper);
+            result.marshal = result.marshal.or(marshal);
+            result.raw = result.raw.or(raw);
+            result.into_raw_with = result.into_raw_with.or(into_raw_with);
+            result.from_raw_with = result.from_raw_with.or(from_raw_with);
+            result.converter = result.converter.or(converter);
+            result.cs_type = result.cs_type.or(cs_type);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the first argument that's only valid on methods, if any.
+    pub fn method_arg(&self) -> Option<&Ident> {
+        self.getter
+            .as_ref()
+            .or(self.setter.as_ref())
+            .or(self.index.as_ref())
+            .or(self.index_mut.as_ref())
+            .or(self.constructor.as_ref())
+    }
+
+    /// Returns the first argument that's only valid on structs, if any.
+    pub fn struct_arg(&self) -> Option<&Ident> {
+        self.std_trait_arg()
+            .or(self.shared.as_ref())
+            .or(self.pool.as_ref())
+            .or(self.flags.as_ref())
+            .or(self.transparent.as_ref())
+            .or(self.wrapper.as_ref())
+    }
+
+    /// Returns the first argument that exports one of the standard traits, if any.
+    pub fn std_trait_arg(&self) -> Option<&Ident> {
+        self.default
+            .as_ref()
+            .or(self.display.as_ref())
+            .or(self.eq.as_ref())
+            .or(self.hash.as_ref())
+            .or(self.ord.as_ref())
+            .or(self.clone.as_ref())
+    }
+
+    /// Returns the format specified with `marshal = "..."`, if any.
+    pub fn marshal_format(&self) -> syn::Result<Option<MarshalFormat>> {
+        self.marshal
+            .as_ref()
+            .map(MarshalFormat::from_lit)
+            .transpose()
+    }
+
+    /// Returns the first argument used to specify custom conversion functions, if any.
+    pub fn custom_arg(&self) -> Option<&LitStr> {
+        self.raw
+            .as_ref()
+            .or(self.into_raw_with.as_ref())
+            .or(self.from_raw_with.as_ref())
+            .or(self.converter.as_ref())
+            .or(self.cs_type.as_ref())
+    }
+}
+
+/// Parses the `#[cs_bindgen]` attributes on a field or enum variant.
+///
+/// Only `rename` and `skip` are supported on fields and variants.
+pub fn member_attrs(attrs: &[Attribute]) -> syn::Result<Attributes> {
+    let attributes = Attributes::from_attrs(attrs)?;
+    if let Some(arg) = attributes.method_arg().or(attributes.struct_arg()) {
+        return Err(Error::new_spanned(
+            arg,
+            "Only `rename` and `skip` are supported on fields and variants",
+        ));
+    }
+
+    if let Some(arg) = attributes.instantiate.first() {
+        return Err(Error::new_spanned(
+            &arg.param,
+            "Only `rename` and `skip` are supported on fields and variants",
+        ));
+    }
+
+    if let Some(arg) = attributes.out.first() {
+        return Err(Error::new_spanned(
+            arg,
+            "Only `rename` and `skip` are supported on fields and variants",
+        ));
+    }
+
+    if let Some(marshal) = attributes.marshal.as_ref().or(attributes.custom_arg()) {
+        return Err(Error::new_spanned(
+            marshal,
+            "Only `rename` and `skip` are supported on fields and variants",
+        ));
+    }
+
+    Ok(attributes)
+}
+
+/// Parses the `#[cs_bindgen]` attributes on a function parameter, returning the
+/// expression for the parameter's default value, if any.
+///
+/// Only `default = ...` is supported on parameters.
+pub fn param_default(attrs: &[Attribute]) -> syn::Result<Option<Expr>> {
+    let mut default = None;
+    for attr in attrs.iter().filter(|attr| is_cs_bindgen_attr(attr)) {
+        default = attr.parse_args_with(|input: ParseStream| {
+            let ident = input.parse::<Ident>()?;
+            if ident != "default" || !input.peek(Token![=]) {
+                return Err(Error::new_spanned(
+                    &ident,
+                    "Only `default = ...` is supported on function parameters",
+                ));
+            }
+
+            input.parse::<Token![=]>()?;
+            let expr = input.parse::<Expr>()?;
+            input.parse::<Option<Comma>>()?;
+            Ok(Some(expr))
+        })?;
+    }
+
+    Ok(default)
+}
+
+/// Returns `true` if `field` is marked with `#[cs_bindgen(skip)]`.
+///
+/// Invalid attributes are treated as not skipping the field, since they're reported
+/// when the attributes are first validated with [`member_attrs`].
+///
+/// [`member_attrs`]: fn.member_attrs.html
+pub fn is_skipped(field: &Field) -> bool {
+    Attributes::from_attrs(&field.attrs)
+        .map(|attributes| attributes.skip.is_some())
+        .unwrap_or(false)
+}
+
+/// Returns the doc comment from the `#[doc]` attributes in `attrs`, if any.
+///
+/// Each line of a `///` comment is a separate attribute, which has the leading space
+/// after the `///` preserved. The leading space is removed so that the lines can be
+/// joined back together as they appear in the source.
+pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| {
+            if line.starts_with(' ') {
+                line[1..].to_string()
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let docs = lines.join("\n");
+    let docs = docs.trim();
+    if docs.is_empty() {
+        None
+    } else {
+        Some(docs.into())
+    }
+}
+
+/// Returns `true` if `attr` is a `#[cs_bindgen]` attribute.
+pub fn is_cs_bindgen_attr(attr: &Attribute) -> bool {
+    attr.path
+        .get_ident()
+        .map(|ident| ident == "cs_bindgen")
+        .unwrap_or(false)
+}
+
+/// Removes all `#[cs_bindgen]` attributes from the methods in an `impl` block and
+/// their parameters.
+///
+/// Attributes on methods are handled by the `#[cs_bindgen]` attribute on the `impl`
+/// block, and would otherwise be expanded again as separate items.
+pub fn strip_method_attrs(mut item: ItemImpl) -> ItemImpl {
+    for impl_item in &mut item.items {
+        if let ImplItem::Method(method) = impl_item {
+            method.attrs.retain(|attr| !is_cs_bindgen_attr(attr));
+            strip_param_attrs(&mut method.sig);
+        }
+    }
+
+    item
+}
+
+/// Removes all `#[cs_bindgen]` attributes from the parameters of a function.
+///
+/// Attributes on parameters are handled by the `#[cs_bindgen]` attribute on the
+/// function, and attribute macros aren't allowed on parameters.
+pub fn strip_param_attrs(signature: &mut Signature) {
+    for input in &mut signature.inputs {
+        if let FnArg::Typed(arg) = input {
+            arg.attrs.retain(|attr| !is_cs_bindgen_attr(attr));
+        }
+    }
+}
+
+/// Removes all `#[cs_bindgen]` attributes from the fields of a struct.
+///
+/// Attributes on fields are handled by the `#[cs_bindgen]` attribute on the struct,
+/// and aren't valid on their own.
+pub fn strip_field_attrs(mut item: ItemStruct) -> ItemStruct {
+    for field in &mut item.fields {
+        field.attrs.retain(|attr| !is_cs_bindgen_attr(attr));
+    }
+
+    item
+}
+
+/// Removes all `#[cs_bindgen]` attributes from the variants of an enum and their
+/// fields.
+pub fn strip_variant_attrs(mut item: ItemEnum) -> ItemEnum {
+    for variant in &mut item.variants {
+        variant.attrs.retain(|attr| !is_cs_bindgen_attr(attr));
+        for field in &mut variant.fields {
+            field.attrs.retain(|attr| !is_cs_bindgen_attr(attr));
+        }
+    }
+
+    item
+}
+
+impl Parse for Attributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attributes = Attributes::default();
+
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            if ident == "instantiate" {
+                let content;
+                parenthesized!(content in input);
+                let args = Punctuated::<TypeArg, Comma>::parse_terminated(&content)?;
+                attributes.instantiate.extend(args);
+            } else if ident == "rename" {
+                input.parse::<Token![=]>()?;
+                let rename = input.parse()?;
+                validate_rename(&rename)?;
+                attributes.rename = Some(rename);
+            } else if ident == "skip" {
+                attributes.skip = Some(ident);
+            } else if ident == "getter" {
+                attributes.getter = Some(ident);
+            } else if ident == "setter" {
+                attributes.setter = Some(ident);
+            } else if ident == "index" {
+                attributes.index = Some(ident);
+            } else if ident == "index_mut" {
+                attributes.index_mut = Some(ident);
+            } else if ident == "constructor" {
+                attributes.constructor = Some(ident);
+            } else if ident == "out" {
+                let content;
+                parenthesized!(content in input);
+                let names = Punctuated::<Ident, Comma>::parse_terminated(&content)?;
+                attributes.out.extend(names);
+            } else if ident == "batch" {
+                attributes.batch = Some(ident);
+            } else if ident == "error" {
+                input.parse::<Token![=]>()?;
+                let error = input.parse::<LitStr>()?;
+                if error.value() != "display" {
+                    return Err(Error::new_spanned(
+                        &error,
+                        "Unknown error format, expected `error = \"display\"`",
+                    ));
+                }
+
+                attributes.error = Some(error);
+            } else if ident == "default" {
+                if input.peek(Token![=]) {
+                    return Err(Error::new_spanned(
+                        &ident,
+                        "`default = ...` is only supported on function parameters",
+                    ));
+                }
+
+                attributes.default = Some(ident);
+            } else if ident == "display" {
+                attributes.display = Some(ident);
+            } else if ident == "eq" {
+                attributes.eq = Some(ident);
+            } else if ident == "hash" {
+                attributes.hash = Some(ident);
+            } else if ident == "ord" {
+                attributes.ord = Some(ident);
+            } else if ident == "clone" {
+                attributes.clone = Some(ident);
+            } else if ident == "shared" {
+                attributes.shared = Some(ident);
+            } else if ident == "pool" {
+                attributes.pool = Some(ident);
+            } else if ident == "flags" {
+                attributes.flags = Some(ident);
+            } else if ident == "transparent" {
+                attributes.transparent = Some(ident);
+            } else if ident == "wrapper" {
+                attributes.wrapper = Some(ident);
+            } else if ident == "marshal" {
+                input.parse::<Token![=]>()?;
+                attributes.marshal = Some(input.parse()?);
+            } else if ident == "raw" {
+                input.parse::<Token![=]>()?;
+                attributes.raw = Some(input.parse()?);
+            } else if ident == "into_raw_with" {
+                input.parse::<Token![=]>()?;
+                attributes.into_raw_with = Some(input.parse()?);
+            } else if ident == "from_raw_with" {
+                input.parse::<Token![=]>()?;
+                attributes.from_raw_with = Some(input.parse()?);
+            } else if ident == "converter" {
+                input.parse::<Token![=]>()?;
+                attributes.converter = Some(input.parse()?);
+            } else if ident == "cs_type" {
+                input.parse::<Token![=]>()?;
+                attributes.cs_type = Some(input.parse()?);
+            } else {
+                return Err(Error::new_spanned(
+                    &ident,
+                    format!("Unknown `#[cs_bindgen]` argument `{}`", ident),
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Comma>()?;
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Verifies that the value of a `rename` argument can be used as an identifier in C#.
+fn validate_rename(rename: &LitStr) -> syn::Result<()> {
+    let name = rename.value();
+    let mut chars = name.chars();
+    let is_ident = match chars.next() {
+        Some(first) => {
+            (first.is_alphabetic() || first == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_')
+        }
+        None => false,
+    };
+
+    if !is_ident {
+        return Err(Error::new_spanned(
+            rename,
+            format!("`{}` is not a valid C# identifier", name),
+        ));
+    }
+
+    if cs_bindgen_shared::is_cs_keyword(&name) {
+        return Err(Error::new_spanned(
+            rename,
+            format!("`{}` is a C# keyword and can't be used as a name", name),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! Code generation for functions exported with `#[cs_bindgen(batch)]`.
+//!
+//! Batched functions export an additional function that returns the command function
+//! for the exported function, which the C# `CommandBuffer` records along with the
+//! arguments for each call. See the `batch` module in `cs_bindgen` for details on how
+//! commands are encoded.
+
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// Names of the argument types that can be recorded in a command buffer.
+///
+/// The raw representation of each of these is a plain value that can be copied into
+/// the buffer directly.
+const BATCH_ARG_TYPES: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+];
+
+/// Verifies that calls to the function can be recorded into a command buffer.
+///
+/// The command buffer can't own or pin any managed data, so batched functions can
+/// only take primitive arguments, and methods must borrow their receiver. Calls aren't
+/// run until the buffer is submitted, so batched functions also can't return a value.
+pub fn validate_batch(signature: &Signature) -> syn::Result<()> {
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "Async functions can't be batched with `#[cs_bindgen]`",
+        ));
+    }
+
+    if let ReturnType::Type(_, ty) = &signature.output {
+        if !matches!(&**ty, Type::Tuple(tuple) if tuple.elems.is_empty()) {
+            return Err(Error::new_spanned(
+                ty,
+                "Batched functions can't return a value with `#[cs_bindgen]`",
+            ));
+        }
+    }
+
+    for input in &signature.inputs {
+        match input {
+            FnArg::Receiver(receiver) if receiver.reference.is_some() => {}
+            FnArg::Receiver(receiver) => {
+                return Err(Error::new_spanned(
+                    receiver,
+                    "Batched methods must take `&self` or `&mut self` with `#[cs_bindgen]`",
+                ))
+            }
+
+            FnArg::Typed(arg) if is_batch_arg(&arg.ty) => {}
+            FnArg::Typed(arg) => {
+                return Err(Error::new_spanned(
+                    &arg.ty,
+                    "Batched functions can only take primitive arguments with `#[cs_bindgen]`",
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the exported function that returns the command function for a batched
+/// function.
+///
+/// The command function reads each of `inputs` from the buffer, in order, and passes
+/// them to the binding function along with the panic message out parameter. `inputs`
+/// lists the name and type of each argument to the binding function, including the
+/// receiver for methods.
+pub fn quote_command_fn(
+    mangled_name: &str,
+    binding_ident: &Ident,
+    inputs: &[(Ident, TokenStream)],
+) -> TokenStream {
+    let command_ident = format_command_ident!(mangled_name);
+    let arg_ident = inputs.iter().map(|(ident, _)| ident);
+    let read_args = inputs.iter().map(|(ident, ty)| {
+        quote! {
+            let #ident = cs_bindgen::batch::read_arg::<<#ty as cs_bindgen::abi::Abi>::Abi>(&mut __cursor);
+        }
+    });
+
+    quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #command_ident() -> cs_bindgen::batch::RawCommandFn {
+            unsafe extern "C" fn command(
+                __args: *const u8,
+                __panic: *mut cs_bindgen::abi::RawString,
+            ) -> usize {
+                #[allow(unused_mut)]
+                let mut __cursor = __args;
+                #( #read_args )*
+                #binding_ident(#( #arg_ident, )* __panic);
+                __cursor as usize - __args as usize
+            }
+
+            command
+        }
+    }
+}
+
+fn is_batch_arg(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .map(|ident| BATCH_ARG_TYPES.iter().any(|name| ident == name))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
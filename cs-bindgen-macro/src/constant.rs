@@ -0,0 +1,138 @@
+//! Bindings for exported `const` and `static` items.
+//!
+//! Every constant exports a binding function that returns its value. Constants with
+//! a primitive or string type also include their value in their description, so that
+//! they can be generated as a `const` in C#, as do constants of a flags type. Other
+//! constants (e.g. values of exported structs) are read through the binding function
+//! instead.
+
+use crate::{extract_type_ident, generic::substitute_type, quote_binding_fn};
+use proc_macro2::{Span, TokenStream};
+use quote::*;
+use syn::*;
+
+/// The primitive types that can be generated as a C# `const`.
+const LITERAL_TYPES: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+];
+
+/// Generates the bindings for a top-level `const` or `static` item.
+pub fn quote_const_item(ident: &Ident, ty: &Type) -> syn::Result<TokenStream> {
+    quote_const_export(ident, ty, None, quote! { #ident }, &ident.to_string())
+}
+
+/// Generates the bindings for an associated `const` declared in an `impl` block.
+///
+/// The generated functions aren't in the scope of the `impl` block, so any uses of
+/// `Self` in the constant's type are replaced with the full self type.
+pub fn quote_assoc_const_item(item: &ImplItemConst, self_ty: &Type) -> syn::Result<TokenStream> {
+    let ident = &item.ident;
+    let ty = substitute_type(
+        &item.ty,
+        &[(Ident::new("Self", Span::call_site()), self_ty.clone())],
+    )?;
+    let self_ident = extract_type_ident(self_ty)?;
+    let mangled_name = format!("{}__{}", ident, self_ident);
+
+    quote_const_export(
+        ident,
+        &ty,
+        Some(self_ty),
+        quote! { <#self_ty>::#ident },
+        &mangled_name,
+    )
+}
+
+/// Generates the binding and describe functions for a constant.
+///
+/// `self_ty` is the type that declares the constant for associated constants.
+/// `value` is the expression used to access the constant, and `mangled_name` is used
+/// to generate the names of the exported functions.
+pub fn quote_const_export(
+    ident: &Ident,
+    ty: &Type,
+    self_ty: Option<&Type>,
+    value: TokenStream,
+    mangled_name: &str,
+) -> syn::Result<TokenStream> {
+    if let Type::Reference(reference) = ty {
+        if reference.mutability.is_some() {
+            return Err(Error::new_spanned(
+                ty,
+                "Mutable references are not supported in constants with `#[cs_bindgen]`",
+            ));
+        }
+    }
+
+    let binding_ident = format_binding_ident!(mangled_name);
+    let describe_ident = format_describe_ident!(mangled_name);
+
+    let binding = quote_binding_fn(
+        &binding_ident,
+        std::iter::empty(),
+        quote! { -> <#ty as cs_bindgen::abi::Abi>::Abi },
+        quote! { cs_bindgen::abi::Abi::into_abi(#value) },
+    );
+
+    let name = ident.to_string();
+    let binding_name = binding_ident.to_string();
+    let describe_value = if is_literal_type(ty) {
+        quote! { Some(cs_bindgen::shared::ConstValue::from(#value)) }
+    } else {
+        // Constants of a flags type are members of the generated C# enum, so their
+        // value is also included.
+        quote! {
+            {
+                #[allow(unused_imports)]
+                use cs_bindgen::flags::{FlagsValue, NotFlagsValue};
+                (&cs_bindgen::flags::ValueProbe(&#value)).const_value()
+            }
+        }
+    };
+
+    let self_type = match self_ty {
+        Some(self_ty) => quote! { Some(<#self_ty as cs_bindgen::shared::Named>::type_name()) },
+        None => quote! { None },
+    };
+
+    Ok(quote! {
+        #binding
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::Const {
+                name: #name.into(),
+                module: module_path!().into(),
+                binding: #binding_name.into(),
+                self_type: #self_type,
+                repr: <#ty as cs_bindgen::abi::Abi>::repr(),
+                value: #describe_value,
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+    })
+}
+
+/// Returns `true` if values of `ty` can be represented as a literal in C#, i.e. the
+/// type is a numeric primitive, `bool`, or `&str`.
+fn is_literal_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path
+                    .path
+                    .get_ident()
+                    .map(|ident| LITERAL_TYPES.iter().any(|literal| ident == literal))
+                    .unwrap_or(false)
+        }
+
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) => path.qself.is_none() && path.path.is_ident("str"),
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
@@ -0,0 +1,176 @@
+//! Bindings for types exported with user-provided conversion functions.
+//!
+//! A type can be exported by converting it to another type that the binding
+//! generator already supports, e.g. a third-party type that can be represented as a
+//! string:
+//!
+//! ```ignore
+//! #[cs_bindgen(
+//!     raw = "String",
+//!     into_raw_with = "GameId::to_hex",
+//!     from_raw_with = "GameId::from_hex",
+//!     converter = "GameIdConverter",
+//!     cs_type = "System.Guid",
+//! )]
+//! pub struct GameId(u128);
+//! ```
+//!
+//! `into_raw_with` is called with a reference to the value, and `from_raw_with` is
+//! called with the raw value. The generated `Abi` impl delegates to the `Abi` impl of
+//! the raw type, and the C# converter class handles the conversion between the C#
+//! type for the raw value and the type exposed to C#.
+
+use crate::{
+    attr::Attributes, describe_named_type, impl_named, quote_abi_mut_impl, quote_convert_list_fn,
+    quote_index_fn, quote_vec_drop_fn, repr_impl, std_impls::StdImpls, BindingStyle,
+};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// The arguments used to export a type with custom conversion functions.
+pub struct CustomMarshal {
+    raw: Type,
+    into_raw_with: Path,
+    from_raw_with: Path,
+    converter: LitStr,
+    cs_type: Option<LitStr>,
+}
+
+impl CustomMarshal {
+    /// Extracts the custom conversion arguments from `attr`, if any were specified.
+    ///
+    /// Returns an error if only some of the required arguments were specified, or if
+    /// they're combined with `marshal`.
+    pub fn from_attrs(attr: &Attributes) -> syn::Result<Option<Self>> {
+        let arg = match attr.custom_arg() {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        if let Some(marshal) = &attr.marshal {
+            return Err(Error::new_spanned(
+                marshal,
+                "`marshal` can't be combined with custom conversion functions",
+            ));
+        }
+
+        let required = |value: &Option<LitStr>, name: &str| {
+            value.clone().ok_or_else(|| {
+                Error::new_spanned(
+                    arg,
+                    format!(
+                        "Missing `{}`, custom conversions require `raw`, `into_raw_with`, \
+                         `from_raw_with`, and `converter`",
+                        name,
+                    ),
+                )
+            })
+        };
+
+        Ok(Some(CustomMarshal {
+            raw: required(&attr.raw, "raw")?.parse()?,
+            into_raw_with: required(&attr.into_raw_with, "into_raw_with")?.parse()?,
+            from_raw_with: required(&attr.from_raw_with, "from_raw_with")?.parse()?,
+            converter: required(&attr.converter, "converter")?,
+            cs_type: attr.cs_type.clone(),
+        }))
+    }
+}
+
+/// Generates the bindings for a struct or enum exported with custom conversion
+/// functions.
+///
+/// Only the raw value is visible in C#, so none of the fields, variants, or
+/// standard trait impls of the type are exported.
+pub fn quote_custom_type(
+    ident: &Ident,
+    custom: &CustomMarshal,
+    rename: Option<&LitStr>,
+    docs: Option<&str>,
+) -> TokenStream {
+    let custom_impls = quote_custom_impls(ident, custom);
+    let named_impl = impl_named(ident, rename);
+    let describe_fn = describe_named_type(
+        ident,
+        BindingStyle::Custom,
+        &StdImpls::default(),
+        &[],
+        docs,
+        &[],
+        None,
+        false,
+    );
+
+    quote! {
+        #custom_impls
+        #named_impl
+        #describe_fn
+    }
+}
+
+/// Generates the `Custom` and `Abi` impls for a type with custom conversion functions,
+/// along with the helper bindings needed to pass collections of the type.
+fn quote_custom_impls(ident: &Ident, custom: &CustomMarshal) -> TokenStream {
+    let CustomMarshal {
+        raw,
+        into_raw_with,
+        from_raw_with,
+        converter,
+        cs_type,
+    } = custom;
+
+    let cs_type = match cs_type {
+        Some(cs_type) => quote! { Some(#cs_type) },
+        None => quote! { None },
+    };
+
+    let repr_fn = repr_impl(ident);
+    let abi_mut_impl = quote_abi_mut_impl(ident);
+    let index_fn = quote_index_fn(ident);
+    let convert_list_fn = quote_convert_list_fn(ident);
+    let vec_drop_fn = quote_vec_drop_fn(ident);
+
+    quote! {
+        impl cs_bindgen::marshal::Custom for #ident {
+            type Raw = #raw;
+
+            const CONVERTER: &'static str = #converter;
+            const CS_TYPE: Option<&'static str> = #cs_type;
+
+            fn into_raw(&self) -> Self::Raw {
+                #into_raw_with(self)
+            }
+
+            fn from_raw(raw: Self::Raw) -> Self {
+                #from_raw_with(raw)
+            }
+        }
+
+        impl cs_bindgen::abi::Abi for #ident {
+            type Abi = <#raw as cs_bindgen::abi::Abi>::Abi;
+
+            #repr_fn
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                let raw = <#raw as cs_bindgen::abi::Abi>::from_abi(abi);
+                <Self as cs_bindgen::marshal::Custom>::from_raw(raw)
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                let raw = <Self as cs_bindgen::marshal::Custom>::into_raw(self);
+                cs_bindgen::abi::Abi::into_abi(raw)
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                let raw = <Self as cs_bindgen::marshal::Custom>::into_raw(&self);
+                cs_bindgen::abi::Abi::into_abi(raw)
+            }
+        }
+
+        #abi_mut_impl
+        #index_fn
+        #convert_list_fn
+        #vec_drop_fn
+    }
+}
@@ -1,40 +1,189 @@
 use crate::{
-    describe_named_type, impl_named, quote_convert_list_fn, quote_index_fn, quote_vec_drop_fn,
-    reject_generics, repr_impl, value, BindingStyle,
+    attr::{doc_comment, member_attrs, Attributes},
+    custom::{self, CustomMarshal},
+    describe_named_type, described_field_ty,
+    generic::Instantiation,
+    impl_named, list, marshal, quote_abi_mut_impl, quote_convert_list_fn, quote_index_fn,
+    quote_size_fn, quote_vec_drop_fn, reject_generics, repr_impl,
+    std_impls::StdImpls,
+    support::{check_type, TypePosition},
+    value, BindingStyle,
 };
 use proc_macro2::{Literal, TokenStream};
 use quote::*;
-use syn::*;
+use syn::{punctuated::Punctuated, token::Comma, *};
+
+/// The integer types that can be used as the discriminant of an enum with
+/// `#[repr(...)]`.
+const DISCRIMINANT_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+];
+
+pub fn quote_enum_item(item: ItemEnum, attr: &Attributes) -> syn::Result<TokenStream> {
+    if !attr.instantiate.is_empty() {
+        return quote_enum_instantiations(item, attr);
+    }
 
-pub fn quote_enum_item(item: ItemEnum) -> syn::Result<TokenStream> {
     reject_generics(
         &item.generics,
-        "Generic enums not supported with `#[cs_bindgen]`",
+        "Generic enums must list their instantiations with `#[cs_bindgen(instantiate(..))]`",
     )?;
 
-    let named_impl = impl_named(&item.ident);
+    let named_impl = impl_named(&item.ident, attr.rename.as_ref());
+
+    // Determine the C# names of any renamed variants. The fields of variants can't be
+    // renamed, since they're generated with the same names as the Rust fields.
+    let variant_renames = item
+        .variants
+        .iter()
+        .map(|variant| {
+            for field in &variant.fields {
+                let attrs = member_attrs(&field.attrs)?;
+                if let Some(rename) = &attrs.rename {
+                    return Err(Error::new_spanned(
+                        rename,
+                        "Renaming the fields of enum variants is not supported with `#[cs_bindgen]`",
+                    ));
+                }
+
+                if let Some(skip) = &attrs.skip {
+                    return Err(Error::new_spanned(
+                        skip,
+                        "Skipping the fields of enum variants is not supported with `#[cs_bindgen]`",
+                    ));
+                }
+            }
+
+            let attrs = member_attrs(&variant.attrs)?;
+            if let Some(skip) = &attrs.skip {
+                return Err(Error::new_spanned(
+                    skip,
+                    "Enum variants can't be skipped with `#[cs_bindgen]`",
+                ));
+            }
+
+            Ok(attrs.rename)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // Enums with custom conversion functions are passed to C# as their raw value, so
+    // the variants aren't exported.
+    if let Some(custom) = CustomMarshal::from_attrs(attr)? {
+        if let Some(rename) = variant_renames.iter().flatten().next() {
+            return Err(Error::new_spanned(
+                rename,
+                "Variants of types with custom conversion functions aren't exported, so they can't be renamed",
+            ));
+        }
+
+        let docs = doc_comment(&item.attrs);
+        return Ok(custom::quote_custom_type(
+            &item.ident,
+            &custom,
+            attr.rename.as_ref(),
+            docs.as_deref(),
+        ));
+    }
 
     // Derive `Describe` for the enum.
     //
     // TODO: Move this into a dedicated derive macro for schematic.
-    let describe_impl = quote_describe_impl(&item)?;
+    let describe_impl = quote_describe_impl(&item, &variant_renames)?;
+    let docs = doc_comment(&item.attrs);
+    let variant_docs = item
+        .variants
+        .iter()
+        .map(|variant| doc_comment(&variant.attrs))
+        .collect::<Vec<_>>();
+
+    // Marshaled enums are serialized as a whole, so the shape of the variants doesn't
+    // affect the bindings.
+    if let Some(format) = attr.marshal_format()? {
+        let marshal_impls = marshal::quote_marshal_impls(&item.ident, format);
+        let describe_fn = describe_named_type(
+            &item.ident,
+            BindingStyle::Marshal(format),
+            &StdImpls::default(),
+            &[],
+            docs.as_deref(),
+            &variant_docs,
+            None,
+            false,
+        );
+
+        return Ok(quote! {
+            #named_impl
+            #describe_impl
+            #marshal_impls
+            #describe_fn
+        });
+    }
 
     // Check the variants to determine if we're dealing with a C-style enum or one that
-    // carries additional data.
+    // carries additional data. Fields that aren't marshaled (e.g. `PhantomData`) don't
+    // count as data.
     let has_fields = item
         .variants
         .iter()
-        .any(|variant| !variant.fields.is_empty());
+        .any(|variant| !value::is_all_omitted(&variant.fields));
+
+    // Non-exhaustive enums get an extra case in C# for variants that were added after
+    // the bindings were generated, which can't have the same name as a Rust variant.
+    let is_non_exhaustive = item
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("non_exhaustive"));
+    if is_non_exhaustive {
+        let unknown_name = if has_fields {
+            "UnknownVariant"
+        } else {
+            "Unknown"
+        };
+        for (variant, rename) in item.variants.iter().zip(&variant_renames) {
+            let conflicts = match rename {
+                Some(rename) => rename.value() == unknown_name,
+                None => variant.ident == unknown_name,
+            };
+
+            if conflicts {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    format!(
+                        "`#[non_exhaustive]` enums can't have a variant named `{}`, since it's \
+                         used in C# for unrecognized variants",
+                        unknown_name,
+                    ),
+                ));
+            }
+        }
+    }
+
+    // The discriminant type only affects the bindings for C-like enums, since
+    // data-carrying enums are marshaled with the index of the variant.
+    let discriminant = if has_fields {
+        None
+    } else {
+        discriminant_repr(&item.attrs)?
+    };
 
     let bindings = if has_fields {
         quote_complex_enum(&item)?
     } else {
-        quote_simple_enum(&item)?
+        quote_simple_enum(&item, discriminant.as_ref())?
     };
 
     // Export a function that describes the exported type.
     let ident = &item.ident;
-    let describe_fn = describe_named_type(&ident, BindingStyle::Value);
+    let describe_fn = describe_named_type(
+        &ident,
+        BindingStyle::Value,
+        &StdImpls::default(),
+        &[],
+        docs.as_deref(),
+        &variant_docs,
+        discriminant.as_ref(),
+        is_non_exhaustive,
+    );
 
     Ok(quote! {
         #named_impl
@@ -44,12 +193,61 @@ pub fn quote_enum_item(item: ItemEnum) -> syn::Result<TokenStream> {
     })
 }
 
-fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
+/// Generates the bindings for each listed instantiation of a generic enum.
+///
+/// Each instantiation is exported as a separate type through a type alias, the same
+/// as for generic structs. See the `generic` module for details.
+fn quote_enum_instantiations(item: ItemEnum, attr: &Attributes) -> syn::Result<TokenStream> {
+    if let Some(rename) = &attr.rename {
+        return Err(Error::new_spanned(
+            rename,
+            "`rename` is not supported on generic enums, since each instantiation is exported as a separate type",
+        ));
+    }
+
+    Instantiation::group(&item.generics, attr.instantiate.clone())?
+        .into_iter()
+        .map(|instantiation| {
+            let ident = &item.ident;
+            let vis = &item.vis;
+            let alias = instantiation.alias_ident(ident);
+            let types = instantiation.types();
+
+            let instance = ItemEnum {
+                ident: alias.clone(),
+                generics: Generics::default(),
+                variants: instantiation.apply_variants(&item.variants)?,
+                ..item.clone()
+            };
+            let attr = Attributes {
+                instantiate: Vec::new(),
+                rename: Some(LitStr::new(&instantiation.type_name(ident), ident.span())),
+                ..attr.clone()
+            };
+            let bindings = quote_enum_item(instance, &attr)?;
+
+            Ok(quote! {
+                #[doc(hidden)]
+                #[allow(bad_style)]
+                #vis type #alias = #ident<#( #types ),*>;
+
+                #bindings
+            })
+        })
+        .collect()
+}
+
+/// Generates the bindings for a C-like enum.
+///
+/// The enum is marshaled as its discriminant, using `discriminant` as the type of the
+/// discriminant if the enum specified one with `#[repr(...)]`.
+fn quote_simple_enum(item: &ItemEnum, discriminant: Option<&Ident>) -> syn::Result<TokenStream> {
     let ident = &item.ident;
 
-    // TODO: Check for a `#[repr(...)]` attribute and handle alternate types for the
-    // discriminant.
-    let discriminant_ty = quote! { isize };
+    let discriminant_ty = match discriminant {
+        Some(discriminant) => discriminant.into_token_stream(),
+        None => quote! { isize },
+    };
 
     let const_ident = item
         .variants
@@ -98,8 +296,17 @@ fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         );
         let variant_ident = &variant.ident;
 
+        // NOTE: Variants of a C-like enum can still have fields that aren't marshaled,
+        // which are initialized with their default value.
+        let fields = value::from_abi_fields(&variant.fields, &quote! { abi });
+        let braces = match &variant.fields {
+            Fields::Named { .. } => quote! { { #fields } },
+            Fields::Unnamed { .. } => quote! { ( #fields ) },
+            Fields::Unit => quote! {},
+        };
+
         quote! {
-            #const_ident => #ident::#variant_ident
+            #const_ident => #ident::#variant_ident #braces
         }
     });
 
@@ -113,6 +320,8 @@ fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
     let index_fn = quote_index_fn(&ident);
     let convert_list_fn = quote_convert_list_fn(&ident);
     let drop_vec_fn = quote_vec_drop_fn(&ident);
+    let size_fn = quote_size_fn(&ident);
+    let abi_mut_impl = quote_abi_mut_impl(&ident);
 
     Ok(quote! {
         #(
@@ -128,7 +337,7 @@ fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
             fn as_abi(&self) -> Self::Abi {
                 match self {
                     #(
-                        Self::#variant_name => #const_ident,
+                        Self::#variant_name { .. } => #const_ident,
                     )*
                 }
             }
@@ -136,7 +345,7 @@ fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
             fn into_abi(self) -> Self::Abi {
                 match self {
                     #(
-                        Self::#variant_name => #const_ident,
+                        Self::#variant_name { .. } => #const_ident,
                     )*
                 }
             }
@@ -154,10 +363,55 @@ fn quote_simple_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         #index_fn
         #convert_list_fn
         #drop_vec_fn
+        #size_fn
+        #abi_mut_impl
     })
 }
 
+/// Returns the integer type specified in the `#[repr(...)]` attribute of an enum, if
+/// any.
+///
+/// Other representation hints (e.g. `C`) are ignored, since they don't affect the
+/// type of the discriminant.
+fn discriminant_repr(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let hints = attr.parse_args_with(Punctuated::<Ident, Comma>::parse_terminated)?;
+        result = hints
+            .into_iter()
+            .find(|hint| DISCRIMINANT_TYPES.iter().any(|ty| hint == ty))
+            .or(result);
+    }
+
+    Ok(result)
+}
+
+/// Generates the destructuring pattern for the fields of a variant.
+///
+/// Fields are bound to their raw field ident, except for omitted fields which aren't
+/// marshaled and so are ignored.
+fn quote_destructure(fields: &Fields) -> TokenStream {
+    let bindings = fields.iter().enumerate().map(|(index, field)| {
+        let raw_ident = value::raw_field_ident(index, field);
+        match (&field.ident, value::is_omitted(field)) {
+            (Some(ident), true) => quote! { #ident: _ },
+            (None, true) => quote! { _ },
+            _ => raw_ident.into_token_stream(),
+        }
+    });
+
+    match fields {
+        Fields::Named { .. } => quote! { { #( #bindings, )* } },
+        Fields::Unnamed { .. } => quote! { ( #( #bindings, )* ) },
+        Fields::Unit => quote! {},
+    }
+}
+
 fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
+    for field in item.variants.iter().flat_map(|variant| &variant.fields) {
+        check_type(&field.ty, TypePosition::Field)?;
+    }
+
     let ident = &item.ident;
     let abi_union_ty = format_binding_ident!(ident);
 
@@ -169,9 +423,9 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         let abi_ident = format_ident!("{}__{}", abi_union_ty, variant.ident);
 
         // NOTE: No binding struct is generated for unit variants or struct/tuple-like
-        // variants that don't actually contain data, since only the discriminant is
-        // needed to restore it.
-        if variant.fields.is_empty() {
+        // variants that don't actually contain data (including variants whose fields are
+        // all `PhantomData`), since only the discriminant is needed to restore it.
+        if value::is_all_omitted(&variant.fields) {
             return None;
         }
 
@@ -181,7 +435,7 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
     let abi_union_fields = item.variants.iter().filter_map(|variant| {
         // NOTE: No binding struct is generated for unit variants or empty variants, since only
         // the discriminant is needed to restore it.
-        if variant.fields == Fields::Unit || variant.fields.is_empty() {
+        if value::is_all_omitted(&variant.fields) {
             return None;
         }
 
@@ -198,22 +452,11 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         let discriminant = Literal::usize_unsuffixed(index);
         let abi_ident = format_ident!("{}__{}", abi_union_ty, variant.ident);
 
-        let field_idents = variant
-            .fields
-            .iter()
-            .enumerate()
-            .map(|(index, field)| value::raw_field_ident(index, field));
-
-        // Generate the destructuring expression for the fields of the variant.
-        let destructure = match &variant.fields {
-            Fields::Named { .. } => quote! { { #( #field_idents, )* } },
-            Fields::Unnamed { .. } => quote! { ( #( #field_idents, )* ) },
-            Fields::Unit => quote! {},
-        };
+        let destructure = quote_destructure(&variant.fields);
 
         // For empty variants use `RawEnum::unit` to create an enum representation with just
         // a discriminant.
-        if variant.fields.is_empty() {
+        if value::is_all_omitted(&variant.fields) {
             return quote! {
                 Self::#variant_ident #destructure => cs_bindgen::abi::RawEnum::unit(#discriminant)
             };
@@ -240,22 +483,11 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         let discriminant = Literal::usize_unsuffixed(index);
         let abi_ident = format_ident!("{}__{}", abi_union_ty, variant.ident);
 
-        let field_idents = variant
-            .fields
-            .iter()
-            .enumerate()
-            .map(|(index, field)| value::raw_field_ident(index, field));
-
-        // Generate the destructuring expression for the fields of the variant.
-        let destructure = match &variant.fields {
-            Fields::Named { .. } => quote! { { #( #field_idents, )* } },
-            Fields::Unnamed { .. } => quote! { ( #( #field_idents, )* ) },
-            Fields::Unit => quote! {},
-        };
+        let destructure = quote_destructure(&variant.fields);
 
         // For empty variants use `RawEnum::unit` to create an enum representation with just
         // a discriminant.
-        if variant.fields.is_empty() {
+        if value::is_all_omitted(&variant.fields) {
             return quote! {
                 Self::#variant_ident #destructure => cs_bindgen::abi::RawEnum::unit(#discriminant)
             };
@@ -303,6 +535,15 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
     let index_fn = quote_index_fn(ident);
     let convert_list_fn = quote_convert_list_fn(ident);
     let vec_drop_fn = quote_vec_drop_fn(ident);
+    let size_fn = quote_size_fn(ident);
+    let abi_mut_impl = quote_abi_mut_impl(ident);
+    let list_fns = list::quote_list_fns(
+        &ident.to_string(),
+        item.variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .map(|field| &field.ty),
+    );
 
     Ok(quote! {
         #[repr(C)]
@@ -346,20 +587,34 @@ fn quote_complex_enum(item: &ItemEnum) -> syn::Result<TokenStream> {
         #index_fn
         #convert_list_fn
         #vec_drop_fn
+        #size_fn
+        #abi_mut_impl
+        #list_fns
     })
 }
 
-fn quote_describe_impl(item: &ItemEnum) -> syn::Result<TokenStream> {
+/// Generates the `Describe` impl for an enum.
+///
+/// `variant_renames` has the new name for each variant of the enum that was renamed,
+/// which is used in place of the original variant name.
+fn quote_describe_impl(
+    item: &ItemEnum,
+    variant_renames: &[Option<LitStr>],
+) -> syn::Result<TokenStream> {
     let ident = &item.ident;
 
     // Iterate over the enum variants and generate the describe logic for each one.
-    let describe_variants = item.variants.iter().map(|variant| {
-        let variant_name = variant.ident.to_string();
+    let describe_variants = item.variants.iter().zip(variant_renames).map(|(variant, rename)| {
+        let variant_name = match rename {
+            Some(rename) => rename.value(),
+            None => variant.ident.to_string(),
+        };
 
         match &variant.fields {
             // Unit variants are described with a single call to `describe_unit_variant`. We
             // also need to pass in the value of the discriminant, if one was specified.
-            Fields::Unit => {
+            // Variants without any marshaled fields are described as unit variants too.
+            _ if value::is_all_omitted(&variant.fields) => {
                 let discriminant = match &variant.discriminant {
                     Some((_, expr)) => quote! { Some((#expr).into()) },
                     None => quote! { None },
@@ -377,8 +632,12 @@ fn quote_describe_impl(item: &ItemEnum) -> syn::Result<TokenStream> {
             // For tuple variants, we generate initially call `start_tuple_variant` and then
             // generate a call to `describe_element` for each element in the tuple.
             Fields::Unnamed(fields) => {
-                let describe_elements = fields.unnamed.iter().map(|field| {
-                    let ty = &field.ty;
+                let described_fields = fields
+                    .unnamed
+                    .iter()
+                    .filter(|field| !value::is_omitted(field));
+                let describe_elements = described_fields.map(|field| {
+                    let ty = described_field_ty(&field.ty);
 
                     quote! {
                         cs_bindgen::shared::schematic::DescribeTupleVariant::describe_element::<#ty>(
@@ -405,9 +664,13 @@ fn quote_describe_impl(item: &ItemEnum) -> syn::Result<TokenStream> {
             // For struct variants, we generate initially call `start_struct_variant` and
             // then generate a call to `describe_field` for each field.
             Fields::Named(fields) => {
-                let describe_fields = fields.named.iter().map(|field| {
+                let described_fields = fields
+                    .named
+                    .iter()
+                    .filter(|field| !value::is_omitted(field));
+                let describe_fields = described_fields.map(|field| {
                     let name = field.ident.as_ref().unwrap().to_string();
-                    let ty = &field.ty;
+                    let ty = described_field_ty(&field.ty);
 
                     quote! {
                         cs_bindgen::shared::schematic::DescribeStructVariant::describe_field::<#ty>(
@@ -431,6 +694,8 @@ fn quote_describe_impl(item: &ItemEnum) -> syn::Result<TokenStream> {
                     }
                 }
             }
+
+            Fields::Unit => unreachable!("Unit variants have already been handled"),
         }
     });
 
@@ -445,7 +710,7 @@ fn quote_describe_impl(item: &ItemEnum) -> syn::Result<TokenStream> {
                 E: cs_bindgen::shared::schematic::Describer,
             {
                 let mut describer = describer.describe_enum(
-                    cs_bindgen::shared::schematic::type_name!(#ident),
+                    <Self as cs_bindgen::shared::Named>::type_name(),
                 )?;
                 #( #describe_variants )*
                 cs_bindgen::shared::schematic::DescribeEnum::end(describer)
@@ -1,10 +1,19 @@
 //! Helper functions for generating raw bindings and descriptor functions.
 
-use proc_macro2::TokenStream;
+use crate::{
+    attr::param_default,
+    support::{check_type, TypePosition},
+};
+use proc_macro2::{Span, TokenStream};
 use quote::*;
-use syn::{punctuated::Punctuated, token::Comma, *};
+use syn::{
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::{Add, Comma},
+    *,
+};
 
-type FnInput = (Ident, Box<Type>);
+pub type FnInput = (Ident, Box<Type>);
 
 /// Processes the raw list of arguments into a format suitable for use in code
 /// generation.
@@ -33,19 +42,238 @@ pub fn extract_inputs(inputs: Punctuated<FnArg, Comma>) -> syn::Result<Vec<FnInp
                 _ => format_ident!("__arg{}", index),
             };
 
+            validate_arg_type(&arg.ty)?;
+
             Ok((ident, arg.ty))
         })
         .collect()
 }
 
+/// Generates an error for argument types that can never be supported.
+fn validate_arg_type(ty: &Type) -> syn::Result<()> {
+    match ArgStyle::from_type(ty) {
+        ArgStyle::Callback(_, args) if args.inputs.len() > MAX_CALLBACK_ARGS => {
+            Err(Error::new_spanned(
+                args,
+                format!(
+                    "Callbacks with more than {} arguments are not supported with `#[cs_bindgen]`",
+                    MAX_CALLBACK_ARGS,
+                ),
+            ))
+        }
+
+        _ => check_type(ty, TypePosition::Argument),
+    }
+}
+
+/// Generates an error for return types that can never be supported.
+pub fn validate_output(output: &ReturnType) -> syn::Result<()> {
+    match output {
+        ReturnType::Default => Ok(()),
+        ReturnType::Type(_, ty) => check_type(ty, TypePosition::Return),
+    }
+}
+
+/// Returns the declared return type, or `None` if the function returns `()` implicitly.
+pub fn output_type(output: &ReturnType) -> Option<&Type> {
+    match output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => Some(ty),
+    }
+}
+
+/// Replaces the error type of the returned `Result` with `String`, for functions
+/// exported with `#[cs_bindgen(error = "display")]`.
+///
+/// The call to the function is wrapped with [`quote_display_error`] so that the error
+/// is converted to its `Display` string before it's passed to C#.
+///
+/// [`quote_display_error`]: fn.quote_display_error.html
+pub fn display_error_output(output: &ReturnType, error: &LitStr) -> syn::Result<ReturnType> {
+    let mut output = output.clone();
+    let err_ty = match &mut output {
+        ReturnType::Type(_, ty) => result_err_ty(ty),
+        ReturnType::Default => None,
+    };
+
+    match err_ty {
+        Some(err_ty) => {
+            *err_ty = parse_quote! { std::string::String };
+            Ok(output)
+        }
+
+        None => Err(Error::new_spanned(
+            error,
+            "`error = \"display\"` is only supported on functions that return a `Result<T, E>`",
+        )),
+    }
+}
+
+/// Converts the error returned by `invoke` to its `Display` string.
+///
+/// For async functions, `invoke` is the future returned by the function, and the
+/// error is converted once the future completes.
+pub fn quote_display_error(invoke: TokenStream, is_async: bool) -> TokenStream {
+    if is_async {
+        quote! {
+            async move {
+                #invoke.await.map_err(|err| std::string::ToString::to_string(&err))
+            }
+        }
+    } else {
+        quote! {
+            #invoke.map_err(|err| std::string::ToString::to_string(&err))
+        }
+    }
+}
+
+/// Returns the error type of a `Result<T, E>`.
+///
+/// NOTE: Aliases that only take the success type (e.g. `io::Result<T>`) aren't
+/// supported, since the error type isn't known from the signature.
+fn result_err_ty(ty: &mut Type) -> Option<&mut Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last_mut()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Result" {
+        return None;
+    }
+
+    match &mut segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 2 => {
+            match args.args.last_mut()? {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+
+        _ => None,
+    }
+}
+
+/// Generates the value of the `out_params` field for an exported function or method.
+///
+/// The names listed in `out(...)` are mapped to the trailing elements of the returned
+/// tuple, so the function must return a tuple with at least that many elements.
+pub fn quote_out_params(out: &[Ident], signature: &Signature) -> syn::Result<TokenStream> {
+    let first = match out.first() {
+        Some(first) => first,
+        None => return Ok(quote! { Vec::new() }),
+    };
+
+    // The result of an async function is delivered through the returned task, which
+    // has no way to assign `out` parameters.
+    if signature.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            first,
+            "`out` is not supported on async functions",
+        ));
+    }
+
+    match output_type(&signature.output) {
+        Some(Type::Tuple(tuple)) if tuple.elems.len() >= out.len() => {}
+        _ => {
+            return Err(Error::new_spanned(
+                &signature.output,
+                format!(
+                    "`out` requires the function to return a tuple with at least {} elements",
+                    out.len(),
+                ),
+            ))
+        }
+    }
+
+    let names = out.iter().map(|ident| ident.to_string());
+    Ok(quote! { vec![#( #names.into() ),*] })
+}
+
+/// Returns the default value declared with `#[cs_bindgen(default = ...)]` for each of
+/// the function's arguments, in the same order as the arguments returned by
+/// [`extract_inputs`].
+///
+/// C# requires optional parameters to come after all required parameters, so an
+/// argument with a default value can only be followed by other arguments with a
+/// default value or by `Option` arguments, which default to `null`. For the same
+/// reason, default values can't be combined with `out` parameters.
+///
+/// [`extract_inputs`]: fn.extract_inputs.html
+pub fn arg_defaults(
+    inputs: &Punctuated<FnArg, Comma>,
+    out: &[Ident],
+) -> syn::Result<Vec<Option<Expr>>> {
+    let mut defaults = Vec::new();
+    let mut first_default = None;
+    for arg in inputs {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(_) => continue,
+        };
+
+        let default = param_default(&arg.attrs)?;
+        match &default {
+            Some(default) if !out.is_empty() => {
+                return Err(Error::new_spanned(
+                    default,
+                    "Default values can't be combined with `out` parameters, since C# requires optional parameters to come last",
+                ));
+            }
+
+            Some(default) => {
+                first_default.get_or_insert(default.clone());
+            }
+
+            None if first_default.is_some() && !is_option(&arg.ty) => {
+                return Err(Error::new_spanned(
+                    arg,
+                    "Arguments following an argument with a default value must also have a default value or be an `Option`",
+                ));
+            }
+
+            None => {}
+        }
+
+        defaults.push(default);
+    }
+
+    Ok(defaults)
+}
+
+/// Generates the value of the `default` field for an argument.
+pub fn quote_arg_default(default: Option<&Expr>) -> TokenStream {
+    match default {
+        Some(default) => quote_spanned! {default.span()=>
+            Some(cs_bindgen::shared::ConstValue::from(#default))
+        },
+        None => quote! { None },
+    }
+}
+
+/// Returns `true` if `ty` is an `Option`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 /// Generates the declaration for an argument to the binding function.
 ///
 /// This function takes the ident and type of an argument in the original function
 /// and generates the `ident: type` declaration for the corresponding argument in
 /// the binding function. The ident is reused directly, and `Abi` associated type
 /// on the `Abi` impl for `ty` is used as the type of the generated argument.
+///
+/// The declaration is spanned to `ty`, so that the error for a type that doesn't
+/// implement `Abi` points at the type in the original function.
 pub fn quote_binding_inputs<T: ToTokens>(ident: &Ident, ty: T) -> TokenStream {
-    quote! {
+    quote_spanned! {ty.span()=>
         #ident: <#ty as cs_bindgen::abi::Abi>::Abi
     }
 }
@@ -56,3 +284,442 @@ pub fn quote_input_conversion(ident: &Ident) -> TokenStream {
         let #ident = cs_bindgen::abi::Abi::from_abi(#ident);
     }
 }
+
+/// Names of the primitive types that can be borrowed directly as a slice.
+///
+/// Slices of any other type need their elements to be converted into a temporary
+/// `Vec`, see [`ArgStyle`].
+///
+/// [`ArgStyle`]: enum.ArgStyle.html
+const PRIMITIVE_SLICE_ELEMENTS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+];
+
+/// The maximum number of arguments supported for a callback argument.
+///
+/// Matches the number of `call*` methods on `cs_bindgen::abi::RawCallback`.
+pub const MAX_CALLBACK_ARGS: usize = 8;
+
+/// How a callback argument is declared.
+enum CallbackKind {
+    /// `impl Fn(..)`, or any of the other `Fn*` traits.
+    Impl,
+
+    /// `&dyn Fn(..)`.
+    Ref,
+
+    /// `&mut dyn FnMut(..)`.
+    RefMut,
+}
+
+/// How an argument is passed from C# to the binding function.
+///
+/// Most arguments are converted directly with `Abi::from_abi`. Borrowed arguments
+/// that can't point directly into C# memory need to first be converted into
+/// temporary storage that lives for the duration of the call, and the argument
+/// then borrows from that storage.
+///
+/// Note that the check is syntactic, so e.g. a type alias for `&str` will be treated
+/// as a regular argument.
+enum ArgStyle<'a> {
+    /// The argument is converted with `Abi::from_abi`.
+    Direct,
+
+    /// A slice of non-primitive elements, i.e. `&[T]`. The raw elements are converted
+    /// into a temporary `Vec<T>`.
+    ConvertedSlice(&'a Type),
+
+    /// A string slice, i.e. `&str`. C# passes a view of the UTF-16 string data, which
+    /// is decoded into a temporary `StrArg`.
+    Str,
+
+    /// A mutable reference, i.e. `&mut T`. The raw argument is converted into temporary
+    /// storage with `AbiMut::from_abi_mut`, and the argument borrows the storage. Values
+    /// marshaled by value are written back to C# when the storage is dropped.
+    RefMut(&'a Type),
+
+    /// A callback, i.e. `impl Fn(..)`, `&dyn Fn(..)`, or `&mut dyn FnMut(..)`. C# passes
+    /// a `RawCallback`, which is wrapped in a closure that borrows it for the duration
+    /// of the call.
+    Callback(CallbackKind, &'a ParenthesizedGenericArguments),
+
+    /// An `impl Trait` argument for an exported trait. C# passes a trait object, which
+    /// is converted into a `&dyn Trait`. Exported traits are implemented for
+    /// `&dyn Trait`, so the trait object can be passed as the `impl Trait` argument.
+    ImplTrait(&'a Path),
+
+    /// A boxed trait object for an exported trait, i.e. `Box<dyn Trait>`. C# passes a
+    /// `RawManaged` for a C# object implementing the trait, which is wrapped in a
+    /// `Box<dyn Trait>` that takes ownership of the object.
+    BoxDyn(&'a Path),
+}
+
+impl<'a> ArgStyle<'a> {
+    fn from_type(ty: &'a Type) -> Self {
+        let elem = match ty {
+            Type::ImplTrait(impl_trait) => {
+                if let Some(args) = fn_trait_args(&impl_trait.bounds) {
+                    return ArgStyle::Callback(CallbackKind::Impl, args);
+                }
+
+                return impl_trait
+                    .bounds
+                    .iter()
+                    .find_map(|bound| match bound {
+                        TypeParamBound::Trait(bound) => Some(ArgStyle::ImplTrait(&bound.path)),
+                        _ => None,
+                    })
+                    .unwrap_or(ArgStyle::Direct);
+            }
+
+            Type::Reference(reference) => match &*reference.elem {
+                Type::TraitObject(object) => {
+                    let kind = if reference.mutability.is_some() {
+                        CallbackKind::RefMut
+                    } else {
+                        CallbackKind::Ref
+                    };
+
+                    return match fn_trait_args(&object.bounds) {
+                        Some(args) => ArgStyle::Callback(kind, args),
+                        None => ArgStyle::Direct,
+                    };
+                }
+
+                elem if reference.mutability.is_none() => elem,
+                elem => return ArgStyle::RefMut(elem),
+            },
+
+            Type::Path(path) => {
+                return match boxed_trait_object(path) {
+                    Some(path) => ArgStyle::BoxDyn(path),
+                    None => ArgStyle::Direct,
+                };
+            }
+
+            _ => return ArgStyle::Direct,
+        };
+
+        match elem {
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => ArgStyle::Str,
+
+            Type::Slice(slice) => {
+                let is_primitive = match &*slice.elem {
+                    Type::Path(path) => {
+                        path.qself.is_none()
+                            && PRIMITIVE_SLICE_ELEMENTS
+                                .iter()
+                                .any(|name| path.path.is_ident(name))
+                    }
+                    _ => false,
+                };
+
+                if is_primitive {
+                    ArgStyle::Direct
+                } else {
+                    ArgStyle::ConvertedSlice(&slice.elem)
+                }
+            }
+
+            _ => ArgStyle::Direct,
+        }
+    }
+}
+
+/// Returns the parenthesized arguments of the `Fn`, `FnMut`, or `FnOnce` bound in
+/// `bounds`, if any.
+fn fn_trait_args(
+    bounds: &Punctuated<TypeParamBound, Add>,
+) -> Option<&ParenthesizedGenericArguments> {
+    bounds.iter().find_map(|bound| {
+        let bound = match bound {
+            TypeParamBound::Trait(bound) => bound,
+            _ => return None,
+        };
+
+        let segment = bound.path.segments.last()?;
+        let is_fn_trait =
+            segment.ident == "Fn" || segment.ident == "FnMut" || segment.ident == "FnOnce";
+        match &segment.arguments {
+            PathArguments::Parenthesized(args) if is_fn_trait => Some(args),
+            _ => None,
+        }
+    })
+}
+
+/// Returns the path of the trait if `path` is a `Box<dyn Trait>`.
+///
+/// Only trait objects with a single trait bound are matched, since the trait object
+/// created from a C# object doesn't implement any auto traits like `Send`.
+fn boxed_trait_object(path: &TypePath) -> Option<&Path> {
+    if path.qself.is_some() {
+        return None;
+    }
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Box" {
+        return None;
+    }
+
+    let object = match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(Type::TraitObject(object)) => object,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let mut traits = object.bounds.iter().filter_map(|bound| match bound {
+        TypeParamBound::Trait(bound) => Some(&bound.path),
+        _ => None,
+    });
+
+    match (traits.next(), traits.next()) {
+        (Some(path), None) if fn_trait_args(&object.bounds).is_none() => Some(path),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `ty` is passed by value, such that it can be passed from Rust to
+/// C# as an argument to a callback or a method of a C# object.
+pub fn is_owned_arg(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(_) | Type::ImplTrait(_) | Type::TraitObject(_) | Type::BareFn(_) => false,
+        Type::Path(path) => boxed_trait_object(path).is_none(),
+        _ => true,
+    }
+}
+
+/// Returns the return type of a callback, using `()` if no return type is declared.
+fn callback_output(args: &ParenthesizedGenericArguments) -> TokenStream {
+    match &args.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    }
+}
+
+/// Generates the declaration for an argument to the binding function, taking into
+/// account arguments that need special handling.
+///
+/// See [`quote_binding_inputs`] for more information.
+///
+/// [`quote_binding_inputs`]: fn.quote_binding_inputs.html
+pub fn quote_arg_binding_input(ident: &Ident, ty: &Type) -> TokenStream {
+    match ArgStyle::from_type(ty) {
+        ArgStyle::Direct => quote_binding_inputs(ident, ty),
+
+        ArgStyle::ConvertedSlice(element) => quote! {
+            #ident: cs_bindgen::abi::RawSlice<<#element as cs_bindgen::abi::Abi>::Abi>
+        },
+
+        ArgStyle::Str => quote! {
+            #ident: cs_bindgen::abi::RawSlice<u16>
+        },
+
+        ArgStyle::RefMut(elem) => quote! {
+            #ident: <#elem as cs_bindgen::abi::AbiMut>::Abi
+        },
+
+        ArgStyle::Callback(..) => quote! {
+            #ident: cs_bindgen::abi::RawCallback
+        },
+
+        ArgStyle::ImplTrait(path) => quote_binding_inputs(ident, quote! { &dyn #path }),
+
+        ArgStyle::BoxDyn(_) => quote! {
+            #ident: cs_bindgen::abi::RawManaged
+        },
+    }
+}
+
+/// Generates the conversion of a raw binding argument, taking into account arguments
+/// that need special handling.
+pub fn quote_arg_conversion(ident: &Ident, ty: &Type) -> TokenStream {
+    let storage = format_ident!("__storage_{}", ident);
+    match ArgStyle::from_type(ty) {
+        ArgStyle::Direct => quote_input_conversion(ident),
+
+        ArgStyle::ConvertedSlice(element) => quote! {
+            let #storage = cs_bindgen::abi::convert_slice::<#element>(#ident);
+            let #ident = #storage.as_slice();
+        },
+
+        ArgStyle::Str => quote! {
+            let #storage = cs_bindgen::abi::StrArg::from_utf16(#ident);
+            let #ident = #storage.as_str();
+        },
+
+        ArgStyle::RefMut(elem) => quote! {
+            let mut #storage = <#elem as cs_bindgen::abi::AbiMut>::from_abi_mut(#ident);
+            let #ident = &mut *#storage;
+        },
+
+        // NOTE: The closure borrows the raw callback rather than moving it, which ensures
+        // that the closure can't outlive the call. The C# delegate is only kept alive for
+        // the duration of the call, so it would be invalid to call it afterwards.
+        ArgStyle::Callback(kind, args) => {
+            let closure_ident = format_ident!("__callback_{}", ident);
+            let arg_ident = (0..args.inputs.len())
+                .map(|index| format_ident!("__callback_arg{}", index))
+                .collect::<Vec<_>>();
+            let input_ty = args.inputs.iter();
+            let output_ty = callback_output(args);
+            let call = format_ident!("call{}", args.inputs.len());
+
+            let closure = quote! {
+                |#( #arg_ident: #input_ty ),*| -> #output_ty {
+                    #storage.#call(#( #arg_ident ),*)
+                }
+            };
+
+            match kind {
+                CallbackKind::Impl => quote! {
+                    let #storage = #ident;
+                    let #ident = #closure;
+                },
+
+                CallbackKind::Ref => quote! {
+                    let #storage = #ident;
+                    let #closure_ident = #closure;
+                    let #ident = &#closure_ident;
+                },
+
+                CallbackKind::RefMut => quote! {
+                    let #storage = #ident;
+                    let mut #closure_ident = #closure;
+                    let #ident = &mut #closure_ident;
+                },
+            }
+        }
+
+        ArgStyle::ImplTrait(path) => quote! {
+            let #ident = <&dyn #path as cs_bindgen::abi::Abi>::from_abi(#ident);
+        },
+
+        ArgStyle::BoxDyn(path) => quote! {
+            let #ident = <dyn #path as cs_bindgen::abi::FromManaged>::from_managed(#ident);
+        },
+    }
+}
+
+/// Generates the expression for describing the repr of an argument.
+pub fn quote_arg_repr(ty: &Type) -> TokenStream {
+    match ArgStyle::from_type(ty) {
+        ArgStyle::ConvertedSlice(element) => quote! {
+            cs_bindgen::shared::Repr::Slice(Box::new(<#element as cs_bindgen::abi::Abi>::repr()))
+        },
+
+        ArgStyle::Callback(_, args) => {
+            let input_ty = args.inputs.iter();
+            let output_ty = callback_output(args);
+            quote! {
+                cs_bindgen::shared::Repr::Fn {
+                    inputs: vec![#( <#input_ty as cs_bindgen::abi::Abi>::repr(), )*],
+                    output: Box::new(<#output_ty as cs_bindgen::abi::Abi>::repr()),
+                }
+            }
+        }
+
+        ArgStyle::RefMut(elem) => quote! { <#elem as cs_bindgen::abi::AbiMut>::repr() },
+
+        ArgStyle::ImplTrait(path) => quote! { <&dyn #path as cs_bindgen::abi::Abi>::repr() },
+
+        ArgStyle::BoxDyn(path) => quote! {
+            cs_bindgen::shared::Repr::BoxDyn(<dyn #path as cs_bindgen::shared::Named>::type_name())
+        },
+
+        ArgStyle::Direct | ArgStyle::Str => quote! { <#ty as cs_bindgen::abi::Abi>::repr() },
+    }
+}
+
+/// Returns the item type if `ty` is an iterator, i.e. `impl Iterator<Item = T>`.
+///
+/// Iterators are returned to C# as a `RawIter`, which C# exposes as an
+/// `IEnumerable<T>` that pulls items from Rust as it's enumerated.
+fn iterator_item(ty: &Type) -> Option<&Type> {
+    let impl_trait = match ty {
+        Type::ImplTrait(impl_trait) => impl_trait,
+        _ => return None,
+    };
+
+    impl_trait.bounds.iter().find_map(|bound| {
+        let bound = match bound {
+            TypeParamBound::Trait(bound) => bound,
+            _ => return None,
+        };
+
+        let segment = bound.path.segments.last()?;
+        if segment.ident != "Iterator" {
+            return None;
+        }
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Binding(binding) if binding.ident == "Item" => Some(&binding.ty),
+                _ => None,
+            }),
+            _ => None,
+        }
+    })
+}
+
+/// Generates the return type declaration for a binding function.
+pub fn quote_return_decl(output: &ReturnType) -> TokenStream {
+    match output {
+        ReturnType::Default => quote! {},
+
+        ReturnType::Type(_, ty) if iterator_item(ty).is_some() => quote! {
+            -> cs_bindgen::abi::RawIter
+        },
+
+        // NOTE: The raw representation of a reference doesn't depend on its lifetime, so
+        // borrowed return values use `'static` in order to avoid having to infer the
+        // elided lifetime from the arguments of the binding function.
+        ReturnType::Type(_, return_type) => match &**return_type {
+            Type::Reference(reference) if reference.lifetime.is_none() => {
+                let mut reference = reference.clone();
+                reference.lifetime = Some(Lifetime::new("'static", Span::call_site()));
+                quote_spanned! {return_type.span()=>
+                    -> <#reference as cs_bindgen::abi::Abi>::Abi
+                }
+            }
+
+            _ => quote_spanned! {return_type.span()=>
+                -> <#return_type as cs_bindgen::abi::Abi>::Abi
+            },
+        },
+    }
+}
+
+/// Generates the conversion of the value returned by `invoke` into its raw
+/// representation.
+pub fn quote_return_conversion(output: &ReturnType, invoke: TokenStream) -> TokenStream {
+    match output {
+        ReturnType::Default => invoke,
+
+        ReturnType::Type(_, ty) if iterator_item(ty).is_some() => quote! {
+            cs_bindgen::abi::RawIter::new(#invoke)
+        },
+
+        ReturnType::Type(..) => quote! { cs_bindgen::abi::Abi::into_abi(#invoke) },
+    }
+}
+
+/// Generates the expression for describing the output of a function.
+pub fn quote_output_repr(output: &ReturnType) -> TokenStream {
+    match output {
+        ReturnType::Default => quote! { None },
+
+        ReturnType::Type(_, ty) => match iterator_item(ty) {
+            Some(item) => quote! {
+                Some(cs_bindgen::shared::Repr::Iter(Box::new(
+                    <#item as cs_bindgen::abi::Abi>::repr(),
+                )))
+            },
+
+            None => quote! {
+                Some(<#ty as cs_bindgen::abi::Abi>::repr())
+            },
+        },
+    }
+}
@@ -0,0 +1,386 @@
+//! Support for exporting generic functions, structs, enums, and impl blocks with an
+//! explicit list of instantiations.
+//!
+//! A generic function can't be exported directly, since each set of concrete type
+//! arguments needs its own binding function. Instead, the instantiations to export
+//! are listed in the attribute, e.g. `#[cs_bindgen(instantiate(T = Tile, T = u32))]`,
+//! and a separate binding is generated for each one. Every instantiation is
+//! described using the name of the original function, so they're exposed in C# as
+//! overloads of a single wrapper function.
+//!
+//! Each instantiation of a generic struct or enum is exported as a separate type. The
+//! macro declares a type alias for the instantiation, e.g. `History__Tile` for
+//! `History<Tile>`, and generates the bindings for the alias as if it were a
+//! non-generic type. The C# name of the type is the name of the type followed by its
+//! type arguments, e.g. `HistoryTile`. A generic impl block for the type lists its
+//! instantiations the same way, and the methods for each instantiation are exported
+//! as methods of the corresponding alias, so the type must be exported with the same
+//! instantiations.
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::*;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::Comma,
+    *,
+};
+
+/// A single `T = Type` argument in an `instantiate(...)` attribute.
+#[derive(Clone)]
+pub struct TypeArg {
+    pub param: Ident,
+    pub ty: Type,
+}
+
+impl Parse for TypeArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let param = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        Ok(TypeArg { param, ty })
+    }
+}
+
+/// A complete set of concrete type arguments for a generic function.
+pub struct Instantiation {
+    /// The type parameters and their concrete types, in the order that the parameters
+    /// were declared on the function.
+    args: Vec<(Ident, Type)>,
+}
+
+impl Instantiation {
+    /// Groups the list of type arguments from the attribute into complete
+    /// instantiations of the generic parameters.
+    ///
+    /// Arguments are grouped in the order they're listed, with a new instantiation
+    /// starting whenever a parameter is repeated. For a function with a single type
+    /// parameter every argument is a separate instantiation, e.g. `T = Tile, T = u32`,
+    /// and functions with multiple parameters list each parameter in turn, e.g.
+    /// `K = u32, V = Tile, K = String, V = Tile`.
+    pub fn group(generics: &Generics, args: Vec<TypeArg>) -> syn::Result<Vec<Self>> {
+        if generics.lifetimes().next().is_some() || generics.const_params().next().is_some() {
+            return Err(Error::new_spanned(
+                generics,
+                "Generic lifetimes and const parameters are not supported with `#[cs_bindgen]`",
+            ));
+        }
+
+        let params = generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<Vec<_>>();
+
+        let mut instantiations = Vec::new();
+        let mut current: Vec<Option<Type>> = vec![None; params.len()];
+        let mut first_param = None;
+
+        for arg in args {
+            let index = params
+                .iter()
+                .position(|param| *param == arg.param)
+                .ok_or_else(|| {
+                    Error::new_spanned(
+                        &arg.param,
+                        format!("No type parameter named `{}`", arg.param),
+                    )
+                })?;
+
+            if current[index].is_some() {
+                instantiations.push(Self::complete(&params, current, first_param.take())?);
+                current = vec![None; params.len()];
+            }
+
+            first_param.get_or_insert(arg.param);
+            current[index] = Some(arg.ty);
+        }
+
+        if first_param.is_some() {
+            instantiations.push(Self::complete(&params, current, first_param)?);
+        }
+
+        Ok(instantiations)
+    }
+
+    /// Creates an instantiation from a group of type arguments, generating an error if
+    /// any of the parameters weren't specified.
+    fn complete(
+        params: &[Ident],
+        args: Vec<Option<Type>>,
+        first_param: Option<Ident>,
+    ) -> syn::Result<Self> {
+        params
+            .iter()
+            .zip(args)
+            .map(|(param, ty)| match ty {
+                Some(ty) => Ok((param.clone(), ty)),
+                None => Err(Error::new_spanned(
+                    &first_param,
+                    format!("Instantiation doesn't specify a type for `{}`", param),
+                )),
+            })
+            .collect::<syn::Result<_>>()
+            .map(|args| Self { args })
+    }
+
+    /// Returns the concrete type arguments, in the order their parameters were declared.
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.args.iter().map(|(_, ty)| ty)
+    }
+
+    /// Returns a string that uniquely identifies the instantiation, suitable for use in
+    /// the generated binding identifiers.
+    pub fn mangled_name(&self) -> String {
+        mangle_types(self.types())
+    }
+
+    /// Returns the identifier of the type alias for an instantiation of a generic type,
+    /// e.g. `History__Tile` for `History<Tile>`.
+    pub fn alias_ident(&self, ident: &Ident) -> Ident {
+        format_ident!("{}__{}", ident, self.mangled_name())
+    }
+
+    /// Returns the C# name for an instantiation of a generic type, which is the name
+    /// of the type followed by each part of its type arguments in `PascalCase`, e.g.
+    /// `HistoryVecU32` for `History<Vec<u32>>`.
+    pub fn type_name(&self, ident: &Ident) -> String {
+        let mut name = ident.to_string();
+        for part in self
+            .mangled_name()
+            .split('_')
+            .filter(|part| !part.is_empty())
+        {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                name.extend(first.to_uppercase());
+                name.extend(chars);
+            }
+        }
+
+        name
+    }
+
+    /// Creates a copy of the fields of a generic struct, with every use of the type
+    /// parameters replaced with their concrete types.
+    pub fn apply_fields(&self, fields: &Fields) -> syn::Result<Fields> {
+        let mut fields = fields.clone();
+        for field in fields.iter_mut() {
+            field.ty = substitute_type(&field.ty, &self.args)?;
+        }
+
+        Ok(fields)
+    }
+
+    /// Creates a copy of the variants of a generic enum, with every use of the type
+    /// parameters in their fields replaced with their concrete types.
+    pub fn apply_variants(
+        &self,
+        variants: &Punctuated<Variant, Comma>,
+    ) -> syn::Result<Punctuated<Variant, Comma>> {
+        let mut variants = variants.clone();
+        for variant in variants.iter_mut() {
+            variant.fields = self.apply_fields(&variant.fields)?;
+        }
+
+        Ok(variants)
+    }
+
+    /// Creates a non-generic copy of a generic impl block.
+    ///
+    /// The self type is replaced with the type alias for the corresponding
+    /// instantiation of the type, and every use of the type parameters in the trait
+    /// and the signatures of the associated items is replaced with their concrete
+    /// types. The bodies of the methods are left as-is, since only the signatures are
+    /// used to generate the bindings.
+    pub fn apply_impl(&self, item: &ItemImpl) -> syn::Result<ItemImpl> {
+        let mut item = item.clone();
+        item.generics = Generics::default();
+        item.self_ty = Box::new(alias_type(&substitute_type(&item.self_ty, &self.args)?)?);
+
+        if let Some((_, trait_, _)) = &mut item.trait_ {
+            *trait_ = parse2(substitute_tokens(trait_.to_token_stream(), &self.args))?;
+        }
+
+        for impl_item in &mut item.items {
+            match impl_item {
+                ImplItem::Method(method) => {
+                    method.sig = substitute_signature(&method.sig, &self.args)?;
+                }
+
+                ImplItem::Const(constant) => {
+                    constant.ty = substitute_type(&constant.ty, &self.args)?;
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(item)
+    }
+
+    /// Creates a non-generic copy of the signature, with every use of the type
+    /// parameters replaced with their concrete types.
+    pub fn apply(&self, signature: &Signature) -> syn::Result<Signature> {
+        let mut signature = substitute_signature(signature, &self.args)?;
+        signature.generics = Generics::default();
+        Ok(signature)
+    }
+}
+
+/// Returns a string identifying a list of types, suitable for use in identifiers.
+pub fn mangle_types<'a>(types: impl Iterator<Item = &'a Type>) -> String {
+    types
+        .map(|ty| {
+            ty.to_token_stream()
+                .to_string()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("_")
+        })
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+/// Returns the type alias declared for an instantiation of a generic type, e.g.
+/// `foo::History__Tile` for `foo::History<Tile>`.
+fn alias_type(ty: &Type) -> syn::Result<Type> {
+    let mut path = match ty {
+        Type::Path(path) if path.qself.is_none() => path.clone(),
+        _ => {
+            return Err(Error::new_spanned(
+                ty,
+                "The self type of a generic `impl` block must be a path to a generic struct or enum with `#[cs_bindgen]`",
+            ))
+        }
+    };
+
+    let segment = path
+        .path
+        .segments
+        .last_mut()
+        .ok_or_else(|| Error::new_spanned(ty, "Invalid type path"))?;
+    let types = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    if types.is_empty() {
+        return Err(Error::new_spanned(
+            ty,
+            "The self type of a generic `impl` block must be a generic struct or enum with `#[cs_bindgen]`",
+        ));
+    }
+
+    segment.ident = format_ident!("{}__{}", segment.ident, mangle_types(types.into_iter()));
+    segment.arguments = PathArguments::None;
+    Ok(Type::Path(path))
+}
+
+/// Replaces every use of the specified idents in the argument and return types of
+/// `signature` with their corresponding types.
+///
+/// This is used both for instantiating generic parameters and for replacing `Self`
+/// in methods, since the generated binding functions aren't in the scope of the
+/// original item.
+pub fn substitute_signature(
+    signature: &Signature,
+    args: &[(Ident, Type)],
+) -> syn::Result<Signature> {
+    let mut signature = signature.clone();
+
+    signature.inputs = signature
+        .inputs
+        .into_iter()
+        .map(|arg| match arg {
+            FnArg::Typed(mut arg) => {
+                arg.ty = Box::new(substitute_type(&arg.ty, args)?);
+                Ok(FnArg::Typed(arg))
+            }
+
+            arg @ FnArg::Receiver(_) => Ok(arg),
+        })
+        .collect::<syn::Result<Punctuated<_, Comma>>>()?;
+
+    if let ReturnType::Type(arrow, ty) = signature.output {
+        signature.output = ReturnType::Type(arrow, Box::new(substitute_type(&ty, args)?));
+    }
+
+    Ok(signature)
+}
+
+/// Replaces every use of the specified idents in `ty` with their corresponding types.
+pub fn substitute_type(ty: &Type, args: &[(Ident, Type)]) -> syn::Result<Type> {
+    parse2(substitute_tokens(ty.to_token_stream(), args))
+}
+
+/// Replaces any of the specified idents in `tokens` with their corresponding type.
+///
+/// Idents that follow a `::` are part of a longer path (e.g. `foo::T`), and so
+/// aren't a reference to the type parameter.
+fn substitute_tokens(tokens: TokenStream, args: &[(Ident, Type)]) -> TokenStream {
+    let mut result = TokenStream::new();
+    let mut after_colon = false;
+
+    for token in tokens {
+        let is_colon = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ':');
+
+        match token {
+            TokenTree::Ident(ident) if !after_colon => {
+                match args.iter().find(|(param, _)| *param == ident) {
+                    Some((_, ty)) => ty.to_tokens(&mut result),
+                    None => result.append(ident),
+                }
+            }
+
+            TokenTree::Group(group) => {
+                let mut substituted =
+                    Group::new(group.delimiter(), substitute_tokens(group.stream(), args));
+                substituted.set_span(group.span());
+                result.append(substituted);
+            }
+
+            token => result.append(token),
+        }
+
+        after_colon = is_colon;
+    }
+
+    result
+}
+
+/// Generates an error if any of the type parameters aren't used by the arguments of
+/// the function.
+///
+/// Instantiations are exposed as overloads in C#, which can only be distinguished by
+/// their parameter types.
+pub fn require_params_in_inputs(signature: &Signature) -> syn::Result<()> {
+    let inputs = signature.inputs.to_token_stream();
+    for param in signature.generics.type_params() {
+        if !contains_ident(inputs.clone(), &param.ident) {
+            return Err(Error::new_spanned(
+                &param.ident,
+                format!(
+                    "Type parameter `{}` must be used by an argument in order to export the function with `#[cs_bindgen]`",
+                    param.ident,
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn contains_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        _ => false,
+    })
+}
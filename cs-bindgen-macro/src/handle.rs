@@ -1,15 +1,145 @@
 //! Utilities for generating the bindings for types that should be marshaled as a handle.
 
-use crate::{describe_named_type, impl_named, repr_impl, BindingStyle};
+use crate::{
+    describe_named_type, drop_vec_fn_ident, impl_named, index_fn_ident, quote_convert_list_fn,
+    repr_impl, std_impls::StdImpls, BindingStyle,
+};
 use proc_macro2::TokenStream;
 use quote::*;
 use syn::*;
 
-pub fn quote_type_as_handle(ident: &Ident) -> syn::Result<TokenStream> {
+/// Generates the bindings for a type that's marshaled as a handle.
+///
+/// Handles are normally a pointer to a `Box` owned by the C# object. Shared handles
+/// are instead a pointer to an `Arc`, where each C# object owns a strong reference,
+/// and pooled handles are a pointer to an allocation from the type's pool.
+pub fn quote_type_as_handle(
+    ident: &Ident,
+    impls: &StdImpls,
+    pooled: bool,
+    rename: Option<&LitStr>,
+    docs: Option<&str>,
+) -> syn::Result<TokenStream> {
     let drop_ident = format_drop_ident!(ident);
-    let describe_fn = describe_named_type(ident, BindingStyle::Handle);
+    let describe_fn = describe_named_type(
+        ident,
+        BindingStyle::Handle,
+        impls,
+        &[],
+        docs,
+        &[],
+        None,
+        false,
+    );
     let repr_fn = repr_impl(ident);
-    let named_impl = impl_named(ident);
+    let named_impl = impl_named(ident, rename);
+    let index_fn = index_fn_ident(ident);
+    let drop_vec_fn = drop_vec_fn_ident(ident);
+    let convert_list_fn = quote_convert_list_fn(ident);
+
+    let (into_abi, from_abi, drop_handle) = if impls.shared {
+        (
+            quote! { std::sync::Arc::into_raw(std::sync::Arc::new(self)) },
+            quote! {
+                cs_bindgen::diagnostics::handle_released::<#ident>();
+                match std::sync::Arc::try_unwrap(std::sync::Arc::from_raw(abi)) {
+                    Ok(value) => value,
+                    Err(_) => panic!(
+                        "Can't take ownership of shared `{}` while other handles to it exist",
+                        stringify!(#ident),
+                    ),
+                }
+            },
+            quote! {
+                cs_bindgen::diagnostics::handle_released::<#ident>();
+                std::mem::drop(std::sync::Arc::from_raw(handle));
+            },
+        )
+    } else if pooled {
+        (
+            quote! { cs_bindgen::pool::into_raw(self) },
+            quote! {
+                cs_bindgen::diagnostics::handle_released::<#ident>();
+                cs_bindgen::pool::from_raw(abi)
+            },
+            quote! { std::mem::drop(<#ident as cs_bindgen::abi::Abi>::from_abi(handle)); },
+        )
+    } else {
+        (
+            quote! { std::boxed::Box::into_raw(std::boxed::Box::new(self)) },
+            quote! {
+                cs_bindgen::diagnostics::handle_released::<#ident>();
+                *std::boxed::Box::from_raw(abi as *mut _)
+            },
+            quote! { std::mem::drop(<#ident as cs_bindgen::abi::Abi>::from_abi(handle)); },
+        )
+    };
+
+    // Shared handles can be accessed from multiple C# objects, so they can only be
+    // borrowed immutably, and have to be safe to access from multiple threads.
+    let shared_impls = if impls.shared {
+        quote! {
+            unsafe impl cs_bindgen::abi::SharedHandle for #ident {}
+
+            const _: () = {
+                fn assert_send_sync<T: Send + Sync>() {}
+                let _ = assert_send_sync::<#ident>;
+            };
+        }
+    } else {
+        quote! {
+            impl<'a> cs_bindgen::abi::Abi for &'a mut #ident {
+                type Abi = *const #ident;
+
+                fn repr() -> cs_bindgen::shared::Repr {
+                    cs_bindgen::shared::Repr::Ref(Box::new(#ident::repr()))
+                }
+
+                fn as_abi(&self) -> Self::Abi {
+                    #ident::as_abi(self)
+                }
+
+                fn into_abi(self) -> Self::Abi {
+                    #ident::as_abi(self)
+                }
+
+                unsafe fn from_abi(abi: Self::Abi) -> Self {
+                    &mut *(abi as *mut _)
+                }
+            }
+
+            // `&mut T` arguments borrow the handle directly, so they use the same repr as
+            // `&T` arguments.
+            impl cs_bindgen::abi::AbiMut for #ident {
+                type Abi = *const #ident;
+                type Storage = cs_bindgen::abi::HandleMut<#ident>;
+
+                fn repr() -> cs_bindgen::shared::Repr {
+                    cs_bindgen::shared::Repr::Ref(Box::new(#ident::repr()))
+                }
+
+                unsafe fn from_abi_mut(abi: Self::Abi) -> Self::Storage {
+                    cs_bindgen::abi::HandleMut::new(abi)
+                }
+            }
+        }
+    };
+
+    let pool_impl = if pooled {
+        quote! {
+            impl cs_bindgen::pool::Pooled for #ident {
+                fn pool() -> &'static std::thread::LocalKey<cs_bindgen::pool::Pool<Self>> {
+                    std::thread_local! {
+                        static POOL: cs_bindgen::pool::Pool<#ident> = cs_bindgen::pool::Pool::new();
+                    }
+
+                    &POOL
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     Ok(quote! {
         // Implement `Abi` for the type and references to the type.
@@ -24,14 +154,17 @@ pub fn quote_type_as_handle(ident: &Ident) -> syn::Result<TokenStream> {
             }
 
             fn into_abi(self) -> Self::Abi {
-                std::boxed::Box::into_raw(std::boxed::Box::new(self))
+                cs_bindgen::diagnostics::handle_created::<#ident>();
+                #into_abi
             }
 
             unsafe fn from_abi(abi: Self::Abi) -> Self {
-                *std::boxed::Box::from_raw(abi as *mut _)
+                #from_abi
             }
         }
 
+        impl cs_bindgen::abi::Handle for #ident {}
+
         impl<'a> cs_bindgen::abi::Abi for &'a #ident {
             type Abi = *const #ident;
 
@@ -52,25 +185,8 @@ pub fn quote_type_as_handle(ident: &Ident) -> syn::Result<TokenStream> {
             }
         }
 
-        impl<'a> cs_bindgen::abi::Abi for &'a mut #ident {
-            type Abi = *const #ident;
-
-            fn repr() -> cs_bindgen::shared::Repr {
-                cs_bindgen::shared::Repr::Ref(Box::new(#ident::repr()))
-            }
-
-            fn as_abi(&self) -> Self::Abi {
-                #ident::as_abi(self)
-            }
-
-            fn into_abi(self) -> Self::Abi {
-                #ident::as_abi(self)
-            }
-
-            unsafe fn from_abi(abi: Self::Abi) -> Self {
-                &mut *(abi as *mut _)
-            }
-        }
+        #shared_impls
+        #pool_impl
 
         // Export a function that describes the exported type.
         #describe_fn
@@ -78,8 +194,35 @@ pub fn quote_type_as_handle(ident: &Ident) -> syn::Result<TokenStream> {
         // Implement the `Named` trait for the type.
         #named_impl
 
-        // Export a function that can be used for dropping an instance of the type.
+        // Export a function that can be used for dropping an instance of the type. The
+        // C# handle class calls this exactly once, when the handle is disposed or
+        // finalized.
         #[no_mangle]
-        pub unsafe extern "C" fn #drop_ident(_: <#ident as cs_bindgen::abi::Abi>::Abi) {}
+        pub unsafe extern "C" fn #drop_ident(handle: <#ident as cs_bindgen::abi::Abi>::Abi) {
+            if !handle.is_null() {
+                #drop_handle
+            }
+        }
+
+        // Export the functions for passing lists of the type. Each element of a list
+        // returned to C# is moved into its own handle, so the vec is freed without
+        // dropping its elements. Lists passed to Rust take ownership of each handle, the
+        // same as passing a single handle by value.
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #index_fn(
+            slice: cs_bindgen::abi::RawSlice<#ident>,
+            index: usize,
+        ) -> <#ident as cs_bindgen::abi::Abi>::Abi {
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::take_element(slice, index))
+        }
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #drop_vec_fn(raw: cs_bindgen::abi::RawVec<#ident>) {
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::drop_vec_storage(raw))
+        }
+
+        #convert_list_fn
     })
 }
@@ -1,5 +1,8 @@
-use crate::{enumeration::*, func::*, strukt::*};
-use proc_macro2::TokenStream;
+use crate::{
+    attr::*, enumeration::*, func::*, generic::*, marshal::MarshalFormat, std_impls::StdImpls,
+    strukt::*, traits::*,
+};
+use proc_macro2::{Span, TokenStream};
 use quote::*;
 use std::fmt::Display;
 use syn::*;
@@ -22,17 +25,36 @@ macro_rules! format_drop_ident {
     };
 }
 
+macro_rules! format_command_ident {
+    ($ident:expr) => {
+        format_ident!("__cs_bindgen_command__{}", $ident);
+    };
+}
+
+mod alias;
+mod attr;
+mod batch;
+mod constant;
+mod custom;
 mod enumeration;
 mod func;
+mod generic;
 mod handle;
+mod list;
+mod marshal;
+mod std_impls;
 mod strukt;
+mod support;
+mod traits;
 mod value;
 
 #[proc_macro_attribute]
 pub fn cs_bindgen(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     tokens: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let attr = parse_macro_input!(attr as Attributes);
+
     // Create a copy of the input token stream that we can later extend with the
     // generated code. This allows us to consume the input stream without needing to
     // manually reconstruct the original input later when returning the result.
@@ -41,10 +63,127 @@ pub fn cs_bindgen(
     // Generate the bindings for the annotated item, or generate an error if the
     // item/attribute is invalid.
     let generated = match parse_macro_input!(tokens as Item) {
-        Item::Fn(item) => quote_fn_item(item),
-        Item::Struct(item) => quote_struct_item(item),
-        Item::Impl(item) => quote_impl_item(item),
-        Item::Enum(item) => quote_enum_item(item),
+        // Method-only arguments can't be used on top-level items.
+        _ if attr.method_arg().is_some() => Err(Error::new_spanned(
+            attr.method_arg(),
+            "Only supported on methods in an `impl` block with `#[cs_bindgen]`",
+        )),
+
+        // Skipping is only meaningful for items nested in an exported item.
+        _ if attr.skip.is_some() => Err(Error::new_spanned(
+            attr.skip.as_ref(),
+            "Only supported on fields and on methods in an `impl` block with `#[cs_bindgen]`",
+        )),
+
+        // Only structs can opt in to exporting their `Default` impl.
+        ref item if attr.struct_arg().is_some() && !matches!(item, Item::Struct(_)) => {
+            Err(Error::new_spanned(
+                attr.struct_arg(),
+                "Only supported on structs with `#[cs_bindgen]`",
+            ))
+        }
+
+        // Only types can be marshaled with serde.
+        ref item
+            if attr.marshal.is_some() && !matches!(item, Item::Struct(_) | Item::Enum(_)) =>
+        {
+            Err(Error::new_spanned(
+                attr.marshal.as_ref(),
+                "Only supported on structs and enums with `#[cs_bindgen]`",
+            ))
+        }
+
+        // Only types can provide custom conversion functions.
+        ref item
+            if attr.custom_arg().is_some() && !matches!(item, Item::Struct(_) | Item::Enum(_)) =>
+        {
+            Err(Error::new_spanned(
+                attr.custom_arg(),
+                "Only supported on structs and enums with `#[cs_bindgen]`",
+            ))
+        }
+
+        // Renaming is only supported on items that have a name in C#. Methods, fields,
+        // and variants are renamed with attributes on the nested item.
+        ref item
+            if attr.rename.is_some()
+                && !matches!(
+                    item,
+                    Item::Fn(_) | Item::Struct(_) | Item::Enum(_) | Item::Type(_)
+                ) =>
+        {
+            Err(Error::new_spanned(
+                attr.rename.as_ref(),
+                "Only supported on functions, structs, enums, type aliases, methods, fields, and variants with `#[cs_bindgen]`",
+            ))
+        }
+
+        // Only functions return values that can be mapped to `out` parameters.
+        ref item if !attr.out.is_empty() && !matches!(item, Item::Fn(_)) => Err(Error::new_spanned(
+            &attr.out[0],
+            "Only supported on functions and methods with `#[cs_bindgen]`",
+        )),
+
+        // Only functions and methods return errors.
+        ref item if attr.error.is_some() && !matches!(item, Item::Fn(_)) => {
+            Err(Error::new_spanned(
+                attr.error.as_ref(),
+                "Only supported on functions and methods with `#[cs_bindgen]`",
+            ))
+        }
+
+        // Only functions and methods can be recorded into a command buffer.
+        ref item if attr.batch.is_some() && !matches!(item, Item::Fn(_)) => {
+            Err(Error::new_spanned(
+                attr.batch.as_ref(),
+                "Only supported on functions and methods with `#[cs_bindgen]`",
+            ))
+        }
+
+        Item::Fn(item) => {
+            let mut stripped = item.clone();
+            strip_param_attrs(&mut stripped.sig);
+            result = stripped.into_token_stream();
+            quote_fn_item(item, attr)
+        }
+
+        // Only functions, structs, enums, and impl blocks support instantiating generic
+        // parameters.
+        ref item
+            if !attr.instantiate.is_empty()
+                && !matches!(item, Item::Struct(_) | Item::Enum(_) | Item::Impl(_)) =>
+        {
+            Err(Error::new_spanned(
+                &attr.instantiate[0].param,
+                "`instantiate` is only supported on functions, structs, enums, and `impl` blocks with `#[cs_bindgen]`",
+            ))
+        }
+
+        Item::Struct(item) => {
+            result = strip_field_attrs(item.clone()).into_token_stream();
+            quote_struct_item(item, &attr)
+        }
+        Item::Impl(item) => {
+            result = strip_method_attrs(item.clone()).into_token_stream();
+            quote_impl_item(item, attr.instantiate)
+        }
+
+        Item::Enum(item) => {
+            result = strip_variant_attrs(item.clone()).into_token_stream();
+            quote_enum_item(item, &attr)
+        }
+        Item::Trait(item) => quote_trait_item(item),
+        Item::Const(item) => constant::quote_const_item(&item.ident, &item.ty),
+
+        // Mutable statics can't be read safely, so only immutable statics are supported.
+        Item::Static(item) => match &item.mutability {
+            Some(mutability) => Err(Error::new_spanned(
+                mutability,
+                "Mutable statics are not supported with `#[cs_bindgen]`",
+            )),
+            None => constant::quote_const_item(&item.ident, &item.ty),
+        },
+        Item::Type(item) => alias::quote_alias_item(item, &attr),
 
         // Generate an error for any unknown item types.
         item @ _ => Err(Error::new_spanned(
@@ -64,80 +203,210 @@ pub fn cs_bindgen(
 enum BindingStyle {
     Handle,
     Value,
+    Marshal(MarshalFormat),
+    Custom,
+
+    /// A newtype struct passed as its inner value, optionally with a C# wrapper
+    /// struct.
+    Transparent(bool),
 }
 
-fn quote_fn_item(item: ItemFn) -> syn::Result<TokenStream> {
+fn quote_fn_item(item: ItemFn, attr: Attributes) -> syn::Result<TokenStream> {
     // Extract the signature, which contains the bulk of the information we care about.
+    let docs = doc_comment(&item.attrs);
     let signature = item.sig;
+    let ident = signature.ident.clone();
+    let rename = attr.rename;
+    let out = attr.out;
+    let batch = attr.batch.is_some();
+    let error = attr.error;
+
+    // Generic functions can only be exported if the user has listed the concrete
+    // instantiations to generate bindings for.
+    if attr.instantiate.is_empty() {
+        reject_generics(
+            &signature.generics,
+            "Generic functions must list their instantiations with `#[cs_bindgen(instantiate(..))]`",
+        )?;
+
+        return quote_fn_export(
+            signature,
+            ident.to_string(),
+            ident.into_token_stream(),
+            rename.as_ref(),
+            &out,
+            batch,
+            error.as_ref(),
+            docs.as_deref(),
+        );
+    }
 
-    // Generate an error for any generic parameters.
-    reject_generics(
-        &signature.generics,
-        "Generic functions not supported with `#[cs_bindgen]`",
-    )?;
+    require_params_in_inputs(&signature)?;
 
+    Instantiation::group(&signature.generics, attr.instantiate)?
+        .into_iter()
+        .map(|instantiation| {
+            let mangled_name = format!("{}__{}", ident, instantiation.mangled_name());
+            let types = instantiation.types();
+            let invoke = quote! { #ident::<#( #types ),*> };
+            quote_fn_export(
+                instantiation.apply(&signature)?,
+                mangled_name,
+                invoke,
+                rename.as_ref(),
+                &out,
+                batch,
+                error.as_ref(),
+                docs.as_deref(),
+            )
+        })
+        .collect()
+}
+
+/// Generates the binding and describe functions for a non-generic function.
+///
+/// `mangled_name` is used to generate the names of the binding and describe
+/// functions, and `invoke` is the path used to call the original function. These
+/// only differ from the name of the function for instantiations of generic
+/// functions. `rename` is the name of the function in C#, if it was renamed, `out`
+/// lists the names of any `out` parameters, `batch` is set if calls to the function
+/// can be recorded into a command buffer, `error` is set if the returned error is
+/// passed to C# as its `Display` string, and `docs` is the doc comment on the
+/// original function.
+fn quote_fn_export(
+    mut signature: Signature,
+    mangled_name: String,
+    invoke: TokenStream,
+    rename: Option<&LitStr>,
+    out: &[Ident],
+    batch: bool,
+    error: Option<&LitStr>,
+    docs: Option<&str>,
+) -> syn::Result<TokenStream> {
     // Determine the name of the generated function.
     let ident = signature.ident;
-    let binding_ident = format_binding_ident!(ident);
+    let binding_ident = format_binding_ident!(mangled_name);
+
+    // Async functions are spawned onto the executor and report their result through a
+    // completion callback, rather than returning it from the binding function.
+    let is_async = signature.asyncness.is_some();
+
+    if batch {
+        batch::validate_batch(&signature)?;
+    }
+
+    if let Some(error) = error {
+        signature.output = display_error_output(&signature.output, error)?;
+    }
 
     // Process the arguments to the function.
+    let describe_out_params = quote_out_params(out, &signature)?;
+    let defaults = arg_defaults(&signature.inputs, out)?;
     let inputs = extract_inputs(signature.inputs)?;
+    validate_output(&signature.output)?;
+    let list_fns = list::quote_list_fns(
+        &mangled_name,
+        inputs
+            .iter()
+            .map(|(_, ty)| &**ty)
+            .chain(output_type(&signature.output)),
+    );
+
+    // The future for an async function outlives the call to the binding function, so it
+    // can't borrow any of its arguments.
+    if is_async {
+        if let Some((_, ty)) = inputs.iter().find(|(_, ty)| !is_owned_arg(ty)) {
+            return Err(Error::new_spanned(
+                ty,
+                "Async functions must take all arguments by value with `#[cs_bindgen]`",
+            ));
+        }
+    }
+
+    let completion_input = if is_async {
+        Some(quote! { __completion: cs_bindgen::abi::RawCompletion })
+    } else {
+        None
+    };
     let binding_inputs = inputs
         .iter()
-        .map(|(ident, ty)| quote_binding_inputs(ident, ty));
+        .map(|(ident, ty)| quote_arg_binding_input(ident, ty))
+        .chain(completion_input);
     let convert_inputs = inputs
         .iter()
-        .map(|(ident, _)| quote_input_conversion(ident));
+        .map(|(ident, ty)| quote_arg_conversion(ident, ty));
 
     // Generate the output portion of the binding function declaration.
-    let return_decl = match &signature.output {
-        ReturnType::Default => quote! {},
-        ReturnType::Type(_, return_type) => quote! {
-            -> <#return_type as cs_bindgen::abi::Abi>::Abi
-        },
+    let return_decl = if is_async {
+        quote! {}
+    } else {
+        quote_return_decl(&signature.output)
     };
 
     // Generate the expression for describing the output of the function.
-    let describe_output = match &signature.output {
-        ReturnType::Default => quote! { None },
-        ReturnType::Type(_, return_type) => quote! {
-            Some(<#return_type as cs_bindgen::abi::Abi>::repr())
-        },
-    };
+    let describe_output = quote_output_repr(&signature.output);
 
     // Generate the list of argument names. Used both for forwarding arguments into the
     // original function, and for populating the metadata item.
     let arg_names = inputs.iter().map(|(ident, _)| ident);
 
-    let invoke_expr = quote! { #ident(#( #arg_names, )*) };
-    let return_expr = match &signature.output {
-        ReturnType::Default => invoke_expr,
-        ReturnType::Type(..) => quote! { cs_bindgen::abi::Abi::into_abi(#invoke_expr) },
+    let mut invoke_expr = quote! { #invoke(#( #arg_names, )*) };
+    if error.is_some() {
+        invoke_expr = quote_display_error(invoke_expr, is_async);
+    }
+
+    let return_expr = if is_async {
+        quote! { cs_bindgen::task::spawn(#invoke_expr, __completion) }
+    } else {
+        quote_return_conversion(&signature.output, invoke_expr)
     };
 
     // Compose the various pieces together into the final binding function.
-    let binding = quote! {
-        #[no_mangle]
-        #[allow(bad_style)]
-        pub unsafe extern "C" fn #binding_ident(
-            #( #binding_inputs, )*
-        ) #return_decl {
+    let binding = quote_binding_fn(
+        &binding_ident,
+        binding_inputs,
+        return_decl,
+        quote! {
             #( #convert_inputs )*
             #return_expr
-        }
+        },
+    );
+
+    // Batched functions also export the command function used to record calls into a
+    // command buffer.
+    let (command, describe_command) = if batch {
+        let command_inputs = inputs
+            .iter()
+            .map(|(ident, ty)| (ident.clone(), ty.into_token_stream()))
+            .collect::<Vec<_>>();
+        let command_name = format_command_ident!(mangled_name).to_string();
+        (
+            Some(batch::quote_command_fn(
+                &mangled_name,
+                &binding_ident,
+                &command_inputs,
+            )),
+            quote! { Some(#command_name.into()) },
+        )
+    } else {
+        (None, quote! { None })
     };
 
     // Generate the name of the describe function.
-    let describe_ident = format_describe_ident!(ident);
+    let describe_ident = format_describe_ident!(mangled_name);
 
     // Generate string versions of the two function idents.
     let name = ident.to_string();
     let binding_name = binding_ident.to_string();
+    let rename = quote_rename(rename);
+    let docs = quote_docs(docs);
 
-    let describe_args = inputs.iter().map(|(ident, ty)| {
+    let describe_args = inputs.iter().zip(&defaults).map(|((ident, ty), default)| {
         let name = ident.to_string();
+        let repr = quote_arg_repr(ty);
+        let default = quote_arg_default(default.as_ref());
         quote! {
-            cs_bindgen::shared::FnArg::new(#name, <#ty as cs_bindgen::abi::Abi>::repr())
+            cs_bindgen::shared::FnArg::new(#name, #repr).with_default(#default)
         }
     });
 
@@ -149,11 +418,17 @@ fn quote_fn_item(item: ItemFn) -> syn::Result<TokenStream> {
 
             let export = Func {
                 name: #name.into(),
+                module: module_path!().into(),
                 binding: #binding_name.into(),
+                rename: #rename,
+                docs: #docs,
                 inputs: vec![#(
                     #describe_args,
                 )*],
                 output: #describe_output,
+                out_params: #describe_out_params,
+                is_async: #is_async,
+                command: #describe_command,
             };
 
             std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
@@ -162,27 +437,51 @@ fn quote_fn_item(item: ItemFn) -> syn::Result<TokenStream> {
 
     Ok(quote! {
         #binding
+        #command
         #describe
+        #list_fns
     })
 }
 
-fn quote_impl_item(item: ItemImpl) -> syn::Result<TokenStream> {
+fn quote_impl_item(item: ItemImpl, instantiate: Vec<TypeArg>) -> syn::Result<TokenStream> {
+    // Generic impl blocks generate the bindings for each instantiation separately.
+    if !instantiate.is_empty() {
+        return Instantiation::group(&item.generics, instantiate)?
+            .into_iter()
+            .map(|instantiation| quote_impl_item(instantiation.apply_impl(&item)?, Vec::new()))
+            .collect();
+    }
+
     // Generate an error for any generic parameters.
     reject_generics(
         &item.generics,
-        "Generic `impl` blocks are not supported with `#[cs_bindgen]`",
+        "Generic `impl` blocks must list their instantiations with `#[cs_bindgen(instantiate(..))]`",
     )?;
 
-    // Generate an error for trait impls. Only inherent impls are allowed for now.
-    if let Some((_, trait_, _)) = item.trait_ {
-        return Err(Error::new_spanned(
-            trait_,
-            "Trait impls not supported with `#[cs_bindgen]`",
-        ));
-    }
-
     let self_ty = item.self_ty;
 
+    // Impls of standard library traits export a binding for the trait, which the
+    // generated C# type uses to implement the corresponding .NET interface or override,
+    // and impls of `From`, `TryFrom`, and the operator traits export a binding for the
+    // conversion or operator.
+    // Impls of exported traits only need to export the conversion to a trait object,
+    // since the methods of the trait are exported along with the trait itself.
+    if let Some((_, trait_, _)) = &item.trait_ {
+        if let Some(std_trait) = std_impls::std_trait(trait_)? {
+            return std_impls::quote_std_trait_impl(std_trait, &self_ty);
+        }
+
+        if let Some((conversion, from_ty)) = std_impls::conversion_trait(trait_)? {
+            return std_impls::quote_conversion_impl(conversion, from_ty, &self_ty);
+        }
+
+        if let Some(operator) = std_impls::operator_trait(trait_, &self_ty)? {
+            return std_impls::quote_operator_impl(operator, &self_ty);
+        }
+
+        return quote_trait_impl_item(trait_, &self_ty);
+    }
+
     // Iterate over the items declared in the impl block and generate bindings for any
     // supported item types.
     item.items
@@ -190,6 +489,7 @@ fn quote_impl_item(item: ItemImpl) -> syn::Result<TokenStream> {
         .filter_map(|item| {
             match item {
                 ImplItem::Method(item) => Some(quote_method_item(item, &self_ty)),
+                ImplItem::Const(item) => Some(constant::quote_assoc_const_item(&item, &self_ty)),
 
                 // Ignore all other unsupported associated item types. We don't generate bindings
                 // for them, but it's otherwise not an error to include them in an `impl` block
@@ -201,11 +501,22 @@ fn quote_impl_item(item: ItemImpl) -> syn::Result<TokenStream> {
 }
 
 fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenStream> {
+    // Skipped methods don't get any bindings, so they don't need to be exportable.
+    let attr = Attributes::from_attrs(&item.attrs)?;
+    if attr.skip.is_some() {
+        return Ok(TokenStream::new());
+    }
+
     // Generate the binding function
     // =============================
 
     // Extract the signature, which contains the bulk of the information we care about.
-    let signature = item.sig;
+    // The binding function isn't declared in the `impl` block, so any uses of `Self`
+    // need to be replaced with the full self type.
+    let mut signature = substitute_signature(
+        &item.sig,
+        &[(Ident::new("Self", Span::call_site()), self_ty.clone())],
+    )?;
 
     // Generate an error for any generic parameters.
     reject_generics(
@@ -213,6 +524,103 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
         "Generic functions not supported with `#[cs_bindgen]`",
     )?;
 
+    if let Some(arg) = attr.instantiate.first() {
+        return Err(Error::new_spanned(
+            &arg.param,
+            "`instantiate` is only supported on functions, structs, enums, and `impl` blocks with `#[cs_bindgen]`",
+        ));
+    }
+
+    if let Some(arg) = attr.struct_arg() {
+        return Err(Error::new_spanned(
+            arg,
+            "Only supported on structs with `#[cs_bindgen]`",
+        ));
+    }
+
+    if let Some(marshal) = attr.marshal.as_ref().or(attr.custom_arg()) {
+        return Err(Error::new_spanned(
+            marshal,
+            "Only supported on structs and enums with `#[cs_bindgen]`",
+        ));
+    }
+
+    let describe_accessor = quote_accessor(&attr, &signature)?;
+
+    // Property accessors are only supported on handle types, since the receiver has to
+    // be borrowed from the C# object. The assertion is spanned to the accessor argument
+    // so that the error points at the attribute.
+    let assert_handle = attr
+        .getter
+        .as_ref()
+        .or(attr.setter.as_ref())
+        .or(attr.index.as_ref())
+        .or(attr.index_mut.as_ref())
+        .map(|accessor| {
+            quote_spanned! {accessor.span()=>
+                const _: () = {
+                    fn assert_handle<T: cs_bindgen::abi::Handle>() {}
+                    let _ = assert_handle::<#self_ty>;
+                };
+            }
+        });
+    let is_constructor = is_constructor(&attr, &signature, self_ty)?;
+
+    // Constructors and accessors are named after the type and property, so they don't
+    // have a method name to rename.
+    if let Some(rename) = &attr.rename {
+        if is_constructor
+            || attr.getter.is_some()
+            || attr.setter.is_some()
+            || attr.index.is_some()
+            || attr.index_mut.is_some()
+        {
+            return Err(Error::new_spanned(
+                rename,
+                "`rename` is not supported on constructors or property accessors",
+            ));
+        }
+    }
+
+    // Constructors and accessors don't have a C# parameter list that `out` parameters
+    // could be added to.
+    if let Some(out) = attr.out.first() {
+        if is_constructor || attr.method_arg().is_some() {
+            return Err(Error::new_spanned(
+                out,
+                "`out` is not supported on constructors or property accessors",
+            ));
+        }
+    }
+
+    // Constructors and accessors aren't called as regular methods in C#, so there's
+    // nothing to record them with.
+    if let Some(batch) = &attr.batch {
+        if is_constructor || attr.method_arg().is_some() {
+            return Err(Error::new_spanned(
+                batch,
+                "`batch` is not supported on constructors or property accessors",
+            ));
+        }
+
+        batch::validate_batch(&signature)?;
+    }
+
+    // TODO: Support async methods. The future would need to own the receiver, which
+    // isn't possible for handle types since the C# object retains ownership.
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "Async methods are not supported with `#[cs_bindgen]`",
+        ));
+    }
+
+    if let Some(error) = &attr.error {
+        signature.output = display_error_output(&signature.output, error)?;
+    }
+
+    let describe_out_params = quote_out_params(&attr.out, &signature)?;
+
     // Process the receiver for the method, if any:
     //
     // * For the binding function, we need to add the additional input to the list of
@@ -223,9 +631,13 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
     // TODO: Rewrite all this it's very bad and super hard to follow. Probably the thing
     // to do would be to first parse out the receiver style as an enum, then do a
     // separate `match` on it for each of the values we want to generate.
-    let (mut binding_args, describe_receiver) = match signature.receiver() {
+    //
+    // Borrowed receivers also borrow the handle for the duration of the call, so that
+    // re-entrant or aliased calls can be detected when the `borrow-checks` feature is
+    // enabled.
+    let (mut binding_args, describe_receiver, borrow_receiver) = match signature.receiver() {
         Some(arg) => {
-            let (self_ty, describe) = match arg {
+            let (self_ty, describe, borrow) = match arg {
                 // Expand the full self type based on how the receiver was declared:
                 //
                 // * `self` -> `self_ty`
@@ -237,14 +649,20 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
                             (
                                 quote! { &mut #self_ty },
                                 quote! { Some(ReceiverStyle::RefMut) },
+                                quote! { let __borrow = cs_bindgen::borrow::borrow_mut(self_); },
                             )
                         } else {
-                            (quote! { & #self_ty }, quote! { Some(ReceiverStyle::Ref) })
+                            (
+                                quote! { & #self_ty },
+                                quote! { Some(ReceiverStyle::Ref) },
+                                quote! { let __borrow = cs_bindgen::borrow::borrow(self_); },
+                            )
                         }
                     } else {
                         (
                             self_ty.to_token_stream(),
                             quote! { Some(ReceiverStyle::Move) },
+                            quote! {},
                         )
                     }
                 }
@@ -259,13 +677,13 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
                 // We probably want to treat arbitrary self type functions more like static functions
                 // in C# than methods. So maybe convert it to a regular function with a normal self type,
                 // i.e. treat it as if there were no receiver?
-                FnArg::Typed(arg) => (arg.ty.to_token_stream(), quote! { None }),
+                FnArg::Typed(arg) => (arg.ty.to_token_stream(), quote! { None }, quote! {}),
             };
 
-            (vec![(format_ident!("self_"), self_ty)], describe)
+            (vec![(format_ident!("self_"), self_ty)], describe, borrow)
         }
 
-        None => (Default::default(), quote! { None }),
+        None => (Default::default(), quote! { None }, quote! {}),
     };
 
     // Determine the name of the generated function.
@@ -274,58 +692,103 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
     let mangled_name = format!("{}__{}", ident, self_ident);
     let binding_ident = format_binding_ident!(mangled_name);
 
-    // Process the arguments to the function.
+    // Property accessors are generated as C# properties and indexers, which don't
+    // have a parameter list with optional parameters.
+    let defaults = arg_defaults(&signature.inputs, &attr.out)?;
+    if attr.getter.is_some()
+        || attr.setter.is_some()
+        || attr.index.is_some()
+        || attr.index_mut.is_some()
+    {
+        if let Some(default) = defaults.iter().flatten().next() {
+            return Err(Error::new_spanned(
+                default,
+                "Default values are not supported on property accessors",
+            ));
+        }
+    }
+
+    // Process the arguments to the function. The receiver (if any) is always converted
+    // directly, whereas the remaining arguments may need special handling.
     let inputs = extract_inputs(signature.inputs)?;
-    binding_args.extend(
+    validate_output(&signature.output)?;
+    let list_fns = list::quote_list_fns(
+        &mangled_name,
         inputs
             .iter()
-            .map(|(ident, ty)| (ident.clone(), ty.into_token_stream())),
+            .map(|(_, ty)| &**ty)
+            .chain(output_type(&signature.output)),
     );
     let binding_inputs = binding_args
         .iter()
-        .map(|(ident, ty)| quote_binding_inputs(ident, ty));
+        .map(|(ident, ty)| quote_binding_inputs(ident, ty))
+        .chain(
+            inputs
+                .iter()
+                .map(|(ident, ty)| quote_arg_binding_input(ident, ty)),
+        )
+        .collect::<Vec<_>>();
     let convert_inputs = binding_args
         .iter()
-        .map(|(ident, _)| quote_input_conversion(ident));
+        .map(|(ident, _)| quote_input_conversion(ident))
+        .chain(
+            inputs
+                .iter()
+                .map(|(ident, ty)| quote_arg_conversion(ident, ty)),
+        )
+        .collect::<Vec<_>>();
 
     // Generate the list of argument names. Used both for forwarding arguments into the
     // original function, and for populating the metadata item.
+    binding_args.extend(
+        inputs
+            .iter()
+            .map(|(ident, ty)| (ident.clone(), ty.into_token_stream())),
+    );
     let arg_names = binding_args
         .iter()
         .map(|(ident, _)| ident.to_token_stream());
 
     // Generate the output portion of the binding function declaration.
-    let return_decl = match &signature.output {
-        ReturnType::Default => quote! {},
-        ReturnType::Type(_, return_type) => quote! {
-            -> <#return_type as cs_bindgen::abi::Abi>::Abi
-        },
-    };
+    let return_decl = quote_return_decl(&signature.output);
 
     // Generate the expression for describing the output of the function.
-    let describe_output = match &signature.output {
-        ReturnType::Default => quote! { None },
-        ReturnType::Type(_, return_type) => quote! {
-            Some(<#return_type as cs_bindgen::abi::Abi>::repr())
-        },
-    };
+    let describe_output = quote_output_repr(&signature.output);
 
-    let invoke = quote! { #self_ty::#ident(#( #arg_names, )*) };
-    let return_expr = match &signature.output {
-        ReturnType::Default => invoke,
-        ReturnType::Type(..) => quote! { cs_bindgen::abi::Abi::into_abi(#invoke) },
-    };
+    let mut invoke = quote! { #self_ty::#ident(#( #arg_names, )*) };
+    if attr.error.is_some() {
+        invoke = quote_display_error(invoke, false);
+    }
+
+    let return_expr = quote_return_conversion(&signature.output, invoke);
 
     // Compose the various pieces together into the final binding function.
-    let binding = quote! {
-        #[no_mangle]
-        #[allow(bad_style)]
-        pub unsafe extern "C" fn #binding_ident(
-            #( #binding_inputs, )*
-        ) #return_decl {
+    let binding = quote_binding_fn(
+        &binding_ident,
+        binding_inputs.into_iter(),
+        return_decl,
+        quote! {
+            #borrow_receiver
             #( #convert_inputs )*
             #return_expr
-        }
+        },
+    );
+
+    // Batched methods also export the command function used to record calls into a
+    // command buffer. The receiver is read from the buffer along with the rest of the
+    // arguments.
+    let (command, describe_command) = if attr.batch.is_some() {
+        let command_name = format_command_ident!(mangled_name).to_string();
+        (
+            Some(batch::quote_command_fn(
+                &mangled_name,
+                &binding_ident,
+                &binding_args,
+            )),
+            quote! { Some(#command_name.into()) },
+        )
+    } else {
+        (None, quote! { None })
     };
 
     // Generate the describe function.
@@ -337,11 +800,15 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
     // Generate string versions of the two function idents.
     let name = ident.to_string();
     let binding_name = binding_ident.to_string();
+    let rename = quote_rename(attr.rename.as_ref());
+    let docs = quote_docs(doc_comment(&item.attrs).as_deref());
 
-    let describe_args = inputs.iter().map(|(ident, ty)| {
+    let describe_args = inputs.iter().zip(&defaults).map(|((ident, ty), default)| {
         let name = ident.to_string();
+        let repr = quote_arg_repr(ty);
+        let default = quote_arg_default(default.as_ref());
         quote! {
-            cs_bindgen::shared::FnArg::new(#name, <#ty as cs_bindgen::abi::Abi>::repr())
+            cs_bindgen::shared::FnArg::new(#name, #repr).with_default(#default)
         }
     });
 
@@ -354,11 +821,17 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
                 name: #name.into(),
                 binding: #binding_name.into(),
                 self_type: <#self_ty as cs_bindgen::shared::Named>::type_name(),
+                rename: #rename,
+                docs: #docs,
                 receiver: #describe_receiver,
                 inputs: vec![#(
                     #describe_args,
                 )*],
                 output: #describe_output,
+                out_params: #describe_out_params,
+                accessor: #describe_accessor,
+                is_constructor: #is_constructor,
+                command: #describe_command,
             };
 
             std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
@@ -366,13 +839,230 @@ fn quote_method_item(item: ImplItemMethod, self_ty: &Type) -> syn::Result<TokenS
     };
 
     Ok(quote! {
+        #assert_handle
         #binding
+        #command
         #describe
+        #list_fns
     })
 }
 
-/// Returns `true` if any of the specified attributes are a `derive()` containing `Copy`.
-fn has_derive_copy(attributes: &[Attribute]) -> syn::Result<bool> {
+/// Generates the value of the `accessor` field for an exported method.
+///
+/// Getters must take `&self` and return the value of the property, and setters must
+/// take `&mut self` and a single argument. The name of the property is the name of
+/// the method without the `get_`/`set_` prefix. Indexer accessors are the same, but
+/// take the index as an additional first argument.
+fn quote_accessor(attr: &Attributes, signature: &Signature) -> syn::Result<TokenStream> {
+    let name = signature.ident.to_string();
+    let receiver = match signature.receiver() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            Some(receiver.mutability.is_some())
+        }
+        _ => None,
+    };
+    let arg_count = signature.inputs.len() - signature.receiver().iter().count();
+    let has_output = matches!(signature.output, ReturnType::Type(..));
+
+    let mut accessors = attr
+        .getter
+        .iter()
+        .chain(&attr.setter)
+        .chain(&attr.index)
+        .chain(&attr.index_mut);
+    let accessor = match accessors.next() {
+        Some(accessor) => accessor,
+        None => return Ok(quote! { None }),
+    };
+    if let Some(other) = accessors.next() {
+        return Err(Error::new_spanned(
+            other,
+            "A method can only be one of `getter`, `setter`, `index`, or `index_mut`",
+        ));
+    }
+
+    if accessor == "getter" {
+        if receiver != Some(false) || arg_count != 0 || !has_output {
+            return Err(Error::new_spanned(
+                accessor,
+                "Getters must take `&self`, have no other arguments, and return a value",
+            ));
+        }
+
+        let property = name.strip_prefix("get_").unwrap_or(&name);
+        Ok(quote! { Some(cs_bindgen::shared::Accessor::Getter(#property.into())) })
+    } else if accessor == "setter" {
+        if receiver != Some(true) || arg_count != 1 || has_output {
+            return Err(Error::new_spanned(
+                accessor,
+                "Setters must take `&mut self` and a single argument, and can't return a value",
+            ));
+        }
+
+        let property = name.strip_prefix("set_").unwrap_or(&name);
+        Ok(quote! { Some(cs_bindgen::shared::Accessor::Setter(#property.into())) })
+    } else if accessor == "index" {
+        if receiver != Some(false) || arg_count != 1 || !has_output {
+            return Err(Error::new_spanned(
+                accessor,
+                "Index getters must take `&self` and an index, and return a value",
+            ));
+        }
+
+        Ok(quote! { Some(cs_bindgen::shared::Accessor::IndexGetter) })
+    } else {
+        if receiver != Some(true) || arg_count != 2 || has_output {
+            return Err(Error::new_spanned(
+                accessor,
+                "Index setters must take `&mut self`, an index, and a value, and can't return a value",
+            ));
+        }
+
+        Ok(quote! { Some(cs_bindgen::shared::Accessor::IndexSetter) })
+    }
+}
+
+/// Returns the inner type if `ty` is an `Arc<T>`.
+fn arc_inner_ty(ty: &Type) -> Option<&Type> {
+    wrapper_inner_ty(ty, "Arc")
+}
+
+/// Returns the inner type if `ty` is a `Box<T>`.
+fn box_inner_ty(ty: &Type) -> Option<&Type> {
+    wrapper_inner_ty(ty, "Box")
+}
+
+/// Returns the inner type if `ty` is a path type named `wrapper` with a single type
+/// argument, e.g. `Arc<T>`.
+fn wrapper_inner_ty<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != wrapper {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the type used to describe a field of type `ty`.
+///
+/// Boxes (including boxes nested in other types, e.g. `Option<Box<T>>`) are
+/// described with `cs_bindgen::shared::Boxed`, which only refers to the boxed type by
+/// name. This keeps the extra indirection visible to the generated bindings, and
+/// allows recursive types to be described without recursing forever.
+fn described_field_ty(ty: &Type) -> Type {
+    let mut ty = ty.clone();
+    replace_boxes(&mut ty);
+    ty
+}
+
+fn replace_boxes(ty: &mut Type) {
+    // NOTE: Boxed trait objects are passed to C# as interfaces, so they're described as
+    // usual.
+    if let Some(inner) = box_inner_ty(ty) {
+        if !matches!(inner, Type::TraitObject(_)) {
+            *ty = parse_quote! { cs_bindgen::shared::Boxed<#inner> };
+            return;
+        }
+    }
+
+    match ty {
+        Type::Paren(paren) => replace_boxes(&mut paren.elem),
+        Type::Group(group) => replace_boxes(&mut group.elem),
+        Type::Array(array) => replace_boxes(&mut array.elem),
+        Type::Slice(slice) => replace_boxes(&mut slice.elem),
+        Type::Reference(reference) => replace_boxes(&mut reference.elem),
+        Type::Tuple(tuple) => tuple.elems.iter_mut().for_each(replace_boxes),
+
+        Type::Path(path) => {
+            for segment in &mut path.path.segments {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in &mut args.args {
+                        if let GenericArgument::Type(arg) = arg {
+                            replace_boxes(arg);
+                        }
+                    }
+                }
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Determines if an exported method should be exposed as a C# constructor.
+///
+/// Methods named `new` are treated as constructors if they don't have a receiver and
+/// return `Self`. Any other method with the same signature can opt in with
+/// `#[cs_bindgen(constructor)]`.
+fn is_constructor(attr: &Attributes, signature: &Signature, self_ty: &Type) -> syn::Result<bool> {
+    // NOTE: `Self` has already been replaced with the full self type, so we only need to
+    // compare against `self_ty`. Shared handles can also be constructed from an
+    // `Arc<Self>`.
+    let self_ty = self_ty.to_token_stream().to_string();
+    let returns_self = match &signature.output {
+        ReturnType::Type(_, ty) => {
+            ty.to_token_stream().to_string() == self_ty
+                || arc_inner_ty(ty)
+                    .map(|inner| inner.to_token_stream().to_string() == self_ty)
+                    .unwrap_or(false)
+        }
+        ReturnType::Default => false,
+    };
+    let can_construct = signature.receiver().is_none() && returns_self;
+
+    match &attr.constructor {
+        Some(constructor) if !can_construct => Err(Error::new_spanned(
+            constructor,
+            "Constructors can't take `self` and must return `Self` or `Arc<Self>`",
+        )),
+
+        Some(_) => Ok(true),
+        None => Ok(can_construct && signature.ident == "new"),
+    }
+}
+
+/// Generates the `extern "C"` binding function for an exported function or method.
+///
+/// In addition to the declared inputs, the binding function takes a trailing
+/// `__panic` out parameter. The body of the function is run with
+/// `cs_bindgen::panic::catch_panic` so that any panic is reported through
+/// `__panic` rather than unwinding across the FFI boundary. The call is traced with
+/// `cs_bindgen::trace`, which does nothing unless the `trace` feature is enabled.
+fn quote_binding_fn(
+    binding_ident: &Ident,
+    binding_inputs: impl Iterator<Item = TokenStream>,
+    return_decl: TokenStream,
+    body: TokenStream,
+) -> TokenStream {
+    let binding_name = binding_ident.to_string();
+    quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #binding_ident(
+            #( #binding_inputs, )*
+            __panic: *mut cs_bindgen::abi::RawString,
+        ) #return_decl {
+            let __trace = cs_bindgen::trace::enter(#binding_name);
+            cs_bindgen::panic::catch_panic(__panic, move || {
+                #body
+            })
+        }
+    }
+}
+
+/// Returns `true` if any of the specified attributes are a `derive()` containing
+/// the specified trait, e.g. `Copy`.
+fn has_derive(attributes: &[Attribute], trait_name: &str) -> syn::Result<bool> {
     // Get the `#[derive(..)]` attribute, or return `false` if none is present.
     let attr = match attributes.iter().find(|attr| {
         attr.path
@@ -403,7 +1093,7 @@ fn has_derive_copy(attributes: &[Attribute]) -> syn::Result<bool> {
         // TODO: Handle the case where the user specified the full path for the trait, i.e.
         // `std::marker::Copy`.
         path.get_ident()
-            .map(|ident| ident == "Copy")
+            .map(|ident| ident == trait_name)
             .unwrap_or(false)
     }))
 }
@@ -425,12 +1115,55 @@ fn reject_generics<M: Display>(generics: &Generics, message: M) -> syn::Result<(
     }
 }
 
-fn describe_named_type(ident: &Ident, style: BindingStyle) -> TokenStream {
+/// Generates the describe function for an exported type.
+///
+/// Also generates the `AbiRef` impl for the type, since that only depends on how the
+/// type is passed to C#.
+fn describe_named_type(
+    ident: &Ident,
+    style: BindingStyle,
+    impls: &StdImpls,
+    renamed_fields: &[LitStr],
+    docs: Option<&str>,
+    variant_docs: &[Option<String>],
+    discriminant: Option<&Ident>,
+    is_non_exhaustive: bool,
+) -> TokenStream {
     let describe_ident = format_describe_ident!(ident);
+    let discriminant = match discriminant {
+        Some(discriminant) => quote! { Some(<#discriminant as cs_bindgen::abi::Abi>::repr()) },
+        None => quote! { None },
+    };
+    let docs = quote_docs(docs);
+    let variant_docs = variant_docs.iter().map(|docs| quote_docs(docs.as_deref()));
+    let std_impl_fns = impls.quote_describe_fields(ident);
     let index_fn = index_fn_ident(ident).to_string();
     let convert_list_fn = convert_list_fn_ident(ident).to_string();
     let drop_vec_fn = drop_vec_fn_ident(ident).to_string();
 
+    let abi_ref_storage = match style {
+        BindingStyle::Handle => quote! { cs_bindgen::abi::HandleRef<#ident> },
+        BindingStyle::Value
+        | BindingStyle::Marshal(_)
+        | BindingStyle::Custom
+        | BindingStyle::Transparent(_) => quote! { cs_bindgen::abi::ValueRef<#ident> },
+    };
+
+    let (drop_fn, size_fn) = match style {
+        BindingStyle::Handle => {
+            let drop_fn = format_drop_ident!(ident);
+            let drop_fn = drop_fn.to_string();
+            (quote! { Some(#drop_fn.into()) }, quote! { None })
+        }
+        BindingStyle::Value => {
+            let size_fn = size_fn_ident(ident).to_string();
+            (quote! { None }, quote! { Some(#size_fn.into()) })
+        }
+        BindingStyle::Marshal(_) | BindingStyle::Custom | BindingStyle::Transparent(_) => {
+            (quote! { None }, quote! { None })
+        }
+    };
+
     let style = match style {
         BindingStyle::Handle => quote! {
             Handle
@@ -439,9 +1172,35 @@ fn describe_named_type(ident: &Ident, style: BindingStyle) -> TokenStream {
         BindingStyle::Value => quote! {
             Value(cs_bindgen::shared::schematic::describe::<#ident>())
         },
+
+        BindingStyle::Marshal(format) => quote! {
+            Marshal(
+                cs_bindgen::shared::MarshalFormat::#format,
+                cs_bindgen::shared::schematic::describe::<#ident>(),
+            )
+        },
+
+        BindingStyle::Custom => quote! {
+            Custom(cs_bindgen::marshal::describe_custom::<#ident>())
+        },
+
+        BindingStyle::Transparent(wrapper) => quote! {
+            Transparent(cs_bindgen::shared::Transparent {
+                inner: <<#ident as cs_bindgen::abi::Transparent>::Inner as cs_bindgen::abi::Abi>::repr(),
+                wrapper: #wrapper,
+            })
+        },
     };
 
     quote! {
+        impl cs_bindgen::abi::AbiRef for #ident {
+            type Storage = #abi_ref_storage;
+
+            unsafe fn from_abi_ref(abi: Self::Abi) -> Self::Storage {
+                <#abi_ref_storage>::new(abi)
+            }
+        }
+
         #[no_mangle]
         pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
             // NOTE: We need to import `schematic` so that usage of the `type_name!` macro
@@ -454,6 +1213,29 @@ fn describe_named_type(ident: &Ident, style: BindingStyle) -> TokenStream {
                 index_fn: #index_fn.into(),
                 convert_list_fn: #convert_list_fn.into(),
                 drop_vec_fn: #drop_vec_fn.into(),
+                drop_fn: #drop_fn,
+                size_fn: #size_fn,
+                renamed_fields: vec![#( #renamed_fields.into() ),*],
+                docs: #docs,
+                variant_docs: vec![#( #variant_docs ),*],
+                discriminant: #discriminant,
+                is_non_exhaustive: #is_non_exhaustive,
+                is_send: {
+                    #[allow(unused_imports)]
+                    use cs_bindgen::thread::{IsSend, NotSend};
+                    (&cs_bindgen::thread::Probe::<#ident>(std::marker::PhantomData)).is_send()
+                },
+                is_sync: {
+                    #[allow(unused_imports)]
+                    use cs_bindgen::thread::{IsSync, NotSync};
+                    (&cs_bindgen::thread::Probe::<#ident>(std::marker::PhantomData)).is_sync()
+                },
+                is_flags: {
+                    #[allow(unused_imports)]
+                    use cs_bindgen::flags::{IsFlags, NotFlags};
+                    (&cs_bindgen::flags::Probe::<#ident>(std::marker::PhantomData)).is_flags()
+                },
+                #std_impl_fns
             };
 
             std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
@@ -462,16 +1244,40 @@ fn describe_named_type(ident: &Ident, style: BindingStyle) -> TokenStream {
 }
 
 /// Generates an impl of `Named` for the specified type.
-fn impl_named(ident: &Ident) -> TokenStream {
+///
+/// Renamed types use their new name as their type name, so that all references to
+/// the type use the C# name.
+fn impl_named(ident: &Ident, rename: Option<&LitStr>) -> TokenStream {
+    let name = match rename {
+        Some(rename) => rename.value(),
+        None => ident.to_string(),
+    };
+
     quote! {
         impl cs_bindgen::shared::Named for #ident {
             fn type_name() -> cs_bindgen::shared::TypeName {
-                cs_bindgen::shared::TypeName::new(stringify!(#ident), module_path!())
+                cs_bindgen::shared::TypeName::new(#name, module_path!())
             }
         }
     }
 }
 
+/// Generates the value of the `rename` field for an exported function or method.
+fn quote_rename(rename: Option<&LitStr>) -> TokenStream {
+    match rename {
+        Some(rename) => quote! { Some(#rename.into()) },
+        None => quote! { None },
+    }
+}
+
+/// Generates the value of the `docs` field for an exported item.
+fn quote_docs(docs: Option<&str>) -> TokenStream {
+    match docs {
+        Some(docs) => quote! { Some(#docs.into()) },
+        None => quote! { None },
+    }
+}
+
 /// Generates an impl of the `repr` function in the `Abi` trait for the specified
 /// type.
 fn repr_impl(ident: &Ident) -> TokenStream {
@@ -517,7 +1323,7 @@ fn quote_index_fn(ty: &Ident) -> TokenStream {
             slice: cs_bindgen::abi::RawSlice<#ty>,
             index: usize,
         ) -> <#ty as cs_bindgen::abi::Abi>::Abi {
-            slice.convert_element(index)
+            cs_bindgen::panic::abort_on_panic(move || slice.convert_element(index))
         }
     }
 }
@@ -532,7 +1338,43 @@ fn quote_vec_drop_fn(ty: &Ident) -> TokenStream {
         #[no_mangle]
         #[allow(bad_style)]
         pub unsafe extern "C" fn #fn_ident(raw: cs_bindgen::abi::RawVec<#ty>) {
-            let _ = raw.into_vec();
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::drop_vec(raw))
+        }
+    }
+}
+
+fn size_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_size_of_{}", ty)
+}
+
+/// Generates a function that returns the size of the raw representation of a type that
+/// is marshaled by value.
+fn quote_size_fn(ty: &Ident) -> TokenStream {
+    let fn_ident = size_fn_ident(ty);
+    quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #fn_ident() -> usize {
+            std::mem::size_of::<<#ty as cs_bindgen::abi::Abi>::Abi>()
+        }
+    }
+}
+
+/// Generates the `AbiMut` impl for a type that is marshaled by value, which allows the
+/// type to be passed as a `&mut T` argument.
+fn quote_abi_mut_impl(ty: &Ident) -> TokenStream {
+    quote! {
+        impl cs_bindgen::abi::AbiMut for #ty {
+            type Abi = *mut <#ty as cs_bindgen::abi::Abi>::Abi;
+            type Storage = cs_bindgen::abi::MutArg<#ty>;
+
+            fn repr() -> cs_bindgen::shared::Repr {
+                cs_bindgen::shared::Repr::RefMut(Box::new(<#ty as cs_bindgen::abi::Abi>::repr()))
+            }
+
+            unsafe fn from_abi_mut(abi: Self::Abi) -> Self::Storage {
+                cs_bindgen::abi::MutArg::new(abi)
+            }
         }
     }
 }
@@ -547,7 +1389,7 @@ fn quote_convert_list_fn(ty: &Ident) -> TokenStream {
         #[no_mangle]
         #[allow(bad_style)]
         pub unsafe extern "C" fn #fn_ident(raw: cs_bindgen::abi::RawSlice<<#ty as cs_bindgen::abi::Abi>::Abi>) -> cs_bindgen::abi::RawVec<#ty> {
-            cs_bindgen::abi::convert_list(raw)
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::convert_list(raw))
         }
     }
 }
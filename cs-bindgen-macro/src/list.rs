@@ -0,0 +1,212 @@
+//! Helper functions for passing lists whose elements aren't primitives.
+//!
+//! C# converts a `RawVec<T>` one element at a time, using a function that converts
+//! the element at an index to its raw representation, and then frees the vec with a
+//! function that drops it. Lists passed to Rust are converted the other way, with a
+//! function that converts a slice of raw elements into a `RawVec<T>`. These functions
+//! have to be exported from the dylib for every element type, which means that they
+//! can't be generic.
+//!
+//! The runtime exports the functions for primitives, and each exported type generates
+//! the functions for itself. For any other element type, e.g. the `Vec<String>` in a
+//! `Vec<Vec<String>>`, the functions are generated by the item that uses the list type,
+//! along with a describe function that tells the generated C# which element type they
+//! convert. Since the functions are named after the item, the same element type can
+//! end up with several copies of the functions, and the C# uses whichever one it finds
+//! first.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::*;
+use syn::*;
+
+/// The names of the primitive types, which use the list functions exported by the
+/// runtime.
+const PRIMITIVES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize", "f32", "f64", "bool",
+    "char",
+];
+
+/// Generates the list functions for the element types of any lists used in `types`.
+///
+/// `item` is the name of the item that uses the types, e.g. the mangled name of an
+/// exported function, which is used to give the functions unique names.
+pub fn quote_list_fns<'a>(item: &str, types: impl IntoIterator<Item = &'a Type>) -> TokenStream {
+    let mut elements = Vec::new();
+    for ty in types {
+        collect_elements(ty, &mut elements);
+    }
+
+    elements
+        .iter()
+        .enumerate()
+        .map(|(index, element)| quote_element_fns(&format!("{}__{}", item, index), element))
+        .collect()
+}
+
+fn quote_element_fns(name: &str, element: &Type) -> TokenStream {
+    let index_fn = format_ident!("__cs_bindgen_generated_index__{}", name);
+    let convert_list_fn = format_ident!("__cs_bindgen_generated_convert_vec__{}", name);
+    let drop_vec_fn = format_ident!("__cs_bindgen_generated_drop_vec__{}", name);
+    let describe_fn = format_describe_ident!(format!("list__{}", name));
+
+    let index_name = index_fn.to_string();
+    let convert_list_name = convert_list_fn.to_string();
+    let drop_vec_name = drop_vec_fn.to_string();
+
+    quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #index_fn(
+            slice: cs_bindgen::abi::RawSlice<#element>,
+            index: usize,
+        ) -> <#element as cs_bindgen::abi::Abi>::Abi {
+            cs_bindgen::panic::abort_on_panic(move || slice.convert_element(index))
+        }
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #convert_list_fn(
+            raw: cs_bindgen::abi::RawSlice<<#element as cs_bindgen::abi::Abi>::Abi>,
+        ) -> cs_bindgen::abi::RawVec<#element> {
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::convert_list(raw))
+        }
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #drop_vec_fn(raw: cs_bindgen::abi::RawVec<#element>) {
+            cs_bindgen::panic::abort_on_panic(move || cs_bindgen::abi::drop_vec(raw))
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_fn() -> Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::ListElement {
+                element: <#element as cs_bindgen::abi::Abi>::repr(),
+                index_fn: #index_name.into(),
+                drop_vec_fn: #drop_vec_name.into(),
+                convert_list_fn: #convert_list_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+    }
+}
+
+/// Collects the element types of the `Vec<T>` and `&[T]` types within `ty`, including
+/// lists nested inside other types, e.g. both `Vec<String>` and `String` for a
+/// `Vec<Vec<String>>`.
+///
+/// Primitives are skipped, as are borrowed elements (e.g. `&str`), since the functions
+/// are declared outside of the item and so can't name its lifetimes. Each element type
+/// is only included once.
+fn collect_elements(ty: &Type, elements: &mut Vec<Type>) {
+    match ty {
+        Type::Path(path) => {
+            for segment in &path.path.segments {
+                let args = match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => args,
+                    PathArguments::Parenthesized(args) => {
+                        for input in &args.inputs {
+                            collect_elements(input, elements);
+                        }
+
+                        if let ReturnType::Type(_, output) = &args.output {
+                            collect_elements(output, elements);
+                        }
+
+                        continue;
+                    }
+                    PathArguments::None => continue,
+                };
+
+                for arg in &args.args {
+                    match arg {
+                        GenericArgument::Type(arg) => {
+                            if segment.ident == "Vec" {
+                                push_element(arg, elements);
+                            }
+
+                            collect_elements(arg, elements);
+                        }
+
+                        GenericArgument::Binding(binding) => {
+                            collect_elements(&binding.ty, elements)
+                        }
+
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Type::Reference(reference) => {
+            if let Type::Slice(slice) = &*reference.elem {
+                push_element(&slice.elem, elements);
+            }
+
+            collect_elements(&reference.elem, elements);
+        }
+
+        Type::Slice(slice) => collect_elements(&slice.elem, elements),
+        Type::Array(array) => collect_elements(&array.elem, elements),
+        Type::Paren(paren) => collect_elements(&paren.elem, elements),
+        Type::Group(group) => collect_elements(&group.elem, elements),
+
+        Type::Tuple(tuple) => {
+            for elem in &tuple.elems {
+                collect_elements(elem, elements);
+            }
+        }
+
+        Type::ImplTrait(TypeImplTrait { bounds, .. })
+        | Type::TraitObject(TypeTraitObject { bounds, .. }) => {
+            for bound in bounds {
+                if let TypeParamBound::Trait(bound) = bound {
+                    collect_elements(
+                        &Type::Path(TypePath {
+                            qself: None,
+                            path: bound.path.clone(),
+                        }),
+                        elements,
+                    );
+                }
+            }
+        }
+
+        _ => {}
+    }
+}
+
+fn push_element(element: &Type, elements: &mut Vec<Type>) {
+    let tokens = element.to_token_stream().to_string();
+    if is_primitive(element)
+        || is_unnameable(element.to_token_stream())
+        || elements
+            .iter()
+            .any(|other| other.to_token_stream().to_string() == tokens)
+    {
+        return;
+    }
+
+    elements.push(element.clone());
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .map_or(false, |ident| PRIMITIVES.iter().any(|prim| ident == prim)),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the type contains a reference, a lifetime, or any other type that
+/// can't be named outside of the item that uses it.
+fn is_unnameable(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punct) => punct.as_char() == '&' || punct.as_char() == '\'',
+        TokenTree::Ident(ident) => ident == "impl" || ident == "dyn" || ident == "Self",
+        TokenTree::Group(group) => is_unnameable(group.stream()),
+        TokenTree::Literal(_) => false,
+    })
+}
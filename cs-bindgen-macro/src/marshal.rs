@@ -0,0 +1,89 @@
+//! Bindings for types exported with `#[cs_bindgen(marshal = "...")]`.
+//!
+//! Marshaled types are serialized with serde instead of being converted field by
+//! field, so the generated `Abi` impl only needs to call the serialization functions
+//! in `cs_bindgen::marshal` for the chosen format. The type must implement
+//! `Serialize` and `Deserialize` in addition to having its schema described, which
+//! is used to generate the C# class that the value is deserialized into.
+
+use crate::{
+    quote_abi_mut_impl, quote_convert_list_fn, quote_index_fn, quote_vec_drop_fn, repr_impl,
+};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// The serialization formats supported with `marshal = "..."`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarshalFormat {
+    Json,
+    MessagePack,
+}
+
+impl MarshalFormat {
+    pub fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match &*lit.value() {
+            "json" => Ok(MarshalFormat::Json),
+            "msgpack" => Ok(MarshalFormat::MessagePack),
+            format => Err(Error::new_spanned(
+                lit,
+                format!(
+                    "Unknown marshaling format `{}`, expected `\"json\"` or `\"msgpack\"`",
+                    format
+                ),
+            )),
+        }
+    }
+}
+
+/// Quotes the name of the matching `MarshalFormat` variant in `cs_bindgen::shared`.
+impl ToTokens for MarshalFormat {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            MarshalFormat::Json => format_ident!("Json"),
+            MarshalFormat::MessagePack => format_ident!("MessagePack"),
+        };
+
+        variant.to_tokens(tokens);
+    }
+}
+
+/// Generates the `Abi` impl for a marshaled type, along with the helper bindings
+/// needed to pass collections of the type.
+pub fn quote_marshal_impls(ident: &Ident, format: MarshalFormat) -> TokenStream {
+    let (to_fn, from_fn) = match format {
+        MarshalFormat::Json => (format_ident!("to_json"), format_ident!("from_json")),
+        MarshalFormat::MessagePack => (format_ident!("to_msgpack"), format_ident!("from_msgpack")),
+    };
+
+    let repr_fn = repr_impl(ident);
+    let abi_mut_impl = quote_abi_mut_impl(ident);
+    let index_fn = quote_index_fn(ident);
+    let convert_list_fn = quote_convert_list_fn(ident);
+    let vec_drop_fn = quote_vec_drop_fn(ident);
+
+    quote! {
+        impl cs_bindgen::abi::Abi for #ident {
+            type Abi = cs_bindgen::abi::RawVec<u8>;
+
+            #repr_fn
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                cs_bindgen::marshal::#from_fn(abi)
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                cs_bindgen::marshal::#to_fn(self)
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                cs_bindgen::marshal::#to_fn(&self)
+            }
+        }
+
+        #abi_mut_impl
+        #index_fn
+        #convert_list_fn
+        #vec_drop_fn
+    }
+}
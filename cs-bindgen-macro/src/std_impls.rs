@@ -0,0 +1,689 @@
+//! Bindings for standard library traits implemented by exported types.
+//!
+//! The macro can't see which traits a type implements, so they're detected from the
+//! `#[derive(..)]` attribute on the type. For traits that can't be derived (or that
+//! are implemented manually), the user can indicate that the type implements the
+//! trait with an argument to the attribute, e.g. `#[cs_bindgen(display)]`. Manual
+//! impls can instead be tagged with `#[cs_bindgen]` directly, which exports the
+//! binding along with a separate description of the impl.
+//!
+//! A binding function is exported for each supported trait, and its name is included
+//! in the description of the type so that the generated C# can call it.
+//!
+//! `From` and `TryFrom` impls tagged with `#[cs_bindgen]` are exported as conversions
+//! instead, which are generated as C# conversion operators. Similarly, impls of the
+//! arithmetic and bitwise operator traits (e.g. `Add` and `Neg`) are generated as C#
+//! operator overloads.
+
+use crate::{
+    attr::Attributes, extract_type_ident, generic, has_derive, list, quote_binding_fn, BindingStyle,
+};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// The standard traits implemented by an exported type.
+#[derive(Debug, Default)]
+pub struct StdImpls {
+    pub default: bool,
+    pub display: bool,
+    pub eq: bool,
+    pub hash: bool,
+    pub ord: bool,
+    pub clone: bool,
+
+    /// Whether the type is a shared handle, in which case cloning the handle shares the
+    /// value rather than cloning it.
+    pub shared: bool,
+}
+
+impl StdImpls {
+    /// Determines which traits are implemented based on the attributes on the type and
+    /// the arguments to `#[cs_bindgen]`.
+    ///
+    /// `Clone` is only exported for handle types, since types marshaled by value are
+    /// already copied when passed to C#. Shared handles can always be cloned, since
+    /// the clone only increments the reference count.
+    pub fn from_attrs(
+        attrs: &[Attribute],
+        attr: &Attributes,
+        style: BindingStyle,
+    ) -> syn::Result<Self> {
+        Ok(StdImpls {
+            default: attr.default.is_some() || has_derive(attrs, "Default")?,
+            display: attr.display.is_some(),
+            eq: attr.eq.is_some() || has_derive(attrs, "PartialEq")?,
+            hash: attr.hash.is_some() || has_derive(attrs, "Hash")?,
+            ord: attr.ord.is_some() || has_derive(attrs, "PartialOrd")?,
+            clone: style == BindingStyle::Handle
+                && (attr.shared.is_some() || attr.clone.is_some() || has_derive(attrs, "Clone")?),
+            shared: attr.shared.is_some(),
+        })
+    }
+
+    /// Generates the binding functions for each of the implemented traits.
+    pub fn quote_binding_fns(&self, ident: &Ident, style: BindingStyle) -> TokenStream {
+        let self_ty = quote_self_ty(ident, style);
+        let self_input = quote! { self_: <#self_ty as cs_bindgen::abi::Abi>::Abi };
+        let convert_self = quote! {
+            let self_ = <#self_ty as cs_bindgen::abi::Abi>::from_abi(self_);
+        };
+
+        let default_fn = if self.default {
+            Some(quote_binding_fn(
+                &default_fn_ident(ident),
+                std::iter::empty(),
+                quote! { -> <#ident as cs_bindgen::abi::Abi>::Abi },
+                quote! {
+                    cs_bindgen::abi::Abi::into_abi(<#ident as std::default::Default>::default())
+                },
+            ))
+        } else {
+            None
+        };
+
+        let display_fn = if self.display {
+            Some(quote_binding_fn(
+                &display_fn_ident(ident),
+                std::iter::once(self_input.clone()),
+                quote! { -> cs_bindgen::abi::RawString },
+                quote! {
+                    #convert_self
+                    cs_bindgen::abi::Abi::into_abi(std::string::ToString::to_string(&self_))
+                },
+            ))
+        } else {
+            None
+        };
+
+        let eq_fn = if self.eq {
+            Some(quote_binding_fn(
+                &eq_fn_ident(ident),
+                vec![
+                    self_input.clone(),
+                    quote! { other: <#self_ty as cs_bindgen::abi::Abi>::Abi },
+                ]
+                .into_iter(),
+                quote! { -> <bool as cs_bindgen::abi::Abi>::Abi },
+                quote! {
+                    #convert_self
+                    let other = <#self_ty as cs_bindgen::abi::Abi>::from_abi(other);
+                    cs_bindgen::abi::Abi::into_abi(self_ == other)
+                },
+            ))
+        } else {
+            None
+        };
+
+        let hash_fn = if self.hash {
+            Some(quote_binding_fn(
+                &hash_fn_ident(ident),
+                std::iter::once(self_input.clone()),
+                quote! { -> u64 },
+                quote! {
+                    use std::hash::{Hash, Hasher};
+
+                    #convert_self
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    self_.hash(&mut hasher);
+                    hasher.finish()
+                },
+            ))
+        } else {
+            None
+        };
+
+        // NOTE: `IComparable<T>` requires a total order, so values that `partial_cmp`
+        // reports as incomparable (e.g. a field containing `NaN`) cause a panic, which is
+        // rethrown as an exception in C#.
+        let cmp_fn = if self.ord {
+            Some(quote_binding_fn(
+                &cmp_fn_ident(ident),
+                vec![
+                    self_input,
+                    quote! { other: <#self_ty as cs_bindgen::abi::Abi>::Abi },
+                ]
+                .into_iter(),
+                quote! { -> i32 },
+                quote! {
+                    #convert_self
+                    let other = <#self_ty as cs_bindgen::abi::Abi>::from_abi(other);
+                    match std::cmp::PartialOrd::partial_cmp(&self_, &other) {
+                        Some(std::cmp::Ordering::Less) => -1,
+                        Some(std::cmp::Ordering::Equal) => 0,
+                        Some(std::cmp::Ordering::Greater) => 1,
+                        None => panic!("Values of type `{}` are not comparable", stringify!(#ident)),
+                    }
+                },
+            ))
+        } else {
+            None
+        };
+
+        let clone_fn = if self.shared {
+            Some(quote_binding_fn(
+                &clone_fn_ident(ident),
+                std::iter::once(self_input),
+                quote! { -> <#ident as cs_bindgen::abi::Abi>::Abi },
+                quote! {
+                    // NOTE: The handle passed in is still owned by the C# object, so we
+                    // can't let the reconstructed `Arc` decrement the reference count.
+                    let arc = std::mem::ManuallyDrop::new(std::sync::Arc::from_raw(self_));
+                    cs_bindgen::diagnostics::handle_created::<#ident>();
+                    std::sync::Arc::into_raw(std::sync::Arc::clone(&arc))
+                },
+            ))
+        } else if self.clone {
+            Some(quote_binding_fn(
+                &clone_fn_ident(ident),
+                std::iter::once(self_input),
+                quote! { -> <#ident as cs_bindgen::abi::Abi>::Abi },
+                quote! {
+                    #convert_self
+                    cs_bindgen::abi::Abi::into_abi(std::clone::Clone::clone(self_))
+                },
+            ))
+        } else {
+            None
+        };
+
+        quote! {
+            #default_fn
+            #display_fn
+            #eq_fn
+            #hash_fn
+            #cmp_fn
+            #clone_fn
+        }
+    }
+
+    /// Generates the fields of the `NamedType` description that specify the names of
+    /// the binding functions.
+    pub fn quote_describe_fields(&self, ident: &Ident) -> TokenStream {
+        let default_fn = describe_fn_name(self.default, default_fn_ident(ident));
+        let display_fn = describe_fn_name(self.display, display_fn_ident(ident));
+        let eq_fn = describe_fn_name(self.eq, eq_fn_ident(ident));
+        let hash_fn = describe_fn_name(self.hash, hash_fn_ident(ident));
+        let cmp_fn = describe_fn_name(self.ord, cmp_fn_ident(ident));
+        let clone_fn = describe_fn_name(self.clone, clone_fn_ident(ident));
+        quote! {
+            default_fn: #default_fn,
+            display_fn: #display_fn,
+            eq_fn: #eq_fn,
+            hash_fn: #hash_fn,
+            cmp_fn: #cmp_fn,
+            clone_fn: #clone_fn,
+        }
+    }
+}
+
+/// Quotes the type used to pass the receiver to binding functions.
+///
+/// Handle types are passed by reference, since the C# object retains ownership of the
+/// value. Types that are marshaled by value are `Copy`, and marshaled types are
+/// deserialized into a new value, so they're passed directly.
+fn quote_self_ty(ident: &Ident, style: BindingStyle) -> TokenStream {
+    match style {
+        BindingStyle::Handle => quote! { &#ident },
+        BindingStyle::Value
+        | BindingStyle::Marshal(_)
+        | BindingStyle::Custom
+        | BindingStyle::Transparent(_) => quote! { #ident },
+    }
+}
+
+fn describe_fn_name(implemented: bool, ident: Ident) -> TokenStream {
+    if implemented {
+        let name = ident.to_string();
+        quote! { Some(#name.into()) }
+    } else {
+        quote! { None }
+    }
+}
+
+fn default_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_default__{}", ty)
+}
+
+fn display_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_display__{}", ty)
+}
+
+fn eq_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_eq__{}", ty)
+}
+
+fn hash_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_hash__{}", ty)
+}
+
+fn cmp_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_cmp__{}", ty)
+}
+
+fn clone_fn_ident(ty: &Ident) -> Ident {
+    format_ident!("__cs_bindgen_generated_clone__{}", ty)
+}
+
+/// The standard library traits that can be exported with an `impl` block tagged with
+/// `#[cs_bindgen]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdTrait {
+    Default,
+    Display,
+    PartialEq,
+    Hash,
+    PartialOrd,
+    Clone,
+}
+
+/// Returns the standard library trait implemented by a trait impl, or `None` if the
+/// trait isn't one of the supported standard traits.
+///
+/// The trait is identified by the last segment of its path, so `Display`,
+/// `fmt::Display`, and `std::fmt::Display` are all supported. Only impls comparing a
+/// type with itself are supported, e.g. `PartialEq` and not `PartialEq<Other>`.
+pub fn std_trait(trait_: &Path) -> syn::Result<Option<StdTrait>> {
+    let segment = match trait_.segments.last() {
+        Some(segment) => segment,
+        None => return Ok(None),
+    };
+
+    let std_trait = match &*segment.ident.to_string() {
+        "Default" => StdTrait::Default,
+        "Display" => StdTrait::Display,
+        "PartialEq" => StdTrait::PartialEq,
+        "Hash" => StdTrait::Hash,
+        "PartialOrd" => StdTrait::PartialOrd,
+        "Clone" => StdTrait::Clone,
+        _ => return Ok(None),
+    };
+
+    if !segment.arguments.is_empty() {
+        return Err(Error::new_spanned(
+            &segment.arguments,
+            "Only impls comparing a type with itself are supported with `#[cs_bindgen]`",
+        ));
+    }
+
+    Ok(Some(std_trait))
+}
+
+/// Generates the bindings for an impl of a standard library trait.
+///
+/// Exports the same binding function that would be generated if the trait was
+/// specified on the type, e.g. with `#[derive(..)]` or `#[cs_bindgen(display)]`, along
+/// with a description of the impl that identifies the binding for the type. Since the
+/// impl doesn't know how the type is passed to C#, the receiver is converted with
+/// `AbiRef`.
+pub fn quote_std_trait_impl(std_trait: StdTrait, self_ty: &Type) -> syn::Result<TokenStream> {
+    let self_ident = extract_type_ident(self_ty)?;
+    let self_input = quote! { self_: <#self_ty as cs_bindgen::abi::Abi>::Abi };
+    let other_input = quote! { other: <#self_ty as cs_bindgen::abi::Abi>::Abi };
+    let convert_self = quote! {
+        let self_ = <#self_ty as cs_bindgen::abi::AbiRef>::from_abi_ref(self_);
+    };
+    let convert_other = quote! {
+        let other = <#self_ty as cs_bindgen::abi::AbiRef>::from_abi_ref(other);
+    };
+
+    let (trait_name, inputs, return_decl, body) = match std_trait {
+        StdTrait::Default => (
+            "Default",
+            vec![],
+            quote! { -> <#self_ty as cs_bindgen::abi::Abi>::Abi },
+            quote! {
+                cs_bindgen::abi::Abi::into_abi(<#self_ty as std::default::Default>::default())
+            },
+        ),
+
+        StdTrait::Display => (
+            "Display",
+            vec![self_input],
+            quote! { -> cs_bindgen::abi::RawString },
+            quote! {
+                #convert_self
+                cs_bindgen::abi::Abi::into_abi(std::string::ToString::to_string(&*self_))
+            },
+        ),
+
+        StdTrait::PartialEq => (
+            "PartialEq",
+            vec![self_input, other_input],
+            quote! { -> <bool as cs_bindgen::abi::Abi>::Abi },
+            quote! {
+                #convert_self
+                #convert_other
+                cs_bindgen::abi::Abi::into_abi(*self_ == *other)
+            },
+        ),
+
+        StdTrait::Hash => (
+            "Hash",
+            vec![self_input],
+            quote! { -> u64 },
+            quote! {
+                use std::hash::{Hash, Hasher};
+
+                #convert_self
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                (*self_).hash(&mut hasher);
+                hasher.finish()
+            },
+        ),
+
+        // NOTE: As with `#[derive(PartialOrd)]`, values that aren't comparable cause a
+        // panic, since `IComparable<T>` requires a total order.
+        StdTrait::PartialOrd => (
+            "PartialOrd",
+            vec![self_input, other_input],
+            quote! { -> i32 },
+            quote! {
+                #convert_self
+                #convert_other
+                match std::cmp::PartialOrd::partial_cmp(&*self_, &*other) {
+                    Some(std::cmp::Ordering::Less) => -1,
+                    Some(std::cmp::Ordering::Equal) => 0,
+                    Some(std::cmp::Ordering::Greater) => 1,
+                    None => panic!("Values of type `{}` are not comparable", stringify!(#self_ty)),
+                }
+            },
+        ),
+
+        StdTrait::Clone => (
+            "Clone",
+            vec![self_input],
+            quote! { -> <#self_ty as cs_bindgen::abi::Abi>::Abi },
+            quote! {
+                #convert_self
+                cs_bindgen::abi::Abi::into_abi(std::clone::Clone::clone(&*self_))
+            },
+        ),
+    };
+
+    let binding_ident = format_ident!("__cs_bindgen_generated_impl_{}__{}", trait_name, self_ident);
+    let binding_name = binding_ident.to_string();
+    let binding_fn = quote_binding_fn(&binding_ident, inputs.into_iter(), return_decl, body);
+
+    let trait_ident = format_ident!("{}", trait_name);
+    let describe_ident = format_describe_ident!(format!("impl__{}__{}", trait_name, self_ident));
+
+    Ok(quote! {
+        #binding_fn
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::StdImpl {
+                self_type: <#self_ty as cs_bindgen::shared::Named>::type_name(),
+                trait_: cs_bindgen::shared::StdTrait::#trait_ident,
+                binding: #binding_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+    })
+}
+
+/// The conversion traits that can be exported with an `impl` block tagged with
+/// `#[cs_bindgen]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionTrait {
+    From,
+    TryFrom,
+}
+
+/// Returns the conversion trait implemented by a trait impl along with the type that's
+/// converted from, or `None` if the trait isn't `From` or `TryFrom`.
+///
+/// As with [`std_trait`], the trait is identified by the last segment of its path.
+///
+/// [`std_trait`]: fn.std_trait.html
+pub fn conversion_trait(trait_: &Path) -> syn::Result<Option<(ConversionTrait, &Type)>> {
+    let segment = match trait_.segments.last() {
+        Some(segment) => segment,
+        None => return Ok(None),
+    };
+
+    let conversion = match &*segment.ident.to_string() {
+        "From" => ConversionTrait::From,
+        "TryFrom" => ConversionTrait::TryFrom,
+        _ => return Ok(None),
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(from_ty) => Ok(Some((conversion, from_ty))),
+            arg => Err(Error::new_spanned(
+                arg,
+                "Expected the type being converted from",
+            )),
+        },
+
+        _ => Err(Error::new_spanned(
+            segment,
+            "Conversion traits must specify the type being converted from, e.g. `From<u8>`",
+        )),
+    }
+}
+
+/// Generates the bindings for an impl of `From` or `TryFrom`.
+///
+/// The binding function takes the value being converted and returns the converted
+/// value. For `TryFrom`, the binding instead returns an `Option` that's `None` if the
+/// conversion failed, since the error type doesn't necessarily implement `Abi`.
+pub fn quote_conversion_impl(
+    conversion: ConversionTrait,
+    from_ty: &Type,
+    self_ty: &Type,
+) -> syn::Result<TokenStream> {
+    let self_ident = extract_type_ident(self_ty)?;
+    let mangled_name = format!(
+        "{:?}_{}__{}",
+        conversion,
+        generic::mangle_types(std::iter::once(from_ty)),
+        self_ident,
+    );
+
+    let (fallible, return_decl, convert) = match conversion {
+        ConversionTrait::From => (
+            false,
+            quote! { -> <#self_ty as cs_bindgen::abi::Abi>::Abi },
+            quote! { <#self_ty as std::convert::From<#from_ty>>::from(value) },
+        ),
+
+        ConversionTrait::TryFrom => (
+            true,
+            quote! { -> <std::option::Option<#self_ty> as cs_bindgen::abi::Abi>::Abi },
+            quote! { <#self_ty as std::convert::TryFrom<#from_ty>>::try_from(value).ok() },
+        ),
+    };
+
+    let binding_ident = format_ident!("__cs_bindgen_generated_impl_{}", mangled_name);
+    let binding_name = binding_ident.to_string();
+    let binding_fn = quote_binding_fn(
+        &binding_ident,
+        std::iter::once(quote! { value: <#from_ty as cs_bindgen::abi::Abi>::Abi }),
+        return_decl,
+        quote! {
+            let value = <#from_ty as cs_bindgen::abi::Abi>::from_abi(value);
+            cs_bindgen::abi::Abi::into_abi(#convert)
+        },
+    );
+
+    let describe_ident = format_describe_ident!(format!("impl__{}", mangled_name));
+    let list_fns = list::quote_list_fns(&mangled_name, vec![from_ty, self_ty]);
+
+    Ok(quote! {
+        #binding_fn
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::Conversion {
+                from: <#from_ty as cs_bindgen::abi::Abi>::repr(),
+                into: <#self_ty as cs_bindgen::abi::Abi>::repr(),
+                fallible: #fallible,
+                binding: #binding_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+
+        #list_fns
+    })
+}
+
+/// The operator traits in `std::ops` that can be exported with an `impl` block tagged
+/// with `#[cs_bindgen]`, along with the name of the trait's method and whether it's a
+/// binary operator.
+const OPERATOR_TRAITS: &[(&str, &str, bool)] = &[
+    ("Add", "add", true),
+    ("Sub", "sub", true),
+    ("Mul", "mul", true),
+    ("Div", "div", true),
+    ("Rem", "rem", true),
+    ("BitAnd", "bitand", true),
+    ("BitOr", "bitor", true),
+    ("BitXor", "bitxor", true),
+    ("Neg", "neg", false),
+    ("Not", "not", false),
+];
+
+/// An impl of one of the operator traits in `std::ops`.
+pub struct OperatorImpl<'a> {
+    trait_name: &'static str,
+    method: &'static str,
+
+    /// The type of the right-hand operand, or `None` for unary operators.
+    rhs: Option<&'a Type>,
+}
+
+/// Returns the operator trait implemented by a trait impl, or `None` if the trait isn't
+/// one of the supported operator traits.
+///
+/// As with [`std_trait`], the trait is identified by the last segment of its path. The
+/// right-hand operand of a binary operator defaults to the self type, the same as for
+/// the trait itself.
+///
+/// [`std_trait`]: fn.std_trait.html
+pub fn operator_trait<'a>(
+    trait_: &'a Path,
+    self_ty: &'a Type,
+) -> syn::Result<Option<OperatorImpl<'a>>> {
+    let segment = match trait_.segments.last() {
+        Some(segment) => segment,
+        None => return Ok(None),
+    };
+
+    let ident = segment.ident.to_string();
+    let (trait_name, method, binary) =
+        match OPERATOR_TRAITS.iter().find(|(name, ..)| *name == ident) {
+            Some(&operator) => operator,
+            None => return Ok(None),
+        };
+
+    let rhs = match &segment.arguments {
+        PathArguments::None if binary => Some(self_ty),
+        PathArguments::None => None,
+        PathArguments::AngleBracketed(args) if binary && args.args.len() == 1 => {
+            match &args.args[0] {
+                GenericArgument::Type(rhs) => Some(rhs),
+                arg => {
+                    return Err(Error::new_spanned(
+                        arg,
+                        "Expected the type of the right-hand operand",
+                    ))
+                }
+            }
+        }
+        arguments => {
+            return Err(Error::new_spanned(
+                arguments,
+                "Unexpected arguments for the operator trait",
+            ))
+        }
+    };
+
+    Ok(Some(OperatorImpl {
+        trait_name,
+        method,
+        rhs,
+    }))
+}
+
+/// Generates the bindings for an impl of an operator trait.
+///
+/// The binding function takes the operands by value and returns the output of the
+/// operator.
+pub fn quote_operator_impl(operator: OperatorImpl<'_>, self_ty: &Type) -> syn::Result<TokenStream> {
+    let OperatorImpl {
+        trait_name,
+        method,
+        rhs,
+    } = operator;
+
+    let self_ident = extract_type_ident(self_ty)?;
+    let mangled_name = match rhs {
+        Some(rhs) => format!(
+            "{}_{}__{}",
+            trait_name,
+            generic::mangle_types(std::iter::once(rhs)),
+            self_ident,
+        ),
+        None => format!("{}__{}", trait_name, self_ident),
+    };
+
+    let trait_ident = format_ident!("{}", trait_name);
+    let method_ident = format_ident!("{}", method);
+    let trait_path = match rhs {
+        Some(rhs) => quote! { std::ops::#trait_ident<#rhs> },
+        None => quote! { std::ops::#trait_ident },
+    };
+    let output_ty = quote! { <#self_ty as #trait_path>::Output };
+
+    let mut inputs = vec![quote! { lhs: <#self_ty as cs_bindgen::abi::Abi>::Abi }];
+    let mut args = vec![quote! { lhs }];
+    let mut convert = vec![quote! { let lhs = <#self_ty as cs_bindgen::abi::Abi>::from_abi(lhs); }];
+    if let Some(rhs) = rhs {
+        inputs.push(quote! { rhs: <#rhs as cs_bindgen::abi::Abi>::Abi });
+        args.push(quote! { rhs });
+        convert.push(quote! { let rhs = <#rhs as cs_bindgen::abi::Abi>::from_abi(rhs); });
+    }
+
+    let binding_ident = format_ident!("__cs_bindgen_generated_impl_{}", mangled_name);
+    let binding_name = binding_ident.to_string();
+    let binding_fn = quote_binding_fn(
+        &binding_ident,
+        inputs.into_iter(),
+        quote! { -> <#output_ty as cs_bindgen::abi::Abi>::Abi },
+        quote! {
+            #( #convert )*
+            cs_bindgen::abi::Abi::into_abi(<#self_ty as #trait_path>::#method_ident(#( #args ),*))
+        },
+    );
+
+    let describe_rhs = match rhs {
+        Some(rhs) => quote! { Some(<#rhs as cs_bindgen::abi::Abi>::repr()) },
+        None => quote! { None },
+    };
+    let describe_ident = format_describe_ident!(format!("impl__{}", mangled_name));
+    let list_fns = list::quote_list_fns(&mangled_name, std::iter::once(self_ty).chain(rhs));
+
+    Ok(quote! {
+        #binding_fn
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::Operator {
+                trait_: cs_bindgen::shared::OperatorTrait::#trait_ident,
+                lhs: <#self_ty as cs_bindgen::abi::Abi>::repr(),
+                rhs: #describe_rhs,
+                output: <#output_ty as cs_bindgen::abi::Abi>::repr(),
+                binding: #binding_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+
+        #list_fns
+    })
+}
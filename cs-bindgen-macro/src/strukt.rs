@@ -1,24 +1,264 @@
 use crate::{
-    describe_named_type, handle, has_derive_copy, impl_named, quote_convert_list_fn,
-    quote_index_fn, quote_vec_drop_fn, reject_generics, repr_impl, value, BindingStyle,
+    attr::{doc_comment, member_attrs, Attributes},
+    custom::{self, CustomMarshal},
+    describe_named_type, described_field_ty,
+    generic::Instantiation,
+    handle, has_derive, impl_named, list, marshal, quote_abi_mut_impl, quote_convert_list_fn,
+    quote_index_fn, quote_size_fn, quote_vec_drop_fn, reject_generics, repr_impl,
+    std_impls::StdImpls,
+    support::{check_type, TypePosition},
+    value, BindingStyle,
 };
 use proc_macro2::{Literal, TokenStream};
 use quote::*;
-use syn::*;
+use syn::{punctuated::Punctuated, token::Comma, *};
+
+/// The integer types that can be used to store the bits of a flags type.
+const FLAGS_TYPES: &[&str] = &["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64"];
 
 /// Generates the bindings for an exported struct.
-pub fn quote_struct_item(item: ItemStruct) -> syn::Result<TokenStream> {
+pub fn quote_struct_item(item: ItemStruct, attr: &Attributes) -> syn::Result<TokenStream> {
+    if !attr.instantiate.is_empty() {
+        return quote_struct_instantiations(item, attr);
+    }
+
     reject_generics(
         &item.generics,
-        "Generic structs are not supported with `#[cs_bindgen]`",
+        "Generic structs must list their instantiations with `#[cs_bindgen(instantiate(..))]`",
     )?;
 
+    // Types with custom conversion functions are passed to C# as their raw value, so
+    // none of the other bindings for structs apply to them.
+    if let Some(custom) = CustomMarshal::from_attrs(attr)? {
+        if let Some(arg) = attr.struct_arg() {
+            return Err(Error::new_spanned(
+                arg,
+                "Not supported on types exported with custom conversion functions",
+            ));
+        }
+
+        for field in &item.fields {
+            let attrs = member_attrs(&field.attrs)?;
+            let arg = attrs
+                .rename
+                .as_ref()
+                .map(ToTokens::to_token_stream)
+                .or_else(|| attrs.skip.as_ref().map(ToTokens::to_token_stream));
+            if let Some(arg) = arg {
+                return Err(Error::new_spanned(
+                    arg,
+                    "Fields of types with custom conversion functions aren't exported, so they can't be renamed or skipped",
+                ));
+            }
+        }
+
+        let docs = doc_comment(&item.attrs);
+        return Ok(custom::quote_custom_type(
+            &item.ident,
+            &custom,
+            attr.rename.as_ref(),
+            docs.as_deref(),
+        ));
+    }
+
     let repr_fn = repr_impl(&item.ident);
 
-    // Determine whether we should marshal the type as a handle or by value.
-    if has_derive_copy(&item.attrs)? {
-        let named_impl = impl_named(&item.ident);
-        let describe_impl = describe_struct(&item);
+    // Explicitly transparent newtypes can't also be flags or shared handles, but
+    // `#[repr(transparent)]` structs can still opt in to either.
+    if attr.transparent.is_some() {
+        if let Some(arg) = attr.flags.as_ref().or(attr.shared.as_ref()) {
+            return Err(Error::new_spanned(
+                arg,
+                "Not supported on structs exported with `transparent`",
+            ));
+        }
+    }
+
+    let is_transparent = attr.transparent.is_some()
+        || (attr.flags.is_none() && attr.shared.is_none() && has_repr_transparent(&item.attrs)?);
+
+    if let (Some(wrapper), false) = (&attr.wrapper, is_transparent) {
+        return Err(Error::new_spanned(
+            wrapper,
+            "`wrapper` is only supported on transparent newtypes, i.e. with `#[cs_bindgen(transparent)]` or `#[repr(transparent)]`",
+        ));
+    }
+
+    // Determine whether we should marshal the type as a handle or by value. Types that
+    // opt in to being serialized are always marshaled that way.
+    let style = if let Some(format) = attr.marshal_format()? {
+        BindingStyle::Marshal(format)
+    } else if is_transparent {
+        BindingStyle::Transparent(attr.wrapper.is_some())
+    } else if has_derive(&item.attrs, "Copy")? {
+        BindingStyle::Value
+    } else {
+        BindingStyle::Handle
+    };
+
+    // Marshaled types are deserialized into a plain C# class, which doesn't have any
+    // of the generated members for the standard traits.
+    if let (Some(arg), BindingStyle::Marshal(_)) = (attr.struct_arg(), style) {
+        return Err(Error::new_spanned(
+            arg,
+            "Not supported on types exported with `marshal`",
+        ));
+    }
+
+    // Transparent newtypes are exposed as their inner value (or a minimal wrapper
+    // around it), which doesn't have any of the generated members for the standard
+    // traits.
+    if let (Some(arg), BindingStyle::Transparent(_)) = (attr.std_trait_arg(), style) {
+        return Err(Error::new_spanned(
+            arg,
+            "Not supported on transparent newtypes",
+        ));
+    }
+
+    // Shared handles are reference counted, which isn't possible for types that are
+    // copied when passed to C#.
+    if let (Some(shared), BindingStyle::Value) = (&attr.shared, style) {
+        return Err(Error::new_spanned(
+            shared,
+            "`Copy` types are marshaled by value and can't be shared handles",
+        ));
+    }
+
+    // Only boxed handles have an allocation that can be reused. Shared handles are
+    // allocated by `Arc`, which frees the allocation along with the last reference.
+    if let Some(pool) = &attr.pool {
+        if style != BindingStyle::Handle {
+            return Err(Error::new_spanned(
+                pool,
+                "`pool` is only supported on types marshaled as handles",
+            ));
+        }
+
+        if attr.shared.is_some() {
+            return Err(Error::new_spanned(pool, "Shared handles can't be pooled"));
+        }
+    }
+
+    // Flags are generated as a C# enum, which is always marshaled by value.
+    if let (Some(flags), BindingStyle::Handle) = (&attr.flags, style) {
+        return Err(Error::new_spanned(
+            flags,
+            "Flags types are marshaled by value and must implement `Copy`",
+        ));
+    }
+
+    // Determine which standard traits the type implements, so that their bindings can
+    // be exported along with the type.
+    let impls = match style {
+        BindingStyle::Marshal(_) | BindingStyle::Transparent(_) => StdImpls::default(),
+        _ => StdImpls::from_attrs(&item.attrs, attr, style)?,
+    };
+    let std_impl_fns = impls.quote_binding_fns(&item.ident, style);
+
+    // Validate the attributes on the fields and determine the C# names of any renamed
+    // fields. Only the fields of types marshaled by value are visible in C#, and unnamed
+    // fields are always named by their index.
+    let mut abi_fields = Vec::new();
+    let field_renames = item
+        .fields
+        .iter()
+        .map(|field| {
+            let attrs = member_attrs(&field.attrs)?;
+            let arg = attrs
+                .rename
+                .as_ref()
+                .map(ToTokens::to_token_stream)
+                .or_else(|| attrs.skip.as_ref().map(ToTokens::to_token_stream));
+
+            // Only the fields of types marshaled by value are converted with `Abi`.
+            if matches!(style, BindingStyle::Value | BindingStyle::Transparent(_))
+                && attrs.skip.is_none()
+            {
+                check_type(&field.ty, TypePosition::Field)?;
+                abi_fields.push(&field.ty);
+            }
+
+            match (arg, &field.ident, style) {
+                (Some(arg), None, _) => Err(Error::new_spanned(
+                    arg,
+                    "Only named fields can be renamed or skipped with `#[cs_bindgen]`",
+                )),
+                (Some(arg), _, BindingStyle::Handle) => Err(Error::new_spanned(
+                    arg,
+                    "Fields of types marshaled as handles aren't exported, so they can't be renamed or skipped",
+                )),
+                (Some(arg), _, BindingStyle::Transparent(_)) => Err(Error::new_spanned(
+                    arg,
+                    "Transparent newtypes are passed as the value of their field, so it can't be renamed or skipped",
+                )),
+
+                // The names of the fields in C# must match the names used by serde, so
+                // they can't be changed by `#[cs_bindgen]`.
+                (Some(arg), _, BindingStyle::Marshal(_)) => Err(Error::new_spanned(
+                    arg,
+                    "Fields of marshaled types are serialized with serde, so they can't be renamed or skipped",
+                )),
+                _ => Ok(attrs.rename),
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let bindings = if let BindingStyle::Marshal(format) = style {
+        let named_impl = impl_named(&item.ident, attr.rename.as_ref());
+        let describe_impl = describe_struct(&item, &field_renames);
+        let docs = doc_comment(&item.attrs);
+        let describe_fn = describe_named_type(
+            &item.ident,
+            style,
+            &impls,
+            &[],
+            docs.as_deref(),
+            &[],
+            None,
+            false,
+        );
+        let marshal_impls = marshal::quote_marshal_impls(&item.ident, format);
+
+        quote! {
+            #marshal_impls
+            #named_impl
+            #describe_impl
+            #describe_fn
+        }
+    } else if let BindingStyle::Transparent(_) = style {
+        let field = newtype_field(&item, "Transparent newtypes must have exactly one field")?;
+        let abi_impl = quote_newtype_abi_impl(&item, field, &repr_fn);
+        let inner = &field.ty;
+        let ident = &item.ident;
+
+        let named_impl = impl_named(ident, attr.rename.as_ref());
+        let describe_impl = describe_struct(&item, &field_renames);
+        let docs = doc_comment(&item.attrs);
+        let describe_fn =
+            describe_named_type(ident, style, &impls, &[], docs.as_deref(), &[], None, false);
+        let abi_mut_impl = quote_abi_mut_impl(ident);
+        let index_fn = quote_index_fn(ident);
+        let convert_list_fn = quote_convert_list_fn(ident);
+        let vec_drop_fn = quote_vec_drop_fn(ident);
+
+        quote! {
+            #abi_impl
+
+            impl cs_bindgen::abi::Transparent for #ident {
+                type Inner = #inner;
+            }
+
+            #abi_mut_impl
+            #named_impl
+            #describe_impl
+            #describe_fn
+            #index_fn
+            #convert_list_fn
+            #vec_drop_fn
+        }
+    } else if style == BindingStyle::Value {
+        let named_impl = impl_named(&item.ident, attr.rename.as_ref());
+        let describe_impl = describe_struct(&item, &field_renames);
 
         fn field_accessor(index: usize, field: &Field) -> TokenStream {
             field
@@ -28,74 +268,279 @@ pub fn quote_struct_item(item: ItemStruct) -> syn::Result<TokenStream> {
                 .unwrap_or_else(|| Literal::usize_unsuffixed(index).into_token_stream())
         }
 
-        let abi_struct_ident = format_binding_ident!(item.ident);
-        let abi_struct = value::quote_abi_struct(&abi_struct_ident, &item.fields);
-        let describe_fn = describe_named_type(&item.ident, BindingStyle::Value);
+        let renamed_fields = field_renames.iter().flatten().cloned().collect::<Vec<_>>();
+        let docs = doc_comment(&item.attrs);
+        let describe_fn = describe_named_type(
+            &item.ident,
+            BindingStyle::Value,
+            &impls,
+            &renamed_fields,
+            docs.as_deref(),
+            &[],
+            None,
+            false,
+        );
         let index_fn = quote_index_fn(&item.ident);
         let convert_list_fn = quote_convert_list_fn(&item.ident);
         let vec_drop_fn = quote_vec_drop_fn(&item.ident);
+        let size_fn = quote_size_fn(&item.ident);
+        let abi_mut_impl = quote_abi_mut_impl(&item.ident);
 
-        let into_abi_fields = value::into_abi_fields(&item.fields, |index, field| {
-            let accessor = field_accessor(index, field);
-            quote! { self.#accessor }
-        });
-
-        let as_abi_fields = value::as_abi_fields(&item.fields, |index, field| {
-            let accessor = field_accessor(index, field);
-            quote! { &self.#accessor }
-        });
-
-        // Generate the `from_abi` conversions for the fields, then wrap that code in the
-        // appropriate kind of braces based on the style of the struct.
-        let from_abi_fields = value::from_abi_fields(&item.fields, &quote! { abi });
-        let from_abi_braces = match &item.fields {
-            Fields::Named(_) => quote! { { #from_abi_fields } },
-            Fields::Unnamed(_) => quote! { ( #from_abi_fields ) },
-            Fields::Unit => quote! {},
-        };
+        let abi_impl = match &attr.flags {
+            Some(flags) => quote_flags_impls(&item, flags, &repr_fn)?,
 
-        let ident = item.ident;
-        Ok(quote! {
-            #abi_struct
+            None => {
+                let abi_struct_ident = format_binding_ident!(item.ident);
+                let abi_struct = value::quote_abi_struct(&abi_struct_ident, &item.fields);
 
-            impl cs_bindgen::abi::Abi for #ident {
-                type Abi = #abi_struct_ident;
+                let into_abi_fields = value::into_abi_fields(&item.fields, |index, field| {
+                    let accessor = field_accessor(index, field);
+                    quote! { self.#accessor }
+                });
 
-                #repr_fn
+                let as_abi_fields = value::as_abi_fields(&item.fields, |index, field| {
+                    let accessor = field_accessor(index, field);
+                    quote! { &self.#accessor }
+                });
 
-                unsafe fn from_abi(abi: Self::Abi) -> Self {
-                    Self #from_abi_braces
-                }
+                // Generate the `from_abi` conversions for the fields, then wrap that code in
+                // the appropriate kind of braces based on the style of the struct.
+                let from_abi_fields = value::from_abi_fields(&item.fields, &quote! { abi });
+                let from_abi_braces = match &item.fields {
+                    Fields::Named(_) => quote! { { #from_abi_fields } },
+                    Fields::Unnamed(_) => quote! { ( #from_abi_fields ) },
+                    Fields::Unit => quote! {},
+                };
 
-                fn as_abi(&self) -> Self::Abi {
-                    Self::Abi {
-                        #as_abi_fields
-                    }
-                }
+                let ident = &item.ident;
+                quote! {
+                    #abi_struct
+
+                    impl cs_bindgen::abi::Abi for #ident {
+                        type Abi = #abi_struct_ident;
 
-                fn into_abi(self) -> Self::Abi {
-                    Self::Abi {
-                        #into_abi_fields
+                        #repr_fn
+
+                        unsafe fn from_abi(abi: Self::Abi) -> Self {
+                            Self #from_abi_braces
+                        }
+
+                        fn as_abi(&self) -> Self::Abi {
+                            Self::Abi {
+                                #as_abi_fields
+                            }
+                        }
+
+                        fn into_abi(self) -> Self::Abi {
+                            Self::Abi {
+                                #into_abi_fields
+                            }
+                        }
                     }
                 }
             }
+        };
 
+        quote! {
+            #abi_impl
+            #abi_mut_impl
             #named_impl
             #describe_impl
             #describe_fn
             #index_fn
             #convert_list_fn
             #vec_drop_fn
-        })
+            #size_fn
+        }
     } else {
-        handle::quote_type_as_handle(&item.ident)
+        let docs = doc_comment(&item.attrs);
+        handle::quote_type_as_handle(
+            &item.ident,
+            &impls,
+            attr.pool.is_some(),
+            attr.rename.as_ref(),
+            docs.as_deref(),
+        )?
+    };
+
+    let list_fns = list::quote_list_fns(&item.ident.to_string(), abi_fields);
+
+    Ok(quote! {
+        #bindings
+        #std_impl_fns
+        #list_fns
+    })
+}
+
+/// Generates the bindings for each listed instantiation of a generic struct.
+///
+/// Each instantiation is exported as a separate type through a type alias, see the
+/// `generic` module for details.
+fn quote_struct_instantiations(item: ItemStruct, attr: &Attributes) -> syn::Result<TokenStream> {
+    if let Some(rename) = &attr.rename {
+        return Err(Error::new_spanned(
+            rename,
+            "`rename` is not supported on generic structs, since each instantiation is exported as a separate type",
+        ));
+    }
+
+    Instantiation::group(&item.generics, attr.instantiate.clone())?
+        .into_iter()
+        .map(|instantiation| {
+            let ident = &item.ident;
+            let vis = &item.vis;
+            let alias = instantiation.alias_ident(ident);
+            let types = instantiation.types();
+
+            let instance = ItemStruct {
+                ident: alias.clone(),
+                generics: Generics::default(),
+                fields: instantiation.apply_fields(&item.fields)?,
+                ..item.clone()
+            };
+            let attr = Attributes {
+                instantiate: Vec::new(),
+                rename: Some(LitStr::new(&instantiation.type_name(ident), ident.span())),
+                ..attr.clone()
+            };
+            let bindings = quote_struct_item(instance, &attr)?;
+
+            Ok(quote! {
+                #[doc(hidden)]
+                #[allow(bad_style)]
+                #vis type #alias = #ident<#( #types ),*>;
+
+                #bindings
+            })
+        })
+        .collect()
+}
+
+/// Generates the `Abi` and `Flags` impls for a struct exported with
+/// `#[cs_bindgen(flags)]`.
+///
+/// Flags types are marshaled as their integer value, so the struct must have exactly
+/// one field with a fixed-size integer type. Pointer-sized integers aren't supported
+/// because they can't be used as the underlying type of a C# enum.
+fn quote_flags_impls(
+    item: &ItemStruct,
+    flags: &Ident,
+    repr_fn: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let field = newtype_field(
+        item,
+        "Flags types must have exactly one field containing the bits of the flags",
+    )
+    .map_err(|err| Error::new_spanned(flags, err))?;
+
+    let bits = &field.ty;
+    let is_flags_int = match bits {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && FLAGS_TYPES
+                    .iter()
+                    .any(|flags_ty| path.path.is_ident(flags_ty))
+        }
+        _ => false,
+    };
+    if !is_flags_int {
+        return Err(Error::new_spanned(
+            bits,
+            "The bits of a flags type must be a fixed-size integer, e.g. `u32`",
+        ));
+    }
+
+    let abi_impl = quote_newtype_abi_impl(item, field, repr_fn);
+    let accessor = newtype_accessor(field);
+    let ident = &item.ident;
+    Ok(quote! {
+        #abi_impl
+
+        impl cs_bindgen::flags::Flags for #ident {
+            type Bits = #bits;
+
+            fn bits(self) -> Self::Bits {
+                self.#accessor
+            }
+        }
+    })
+}
+
+/// Returns the only field of a newtype struct, or an error with `message` if the
+/// struct doesn't have exactly one field.
+fn newtype_field<'a>(item: &'a ItemStruct, message: &str) -> syn::Result<&'a Field> {
+    match &item.fields {
+        Fields::Named(fields) if fields.named.len() == 1 => Ok(&fields.named[0]),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(&fields.unnamed[0]),
+        _ => Err(Error::new_spanned(&item.ident, message)),
+    }
+}
+
+/// Returns the expression used to access the only field of a newtype struct.
+fn newtype_accessor(field: &Field) -> TokenStream {
+    match &field.ident {
+        Some(ident) => ident.into_token_stream(),
+        None => Literal::usize_unsuffixed(0).into_token_stream(),
+    }
+}
+
+/// Generates the `Abi` impl for a newtype struct that's passed as the value of its
+/// only field.
+fn quote_newtype_abi_impl(item: &ItemStruct, field: &Field, repr_fn: &TokenStream) -> TokenStream {
+    let ident = &item.ident;
+    let inner = &field.ty;
+    let accessor = newtype_accessor(field);
+
+    quote! {
+        impl cs_bindgen::abi::Abi for #ident {
+            type Abi = <#inner as cs_bindgen::abi::Abi>::Abi;
+
+            #repr_fn
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                Self { #accessor: cs_bindgen::abi::Abi::from_abi(abi) }
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                cs_bindgen::abi::Abi::as_abi(&self.#accessor)
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                cs_bindgen::abi::Abi::into_abi(self.#accessor)
+            }
+        }
+    }
+}
+
+/// Returns `true` if the struct is marked with `#[repr(transparent)]`.
+fn has_repr_transparent(attrs: &[Attribute]) -> syn::Result<bool> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let hints = attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+        if hints.iter().any(|hint| hint.path().is_ident("transparent")) {
+            return Ok(true);
+        }
     }
+
+    Ok(false)
 }
 
-fn describe_struct(item: &ItemStruct) -> TokenStream {
+/// Generates the `Describe` impl for a struct.
+///
+/// `field_renames` has the new name for each field of the struct that was renamed,
+/// which is used in place of the original field name. Omitted fields (i.e. skipped
+/// fields and `PhantomData` fields) aren't included in the description, so a struct
+/// with only omitted fields is described as a unit struct.
+fn describe_struct(item: &ItemStruct, field_renames: &[Option<LitStr>]) -> TokenStream {
     let ident = &item.ident;
 
-    let body = if item.fields.is_empty() {
+    let described_fields = item
+        .fields
+        .iter()
+        .zip(field_renames)
+        .filter(|(field, _)| !value::is_omitted(field))
+        .collect::<Vec<_>>();
+
+    let body = if described_fields.is_empty() {
         quote! {
             cs_bindgen::shared::schematic::Describer::describe_unit_struct(type_name)
         }
@@ -108,15 +553,17 @@ fn describe_struct(item: &ItemStruct) -> TokenStream {
             // * For any other number of elements, it is considered a tuple struct.
             //
             // An empty tuple-like struct is treated like a unit-struct in the data model,
-            // though that case is already handled above when we check `item.fields.is_empty()`.
-            Fields::Unnamed(fields) => {
-                if fields.unnamed.len() == 1 {
-                    let inner = &fields.unnamed[0].ty;
+            // though that case is already handled above when we check `described_fields`.
+            Fields::Unnamed(_) => {
+                if described_fields.len() == 1 {
+                    let inner = described_field_ty(&described_fields[0].0.ty);
                     quote! {
                         describer.describe_newtype_struct::<#inner>(type_name)
                     }
                 } else {
-                    let element_ty = fields.unnamed.iter().map(|field| &field.ty);
+                    let element_ty = described_fields
+                        .iter()
+                        .map(|(field, _)| described_field_ty(&field.ty));
                     quote! {
                         let mut describer = describer.describe_tuple_struct(type_name)?;
                         #(
@@ -129,13 +576,14 @@ fn describe_struct(item: &ItemStruct) -> TokenStream {
 
             // Normal structs (i.e. with named fields) are always considered structs in the data
             // model. The only exception being one with no fields, though that case is already
-            // handled above when we check `item.fields.is_empty()`.
-            Fields::Named(fields) => {
-                let field_name = fields
-                    .named
+            // handled above when we check `described_fields`.
+            Fields::Named(_) => {
+                let field_name = described_fields
                     .iter()
-                    .map(|field| field.ident.as_ref().unwrap().to_string());
-                let field_ty = fields.named.iter().map(|field| &field.ty);
+                    .map(|(field, rename)| field_name(field, rename.as_ref()));
+                let field_ty = described_fields
+                    .iter()
+                    .map(|(field, _)| described_field_ty(&field.ty));
                 quote! {
                     let mut describer = describer.describe_struct(type_name)?;
                     #(
@@ -167,3 +615,11 @@ fn describe_struct(item: &ItemStruct) -> TokenStream {
         }
     }
 }
+
+/// Returns the name of a named field in the struct's description.
+fn field_name(field: &Field, rename: Option<&LitStr>) -> String {
+    match rename {
+        Some(rename) => rename.value(),
+        None => field.ident.as_ref().unwrap().to_string(),
+    }
+}
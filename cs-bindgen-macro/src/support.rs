@@ -0,0 +1,287 @@
+//! Diagnostics for argument, return, and field types that can't be exported.
+//!
+//! Whether a type can be passed to C# is ultimately determined by whether it
+//! implements `Abi`, but the compiler error for a missing impl points into the
+//! generated bindings and doesn't say what to do instead. The checks here catch the
+//! common cases syntactically, so that the error points at the offending type and
+//! explains why it isn't supported. Types that pass the checks but still don't
+//! implement `Abi` (e.g. a struct that isn't exported) are reported by the compiler,
+//! with the generated code spanned to the type so that the error points at it.
+
+use std::fmt::{self, Display};
+use syn::*;
+
+/// The categories of types that can be passed to C#, listed in the error messages.
+const SUPPORTED_TYPES: &str = "Supported types are numeric primitives, `bool`, `char`, `String` and `&str`, `Vec<T>` and `&[T]`, `Option<T>`, `Result<T, E>`, `Box<T>` of an exported type, tuples, arrays, `HashMap<K, V>` and `BTreeMap<K, V>`, `CancelFlag` arguments, and types exported with `#[cs_bindgen]`";
+
+/// The attribute-based workarounds for exporting a type that isn't supported directly.
+const WORKAROUNDS: &str = "To pass it anyway, wrap it in a type exported with `#[cs_bindgen]`, serialize it with `#[cs_bindgen(marshal = \"json\")]`, or convert it with `#[cs_bindgen(raw = \"...\", into_raw_with = \"...\", from_raw_with = \"...\", converter = \"...\")]`";
+
+/// The number of elements in the largest tuple that implements `Abi`. Tuples with a
+/// single element don't implement `Abi`, but the unit type does.
+const MAX_TUPLE_LEN: usize = 7;
+
+/// The length of the largest array that implements `Abi`.
+const MAX_ARRAY_LEN: usize = 32;
+
+/// Where a type appears in an exported item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypePosition {
+    Argument,
+    Return,
+    Field,
+}
+
+impl Display for TypePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypePosition::Argument => f.write_str("argument"),
+            TypePosition::Return => f.write_str("return"),
+            TypePosition::Field => f.write_str("field"),
+        }
+    }
+}
+
+/// Checks that `ty` isn't one of the types that can never be exported in `position`.
+///
+/// The error points at the innermost unsupported type, e.g. the pointer in
+/// `Vec<*const u8>`.
+pub fn check_type(ty: &Type, position: TypePosition) -> syn::Result<()> {
+    match ty {
+        Type::Paren(paren) => check_type(&paren.elem, position),
+        Type::Group(group) => check_type(&group.elem, position),
+
+        Type::Path(path) => {
+            if let Some(reason) = unsupported_std_type(path) {
+                return Err(unsupported(ty, position, reason));
+            }
+
+            if position != TypePosition::Argument && is_cancel_flag(ty) {
+                return Err(unsupported(
+                    ty,
+                    position,
+                    "cancellation flags are created by the C# caller. Take a `CancelFlag` argument instead",
+                ));
+            }
+
+            for segment in &path.path.segments {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let GenericArgument::Type(arg) = arg {
+                            check_nested_type(arg, position)?;
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        Type::Reference(reference) => {
+            if let (TypePosition::Return, Some(lifetime)) = (position, &reference.lifetime) {
+                if lifetime.ident != "static" {
+                    return Err(Error::new_spanned(
+                        lifetime,
+                        "Returned references can't use a named lifetime with `#[cs_bindgen]`, elide it instead (e.g. `&Foo` rather than `&'a Foo`)",
+                    ));
+                }
+            }
+
+            match &*reference.elem {
+                // Trait objects are checked when the argument is converted, see
+                // `ArgStyle`.
+                Type::TraitObject(_) => Ok(()),
+                elem => check_type(elem, position),
+            }
+        }
+
+        Type::Tuple(tuple) if tuple.elems.len() == 1 || tuple.elems.len() > MAX_TUPLE_LEN => {
+            Err(unsupported(
+                ty,
+                position,
+                format!(
+                    "only tuples with 2 to {} elements are supported, export a struct with `#[cs_bindgen]` instead",
+                    MAX_TUPLE_LEN,
+                ),
+            ))
+        }
+
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .try_for_each(|elem| check_nested_type(elem, position)),
+
+        Type::Array(array) => {
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Int(len), ..
+            }) = &array.len
+            {
+                let len = len.base10_parse::<usize>()?;
+                if len == 0 || len > MAX_ARRAY_LEN {
+                    return Err(unsupported(
+                        ty,
+                        position,
+                        format!(
+                            "only arrays with 1 to {} elements are supported, use `Vec<T>` instead",
+                            MAX_ARRAY_LEN,
+                        ),
+                    ));
+                }
+            }
+
+            check_nested_type(&array.elem, position)
+        }
+
+        Type::Ptr(_) => Err(unsupported(
+            ty,
+            position,
+            "raw pointers don't say who owns the data or how long it lives, so C# can't use them safely. Export the pointee with `#[cs_bindgen]` and pass it by value or by reference instead",
+        )),
+
+        // Function pointers can't capture the C# delegate, so there's no way to create one
+        // from a C# callback.
+        Type::BareFn(_) if position == TypePosition::Argument => Err(Error::new_spanned(
+            ty,
+            "Function pointer arguments are not supported with `#[cs_bindgen]`, use `impl Fn(..)` instead",
+        )),
+
+        Type::BareFn(_) => Err(unsupported(
+            ty,
+            position,
+            "C# can't call Rust function pointers. Export the function itself with `#[cs_bindgen]` instead",
+        )),
+
+        Type::Never(_) => Err(unsupported(
+            ty,
+            position,
+            "the binding has to return a value to C#. Return `()` instead",
+        )),
+
+        Type::ImplTrait(impl_trait) => match position {
+            TypePosition::Return if !is_iterator(&impl_trait.bounds) => Err(unsupported(
+                ty,
+                position,
+                "the only `impl Trait` return type that is supported is `impl Iterator<Item = T>`. Return a concrete type instead",
+            )),
+
+            TypePosition::Argument if is_iterator(&impl_trait.bounds) => Err(unsupported(
+                ty,
+                position,
+                "C# can't pass an iterator to Rust. Take a `Vec<T>` or `&[T]` instead",
+            )),
+
+            _ => Ok(()),
+        },
+
+        Type::Infer(_) => Err(unsupported(
+            ty,
+            position,
+            "the type must be written out so that its binding can be generated",
+        )),
+
+        _ => Ok(()),
+    }
+}
+
+/// Checks a type nested within another type, e.g. the element type of a `Vec<T>`.
+///
+/// Callbacks, exported traits, iterators, and cancellation flags are only supported at
+/// the top level of an argument or return type.
+fn check_nested_type(ty: &Type, position: TypePosition) -> syn::Result<()> {
+    match ty {
+        Type::ImplTrait(_) => Err(Error::new_spanned(
+            ty,
+            format!(
+                "`impl Trait` is only supported as the outermost {} type with `#[cs_bindgen]`",
+                position,
+            ),
+        )),
+
+        _ if position == TypePosition::Argument && is_cancel_flag(ty) => Err(Error::new_spanned(
+            ty,
+            "`CancelFlag` is only supported as the outermost argument type with `#[cs_bindgen]`",
+        )),
+
+        _ => check_type(ty, position),
+    }
+}
+
+/// Returns the reason that `path` isn't supported, if it's a well-known standard
+/// library type that doesn't implement `Abi`.
+///
+/// Generic types are only matched if they have type arguments, and all types are only
+/// matched if they're named directly or through `std`, `core`, or `alloc`, so that
+/// exported types that happen to share a name (e.g. a non-generic `Cell`) aren't
+/// rejected.
+fn unsupported_std_type(path: &TypePath) -> Option<&'static str> {
+    if path.qself.is_some() {
+        return None;
+    }
+
+    let segments = &path.path.segments;
+    let is_std = segments.len() == 1
+        || ["std", "core", "alloc"]
+            .iter()
+            .any(|krate| segments[0].ident == krate);
+    if !is_std {
+        return None;
+    }
+
+    let segment = segments.last()?;
+    let has_type_args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .any(|arg| matches!(arg, GenericArgument::Type(_))),
+        _ => false,
+    };
+
+    let reason = match &*segment.ident.to_string() {
+        "Rc" if has_type_args => "`Rc` isn't thread-safe, so it can't be shared with C#. Use `Arc<T>` with a type exported with `#[cs_bindgen(shared)]` instead",
+        "Cell" | "RefCell" | "Mutex" | "RwLock" if has_type_args => "interior mutability can't be expressed in C#. Export a type that wraps the value with `#[cs_bindgen]` and mutate it through its methods instead",
+        "HashSet" | "BTreeSet" | "VecDeque" | "LinkedList" | "BinaryHeap" if has_type_args => "the only collections that are supported are `Vec<T>`, `HashMap<K, V>`, and `BTreeMap<K, V>`. Convert it into a `Vec<T>` instead",
+        "Cow" if has_type_args => "borrowed strings and slices are only supported as `&str` and `&[T]`. Use `String` or `Vec<T>` instead",
+        "PathBuf" | "OsString" | "CString" => "platform strings can't be converted to C# strings losslessly. Convert it into a `String` instead",
+        _ => return None,
+    };
+
+    Some(reason)
+}
+
+/// Returns `true` if `ty` names `cs_bindgen::cancel::CancelFlag`.
+///
+/// The flag is matched by name, so that it's recognized however it was imported.
+fn is_cancel_flag(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "CancelFlag"),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `bounds` includes an `Iterator` bound.
+fn is_iterator(bounds: &punctuated::Punctuated<TypeParamBound, token::Add>) -> bool {
+    bounds.iter().any(|bound| match bound {
+        TypeParamBound::Trait(bound) => bound
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Iterator"),
+        _ => false,
+    })
+}
+
+/// Creates the error for an unsupported type, followed by the list of supported types
+/// and the available workarounds.
+fn unsupported<R: Display>(ty: &Type, position: TypePosition, reason: R) -> Error {
+    Error::new_spanned(
+        ty,
+        format!(
+            "Unsupported {} type for `#[cs_bindgen]`: {}.\n\n{}.\n\n{}.",
+            position, reason, SUPPORTED_TYPES, WORKAROUNDS,
+        ),
+    )
+}
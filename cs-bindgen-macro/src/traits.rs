@@ -0,0 +1,507 @@
+//! Code generation for exported traits and trait impls.
+//!
+//! An exported trait can be used as an argument to exported functions, either as
+//! `&dyn Trait` or `impl Trait`. The C# side passes a `RawDyn` created from the
+//! handle of any exported type that implements the trait, which is why trait impls
+//! for exported types also need to be tagged with `#[cs_bindgen]`.
+//!
+//! The methods of the trait are exported once for the trait, taking the trait object
+//! as the receiver, rather than once for each impl. This way default methods that
+//! aren't overridden in an impl can still be called from C#.
+//!
+//! Traits whose methods only take arguments by value can also be implemented by C#
+//! classes, which are passed to Rust as a `Box<dyn Trait>`. C# passes a `RawManaged`,
+//! and the macro generates an impl of the trait for a wrapper around the C# object
+//! that forwards each method call to C# through the object's vtable.
+//!
+//! Functions can also return a `Box<dyn Trait>`, which is passed to C# as a handle to
+//! the box. C# borrows the handle as a `RawDyn` in order to call the trait's methods,
+//! and drops it once the C# object is disposed.
+
+use crate::{extract_type_ident, func::*, list, quote_binding_fn, reject_generics};
+use proc_macro2::TokenStream;
+use quote::*;
+use syn::*;
+
+/// Generates the bindings for an exported trait.
+///
+/// In addition to the describe function, this generates:
+///
+/// * A binding function for each method, taking a `RawDyn` as the receiver.
+/// * An `Abi` impl for `&dyn Trait`, which is how trait objects are passed from C#.
+/// * An impl of the trait for `&dyn Trait` that forwards to the trait object, so
+///   that a `&dyn Trait` can be passed to functions taking an `impl Trait` argument.
+/// * An `Abi` impl for `Box<dyn Trait>`, which is how trait objects are returned to
+///   C#, along with the functions for borrowing and dropping the returned handle.
+pub fn quote_trait_item(item: ItemTrait) -> syn::Result<TokenStream> {
+    reject_generics(
+        &item.generics,
+        "Generic traits are not supported with `#[cs_bindgen]`",
+    )?;
+
+    let ident = &item.ident;
+
+    // Gather the methods of the trait, generating an error for any methods that can't
+    // be called through a `&dyn Trait`.
+    let mut methods = Vec::new();
+    for item in &item.items {
+        let method = match item {
+            TraitItem::Method(method) => method,
+            _ => continue,
+        };
+
+        reject_generics(
+            &method.sig.generics,
+            "Generic trait methods are not supported with `#[cs_bindgen]`",
+        )?;
+
+        if let Some(asyncness) = &method.sig.asyncness {
+            return Err(Error::new_spanned(
+                asyncness,
+                "Async trait methods are not supported with `#[cs_bindgen]`",
+            ));
+        }
+
+        match method.sig.receiver() {
+            Some(FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+
+            _ => {
+                return Err(Error::new_spanned(
+                    &method.sig,
+                    "Methods of exported traits must take `&self` with `#[cs_bindgen]`",
+                ))
+            }
+        }
+
+        let inputs = extract_inputs(method.sig.inputs.clone())?;
+        validate_output(&method.sig.output)?;
+        methods.push((method, inputs));
+    }
+
+    // Determine if the trait can be implemented in C#. The vtable methods are invoked as
+    // callbacks, so they have the same restrictions on their arguments. Methods returning
+    // a `Result` are also excluded, since the C# method throws instead of returning the
+    // error, as are traits with supertraits since the C# object can't implement them.
+    let managed = item.supertraits.is_empty()
+        && methods.iter().all(|(method, inputs)| {
+            inputs.len() <= MAX_CALLBACK_ARGS
+                && inputs.iter().all(|(_, ty)| is_owned_arg(ty))
+                && !returns_result(&method.sig)
+        });
+
+    // Generate the forwarding impl for `&dyn Trait`.
+    let forward_methods = methods.iter().map(|(method, inputs)| {
+        let name = &method.sig.ident;
+        let arg_ident = inputs.iter().map(|(ident, _)| ident);
+        let decl = quote_method_decl(&method.sig, inputs);
+
+        quote! {
+            #decl {
+                (**self).#name(#( #arg_ident ),*)
+            }
+        }
+    });
+
+    // Generate the binding functions for the methods of the trait.
+    let bindings = methods.iter().map(|(method, inputs)| {
+        let sig = &method.sig;
+        let name = &sig.ident;
+        let binding_ident = format_binding_ident!(format!("{}__{}", ident, name));
+
+        let binding_inputs = Some(quote! { self_: cs_bindgen::abi::RawDyn })
+            .into_iter()
+            .chain(
+                inputs
+                    .iter()
+                    .map(|(ident, ty)| quote_arg_binding_input(ident, ty)),
+            );
+        let convert_inputs = inputs
+            .iter()
+            .map(|(ident, ty)| quote_arg_conversion(ident, ty));
+
+        let return_decl = match &sig.output {
+            ReturnType::Default => quote! {},
+            ReturnType::Type(_, return_type) => quote! {
+                -> <#return_type as cs_bindgen::abi::Abi>::Abi
+            },
+        };
+
+        let arg_names = inputs.iter().map(|(ident, _)| ident);
+        let invoke = quote! { self_.#name(#( #arg_names, )*) };
+        let return_expr = match &sig.output {
+            ReturnType::Default => invoke,
+            ReturnType::Type(..) => quote! { cs_bindgen::abi::Abi::into_abi(#invoke) },
+        };
+
+        quote_binding_fn(
+            &binding_ident,
+            binding_inputs,
+            return_decl,
+            quote! {
+                let self_ = <&dyn #ident as cs_bindgen::abi::Abi>::from_abi(self_);
+                #( #convert_inputs )*
+                #return_expr
+            },
+        )
+    });
+
+    // Generate the descriptions of the methods of the trait.
+    let describe_methods = methods.iter().map(|(method, inputs)| {
+        let sig = &method.sig;
+        let name = sig.ident.to_string();
+        let binding_ident = format_binding_ident!(format!("{}__{}", ident, sig.ident));
+        let binding_name = binding_ident.to_string();
+        let describe_args = inputs.iter().map(|(ident, ty)| {
+            let name = ident.to_string();
+            let repr = quote_arg_repr(ty);
+            quote! {
+                cs_bindgen::shared::FnArg::new(#name, #repr)
+            }
+        });
+
+        let describe_output = match &sig.output {
+            ReturnType::Default => quote! { None },
+            ReturnType::Type(_, return_type) => quote! {
+                Some(<#return_type as cs_bindgen::abi::Abi>::repr())
+            },
+        };
+
+        let describe_default = if managed && method.default.is_some() {
+            let default_name = default_binding_ident(ident, &sig.ident).to_string();
+            quote! { Some(#default_name.into()) }
+        } else {
+            quote! { None }
+        };
+
+        quote! {
+            cs_bindgen::shared::TraitMethod {
+                name: #name.into(),
+                binding: #binding_name.into(),
+                inputs: vec![#( #describe_args, )*],
+                output: #describe_output,
+                default_binding: #describe_default,
+            }
+        }
+    });
+
+    let managed_impl = if managed {
+        quote_managed_impl(ident, &methods)
+    } else {
+        quote! {}
+    };
+
+    let boxed_as_dyn_ident = format_ident!("__cs_bindgen_boxed_as_dyn__{}", ident);
+    let boxed_as_dyn_name = boxed_as_dyn_ident.to_string();
+    let drop_boxed_ident = format_drop_ident!(format!("boxed__{}", ident));
+    let drop_boxed_name = drop_boxed_ident.to_string();
+
+    let describe_ident = format_describe_ident!(ident);
+    let list_fns = list::quote_list_fns(
+        &ident.to_string(),
+        methods.iter().flat_map(|(method, inputs)| {
+            inputs
+                .iter()
+                .map(|(_, ty)| &**ty)
+                .chain(output_type(&method.sig.output))
+        }),
+    );
+
+    Ok(quote! {
+        #( #bindings )*
+        #list_fns
+
+        impl<'a> #ident for &'a dyn #ident {
+            #( #forward_methods )*
+        }
+
+        impl<'a> cs_bindgen::abi::Abi for &'a dyn #ident {
+            type Abi = cs_bindgen::abi::RawDyn;
+
+            fn repr() -> cs_bindgen::shared::Repr {
+                cs_bindgen::shared::Repr::Dyn(
+                    <dyn #ident as cs_bindgen::shared::Named>::type_name(),
+                )
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                cs_bindgen::abi::RawDyn::new::<dyn #ident + 'a>(*self)
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                cs_bindgen::abi::RawDyn::new::<dyn #ident + 'a>(self)
+            }
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                abi.into_ref::<dyn #ident>()
+            }
+        }
+
+        // Owned trait objects returned to C# are passed as a handle to the box. Borrowing
+        // the box passes a pointer to the box itself, which has the same layout.
+        impl cs_bindgen::abi::Abi for std::boxed::Box<dyn #ident> {
+            type Abi = *mut std::ffi::c_void;
+
+            fn repr() -> cs_bindgen::shared::Repr {
+                cs_bindgen::shared::Repr::DynHandle(
+                    <dyn #ident as cs_bindgen::shared::Named>::type_name(),
+                )
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                self as *const Self as *mut std::ffi::c_void
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                cs_bindgen::abi::into_dyn_handle(self)
+            }
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                cs_bindgen::abi::from_dyn_handle(abi)
+            }
+        }
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #boxed_as_dyn_ident(
+            handle: *mut std::ffi::c_void,
+        ) -> cs_bindgen::abi::RawDyn {
+            cs_bindgen::panic::abort_on_panic(move || {
+                cs_bindgen::abi::RawDyn::new::<dyn #ident>(
+                    cs_bindgen::abi::dyn_handle_ref::<dyn #ident>(handle),
+                )
+            })
+        }
+
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #drop_boxed_ident(handle: *mut std::ffi::c_void) {
+            if !handle.is_null() {
+                std::mem::drop(cs_bindgen::abi::from_dyn_handle::<dyn #ident>(handle));
+            }
+        }
+
+        impl cs_bindgen::shared::Named for dyn #ident {
+            fn type_name() -> cs_bindgen::shared::TypeName {
+                cs_bindgen::shared::TypeName::new(stringify!(#ident), module_path!())
+            }
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::Trait {
+                type_name: <dyn #ident as cs_bindgen::shared::Named>::type_name(),
+                methods: vec![#( #describe_methods, )*],
+                managed: #managed,
+                boxed_as_dyn_fn: #boxed_as_dyn_name.into(),
+                drop_boxed_fn: #drop_boxed_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+
+        #managed_impl
+    })
+}
+
+/// Generates the impls that allow C# classes to implement an exported trait.
+///
+/// Two wrappers around the C# object are generated:
+///
+/// * The managed wrapper owns the C# object and forwards every method to C#. This is
+///   the type used for `Box<dyn Trait>` arguments.
+/// * The defaults wrapper borrows the C# object and only forwards the required
+///   methods, so that the default implementations of the trait's provided methods
+///   are used. This is used by the bindings that C# calls in order to invoke the
+///   default implementation of a method it doesn't override.
+///
+/// The index of each method in the vtable is the index of the method in the trait.
+fn quote_managed_impl(ident: &Ident, methods: &[(&TraitItemMethod, Vec<FnInput>)]) -> TokenStream {
+    let managed_ident = format_binding_ident!(format!("Managed__{}", ident));
+    let defaults_ident = format_binding_ident!(format!("ManagedDefaults__{}", ident));
+
+    let quote_forward = |index: usize, method: &TraitItemMethod, inputs: &[FnInput]| {
+        let decl = quote_method_decl(&method.sig, inputs);
+        let arg_ident = inputs.iter().map(|(ident, _)| ident);
+        let call = format_ident!("call{}", inputs.len());
+        quote! {
+            #decl {
+                unsafe { self.raw().method(#index).#call(#( #arg_ident ),*) }
+            }
+        }
+    };
+
+    let managed_methods = methods
+        .iter()
+        .enumerate()
+        .map(|(index, (method, inputs))| quote_forward(index, method, inputs));
+
+    let required_methods = methods
+        .iter()
+        .enumerate()
+        .filter(|(_, (method, _))| method.default.is_none())
+        .map(|(index, (method, inputs))| quote_forward(index, method, inputs));
+
+    // Generate the bindings for invoking the default implementations of provided
+    // methods on a C# object.
+    let default_bindings = methods
+        .iter()
+        .filter(|(method, _)| method.default.is_some())
+        .map(|(method, inputs)| {
+            let name = &method.sig.ident;
+            let binding_ident = default_binding_ident(ident, name);
+
+            let binding_inputs = Some(quote! { self_: cs_bindgen::abi::RawManaged })
+                .into_iter()
+                .chain(
+                    inputs
+                        .iter()
+                        .map(|(ident, ty)| quote_arg_binding_input(ident, ty)),
+                );
+            let convert_inputs = inputs
+                .iter()
+                .map(|(ident, ty)| quote_arg_conversion(ident, ty));
+
+            let return_decl = match &method.sig.output {
+                ReturnType::Default => quote! {},
+                ReturnType::Type(_, return_type) => quote! {
+                    -> <#return_type as cs_bindgen::abi::Abi>::Abi
+                },
+            };
+
+            let arg_names = inputs.iter().map(|(ident, _)| ident);
+            let invoke = quote! { self_.#name(#( #arg_names, )*) };
+            let return_expr = match &method.sig.output {
+                ReturnType::Default => invoke,
+                ReturnType::Type(..) => quote! { cs_bindgen::abi::Abi::into_abi(#invoke) },
+            };
+
+            quote_binding_fn(
+                &binding_ident,
+                binding_inputs,
+                return_decl,
+                quote! {
+                    let self_ = #defaults_ident(self_);
+                    #( #convert_inputs )*
+                    #return_expr
+                },
+            )
+        });
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(bad_style)]
+        pub struct #managed_ident(cs_bindgen::abi::ManagedObject);
+
+        impl #managed_ident {
+            fn raw(&self) -> &cs_bindgen::abi::RawManaged {
+                self.0.raw()
+            }
+        }
+
+        impl #ident for #managed_ident {
+            #( #managed_methods )*
+        }
+
+        #[doc(hidden)]
+        #[allow(bad_style)]
+        pub struct #defaults_ident(cs_bindgen::abi::RawManaged);
+
+        impl #defaults_ident {
+            fn raw(&self) -> &cs_bindgen::abi::RawManaged {
+                &self.0
+            }
+        }
+
+        impl #ident for #defaults_ident {
+            #( #required_methods )*
+        }
+
+        impl cs_bindgen::abi::FromManaged for dyn #ident {
+            unsafe fn from_managed(
+                raw: cs_bindgen::abi::RawManaged,
+            ) -> std::boxed::Box<Self> {
+                std::boxed::Box::new(#managed_ident(
+                    cs_bindgen::abi::ManagedObject::from_raw(raw),
+                ))
+            }
+        }
+
+        #( #default_bindings )*
+    }
+}
+
+/// Returns the identifier of the binding function that invokes the default
+/// implementation of a trait method for a C# object.
+fn default_binding_ident(trait_ident: &Ident, method: &Ident) -> Ident {
+    let ident = format_binding_ident!(format!("{}__{}__default", trait_ident, method));
+    ident
+}
+
+/// Returns `true` if the method's return type is syntactically a `Result`.
+fn returns_result(sig: &Signature) -> bool {
+    match &sig.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "Result")
+                .unwrap_or_default(),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+/// Generates the declaration of a trait method for an impl that forwards the method,
+/// using the generated identifiers for the arguments.
+fn quote_method_decl(sig: &Signature, inputs: &[FnInput]) -> TokenStream {
+    let name = &sig.ident;
+    let unsafety = &sig.unsafety;
+    let output = &sig.output;
+    let arg_ident = inputs.iter().map(|(ident, _)| ident);
+    let arg_ty = inputs.iter().map(|(_, ty)| ty);
+
+    quote! {
+        #unsafety fn #name(&self, #( #arg_ident: #arg_ty ),*) #output
+    }
+}
+
+/// Generates the bindings for an impl of an exported trait.
+///
+/// Exports a function for converting a handle to the type into a `RawDyn` for the
+/// trait, which the generated C# class uses to implement the trait's interface.
+pub fn quote_trait_impl_item(trait_: &Path, self_ty: &Type) -> syn::Result<TokenStream> {
+    let trait_ident = &trait_
+        .segments
+        .last()
+        .ok_or_else(|| Error::new_spanned(trait_, "Invalid trait path"))?
+        .ident;
+    let self_ident = extract_type_ident(self_ty)?;
+
+    let as_dyn_ident = format_ident!("__cs_bindgen_as_dyn__{}__{}", trait_ident, self_ident);
+    let as_dyn_name = as_dyn_ident.to_string();
+    let describe_ident = format_describe_ident!(format!("impl__{}__{}", trait_ident, self_ident));
+
+    Ok(quote! {
+        #[no_mangle]
+        #[allow(bad_style)]
+        pub unsafe extern "C" fn #as_dyn_ident(handle: *const #self_ty) -> cs_bindgen::abi::RawDyn {
+            cs_bindgen::panic::abort_on_panic(move || {
+                cs_bindgen::abi::RawDyn::new::<dyn #trait_>(&*handle)
+            })
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn #describe_ident() -> std::boxed::Box<cs_bindgen::abi::RawString> {
+            let export = cs_bindgen::shared::TraitImpl {
+                trait_name: <dyn #trait_ as cs_bindgen::shared::Named>::type_name(),
+                self_type: <#self_ty as cs_bindgen::shared::Named>::type_name(),
+                as_dyn_fn: #as_dyn_name.into(),
+            };
+
+            std::boxed::Box::new(cs_bindgen::shared::serialize_export(export).into())
+        }
+    })
+}
@@ -1,7 +1,14 @@
+use crate::attr::is_skipped;
 use proc_macro2::TokenStream;
 use quote::*;
-use syn::*;
+use syn::{spanned::Spanned, *};
 
+/// Generates the binding struct for a set of fields.
+///
+/// Omitted fields (see [`is_omitted`]) aren't included in the binding struct, and are
+/// initialized with `Default::default()` when converting from the binding struct.
+///
+/// [`is_omitted`]: fn.is_omitted.html
 pub fn quote_abi_struct(ident: &Ident, fields: &Fields) -> TokenStream {
     // Extract the list of fields for the binding struct. The generated struct is the
     // same for both struct-like and tuple-like variants, though in the latter case we
@@ -9,11 +16,14 @@ pub fn quote_abi_struct(ident: &Ident, fields: &Fields) -> TokenStream {
     let from_fields = fields
         .iter()
         .enumerate()
+        .filter(|(_, field)| !is_omitted(field))
         .map(|(index, field)| {
             let field_ty = &field.ty;
             let field_ident = raw_field_ident(index, field);
 
-            quote! {
+            // NOTE: The field is spanned to its type, so that the error for a type that
+            // doesn't implement `Abi` points at the field in the original type.
+            quote_spanned! {field_ty.span()=>
                 #field_ident: <#field_ty as cs_bindgen::abi::Abi>::Abi
             }
         })
@@ -61,14 +71,19 @@ pub fn into_abi_fields(
     let abi_field = fields
         .iter()
         .enumerate()
+        .filter(|(_, field)| !is_omitted(field))
         .map(|(index, field)| raw_field_ident(index, field));
 
-    let conversion = fields.iter().enumerate().map(|(index, field)| {
-        let input_field = field_accessor(index, field);
-        quote! {
-            cs_bindgen::abi::Abi::into_abi(#input_field)
-        }
-    });
+    let conversion = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !is_omitted(field))
+        .map(|(index, field)| {
+            let input_field = field_accessor(index, field);
+            quote! {
+                cs_bindgen::abi::Abi::into_abi(#input_field)
+            }
+        });
 
     quote! {
         #(
@@ -84,14 +99,19 @@ pub fn as_abi_fields(
     let abi_field = fields
         .iter()
         .enumerate()
+        .filter(|(_, field)| !is_omitted(field))
         .map(|(index, field)| raw_field_ident(index, field));
 
-    let conversion = fields.iter().enumerate().map(|(index, field)| {
-        let input_field = field_accessor(index, field);
-        quote! {
-            cs_bindgen::abi::Abi::as_abi(#input_field)
-        }
-    });
+    let conversion = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !is_omitted(field))
+        .map(|(index, field)| {
+            let input_field = field_accessor(index, field);
+            quote! {
+                cs_bindgen::abi::Abi::as_abi(#input_field)
+            }
+        });
 
     quote! {
         #(
@@ -107,6 +127,10 @@ pub fn from_abi_fields(fields: &Fields, input: &TokenStream) -> TokenStream {
     });
 
     let conversion = fields.iter().enumerate().map(|(index, field)| {
+        if is_omitted(field) {
+            return quote! { std::default::Default::default() };
+        }
+
         let field_ident = raw_field_ident(index, field);
         quote! { cs_bindgen::abi::Abi::from_abi(#input.#field_ident) }
     });
@@ -131,3 +155,32 @@ pub fn raw_field_ident(index: usize, field: &Field) -> Ident {
         .map(Clone::clone)
         .unwrap_or_else(|| format_ident!("element_{}", index))
 }
+
+/// Returns `true` if `field` isn't marshaled, i.e. if it's marked with
+/// `#[cs_bindgen(skip)]` or is a `PhantomData`.
+///
+/// `PhantomData` fields are zero-sized, so there's nothing to marshal.
+pub fn is_omitted(field: &Field) -> bool {
+    is_skipped(field) || is_phantom_data(&field.ty)
+}
+
+/// Returns `true` if none of `fields` are marshaled, see [`is_omitted`].
+///
+/// [`is_omitted`]: fn.is_omitted.html
+pub fn is_all_omitted(fields: &Fields) -> bool {
+    fields.iter().all(is_omitted)
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "PhantomData")
+            .unwrap_or(false),
+        Type::Paren(paren) => is_phantom_data(&paren.elem),
+        Type::Group(group) => is_phantom_data(&group.elem),
+        _ => false,
+    }
+}
@@ -1,26 +1,158 @@
 use derive_more::From;
 use serde::*;
-use std::borrow::Cow;
+use std::{borrow::Cow, marker::PhantomData};
 
 // Re-export schematic so that dependent crates don't need to directly depend on it.
 pub use schematic;
 pub use schematic::{Schema, TypeName};
 
+use schematic::{Describe, Describer};
+
 pub fn serialize_export<E: Into<Export>>(export: E) -> String {
     let export = export.into();
     serde_json::to_string(&export).expect("Failed to serialize export")
 }
 
+/// The reserved C# keywords, which can only be used as identifiers when prefixed with
+/// `@`.
+///
+/// Contextual keywords (e.g. `var` or `async`) are valid identifiers, so they aren't
+/// included.
+#[rustfmt::skip]
+pub const CS_KEYWORDS: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked",
+    "class", "const", "continue", "decimal", "default", "delegate", "do", "double", "else",
+    "enum", "event", "explicit", "extern", "false", "finally", "fixed", "float", "for",
+    "foreach", "goto", "if", "implicit", "in", "int", "interface", "internal", "is", "lock",
+    "long", "namespace", "new", "null", "object", "operator", "out", "override", "params",
+    "private", "protected", "public", "readonly", "ref", "return", "sbyte", "sealed", "short",
+    "sizeof", "stackalloc", "static", "string", "struct", "switch", "this", "throw", "true",
+    "try", "typeof", "uint", "ulong", "unchecked", "unsafe", "ushort", "using", "virtual",
+    "void", "volatile", "while",
+];
+
+/// Returns `true` if `ident` is a reserved C# keyword.
+pub fn is_cs_keyword(ident: &str) -> bool {
+    CS_KEYWORDS.contains(&ident)
+}
+
+/// The version of the ABI between the dylib and the generated bindings.
+///
+/// This must be incremented whenever the raw representation of any type or the
+/// signature of any of the runtime's exported functions changes, so that bindings
+/// generated by an older version of cs-bindgen refuse to load a newer dylib (and vice
+/// versa) rather than corrupting memory.
+pub const ABI_VERSION: u32 = 2;
+
+/// The initial value of the hash of the exported declarations, see
+/// [`hash_metadata`].
+///
+/// [`hash_metadata`]: fn.hash_metadata.html
+pub const METADATA_HASH_SEED: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Adds the serialized declaration of an export to the hash of a dylib's exported
+/// declarations.
+///
+/// The hash is computed both by the CLI when generating the bindings and by the dylib
+/// when the bindings are loaded, which may have been built with different versions of
+/// Rust, so this uses FNV-1a rather than the unstable hash used by `std`.
+pub fn hash_metadata(hash: u64, declaration: &[u8]) -> u64 {
+    declaration.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
 /// An item exported from the Rust as a language binding.
 #[derive(Debug, Clone, From, Serialize, Deserialize)]
 pub enum Export {
     Fn(Func),
     Method(Method),
     Named(NamedType),
+    Trait(Trait),
+    TraitImpl(TraitImpl),
+    StdImpl(StdImpl),
+    Conversion(Conversion),
+    Operator(Operator),
+    Const(Const),
+    Alias(Alias),
+    List(ListElement),
+}
+
+/// A `const` or `static` item exported from the Rust lib.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Const {
+    /// The name of the constant, as declared in the Rust source code.
+    pub name: Cow<'static, str>,
+
+    /// The path of the module that the constant is declared in.
+    pub module: Cow<'static, str>,
+
+    /// The name of the generated binding function that returns the value of the
+    /// constant.
+    pub binding: Cow<'static, str>,
+
+    /// The type that the constant is associated with, if it was declared in an
+    /// `impl` block.
+    pub self_type: Option<TypeName>,
+
+    pub repr: Repr,
+
+    /// The value of the constant, if it can be represented as a literal in C#.
+    pub value: Option<ConstValue>,
+}
+
+/// The value of a constant with a primitive or string type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConstValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(Cow<'static, str>),
+}
+
+macro_rules! const_value_from {
+    ($($ty:ty => $variant:ident as $inner:ty,)*) => {
+        $(
+            impl From<$ty> for ConstValue {
+                #[allow(clippy::unnecessary_cast)]
+                fn from(value: $ty) -> Self {
+                    ConstValue::$variant(value as $inner)
+                }
+            }
+        )*
+    };
+}
+
+const_value_from! {
+    i8 => Int as i64,
+    i16 => Int as i64,
+    i32 => Int as i64,
+    i64 => Int as i64,
+    isize => Int as i64,
+    u8 => UInt as u64,
+    u16 => UInt as u64,
+    u32 => UInt as u64,
+    u64 => UInt as u64,
+    usize => UInt as u64,
+    f32 => Float as f64,
+    f64 => Float as f64,
+}
+
+impl From<bool> for ConstValue {
+    fn from(value: bool) -> Self {
+        ConstValue::Bool(value)
+    }
+}
+
+impl From<&'static str> for ConstValue {
+    fn from(value: &'static str) -> Self {
+        ConstValue::String(value.into())
+    }
 }
 
 /// A free function exported from the Rust lib.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Func {
     /// The original name of the function, as declared in the Rust source code.
     ///
@@ -28,6 +160,9 @@ pub struct Func {
     /// value of `binding` specifies the name of the generated binding function.
     pub name: Cow<'static, str>,
 
+    /// The path of the module that the function is declared in.
+    pub module: Cow<'static, str>,
+
     /// The name of the generated binding function.
     ///
     /// This is the exported function that is directly accessible in the generated
@@ -36,6 +171,16 @@ pub struct Func {
     /// "true" name of the function, see `name`.
     pub binding: Cow<'static, str>,
 
+    /// The name of the function in C#, if it was renamed with
+    /// `#[cs_bindgen(rename = "...")]`.
+    ///
+    /// Renamed functions use this name as-is, rather than converting `name` to the
+    /// C# naming conventions.
+    pub rename: Option<Cow<'static, str>>,
+
+    /// The doc comment on the function, if any.
+    pub docs: Option<Cow<'static, str>>,
+
     /// The argument types for the function.
     ///
     /// Note that these are the types of the original function, NOT the generated
@@ -45,8 +190,29 @@ pub struct Func {
     /// The return type of the function.
     ///
     /// Note that this is the return type of the original function, NOT the generated
-    /// binding function.
+    /// binding function. For async functions, this is the output of the future.
     pub output: Option<Repr>,
+
+    /// The names of the C# `out` parameters for the trailing elements of a tuple
+    /// return type, set with `#[cs_bindgen(out(...))]`.
+    ///
+    /// The remaining leading elements of the tuple are returned from the C# method as
+    /// usual.
+    pub out_params: Vec<Cow<'static, str>>,
+
+    /// Whether the function is an `async fn`.
+    ///
+    /// The binding function for an async function takes an additional `RawCompletion`
+    /// argument and doesn't return a value. The future is run on a background thread,
+    /// and the completion callback is invoked with the result once it's done.
+    pub is_async: bool,
+
+    /// The name of the function that returns the command function for batched calls,
+    /// if the function was exported with `#[cs_bindgen(batch)]`.
+    ///
+    /// Batched functions can also be recorded into a C# `CommandBuffer` and submitted
+    /// along with other calls in a single call into the lib.
+    pub command: Option<Cow<'static, str>>,
 }
 
 /// A user-defined type (i.e. a struct or an enum).
@@ -57,6 +223,9 @@ pub struct Func {
 ///
 /// An exported name type can only be a struct or an enum, as exporting unions is
 /// not supported.
+///
+/// Types renamed with `#[cs_bindgen(rename = "...")]` use the new name in
+/// `type_name`, and renamed fields and variants use their new names in the schema.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NamedType {
     pub type_name: TypeName,
@@ -65,13 +234,83 @@ pub struct NamedType {
     pub index_fn: Cow<'static, str>,
     pub drop_vec_fn: Cow<'static, str>,
     pub convert_list_fn: Cow<'static, str>,
+
+    /// The name of the function that drops a handle to the type, if the type is
+    /// marshaled as a handle.
+    pub drop_fn: Option<Cow<'static, str>>,
+
+    /// The name of the function that returns the size of the type's raw
+    /// representation, if the type is marshaled by value.
+    ///
+    /// Used to verify that structs passed directly to Rust have the same layout in C#.
+    pub size_fn: Option<Cow<'static, str>>,
+
+    /// The fields of the type that were renamed, using their new names.
+    ///
+    /// Renamed fields are used as-is, rather than being converted to the C# naming
+    /// conventions.
+    pub renamed_fields: Vec<Cow<'static, str>>,
+
+    /// The doc comment on the type, if any.
+    pub docs: Option<Cow<'static, str>>,
+
+    /// The doc comments on the variants of an enum, in the order the variants are
+    /// declared. Empty for structs.
+    pub variant_docs: Vec<Option<Cow<'static, str>>>,
+
+    /// The integer type of the discriminant of a C-like enum, if the enum declares one
+    /// with a `#[repr(...)]` attribute. C-like enums without a `repr` use `isize`.
+    pub discriminant: Option<Repr>,
+
+    /// Whether the enum is `#[non_exhaustive]`, in which case the C# bindings accept
+    /// variants that were added to the enum after the bindings were generated.
+    pub is_non_exhaustive: bool,
+
+    /// Whether the type implements `Send`.
+    pub is_send: bool,
+
+    /// Whether the type implements `Sync`. Handles for types that aren't `Sync` must
+    /// not be used from multiple threads at once.
+    pub is_sync: bool,
+
+    /// Whether the type is a set of bit flags, i.e. it was exported with
+    /// `#[cs_bindgen(flags)]`. Flags types are generated as a C# `[Flags]` enum
+    /// instead of a struct.
+    pub is_flags: bool,
+
+    /// The name of the binding function that creates the default value of the type,
+    /// if the type implements `Default`.
+    pub default_fn: Option<Cow<'static, str>>,
+
+    /// The name of the binding function that formats the value with its `Display`
+    /// impl, if the type implements `Display`.
+    pub display_fn: Option<Cow<'static, str>>,
+
+    /// The name of the binding function that compares two values with their
+    /// `PartialEq` impl, if the type implements `PartialEq`.
+    pub eq_fn: Option<Cow<'static, str>>,
+
+    /// The name of the binding function that hashes the value with its `Hash` impl,
+    /// if the type implements `Hash`.
+    pub hash_fn: Option<Cow<'static, str>>,
+
+    /// The name of the binding function that compares two values with their
+    /// `PartialOrd` impl, if the type implements `PartialOrd`.
+    pub cmp_fn: Option<Cow<'static, str>>,
+
+    /// The name of the binding function that clones the value, if the type is marshaled
+    /// as a handle and implements `Clone`.
+    pub clone_fn: Option<Cow<'static, str>>,
 }
 
 impl NamedType {
     pub fn schema(&self) -> Option<&Schema> {
         match &self.binding_style {
             BindingStyle::Value(schema) => Some(schema),
-            BindingStyle::Handle => None,
+            BindingStyle::Handle
+            | BindingStyle::Marshal(..)
+            | BindingStyle::Custom(_)
+            | BindingStyle::Transparent(_) => None,
         }
     }
 }
@@ -81,15 +320,240 @@ pub struct Method {
     pub name: Cow<'static, str>,
     pub binding: Cow<'static, str>,
     pub self_type: TypeName,
+
+    /// The name of the method in C#, if it was renamed with
+    /// `#[cs_bindgen(rename = "...")]`.
+    pub rename: Option<Cow<'static, str>>,
+
+    /// The doc comment on the method, if any.
+    pub docs: Option<Cow<'static, str>>,
+
     pub receiver: Option<ReceiverStyle>,
     pub inputs: Vec<FnArg>,
     pub output: Option<Repr>,
+
+    /// The names of the C# `out` parameters for the trailing elements of a tuple
+    /// return type. See `Func::out_params`.
+    pub out_params: Vec<Cow<'static, str>>,
+
+    /// Set if the method is exposed as part of a C# property rather than as a method.
+    pub accessor: Option<Accessor>,
+
+    /// Whether the method is exposed as a constructor for the C# class.
+    ///
+    /// Constructors don't have a receiver and return `Self` (or `Arc<Self>` for shared
+    /// handles). Methods named `new` are
+    /// treated as constructors by default, and other methods can opt in with
+    /// `#[cs_bindgen(constructor)]`.
+    pub is_constructor: bool,
+
+    /// The name of the function that returns the command function for batched calls,
+    /// if the method was exported with `#[cs_bindgen(batch)]`. See `Func::command`.
+    pub command: Option<Cow<'static, str>>,
+}
+
+/// Identifies a method as the getter or setter for a property or indexer.
+///
+/// The getter takes `&self` and returns the value of the property, and the setter
+/// takes `&mut self` and the new value for the property. Property accessors carry the
+/// name of the property, which is the name of the method with any `get_`/`set_`
+/// prefix removed. Indexer accessors take the index as an additional first argument.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Accessor {
+    Getter(Cow<'static, str>),
+    Setter(Cow<'static, str>),
+    IndexGetter,
+    IndexSetter,
+}
+
+impl Accessor {
+    /// Returns the name of the property, or `None` if the accessor is for an indexer.
+    pub fn property_name(&self) -> Option<&str> {
+        match self {
+            Accessor::Getter(name) | Accessor::Setter(name) => Some(name),
+            Accessor::IndexGetter | Accessor::IndexSetter => None,
+        }
+    }
+
+    pub fn is_getter(&self) -> bool {
+        matches!(self, Accessor::Getter(_) | Accessor::IndexGetter)
+    }
+}
+
+/// A trait exported from the Rust lib.
+///
+/// Exported traits are generated as C# interfaces, which are implemented by the
+/// C# classes for any exported types that implement the trait (see [`TraitImpl`]).
+///
+/// If `managed` is set, C# classes can also implement the trait and be passed to Rust
+/// as a `Box<dyn Trait>`. This is only supported for traits whose methods take their
+/// arguments by value.
+///
+/// A `Box<dyn Trait>` returned from Rust is passed to C# as a handle. `boxed_as_dyn_fn`
+/// is the name of the function that borrows the boxed trait object as the raw
+/// representation of a `&dyn Trait`, and `drop_boxed_fn` is the name of the function
+/// that drops it.
+///
+/// [`TraitImpl`]: struct.TraitImpl.html
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trait {
+    pub type_name: TypeName,
+    pub methods: Vec<TraitMethod>,
+    pub managed: bool,
+    pub boxed_as_dyn_fn: Cow<'static, str>,
+    pub drop_boxed_fn: Cow<'static, str>,
+}
+
+/// A method declared in an exported trait.
+///
+/// All trait methods take `&self` as the receiver, so the receiver isn't included
+/// in `inputs`. The binding function takes the raw trait object as its first
+/// argument.
+///
+/// `default_binding` is set for methods with a default implementation in traits that
+/// can be implemented in C#. It's the name of the binding function that invokes the
+/// default implementation for a C# object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraitMethod {
+    pub name: Cow<'static, str>,
+    pub binding: Cow<'static, str>,
+    pub inputs: Vec<FnArg>,
+    pub output: Option<Repr>,
+    pub default_binding: Option<Cow<'static, str>>,
 }
 
+/// An implementation of an exported trait for an exported type.
+///
+/// `as_dyn_fn` is the name of the generated function that converts a handle to the
+/// type into the raw representation of a `&dyn Trait`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraitImpl {
+    pub trait_name: TypeName,
+    pub self_type: TypeName,
+    pub as_dyn_fn: Cow<'static, str>,
+}
+
+/// An implementation of a standard library trait for an exported type, declared in
+/// an `impl` block tagged with `#[cs_bindgen]` rather than on the type itself.
+///
+/// `binding` is the name of the binding function for the trait, which has the same
+/// signature as the corresponding function on [`NamedType`], e.g. `display_fn` for
+/// `Display`. Trait impls are merged into the declaration of the type when the
+/// exports are loaded.
+///
+/// [`NamedType`]: struct.NamedType.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StdImpl {
+    pub self_type: TypeName,
+    pub trait_: StdTrait,
+    pub binding: Cow<'static, str>,
+}
+
+/// A conversion between two types, declared with an impl of `From` or `TryFrom` tagged
+/// with `#[cs_bindgen]`.
+///
+/// `binding` is the name of the binding function that converts a `from` value into an
+/// `into` value. For fallible (i.e. `TryFrom`) conversions, the binding function
+/// instead returns an `Option<into>` that's `None` if the conversion failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conversion {
+    pub from: Repr,
+    pub into: Repr,
+    pub fallible: bool,
+    pub binding: Cow<'static, str>,
+}
+
+/// An arithmetic or bitwise operator, declared with an impl of one of the operator
+/// traits in `std::ops` tagged with `#[cs_bindgen]`.
+///
+/// `binding` is the name of the binding function that applies the operator, which
+/// takes `lhs` and `rhs` (for binary operators) and returns `output`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operator {
+    pub trait_: OperatorTrait,
+    pub lhs: Repr,
+    pub rhs: Option<Repr>,
+    pub output: Repr,
+    pub binding: Cow<'static, str>,
+}
+
+/// The operator traits that can be exported with an `impl` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperatorTrait {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Neg,
+    Not,
+}
+
+/// A type alias tagged with `#[cs_bindgen]`, e.g. `type Seat = u8;`.
+///
+/// Aliases don't have any bindings of their own, since values of the alias are passed
+/// as the aliased type. They're exported so that the generated bindings can refer to
+/// the type by the same name as the Rust code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alias {
+    /// The name of the alias, as declared in the Rust source code.
+    pub name: Cow<'static, str>,
+
+    /// The path of the module that the alias is declared in.
+    pub module: Cow<'static, str>,
+
+    /// The name of the alias in C#, if it was renamed with
+    /// `#[cs_bindgen(rename = "...")]`.
+    pub rename: Option<Cow<'static, str>>,
+
+    /// The doc comment on the alias, if any.
+    pub docs: Option<Cow<'static, str>>,
+
+    /// The aliased type.
+    pub repr: Repr,
+}
+
+/// The helper functions for passing lists of an element type that isn't a primitive,
+/// e.g. the `Vec<String>` elements of a `Vec<Vec<String>>`.
+///
+/// These are generated for each element type of the `Vec<T>` and `&[T]` types used in
+/// the signatures of exported functions and the fields of exported types, so the same
+/// element type may be described more than once. Each set of functions has the same
+/// signatures as the corresponding functions on [`NamedType`].
+///
+/// [`NamedType`]: struct.NamedType.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListElement {
+    pub element: Repr,
+    pub index_fn: Cow<'static, str>,
+    pub drop_vec_fn: Cow<'static, str>,
+    pub convert_list_fn: Cow<'static, str>,
+}
+
+/// The standard library traits that can be exported with an `impl` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StdTrait {
+    Default,
+    Display,
+    PartialEq,
+    Hash,
+    PartialOrd,
+    Clone,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FnArg {
     pub name: Cow<'static, str>,
     pub repr: Repr,
+
+    /// The default value of the argument, if it was declared with
+    /// `#[cs_bindgen(default = ...)]`.
+    ///
+    /// Arguments with a default value are generated as C# optional parameters.
+    pub default: Option<ConstValue>,
 }
 
 impl FnArg {
@@ -100,8 +564,15 @@ impl FnArg {
         Self {
             name: name.into(),
             repr,
+            default: None,
         }
     }
+
+    /// Sets the default value of the argument.
+    pub fn with_default(mut self, default: Option<ConstValue>) -> Self {
+        self.default = default;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -119,6 +590,67 @@ pub enum BindingStyle {
 
     /// Values of the type are marshalled directly into C# values.
     Value(Schema),
+
+    /// Values of the type are serialized with serde and deserialized into a C# class,
+    /// i.e. the type was exported with `#[cs_bindgen(marshal = "...")]`.
+    ///
+    /// This is a fallback for types that can't be represented structurally.
+    Marshal(MarshalFormat, Schema),
+
+    /// Values of the type are converted to and from another type with user-provided
+    /// conversion functions, i.e. the type was exported with
+    /// `#[cs_bindgen(raw = "...", into_raw_with = "...", from_raw_with = "...")]`.
+    Custom(CustomMarshal),
+
+    /// Values of the type are passed as the value of the type's only field, i.e. the
+    /// type is a newtype struct exported with `#[cs_bindgen(transparent)]` or
+    /// `#[repr(transparent)]`.
+    Transparent(Transparent),
+}
+
+/// The description of a newtype struct that's passed as its inner value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Transparent {
+    /// The type of the struct's only field.
+    pub inner: Repr,
+
+    /// Whether a C# wrapper struct is generated for the type, i.e. the type was
+    /// exported with `#[cs_bindgen(transparent, wrapper)]`. Otherwise the type is
+    /// exposed as the C# type for `inner`.
+    pub wrapper: bool,
+}
+
+/// The conversions for a type exported with user-provided conversion functions.
+///
+/// The value is converted to the `raw` type in Rust, which is passed across the FFI
+/// boundary like any other value of that type. On the C# side, the converter class
+/// converts between the C# type for `raw` and `cs_type`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomMarshal {
+    /// The type that values are converted to before being passed across the FFI
+    /// boundary.
+    pub raw: Repr,
+
+    /// The fully-qualified name of the C# class that converts values, which must
+    /// have static `FromRaw` and `IntoRaw` methods.
+    pub converter: Cow<'static, str>,
+
+    /// The fully-qualified name of the C# type that values are exposed as, if the
+    /// type doesn't have a C# type with the same name as the Rust type.
+    pub cs_type: Option<Cow<'static, str>>,
+}
+
+/// The serialization format used for a type exported with
+/// `#[cs_bindgen(marshal = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarshalFormat {
+    /// Serialized with `serde_json` and deserialized with `System.Text.Json`, i.e.
+    /// `marshal = "json"`.
+    Json,
+
+    /// Serialized with `rmp-serde` and deserialized with MessagePack-CSharp, i.e.
+    /// `marshal = "msgpack"`.
+    MessagePack,
 }
 
 /// The supported type representations that can be passed across the FFI boundary.
@@ -140,6 +672,12 @@ pub enum Repr {
     U64,
     USize,
 
+    /// A 128-bit signed integer, passed as two 64-bit halves.
+    I128,
+
+    /// A 128-bit unsigned integer, passed as two 64-bit halves.
+    U128,
+
     F32,
     F64,
 
@@ -156,6 +694,13 @@ pub enum Repr {
     /// A borrowed pointer.
     Ref(Box<Repr>),
 
+    /// A mutable reference to a value that's marshaled by value, i.e. a `&mut T`
+    /// argument.
+    ///
+    /// The caller passes a pointer to the raw value, and the modified value is written
+    /// back to the caller once the call completes.
+    RefMut(Box<Repr>),
+
     /// An owned array of elements.
     Vec(Box<Repr>),
 
@@ -168,12 +713,43 @@ pub enum Repr {
         len: usize,
     },
 
+    /// A tuple of values.
+    Tuple(Vec<Repr>),
+
+    /// An owned map of key-value pairs, e.g. a `HashMap` or `BTreeMap`.
+    Map {
+        key: Box<Repr>,
+        value: Box<Repr>,
+    },
+
     /// An owned string.
     String,
 
     /// A borrowed string slice.
     Str,
 
+    /// A span of time, i.e. `std::time::Duration`.
+    ///
+    /// Passed as the number of .NET ticks (100 nanoseconds) in the duration.
+    Duration,
+
+    /// A point in time, i.e. `std::time::SystemTime`.
+    ///
+    /// Passed as the number of .NET ticks since the Unix epoch, which is negative for
+    /// times before the epoch.
+    SystemTime,
+
+    /// A calendar date without a time zone, i.e. `chrono::NaiveDate`.
+    ///
+    /// Passed as the number of days since January 1st, 0001, which is the `DayNumber`
+    /// of a C# `DateOnly`.
+    Date,
+
+    /// A UUID, i.e. `uuid::Uuid`.
+    ///
+    /// Passed using the same memory layout as a C# `Guid`.
+    Uuid,
+
     /// An optional value.
     Option(Box<Repr>),
 
@@ -182,6 +758,45 @@ pub enum Repr {
         ok: Box<Repr>,
         err: Box<Repr>,
     },
+
+    /// A borrowed trait object for an exported trait, i.e. `&dyn Trait`.
+    ///
+    /// The referenced trait must be included in the set of exported traits.
+    Dyn(TypeName),
+
+    /// An owned trait object for an exported trait implemented by a C# class, i.e.
+    /// `Box<dyn Trait>`.
+    BoxDyn(TypeName),
+
+    /// An owned trait object for an exported trait returned to the caller, i.e.
+    /// `Box<dyn Trait>`.
+    ///
+    /// Passed as a handle to the boxed trait object, which the caller uses through the
+    /// trait's interface.
+    DynHandle(TypeName),
+
+    /// An iterator returned to the caller, i.e. `impl Iterator<Item = T>`.
+    ///
+    /// Items are converted lazily as the caller pulls them from the iterator.
+    Iter(Box<Repr>),
+
+    /// A queue of events returned to the caller, i.e. `cs_bindgen::event::EventQueue<T>`.
+    ///
+    /// Events are converted as the caller pulls them from the queue.
+    Events(Box<Repr>),
+
+    /// A callback provided by the caller, e.g. an `impl Fn(A, B) -> C` argument.
+    ///
+    /// Callbacks that don't return a value have an output of `Repr::Unit`.
+    Fn {
+        inputs: Vec<Repr>,
+        output: Box<Repr>,
+    },
+
+    /// A cancellation flag provided by the caller, i.e. `cs_bindgen::cancel::CancelFlag`.
+    ///
+    /// Only supported as an argument, where it's exposed to C# as a `CancellationToken`.
+    CancelFlag,
 }
 
 impl Repr {
@@ -200,3 +815,69 @@ pub trait Named {
     // custom `TypeName` type doesn't require allocation.
     fn type_name() -> TypeName;
 }
+
+/// The module of the type name used in the description of boxed fields, see
+/// [`Boxed`].
+///
+/// [`Boxed`]: struct.Boxed.html
+pub const BOXED_MODULE: &str = "cs_bindgen::boxed";
+
+/// Describes a `Box<T>` field of an exported type, where `T` is also exported.
+///
+/// schematic describes a `Box<T>` the same as a `T`, which hides the extra
+/// indirection from the generated bindings, and never terminates when describing a
+/// recursive type (e.g. an enum with a `Box<Self>` field). Instead, `#[cs_bindgen]`
+/// describes boxed fields as a newtype struct named `Box` (in [`BOXED_MODULE`])
+/// wrapping a unit struct that only has the name of `T`, which the generated
+/// bindings resolve to the full description of `T`. Use [`boxed_type_name`] to
+/// recognize boxed fields in a schema.
+///
+/// This type is never constructed, it's only used for its `Describe` impl.
+///
+/// [`BOXED_MODULE`]: constant.BOXED_MODULE.html
+/// [`boxed_type_name`]: fn.boxed_type_name.html
+pub struct Boxed<T>(PhantomData<T>);
+
+impl<T: Named> Describe for Boxed<T> {
+    fn type_name() -> TypeName {
+        TypeName::new("Box", BOXED_MODULE)
+    }
+
+    fn describe<D>(describer: D) -> Result<D::Ok, D::Error>
+    where
+        D: Describer,
+    {
+        describer.describe_newtype_struct::<TypeRef<T>>(Self::type_name())
+    }
+}
+
+/// Describes only the name of `T`, see [`Boxed`].
+///
+/// [`Boxed`]: struct.Boxed.html
+struct TypeRef<T>(PhantomData<T>);
+
+impl<T: Named> Describe for TypeRef<T> {
+    fn type_name() -> TypeName {
+        T::type_name()
+    }
+
+    fn describe<D>(describer: D) -> Result<D::Ok, D::Error>
+    where
+        D: Describer,
+    {
+        describer.describe_unit_struct(T::type_name())
+    }
+}
+
+/// Returns the name of the boxed type if `schema` describes a boxed field, i.e. a field
+/// described with [`Boxed`].
+///
+/// [`Boxed`]: struct.Boxed.html
+pub fn boxed_type_name(schema: &Schema) -> Option<&TypeName> {
+    let type_name = schema.type_name()?;
+    if type_name.name != "Box" || type_name.module != BOXED_MODULE {
+        return None;
+    }
+
+    schema.as_struct_like()?.fields.first()?.schema.type_name()
+}
@@ -13,11 +13,30 @@
 //! > TODO: Describe what safety invariants have to be upheld when implementing the
 //! > unsafe traits.
 //!
+//! Raw representations must be valid when zero-initialized, since the generated
+//! bindings return a zeroed value when a panic is caught at the FFI boundary. See
+//! [the `panic` module](../panic/index.html) for more.
+//!
 //! [nomicon-interop]: https://doc.rust-lang.org/nomicon/ffi.html#interoperability-with-foreign-code
 
+use crate::{
+    alloc,
+    borrow::{self, BorrowGuard},
+    diagnostics,
+};
 use core::mem::MaybeUninit;
 use cs_bindgen_shared::Repr;
-use std::{convert::TryInto, mem, slice, str};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryInto,
+    ffi::c_void,
+    hash::{BuildHasher, Hash},
+    mem,
+    ops::{Deref, DerefMut},
+    ptr, slice, str,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 /// The ABI-compatible equivalent to [`String`].
 ///
@@ -38,6 +57,12 @@ pub type RawStr = RawSlice<u8>;
 ///
 /// See [the module level documentation](./index.html) for more.
 ///
+/// # Safety
+///
+/// A zero-initialized value of the type must be valid, since binding functions return
+/// a zeroed value after catching a panic. This rules out references, function
+/// pointers, and other types with a niche.
+///
 /// [`AbiArgument`]: trait.AbiArgument.html
 /// [`AbiReturn`]: trait.AbiReturn.html
 pub unsafe trait AbiPrimitive: Copy {}
@@ -125,6 +150,37 @@ abi_primitives! {
     f64 => F64,
 }
 
+// Slices are passed as a pointer to the Rust elements. This allows slices of
+// primitives to be borrowed directly from C#, since the raw representation of each
+// element is the same as the Rust representation. Slices of any other type are only
+// returned to C#, which reads the elements in place through the borrowed slice.
+impl<'a, T: Abi> Abi for &'a [T] {
+    type Abi = RawSlice<T>;
+
+    fn repr() -> Repr {
+        Repr::Slice(Box::new(T::repr()))
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        RawSlice {
+            ptr: self.as_ptr(),
+            len: self.len(),
+        }
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        self.as_abi()
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.as_slice()
+    }
+}
+
+// NOTE: Binding functions for functions that don't return a value return `()`
+// directly, rather than its raw representation.
+unsafe impl AbiPrimitive for () {}
+
 impl Abi for () {
     type Abi = u8;
 
@@ -146,30 +202,95 @@ impl Abi for () {
 }
 
 // Pointers to any ABI primitive are also valid ABI primitives.
-unsafe impl<'a, T> AbiPrimitive for &'a T {}
+//
+// NOTE: References are deliberately not ABI primitives, since a zeroed reference is
+// invalid. Use raw pointers instead.
 unsafe impl<T> AbiPrimitive for *const T {}
 unsafe impl<T> AbiPrimitive for *mut T {}
 
+// A `Box<T>` is passed as a pointer to a separate allocation holding the raw value of
+// the `T`, so that recursive types have a finite raw representation. The allocation
+// is a buffer allocated with `alloc_buffer`, so either side can free it with
+// `free_buffer`, passing the size of the raw value.
 impl<T: Abi> Abi for Box<T> {
-    type Abi = *const T;
+    type Abi = *mut T::Abi;
 
     fn repr() -> Repr {
         Repr::Box(Box::new(T::repr()))
     }
 
+    // NOTE: Unlike other types, borrowing a box allocates, since the raw value of the
+    // contents has to be written somewhere. The receiver frees the allocation but
+    // doesn't drop its contents.
+    fn as_abi(&self) -> Self::Abi {
+        box_raw(T::as_abi(self))
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        box_raw(T::into_abi(*self))
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        let raw = ptr::read(abi);
+        free_buffer(abi as *mut u8, mem::size_of::<T::Abi>());
+        Box::new(T::from_abi(raw))
+    }
+}
+
+/// Moves a raw value into a buffer allocated with [`alloc_buffer`].
+///
+/// [`alloc_buffer`]: fn.alloc_buffer.html
+fn box_raw<T>(raw: T) -> *mut T {
+    assert!(
+        mem::align_of::<T>() <= BUFFER_ALIGN,
+        "Boxed raw values must not have an alignment larger than {}",
+        BUFFER_ALIGN,
+    );
+
+    unsafe {
+        let ptr = alloc_buffer(mem::size_of::<T>()) as *mut T;
+        ptr::write(ptr, raw);
+        ptr
+    }
+}
+
+/// A handle type that's shared between Rust and C# through an [`Arc`].
+///
+/// Each C# object holding a shared handle owns one strong reference to the value,
+/// which is released when the object is disposed. This trait is implemented by
+/// `#[cs_bindgen(shared)]` and shouldn't be implemented manually.
+///
+/// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+pub unsafe trait SharedHandle: Abi<Abi = *const Self> {}
+
+/// A type that's passed to C# as a handle, i.e. an owned pointer to the value.
+///
+/// Some features (e.g. property accessors) require that the value can be borrowed
+/// from the C# object, so they're only supported for handle types. The code generated
+/// for these features asserts that the type implements `Handle`, so that using them
+/// with a type that's marshaled by value is reported when compiling the Rust crate.
+/// This trait is implemented by `#[cs_bindgen]` and shouldn't be implemented manually.
+pub trait Handle: Abi<Abi = *const Self> {}
+
+impl<T: SharedHandle> Abi for Arc<T> {
+    type Abi = *const T;
+
+    fn repr() -> Repr {
+        T::repr()
+    }
+
     fn as_abi(&self) -> Self::Abi {
         &**self as *const _
     }
 
     fn into_abi(self) -> Self::Abi {
-        Box::into_raw(self)
+        diagnostics::handle_created::<T>();
+        Arc::into_raw(self)
     }
 
     unsafe fn from_abi(abi: Self::Abi) -> Self {
-        // NOTE: We need to cast the raw pointer to a `*mut T` in order to reconstruct the
-        // `Box`. If the calling code never did anything invalid with the pointer (such as
-        // mutating its contents) this should be safe.
-        Box::from_raw(abi as *mut T)
+        diagnostics::handle_released::<T>();
+        Arc::from_raw(abi)
     }
 }
 
@@ -213,392 +334,1771 @@ impl Abi for bool {
     }
 }
 
-impl<T> Abi for Vec<T>
-where
-    T: Abi,
-{
-    type Abi = RawVec<T>;
+macro_rules! abi_128 {
+    ($($ty:ty => $raw:ident, $repr:ident,)*) => {
+        $(
+            /// The raw representation of a 128-bit integer, split into two 64-bit halves.
+            ///
+            /// C# doesn't have a 128-bit integer type that can be passed across the FFI
+            /// boundary, so the halves are combined into a `BigInteger` on the C# side.
+            #[repr(C)]
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+            pub struct $raw {
+                pub low: u64,
+                pub high: u64,
+            }
+
+            unsafe impl AbiPrimitive for $raw {}
+
+            impl Abi for $ty {
+                type Abi = $raw;
+
+                fn repr() -> Repr {
+                    Repr::$repr
+                }
+
+                fn as_abi(&self) -> Self::Abi {
+                    (*self).into_abi()
+                }
+
+                fn into_abi(self) -> Self::Abi {
+                    $raw {
+                        low: self as u64,
+                        high: (self >> 64) as u64,
+                    }
+                }
+
+                unsafe fn from_abi(abi: Self::Abi) -> Self {
+                    (((abi.high as u128) << 64) | abi.low as u128) as $ty
+                }
+            }
+        )*
+    };
+}
+
+abi_128! {
+    i128 => RawI128, I128,
+    u128 => RawU128, U128,
+}
+
+/// The number of nanoseconds in a .NET tick, which is the unit used by `TimeSpan` and
+/// `DateTimeOffset`.
+const NANOS_PER_TICK: u128 = 100;
+
+/// The number of .NET ticks in a second.
+const TICKS_PER_SEC: u64 = 10_000_000;
+
+/// `Duration` is passed as the number of ticks in the duration, which is the raw
+/// representation of a C# `TimeSpan`. Any precision finer than a tick is truncated.
+///
+/// Negative `TimeSpan` values can't be represented as a `Duration`, and converting
+/// one panics.
+impl Abi for Duration {
+    type Abi = i64;
 
     fn repr() -> Repr {
-        Repr::Vec(Box::new(T::repr()))
+        Repr::Duration
     }
 
     fn as_abi(&self) -> Self::Abi {
-        self.as_slice().into()
+        (*self).into_abi()
     }
 
     fn into_abi(self) -> Self::Abi {
-        self.into()
+        (self.as_nanos() / NANOS_PER_TICK)
+            .try_into()
+            .expect("Duration is too long to be converted to a `TimeSpan`")
     }
 
     unsafe fn from_abi(abi: Self::Abi) -> Self {
-        abi.into_vec()
+        let ticks: u64 = abi
+            .try_into()
+            .expect("Negative `TimeSpan` can't be converted to a `Duration`");
+        duration_from_ticks(ticks)
     }
 }
 
-impl Abi for String {
-    type Abi = RawVec<u8>;
+/// `SystemTime` is passed as the number of ticks since the Unix epoch, and is exposed
+/// to C# as a `DateTimeOffset` in UTC.
+impl Abi for SystemTime {
+    type Abi = i64;
 
     fn repr() -> Repr {
-        Repr::String
+        Repr::SystemTime
     }
 
     fn as_abi(&self) -> Self::Abi {
-        self.as_bytes().into()
+        (*self).into_abi()
     }
 
     fn into_abi(self) -> Self::Abi {
-        self.into_bytes().into()
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.into_abi(),
+            Err(err) => -err.duration().into_abi(),
+        }
     }
 
     unsafe fn from_abi(abi: Self::Abi) -> Self {
-        abi.into_string()
+        // NOTE: `i64::MIN` can't be negated, but its magnitude still fits in a `u64`.
+        let ticks = (abi as i128).abs() as u64;
+        if abi >= 0 {
+            UNIX_EPOCH + duration_from_ticks(ticks)
+        } else {
+            UNIX_EPOCH - duration_from_ticks(ticks)
+        }
     }
 }
 
-impl<'a> Abi for &'a str {
-    type Abi = RawSlice<u8>;
+fn duration_from_ticks(ticks: u64) -> Duration {
+    let subsec_nanos = (ticks % TICKS_PER_SEC) as u128 * NANOS_PER_TICK;
+    Duration::new(ticks / TICKS_PER_SEC, subsec_nanos as u32)
+}
+
+/// The raw representation of a `Uuid`, which has the same layout as a C# `Guid`.
+///
+/// `Guid` stores its first three fields as little-endian integers, whereas a `Uuid`
+/// stores all of its bytes in big-endian order, so the bytes of those fields are
+/// swapped when converting.
+#[cfg(feature = "uuid")]
+#[repr(C, align(4))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawGuid([u8; 16]);
+
+#[cfg(feature = "uuid")]
+unsafe impl AbiPrimitive for RawGuid {}
+
+#[cfg(feature = "uuid")]
+impl RawGuid {
+    /// Swaps the byte order of the first three fields, converting between the byte
+    /// order of a `Uuid` and a `Guid`.
+    fn swap_fields(mut bytes: [u8; 16]) -> [u8; 16] {
+        bytes[0..4].reverse();
+        bytes[4..6].reverse();
+        bytes[6..8].reverse();
+        bytes
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Abi for uuid::Uuid {
+    type Abi = RawGuid;
 
     fn repr() -> Repr {
-        Repr::Str
+        Repr::Uuid
     }
 
     fn as_abi(&self) -> Self::Abi {
-        (*self).into()
+        RawGuid(RawGuid::swap_fields(*self.as_bytes()))
     }
 
     fn into_abi(self) -> Self::Abi {
-        self.into()
+        self.as_abi()
     }
 
     unsafe fn from_abi(abi: Self::Abi) -> Self {
-        abi.as_str_unchecked()
+        uuid::Uuid::from_bytes(RawGuid::swap_fields(abi.0))
     }
 }
 
-/// Raw representation of a [`Vec`] compatible with FFI.
-///
-/// When converting a `Vec<T>` into a `RawVec<T>`, no conversion is performed for
-/// the elements of the vec. Instead, the generated C# code is expected to determine
-/// if conversion is needed or not, and either memcopy the entire array or to
-/// individually convert each element. The `[cs_bindgen]` proc macro generates a
-/// conversion function for each type that the C# code uses to perform this
-/// conversion.
-///
-/// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
-#[repr(C)]
-pub struct RawVec<T> {
-    pub ptr: *const T,
-    pub len: usize,
-    pub capacity: usize,
-}
+/// `chrono::Duration` is passed as the number of ticks in the duration, the same as a
+/// std `Duration`. Unlike a std `Duration` it can be negative, so any `TimeSpan` can be
+/// converted to it.
+#[cfg(feature = "chrono")]
+impl Abi for chrono::Duration {
+    type Abi = i64;
 
-impl<T> RawVec<T> {
-    pub unsafe fn into_vec(self) -> Vec<T> {
-        // NOTE: We need to cast the raw pointer to a `*mut T` in order to reconstruct the
-        // `Vec`. If the calling code never did anything invalid with the pointer (such as
-        // mutating its contents) this should be safe.
-        Vec::from_raw_parts(self.ptr as *mut _, self.len, self.capacity)
+    fn repr() -> Repr {
+        Repr::Duration
     }
-}
 
-impl<T> Clone for RawVec<T> {
-    fn clone(&self) -> Self {
-        Self {
-            ptr: self.ptr,
-            len: self.len,
-            capacity: self.capacity,
-        }
+    fn as_abi(&self) -> Self::Abi {
+        (*self).into_abi()
     }
-}
-impl<T> Copy for RawVec<T> {}
 
-impl RawVec<u8> {
-    /// Reconstructs the original string from its raw parts.
-    ///
-    /// # Safety
-    ///
-    /// `into_string` must only be called once per string instance. Calling it more than
-    /// once on the same string will result in undefined behavior.
-    pub unsafe fn into_string(self) -> String {
-        // NOTE: We need to cast the raw pointer to a `*mut T` in order to reconstruct the
-        // `STring`. If the calling code never did anything invalid with the pointer (such
-        // as mutating its contents) this should be safe.
-        String::from_raw_parts(self.ptr as *mut _, self.len, self.capacity)
+    fn into_abi(self) -> Self::Abi {
+        // NOTE: The duration is split into whole seconds first, since the number of
+        // nanoseconds in a duration may not fit in an `i64`.
+        let secs = self.num_seconds();
+        let subsec_nanos = (self - chrono::Duration::seconds(secs))
+            .num_nanoseconds()
+            .unwrap();
+        secs.checked_mul(TICKS_PER_SEC as i64)
+            .and_then(|ticks| ticks.checked_add(subsec_nanos / NANOS_PER_TICK as i64))
+            .expect("Duration is too long to be converted to a `TimeSpan`")
     }
-}
-
-unsafe impl<T> AbiPrimitive for RawVec<T> {}
 
-impl<T> From<&'_ [T]> for RawVec<T> {
-    fn from(from: &[T]) -> Self {
-        Self {
-            ptr: from.as_ptr(),
-            len: from.len(),
-            capacity: 0,
-        }
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        let ticks_per_sec = TICKS_PER_SEC as i64;
+        chrono::Duration::seconds(abi / ticks_per_sec)
+            + chrono::Duration::nanoseconds(abi % ticks_per_sec * NANOS_PER_TICK as i64)
     }
 }
 
-impl<T> From<Vec<T>> for RawVec<T> {
-    fn from(mut from: Vec<T>) -> Self {
-        let raw = Self {
-            ptr: from.as_mut_ptr(),
-            len: from.len(),
-            capacity: from.capacity(),
-        };
-
-        // Ensure that the `Vec` isn't de-allocated, effectively transferring ownership of
-        // its data to the `RawVec`.
-        mem::forget(from);
+/// `DateTime<Utc>` is passed as the number of ticks since the Unix epoch, the same as a
+/// `SystemTime`, and is exposed to C# as a `DateTimeOffset` in UTC.
+#[cfg(feature = "chrono")]
+impl Abi for chrono::DateTime<chrono::Utc> {
+    type Abi = i64;
 
-        raw
+    fn repr() -> Repr {
+        Repr::SystemTime
     }
-}
 
-impl From<String> for RawVec<u8> {
-    fn from(mut from: String) -> Self {
-        let raw = Self {
-            ptr: from.as_mut_ptr(),
-            len: from.len(),
-            capacity: from.capacity(),
-        };
+    fn as_abi(&self) -> Self::Abi {
+        (*self).into_abi()
+    }
 
-        // Ensure that the string isn't de-allocated, effectively transferring ownership of
-        // its data to the `RawString`.
-        mem::forget(from);
+    fn into_abi(self) -> Self::Abi {
+        // NOTE: The timestamp is rounded down to the second, so the sub-second part is
+        // always positive, including for times before the epoch.
+        self.timestamp()
+            .checked_mul(TICKS_PER_SEC as i64)
+            .and_then(|ticks| {
+                ticks.checked_add((self.timestamp_subsec_nanos() as u128 / NANOS_PER_TICK) as i64)
+            })
+            .expect("DateTime is out of range for a `DateTimeOffset`")
+    }
 
-        raw
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        use chrono::TimeZone;
+
+        let ticks_per_sec = TICKS_PER_SEC as i64;
+        let secs = abi.div_euclid(ticks_per_sec);
+        let subsec_nanos = abi.rem_euclid(ticks_per_sec) as u128 * NANOS_PER_TICK;
+        chrono::Utc
+            .timestamp_opt(secs, subsec_nanos as u32)
+            .single()
+            .expect("DateTimeOffset is out of range for a `DateTime`")
     }
 }
 
-/// Raw representation of a `&[T]`.
+/// `NaiveDate` is passed as the number of days since January 1st, 0001, which is the
+/// `DayNumber` of a C# `DateOnly`.
 ///
-/// When converting a `&[T]` into a `RawSlice<T>`, no conversion is performed for
-/// the elements of the slice. Instead, the generated C# code is expected to
-/// determine if conversion is needed or not, and either memcopy the entire array or
-/// individually convert each element. The `[cs_bindgen]` proc macro generates a
-/// conversion function for each type that the C# code uses to perform this
-/// conversion.
-#[derive(Debug, PartialEq, Eq, Hash)]
-#[repr(C)]
-pub struct RawSlice<T> {
-    pub ptr: *const T,
-    pub len: usize,
-}
+/// Dates before January 1st, 0001 can't be represented in C#, and converting one throws
+/// an `ArgumentOutOfRangeException` in C#.
+#[cfg(feature = "chrono")]
+impl Abi for chrono::NaiveDate {
+    type Abi = i32;
 
-impl<T> RawSlice<T> {
-    pub unsafe fn as_slice<'a>(self) -> &'a [T] {
-        slice::from_raw_parts(self.ptr, self.len)
+    fn repr() -> Repr {
+        Repr::Date
     }
-}
 
-impl<'a, T: 'a> RawSlice<T>
-where
-    T: Abi,
-{
-    pub unsafe fn convert_element(self, index: usize) -> T::Abi {
-        let slice = self.as_slice();
-        let element = &slice[index];
-        Abi::as_abi(element)
+    fn as_abi(&self) -> Self::Abi {
+        (*self).into_abi()
     }
-}
 
-impl RawSlice<u8> {
-    pub unsafe fn as_str<'a>(self) -> Result<&'a str, str::Utf8Error> {
-        str::from_utf8(slice::from_raw_parts(self.ptr, self.len))
+    fn into_abi(self) -> Self::Abi {
+        use chrono::Datelike;
+
+        // NOTE: `num_days_from_ce` counts January 1st, 0001 as day 1, whereas `DayNumber`
+        // counts it as day 0.
+        self.num_days_from_ce() - 1
     }
 
-    pub unsafe fn as_str_unchecked<'a>(self) -> &'a str {
-        str::from_utf8_unchecked(slice::from_raw_parts(self.ptr, self.len))
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.checked_add(1)
+            .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+            .expect("Date is out of range for a `NaiveDate`")
     }
 }
 
-impl RawSlice<u16> {
-    pub unsafe fn into_string(self) -> Result<String, std::string::FromUtf16Error> {
-        let chars = slice::from_raw_parts(self.ptr, self.len as usize);
+/// A newtype struct that's passed to C# as the value of its only field.
+///
+/// This is implemented by `#[cs_bindgen]` for structs exported with
+/// `#[cs_bindgen(transparent)]` or `#[repr(transparent)]`, and shouldn't be
+/// implemented manually.
+pub trait Transparent: Abi {
+    /// The type of the struct's only field.
+    type Inner: Abi;
+}
 
-        // TODO: Is a lossy conversion the thing to do here?
-        String::from_utf16(chars)
-    }
+/// A type that can be passed from C# as a `&mut T` argument.
+///
+/// Types that are marshaled by value are passed as a pointer to the caller's raw
+/// value. The raw value is converted into a temporary [`MutArg`] that the argument
+/// borrows, and the modified value is written back when the call completes. Handle
+/// types are borrowed directly from the C# object, the same as for `&T` arguments.
+///
+/// This is implemented by `#[cs_bindgen]` for exported types, and shouldn't be
+/// implemented manually.
+///
+/// [`MutArg`]: struct.MutArg.html
+pub trait AbiMut: Sized {
+    /// The FFI-compatible representation of the argument.
+    type Abi: AbiPrimitive;
 
-    pub unsafe fn into_string_lossy(self) -> String {
-        let chars = slice::from_raw_parts(self.ptr, self.len as usize);
+    /// The storage borrowed by the `&mut T` argument for the duration of the call.
+    type Storage: DerefMut<Target = Self>;
 
-        // TODO: Is a lossy conversion the thing to do here?
-        String::from_utf16_lossy(chars)
-    }
+    fn repr() -> Repr;
+
+    /// Converts the raw argument into the storage for the `&mut T` argument.
+    ///
+    /// # Safety
+    ///
+    /// `abi` must point to a valid value for the duration of the call, and the value
+    /// must not be accessed by anything else until the returned storage is dropped.
+    unsafe fn from_abi_mut(abi: Self::Abi) -> Self::Storage;
 }
 
-impl<T> Clone for RawSlice<T> {
-    fn clone(&self) -> Self {
+/// Temporary storage for a `&mut T` argument of a type that's marshaled by value.
+///
+/// The modified value is written back to the caller's raw value when the storage is
+/// dropped at the end of the call. If the call panics the value is dropped instead,
+/// since the caller never reads the raw value back in that case.
+pub struct MutArg<T: Abi> {
+    raw: *mut T::Abi,
+    value: Option<T>,
+}
+
+impl<T: Abi> MutArg<T> {
+    /// Takes ownership of the raw value pointed to by `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must point to a valid raw value of `T`, and ownership of that value is
+    /// transferred to the returned storage.
+    pub unsafe fn new(raw: *mut T::Abi) -> Self {
         Self {
-            ptr: self.ptr,
-            len: self.len,
+            raw,
+            value: Some(T::from_abi(ptr::read(raw))),
         }
     }
 }
 
-impl<T> Copy for RawSlice<T> {}
+impl<T: Abi> Deref for MutArg<T> {
+    type Target = T;
 
-unsafe impl<T> AbiPrimitive for RawSlice<T> {}
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("Value already written back")
+    }
+}
 
-impl<'a, T> From<&'a [T]> for RawSlice<T>
+impl<T: Abi> DerefMut for MutArg<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("Value already written back")
+    }
+}
+
+impl<T: Abi> Drop for MutArg<T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            if !std::thread::panicking() {
+                unsafe { ptr::write(self.raw, value.into_abi()) };
+            }
+        }
+    }
+}
+
+/// Storage for a `&mut T` argument of a handle type, which borrows the value owned by
+/// the C# object.
+///
+/// The handle is borrowed exclusively while the storage is alive, see the [`borrow`]
+/// module.
+///
+/// [`borrow`]: ../borrow/index.html
+pub struct HandleMut<T>(*mut T, BorrowGuard);
+
+impl<T> HandleMut<T> {
+    /// Borrows the value behind a handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid handle that isn't borrowed anywhere else for the
+    /// lifetime of the returned storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `borrow-checks` feature is enabled and the handle is already
+    /// borrowed.
+    pub unsafe fn new(handle: *const T) -> Self {
+        HandleMut(handle as *mut T, borrow::borrow_mut(handle))
+    }
+}
+
+impl<T> Deref for HandleMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0 }
+    }
+}
+
+impl<T> DerefMut for HandleMut<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.0 }
+    }
+}
+
+macro_rules! abi_mut_values {
+    ($($ty:ty,)*) => {
+        $(
+            impl AbiMut for $ty {
+                type Abi = *mut <Self as Abi>::Abi;
+                type Storage = MutArg<Self>;
+
+                fn repr() -> Repr {
+                    Repr::RefMut(Box::new(<Self as Abi>::repr()))
+                }
+
+                unsafe fn from_abi_mut(abi: Self::Abi) -> Self::Storage {
+                    MutArg::new(abi)
+                }
+            }
+        )*
+    };
+}
+
+abi_mut_values! {
+    i8, i16, i32, i64, isize,
+    u8, u16, u32, u64, usize,
+    i128, u128,
+    f32, f64,
+    bool, char,
+    Duration, SystemTime,
+}
+
+#[cfg(feature = "uuid")]
+abi_mut_values! {
+    uuid::Uuid,
+}
+
+#[cfg(feature = "chrono")]
+abi_mut_values! {
+    chrono::Duration,
+    chrono::DateTime<chrono::Utc>,
+    chrono::NaiveDate,
+}
+
+/// A type that can be borrowed from its raw representation.
+///
+/// This is used for the receiver of the binding functions generated for trait impls
+/// tagged with `#[cs_bindgen]`, since the impl doesn't know how the type is passed
+/// to C#. Handle types are borrowed directly from the C# object, and types that are
+/// marshaled by value are converted into a temporary value that's dropped at the end
+/// of the call.
+///
+/// This is implemented by `#[cs_bindgen]` for exported types, and shouldn't be
+/// implemented manually.
+pub trait AbiRef: Abi {
+    /// The storage borrowed by the receiver for the duration of the call.
+    type Storage: Deref<Target = Self>;
+
+    /// Converts the raw value into the storage for the receiver.
+    ///
+    /// # Safety
+    ///
+    /// `abi` must be a valid raw value of `Self`, with the same ownership as when
+    /// passing the value to `from_abi` for types that are marshaled by value.
+    unsafe fn from_abi_ref(abi: Self::Abi) -> Self::Storage;
+}
+
+/// Storage for a borrowed receiver of a handle type, which borrows the value owned
+/// by the C# object.
+pub struct HandleRef<T>(*const T);
+
+impl<T> HandleRef<T> {
+    /// Borrows the value behind a handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid handle that isn't mutably borrowed anywhere else for
+    /// the lifetime of the returned storage.
+    pub unsafe fn new(handle: *const T) -> Self {
+        HandleRef(handle)
+    }
+}
+
+impl<T> Deref for HandleRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0 }
+    }
+}
+
+/// Storage for a borrowed receiver of a type that's marshaled by value.
+pub struct ValueRef<T>(T);
+
+impl<T: Abi> ValueRef<T> {
+    /// Takes ownership of the raw value.
+    ///
+    /// # Safety
+    ///
+    /// `abi` must be a valid raw value of `T`, as for `Abi::from_abi`.
+    pub unsafe fn new(abi: T::Abi) -> Self {
+        ValueRef(T::from_abi(abi))
+    }
+}
+
+impl<T> Deref for ValueRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Abi for Vec<T>
 where
-    T: AbiPrimitive,
+    T: Abi,
 {
+    type Abi = RawVec<T>;
+
+    fn repr() -> Repr {
+        Repr::Vec(Box::new(T::repr()))
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        self.as_slice().into()
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        self.into()
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.into_vec()
+    }
+}
+
+impl Abi for String {
+    type Abi = RawVec<u8>;
+
+    fn repr() -> Repr {
+        Repr::String
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        self.as_bytes().into()
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        self.into_bytes().into()
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.into_string()
+    }
+}
+
+impl<'a> Abi for &'a str {
+    type Abi = RawSlice<u8>;
+
+    fn repr() -> Repr {
+        Repr::Str
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        (*self).into()
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        self.into()
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.as_str_unchecked()
+    }
+}
+
+/// The maximum number of UTF-16 code units in a string argument that will be
+/// decoded without allocating.
+///
+/// Each UTF-16 code unit decodes to at most 3 bytes of UTF-8, so this is sized such
+/// that the inline buffer of [`StrArg`] is always large enough.
+///
+/// [`StrArg`]: enum.StrArg.html
+const STR_ARG_INLINE_UTF16_LEN: usize = 64;
+
+/// Temporary storage for a `&str` argument received from C#.
+///
+/// C# strings are UTF-16, so they can't be borrowed directly as a `&str`. Instead,
+/// the generated C# pins the string and passes a view of its UTF-16 data, which is
+/// decoded into a `StrArg` that lives for the duration of the call. Short strings are
+/// decoded into an inline buffer, so that the common case of passing a short string
+/// doesn't need to allocate.
+pub enum StrArg {
+    Inline {
+        buffer: [u8; STR_ARG_INLINE_UTF16_LEN * 3],
+        len: usize,
+    },
+    Heap(String),
+}
+
+impl StrArg {
+    /// Decodes the UTF-16 string data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string data isn't valid UTF-16.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must point to a valid UTF-16 string that lives at least as long as this
+    /// function call.
+    pub unsafe fn from_utf16(raw: RawSlice<u16>) -> Self {
+        if raw.len > STR_ARG_INLINE_UTF16_LEN {
+            return StrArg::Heap(
+                raw.into_string()
+                    .expect("Failed to convert C# string to Rust string"),
+            );
+        }
+
+        let mut buffer = [0; STR_ARG_INLINE_UTF16_LEN * 3];
+        let mut len = 0;
+        for c in char::decode_utf16(raw.as_slice().iter().copied()) {
+            let c = c.expect("Failed to convert C# string to Rust string");
+            len += c.encode_utf8(&mut buffer[len..]).len();
+        }
+
+        StrArg::Inline { buffer, len }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            // NOTE: The inline buffer only ever contains the bytes written by
+            // `char::encode_utf8`, so it's guaranteed to be valid UTF-8.
+            StrArg::Inline { buffer, len } => unsafe { str::from_utf8_unchecked(&buffer[..*len]) },
+            StrArg::Heap(string) => string,
+        }
+    }
+}
+
+/// Raw representation of a [`Vec`] compatible with FFI.
+///
+/// When converting a `Vec<T>` into a `RawVec<T>`, no conversion is performed for
+/// the elements of the vec. Instead, the generated C# code is expected to determine
+/// if conversion is needed or not, and either memcopy the entire array or to
+/// individually convert each element. The `[cs_bindgen]` proc macro generates a
+/// conversion function for each type that the C# code uses to perform this
+/// conversion.
+///
+/// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+#[repr(C)]
+pub struct RawVec<T> {
+    pub ptr: *const T,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl<T> RawVec<T> {
+    pub unsafe fn into_vec(self) -> Vec<T> {
+        // NOTE: We need to cast the raw pointer to a `*mut T` in order to reconstruct the
+        // `Vec`. If the calling code never did anything invalid with the pointer (such as
+        // mutating its contents) this should be safe.
+        Vec::from_raw_parts(self.ptr as *mut _, self.len, self.capacity)
+    }
+}
+
+impl<T> Clone for RawVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr,
+            len: self.len,
+            capacity: self.capacity,
+        }
+    }
+}
+impl<T> Copy for RawVec<T> {}
+
+impl RawVec<u8> {
+    /// Reconstructs the original string from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// `into_string` must only be called once per string instance. Calling it more than
+    /// once on the same string will result in undefined behavior.
+    pub unsafe fn into_string(self) -> String {
+        // NOTE: We need to cast the raw pointer to a `*mut T` in order to reconstruct the
+        // `String`. If the calling code never did anything invalid with the pointer (such
+        // as mutating its contents) this should be safe.
+        String::from_raw_parts(self.ptr as *mut _, self.len, self.capacity)
+    }
+}
+
+unsafe impl<T> AbiPrimitive for RawVec<T> {}
+
+impl<T> From<&'_ [T]> for RawVec<T> {
     fn from(from: &[T]) -> Self {
         Self {
             ptr: from.as_ptr(),
             len: from.len(),
+            capacity: 0,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for RawVec<T> {
+    fn from(mut from: Vec<T>) -> Self {
+        let raw = Self {
+            ptr: from.as_mut_ptr(),
+            len: from.len(),
+            capacity: from.capacity(),
+        };
+
+        // Ensure that the `Vec` isn't de-allocated, effectively transferring ownership of
+        // its data to the `RawVec`.
+        mem::forget(from);
+
+        raw
+    }
+}
+
+impl From<String> for RawVec<u8> {
+    fn from(from: String) -> Self {
+        // NOTE: Strings are passed as their pointer, length, and capacity, same as any
+        // other vec, so any interior NULs are preserved.
+        from.into_bytes().into()
+    }
+}
+
+/// Raw representation of a `&[T]`.
+///
+/// When converting a `&[T]` into a `RawSlice<T>`, no conversion is performed for
+/// the elements of the slice. Instead, the generated C# code is expected to
+/// determine if conversion is needed or not, and either memcopy the entire array or
+/// individually convert each element. The `[cs_bindgen]` proc macro generates a
+/// conversion function for each type that the C# code uses to perform this
+/// conversion.
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct RawSlice<T> {
+    pub ptr: *const T,
+    pub len: usize,
+}
+
+impl<T> RawSlice<T> {
+    pub unsafe fn as_slice<'a>(self) -> &'a [T] {
+        // NOTE: C# may pass a null pointer for an empty slice (e.g. when pinning an empty
+        // span), which isn't valid for `slice::from_raw_parts`.
+        if self.len == 0 {
+            return &[];
+        }
+
+        slice::from_raw_parts(self.ptr, self.len)
+    }
+}
+
+impl<'a, T: 'a> RawSlice<T>
+where
+    T: Abi,
+{
+    pub unsafe fn convert_element(self, index: usize) -> T::Abi {
+        let slice = self.as_slice();
+        let element = &slice[index];
+        Abi::as_abi(element)
+    }
+}
+
+impl RawSlice<u8> {
+    pub unsafe fn as_str<'a>(self) -> Result<&'a str, str::Utf8Error> {
+        str::from_utf8(slice::from_raw_parts(self.ptr, self.len))
+    }
+
+    pub unsafe fn as_str_unchecked<'a>(self) -> &'a str {
+        str::from_utf8_unchecked(slice::from_raw_parts(self.ptr, self.len))
+    }
+}
+
+impl RawSlice<u16> {
+    pub unsafe fn into_string(self) -> Result<String, std::string::FromUtf16Error> {
+        let chars = slice::from_raw_parts(self.ptr, self.len as usize);
+
+        // TODO: Is a lossy conversion the thing to do here?
+        String::from_utf16(chars)
+    }
+
+    pub unsafe fn into_string_lossy(self) -> String {
+        let chars = slice::from_raw_parts(self.ptr, self.len as usize);
+
+        // TODO: Is a lossy conversion the thing to do here?
+        String::from_utf16_lossy(chars)
+    }
+}
+
+impl<T> Clone for RawSlice<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr,
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Copy for RawSlice<T> {}
+
+unsafe impl<T> AbiPrimitive for RawSlice<T> {}
+
+impl<'a, T> From<&'a [T]> for RawSlice<T>
+where
+    T: AbiPrimitive,
+{
+    fn from(from: &[T]) -> Self {
+        Self {
+            ptr: from.as_ptr(),
+            len: from.len(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for RawSlice<u8> {
+    fn from(from: &str) -> Self {
+        Self {
+            ptr: from.as_ptr(),
+            len: from.len(),
+        }
+    }
+}
+
+/// Converts a slice of `T::Abi` to a vec of `T`, converting each element.
+pub unsafe fn convert_list<T: Abi>(raw: RawSlice<T::Abi>) -> RawVec<T> {
+    convert_slice(raw).into()
+}
+
+/// Drops a vec that was passed to C#, along with its elements.
+///
+/// Lists are converted to C# by converting each element in place with `Abi::as_abi`,
+/// so a list nested inside another list (e.g. an element of a `Vec<Vec<T>>`) is
+/// borrowed from its parent rather than owned, and is passed with a capacity of `0`.
+/// Borrowed vecs are left alone, since their elements are dropped with the vec that
+/// owns them.
+///
+/// # Safety
+///
+/// `raw` must have been created from a `Vec<T>` or a `&[T]`, and must not be used
+/// again after being dropped.
+pub unsafe fn drop_vec<T>(raw: RawVec<T>) {
+    // NOTE: A vec with a capacity of `0` doesn't own an allocation, so it can only hold
+    // elements if it was created from a slice.
+    if raw.capacity == 0 {
+        return;
+    }
+
+    let _ = raw.into_vec();
+}
+
+/// Moves the element at `index` out of a vec that's being converted to a C# list,
+/// converting it into its raw representation.
+///
+/// This is used for lists of handle types. Elements of other types are converted in
+/// place with `Abi::as_abi`, but each C# handle object owns its own allocation, so
+/// the element has to be moved out of the vec instead. Once every element has been
+/// taken, the vec is freed with [`drop_vec_storage`].
+///
+/// # Safety
+///
+/// `slice` must point to the elements of a vec that was passed to C# by value, and
+/// each element must be taken exactly once.
+///
+/// [`drop_vec_storage`]: fn.drop_vec_storage.html
+pub unsafe fn take_element<T: Abi>(slice: RawSlice<T>, index: usize) -> T::Abi {
+    assert!(
+        index < slice.len,
+        "Index {} out of bounds for list of length {}",
+        index,
+        slice.len,
+    );
+
+    ptr::read(slice.ptr.add(index)).into_abi()
+}
+
+/// Frees a vec that was passed to C# without dropping its elements, since they've
+/// already been moved out with [`take_element`].
+///
+/// # Safety
+///
+/// `raw` must have been created from a `Vec<T>`, every element must have been taken,
+/// and `raw` must not be used again after being dropped.
+///
+/// [`take_element`]: fn.take_element.html
+pub unsafe fn drop_vec_storage<T>(raw: RawVec<T>) {
+    // NOTE: Lists of handles are never borrowed from another list, so a vec with a
+    // capacity of `0` doesn't own an allocation.
+    if raw.capacity == 0 {
+        return;
+    }
+
+    let mut vec = raw.into_vec();
+    vec.set_len(0);
+}
+
+/// Converts a slice of `T::Abi` to a `Vec<T>`, converting each element.
+///
+/// This is used for slice arguments (i.e. `&[T]`) where `T` isn't a primitive, since
+/// the converted elements need to be stored somewhere in order to be borrowed.
+/// Ownership of each raw element is transferred to the returned `Vec`.
+pub unsafe fn convert_slice<T: Abi>(raw: RawSlice<T::Abi>) -> Vec<T> {
+    raw.as_slice().iter().map(|&raw| T::from_abi(raw)).collect()
+}
+
+/// Generates the `Abi` implementation for arrays of different lengths.
+///
+/// For an array of type `T`, it's ABI-compatible representation is an array of the
+/// same length of type `T::Abi`. Conversion is performed directly for each element.
+/// This macro helps cut down on the boilerplate needed for the implementations.
+macro_rules! array_abi {
+    ( $len:expr; $($elem:ident),* ) => {
+        unsafe impl<T: AbiPrimitive> AbiPrimitive for [T; $len] {}
+
+        impl<T: Abi> Abi for [T; $len] {
+            type Abi = [T::Abi; $len];
+
+            fn repr() -> Repr {
+                Repr::Array {
+                    element: Box::new(T::repr()),
+                    len: $len,
+                }
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                let [
+                    $( $elem, )*
+                ] = self;
+
+                [
+                    $(
+                        $crate::abi::Abi::as_abi($elem),
+                    )*
+                ]
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                let [
+                    $( $elem, )*
+                ] = self;
+
+                [
+                    $(
+                        $crate::abi::Abi::into_abi($elem),
+                    )*
+                ]
+            }
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                let [
+                    $( $elem, )*
+                ] = abi;
+
+                [
+                    $(
+                        $crate::abi::Abi::from_abi($elem),
+                    )*
+                ]
+            }
+        }
+    };
+}
+
+array_abi!(1; a);
+array_abi!(2; a, b);
+array_abi!(3; a, b, c);
+array_abi!(4; a, b, c, d);
+array_abi!(5; a, b, c, d, e);
+array_abi!(6; a, b, c, d, e, f);
+array_abi!(7; a, b, c, d, e, f, g);
+array_abi!(8; a, b, c, d, e, f, g, h);
+array_abi!(9; a, b, c, d, e, f, g, h, i);
+array_abi!(10; a, b, c, d, e, f, g, h, i, j);
+array_abi!(11; a, b, c, d, e, f, g, h, i, j, k);
+array_abi!(12; a, b, c, d, e, f, g, h, i, j, k, l);
+array_abi!(13; a, b, c, d, e, f, g, h, i, j, k, l, m);
+array_abi!(14; a, b, c, d, e, f, g, h, i, j, k, l, m, n);
+array_abi!(15; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o);
+array_abi!(16; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p);
+array_abi!(17; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q);
+array_abi!(18; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r);
+array_abi!(19; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s);
+array_abi!(20; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t);
+array_abi!(21; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u);
+array_abi!(22; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v);
+array_abi!(23; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w);
+array_abi!(24; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x);
+array_abi!(25; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y);
+array_abi!(26; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z);
+array_abi!(27; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa);
+array_abi!(28; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb);
+array_abi!(29; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc);
+array_abi!(30; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd);
+array_abi!(31; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd, ee);
+array_abi!(32; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd, ee, ff);
+
+/// Generates the raw representation and `Abi` implementation for tuples of
+/// different lengths.
+///
+/// Rust tuples don't have a defined layout, so each tuple length gets a dedicated
+/// `#[repr(C)]` struct with one field per element. Conversion is performed directly
+/// for each element.
+macro_rules! tuple_abi {
+    ( $raw:ident; $( $elem:ident => $field:ident ),* ) => {
+        /// Raw representation of a tuple, compatible with FFI.
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy)]
+        pub struct $raw<$( $elem ),*> {
+            $( pub $field: $elem, )*
+        }
+
+        unsafe impl<$( $elem: AbiPrimitive ),*> AbiPrimitive for $raw<$( $elem ),*> {}
+
+        impl<$( $elem: Abi ),*> Abi for ($( $elem, )*) {
+            type Abi = $raw<$( <$elem as Abi>::Abi ),*>;
+
+            fn repr() -> Repr {
+                Repr::Tuple(vec![$( $elem::repr() ),*])
+            }
+
+            fn as_abi(&self) -> Self::Abi {
+                let ($( $field, )*) = self;
+                $raw {
+                    $( $field: $crate::abi::Abi::as_abi($field), )*
+                }
+            }
+
+            fn into_abi(self) -> Self::Abi {
+                let ($( $field, )*) = self;
+                $raw {
+                    $( $field: $crate::abi::Abi::into_abi($field), )*
+                }
+            }
+
+            unsafe fn from_abi(abi: Self::Abi) -> Self {
+                ($( $crate::abi::Abi::from_abi(abi.$field), )*)
+            }
+        }
+    };
+}
+
+tuple_abi!(RawTuple2; A => element0, B => element1);
+tuple_abi!(RawTuple3; A => element0, B => element1, C => element2);
+tuple_abi!(RawTuple4; A => element0, B => element1, C => element2, D => element3);
+tuple_abi!(RawTuple5; A => element0, B => element1, C => element2, D => element3, E => element4);
+tuple_abi!(RawTuple6; A => element0, B => element1, C => element2, D => element3, E => element4, F => element5);
+tuple_abi!(RawTuple7; A => element0, B => element1, C => element2, D => element3, E => element4, F => element5, G => element6);
+
+/// Deconstructed representation of an enum, compatible with FFI.
+///
+/// The raw representation of an enum is an explicit discriminant value paired with
+/// a union of all the fields. When converting back from the raw representation, use
+/// the value of the discriminant to determine which union field is valid.
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct RawEnum<D, V> {
+    pub discriminant: D,
+    pub value: MaybeUninit<V>,
+}
+
+impl<D: Clone, V: Copy> Clone for RawEnum<D, V> {
+    fn clone(&self) -> Self {
+        Self {
+            discriminant: self.discriminant.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<D, V> RawEnum<D, V> {
+    pub const fn new(discriminant: D, value: V) -> Self {
+        Self {
+            discriminant,
+            value: MaybeUninit::new(value),
+        }
+    }
+
+    pub const fn unit(discriminant: D) -> Self {
+        Self {
+            discriminant,
+            value: MaybeUninit::uninit(),
+        }
+    }
+}
+
+unsafe impl<D: AbiPrimitive, V: AbiPrimitive> AbiPrimitive for RawEnum<D, V> {}
+
+/// Raw representation of an [`Option`], compatible with FFI.
+///
+/// The raw representation is a presence flag paired with the (possibly
+/// uninitialized) raw value. `value` is only valid to read if `is_some` is non-zero.
+///
+/// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct RawOption<T> {
+    pub is_some: u8,
+    pub value: MaybeUninit<T>,
+}
+
+impl<T: Copy> Clone for RawOption<T> {
+    fn clone(&self) -> Self {
+        Self {
+            is_some: self.is_some,
+            value: self.value,
+        }
+    }
+}
+
+impl<T> RawOption<T> {
+    pub const fn some(value: T) -> Self {
+        Self {
+            is_some: 1,
+            value: MaybeUninit::new(value),
+        }
+    }
+
+    pub const fn none() -> Self {
+        Self {
+            is_some: 0,
+            value: MaybeUninit::uninit(),
+        }
+    }
+}
+
+unsafe impl<T: AbiPrimitive> AbiPrimitive for RawOption<T> {}
+
+impl<T: Abi> Abi for Option<T> {
+    type Abi = RawOption<T::Abi>;
+
+    fn repr() -> Repr {
+        Repr::Option(Box::new(T::repr()))
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        match self {
+            Some(value) => RawOption::some(value.as_abi()),
+            None => RawOption::none(),
+        }
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        match self {
+            Some(value) => RawOption::some(value.into_abi()),
+            None => RawOption::none(),
+        }
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        if abi.is_some != 0 {
+            Some(T::from_abi(abi.value.assume_init()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Raw representation of a [`Result`], compatible with FFI.
+///
+/// The raw representation is a flag indicating whether or not the operation was
+/// successful, paired with a union of the raw success and error values. Use the
+/// value of `is_ok` to determine which union field is valid.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[repr(C)]
+#[derive(Copy)]
+pub struct RawResult<T: Copy, E: Copy> {
+    pub is_ok: u8,
+    pub value: RawResultValue<T, E>,
+}
+
+impl<T: Copy, E: Copy> Clone for RawResult<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, E: Copy> RawResult<T, E> {
+    pub fn ok(ok: T) -> Self {
+        Self {
+            is_ok: 1,
+            value: RawResultValue { ok },
+        }
+    }
+
+    pub fn err(err: E) -> Self {
+        Self {
+            is_ok: 0,
+            value: RawResultValue { err },
+        }
+    }
+}
+
+unsafe impl<T: AbiPrimitive, E: AbiPrimitive> AbiPrimitive for RawResult<T, E> {}
+
+/// The union of the success and error values in a [`RawResult`].
+///
+/// [`RawResult`]: struct.RawResult.html
+#[repr(C)]
+#[derive(Copy)]
+pub union RawResultValue<T: Copy, E: Copy> {
+    pub ok: T,
+    pub err: E,
+}
+
+impl<T: Copy, E: Copy> Clone for RawResultValue<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Results are marshaled by returning the raw `Ok` or `Err` value along with a flag
+/// indicating which one is present. The generated C# rethrows the `Err` case as an
+/// exception.
+///
+/// Only exported error types (and built-in types like `String`) can be marshaled.
+/// Functions returning other error types can be exported with
+/// `#[cs_bindgen(error = "display")]`, which passes the error to C# as its `Display`
+/// string instead.
+impl<T: Abi, E: Abi> Abi for Result<T, E> {
+    type Abi = RawResult<T::Abi, E::Abi>;
+
+    fn repr() -> Repr {
+        Repr::Result {
+            ok: Box::new(T::repr()),
+            err: Box::new(E::repr()),
+        }
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        match self {
+            Ok(ok) => RawResult::ok(ok.as_abi()),
+            Err(err) => RawResult::err(err.as_abi()),
+        }
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        match self {
+            Ok(ok) => RawResult::ok(ok.into_abi()),
+            Err(err) => RawResult::err(err.into_abi()),
         }
     }
-}
 
-impl<'a> From<&'a str> for RawSlice<u8> {
-    fn from(from: &str) -> Self {
-        Self {
-            ptr: from.as_ptr(),
-            len: from.len(),
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        if abi.is_ok != 0 {
+            Ok(T::from_abi(abi.value.ok))
+        } else {
+            Err(E::from_abi(abi.value.err))
         }
     }
 }
 
-/// Converts a slice of `T::Abi` to a vec of `T`, converting each element.
-pub unsafe fn convert_list<T: Abi>(raw: RawSlice<T::Abi>) -> RawVec<T> {
-    raw.as_slice()
-        .iter()
-        .map(|&raw| T::from_abi(raw))
-        .collect::<Vec<_>>()
-        .into()
+/// The alignment used for buffers allocated with [`alloc_buffer`].
+///
+/// C# has no way to query the alignment of a type, so buffers that are shared
+/// between Rust and C# always use a fixed alignment that's large enough for any raw
+/// representation.
+///
+/// [`alloc_buffer`]: fn.alloc_buffer.html
+pub const BUFFER_ALIGN: usize = 8;
+
+/// Allocates an uninitialized buffer of `size` bytes with an alignment of
+/// [`BUFFER_ALIGN`].
+///
+/// The buffer must be freed with [`free_buffer`], passing the same size.
+///
+/// [`BUFFER_ALIGN`]: constant.BUFFER_ALIGN.html
+/// [`free_buffer`]: fn.free_buffer.html
+pub unsafe fn alloc_buffer(size: usize) -> *mut u8 {
+    alloc::alloc(size, BUFFER_ALIGN)
 }
 
-/// Generates the `Abi` implementation for arrays of different lengths.
+/// Frees a buffer that was allocated with [`alloc_buffer`].
 ///
-/// For an array of type `T`, it's ABI-compatible representation is an array of the
-/// same length of type `T::Abi`. Conversion is performed directly for each element.
-/// This macro helps cut down on the boilerplate needed for the implementations.
-macro_rules! array_abi {
-    ( $len:expr; $($elem:ident),* ) => {
-        unsafe impl<T: AbiPrimitive> AbiPrimitive for [T; $len] {}
+/// # Safety
+///
+/// `ptr` must have been returned by `alloc_buffer`, and `size` must be the same size
+/// that was passed to `alloc_buffer`.
+///
+/// [`alloc_buffer`]: fn.alloc_buffer.html
+pub unsafe fn free_buffer(ptr: *mut u8, size: usize) {
+    alloc::free(ptr, size, BUFFER_ALIGN)
+}
 
-        impl<T: Abi> Abi for [T; $len] {
-            type Abi = [T::Abi; $len];
+/// Raw representation of a map type (e.g. [`HashMap`] or [`BTreeMap`]), compatible
+/// with FFI.
+///
+/// The raw representation is a pair of parallel buffers containing the raw keys and
+/// values, respectively. Unlike `RawVec<T>`, the elements of a map are converted
+/// eagerly, and the buffers are allocated with [`alloc_buffer`] so that they can be
+/// allocated and freed from C# without knowing the alignment of the raw types.
+///
+/// Ownership of the buffers is transferred along with the map: Converting a
+/// `RawMap` back into a map with `Abi::from_abi` frees the buffers, and the C#
+/// code is responsible for freeing the buffers of any map returned from Rust.
+///
+/// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+/// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+/// [`alloc_buffer`]: fn.alloc_buffer.html
+#[repr(C)]
+#[derive(Debug)]
+pub struct RawMap<K, V> {
+    pub keys: *mut K,
+    pub values: *mut V,
+    pub len: usize,
+}
 
-            fn repr() -> Repr {
-                Repr::Array {
-                    element: Box::new(T::repr()),
-                    len: $len,
-                }
+impl<K, V> Clone for RawMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for RawMap<K, V> {}
+
+impl<K, V> RawMap<K, V> {
+    /// Moves the raw entries into newly-allocated buffers.
+    pub fn from_entries<I>(entries: I) -> Self
+    where
+        I: ExactSizeIterator<Item = (K, V)>,
+    {
+        assert!(
+            mem::align_of::<K>() <= BUFFER_ALIGN && mem::align_of::<V>() <= BUFFER_ALIGN,
+            "Raw map elements must not have an alignment larger than {}",
+            BUFFER_ALIGN,
+        );
+
+        let len = entries.len();
+        unsafe {
+            let keys = alloc_buffer(len * mem::size_of::<K>()) as *mut K;
+            let values = alloc_buffer(len * mem::size_of::<V>()) as *mut V;
+
+            // NOTE: We use `take` to ensure that we don't write past the end of the
+            // buffers, even if the iterator reports the wrong length.
+            for (index, (key, value)) in entries.take(len).enumerate() {
+                ptr::write(keys.add(index), key);
+                ptr::write(values.add(index), value);
             }
 
-            fn as_abi(&self) -> Self::Abi {
-                let [
-                    $( $elem, )*
-                ] = self;
+            Self { keys, values, len }
+        }
+    }
 
-                [
-                    $(
-                        $crate::abi::Abi::as_abi($elem),
-                    )*
-                ]
-            }
+    /// Moves the entries out of the raw buffers, freeing the buffers.
+    ///
+    /// # Safety
+    ///
+    /// The buffers must have been allocated with [`alloc_buffer`] and must contain
+    /// `len` initialized elements. This must only be called once for a given map.
+    ///
+    /// [`alloc_buffer`]: fn.alloc_buffer.html
+    pub unsafe fn into_entries(self) -> Vec<(K, V)> {
+        let entries = (0..self.len)
+            .map(|index| {
+                (
+                    ptr::read(self.keys.add(index)),
+                    ptr::read(self.values.add(index)),
+                )
+            })
+            .collect();
+
+        free_buffer(self.keys as *mut u8, self.len * mem::size_of::<K>());
+        free_buffer(self.values as *mut u8, self.len * mem::size_of::<V>());
+
+        entries
+    }
+}
 
-            fn into_abi(self) -> Self::Abi {
-                let [
-                    $( $elem, )*
-                ] = self;
+unsafe impl<K: AbiPrimitive, V: AbiPrimitive> AbiPrimitive for RawMap<K, V> {}
 
-                [
-                    $(
-                        $crate::abi::Abi::into_abi($elem),
-                    )*
-                ]
-            }
+/// Generates the `Abi` implementation for the standard map types.
+///
+/// Note that `as_abi` still allocates new buffers for the raw entries, since maps
+/// don't store their entries in a contiguous buffer that could be borrowed.
+macro_rules! map_abi {
+    ( $( $map:ident < K, V $( , $hasher:ident )? > where K: $( $bound:path ),*; )* ) => {
+        $(
+            impl<K, V $( , $hasher )?> Abi for $map<K, V $( , $hasher )?>
+            where
+                K: Abi $( + $bound )*,
+                V: Abi,
+                $( $hasher: BuildHasher + Default, )?
+            {
+                type Abi = RawMap<K::Abi, V::Abi>;
 
-            unsafe fn from_abi(abi: Self::Abi) -> Self {
-                let [
-                    $( $elem, )*
-                ] = abi;
+                fn repr() -> Repr {
+                    Repr::Map {
+                        key: Box::new(K::repr()),
+                        value: Box::new(V::repr()),
+                    }
+                }
 
-                [
-                    $(
-                        $crate::abi::Abi::from_abi($elem),
-                    )*
-                ]
+                fn as_abi(&self) -> Self::Abi {
+                    RawMap::from_entries(
+                        self.iter()
+                            .map(|(key, value)| (key.as_abi(), value.as_abi())),
+                    )
+                }
+
+                fn into_abi(self) -> Self::Abi {
+                    RawMap::from_entries(
+                        self.into_iter()
+                            .map(|(key, value)| (key.into_abi(), value.into_abi())),
+                    )
+                }
+
+                unsafe fn from_abi(abi: Self::Abi) -> Self {
+                    abi.into_entries()
+                        .into_iter()
+                        .map(|(key, value)| (K::from_abi(key), V::from_abi(value)))
+                        .collect()
+                }
             }
+        )*
+    };
+}
+
+map_abi! {
+    HashMap<K, V, S> where K: Eq, Hash;
+    BTreeMap<K, V> where K: Ord;
+}
+
+/// Raw representation of a callback passed as an argument from C#.
+///
+/// `func` is a pointer to a C# function that takes `context` as its first argument,
+/// followed by the raw arguments to the callback and a trailing `*mut u8` flag that
+/// is set if the callback threw an exception. `context` identifies the C# delegate
+/// being invoked, and is only valid for the duration of the exported function call
+/// that received the callback.
+///
+/// The `#[cs_bindgen]` macro wraps a `RawCallback` in a closure that borrows it, so
+/// that the closure can't outlive the call.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct RawCallback {
+    pub func: *const c_void,
+    pub context: *mut c_void,
+}
+
+/// Generates the `call*` methods on `RawCallback` for callbacks with different
+/// numbers of arguments.
+///
+/// Each argument is converted with `Abi::into_abi`, and the return value is
+/// converted back with `Abi::from_abi`. If the C# callback throws an exception, the
+/// method panics so that the panic is reported back to C#. The generated C# then
+/// rethrows the original exception.
+macro_rules! callback_calls {
+    ( $( $call:ident( $( $arg:ident: $ty:ident ),* ); )* ) => {
+        impl RawCallback {
+            $(
+                /// Invokes the C# callback with the given arguments.
+                ///
+                /// # Safety
+                ///
+                /// The signature of the callback must match the argument and return types.
+                pub unsafe fn $call<$( $ty: Abi, )* R: Abi>(&self $( , $arg: $ty )*) -> R {
+                    let func: unsafe extern "C" fn(
+                        *mut c_void,
+                        $( <$ty as Abi>::Abi, )*
+                        *mut u8,
+                    ) -> R::Abi = mem::transmute(self.func);
+
+                    let mut threw = 0;
+                    let result = func(self.context, $( $arg.into_abi(), )* &mut threw);
+                    if threw != 0 {
+                        panic!("C# callback threw an exception");
+                    }
+
+                    R::from_abi(result)
+                }
+            )*
         }
     };
 }
 
-array_abi!(1; a);
-array_abi!(2; a, b);
-array_abi!(3; a, b, c);
-array_abi!(4; a, b, c, d);
-array_abi!(5; a, b, c, d, e);
-array_abi!(6; a, b, c, d, e, f);
-array_abi!(7; a, b, c, d, e, f, g);
-array_abi!(8; a, b, c, d, e, f, g, h);
-array_abi!(9; a, b, c, d, e, f, g, h, i);
-array_abi!(10; a, b, c, d, e, f, g, h, i, j);
-array_abi!(11; a, b, c, d, e, f, g, h, i, j, k);
-array_abi!(12; a, b, c, d, e, f, g, h, i, j, k, l);
-array_abi!(13; a, b, c, d, e, f, g, h, i, j, k, l, m);
-array_abi!(14; a, b, c, d, e, f, g, h, i, j, k, l, m, n);
-array_abi!(15; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o);
-array_abi!(16; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p);
-array_abi!(17; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q);
-array_abi!(18; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r);
-array_abi!(19; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s);
-array_abi!(20; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t);
-array_abi!(21; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u);
-array_abi!(22; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v);
-array_abi!(23; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w);
-array_abi!(24; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x);
-array_abi!(25; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y);
-array_abi!(26; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z);
-array_abi!(27; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa);
-array_abi!(28; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb);
-array_abi!(29; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc);
-array_abi!(30; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd);
-array_abi!(31; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd, ee);
-array_abi!(32; a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z, aa, bb, cc, dd, ee, ff);
+callback_calls! {
+    call0();
+    call1(arg0: A0);
+    call2(arg0: A0, arg1: A1);
+    call3(arg0: A0, arg1: A1, arg2: A2);
+    call4(arg0: A0, arg1: A1, arg2: A2, arg3: A3);
+    call5(arg0: A0, arg1: A1, arg2: A2, arg3: A3, arg4: A4);
+    call6(arg0: A0, arg1: A1, arg2: A2, arg3: A3, arg4: A4, arg5: A5);
+    call7(arg0: A0, arg1: A1, arg2: A2, arg3: A3, arg4: A4, arg5: A5, arg6: A6);
+    call8(arg0: A0, arg1: A1, arg2: A2, arg3: A3, arg4: A4, arg5: A5, arg6: A6, arg7: A7);
+}
 
-/// Deconstructed representation of an enum, compatible with FFI.
+/// Raw representation of the completion callback for an async function called from
+/// C#.
 ///
-/// The raw representation of an enum is an explicit discriminant value paired with
-/// a union of all the fields. When converting back from the raw representation, use
-/// the value of the discriminant to determine which union field is valid.
+/// `func` is a pointer to a C# function that takes `context` as its first argument,
+/// followed by the raw result of the future and a `RawString` containing the panic
+/// message if the future panicked. If the future panicked, the result is
+/// zero-initialized and should be ignored. `context` identifies the C# task being
+/// completed.
+///
+/// The completion callback must be invoked exactly once, which is enforced by
+/// [`complete`] taking `self` by value. It may be invoked from any thread.
+///
+/// [`complete`]: #method.complete
+#[derive(Debug)]
 #[repr(C)]
-#[derive(Debug, Copy)]
-pub struct RawEnum<D, V> {
-    pub discriminant: D,
-    pub value: MaybeUninit<V>,
+pub struct RawCompletion {
+    pub func: *const c_void,
+    pub context: *mut c_void,
 }
 
-impl<D: Clone, V: Copy> Clone for RawEnum<D, V> {
-    fn clone(&self) -> Self {
-        Self {
-            discriminant: self.discriminant.clone(),
-            value: self.value.clone(),
+// NOTE: The C# completion callback is thread-safe, so the completion can be sent to
+// the executor thread.
+unsafe impl Send for RawCompletion {}
+
+impl RawCompletion {
+    /// Completes the C# task with the result of the future, or with the panic message
+    /// if the future panicked.
+    ///
+    /// # Safety
+    ///
+    /// `T` must match the output type expected by the C# completion callback.
+    pub unsafe fn complete<T: Abi>(self, result: Result<T, String>) {
+        let func: unsafe extern "C" fn(*mut c_void, T::Abi, RawString) = mem::transmute(self.func);
+        match result {
+            Ok(value) => func(self.context, value.into_abi(), mem::zeroed()),
+            Err(message) => func(self.context, mem::zeroed(), message.into()),
         }
     }
 }
 
-impl<D, V> RawEnum<D, V> {
-    pub const fn new(discriminant: D, value: V) -> Self {
+/// Raw representation of an iterator returned to C#.
+///
+/// The iterator is boxed, and `next` and `drop` point to functions monomorphized for
+/// the concrete iterator type. This allows C# to pull items from any iterator through
+/// the same exported functions, without needing to know the concrete type.
+///
+/// `next` and `drop` are stored as raw pointers, rather than function pointers, so
+/// that a zeroed `RawIter` is valid. They're only called from Rust, so they use the
+/// Rust ABI, which allows panics in the iterator to be caught by the caller.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct RawIter {
+    iter: *mut c_void,
+    next: *const c_void,
+    drop: *const c_void,
+}
+
+impl RawIter {
+    /// Boxes `iter` and creates its raw representation.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: Iterator + 'static,
+        I::Item: Abi,
+    {
+        unsafe fn next<I>(iter: *mut c_void, item: *mut c_void) -> bool
+        where
+            I: Iterator,
+            I::Item: Abi,
+        {
+            match (*(iter as *mut I)).next() {
+                Some(value) => {
+                    ptr::write(item as *mut <I::Item as Abi>::Abi, value.into_abi());
+                    true
+                }
+
+                None => false,
+            }
+        }
+
+        unsafe fn drop<I>(iter: *mut c_void) {
+            let _ = Box::from_raw(iter as *mut I);
+        }
+
         Self {
-            discriminant,
-            value: MaybeUninit::new(value),
+            iter: Box::into_raw(Box::new(iter)) as *mut c_void,
+            next: next::<I> as *const c_void,
+            drop: drop::<I> as *const c_void,
         }
     }
 
-    pub const fn unit(discriminant: D) -> Self {
+    /// Advances the iterator, writing the raw representation of the next item to
+    /// `item`. Returns `false` if the iterator is exhausted, in which case `item` isn't
+    /// modified.
+    ///
+    /// # Safety
+    ///
+    /// `item` must point to memory that's valid to write the raw item to, and the
+    /// iterator must not have been dropped.
+    pub unsafe fn next(&self, item: *mut c_void) -> bool {
+        let next: unsafe fn(*mut c_void, *mut c_void) -> bool = mem::transmute(self.next);
+        next(self.iter, item)
+    }
+
+    /// Drops the iterator, freeing the box.
+    ///
+    /// # Safety
+    ///
+    /// The iterator must not be used after it's been freed.
+    pub unsafe fn free(self) {
+        let drop: unsafe fn(*mut c_void) = mem::transmute(self.drop);
+        drop(self.iter);
+    }
+
+    /// Takes back ownership of the boxed iterator.
+    ///
+    /// # Safety
+    ///
+    /// `I` must be the type of the iterator that was passed to [`new`], and the
+    /// iterator must not be used after it's been taken.
+    ///
+    /// [`new`]: #method.new
+    pub unsafe fn into_inner<I>(self) -> I {
+        *Box::from_raw(self.iter as *mut I)
+    }
+}
+
+unsafe impl AbiPrimitive for RawIter {}
+
+/// Raw representation of a borrowed trait object (i.e. `&dyn Trait`), compatible
+/// with FFI.
+///
+/// Trait object pointers are twice the size of a regular pointer and don't have a
+/// stable layout, so the pointer is boxed and the raw representation is a thin
+/// pointer to the box. Ownership of the box is transferred along with the raw value,
+/// and it's freed when converting back into a reference with [`into_ref`].
+///
+/// [`into_ref`]: #method.into_ref
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct RawDyn {
+    ptr: *mut c_void,
+}
+
+impl RawDyn {
+    /// Creates the raw representation of a reference to a trait object.
+    pub fn new<T: ?Sized>(value: &T) -> Self {
+        let ptr = Box::into_raw(Box::new(value as *const T));
         Self {
-            discriminant,
-            value: MaybeUninit::uninit(),
+            ptr: ptr as *mut c_void,
         }
     }
+
+    /// Converts the raw representation back into a reference to the trait object.
+    ///
+    /// # Safety
+    ///
+    /// `T` must be the same type that was used to create the `RawDyn`, and the
+    /// referenced value must outlive `'a`. Each `RawDyn` must only be converted once.
+    pub unsafe fn into_ref<'a, T: ?Sized>(self) -> &'a T {
+        let ptr = *Box::from_raw(self.ptr as *mut *const T);
+        &*ptr
+    }
 }
 
-unsafe impl<D: AbiPrimitive, V: AbiPrimitive> AbiPrimitive for RawEnum<D, V> {}
+unsafe impl AbiPrimitive for RawDyn {}
+
+/// Converts an owned trait object into a handle that can be passed to C#.
+///
+/// A `Box<dyn Trait>` is a fat pointer, so it's boxed again in order to pass it as a
+/// single pointer. The `#[cs_bindgen]` macro uses this to implement `Abi` for
+/// `Box<dyn Trait>` for exported traits.
+pub fn into_dyn_handle<T: ?Sized>(value: Box<T>) -> *mut c_void {
+    Box::into_raw(Box::new(value)) as *mut c_void
+}
+
+/// Borrows the trait object behind a handle created with [`into_dyn_handle`].
+///
+/// # Safety
+///
+/// `T` must be the same type that was used to create the handle, and the handle must
+/// not be dropped while the returned reference is in use.
+///
+/// [`into_dyn_handle`]: fn.into_dyn_handle.html
+pub unsafe fn dyn_handle_ref<'a, T: ?Sized>(handle: *mut c_void) -> &'a T {
+    &**(handle as *const Box<T>)
+}
+
+/// Takes back ownership of the trait object behind a handle created with
+/// [`into_dyn_handle`].
+///
+/// # Safety
+///
+/// `T` must be the same type that was used to create the handle, and the handle must
+/// not be used again afterwards.
+///
+/// [`into_dyn_handle`]: fn.into_dyn_handle.html
+pub unsafe fn from_dyn_handle<T: ?Sized>(handle: *mut c_void) -> Box<T> {
+    *Box::from_raw(handle as *mut Box<T>)
+}
+
+/// Raw representation of a C# object implementing an exported trait.
+///
+/// `vtable` points to a table of C# function pointers. The first entry releases the
+/// C# object, and the remaining entries are the methods of the trait in declaration
+/// order. Each method has the same signature as a callback (see [`RawCallback`]),
+/// with `context` identifying the C# object.
+///
+/// [`RawCallback`]: struct.RawCallback.html
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct RawManaged {
+    pub vtable: *const *const c_void,
+    pub context: *mut c_void,
+}
+
+impl RawManaged {
+    /// Returns a callback for invoking the method at `index` in the vtable.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the number of methods in the vtable, and the C#
+    /// object must not have been released.
+    pub unsafe fn method(&self, index: usize) -> RawCallback {
+        RawCallback {
+            func: *self.vtable.add(index + 1),
+            context: self.context,
+        }
+    }
+
+    /// Releases the C# object, allowing it to be garbage collected.
+    ///
+    /// # Safety
+    ///
+    /// The object must not be used after it's been released.
+    pub unsafe fn release(self) {
+        let release: unsafe extern "C" fn(*mut c_void) = mem::transmute(*self.vtable);
+        release(self.context);
+    }
+}
+
+unsafe impl AbiPrimitive for RawManaged {}
+
+/// An owned C# object implementing an exported trait.
+///
+/// Keeps the C# object alive until dropped. The `#[cs_bindgen]` macro generates an
+/// impl of the trait for a wrapper around `ManagedObject` that forwards each method
+/// to C#.
+#[derive(Debug)]
+pub struct ManagedObject {
+    raw: RawManaged,
+}
+
+impl ManagedObject {
+    /// Takes ownership of a C# object passed to Rust.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid C# object that isn't owned by any other `ManagedObject`.
+    pub unsafe fn from_raw(raw: RawManaged) -> Self {
+        Self { raw }
+    }
+
+    pub fn raw(&self) -> &RawManaged {
+        &self.raw
+    }
+}
+
+impl Drop for ManagedObject {
+    fn drop(&mut self) {
+        unsafe {
+            self.raw.release();
+        }
+    }
+}
+
+/// An exported trait that can be implemented by C# classes.
+///
+/// Implemented for `dyn Trait` by the `#[cs_bindgen]` macro.
+pub trait FromManaged {
+    /// Wraps an owned C# object in a trait object.
+    ///
+    /// # Safety
+    ///
+    /// The vtable of `raw` must have been generated for the trait.
+    unsafe fn from_managed(raw: RawManaged) -> Box<Self>;
+}
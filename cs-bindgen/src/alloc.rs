@@ -0,0 +1,191 @@
+//! Sharing an allocator between Rust and C#.
+//!
+//! Buffers are normally freed by whichever side allocated them, e.g. a `RawVec`
+//! returned to C# has to be passed back to Rust to be dropped. To allow buffers to
+//! change hands in either direction, the dylib exports `__cs_bindgen_alloc` and
+//! `__cs_bindgen_free`, which allocate with Rust's global allocator (exposed in C# as
+//! `RustAllocator.Alloc` and `RustAllocator.Free`). Memory allocated with
+//! `__cs_bindgen_alloc` can be passed to Rust as the buffer of a `RawVec` with the
+//! same size and alignment, and Rust takes ownership of it.
+//!
+//! The C# host can also provide the allocator used by Rust, e.g. to track Rust's
+//! allocations in the host's memory profiler. This requires the dylib to use
+//! [`HostAllocator`] as its global allocator:
+//!
+//! ```
+//! #[global_allocator]
+//! static ALLOCATOR: cs_bindgen::alloc::HostAllocator = cs_bindgen::alloc::HostAllocator;
+//! # fn main() {}
+//! ```
+//!
+//! The host then registers its allocation functions with `RustAllocator.SetHooks`
+//! before calling into Rust. Memory has to be freed by the allocator that allocated
+//! it, so the hooks can only be registered before the first allocation, and can't be
+//! changed afterwards. Until the hooks are registered, `HostAllocator` uses the system
+//! allocator.
+//!
+//! [`HostAllocator`]: struct.HostAllocator.html
+
+use std::{
+    alloc::{self, GlobalAlloc, Layout, System},
+    ffi::c_void,
+    hint, mem, ptr,
+    sync::atomic::{AtomicPtr, AtomicU8, Ordering},
+};
+
+/// Signature of the C# function that allocates memory for Rust.
+///
+/// Must return a pointer to `size` bytes aligned to `align`, or null if the allocation
+/// failed.
+pub type RawAllocFn = unsafe extern "C" fn(size: usize, align: usize) -> *mut u8;
+
+/// Signature of the C# function that frees memory allocated with the [`RawAllocFn`],
+/// which is passed the same size and alignment that the memory was allocated with.
+///
+/// [`RawAllocFn`]: type.RawAllocFn.html
+pub type RawFreeFn = unsafe extern "C" fn(ptr: *mut u8, size: usize, align: usize);
+
+/// No allocations have been made with the [`HostAllocator`] yet.
+const UNUSED: u8 = 0;
+
+/// The [`HostAllocator`] allocates with the system allocator.
+const SYSTEM: u8 = 1;
+
+/// The hooks are being registered, and will be used once they've been stored.
+const REGISTERING: u8 = 2;
+
+/// The [`HostAllocator`] allocates with the hooks registered by C#.
+const HOOKS: u8 = 3;
+
+/// Which allocator the [`HostAllocator`] uses. This only ever changes from `UNUSED` to
+/// `SYSTEM`, or from `UNUSED` to `HOOKS` through `REGISTERING`, so an allocation is
+/// always freed by the same allocator.
+static STATE: AtomicU8 = AtomicU8::new(UNUSED);
+
+static ALLOC_HOOK: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+static FREE_HOOK: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+/// A global allocator that uses the allocation functions registered by the C# host,
+/// or the system allocator if the host didn't register any.
+///
+/// See [the module documentation](index.html) for more information.
+pub struct HostAllocator;
+
+impl HostAllocator {
+    fn uses_hooks(&self) -> bool {
+        loop {
+            match STATE.load(Ordering::Acquire) {
+                HOOKS => return true,
+                SYSTEM => return false,
+
+                // NOTE: Another thread has claimed the allocator for the hooks but hasn't
+                // stored them yet, which only takes a moment. Allocating with the system
+                // allocator isn't an option, since the memory would be freed with the
+                // hooks.
+                REGISTERING => hint::spin_loop(),
+
+                // If the state has changed since it was loaded, check it again.
+                _ => {
+                    if STATE
+                        .compare_exchange(UNUSED, SYSTEM, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for HostAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if self.uses_hooks() {
+            let alloc: RawAllocFn = mem::transmute(ALLOC_HOOK.load(Ordering::Acquire));
+            alloc(layout.size(), layout.align())
+        } else {
+            System.alloc(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if self.uses_hooks() {
+            let free: RawFreeFn = mem::transmute(FREE_HOOK.load(Ordering::Acquire));
+            free(ptr, layout.size(), layout.align())
+        } else {
+            System.dealloc(ptr, layout)
+        }
+    }
+}
+
+/// Registers the C# functions used by [`HostAllocator`] to allocate and free memory.
+///
+/// Returns `false` if any memory has already been allocated with `HostAllocator`, or
+/// if hooks have already been registered, in which case the hooks aren't used.
+///
+/// # Safety
+///
+/// The functions must remain valid to call from any thread for the rest of the
+/// process, and must behave as described by [`RawAllocFn`] and [`RawFreeFn`].
+///
+/// [`HostAllocator`]: struct.HostAllocator.html
+/// [`RawAllocFn`]: type.RawAllocFn.html
+/// [`RawFreeFn`]: type.RawFreeFn.html
+pub unsafe fn set_hooks(alloc: RawAllocFn, free: RawFreeFn) -> bool {
+    // Claim the allocator before storing the hooks, so that concurrent calls can't
+    // overwrite the hooks stored by the call that succeeds.
+    if STATE
+        .compare_exchange(UNUSED, REGISTERING, Ordering::AcqRel, Ordering::Acquire)
+        .is_err()
+    {
+        return false;
+    }
+
+    // NOTE: The hooks are stored before the state is published, so they're always
+    // visible to any thread that sees the new state.
+    ALLOC_HOOK.store(alloc as *mut c_void, Ordering::Release);
+    FREE_HOOK.store(free as *mut c_void, Ordering::Release);
+    STATE.store(HOOKS, Ordering::Release);
+    true
+}
+
+/// Allocates `size` bytes aligned to `align` with the global allocator.
+///
+/// Returns null if `align` isn't a power of two. Zero-sized allocations return a
+/// dangling pointer that's aligned to `align`, which doesn't need to be freed.
+pub unsafe fn alloc(size: usize, align: usize) -> *mut u8 {
+    let layout = match Layout::from_size_align(size, align) {
+        Ok(layout) => layout,
+        Err(_) => return ptr::null_mut(),
+    };
+
+    // Zero-sized allocations aren't allowed, so we return a dangling (but well-aligned)
+    // pointer instead, matching what `Vec` expects for an empty buffer.
+    if size == 0 {
+        return align as *mut u8;
+    }
+
+    let ptr = alloc::alloc(layout);
+    if ptr.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+
+    ptr
+}
+
+/// Frees memory allocated with [`alloc`].
+///
+/// # Safety
+///
+/// `ptr` must have been returned by `alloc` (or allocated by Rust's global allocator
+/// with the same layout, e.g. as the buffer of a `Vec`), and `size` and `align` must be
+/// the size and alignment that it was allocated with.
+///
+/// [`alloc`]: fn.alloc.html
+pub unsafe fn free(ptr: *mut u8, size: usize, align: usize) {
+    if size == 0 {
+        return;
+    }
+
+    alloc::dealloc(ptr, Layout::from_size_align_unchecked(size, align));
+}
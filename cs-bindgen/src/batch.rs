@@ -0,0 +1,72 @@
+//! Support for submitting many calls from C# in a single FFI call.
+//!
+//! Every call from C# into Rust pays the fixed overhead of a P/Invoke transition,
+//! which dominates the cost of small functions that are called at a high frequency.
+//! Functions and methods exported with `#[cs_bindgen(batch)]` can instead be recorded
+//! into a C# `CommandBuffer`, which runs all of the recorded calls with a single call
+//! to `__cs_bindgen_submit_commands`.
+//!
+//! Each recorded call (a "command") is encoded as the address of the [`RawCommandFn`]
+//! generated for the function, followed by the raw arguments to the function in
+//! order. The values are written in native byte order without any padding, so they
+//! are read with [`read_arg`] rather than being borrowed from the buffer. The buffer
+//! can't own or pin any managed data, so only functions that take primitive
+//! arguments (and borrow their receiver, for methods) can be batched, and batched
+//! functions can't return a value.
+//!
+//! If a command panics, the remaining commands in the buffer are skipped and the
+//! panic is reported to C# as usual.
+//!
+//! [`RawCommandFn`]: type.RawCommandFn.html
+//! [`read_arg`]: fn.read_arg.html
+
+use crate::abi::{RawSlice, RawString};
+use std::{mem, ptr};
+
+/// Signature of the function generated for a batched function, which reads the
+/// arguments for one call from the buffer and invokes the function's binding.
+///
+/// Returns the number of bytes of arguments that were read, i.e. the offset of the
+/// next command from `args`.
+pub type RawCommandFn = unsafe extern "C" fn(args: *const u8, panic_out: *mut RawString) -> usize;
+
+/// Reads the next argument of a command, advancing `cursor` past the argument.
+///
+/// # Safety
+///
+/// `cursor` must point to a valid value of type `T`, which doesn't need to be
+/// aligned.
+pub unsafe fn read_arg<T>(cursor: &mut *const u8) -> T {
+    let value = ptr::read_unaligned(*cursor as *const T);
+    *cursor = cursor.add(mem::size_of::<T>());
+    value
+}
+
+/// Runs the commands in `buffer` in order, stopping after the first command that
+/// panics. Returns the number of commands that completed without panicking.
+///
+/// # Safety
+///
+/// `buffer` must contain a sequence of commands encoded as described in [the module
+/// documentation](index.html), and `panic_out` must point to an empty `RawString`.
+pub unsafe fn submit(buffer: RawSlice<u8>, panic_out: *mut RawString) -> usize {
+    let buffer = buffer.as_slice();
+    let end = buffer.as_ptr().add(buffer.len());
+
+    let mut cursor = buffer.as_ptr();
+    let mut completed = 0;
+    while cursor < end {
+        let command = read_arg::<RawCommandFn>(&mut cursor);
+        cursor = cursor.add(command(cursor, panic_out));
+
+        // NOTE: The binding only writes to `panic_out` if the function panics, and a
+        // panic message is never a null pointer, even if it's empty.
+        if !(*panic_out).ptr.is_null() {
+            break;
+        }
+
+        completed += 1;
+    }
+
+    completed
+}
@@ -0,0 +1,126 @@
+//! Opt-in checks for aliased borrows of handles.
+//!
+//! Nothing prevents C# code from calling into the same handle from multiple places at
+//! once. For example, a `&mut self` method that invokes a C# callback can be re-entered
+//! if the callback calls another method on the same object, and the same object can
+//! be passed for two `&mut T` arguments of a single function. Either case creates
+//! aliased references to the value behind the handle, which is undefined behavior.
+//!
+//! When the `borrow-checks` feature is enabled, each handle carries a borrow state
+//! while it's borrowed by a binding function, similar to a `RefCell`: Methods taking
+//! `&self` borrow the handle for the duration of the call, and methods taking
+//! `&mut self` (as well as `&mut T` arguments of a handle type) borrow it exclusively.
+//! A call that would alias an exclusive borrow panics instead, so the conflict is
+//! reported to C# as a `RustPanicException` naming the type, rather than corrupting
+//! the value.
+//!
+//! The checks add a global lock to every method call on a handle, so they're intended
+//! for debug builds. Without the feature, the guards are zero-sized and the checks do
+//! nothing.
+
+use std::any;
+
+#[cfg(feature = "borrow-checks")]
+use lazy_static::lazy_static;
+#[cfg(feature = "borrow-checks")]
+use std::{collections::HashMap, sync::Mutex};
+
+/// The borrow of a handle that's held for the duration of a call, released when
+/// dropped.
+#[must_use = "the handle is only borrowed until the guard is dropped"]
+pub struct BorrowGuard {
+    #[cfg(feature = "borrow-checks")]
+    handle: usize,
+}
+
+/// Borrows the value behind `handle` for the lifetime of the returned guard.
+///
+/// # Panics
+///
+/// Panics if the handle is currently borrowed exclusively.
+pub fn borrow<T: ?Sized>(handle: *const T) -> BorrowGuard {
+    acquire(handle as *const () as usize, false, any::type_name::<T>())
+}
+
+/// Borrows the value behind `handle` exclusively for the lifetime of the returned
+/// guard.
+///
+/// # Panics
+///
+/// Panics if the handle is currently borrowed.
+pub fn borrow_mut<T: ?Sized>(handle: *const T) -> BorrowGuard {
+    acquire(handle as *const () as usize, true, any::type_name::<T>())
+}
+
+/// The number of outstanding shared borrows of a handle, or `EXCLUSIVE` if the handle
+/// is borrowed exclusively.
+#[cfg(feature = "borrow-checks")]
+type BorrowState = isize;
+
+#[cfg(feature = "borrow-checks")]
+const EXCLUSIVE: BorrowState = -1;
+
+#[cfg(feature = "borrow-checks")]
+fn acquire(handle: usize, exclusive: bool, type_name: &str) -> BorrowGuard {
+    let acquired = {
+        let mut states = STATES.lock().unwrap();
+        let state = states.entry(handle).or_default();
+        match (*state, exclusive) {
+            (0, true) => {
+                *state = EXCLUSIVE;
+                true
+            }
+            (EXCLUSIVE, _) | (_, true) => false,
+            (_, false) => {
+                *state += 1;
+                true
+            }
+        }
+    };
+
+    // NOTE: The lock is released before panicking so that it isn't poisoned.
+    if !acquired {
+        if exclusive {
+            panic!(
+                "Can't mutably borrow `{}` because it's already borrowed by a re-entrant or aliased call",
+                type_name,
+            );
+        } else {
+            panic!(
+                "Can't borrow `{}` because it's already mutably borrowed by a re-entrant or aliased call",
+                type_name,
+            );
+        }
+    }
+
+    BorrowGuard { handle }
+}
+
+#[cfg(not(feature = "borrow-checks"))]
+fn acquire(_handle: usize, _exclusive: bool, _type_name: &str) -> BorrowGuard {
+    BorrowGuard {}
+}
+
+#[cfg(feature = "borrow-checks")]
+impl Drop for BorrowGuard {
+    fn drop(&mut self) {
+        let mut states = match STATES.lock() {
+            Ok(states) => states,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Some(state) = states.get_mut(&self.handle) {
+            if *state == EXCLUSIVE || *state == 1 {
+                states.remove(&self.handle);
+            } else {
+                *state -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "borrow-checks")]
+lazy_static! {
+    /// The borrow state of each borrowed handle, keyed by the handle's address.
+    static ref STATES: Mutex<HashMap<usize, BorrowState>> = Mutex::new(HashMap::new());
+}
@@ -0,0 +1,116 @@
+//! Cancelling long-running calls from C#.
+//!
+//! An exported function that takes a [`CancelFlag`] argument is exposed in C# as taking
+//! a `CancellationToken`. The generated wrapper creates a flag for the call and trips
+//! it when the token is cancelled, which may happen on any thread while the function is
+//! running. The function is expected to check the flag periodically and stop early once
+//! it has been cancelled:
+//!
+//! ```
+//! use cs_bindgen::cancel::{CancelFlag, Cancelled};
+//!
+//! fn simulate(rounds: u32, cancel: CancelFlag) -> Result<u32, Cancelled> {
+//!     let mut wins = 0;
+//!     for round in 0..rounds {
+//!         cancel.check()?;
+//!         wins += round % 2;
+//!     }
+//!
+//!     Ok(wins)
+//! }
+//!
+//! let cancel = CancelFlag::new();
+//! assert_eq!(Ok(5), simulate(10, cancel.clone()));
+//!
+//! cancel.cancel();
+//! assert_eq!(Err(Cancelled), simulate(10, cancel));
+//! ```
+//!
+//! Cancellation is cooperative, so nothing happens if the function never checks the
+//! flag. For async functions the flag stays connected to the token until the returned
+//! task completes.
+
+use crate::{abi::Abi, shared::Repr};
+use std::{
+    error::Error,
+    fmt,
+    mem::ManuallyDrop,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A flag that the caller sets to request that an operation stop early.
+///
+/// Cloning the flag creates a new handle to the same flag, so cancelling any clone
+/// cancels all of them.
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    /// Creates a flag that hasn't been cancelled.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Requests that the operation stop early.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if the operation has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Returns `Err(Cancelled)` if the operation has been cancelled, for use with the
+    /// `?` operator.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The flag is passed as a pointer to the shared flag, which holds a reference to it.
+///
+/// Converting from the raw flag clones the reference, so the caller keeps its own
+/// reference and can still cancel the flag while the call is running. The caller
+/// releases its reference with `__cs_bindgen_cancel_flag_drop`.
+impl Abi for CancelFlag {
+    type Abi = *const AtomicBool;
+
+    fn repr() -> Repr {
+        Repr::CancelFlag
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        self.clone().into_abi()
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        Arc::into_raw(self.0)
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        let flag = ManuallyDrop::new(Arc::from_raw(abi));
+        CancelFlag(Arc::clone(&flag))
+    }
+}
+
+/// The error returned by [`CancelFlag::check`] once the operation has been cancelled.
+///
+/// [`CancelFlag::check`]: struct.CancelFlag.html#method.check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("The operation was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
@@ -0,0 +1,103 @@
+//! Opt-in diagnostics for tracking down leaked handles.
+//!
+//! When the `diagnostics` feature is enabled, every handle passed to C# is counted
+//! per type: A handle is counted as live from the time its value is converted into a
+//! raw handle until ownership of the value is taken back by Rust, either because the
+//! C# object was disposed (or finalized) or because the handle was passed back to
+//! Rust by value. The counts are exposed to C# through `RustDiagnostics.LiveHandles()`,
+//! so a steadily increasing count for a type indicates that C# is holding on to
+//! objects it no longer needs.
+//!
+//! The C# side can additionally record the stack trace where each handle object was
+//! created by setting `RustDiagnostics.CaptureStackTraces`, which is useful in debug
+//! builds for finding the code that created the leaked objects.
+//!
+//! Without the feature, the tracking functions do nothing and the report is always
+//! empty.
+
+use std::any;
+
+#[cfg(feature = "diagnostics")]
+use lazy_static::lazy_static;
+#[cfg(feature = "diagnostics")]
+use std::{collections::HashMap, sync::Mutex};
+
+/// The number of handles of a single type that have been passed to C#.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HandleCount {
+    /// The fully-qualified name of the Rust type.
+    pub type_name: &'static str,
+
+    /// The number of handles that are currently owned by C#.
+    pub live: u64,
+
+    /// The total number of handles that have been passed to C#.
+    pub created: u64,
+}
+
+/// Records that a handle to a `T` has been passed to C#.
+pub fn handle_created<T: ?Sized>() {
+    record(any::type_name::<T>(), true);
+}
+
+/// Records that ownership of a handle to a `T` has been returned to Rust.
+pub fn handle_released<T: ?Sized>() {
+    record(any::type_name::<T>(), false);
+}
+
+/// Returns the handle counts for every type that has been passed to C#, sorted by type
+/// name.
+///
+/// Always returns an empty list if the `diagnostics` feature isn't enabled.
+pub fn live_handles() -> Vec<HandleCount> {
+    #[cfg(feature = "diagnostics")]
+    {
+        let mut counts = COUNTS
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&type_name, &(live, created))| HandleCount {
+                type_name,
+                live,
+                created,
+            })
+            .collect::<Vec<_>>();
+        counts.sort_by_key(|count| count.type_name);
+        counts
+    }
+
+    #[cfg(not(feature = "diagnostics"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Formats the handle counts as the text report passed to C#, with one line per type
+/// containing the type name, the live count, and the created count separated by tabs.
+pub fn report() -> String {
+    live_handles()
+        .iter()
+        .map(|count| format!("{}\t{}\t{}\n", count.type_name, count.live, count.created))
+        .collect()
+}
+
+#[cfg(feature = "diagnostics")]
+fn record(type_name: &'static str, created: bool) {
+    let mut counts = COUNTS.lock().unwrap();
+    let (live, total) = counts.entry(type_name).or_default();
+    if created {
+        *live += 1;
+        *total += 1;
+    } else {
+        *live = live.saturating_sub(1);
+    }
+}
+
+#[cfg(not(feature = "diagnostics"))]
+fn record(_type_name: &'static str, _created: bool) {}
+
+#[cfg(feature = "diagnostics")]
+lazy_static! {
+    /// The live and created counts for each type, keyed by type name.
+    static ref COUNTS: Mutex<HashMap<&'static str, (u64, u64)>> = Mutex::new(HashMap::new());
+}
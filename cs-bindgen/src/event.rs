@@ -0,0 +1,137 @@
+//! Queues for events sent from Rust to C#.
+//!
+//! An [`EventQueue`] is a thread-safe queue that Rust code pushes events into, e.g.
+//! from a background thread driving the game. Returning a clone of the queue from an
+//! exported function gives C# a `EventQueue<T>` object that shares the same queue.
+//! C# then pulls events out of the queue on its own schedule, either by calling
+//! `Poll()`, which raises the `Received` event for each pending event, or by calling
+//! `Drain()` to get the pending events as a list. This keeps event handlers on the
+//! thread that polls the queue, e.g. Unity's main thread, rather than invoking C#
+//! directly from whichever Rust thread produced the event.
+//!
+//! ```
+//! use cs_bindgen::event::EventQueue;
+//!
+//! let queue = EventQueue::new();
+//! let events = queue.clone();
+//!
+//! queue.push("discard");
+//! queue.push("draw");
+//!
+//! assert_eq!(Some("discard"), events.pop());
+//! assert_eq!(1, events.len());
+//! ```
+//!
+//! Events are converted to their C# representation as they're pulled from the queue,
+//! so the pending events are only ever owned by Rust.
+
+use crate::{
+    abi::{Abi, RawIter},
+    shared::Repr,
+};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// A thread-safe queue of events that can be returned to C#.
+///
+/// Cloning the queue creates a new handle to the same queue, so events pushed through
+/// any clone can be received through every other clone.
+pub struct EventQueue<T> {
+    events: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T> EventQueue<T> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Self {
+            events: Default::default(),
+        }
+    }
+
+    /// Adds an event to the back of the queue.
+    pub fn push(&self, event: T) {
+        self.events.lock().unwrap().push_back(event);
+    }
+
+    /// Removes the event at the front of the queue, if any.
+    pub fn pop(&self) -> Option<T> {
+        self.events.lock().unwrap().pop_front()
+    }
+
+    /// Returns the number of pending events.
+    pub fn len(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    /// Returns `true` if there are no pending events.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for EventQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl<T> Default for EventQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for EventQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The queue is passed to C# as a `RawIter` that yields the pending events, so that C#
+/// can receive events through the same exported functions used for iterators. Unlike
+/// a regular iterator, reaching the end only means that there are no events pending
+/// *yet*.
+///
+/// `T` must be `Send` because the C# object holding the queue may be finalized on a
+/// different thread than the one that received it.
+impl<T> Abi for EventQueue<T>
+where
+    T: Abi + Send + 'static,
+{
+    type Abi = RawIter;
+
+    fn repr() -> Repr {
+        Repr::Events(Box::new(T::repr()))
+    }
+
+    fn as_abi(&self) -> Self::Abi {
+        self.clone().into_abi()
+    }
+
+    fn into_abi(self) -> Self::Abi {
+        RawIter::new(Pending(self))
+    }
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.into_inner::<Pending<T>>().0
+    }
+}
+
+/// Iterator over the events currently in the queue, used as the raw representation
+/// of the queue.
+struct Pending<T>(EventQueue<T>);
+
+impl<T> Iterator for Pending<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
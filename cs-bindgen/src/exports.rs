@@ -14,13 +14,23 @@
 //!
 //! [`export`]: ../macro.export.html
 
-use crate::abi::{self, Abi, RawSlice, RawString, RawVec};
+use crate::{
+    abi::{self, Abi, RawIter, RawSlice, RawString, RawVec},
+    alloc, batch,
+    cancel::CancelFlag,
+    diagnostics, logging, panic,
+};
+use std::{
+    ffi::c_void,
+    mem, slice,
+    sync::{atomic::AtomicBool, Arc},
+};
 
 macro_rules! drop_vec {
     ( $( $prim:ty => [$drop_fn:ident, $convert_fn:ident], )* ) => {
         $(
             pub unsafe fn $drop_fn(raw: RawVec<$prim>) {
-                let _ = raw.into_vec();
+                abi::drop_vec(raw);
             }
 
             pub unsafe fn $convert_fn(raw: RawSlice<<$prim as Abi>::Abi>) -> RawVec<$prim> {
@@ -50,9 +60,163 @@ drop_vec! {
     char => [__cs_bindgen_drop_vec_char, __cs_bindgen_convert_vec_char],
 }
 
+/// Returns the version of the ABI that the dylib was built with.
+pub fn __cs_bindgen_abi_version() -> u32 {
+    cs_bindgen_shared::ABI_VERSION
+}
+
+/// Adds the declaration returned by one of the dylib's describe functions to `hash`,
+/// and frees the declaration.
+///
+/// The generated bindings hash the declarations of all of the dylib's exports when
+/// they're first used, and compare the result to the hash of the declarations that
+/// they were generated from.
+pub unsafe fn __cs_bindgen_hash_declaration(hash: u64, declaration: Box<RawString>) -> u64 {
+    let declaration = declaration.into_string();
+    cs_bindgen_shared::hash_metadata(hash, declaration.as_bytes())
+}
+
 /// Converts a C# string (i.e. a UTF-16 slice) into a Rust string.
 pub unsafe fn __cs_bindgen_string_from_utf16(raw: RawSlice<u16>) -> RawString {
     raw.into_string()
         .expect("Failed to convert C# string to Rust string")
         .into()
 }
+
+/// Returns the number of UTF-16 code units needed to encode a Rust string.
+///
+/// Used along with `__cs_bindgen_string_write_utf16` to convert a Rust string
+/// directly into a C# string, see `cs-bindgen-cli` for more details.
+pub unsafe fn __cs_bindgen_string_utf16_len(raw: RawSlice<u8>) -> usize {
+    raw.as_str_unchecked().encode_utf16().count()
+}
+
+/// Writes the UTF-16 encoding of a Rust string into a buffer provided by C#.
+///
+/// `len` is the length of the buffer in UTF-16 code units, which must be the value
+/// returned by `__cs_bindgen_string_utf16_len` for the same string.
+pub unsafe fn __cs_bindgen_string_write_utf16(raw: RawSlice<u8>, dest: *mut u16, len: usize) {
+    if len == 0 {
+        return;
+    }
+
+    let dest = slice::from_raw_parts_mut(dest, len);
+    for (dest, code_unit) in dest.iter_mut().zip(raw.as_str_unchecked().encode_utf16()) {
+        *dest = code_unit;
+    }
+}
+
+/// Allocates a buffer that can be shared between Rust and C#, e.g. the key and value
+/// buffers of a [`RawMap`](../abi/struct.RawMap.html).
+pub unsafe fn __cs_bindgen_alloc_buffer(size: usize) -> *mut u8 {
+    abi::alloc_buffer(size)
+}
+
+/// Frees a buffer allocated with `__cs_bindgen_alloc_buffer`.
+pub unsafe fn __cs_bindgen_free_buffer(ptr: *mut u8, size: usize) {
+    abi::free_buffer(ptr, size)
+}
+
+/// Allocates `size` bytes aligned to `align` with Rust's global allocator.
+///
+/// See [the `alloc` module](../alloc/index.html) for more information.
+pub unsafe fn __cs_bindgen_alloc(size: usize, align: usize) -> *mut u8 {
+    alloc::alloc(size, align)
+}
+
+/// Frees memory allocated with `__cs_bindgen_alloc`, or allocated by Rust with the same
+/// size and alignment.
+pub unsafe fn __cs_bindgen_free(ptr: *mut u8, size: usize, align: usize) {
+    alloc::free(ptr, size, align)
+}
+
+/// Registers the C# functions used by `HostAllocator`, returning `1` if they were
+/// registered or `0` if Rust has already allocated memory.
+pub unsafe fn __cs_bindgen_set_allocator(alloc_fn: *const c_void, free_fn: *const c_void) -> u8 {
+    let alloc_fn = mem::transmute::<*const c_void, alloc::RawAllocFn>(alloc_fn);
+    let free_fn = mem::transmute::<*const c_void, alloc::RawFreeFn>(free_fn);
+    alloc::set_hooks(alloc_fn, free_fn) as u8
+}
+
+/// Creates a cancellation flag that hasn't been cancelled, returning C#'s reference to
+/// it.
+///
+/// See [the `cancel` module](../cancel/index.html) for more information.
+pub fn __cs_bindgen_cancel_flag_new() -> *const AtomicBool {
+    CancelFlag::new().into_abi()
+}
+
+/// Cancels a cancellation flag created with `__cs_bindgen_cancel_flag_new`.
+pub unsafe fn __cs_bindgen_cancel_flag_cancel(flag: *const AtomicBool) {
+    CancelFlag::from_abi(flag).cancel()
+}
+
+/// Releases C#'s reference to a cancellation flag.
+pub unsafe fn __cs_bindgen_cancel_flag_drop(flag: *const AtomicBool) {
+    drop(Arc::from_raw(flag))
+}
+
+/// Advances an iterator returned to C#, writing the raw representation of the next
+/// item to `item`. Returns `1` if an item was written, or `0` if the iterator is
+/// exhausted.
+///
+/// Panics in the iterator are reported through `panic_out`, matching the binding
+/// functions for exported functions.
+pub unsafe fn __cs_bindgen_iter_next(
+    raw: RawIter,
+    item: *mut c_void,
+    panic_out: *mut RawString,
+) -> u8 {
+    panic::catch_panic(panic_out, move || raw.next(item) as u8)
+}
+
+/// Drops an iterator returned to C#.
+pub unsafe fn __cs_bindgen_iter_drop(raw: RawIter) {
+    raw.free()
+}
+
+/// Runs the commands recorded by a C# `CommandBuffer`, returning the number of
+/// commands that completed.
+///
+/// See [the `batch` module](../batch/index.html) for more information.
+pub unsafe fn __cs_bindgen_submit_commands(
+    buffer: RawSlice<u8>,
+    panic_out: *mut RawString,
+) -> usize {
+    batch::submit(buffer, panic_out)
+}
+
+/// Registers the C# handler for log records, or unregisters the current handler if
+/// `handler` is null. Returns `1` if the handler was registered, or `0` if a different
+/// logger has already been installed.
+///
+/// See [the `logging` module](../logging/index.html) for more information.
+pub unsafe fn __cs_bindgen_set_log_handler(handler: *const c_void, max_level: u8) -> u8 {
+    let handler = if handler.is_null() {
+        None
+    } else {
+        Some(mem::transmute::<*const c_void, logging::RawLogFn>(handler))
+    };
+
+    logging::set_handler(handler, logging::level_filter_from_raw(max_level)) as u8
+}
+
+/// Registers the global error hook, or unregisters the current hook if `hook` is null.
+///
+/// See [the `panic` module](../panic/index.html) for more information.
+pub unsafe fn __cs_bindgen_set_error_hook(hook: *const c_void) {
+    let hook = if hook.is_null() {
+        None
+    } else {
+        Some(mem::transmute::<*const c_void, panic::RawErrorHookFn>(hook))
+    };
+
+    panic::set_error_hook(hook)
+}
+
+/// Returns the report of live handles for each type.
+///
+/// See [the `diagnostics` module](../diagnostics/index.html) for more information.
+pub fn __cs_bindgen_live_handles() -> RawString {
+    diagnostics::report().into()
+}
@@ -0,0 +1,93 @@
+//! Support for types exported as C# `[Flags]` enums.
+//!
+//! Types marked with `#[cs_bindgen(flags)]` implement [`Flags`], which exposes the
+//! integer value of the flags. The associated constants of a flags type become the
+//! members of the C# enum, so their values need to be known when the bindings are
+//! generated. The `#[cs_bindgen]` macro can't tell whether the type of a constant is
+//! a flags type, so the generated code checks when the constant is described using the
+//! same autoref-based specialization as [the `thread` module](../thread/index.html).
+//!
+//! ```
+//! use cs_bindgen::{
+//!     flags::{Flags, FlagsValue, IsFlags, NotFlags, NotFlagsValue, Probe, ValueProbe},
+//!     shared::ConstValue,
+//! };
+//! use std::marker::PhantomData;
+//!
+//! #[derive(Clone, Copy)]
+//! struct Permissions(u8);
+//!
+//! impl Flags for Permissions {
+//!     type Bits = u8;
+//!
+//!     fn bits(self) -> u8 {
+//!         self.0
+//!     }
+//! }
+//!
+//! assert!((&Probe::<Permissions>(PhantomData)).is_flags());
+//! assert!(!(&Probe::<u8>(PhantomData)).is_flags());
+//!
+//! assert_eq!(
+//!     Some(ConstValue::UInt(3)),
+//!     (&ValueProbe(&Permissions(3))).const_value(),
+//! );
+//! assert_eq!(None, (&ValueProbe(&"not flags")).const_value());
+//! ```
+
+use crate::shared::ConstValue;
+use std::marker::PhantomData;
+
+/// A set of bit flags that is exported as a C# `[Flags]` enum.
+///
+/// This is implemented by `#[cs_bindgen(flags)]`, and shouldn't need to be
+/// implemented manually.
+pub trait Flags: Copy {
+    /// The integer type used to store the flags, which is the underlying type of the
+    /// C# enum.
+    type Bits: Into<ConstValue>;
+
+    /// Returns the raw integer value of the flags.
+    fn bits(self) -> Self::Bits;
+}
+
+/// A zero-sized value used to probe whether `T` is a flags type.
+pub struct Probe<T: ?Sized>(pub PhantomData<T>);
+
+pub trait IsFlags {
+    fn is_flags(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Flags> IsFlags for Probe<T> {}
+
+pub trait NotFlags {
+    fn is_flags(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> NotFlags for &Probe<T> {}
+
+/// A reference to the value of a constant, used to retrieve the value of constants
+/// with a flags type.
+pub struct ValueProbe<'a, T: ?Sized>(pub &'a T);
+
+pub trait FlagsValue {
+    fn const_value(&self) -> Option<ConstValue>;
+}
+
+impl<T: Flags> FlagsValue for ValueProbe<'_, T> {
+    fn const_value(&self) -> Option<ConstValue> {
+        Some(self.0.bits().into())
+    }
+}
+
+pub trait NotFlagsValue {
+    fn const_value(&self) -> Option<ConstValue> {
+        None
+    }
+}
+
+impl<T: ?Sized> NotFlagsValue for &ValueProbe<'_, T> {}
@@ -1,5 +1,19 @@
 pub mod abi;
+pub mod alloc;
+pub mod batch;
+pub mod borrow;
+pub mod cancel;
+pub mod diagnostics;
+pub mod event;
 pub mod exports;
+pub mod flags;
+pub mod logging;
+pub mod marshal;
+pub mod panic;
+pub mod pool;
+pub mod task;
+pub mod thread;
+pub mod trace;
 
 // Re-export crates used in the generated code.
 pub use cs_bindgen_shared as shared;
@@ -22,7 +36,7 @@ macro_rules! export {
     (fn $name:ident($( $arg:ident : $type:ty ),*) -> $ret:ty) => {
         #[no_mangle]
         pub unsafe extern "C" fn $name($( $arg : $type),*) -> $ret {
-            $crate::exports::$name($( $arg ),*)
+            $crate::panic::abort_on_panic(move || $crate::exports::$name($( $arg ),*))
         }
     };
 
@@ -31,7 +45,31 @@ macro_rules! export {
     };
 
     () => {
+        $crate::export!(fn __cs_bindgen_abi_version() -> u32);
+        $crate::export!(fn __cs_bindgen_hash_declaration(hash: u64, declaration: Box<$crate::abi::RawString>) -> u64);
+
         $crate::export!(fn __cs_bindgen_string_from_utf16(raw: $crate::abi::RawSlice<u16>) -> $crate::abi::RawString);
+        $crate::export!(fn __cs_bindgen_string_utf16_len(raw: $crate::abi::RawSlice<u8>) -> usize);
+        $crate::export!(fn __cs_bindgen_string_write_utf16(raw: $crate::abi::RawSlice<u8>, dest: *mut u16, len: usize));
+
+        $crate::export!(fn __cs_bindgen_cancel_flag_new() -> *const std::sync::atomic::AtomicBool);
+        $crate::export!(fn __cs_bindgen_cancel_flag_cancel(flag: *const std::sync::atomic::AtomicBool));
+        $crate::export!(fn __cs_bindgen_cancel_flag_drop(flag: *const std::sync::atomic::AtomicBool));
+
+        $crate::export!(fn __cs_bindgen_iter_next(raw: $crate::abi::RawIter, item: *mut std::ffi::c_void, panic_out: *mut $crate::abi::RawString) -> u8);
+        $crate::export!(fn __cs_bindgen_iter_drop(raw: $crate::abi::RawIter));
+
+        $crate::export!(fn __cs_bindgen_submit_commands(buffer: $crate::abi::RawSlice<u8>, panic_out: *mut $crate::abi::RawString) -> usize);
+
+        $crate::export!(fn __cs_bindgen_set_log_handler(handler: *const std::ffi::c_void, max_level: u8) -> u8);
+        $crate::export!(fn __cs_bindgen_set_error_hook(hook: *const std::ffi::c_void));
+        $crate::export!(fn __cs_bindgen_live_handles() -> $crate::abi::RawString);
+
+        $crate::export!(fn __cs_bindgen_alloc_buffer(size: usize) -> *mut u8);
+        $crate::export!(fn __cs_bindgen_free_buffer(ptr: *mut u8, size: usize));
+        $crate::export!(fn __cs_bindgen_alloc(size: usize, align: usize) -> *mut u8);
+        $crate::export!(fn __cs_bindgen_free(ptr: *mut u8, size: usize, align: usize));
+        $crate::export!(fn __cs_bindgen_set_allocator(alloc_fn: *const std::ffi::c_void, free_fn: *const std::ffi::c_void) -> u8);
 
         $crate::export!(fn __cs_bindgen_drop_vec_u8(raw: $crate::abi::RawVec<u8>));
         $crate::export!(fn __cs_bindgen_drop_vec_u16(raw: $crate::abi::RawVec<u16>));
@@ -0,0 +1,124 @@
+//! Forwards records logged with the `log` crate to C#.
+//!
+//! The generated C# includes a `RustLogger` class, which registers a C# delegate as
+//! the handler for log records:
+//!
+//! ```csharp
+//! RustLogger.SetHandler(
+//!     (level, target, message) => UnityEngine.Debug.Log($"[{level}] {target}: {message}"),
+//!     RustLogLevel.Debug);
+//! ```
+//!
+//! Registering the first handler installs a [`Log`] implementation that passes the
+//! level, target, and formatted message of each record to the handler. Only one logger
+//! can be installed per process, so registration fails if the Rust code has already
+//! installed a different logger.
+//!
+//! The handler is invoked synchronously on whichever thread logged the record, so it
+//! must be safe to call from any thread. Exceptions thrown by the handler are caught
+//! and discarded, since they can't be propagated through the `log` macros.
+//!
+//! [`Log`]: https://docs.rs/log/0.4/log/trait.Log.html
+
+use crate::abi::{Abi, RawString};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::{
+    ffi::c_void,
+    mem, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Signature of the C# function that receives log records.
+///
+/// The level is the value of the record's [`Level`], i.e. `1` for `Error` through
+/// `5` for `Trace`, and ownership of the target and message strings is passed to C#.
+///
+/// [`Level`]: https://docs.rs/log/0.4/log/enum.Level.html
+pub type RawLogFn = unsafe extern "C" fn(level: u8, target: RawString, message: RawString);
+
+/// The currently-registered C# handler, or null if no handler is registered.
+static HANDLER: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+static LOGGER: CsLogger = CsLogger;
+
+/// The logger that forwards records to the registered C# handler.
+struct CsLogger;
+
+impl CsLogger {
+    fn handler(&self) -> Option<RawLogFn> {
+        let handler = HANDLER.load(Ordering::Acquire);
+        if handler.is_null() {
+            None
+        } else {
+            Some(unsafe { mem::transmute(handler) })
+        }
+    }
+}
+
+impl Log for CsLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= log::max_level() && self.handler().is_some()
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        if let Some(handler) = self.handler() {
+            let target = record.target().to_owned().into_abi();
+            let message = record.args().to_string().into_abi();
+            unsafe {
+                handler(record.level() as u8, target, message);
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Registers `handler` as the handler for log records, installing the C# logger if it
+/// hasn't already been installed.
+///
+/// Passing `None` unregisters the current handler, which disables logging. Returns
+/// `false` if a different logger has already been installed, in which case `handler`
+/// will never be called.
+///
+/// # Safety
+///
+/// `handler` must remain valid to call from any thread until it's unregistered.
+pub unsafe fn set_handler(handler: Option<RawLogFn>, max_level: LevelFilter) -> bool {
+    // NOTE: `set_logger` fails if any logger has been installed, including ours, so we
+    // need to check whether the installed logger is ours before reporting a failure.
+    let installed = log::set_logger(&LOGGER).is_ok()
+        || ptr::eq(
+            log::logger() as *const dyn Log as *const u8,
+            &LOGGER as *const CsLogger as *const u8,
+        );
+    if !installed {
+        return false;
+    }
+
+    let handler = handler.map_or(ptr::null_mut(), |handler| handler as *mut c_void);
+    HANDLER.store(handler, Ordering::Release);
+    log::set_max_level(if handler.is_null() {
+        LevelFilter::Off
+    } else {
+        max_level
+    });
+
+    true
+}
+
+/// Converts the raw level passed from C# into a level filter, i.e. `0` for `Off`
+/// through `5` for `Trace`. Values greater than `5` are treated as `Trace`.
+pub fn level_filter_from_raw(level: u8) -> LevelFilter {
+    match level {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
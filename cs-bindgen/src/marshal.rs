@@ -0,0 +1,159 @@
+//! Support for types exported with `#[cs_bindgen(marshal = "...")]`.
+//!
+//! Marshaled types are serialized with serde when they're passed to C#, and are
+//! deserialized into a C# class on the other side. This is slower than marshaling
+//! a value structurally, but works for any type that implements `Serialize` and
+//! `Deserialize`, including types that the binding generator can't represent yet.
+//!
+//! Two formats are supported:
+//!
+//! * `marshal = "json"` uses JSON, which is easy to inspect and debug. Requires the
+//!   `json` feature.
+//! * `marshal = "msgpack"` uses MessagePack, which is faster and much more compact,
+//!   making it a better fit for large values. Structs are serialized as an array of
+//!   their field values rather than a map, so the C# class identifies each field by
+//!   its index. Requires the `msgpack` feature.
+//!
+//! Types that can't be serialized either (e.g. types wrapping a third-party type) can
+//! instead provide their own conversion to a type that the binding generator
+//! understands, which is described by the [`Custom`] trait.
+//!
+//! The serialized value is passed across the FFI boundary as a byte buffer, using
+//! the same raw representation as a `Vec<u8>`. The generated `Abi` impl for a
+//! marshaled type calls the functions in this module for the chosen format:
+//!
+//! ```
+//! # #[cfg(feature = "json")]
+//! # fn main() {
+//! use cs_bindgen::marshal;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Discard {
+//!     seat: u8,
+//!     tile: String,
+//! }
+//!
+//! let discard = Discard {
+//!     seat: 2,
+//!     tile: "5p".into(),
+//! };
+//! let raw = marshal::to_json(&discard);
+//! assert_eq!(discard, unsafe { marshal::from_json(raw) });
+//! # }
+//! # #[cfg(not(feature = "json"))]
+//! # fn main() {}
+//! ```
+
+use crate::{
+    abi::{Abi, RawVec},
+    shared::CustomMarshal,
+};
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serializes `value` as JSON, passing ownership of the serialized buffer to the
+/// caller.
+///
+/// # Panics
+///
+/// Panics if `value` can't be serialized, e.g. if it's a map with non-string keys.
+#[cfg(feature = "json")]
+pub fn to_json<T: Serialize>(value: &T) -> RawVec<u8> {
+    serde_json::to_vec(value)
+        .expect("Failed to serialize marshaled value as JSON")
+        .into()
+}
+
+/// Deserializes a value from a JSON buffer created by C#, taking ownership of the
+/// buffer.
+///
+/// # Safety
+///
+/// `raw` must be a valid buffer allocated by Rust, and must not be used after
+/// calling `from_json`.
+///
+/// # Panics
+///
+/// Panics if the buffer doesn't contain a valid JSON representation of `T`.
+#[cfg(feature = "json")]
+pub unsafe fn from_json<T: DeserializeOwned>(raw: RawVec<u8>) -> T {
+    let bytes = raw.into_vec();
+    serde_json::from_slice(&bytes).expect("Failed to deserialize marshaled value from JSON")
+}
+
+/// Serializes `value` as MessagePack, passing ownership of the serialized buffer to
+/// the caller.
+///
+/// # Panics
+///
+/// Panics if `value` can't be serialized.
+#[cfg(feature = "msgpack")]
+pub fn to_msgpack<T: Serialize>(value: &T) -> RawVec<u8> {
+    rmp_serde::to_vec(value)
+        .expect("Failed to serialize marshaled value as MessagePack")
+        .into()
+}
+
+/// Deserializes a value from a MessagePack buffer created by C#, taking ownership of
+/// the buffer.
+///
+/// # Safety
+///
+/// `raw` must be a valid buffer allocated by Rust, and must not be used after
+/// calling `from_msgpack`.
+///
+/// # Panics
+///
+/// Panics if the buffer doesn't contain a valid MessagePack representation of `T`.
+#[cfg(feature = "msgpack")]
+pub unsafe fn from_msgpack<T: DeserializeOwned>(raw: RawVec<u8>) -> T {
+    let bytes = raw.into_vec();
+    rmp_serde::from_slice(&bytes).expect("Failed to deserialize marshaled value from MessagePack")
+}
+
+/// A type exported with user-provided conversion functions.
+///
+/// This is implemented by `#[cs_bindgen]` for types exported with the `raw`,
+/// `into_raw_with`, `from_raw_with`, and `converter` arguments:
+///
+/// ```ignore
+/// #[cs_bindgen(
+///     raw = "String",
+///     into_raw_with = "GameId::to_hex",
+///     from_raw_with = "GameId::from_hex",
+///     converter = "GameIdConverter",
+///     cs_type = "System.Guid",
+/// )]
+/// pub struct GameId(u128);
+/// ```
+///
+/// Values are converted to `Raw` before being passed across the FFI boundary. On the
+/// C# side, the converter class has a static `FromRaw` method that converts the C#
+/// value for `Raw` into the C# type, and a static `IntoRaw` method for the opposite
+/// direction.
+pub trait Custom: Sized {
+    /// The type that values are converted to before being passed to C#.
+    type Raw: Abi;
+
+    /// The fully-qualified name of the C# converter class.
+    const CONVERTER: &'static str;
+
+    /// The fully-qualified name of the C# type that values are exposed as, if it's
+    /// different from the name of the Rust type.
+    const CS_TYPE: Option<&'static str>;
+
+    fn into_raw(&self) -> Self::Raw;
+
+    fn from_raw(raw: Self::Raw) -> Self;
+}
+
+/// Describes the conversions for a type exported with user-provided conversion
+/// functions.
+pub fn describe_custom<T: Custom>() -> CustomMarshal {
+    CustomMarshal {
+        raw: T::Raw::repr(),
+        converter: T::CONVERTER.into(),
+        cs_type: T::CS_TYPE.map(Into::into),
+    }
+}
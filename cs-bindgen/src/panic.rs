@@ -0,0 +1,178 @@
+//! Utilities for preventing panics from unwinding across the FFI boundary.
+//!
+//! Unwinding out of an `extern "C"` function is undefined behavior, so every
+//! function generated by `#[cs_bindgen]` must catch any panics that occur while
+//! running user code. There are two strategies for doing so:
+//!
+//! * The binding functions for exported functions and methods use [`catch_panic`],
+//!   which reports the panic message back to C# through an out parameter. The
+//!   generated C# then throws a `RustPanicException` containing the message.
+//! * Internal helper functions (e.g. the drop and list conversion functions) use
+//!   [`abort_on_panic`], since there's no reasonable way to recover from a panic in
+//!   those cases.
+//!
+//! In addition to the per-call error reporting, the C# host can register a global
+//! error hook with [`set_error_hook`] (exposed in C# as `RustErrorHook.SetHandler`).
+//! Every panic caught at the FFI boundary is reported to the hook along with the
+//! backtrace of the panic, including panics that abort the process and panics in
+//! async functions, so that crashes inside Rust can be forwarded to a crash reporter.
+//! Backtraces are only captured while a hook is registered.
+//!
+//! [`catch_panic`]: fn.catch_panic.html
+//! [`abort_on_panic`]: fn.abort_on_panic.html
+//! [`set_error_hook`]: fn.set_error_hook.html
+
+use crate::abi::{Abi, AbiPrimitive, RawString};
+use backtrace::Backtrace;
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::c_void,
+    mem::{self, MaybeUninit},
+    panic::{self, AssertUnwindSafe},
+    process, ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Once,
+    },
+};
+
+/// Signature of the C# function that receives panics caught at the FFI boundary.
+///
+/// Ownership of the message and backtrace strings is passed to C#. The backtrace is
+/// empty if it couldn't be captured.
+pub type RawErrorHookFn = unsafe extern "C" fn(message: RawString, backtrace: RawString);
+
+/// The currently-registered error hook, or null if no hook is registered.
+static ERROR_HOOK: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+thread_local! {
+    /// The backtrace of the most recent panic on the current thread, captured by the
+    /// panic hook until the panic is caught at the FFI boundary.
+    static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = RefCell::new(None);
+}
+
+/// Registers `hook` as the global error hook, replacing any existing hook. Passing
+/// `None` unregisters the current hook.
+///
+/// The first time a hook is registered, a panic hook is installed to capture the
+/// backtrace of each panic. The previously-installed panic hook is still invoked
+/// afterwards, so the default panic output (or any user-installed hook) is preserved.
+///
+/// # Safety
+///
+/// `hook` must remain valid to call from any thread until it's unregistered.
+pub unsafe fn set_error_hook(hook: Option<RawErrorHookFn>) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if error_hook().is_some() {
+                let backtrace = Backtrace::new();
+                PANIC_BACKTRACE.with(|slot| *slot.borrow_mut() = Some(backtrace));
+            }
+
+            previous(info);
+        }));
+    });
+
+    let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut c_void);
+    ERROR_HOOK.store(hook, Ordering::Release);
+}
+
+fn error_hook() -> Option<RawErrorHookFn> {
+    let hook = ERROR_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        None
+    } else {
+        Some(unsafe { mem::transmute(hook) })
+    }
+}
+
+/// Reports a panic caught at the FFI boundary to the error hook, if one is
+/// registered, and returns the panic message.
+///
+/// This must be called on the thread that panicked in order for the backtrace to be
+/// included in the report.
+pub fn report_panic(payload: &(dyn Any + Send)) -> String {
+    let message = payload_message(payload);
+    let backtrace = PANIC_BACKTRACE.with(|slot| slot.borrow_mut().take());
+
+    if let Some(hook) = error_hook() {
+        let backtrace = backtrace
+            .map(|backtrace| format!("{:?}", backtrace))
+            .unwrap_or_default();
+
+        // NOTE: The C# side of the hook catches any exceptions thrown by the handler, so
+        // calling the hook never unwinds.
+        unsafe {
+            hook(message.clone().into_abi(), backtrace.into_abi());
+        }
+    }
+
+    message
+}
+
+/// Invokes `func`, catching any panic that occurs and reporting it via `panic_out`.
+///
+/// If `func` completes normally its return value is returned and `panic_out` isn't
+/// modified. If `func` panics, the panic message is written to `panic_out` and a
+/// zero-initialized value is returned. The caller is responsible for checking
+/// `panic_out` and discarding the return value if a panic occurred.
+///
+/// # Safety
+///
+/// `panic_out` must either be null or point to a valid `RawString`. If a panic
+/// message is written to `panic_out`, the caller takes ownership of the string and
+/// is responsible for freeing it.
+pub unsafe fn catch_panic<T, F>(panic_out: *mut RawString, func: F) -> T
+where
+    T: AbiPrimitive,
+    F: FnOnce() -> T,
+{
+    match panic::catch_unwind(AssertUnwindSafe(func)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = report_panic(&*payload);
+            if !panic_out.is_null() {
+                *panic_out = message.into();
+            }
+
+            // SAFETY: Raw representations are valid when zero-initialized, as required
+            // by `AbiPrimitive`.
+            MaybeUninit::zeroed().assume_init()
+        }
+    }
+}
+
+/// Invokes `func`, aborting the process if a panic occurs.
+///
+/// This should be used for any FFI function that has no way to report a panic back
+/// to the caller.
+pub fn abort_on_panic<T, F>(func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    match panic::catch_unwind(AssertUnwindSafe(func)) {
+        Ok(result) => result,
+        Err(payload) => {
+            report_panic(&*payload);
+            process::abort()
+        }
+    }
+}
+
+/// Extracts the message from a panic payload.
+///
+/// Panics created with `panic!` have either a `&'static str` or a `String` as the
+/// payload. Panics with any other payload type (i.e. ones created with
+/// `std::panic::panic_any`) get a generic message.
+pub fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Rust code panicked with a non-string payload".into()
+    }
+}
@@ -0,0 +1,107 @@
+//! Pooling the allocations of handle types.
+//!
+//! Passing a value to C# as a handle normally boxes the value, so every handle that's
+//! created costs an allocation, and every handle that's dropped costs a free. For
+//! types that are created and dropped at a high rate (e.g. analysis results that are
+//! recomputed every turn), the allocator can make up a large part of the cost of the
+//! call. Handle types can opt in to reusing their allocations with
+//! `#[cs_bindgen(pool)]`:
+//!
+//! ```
+//! use cs_bindgen::prelude::*;
+//!
+//! #[cs_bindgen(pool)]
+//! pub struct WaitAnalysis {
+//!     waits: Vec<u8>,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! When the C# object is disposed (or the handle is passed back to Rust by value), the
+//! value is moved out of its allocation and the empty allocation is returned to the
+//! pool for the next handle of the same type. Each thread has its own pool for each
+//! type, so no synchronization is needed. An allocation that's released on a
+//! different thread than the one that created it is added to the pool of the thread
+//! that released it.
+//!
+//! Each pool holds at most [`MAX_POOLED`] allocations, any further allocations are
+//! freed as usual. The pooled allocations are freed when the thread exits.
+//!
+//! [`MAX_POOLED`]: constant.MAX_POOLED.html
+
+use std::{cell::RefCell, mem::MaybeUninit, ptr, thread::LocalKey};
+
+/// The maximum number of unused allocations kept by each pool.
+pub const MAX_POOLED: usize = 1024;
+
+/// A handle type whose allocations are reused, i.e. a type exported with
+/// `#[cs_bindgen(pool)]`.
+pub trait Pooled: Sized + 'static {
+    /// Returns the calling thread's pool for the type.
+    fn pool() -> &'static LocalKey<Pool<Self>>;
+}
+
+/// The unused allocations for a single type on a single thread.
+pub struct Pool<T> {
+    free: RefCell<Vec<Box<MaybeUninit<T>>>>,
+}
+
+impl<T> Pool<T> {
+    /// Creates an empty pool.
+    pub const fn new() -> Self {
+        Pool {
+            free: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn take(&self) -> Option<Box<MaybeUninit<T>>> {
+        self.free.borrow_mut().pop()
+    }
+
+    fn put(&self, slot: Box<MaybeUninit<T>>) {
+        let mut free = self.free.borrow_mut();
+        if free.len() < MAX_POOLED {
+            free.push(slot);
+        }
+    }
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Moves `value` into an allocation from the pool, allocating a new one if the pool
+/// is empty, and returns the raw handle to it.
+pub fn into_raw<T: Pooled>(value: T) -> *const T {
+    // NOTE: The pool is inaccessible while the thread is shutting down, in which case
+    // the value is allocated as if it wasn't pooled.
+    let mut slot = T::pool()
+        .try_with(Pool::take)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| Box::new(MaybeUninit::uninit()));
+
+    *slot = MaybeUninit::new(value);
+    Box::into_raw(slot) as *const T
+}
+
+/// Moves the value out of a handle created with [`into_raw`], returning its
+/// allocation to the pool.
+///
+/// # Safety
+///
+/// `raw` must have been returned by `into_raw`, and must not be used afterwards.
+///
+/// [`into_raw`]: fn.into_raw.html
+pub unsafe fn from_raw<T: Pooled>(raw: *const T) -> T {
+    let slot = Box::from_raw(raw as *mut MaybeUninit<T>);
+    let value = ptr::read(slot.as_ptr());
+
+    // NOTE: If the pool is inaccessible the closure isn't run, so the allocation is
+    // freed when the closure is dropped.
+    let _ = T::pool().try_with(move |pool| pool.put(slot));
+
+    value
+}
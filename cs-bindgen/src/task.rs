@@ -0,0 +1,143 @@
+//! Executor for exported async functions.
+//!
+//! Calling an exported `async fn` from C# spawns the returned future onto a single
+//! background thread, which is started the first time an async function is called.
+//! Once the future completes, its output is passed to C# through a completion
+//! callback (see [`RawCompletion`]), which completes the `Task` returned to the C#
+//! caller. This means that awaiting an async function never blocks the calling
+//! thread, e.g. Unity's main thread.
+//!
+//! All spawned futures share the executor thread, so a future that performs a large
+//! amount of work without yielding will delay every other pending future. Such work
+//! should be moved to a dedicated thread.
+//!
+//! If the future panics, the panic is caught and the task is completed with the panic
+//! message, which the generated C# reports as a `RustPanicException`.
+//!
+//! [`RawCompletion`]: ../abi/struct.RawCompletion.html
+
+use crate::{
+    abi::{Abi, RawCompletion},
+    panic::report_panic,
+};
+use lazy_static::lazy_static;
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread,
+};
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Spawns `future` onto the executor, invoking `completion` with its output once it
+/// completes.
+///
+/// # Safety
+///
+/// The output of `future` must match the output type expected by the C# completion
+/// callback.
+pub unsafe fn spawn<F>(future: F, completion: RawCompletion)
+where
+    F: Future + Send + 'static,
+    F::Output: Abi,
+{
+    let task = Arc::new(Task {
+        future: Mutex::new(Some(Box::pin(Completing {
+            future: Box::pin(future),
+            completion: Some(completion),
+        }))),
+    });
+
+    schedule(task);
+}
+
+/// A spawned future.
+///
+/// The future is `None` once it has completed, so that any remaining wakeups are
+/// ignored.
+struct Task {
+    future: Mutex<Option<BoxFuture>>,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        schedule(self);
+    }
+}
+
+/// Wraps a spawned future, invoking the completion callback once the future completes
+/// or panics.
+struct Completing<F: Future> {
+    future: Pin<Box<F>>,
+    completion: Option<RawCompletion>,
+}
+
+impl<F> Future for Completing<F>
+where
+    F: Future,
+    F::Output: Abi,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let future = self.future.as_mut();
+        let result = match panic::catch_unwind(AssertUnwindSafe(move || future.poll(cx))) {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(output)) => Ok(output),
+            Err(payload) => Err(report_panic(&*payload)),
+        };
+
+        // NOTE: The completion is only taken once, since the executor never polls a future
+        // again after it has completed.
+        if let Some(completion) = self.completion.take() {
+            unsafe {
+                completion.complete(result);
+            }
+        }
+
+        Poll::Ready(())
+    }
+}
+
+lazy_static! {
+    /// The sender for the executor thread, which starts the thread the first time it's
+    /// accessed.
+    static ref EXECUTOR: Mutex<mpsc::Sender<Arc<Task>>> = {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("cs-bindgen executor".into())
+            .spawn(move || run(receiver))
+            .expect("Failed to start cs-bindgen executor thread");
+
+        Mutex::new(sender)
+    };
+}
+
+/// Sends `task` to the executor thread to be polled.
+fn schedule(task: Arc<Task>) {
+    EXECUTOR
+        .lock()
+        .unwrap()
+        .send(task)
+        .expect("cs-bindgen executor thread has stopped");
+}
+
+/// Polls tasks as they're scheduled.
+///
+/// A task woken while it's being polled is scheduled again, so it will be polled
+/// again once the current poll returns.
+fn run(receiver: mpsc::Receiver<Arc<Task>>) {
+    for task in receiver {
+        let mut slot = task.future.lock().unwrap();
+        if let Some(mut future) = slot.take() {
+            let waker = Waker::from(task.clone());
+            let mut cx = Context::from_waker(&waker);
+            if future.as_mut().poll(&mut cx).is_pending() {
+                *slot = Some(future);
+            }
+        }
+    }
+}
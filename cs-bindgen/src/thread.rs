@@ -0,0 +1,53 @@
+//! Detection of the thread-safety traits implemented by exported types.
+//!
+//! The `#[cs_bindgen]` macro can't tell whether a type implements `Send` or `Sync`,
+//! so instead the generated code checks when the type is described. This uses
+//! autoref-based specialization: Calling `is_send` on a `&Probe<T>` resolves to the
+//! impl on `Probe<T>` if `T: Send`, and otherwise falls back to the impl on
+//! `&Probe<T>`. This only works when `T` is a concrete type, which is always the case
+//! in the generated code.
+//!
+//! ```
+//! use cs_bindgen::thread::{IsSend, NotSend, Probe};
+//! use std::{marker::PhantomData, rc::Rc};
+//!
+//! assert!((&Probe::<u32>(PhantomData)).is_send());
+//! assert!(!(&Probe::<Rc<u32>>(PhantomData)).is_send());
+//! ```
+
+use std::marker::PhantomData;
+
+/// A zero-sized value used to probe which traits `T` implements.
+pub struct Probe<T: ?Sized>(pub PhantomData<T>);
+
+pub trait IsSend {
+    fn is_send(&self) -> bool {
+        true
+    }
+}
+
+impl<T: ?Sized + Send> IsSend for Probe<T> {}
+
+pub trait NotSend {
+    fn is_send(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> NotSend for &Probe<T> {}
+
+pub trait IsSync {
+    fn is_sync(&self) -> bool {
+        true
+    }
+}
+
+impl<T: ?Sized + Sync> IsSync for Probe<T> {}
+
+pub trait NotSync {
+    fn is_sync(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> NotSync for &Probe<T> {}
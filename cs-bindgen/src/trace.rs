@@ -0,0 +1,57 @@
+//! Opt-in tracing of calls into the generated binding functions.
+//!
+//! When the `trace` feature is enabled, each binding function logs a record with the
+//! `log` crate when it's called and when it returns, including the time spent in the
+//! call. The records use the `cs_bindgen::trace` target at the `Trace` level, so they
+//! can be forwarded to C# with `RustLogger` alongside the records from the C# side
+//! of the bindings (see the `--trace` option of the CLI), or filtered out separately
+//! from the rest of the crate's logging.
+//!
+//! Panics are reported like any other return, since the guard is dropped after the
+//! panic has been caught. Without the feature, the guards are zero-sized and tracing
+//! does nothing.
+
+#[cfg(feature = "trace")]
+use std::time::Instant;
+
+/// The target used for the records logged for each call.
+pub const TARGET: &str = "cs_bindgen::trace";
+
+/// Traces a call to a binding function, logging its return when dropped.
+#[must_use = "the call is only traced until the guard is dropped"]
+pub struct TraceGuard {
+    #[cfg(feature = "trace")]
+    binding: &'static str,
+
+    #[cfg(feature = "trace")]
+    start: Instant,
+}
+
+/// Traces a call to the binding function named `binding` for the lifetime of the
+/// returned guard.
+#[cfg(feature = "trace")]
+pub fn enter(binding: &'static str) -> TraceGuard {
+    log::trace!(target: TARGET, "Calling {}", binding);
+    TraceGuard {
+        binding,
+        start: Instant::now(),
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+#[inline(always)]
+pub fn enter(_binding: &'static str) -> TraceGuard {
+    TraceGuard {}
+}
+
+#[cfg(feature = "trace")]
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        log::trace!(
+            target: TARGET,
+            "{} returned after {:?}",
+            self.binding,
+            self.start.elapsed(),
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Tests verifying that type aliases are described with the aliased type.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wind {
+    East,
+    South,
+    West,
+    North,
+}
+
+/// The index of a player's seat at the table.
+#[cs_bindgen]
+pub type Seat = u8;
+
+#[cs_bindgen(rename = "RoundWinds")]
+pub type WindList = Vec<Wind>;
+
+#[cs_bindgen]
+pub fn next_seat(seat: Seat) -> Seat {
+    (seat + 1) % 4
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn primitive_alias() {
+    let export = describe(unsafe { __cs_bindgen_describe__Seat() });
+    assert!(
+        export.starts_with(r#"{"Alias":{"name":"Seat","#),
+        "{}",
+        export
+    );
+    assert!(export.contains(r#""module":"alias""#), "{}", export);
+    assert!(export.contains(r#""rename":null"#), "{}", export);
+    assert!(
+        export.contains(r#""docs":"The index of a player's seat at the table.""#),
+        "{}",
+        export,
+    );
+    assert!(export.contains(r#""repr":"U8""#), "{}", export);
+
+    // Functions using the alias are described in terms of the aliased type.
+    let export = describe(unsafe { __cs_bindgen_describe__next_seat() });
+    assert!(export.contains(r#""output":"U8""#), "{}", export);
+}
+
+#[test]
+fn renamed_alias() {
+    let export = describe(unsafe { __cs_bindgen_describe__WindList() });
+    assert!(export.contains(r#""name":"WindList""#), "{}", export);
+    assert!(export.contains(r#""rename":"RoundWinds""#), "{}", export);
+    assert!(export.contains(r#""repr":{"Vec":{"Named":"#), "{}", export);
+}
@@ -0,0 +1,64 @@
+//! Tests verifying that memory can be allocated for Rust from outside of Rust, and
+//! that the allocator hooks can't replace an allocator that's already in use.
+
+use cs_bindgen::{
+    abi::{Abi, RawVec},
+    alloc::HostAllocator,
+    exports,
+};
+use pretty_assertions::assert_eq;
+
+#[global_allocator]
+static ALLOCATOR: HostAllocator = HostAllocator;
+
+unsafe extern "C" fn alloc_hook(_size: usize, _align: usize) -> *mut u8 {
+    panic!("The allocator hooks should never be used");
+}
+
+unsafe extern "C" fn free_hook(_ptr: *mut u8, _size: usize, _align: usize) {
+    panic!("The allocator hooks should never be used");
+}
+
+#[test]
+fn pass_allocated_buffer_to_rust() {
+    let tiles = [1u8, 2, 3, 5, 8];
+    let raw = unsafe {
+        let ptr = exports::__cs_bindgen_alloc(tiles.len(), 1);
+        ptr.copy_from_nonoverlapping(tiles.as_ptr(), tiles.len());
+        RawVec {
+            ptr,
+            len: tiles.len(),
+            capacity: tiles.len(),
+        }
+    };
+
+    let result: Vec<u8> = unsafe { Abi::from_abi(raw) };
+    assert_eq!(&tiles[..], &result[..]);
+}
+
+#[test]
+fn free_rust_buffer() {
+    let hand = vec![1u32, 2, 3];
+    let raw: RawVec<u32> = hand.into_abi();
+    unsafe {
+        exports::__cs_bindgen_free(
+            raw.ptr as *mut u8,
+            raw.capacity * std::mem::size_of::<u32>(),
+            std::mem::align_of::<u32>(),
+        );
+    }
+}
+
+#[test]
+fn invalid_alignment() {
+    let ptr = unsafe { exports::__cs_bindgen_alloc(4, 3) };
+    assert!(ptr.is_null());
+}
+
+#[test]
+fn hooks_rejected_after_allocation() {
+    // The test harness has already allocated memory with the global allocator, so the
+    // hooks have to be rejected.
+    let registered = unsafe { cs_bindgen::alloc::set_hooks(alloc_hook, free_hook) };
+    assert!(!registered);
+}
@@ -0,0 +1,36 @@
+//! Tests verifying that fixed-size arrays can be round-tripped through their raw
+//! representation.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructWithArrays {
+    pub scores: [i32; 4],
+    pub names: [String; 2],
+}
+
+#[test]
+fn primitive_array_round_trip() {
+    let original = [1_u8, 2, 3, 4, 5];
+    let result: [u8; 5] = unsafe { Abi::from_abi(original.into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn string_array_round_trip() {
+    let original = [String::from("East"), String::from("South")];
+    let result: [String; 2] = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn struct_array_round_trip() {
+    let original = StructWithArrays {
+        scores: [25_000, 25_000, 30_000, 20_000],
+        names: [String::from("Alice"), String::from("Bob")],
+    };
+    let result: StructWithArrays = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
@@ -0,0 +1,104 @@
+//! Tests verifying that async functions complete through their raw completion
+//! callback.
+
+use cs_bindgen::{
+    abi::{RawCompletion, RawString},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{
+    ffi::c_void,
+    ptr,
+    sync::mpsc::{self, Sender},
+    time::Duration,
+};
+
+#[cs_bindgen]
+pub async fn double_async(value: u32) -> u32 {
+    value * 2
+}
+
+#[cs_bindgen]
+pub async fn greet_async(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[cs_bindgen]
+pub async fn panic_async() -> u32 {
+    panic!("Oh no");
+}
+
+/// The result received by a completion callback, which is either the raw result or
+/// the panic message.
+type Completed<T> = Result<T, String>;
+
+unsafe extern "C" fn complete_u32(context: *mut c_void, result: u32, panic: RawString) {
+    let sender = Box::from_raw(context as *mut Sender<Completed<u32>>);
+    sender.send(completed(result, panic)).unwrap();
+}
+
+unsafe extern "C" fn complete_string(context: *mut c_void, result: RawString, panic: RawString) {
+    let sender = Box::from_raw(context as *mut Sender<Completed<String>>);
+    let result = completed(result, panic).map(|result| result.into_string());
+    sender.send(result).unwrap();
+}
+
+unsafe fn completed<T>(result: T, panic: RawString) -> Completed<T> {
+    if panic.ptr.is_null() {
+        Ok(result)
+    } else {
+        Err(panic.into_string())
+    }
+}
+
+fn completion<T>(func: *const c_void) -> (RawCompletion, mpsc::Receiver<Completed<T>>) {
+    let (sender, receiver) = mpsc::channel();
+    let completion = RawCompletion {
+        func,
+        context: Box::into_raw(Box::new(sender)) as *mut c_void,
+    };
+
+    (completion, receiver)
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn completes_with_result() {
+    let (completion, receiver) = completion(complete_u32 as *const c_void);
+    unsafe {
+        __cs_bindgen_generated__double_async(21, completion, ptr::null_mut());
+    }
+
+    assert_eq!(Ok(42), receiver.recv_timeout(TIMEOUT).unwrap());
+}
+
+#[test]
+fn completes_with_owned_result() {
+    let (completion, receiver) = completion(complete_string as *const c_void);
+    unsafe {
+        __cs_bindgen_generated__greet_async(
+            String::from("Riichi").into(),
+            completion,
+            ptr::null_mut(),
+        );
+    }
+
+    assert_eq!(
+        Ok(String::from("Hello, Riichi!")),
+        receiver.recv_timeout(TIMEOUT).unwrap()
+    );
+}
+
+#[test]
+fn panic_completes_with_message() {
+    let (completion, receiver) = completion(complete_u32 as *const c_void);
+    unsafe {
+        __cs_bindgen_generated__panic_async(completion, ptr::null_mut());
+    }
+
+    assert_eq!(
+        Err(String::from("Oh no")),
+        receiver.recv_timeout(TIMEOUT).unwrap()
+    );
+}
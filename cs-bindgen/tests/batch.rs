@@ -0,0 +1,114 @@
+//! Tests verifying that batched functions can be run from a command buffer.
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    batch::RawCommandFn,
+    exports::__cs_bindgen_submit_commands,
+    prelude::*,
+};
+use std::{
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static TOTAL_POINTS: AtomicU64 = AtomicU64::new(0);
+
+#[cs_bindgen(batch)]
+pub fn add_points(points: u32, doubled: bool) {
+    let points = if doubled { points * 2 } else { points };
+    TOTAL_POINTS.fetch_add(points as u64, Ordering::SeqCst);
+}
+
+#[cs_bindgen]
+pub struct River {
+    tiles: Vec<(u8, bool)>,
+}
+
+#[cs_bindgen]
+impl River {
+    pub fn new() -> Self {
+        River { tiles: Vec::new() }
+    }
+
+    #[cs_bindgen(batch)]
+    pub fn push_discard(&mut self, tile: u8, tsumogiri: bool) {
+        assert!(tile < 34, "Invalid tile {}", tile);
+        self.tiles.push((tile, tsumogiri));
+    }
+}
+
+fn empty_panic() -> RawString {
+    unsafe { MaybeUninit::<RawString>::zeroed().assume_init() }
+}
+
+/// Appends a command to `buffer`, encoded the same way as the C# `CommandBuffer`.
+fn push_command(buffer: &mut Vec<u8>, command: RawCommandFn, args: &[&[u8]]) {
+    buffer.extend_from_slice(&(command as usize).to_ne_bytes());
+    for arg in args {
+        buffer.extend_from_slice(arg);
+    }
+}
+
+#[test]
+fn describe_command() {
+    let export = unsafe { (*__cs_bindgen_describe__add_points()).into_string() };
+    assert!(
+        export.contains(r#""command":"__cs_bindgen_command__add_points""#),
+        "{}",
+        export
+    );
+
+    let export = unsafe { (*__cs_bindgen_describe__new__River()).into_string() };
+    assert!(export.contains(r#""command":null"#), "{}", export);
+}
+
+#[test]
+fn submit_fn_commands() {
+    let command = unsafe { __cs_bindgen_command__add_points() };
+    let mut buffer = Vec::new();
+    push_command(&mut buffer, command, &[&100_u32.to_ne_bytes(), &[0]]);
+    push_command(&mut buffer, command, &[&250_u32.to_ne_bytes(), &[1]]);
+
+    let mut panic = empty_panic();
+    let completed = unsafe { __cs_bindgen_submit_commands(buffer.as_slice().into(), &mut panic) };
+    assert!(panic.ptr.is_null());
+    assert_eq!(2, completed);
+    assert_eq!(600, TOTAL_POINTS.load(Ordering::SeqCst));
+}
+
+#[test]
+fn submit_method_commands() {
+    let river = River::new().into_abi();
+    let handle = (river as usize).to_ne_bytes();
+    let command = unsafe { __cs_bindgen_command__push_discard__River() };
+
+    // Each command is 18 bytes long, so every command after the first is misaligned
+    // within the buffer.
+    let mut buffer = Vec::new();
+    push_command(&mut buffer, command, &[&handle, &[5], &[1]]);
+    push_command(&mut buffer, command, &[&handle, &[33], &[0]]);
+    push_command(&mut buffer, command, &[&handle, &[40], &[0]]);
+    push_command(&mut buffer, command, &[&handle, &[7], &[0]]);
+
+    // Commands after the one that panics are skipped.
+    let mut panic = empty_panic();
+    let completed = unsafe { __cs_bindgen_submit_commands(buffer.as_slice().into(), &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert_eq!(2, completed);
+    assert!(message.contains("Invalid tile 40"), "{}", message);
+    assert_eq!(
+        vec![(5, true), (33, false)],
+        unsafe { &(*river).tiles }.clone()
+    );
+
+    unsafe { __cs_bindgen_drop__River(river) };
+}
+
+#[test]
+fn submit_empty_buffer() {
+    let mut panic = empty_panic();
+    let completed =
+        unsafe { __cs_bindgen_submit_commands(Vec::<u8>::new().as_slice().into(), &mut panic) };
+    assert!(panic.ptr.is_null());
+    assert_eq!(0, completed);
+}
@@ -0,0 +1,40 @@
+//! Tests verifying that types marshaled by value export the size of their raw
+//! representation, which is used to check the layout of structs passed directly to
+//! Rust.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::mem;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileCounts {
+    pub man: u8,
+    pub pin: u8,
+    pub sou: u8,
+    pub honors: u16,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandShape {
+    pub counts: TileCounts,
+    pub shanten: i8,
+    pub ukeire: f32,
+}
+
+#[test]
+fn struct_size() {
+    assert_eq!(6, unsafe { __cs_bindgen_generated_size_of_TileCounts() });
+    let size = unsafe { __cs_bindgen_generated_size_of_HandShape() };
+    assert_eq!(mem::size_of::<<HandShape as Abi>::Abi>(), size);
+}
+
+#[test]
+fn describe_size_fn() {
+    let export = unsafe { (*__cs_bindgen_describe__HandShape()).into_string() };
+    assert!(
+        export.contains(r#""size_fn":"__cs_bindgen_generated_size_of_HandShape""#),
+        "{}",
+        export,
+    );
+}
@@ -0,0 +1,100 @@
+//! Tests verifying that aliased borrows of handles are reported as panics when the
+//! `borrow-checks` feature is enabled.
+
+#![cfg(feature = "borrow-checks")]
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    borrow,
+    prelude::*,
+};
+use std::{mem::MaybeUninit, ptr};
+
+#[cs_bindgen]
+pub struct Hand {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn new() -> Self {
+        Hand { tiles: Vec::new() }
+    }
+
+    pub fn len(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    pub fn draw(&mut self, tile: u8) {
+        self.tiles.push(tile);
+    }
+}
+
+#[cs_bindgen]
+pub fn pass_tile(from: &mut Hand, to: &mut Hand) {
+    if let Some(tile) = from.tiles.pop() {
+        to.tiles.push(tile);
+    }
+}
+
+fn empty_panic() -> RawString {
+    unsafe { MaybeUninit::<RawString>::zeroed().assume_init() }
+}
+
+#[test]
+fn reentrant_mut_call() {
+    let hand = Hand::new().into_abi();
+
+    // Simulate a C# callback calling back into the handle while a `&mut self` method is
+    // running.
+    let guard = borrow::borrow_mut(hand);
+    let mut panic = empty_panic();
+    unsafe { __cs_bindgen_generated__draw__Hand(hand, 1, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(message.contains("::Hand`"), "{}", message);
+    assert!(message.contains("re-entrant or aliased"), "{}", message);
+
+    let mut panic = empty_panic();
+    let len = unsafe { __cs_bindgen_generated__len__Hand(hand, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert_eq!(0, len);
+    assert!(message.contains("already mutably borrowed"), "{}", message);
+
+    // Once the borrow is released the handle can be used normally again.
+    drop(guard);
+    unsafe {
+        __cs_bindgen_generated__draw__Hand(hand, 1, ptr::null_mut());
+        assert_eq!(1, __cs_bindgen_generated__len__Hand(hand, ptr::null_mut()));
+        __cs_bindgen_drop__Hand(hand);
+    }
+}
+
+#[test]
+fn shared_borrows_allowed() {
+    let hand = Hand::new().into_abi();
+
+    let guard = borrow::borrow(hand);
+    let len = unsafe { __cs_bindgen_generated__len__Hand(hand, ptr::null_mut()) };
+    assert_eq!(0, len);
+    drop(guard);
+
+    unsafe { __cs_bindgen_drop__Hand(hand) };
+}
+
+#[test]
+fn aliased_mut_args() {
+    let first = Hand { tiles: vec![1] }.into_abi();
+    let second = Hand::new().into_abi();
+
+    let mut panic = empty_panic();
+    unsafe { __cs_bindgen_generated__pass_tile(first, first, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(message.contains("re-entrant or aliased"), "{}", message);
+
+    // The failed call released the borrow of the first argument.
+    unsafe { __cs_bindgen_generated__pass_tile(first, second, ptr::null_mut()) };
+    let first = unsafe { Hand::from_abi(first) };
+    let second = unsafe { Hand::from_abi(second) };
+    assert!(first.tiles.is_empty());
+    assert_eq!(vec![1], second.tiles);
+}
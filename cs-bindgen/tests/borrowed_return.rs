@@ -0,0 +1,83 @@
+//! Tests verifying that methods can return references that borrow from `self`.
+
+use cs_bindgen::{abi::Abi, prelude::*, shared::Repr};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    pub suit: u8,
+    pub value: u8,
+}
+
+#[cs_bindgen]
+pub struct Hand {
+    tiles: Vec<Tile>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn dealt() -> Self {
+        Hand {
+            tiles: vec![Tile { suit: 0, value: 1 }, Tile { suit: 2, value: 9 }],
+        }
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    pub fn values(&self) -> &[u8] {
+        &[1, 9]
+    }
+}
+
+#[cs_bindgen]
+pub struct Player {
+    hand: Hand,
+}
+
+#[cs_bindgen]
+impl Player {
+    pub fn seated() -> Self {
+        Player {
+            hand: Hand::dealt(),
+        }
+    }
+
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+}
+
+#[test]
+fn borrowed_slice() {
+    let hand = Hand::dealt();
+    let raw = unsafe {
+        __cs_bindgen_generated__tiles__Hand(<&Hand as Abi>::into_abi(&hand), ptr::null_mut())
+    };
+
+    // The slice points directly into the `Vec` owned by the hand.
+    assert_eq!(hand.tiles.as_ptr(), raw.ptr);
+    assert_eq!(&hand.tiles[..], unsafe { raw.as_slice() });
+}
+
+#[test]
+fn borrowed_handle() {
+    let player = Player::seated();
+    let raw = unsafe {
+        __cs_bindgen_generated__hand__Player(<&Player as Abi>::into_abi(&player), ptr::null_mut())
+    };
+    assert_eq!(&player.hand as *const Hand, raw);
+}
+
+#[test]
+fn borrowed_reprs() {
+    assert_eq!(
+        Repr::Slice(Box::new(Tile::repr())),
+        <&[Tile] as Abi>::repr(),
+    );
+    assert_eq!(Repr::Slice(Box::new(Repr::U8)), <&[u8] as Abi>::repr());
+    assert_eq!(Repr::Ref(Box::new(Hand::repr())), <&Hand as Abi>::repr());
+}
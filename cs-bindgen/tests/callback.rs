@@ -0,0 +1,54 @@
+//! Tests verifying that callbacks are invoked correctly through their raw
+//! representation.
+
+use cs_bindgen::abi::{RawCallback, RawString};
+use pretty_assertions::assert_eq;
+use std::{ffi::c_void, panic, ptr};
+
+unsafe extern "C" fn add(context: *mut c_void, left: i32, right: i32, _threw: *mut u8) -> i32 {
+    let offset = *(context as *const i32);
+    left + right + offset
+}
+
+unsafe extern "C" fn string_len(_context: *mut c_void, value: RawString, _threw: *mut u8) -> u32 {
+    value.into_string().len() as u32
+}
+
+unsafe extern "C" fn throws(_context: *mut c_void, threw: *mut u8) -> u8 {
+    *threw = 1;
+    0
+}
+
+#[test]
+fn call_with_context() {
+    let mut offset = 10_i32;
+    let callback = RawCallback {
+        func: add as *const c_void,
+        context: &mut offset as *mut i32 as *mut c_void,
+    };
+
+    let result: i32 = unsafe { callback.call2(1_i32, 2_i32) };
+    assert_eq!(13, result);
+}
+
+#[test]
+fn call_with_owned_argument() {
+    let callback = RawCallback {
+        func: string_len as *const c_void,
+        context: ptr::null_mut(),
+    };
+
+    let result: u32 = unsafe { callback.call1(String::from("Riichi")) };
+    assert_eq!(6, result);
+}
+
+#[test]
+fn exception_panics() {
+    let callback = RawCallback {
+        func: throws as *const c_void,
+        context: ptr::null_mut(),
+    };
+
+    let result = panic::catch_unwind(move || unsafe { callback.call0::<()>() });
+    assert!(result.is_err());
+}
@@ -0,0 +1,32 @@
+//! Tests verifying that a cancellation flag created by C# is shared with the Rust code
+//! it's passed to.
+
+use cs_bindgen::{abi::Abi, cancel::CancelFlag, exports};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn cancel_after_conversion() {
+    unsafe {
+        let raw = exports::__cs_bindgen_cancel_flag_new();
+        let flag = CancelFlag::from_abi(raw);
+        assert!(!flag.is_cancelled());
+
+        exports::__cs_bindgen_cancel_flag_cancel(raw);
+        assert!(flag.is_cancelled());
+
+        exports::__cs_bindgen_cancel_flag_drop(raw);
+    }
+}
+
+#[test]
+fn flag_outlives_caller_reference() {
+    let flag = unsafe {
+        let raw = exports::__cs_bindgen_cancel_flag_new();
+        let flag = CancelFlag::from_abi(raw);
+        exports::__cs_bindgen_cancel_flag_cancel(raw);
+        exports::__cs_bindgen_cancel_flag_drop(raw);
+        flag
+    };
+
+    assert_eq!(Err(cs_bindgen::cancel::Cancelled), flag.check());
+}
@@ -0,0 +1,63 @@
+//! Tests verifying that `chrono` types are passed using the same raw representations
+//! as their .NET counterparts.
+
+#![cfg(feature = "chrono")]
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+pub fn next_match_day(date: NaiveDate) -> NaiveDate {
+    date.succ()
+}
+
+#[cs_bindgen]
+pub fn shorten_turn_timer(remaining: Duration) -> Duration {
+    remaining - Duration::seconds(5)
+}
+
+#[test]
+fn duration_as_ticks() {
+    assert_eq!(15_000_000, Duration::milliseconds(1500).into_abi());
+
+    // Unlike a std `Duration`, negative durations can be passed.
+    assert_eq!(-15_000_000, Duration::milliseconds(-1500).into_abi());
+    assert_eq!(Duration::milliseconds(-1500), unsafe {
+        Duration::from_abi(-15_000_000)
+    });
+
+    // Precision finer than a tick is truncated.
+    assert_eq!(1, Duration::nanoseconds(199).into_abi());
+
+    let result = unsafe { __cs_bindgen_generated__shorten_turn_timer(20_000_000, ptr::null_mut()) };
+    assert_eq!(-30_000_000, result);
+}
+
+#[test]
+fn date_time_as_ticks_since_epoch() {
+    let time = Utc.timestamp_opt(2, 0).unwrap();
+    assert_eq!(20_000_000, time.into_abi());
+    assert_eq!(time, unsafe { Abi::from_abi(20_000_000) });
+
+    // The sub-second part is added to the preceding second for times before the epoch.
+    let before_epoch = Utc.timestamp_opt(-1, 500_000_000).unwrap();
+    assert_eq!(-5_000_000, before_epoch.into_abi());
+    assert_eq!(before_epoch, unsafe { Abi::from_abi(-5_000_000) });
+}
+
+#[test]
+fn date_as_day_number() {
+    let first_day = NaiveDate::from_ymd(1, 1, 1);
+    assert_eq!(0, first_day.into_abi());
+    assert_eq!(first_day, unsafe { NaiveDate::from_abi(0) });
+
+    let epoch = NaiveDate::from_ymd(1970, 1, 1);
+    assert_eq!(719_162, epoch.into_abi());
+
+    let result = unsafe { __cs_bindgen_generated__next_match_day(719_162, ptr::null_mut()) };
+    assert_eq!(NaiveDate::from_ymd(1970, 1, 2), unsafe {
+        NaiveDate::from_abi(result)
+    });
+}
@@ -0,0 +1,29 @@
+//! Tests verifying that the `Clone` impl for handle types is exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameState {
+    turn: u32,
+    discards: Vec<String>,
+}
+
+#[test]
+fn clone_handle() {
+    let state = GameState {
+        turn: 12,
+        discards: vec!["5s".into(), "north".into()],
+    };
+
+    let clone = unsafe {
+        __cs_bindgen_generated_clone__GameState(
+            <&GameState as Abi>::into_abi(&state),
+            ptr::null_mut(),
+        )
+    };
+    let clone = unsafe { <GameState as Abi>::from_abi(clone) };
+    assert_eq!(state, clone);
+}
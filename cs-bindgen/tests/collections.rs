@@ -1,4 +1,7 @@
-use cs_bindgen::{abi::Abi, prelude::*};
+use cs_bindgen::{
+    abi::{Abi, RawSlice},
+    prelude::*,
+};
 use pretty_assertions::assert_eq;
 
 #[cs_bindgen]
@@ -51,6 +54,28 @@ fn string_vec_round_trip() {
     assert_eq!(original, result);
 }
 
+#[test]
+fn nested_vec_round_trip() {
+    let original: Vec<Vec<String>> =
+        vec![vec!["foo".into(), "bar".into()], vec![], vec!["baz".into()]];
+    let result: Vec<Vec<String>> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn drop_borrowed_nested_vec() {
+    let original: Vec<Vec<String>> = vec![vec!["foo".into(), "bar".into()]];
+    let slice: RawSlice<Vec<String>> = original.as_slice().into();
+
+    // Converting an element borrows the nested vec, so dropping it must not free the
+    // data that's still owned by the outer vec.
+    let element = unsafe { slice.convert_element(0) };
+    assert_eq!(0, element.capacity);
+    unsafe { cs_bindgen::abi::drop_vec(element) };
+
+    assert_eq!(vec![vec!["foo".to_string(), "bar".to_string()]], original);
+}
+
 #[test]
 fn int_array_round_trip() {
     let original: [u32; 4] = [1, 2, 3, 4];
@@ -71,3 +96,33 @@ fn string_array_round_trip() {
     let result: [String; 3] = unsafe { Abi::from_abi(original.clone().into_abi()) };
     assert_eq!(original, result);
 }
+
+#[cs_bindgen]
+pub fn sum_slice(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+#[cs_bindgen]
+pub fn count_structs(values: &[StructWithArrays]) -> usize {
+    values.len()
+}
+
+#[test]
+fn primitive_slice_borrow() {
+    let original: &[u32] = &[1, 2, 3];
+    let result: &[u32] = unsafe { Abi::from_abi(original.as_abi()) };
+    assert_eq!(original.as_ptr(), result.as_ptr());
+    assert_eq!(original, result);
+}
+
+#[test]
+fn string_slice_convert() {
+    let original: Vec<String> = vec!["foo".into(), "bar".into()];
+    let raw = original
+        .clone()
+        .into_iter()
+        .map(Abi::into_abi)
+        .collect::<Vec<_>>();
+    let result: Vec<String> = unsafe { cs_bindgen::abi::convert_slice(raw.as_slice().into()) };
+    assert_eq!(original, result);
+}
@@ -0,0 +1,81 @@
+//! Tests verifying that `const` and `static` items are exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+pub const MAX_HAND_SIZE: usize = 14;
+
+#[cs_bindgen]
+pub static TILESET_SIZE: u32 = 136;
+
+#[cs_bindgen]
+pub const RULESET_NAME: &str = "Tenhou";
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dora {
+    pub suit: u8,
+    pub value: u8,
+}
+
+#[cs_bindgen]
+pub const FIRST_DORA: Dora = Dora { suit: 0, value: 5 };
+
+#[test]
+fn primitive_const() {
+    let value = unsafe { __cs_bindgen_generated__MAX_HAND_SIZE(ptr::null_mut()) };
+    assert_eq!(14, value);
+}
+
+#[test]
+fn primitive_static() {
+    let value = unsafe { __cs_bindgen_generated__TILESET_SIZE(ptr::null_mut()) };
+    assert_eq!(136, value);
+}
+
+#[test]
+fn string_const() {
+    let value = unsafe { __cs_bindgen_generated__RULESET_NAME(ptr::null_mut()) };
+    let value = unsafe { <&str as Abi>::from_abi(value) };
+    assert_eq!("Tenhou", value);
+}
+
+#[test]
+fn struct_const() {
+    let value = unsafe { __cs_bindgen_generated__FIRST_DORA(ptr::null_mut()) };
+    let value = unsafe { Dora::from_abi(value) };
+    assert_eq!(FIRST_DORA, value);
+}
+
+#[test]
+fn describe_includes_literal_value() {
+    let export = unsafe { (*__cs_bindgen_describe__MAX_HAND_SIZE()).into_string() };
+    assert!(export.contains(r#""value":{"UInt":14}"#), "{}", export);
+
+    let export = unsafe { (*__cs_bindgen_describe__FIRST_DORA()).into_string() };
+    assert!(export.contains(r#""value":null"#), "{}", export);
+}
+
+#[cs_bindgen]
+impl Dora {
+    pub const RED_FIVE: Self = Dora { suit: 1, value: 5 };
+    pub const MAX_VALUE: u8 = 9;
+}
+
+#[test]
+fn assoc_struct_const() {
+    let value = unsafe { __cs_bindgen_generated__RED_FIVE__Dora(ptr::null_mut()) };
+    let value = unsafe { Dora::from_abi(value) };
+    assert_eq!(Dora::RED_FIVE, value);
+}
+
+#[test]
+fn assoc_primitive_const() {
+    let value = unsafe { __cs_bindgen_generated__MAX_VALUE__Dora(ptr::null_mut()) };
+    assert_eq!(9, value);
+
+    let export = unsafe { (*__cs_bindgen_describe__MAX_VALUE__Dora()).into_string() };
+    assert!(export.contains(r#""self_type":{"#), "{}", export);
+}
@@ -0,0 +1,63 @@
+//! Tests verifying that methods using `Self` in their signature can be exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[derive(Default)]
+pub struct Hand {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn new() -> Self {
+        Self { tiles: Vec::new() }
+    }
+
+    #[cs_bindgen(constructor)]
+    pub fn from_tiles(tiles: Vec<u8>) -> Self {
+        Self { tiles }
+    }
+
+    pub fn merged(&self, other: &Self) -> Self {
+        let mut tiles = self.tiles.clone();
+        tiles.extend(&other.tiles);
+        Self { tiles }
+    }
+
+    pub fn tile_count(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+}
+
+#[test]
+fn self_in_signature() {
+    let first = Hand::from_tiles(vec![1, 2, 3]);
+    let second = Hand::from_tiles(vec![4, 5]);
+
+    let merged = unsafe {
+        __cs_bindgen_generated__merged__Hand(
+            <&Hand as Abi>::into_abi(&first),
+            <&Hand as Abi>::into_abi(&second),
+            ptr::null_mut(),
+        )
+    };
+    let merged = unsafe { Hand::from_abi(merged) };
+    assert_eq!(5, merged.tile_count());
+}
+
+#[test]
+fn constructor_bindings() {
+    let empty = unsafe { Hand::from_abi(__cs_bindgen_generated__new__Hand(ptr::null_mut())) };
+    assert_eq!(0, empty.tile_count());
+
+    let hand = unsafe {
+        Hand::from_abi(__cs_bindgen_generated__from_tiles__Hand(
+            vec![1_u8, 2].into_abi(),
+            ptr::null_mut(),
+        ))
+    };
+    assert_eq!(2, hand.tile_count());
+}
@@ -0,0 +1,50 @@
+//! Tests verifying the bindings generated for `From` and `TryFrom` impls.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{convert::TryFrom, ptr};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wind {
+    pub index: u8,
+}
+
+#[cs_bindgen]
+impl TryFrom<u32> for Wind {
+    type Error = String;
+
+    fn try_from(index: u32) -> Result<Self, String> {
+        if index < 4 {
+            Ok(Wind { index: index as u8 })
+        } else {
+            Err(format!("{} isn't a wind", index))
+        }
+    }
+}
+
+#[cs_bindgen]
+impl From<Wind> for u32 {
+    fn from(wind: Wind) -> u32 {
+        wind.index as u32
+    }
+}
+
+#[test]
+fn try_from_binding() {
+    let raw = unsafe { __cs_bindgen_generated_impl_TryFrom_u32__Wind(2, ptr::null_mut()) };
+    let wind: Option<Wind> = unsafe { Abi::from_abi(raw) };
+    assert_eq!(Some(Wind { index: 2 }), wind);
+
+    // The error is discarded, and the failed conversion is reported as `None`.
+    let raw = unsafe { __cs_bindgen_generated_impl_TryFrom_u32__Wind(4, ptr::null_mut()) };
+    let wind: Option<Wind> = unsafe { Abi::from_abi(raw) };
+    assert_eq!(None, wind);
+}
+
+#[test]
+fn from_binding() {
+    let raw = Wind { index: 3 }.into_abi();
+    let index = unsafe { __cs_bindgen_generated_impl_From_Wind__u32(raw, ptr::null_mut()) };
+    assert_eq!(3, index);
+}
@@ -0,0 +1,111 @@
+//! Tests verifying that types exported with custom conversion functions are passed
+//! across the FFI boundary as their raw type.
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{mem::MaybeUninit, ptr};
+
+#[cs_bindgen(
+    raw = "String",
+    into_raw_with = "GameId::to_hex",
+    from_raw_with = "GameId::from_hex",
+    converter = "GameIdConverter",
+    cs_type = "System.Guid"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameId(u128);
+
+impl GameId {
+    fn to_hex(&self) -> String {
+        format!("{:032x}", self.0)
+    }
+
+    fn from_hex(raw: String) -> Self {
+        GameId(u128::from_str_radix(&raw, 16).expect("Invalid game ID"))
+    }
+}
+
+#[cs_bindgen(
+    raw = "u8",
+    into_raw_with = "Seat::index",
+    from_raw_with = "Seat::from_index",
+    converter = "SeatConverter"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seat {
+    Dealer,
+    NonDealer(u8),
+}
+
+impl Seat {
+    fn index(&self) -> u8 {
+        match self {
+            Seat::Dealer => 0,
+            Seat::NonDealer(index) => *index,
+        }
+    }
+
+    fn from_index(index: u8) -> Self {
+        match index {
+            0 => Seat::Dealer,
+            index => Seat::NonDealer(index),
+        }
+    }
+}
+
+#[cs_bindgen]
+pub fn next_game_id(id: GameId) -> GameId {
+    GameId(id.0 + 1)
+}
+
+#[test]
+fn converted_to_raw() {
+    let raw = GameId(0xabc).into_abi();
+    let hex = unsafe { raw.into_string() };
+    assert_eq!("00000000000000000000000000000abc", hex);
+
+    assert_eq!(2, Seat::NonDealer(2).into_abi());
+    assert_eq!(Seat::Dealer, unsafe { Seat::from_abi(0) });
+}
+
+#[test]
+fn round_trip() {
+    let raw =
+        unsafe { __cs_bindgen_generated__next_game_id(GameId(41).into_abi(), ptr::null_mut()) };
+    let result = unsafe { GameId::from_abi(raw) };
+    assert_eq!(GameId(42), result);
+}
+
+#[test]
+fn invalid_raw_value_panics() {
+    let raw = "not hex".to_string().into_abi();
+
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__next_game_id(raw, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(message.starts_with("Invalid game ID"), "{}", message);
+}
+
+#[test]
+fn describe_custom_type() {
+    let export = unsafe { (*__cs_bindgen_describe__GameId()).into_string() };
+    assert!(
+        export.contains(
+            r#""binding_style":{"Custom":{"raw":"String","converter":"GameIdConverter","cs_type":"System.Guid"}}"#
+        ),
+        "{}",
+        export,
+    );
+
+    let export = unsafe { (*__cs_bindgen_describe__Seat()).into_string() };
+    assert!(
+        export.contains(
+            r#""binding_style":{"Custom":{"raw":"U8","converter":"SeatConverter","cs_type":null}}"#
+        ),
+        "{}",
+        export,
+    );
+}
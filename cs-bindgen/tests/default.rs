@@ -0,0 +1,37 @@
+//! Tests verifying that the `Default` impl for exported types is exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[derive(Debug, Default)]
+pub struct Discards {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen(default)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Points {
+    pub value: i32,
+}
+
+impl Default for Points {
+    fn default() -> Self {
+        Points { value: 25_000 }
+    }
+}
+
+#[test]
+fn derived_default_handle() {
+    let raw = unsafe { __cs_bindgen_generated_default__Discards(ptr::null_mut()) };
+    let discards = unsafe { Discards::from_abi(raw) };
+    assert!(discards.tiles.is_empty());
+}
+
+#[test]
+fn manual_default_value() {
+    let raw = unsafe { __cs_bindgen_generated_default__Points(ptr::null_mut()) };
+    let points = unsafe { Points::from_abi(raw) };
+    assert_eq!(Points { value: 25_000 }, points);
+}
@@ -0,0 +1,76 @@
+//! Tests verifying that handles passed to C# are counted when the `diagnostics`
+//! feature is enabled.
+//!
+//! The counts are global, so each test uses its own types.
+
+#![cfg(feature = "diagnostics")]
+
+use cs_bindgen::{abi::Abi, diagnostics, prelude::*};
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+#[cs_bindgen]
+pub struct Wall {
+    pub remaining: u32,
+}
+
+#[cs_bindgen]
+pub struct Dice {
+    pub value: u8,
+}
+
+#[cs_bindgen(shared)]
+pub struct Table {
+    pub seats: u32,
+}
+
+/// Returns the `(live, created)` counts for the type named `name`.
+fn counts(name: &str) -> (u64, u64) {
+    diagnostics::live_handles()
+        .into_iter()
+        .find(|count| count.type_name.ends_with(name))
+        .map(|count| (count.live, count.created))
+        .unwrap_or_default()
+}
+
+#[test]
+fn count_handles() {
+    let first = Wall { remaining: 70 }.into_abi();
+    let second = Wall { remaining: 14 }.into_abi();
+    assert_eq!((2, 2), counts("::Wall"));
+
+    unsafe { __cs_bindgen_drop__Wall(first) };
+    assert_eq!((1, 2), counts("::Wall"));
+
+    // Taking ownership of the value also releases the handle.
+    let wall = unsafe { Wall::from_abi(second) };
+    assert_eq!(14, wall.remaining);
+    assert_eq!((0, 2), counts("::Wall"));
+}
+
+#[test]
+fn count_shared_handles() {
+    let table = Arc::new(Table { seats: 4 });
+    let first = table.clone().into_abi();
+    let second = table.into_abi();
+    assert_eq!((2, 2), counts("::Table"));
+
+    unsafe { __cs_bindgen_drop__Table(first) };
+    unsafe { __cs_bindgen_drop__Table(second) };
+    assert_eq!((0, 2), counts("::Table"));
+}
+
+#[test]
+fn report_format() {
+    let handle = Dice { value: 6 }.into_abi();
+    let report = diagnostics::report();
+    assert!(
+        report
+            .lines()
+            .any(|line| line.contains("::Dice\t") && line.split('\t').count() == 3),
+        "{}",
+        report,
+    );
+
+    unsafe { __cs_bindgen_drop__Dice(handle) };
+}
@@ -0,0 +1,48 @@
+//! Tests verifying that the `Display` impl for exported types is exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{fmt, ptr};
+
+#[cs_bindgen(display)]
+pub struct Wall {
+    remaining: u32,
+}
+
+impl fmt::Display for Wall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Wall ({} tiles remaining)", self.remaining)
+    }
+}
+
+#[cs_bindgen(display)]
+#[derive(Debug, Clone, Copy)]
+pub struct Dice {
+    pub first: u8,
+    pub second: u8,
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}", self.first, self.second)
+    }
+}
+
+#[test]
+fn display_handle() {
+    let wall = Wall { remaining: 70 };
+    let result = unsafe {
+        __cs_bindgen_generated_display__Wall(<&Wall as Abi>::into_abi(&wall), ptr::null_mut())
+    };
+    assert_eq!("Wall (70 tiles remaining)", unsafe { result.into_string() });
+}
+
+#[test]
+fn display_value() {
+    let dice = Dice {
+        first: 3,
+        second: 5,
+    };
+    let result = unsafe { __cs_bindgen_generated_display__Dice(dice.into_abi(), ptr::null_mut()) };
+    assert_eq!("3 + 5", unsafe { result.into_string() });
+}
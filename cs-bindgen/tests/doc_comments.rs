@@ -0,0 +1,112 @@
+//! Tests verifying that doc comments are included in the descriptions of exported
+//! items.
+
+use cs_bindgen::prelude::*;
+
+/// Returns the number of tiles in a full set.
+#[cs_bindgen]
+pub fn tile_count() -> u32 {
+    136
+}
+
+/// The dora indicator for a round.
+///
+/// Flipped from the dead wall at the start of the round.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Indicator {
+    pub value: u8,
+}
+
+/// A player's hand.
+#[cs_bindgen]
+#[derive(Default)]
+pub struct Hand {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of tiles in the hand.
+    pub fn len(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
+
+/// The seat winds.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum Wind {
+    /// The dealer's seat.
+    East,
+    South,
+    /// Uses `<` and `&` in its docs.
+    West,
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn fn_docs() {
+    let export = describe(unsafe { __cs_bindgen_describe__tile_count() });
+    assert!(
+        export.contains(r#""docs":"Returns the number of tiles in a full set.""#),
+        "{}",
+        export
+    );
+}
+
+#[test]
+fn multi_line_docs() {
+    let export = describe(unsafe { __cs_bindgen_describe__Indicator() });
+    assert!(
+        export.contains(
+            r#""docs":"The dora indicator for a round.\n\nFlipped from the dead wall at the start of the round.""#
+        ),
+        "{}",
+        export
+    );
+}
+
+#[test]
+fn method_docs() {
+    let export = describe(unsafe { __cs_bindgen_describe__Hand() });
+    assert!(
+        export.contains(r#""docs":"A player's hand.""#),
+        "{}",
+        export
+    );
+
+    let export = describe(unsafe { __cs_bindgen_describe__len__Hand() });
+    assert!(
+        export.contains(r#""docs":"The number of tiles in the hand.""#),
+        "{}",
+        export
+    );
+
+    let export = describe(unsafe { __cs_bindgen_describe__is_empty__Hand() });
+    assert!(export.contains(r#""docs":null"#), "{}", export);
+}
+
+#[test]
+fn variant_docs() {
+    let export = describe(unsafe { __cs_bindgen_describe__Wind() });
+    assert!(export.contains(r#""docs":"The seat winds.""#), "{}", export);
+    assert!(
+        export.contains(
+            r#""variant_docs":["The dealer's seat.",null,"Uses `<` and `&` in its docs."]"#
+        ),
+        "{}",
+        export
+    );
+}
@@ -0,0 +1,94 @@
+//! Tests verifying that the `PartialEq` and `Hash` impls for exported types are
+//! exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ptr,
+};
+
+#[cs_bindgen]
+#[derive(PartialEq, Eq, Hash)]
+pub struct Meld {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen(eq)]
+#[derive(Debug, Clone, Copy)]
+pub struct Wind {
+    pub seat: u8,
+    pub round: u8,
+}
+
+// Winds are compared by seat only, in order to verify that the custom impl is used.
+impl PartialEq for Wind {
+    fn eq(&self, other: &Self) -> bool {
+        self.seat == other.seat
+    }
+}
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn eq_handle() {
+    let first = Meld {
+        tiles: vec![1, 2, 3],
+    };
+    let second = Meld {
+        tiles: vec![1, 2, 3],
+    };
+    let third = Meld {
+        tiles: vec![4, 5, 6],
+    };
+
+    let equal = unsafe {
+        __cs_bindgen_generated_eq__Meld(
+            <&Meld as Abi>::into_abi(&first),
+            <&Meld as Abi>::into_abi(&second),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(1, equal);
+
+    let equal = unsafe {
+        __cs_bindgen_generated_eq__Meld(
+            <&Meld as Abi>::into_abi(&first),
+            <&Meld as Abi>::into_abi(&third),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(0, equal);
+}
+
+#[test]
+fn hash_handle() {
+    let meld = Meld {
+        tiles: vec![1, 2, 3],
+    };
+    let hash = unsafe {
+        __cs_bindgen_generated_hash__Meld(<&Meld as Abi>::into_abi(&meld), ptr::null_mut())
+    };
+    assert_eq!(hash_of(&meld), hash);
+}
+
+#[test]
+fn eq_value() {
+    let east = Wind { seat: 0, round: 0 };
+    let east_south = Wind { seat: 0, round: 1 };
+    let south = Wind { seat: 1, round: 0 };
+
+    let equal = unsafe {
+        __cs_bindgen_generated_eq__Wind(east.into_abi(), east_south.into_abi(), ptr::null_mut())
+    };
+    assert_eq!(1, equal);
+
+    let equal = unsafe {
+        __cs_bindgen_generated_eq__Wind(east.into_abi(), south.into_abi(), ptr::null_mut())
+    };
+    assert_eq!(0, equal);
+}
@@ -0,0 +1,64 @@
+//! Tests verifying that panics caught at the FFI boundary are reported to the global
+//! error hook.
+//!
+//! The error hook is global, so everything is tested from a single test function.
+
+use cs_bindgen::{abi::RawString, exports, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, ffi::c_void, mem::MaybeUninit, ptr};
+
+thread_local! {
+    static REPORTS: RefCell<Vec<(String, String)>> = RefCell::new(Vec::new());
+}
+
+unsafe extern "C" fn hook(message: RawString, backtrace: RawString) {
+    let report = (message.into_string(), backtrace.into_string());
+    REPORTS.with(|reports| reports.borrow_mut().push(report));
+}
+
+fn take_reports() -> Vec<(String, String)> {
+    REPORTS.with(|reports| reports.borrow_mut().drain(..).collect())
+}
+
+#[cs_bindgen]
+pub fn declare_riichi(points: i32) -> i32 {
+    if points < 1000 {
+        panic!("Not enough points to declare riichi");
+    }
+
+    points - 1000
+}
+
+#[test]
+fn report_panics() {
+    unsafe {
+        exports::__cs_bindgen_set_error_hook(hook as *const c_void);
+    }
+
+    // Calls that complete normally aren't reported.
+    let points = unsafe { __cs_bindgen_generated__declare_riichi(8000, ptr::null_mut()) };
+    assert_eq!(7000, points);
+    assert!(take_reports().is_empty());
+
+    // The panic is still reported through the out parameter.
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__declare_riichi(500, &mut panic) };
+    assert_eq!("Not enough points to declare riichi", unsafe {
+        panic.into_string()
+    });
+
+    let reports = take_reports();
+    assert_eq!(1, reports.len());
+    assert_eq!("Not enough points to declare riichi", reports[0].0);
+    assert!(!reports[0].1.is_empty());
+
+    // Unregistering the hook stops reporting.
+    unsafe {
+        exports::__cs_bindgen_set_error_hook(ptr::null());
+    }
+
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__declare_riichi(0, &mut panic) };
+    let _ = unsafe { panic.into_string() };
+    assert!(take_reports().is_empty());
+}
@@ -0,0 +1,87 @@
+//! Tests verifying that event queues returned to C# share their events with Rust.
+
+use cs_bindgen::{
+    abi::{Abi, RawIter},
+    event::EventQueue,
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{ffi::c_void, mem::MaybeUninit, ptr, thread};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileDiscarded {
+    pub seat: u8,
+    pub tile: u8,
+}
+
+#[cs_bindgen]
+pub fn discards() -> EventQueue<TileDiscarded> {
+    let queue = EventQueue::new();
+    let sender = queue.clone();
+    thread::spawn(move || {
+        for seat in 0..4 {
+            sender.push(TileDiscarded {
+                seat,
+                tile: seat * 9,
+            });
+        }
+    })
+    .join()
+    .unwrap();
+
+    queue
+}
+
+/// Pulls the events that are currently pending from a raw event queue.
+unsafe fn poll<T: Abi>(raw: &RawIter) -> Vec<T> {
+    let mut events = Vec::new();
+    let mut event = MaybeUninit::<T::Abi>::uninit();
+    while raw.next(event.as_mut_ptr() as *mut c_void) {
+        events.push(T::from_abi(event.assume_init()));
+    }
+
+    events
+}
+
+#[test]
+fn poll_pending_events() {
+    let raw = unsafe { __cs_bindgen_generated__discards(ptr::null_mut()) };
+    let events = unsafe { poll::<TileDiscarded>(&raw) };
+    assert_eq!(
+        vec![
+            TileDiscarded { seat: 0, tile: 0 },
+            TileDiscarded { seat: 1, tile: 9 },
+            TileDiscarded { seat: 2, tile: 18 },
+            TileDiscarded { seat: 3, tile: 27 },
+        ],
+        events,
+    );
+
+    unsafe { raw.free() };
+}
+
+#[test]
+fn events_pushed_after_returning() {
+    let queue = EventQueue::new();
+    let raw = queue.clone().into_abi();
+    assert!(unsafe { poll::<String>(&raw) }.is_empty());
+
+    // An empty queue isn't exhausted, so events pushed later are still received.
+    queue.push(String::from("Riichi"));
+    queue.push(String::from("Tsumo"));
+    assert_eq!(vec!["Riichi", "Tsumo"], unsafe { poll::<String>(&raw) });
+    assert!(queue.is_empty());
+
+    unsafe { raw.free() };
+}
+
+#[test]
+fn round_trip() {
+    let queue = EventQueue::new();
+    queue.push(7u32);
+
+    let queue = unsafe { EventQueue::<u32>::from_abi(queue.into_abi()) };
+    assert_eq!(Some(7), queue.pop());
+    assert_eq!(None, queue.pop());
+}
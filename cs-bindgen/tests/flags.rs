@@ -0,0 +1,69 @@
+//! Tests verifying that types marked with `#[cs_bindgen(flags)]` are marshaled as
+//! their integer value.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen(flags)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Yaku {
+    bits: u16,
+}
+
+#[cs_bindgen]
+impl Yaku {
+    pub const RIICHI: Self = Yaku { bits: 0b001 };
+    pub const TSUMO: Self = Yaku { bits: 0b010 };
+    pub const PINFU: Self = Yaku { bits: 0b100 };
+    pub const MENZEN: Self = Yaku {
+        bits: Self::RIICHI.bits | Self::TSUMO.bits,
+    };
+}
+
+#[cs_bindgen(flags)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Seats(i8);
+
+#[cs_bindgen]
+pub fn combine_yaku(first: Yaku, second: Yaku) -> Yaku {
+    Yaku {
+        bits: first.bits | second.bits,
+    }
+}
+
+#[cs_bindgen]
+pub fn seat_count(seats: Seats) -> u32 {
+    seats.0.count_ones()
+}
+
+#[test]
+fn flags_marshaled_as_bits() {
+    let value = unsafe {
+        __cs_bindgen_generated__combine_yaku(
+            Yaku::RIICHI.into_abi(),
+            Yaku::PINFU.into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(0b101u16, value);
+    assert_eq!(Yaku { bits: 0b101 }, unsafe { Yaku::from_abi(value) });
+
+    let value = unsafe { __cs_bindgen_generated__seat_count(-1, ptr::null_mut()) };
+    assert_eq!(8, value);
+}
+
+#[test]
+fn describe_flags_type() {
+    let export = unsafe { (*__cs_bindgen_describe__Yaku()).into_string() };
+    assert!(export.contains(r#""is_flags":true"#), "{}", export);
+
+    let export = unsafe { (*__cs_bindgen_describe__Seats()).into_string() };
+    assert!(export.contains(r#""is_flags":true"#), "{}", export);
+}
+
+#[test]
+fn describe_flags_value() {
+    let export = unsafe { (*__cs_bindgen_describe__MENZEN__Yaku()).into_string() };
+    assert!(export.contains(r#""value":{"UInt":3}"#), "{}", export);
+}
@@ -0,0 +1,79 @@
+//! Tests verifying that a binding is generated for each instantiation of a generic
+//! function.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{fmt::Display, ptr};
+
+#[cs_bindgen(instantiate(T = u32, T = String))]
+pub fn describe_value<T: Display>(value: T) -> String {
+    format!("<{}>", value)
+}
+
+#[cs_bindgen(instantiate(T = u8, T = i64))]
+pub fn largest<T: PartialOrd + Copy>(values: Vec<T>) -> T {
+    let mut largest = values[0];
+    for &value in &values {
+        if value > largest {
+            largest = value;
+        }
+    }
+
+    largest
+}
+
+#[cs_bindgen(instantiate(K = u32, V = String, K = String, V = bool))]
+pub fn describe_pair<K: Display, V: Display>(key: K, value: V) -> String {
+    format!("{} => {}", key, value)
+}
+
+#[test]
+fn primitive_instantiation() {
+    let result = unsafe { __cs_bindgen_generated__describe_value__u32(7, ptr::null_mut()) };
+    assert_eq!("<7>", unsafe { result.into_string() });
+}
+
+#[test]
+fn string_instantiation() {
+    let result = unsafe {
+        __cs_bindgen_generated__describe_value__String(
+            String::from("Haku").into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!("<Haku>", unsafe { result.into_string() });
+}
+
+#[test]
+fn nested_type_parameter() {
+    let result = unsafe {
+        __cs_bindgen_generated__largest__u8(vec![3_u8, 9, 1].into_abi(), ptr::null_mut())
+    };
+    assert_eq!(9, result);
+
+    let result = unsafe {
+        __cs_bindgen_generated__largest__i64(vec![-3_i64, -9, -1].into_abi(), ptr::null_mut())
+    };
+    assert_eq!(-1, result);
+}
+
+#[test]
+fn multiple_type_parameters() {
+    let result = unsafe {
+        __cs_bindgen_generated__describe_pair__u32__String(
+            1,
+            String::from("East").into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!("1 => East", unsafe { result.into_string() });
+
+    let result = unsafe {
+        __cs_bindgen_generated__describe_pair__String__bool(
+            String::from("Riichi").into_abi(),
+            true.into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!("Riichi => true", unsafe { result.into_string() });
+}
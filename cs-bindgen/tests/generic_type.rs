@@ -0,0 +1,47 @@
+//! Tests verifying that each instantiation of a generic type is exported as a separate
+//! type.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+
+#[cs_bindgen(instantiate(T = u8, T = String))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Claim<T> {
+    Pass,
+    Pon(T),
+    Chi { tiles: Vec<T> },
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn enum_instantiations_are_named_types() {
+    let export = describe(unsafe { __cs_bindgen_describe__Claim__u8() });
+    assert!(export.contains(r#""name":"ClaimU8""#), "{}", export);
+
+    let export = describe(unsafe { __cs_bindgen_describe__Claim__String() });
+    assert!(export.contains(r#""name":"ClaimString""#), "{}", export);
+}
+
+#[test]
+fn enum_instantiation_round_trip() {
+    let claims = vec![
+        Claim::Pass,
+        Claim::Pon(String::from("7p")),
+        Claim::Chi {
+            tiles: vec!["2m".into(), "3m".into(), "4m".into()],
+        },
+    ];
+
+    for claim in claims {
+        let raw = claim.clone().into_abi();
+        let result: Claim<String> = unsafe { Abi::from_abi(raw) };
+        assert_eq!(claim, result);
+    }
+
+    let raw = Claim::Pon(5_u8).into_abi();
+    let result: Claim<u8> = unsafe { Abi::from_abi(raw) };
+    assert_eq!(Claim::Pon(5), result);
+}
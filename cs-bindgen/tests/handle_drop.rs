@@ -0,0 +1,37 @@
+//! Tests verifying that the drop function for handle types drops the value.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[cs_bindgen]
+pub struct Tracked {
+    _id: u32,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn drop_handle() {
+    let handle = Tracked { _id: 7 }.into_abi();
+    assert_eq!(0, DROP_COUNT.load(Ordering::SeqCst));
+
+    unsafe {
+        __cs_bindgen_drop__Tracked(handle);
+    }
+    assert_eq!(1, DROP_COUNT.load(Ordering::SeqCst));
+
+    // Dropping a null handle is a no-op.
+    unsafe {
+        __cs_bindgen_drop__Tracked(ptr::null());
+    }
+    assert_eq!(1, DROP_COUNT.load(Ordering::SeqCst));
+}
@@ -0,0 +1,63 @@
+//! Tests verifying that lists of handle types move each element into its own handle.
+
+use cs_bindgen::{
+    abi::{Abi, RawSlice, RawVec},
+    prelude::*,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[cs_bindgen]
+pub struct Meld {
+    id: u32,
+}
+
+impl Drop for Meld {
+    fn drop(&mut self) {
+        DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn list_of_handles() {
+    let vec = vec![Meld { id: 1 }, Meld { id: 2 }, Meld { id: 3 }];
+    let raw: RawVec<Meld> = vec.into_abi();
+    let slice = RawSlice {
+        ptr: raw.ptr,
+        len: raw.len,
+    };
+
+    // Each element is moved into a new handle, which C# takes ownership of.
+    let handles = (0..raw.len)
+        .map(|index| unsafe { __cs_bindgen_generated_index_Meld(slice, index) })
+        .collect::<Vec<_>>();
+
+    // Freeing the vec doesn't drop the elements that were moved out of it.
+    unsafe { __cs_bindgen_generated_drop_vec_Meld(raw) };
+    assert_eq!(0, DROP_COUNT.load(Ordering::SeqCst));
+
+    let ids = handles
+        .iter()
+        .map(|&handle| unsafe { (*handle).id })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![1, 2, 3], ids);
+
+    for handle in handles {
+        unsafe { __cs_bindgen_drop__Meld(handle) };
+    }
+    assert_eq!(3, DROP_COUNT.load(Ordering::SeqCst));
+
+    // Lists passed to Rust take ownership of each handle.
+    let handles = vec![Meld { id: 4 }.into_abi(), Meld { id: 5 }.into_abi()];
+    let raw = unsafe { __cs_bindgen_generated_convert_vec__Meld(handles.as_slice().into()) };
+    let melds: Vec<Meld> = unsafe { Abi::from_abi(raw) };
+    assert_eq!(
+        vec![4, 5],
+        melds.iter().map(|meld| meld.id).collect::<Vec<_>>()
+    );
+    assert_eq!(3, DROP_COUNT.load(Ordering::SeqCst));
+
+    drop(melds);
+    assert_eq!(5, DROP_COUNT.load(Ordering::SeqCst));
+}
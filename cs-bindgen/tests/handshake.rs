@@ -0,0 +1,29 @@
+//! Tests verifying the functions that the generated bindings use to check that the
+//! loaded dylib matches the bindings.
+
+use cs_bindgen::{exports, prelude::*, shared};
+use pretty_assertions::assert_eq;
+
+#[cs_bindgen]
+pub fn discard_tile(tile: u8) -> u8 {
+    tile
+}
+
+#[test]
+fn abi_version() {
+    assert_eq!(shared::ABI_VERSION, exports::__cs_bindgen_abi_version());
+}
+
+#[test]
+fn hash_declarations() {
+    let declaration = unsafe { (*__cs_bindgen_describe__discard_tile()).into_string() };
+    let expected = shared::hash_metadata(shared::METADATA_HASH_SEED, declaration.as_bytes());
+
+    let hash = unsafe {
+        exports::__cs_bindgen_hash_declaration(
+            shared::METADATA_HASH_SEED,
+            __cs_bindgen_describe__discard_tile(),
+        )
+    };
+    assert_eq!(expected, hash);
+}
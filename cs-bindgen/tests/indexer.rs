@@ -0,0 +1,47 @@
+//! Tests verifying that methods marked as indexer accessors are still exported as
+//! regular bindings.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+pub struct Hand {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn new(tiles: Vec<u8>) -> Hand {
+        Hand { tiles }
+    }
+
+    #[cs_bindgen(index)]
+    pub fn get(&self, index: usize) -> u8 {
+        self.tiles[index]
+    }
+
+    #[cs_bindgen(index_mut)]
+    pub fn set(&mut self, index: usize, tile: u8) {
+        self.tiles[index] = tile;
+    }
+}
+
+#[test]
+fn indexer_bindings() {
+    let mut hand = Hand::new(vec![1, 2, 3]);
+    unsafe {
+        __cs_bindgen_generated__set__Hand(
+            <&mut Hand as Abi>::into_abi(&mut hand),
+            1,
+            9,
+            ptr::null_mut(),
+        );
+    }
+
+    let tile = unsafe {
+        __cs_bindgen_generated__get__Hand(<&Hand as Abi>::into_abi(&hand), 1, ptr::null_mut())
+    };
+    assert_eq!(9, tile);
+    assert_eq!(vec![1, 9, 3], hand.tiles);
+}
@@ -0,0 +1,81 @@
+//! Tests verifying that 128-bit integers are passed as two 64-bit halves.
+
+use cs_bindgen::{
+    abi::{Abi, RawI128, RawU128},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ledger {
+    pub balance: i128,
+    pub total_wagered: u128,
+}
+
+#[cs_bindgen]
+pub fn settle(ledger: Ledger, amount: i128) -> Ledger {
+    Ledger {
+        balance: ledger.balance + amount,
+        total_wagered: ledger.total_wagered + amount.abs() as u128,
+    }
+}
+
+#[test]
+fn split_into_halves() {
+    assert_eq!(RawU128 { low: 1, high: 0 }, 1u128.into_abi());
+    assert_eq!(
+        RawU128 {
+            low: 0,
+            high: u64::MAX,
+        },
+        (u128::MAX << 64).into_abi(),
+    );
+
+    // The high half of a signed integer contains the sign.
+    assert_eq!(
+        RawI128 {
+            low: u64::MAX,
+            high: u64::MAX,
+        },
+        (-1i128).into_abi(),
+    );
+    assert_eq!(
+        RawI128 {
+            low: 0,
+            high: 1 << 63,
+        },
+        i128::MIN.into_abi(),
+    );
+}
+
+#[test]
+fn round_trip() {
+    for &value in &[0, 1, -1, i128::MIN, i128::MAX, 1 << 100, -(1 << 100)] {
+        assert_eq!(value, unsafe { i128::from_abi(value.into_abi()) });
+    }
+
+    for &value in &[0, 1, u128::MAX, 1 << 127] {
+        assert_eq!(value, unsafe { u128::from_abi(value.into_abi()) });
+    }
+}
+
+#[test]
+fn struct_fields() {
+    let ledger = Ledger {
+        balance: -(1 << 80),
+        total_wagered: 1 << 90,
+    };
+
+    let raw = unsafe {
+        __cs_bindgen_generated__settle(ledger.into_abi(), (-5i128).into_abi(), ptr::null_mut())
+    };
+    assert_eq!(
+        Ledger {
+            balance: -(1 << 80) - 5,
+            total_wagered: (1 << 90) + 5,
+        },
+        unsafe { Ledger::from_abi(raw) },
+    );
+}
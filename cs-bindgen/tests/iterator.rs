@@ -0,0 +1,109 @@
+//! Tests verifying that iterators returned from exported functions can be advanced
+//! through their raw representation.
+
+use cs_bindgen::{
+    abi::{RawIter, RawString},
+    exports,
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::Cell, ffi::c_void, mem::MaybeUninit, ptr, rc::Rc};
+
+#[cs_bindgen]
+pub fn count_up(count: u32) -> impl Iterator<Item = u32> {
+    0..count
+}
+
+#[cs_bindgen]
+pub fn winds() -> impl Iterator<Item = String> {
+    vec!["East", "South", "West", "North"]
+        .into_iter()
+        .map(String::from)
+}
+
+#[cs_bindgen]
+pub fn panic_after(count: u32) -> impl Iterator<Item = u32> {
+    (0..).map(move |value| {
+        if value == count {
+            panic!("Iterated too far");
+        }
+
+        value
+    })
+}
+
+/// Collects the remaining items in a raw iterator and frees it.
+unsafe fn collect<T: Copy>(raw: RawIter) -> Vec<T> {
+    let mut items = Vec::new();
+    let mut item = MaybeUninit::<T>::uninit();
+    while raw.next(item.as_mut_ptr() as *mut c_void) {
+        items.push(item.assume_init());
+    }
+
+    raw.free();
+    items
+}
+
+#[test]
+fn collect_primitive_items() {
+    let raw = unsafe { __cs_bindgen_generated__count_up(5, ptr::null_mut()) };
+    assert_eq!(vec![0, 1, 2, 3, 4], unsafe { collect::<u32>(raw) });
+}
+
+#[test]
+fn collect_string_items() {
+    let raw = unsafe { __cs_bindgen_generated__winds(ptr::null_mut()) };
+    let winds = unsafe { collect::<RawString>(raw) }
+        .into_iter()
+        .map(|wind| unsafe { wind.into_string() })
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["East", "South", "West", "North"], winds);
+}
+
+#[test]
+fn free_drops_iterator() {
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(false));
+    let flag = DropFlag(dropped.clone());
+    let raw = RawIter::new((0..3_u32).map(move |value| {
+        let _ = &flag;
+        value
+    }));
+
+    let mut item = 0_u32;
+    assert!(unsafe { raw.next(&mut item as *mut u32 as *mut c_void) });
+    assert!(!dropped.get());
+
+    unsafe { raw.free() };
+    assert!(dropped.get());
+}
+
+#[test]
+fn panic_reported_from_next() {
+    let raw = unsafe { __cs_bindgen_generated__panic_after(1, ptr::null_mut()) };
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    let mut item = 0_u32;
+
+    let has_item = unsafe {
+        exports::__cs_bindgen_iter_next(raw, &mut item as *mut u32 as *mut c_void, &mut panic)
+    };
+    assert_eq!(1, has_item);
+    assert_eq!(0, item);
+    assert!(panic.ptr.is_null());
+
+    let has_item = unsafe {
+        exports::__cs_bindgen_iter_next(raw, &mut item as *mut u32 as *mut c_void, &mut panic)
+    };
+    assert_eq!(0, has_item);
+    assert_eq!("Iterated too far", unsafe { panic.into_string() });
+
+    unsafe { exports::__cs_bindgen_iter_drop(raw) };
+}
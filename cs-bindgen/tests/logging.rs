@@ -0,0 +1,48 @@
+//! Tests verifying that log records are forwarded to the registered handler.
+//!
+//! Only one logger can be installed per process, so everything is tested from a
+//! single test function.
+
+use cs_bindgen::{abi::RawString, exports};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, ffi::c_void, ptr};
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(u8, String, String)>> = RefCell::new(Vec::new());
+}
+
+unsafe extern "C" fn handler(level: u8, target: RawString, message: RawString) {
+    let record = (level, target.into_string(), message.into_string());
+    RECORDS.with(|records| records.borrow_mut().push(record));
+}
+
+fn take_records() -> Vec<(u8, String, String)> {
+    RECORDS.with(|records| records.borrow_mut().drain(..).collect())
+}
+
+#[test]
+fn forward_records() {
+    // Records logged before a handler is registered are dropped.
+    log::error!("Nobody is listening");
+    assert!(take_records().is_empty());
+
+    let registered = unsafe { exports::__cs_bindgen_set_log_handler(handler as *const c_void, 3) };
+    assert_eq!(1, registered);
+
+    log::info!(target: "mahjong", "Discarded {}", "5m");
+    log::warn!(target: "mahjong::rules", "Furiten");
+    log::debug!("Filtered by the max level");
+    assert_eq!(
+        vec![
+            (3, "mahjong".into(), "Discarded 5m".into()),
+            (2, "mahjong::rules".into(), "Furiten".into()),
+        ],
+        take_records(),
+    );
+
+    // Unregistering the handler disables logging.
+    let unregistered = unsafe { exports::__cs_bindgen_set_log_handler(ptr::null(), 5) };
+    assert_eq!(1, unregistered);
+    log::error!("Nobody is listening");
+    assert!(take_records().is_empty());
+}
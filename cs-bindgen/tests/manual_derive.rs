@@ -14,26 +14,39 @@ pub fn example_fn(first: u32, second: String) -> String {
     format!("first: {}, second: {}", first, second)
 }
 
+// The binding function also takes a trailing `__panic` out parameter, which is used
+// to report any panic that occurs back to C# instead of unwinding across the FFI
+// boundary.
+
 #[no_mangle]
 pub unsafe extern "C" fn __cs_bindgen_generated__example_fn(
     first: <u32 as Abi>::Abi,
     second: <String as Abi>::Abi,
+    __panic: *mut RawString,
 ) -> <String as Abi>::Abi {
-    let first = Abi::from_abi(first);
-    let second = Abi::from_abi(second);
-    example_fn(first, second).into_abi()
+    cs_bindgen::panic::catch_panic(__panic, move || {
+        let first = Abi::from_abi(first);
+        let second = Abi::from_abi(second);
+        example_fn(first, second).into_abi()
+    })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn __cs_bindgen_describe__example_fn() -> Box<RawVec<u8>> {
     let export = Func {
         name: "example_fn".into(),
+        module: module_path!().into(),
         binding: "__cs_bindgen_generated__example_fn".into(),
+        rename: None,
+        docs: None,
         inputs: vec![
             FnArg::new("first", u32::repr()),
             FnArg::new("second", String::repr()),
         ],
         output: Some(String::repr()),
+        out_params: Vec::new(),
+        is_async: false,
+        command: None,
     };
 
     Box::new(serialize_export(export).into())
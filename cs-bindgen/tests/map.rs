@@ -0,0 +1,50 @@
+//! Tests verifying that map types can be round-tripped through their raw
+//! representation.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::collections::{BTreeMap, HashMap};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructWithMap {
+    pub scores: HashMap<String, i32>,
+}
+
+#[test]
+fn hash_map_round_trip() {
+    let mut original = HashMap::new();
+    original.insert(String::from("East"), 25_000);
+    original.insert(String::from("South"), -8_000);
+    original.insert(String::from("West"), 32_000);
+
+    let result: HashMap<String, i32> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn btree_map_round_trip() {
+    let mut original = BTreeMap::new();
+    original.insert(1_u8, String::from("Riichi"));
+    original.insert(13_u8, String::from("Kokushi musou"));
+
+    let result: BTreeMap<u8, String> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn empty_map_round_trip() {
+    let original = HashMap::<u32, u64>::new();
+    let result: HashMap<u32, u64> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn struct_map_round_trip() {
+    let mut scores = HashMap::new();
+    scores.insert(String::from("North"), 0);
+
+    let original = StructWithMap { scores };
+    let result: StructWithMap = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
@@ -0,0 +1,107 @@
+//! Tests verifying that types marked with `#[cs_bindgen(marshal = "json")]` are
+//! serialized as JSON when passed across the FFI boundary.
+
+#![cfg(feature = "json")]
+
+use cs_bindgen::{
+    abi::{Abi, RawString, RawVec},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, mem::MaybeUninit, ptr};
+
+#[cs_bindgen(marshal = "json")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub round: u8,
+    pub discards: Vec<String>,
+    pub scores: HashMap<String, i32>,
+    pub last_meld: Option<Meld>,
+}
+
+#[cs_bindgen(marshal = "json")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Meld {
+    Chi { tiles: Vec<u8> },
+    Pon(u8),
+    Kan(u8, bool),
+}
+
+#[cs_bindgen]
+pub fn advance_round(snapshot: Snapshot) -> Snapshot {
+    Snapshot {
+        round: snapshot.round + 1,
+        discards: Vec::new(),
+        ..snapshot
+    }
+}
+
+fn snapshot() -> Snapshot {
+    Snapshot {
+        round: 3,
+        discards: vec!["1m".into(), "9p".into()],
+        scores: vec![("east".to_string(), 25000)].into_iter().collect(),
+        last_meld: Some(Meld::Pon(5)),
+    }
+}
+
+#[test]
+fn marshaled_as_json() {
+    let raw = snapshot().into_abi();
+    let json = unsafe { raw.into_string() };
+    assert_eq!(
+        r#"{"round":3,"discards":["1m","9p"],"scores":{"east":25000},"last_meld":{"Pon":5}}"#,
+        json,
+    );
+
+    let raw = Meld::Kan(7, true).into_abi();
+    let json = unsafe { raw.into_string() };
+    assert_eq!(r#"{"Kan":[7,true]}"#, json);
+}
+
+#[test]
+fn round_trip() {
+    let raw =
+        unsafe { __cs_bindgen_generated__advance_round(snapshot().into_abi(), ptr::null_mut()) };
+    let result = unsafe { Snapshot::from_abi(raw) };
+    assert_eq!(
+        Snapshot {
+            round: 4,
+            discards: Vec::new(),
+            ..snapshot()
+        },
+        result,
+    );
+}
+
+#[test]
+fn invalid_json_panics() {
+    let raw: RawVec<u8> = br#"{"round":3}"#.to_vec().into();
+
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__advance_round(raw, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(
+        message.starts_with("Failed to deserialize marshaled value from JSON"),
+        "{}",
+        message,
+    );
+}
+
+#[test]
+fn describe_marshaled_type() {
+    let export = unsafe { (*__cs_bindgen_describe__Snapshot()).into_string() };
+    assert!(
+        export.contains(r#""binding_style":{"Marshal":["Json","#),
+        "{}",
+        export,
+    );
+
+    let export = unsafe { (*__cs_bindgen_describe__Meld()).into_string() };
+    assert!(
+        export.contains(r#""binding_style":{"Marshal":["Json","#),
+        "{}",
+        export,
+    );
+}
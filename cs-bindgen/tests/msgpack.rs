@@ -0,0 +1,91 @@
+//! Tests verifying that types marked with `#[cs_bindgen(marshal = "msgpack")]` are
+//! serialized as MessagePack when passed across the FFI boundary.
+
+#![cfg(feature = "msgpack")]
+
+use cs_bindgen::{
+    abi::{Abi, RawString, RawVec},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, mem::MaybeUninit, ptr};
+
+#[cs_bindgen(marshal = "msgpack")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub hands: Vec<Vec<u8>>,
+    pub final_scores: HashMap<u8, i32>,
+}
+
+#[cs_bindgen]
+pub fn add_hand(record: GameRecord, hand: Vec<u8>) -> GameRecord {
+    let mut record = record;
+    record.hands.push(hand);
+    record
+}
+
+#[test]
+fn describe_marshaled_type() {
+    let export = unsafe { (*__cs_bindgen_describe__GameRecord()).into_string() };
+    assert!(
+        export.contains(r#""binding_style":{"Marshal":["MessagePack","#),
+        "{}",
+        export,
+    );
+}
+
+#[test]
+fn marshaled_as_msgpack() {
+    let record = GameRecord {
+        hands: vec![vec![1, 2]],
+        final_scores: vec![(0, 25)].into_iter().collect(),
+    };
+
+    // Structs are serialized as an array of their field values, i.e. a fixarray with
+    // two elements.
+    let bytes = unsafe { record.into_abi().into_vec() };
+    assert_eq!(
+        &[0x92, 0x91, 0x92, 0x01, 0x02, 0x81, 0x00, 0x19],
+        &bytes[..]
+    );
+}
+
+#[test]
+fn msgpack_round_trip() {
+    let record = GameRecord {
+        hands: vec![vec![1, 2]],
+        final_scores: vec![(0, 25000), (3, -8000)].into_iter().collect(),
+    };
+
+    let raw = unsafe {
+        __cs_bindgen_generated__add_hand(
+            record.clone().into_abi(),
+            vec![7u8, 8, 9].into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    let result = unsafe { GameRecord::from_abi(raw) };
+    assert_eq!(
+        GameRecord {
+            hands: vec![vec![1, 2], vec![7, 8, 9]],
+            ..record
+        },
+        result,
+    );
+}
+
+#[test]
+fn invalid_msgpack_panics() {
+    // A fixarray with only one of the two fields.
+    let raw: RawVec<u8> = vec![0x91, 0x90].into();
+
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__add_hand(raw, Vec::<u8>::new().into_abi(), &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(
+        message.starts_with("Failed to deserialize marshaled value from MessagePack"),
+        "{}",
+        message,
+    );
+}
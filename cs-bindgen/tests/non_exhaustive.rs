@@ -0,0 +1,32 @@
+//! Tests verifying that `#[non_exhaustive]` enums are described as non-exhaustive.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Wind {
+    East,
+    South,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum Dragon {
+    White,
+    Green,
+    Red,
+}
+
+#[test]
+fn describe_non_exhaustive_enum() {
+    let export = unsafe { (*__cs_bindgen_describe__Wind()).into_string() };
+    assert!(export.contains(r#""is_non_exhaustive":true"#), "{}", export);
+
+    let export = unsafe { (*__cs_bindgen_describe__Dragon()).into_string() };
+    assert!(
+        export.contains(r#""is_non_exhaustive":false"#),
+        "{}",
+        export
+    );
+}
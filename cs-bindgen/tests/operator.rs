@@ -0,0 +1,80 @@
+//! Tests verifying the bindings generated for impls of the operator traits.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{
+    ops::{Add, Mul, Neg},
+    ptr,
+};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Points {
+    pub value: i32,
+}
+
+#[cs_bindgen]
+impl Add for Points {
+    type Output = Points;
+
+    fn add(self, other: Points) -> Points {
+        Points {
+            value: self.value + other.value,
+        }
+    }
+}
+
+#[cs_bindgen]
+impl Mul<i32> for Points {
+    type Output = Points;
+
+    fn mul(self, factor: i32) -> Points {
+        Points {
+            value: self.value * factor,
+        }
+    }
+}
+
+#[cs_bindgen]
+impl Neg for Points {
+    type Output = Points;
+
+    fn neg(self) -> Points {
+        Points { value: -self.value }
+    }
+}
+
+#[test]
+fn binary_operator() {
+    let raw = unsafe {
+        __cs_bindgen_generated_impl_Add_Points__Points(
+            Points { value: 8000 }.into_abi(),
+            Points { value: 4000 }.into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    let points = unsafe { Points::from_abi(raw) };
+    assert_eq!(Points { value: 12000 }, points);
+}
+
+#[test]
+fn binary_operator_with_rhs() {
+    let raw = unsafe {
+        __cs_bindgen_generated_impl_Mul_i32__Points(
+            Points { value: 1000 }.into_abi(),
+            3,
+            ptr::null_mut(),
+        )
+    };
+    let points = unsafe { Points::from_abi(raw) };
+    assert_eq!(Points { value: 3000 }, points);
+}
+
+#[test]
+fn unary_operator() {
+    let raw = unsafe {
+        __cs_bindgen_generated_impl_Neg__Points(Points { value: 1000 }.into_abi(), ptr::null_mut())
+    };
+    let points = unsafe { Points::from_abi(raw) };
+    assert_eq!(Points { value: -1000 }, points);
+}
@@ -0,0 +1,38 @@
+//! Tests verifying that optional values can be round-tripped through their raw
+//! representation.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructWithOptions {
+    pub int_field: Option<u32>,
+    pub string_field: Option<String>,
+}
+
+#[test]
+fn int_option_round_trip() {
+    for original in vec![Some(123_u32), None] {
+        let result: Option<u32> = unsafe { Abi::from_abi(original.into_abi()) };
+        assert_eq!(original, result);
+    }
+}
+
+#[test]
+fn string_option_round_trip() {
+    for original in vec![Some(String::from("foo")), None] {
+        let result: Option<String> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+        assert_eq!(original, result);
+    }
+}
+
+#[test]
+fn struct_option_round_trip() {
+    let original = Some(StructWithOptions {
+        int_field: Some(7),
+        string_field: None,
+    });
+    let result: Option<StructWithOptions> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
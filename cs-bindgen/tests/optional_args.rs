@@ -0,0 +1,85 @@
+//! Tests verifying that default argument values are described with the function.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+const DEFAULT_ITERATIONS: u32 = 1000;
+
+#[cs_bindgen]
+pub fn analyze(
+    hand: Vec<u8>,
+    #[cs_bindgen(default = DEFAULT_ITERATIONS)] iterations: u32,
+    #[cs_bindgen(default = "riichi")] ruleset: String,
+    seed: Option<u64>,
+) -> String {
+    format!("{} {} {} {:?}", hand.len(), iterations, ruleset, seed)
+}
+
+#[cs_bindgen]
+pub struct Simulator {
+    iterations: u32,
+}
+
+#[cs_bindgen]
+impl Simulator {
+    pub fn new(#[cs_bindgen(default = 100)] iterations: u32) -> Self {
+        Simulator { iterations }
+    }
+
+    pub fn scaled(&self, #[cs_bindgen(default = 0.5)] factor: f64) -> f64 {
+        self.iterations as f64 * factor
+    }
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn describe_defaults() {
+    let export = describe(unsafe { __cs_bindgen_describe__analyze() });
+    assert!(
+        export.contains(r#"{"name":"hand","repr":{"Vec":"U8"},"default":null}"#),
+        "{}",
+        export
+    );
+    assert!(
+        export.contains(r#"{"name":"iterations","repr":"U32","default":{"UInt":1000}}"#),
+        "{}",
+        export
+    );
+    assert!(
+        export.contains(r#""default":{"String":"riichi"}"#),
+        "{}",
+        export
+    );
+    assert!(
+        export.contains(r#"{"name":"seed","repr":{"Option":"U64"},"default":null}"#),
+        "{}",
+        export
+    );
+}
+
+#[test]
+fn describe_method_defaults() {
+    let export = describe(unsafe { __cs_bindgen_describe__new__Simulator() });
+    assert!(export.contains(r#""default":{"Int":100}"#), "{}", export);
+
+    let export = describe(unsafe { __cs_bindgen_describe__scaled__Simulator() });
+    assert!(export.contains(r#""default":{"Float":0.5}"#), "{}", export);
+}
+
+#[test]
+fn binding_takes_every_argument() {
+    let result = unsafe {
+        __cs_bindgen_generated__analyze(
+            vec![1_u8, 2, 3].into_abi(),
+            10,
+            String::from("sanma").into_abi(),
+            Some(7_u64).into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!("3 10 sanma Some(7)", unsafe { result.into_string() });
+}
@@ -0,0 +1,70 @@
+//! Tests verifying that the `PartialOrd` impl for exported types is exported.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{cmp::Ordering, ptr};
+
+#[cs_bindgen]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rank {
+    points: i32,
+}
+
+#[cs_bindgen(ord)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    pub value: u8,
+    pub red: bool,
+}
+
+// Tiles are ordered by value only, in order to verify that the custom impl is used.
+impl PartialOrd for Tile {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+fn compare_ranks(left: &Rank, right: &Rank) -> i32 {
+    unsafe {
+        __cs_bindgen_generated_cmp__Rank(
+            <&Rank as Abi>::into_abi(left),
+            <&Rank as Abi>::into_abi(right),
+            ptr::null_mut(),
+        )
+    }
+}
+
+#[test]
+fn cmp_handle() {
+    let low = Rank { points: 100 };
+    let high = Rank { points: 25_000 };
+
+    assert_eq!(-1, compare_ranks(&low, &high));
+    assert_eq!(0, compare_ranks(&low, &low));
+    assert_eq!(1, compare_ranks(&high, &low));
+}
+
+#[test]
+fn cmp_value() {
+    let five = Tile {
+        value: 5,
+        red: false,
+    };
+    let red_five = Tile {
+        value: 5,
+        red: true,
+    };
+    let seven = Tile {
+        value: 7,
+        red: false,
+    };
+
+    let result = unsafe {
+        __cs_bindgen_generated_cmp__Tile(five.into_abi(), seven.into_abi(), ptr::null_mut())
+    };
+    assert_eq!(-1, result);
+
+    let result = unsafe {
+        __cs_bindgen_generated_cmp__Tile(five.into_abi(), red_five.into_abi(), ptr::null_mut())
+    };
+    assert_eq!(0, result);
+}
@@ -0,0 +1,59 @@
+//! Tests verifying that trailing tuple elements listed with `out(...)` are described
+//! as `out` parameters.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::ptr;
+
+#[cs_bindgen(out(ukeire))]
+pub fn shanten_and_ukeire(tiles: Vec<u8>) -> (i8, Vec<u8>) {
+    (tiles.len() as i8 - 1, tiles)
+}
+
+#[cs_bindgen]
+#[derive(Default)]
+pub struct Wall {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Wall {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cs_bindgen(out(remaining, dead_wall))]
+    pub fn counts(&self) -> (u32, u32) {
+        (self.tiles.len() as u32, 14)
+    }
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn fn_out_params() {
+    let export = describe(unsafe { __cs_bindgen_describe__shanten_and_ukeire() });
+    assert!(export.contains(r#""out_params":["ukeire"]"#), "{}", export);
+
+    // The binding function still returns the whole tuple.
+    let result = unsafe {
+        __cs_bindgen_generated__shanten_and_ukeire(vec![1, 2, 3].into_abi(), ptr::null_mut())
+    };
+    let (shanten, ukeire): (i8, Vec<u8>) = unsafe { Abi::from_abi(result) };
+    assert_eq!(2, shanten);
+    assert_eq!(vec![1, 2, 3], ukeire);
+}
+
+#[test]
+fn method_out_params() {
+    let export = describe(unsafe { __cs_bindgen_describe__counts__Wall() });
+    assert!(
+        export.contains(r#""out_params":["remaining","dead_wall"]"#),
+        "{}",
+        export,
+    );
+
+    let export = describe(unsafe { __cs_bindgen_describe__new__Wall() });
+    assert!(export.contains(r#""out_params":[]"#), "{}", export);
+}
@@ -0,0 +1,60 @@
+//! Tests verifying that pooled handles reuse their allocations.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[cs_bindgen(pool)]
+pub struct Analysis {
+    shanten: u32,
+}
+
+impl Drop for Analysis {
+    fn drop(&mut self) {
+        DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cs_bindgen]
+impl Analysis {
+    pub fn new(shanten: u32) -> Self {
+        Analysis { shanten }
+    }
+
+    pub fn shanten(&self) -> u32 {
+        self.shanten
+    }
+}
+
+#[test]
+fn dropped_handle_is_reused() {
+    let first = Analysis { shanten: 1 }.into_abi();
+    unsafe {
+        __cs_bindgen_drop__Analysis(first);
+    }
+    assert_eq!(1, DROP_COUNT.load(Ordering::SeqCst));
+
+    // The next handle created on the same thread reuses the allocation of the dropped
+    // handle.
+    let second = unsafe { __cs_bindgen_generated__new__Analysis(2, std::ptr::null_mut()) };
+    assert_eq!(first, second);
+
+    let shanten =
+        unsafe { __cs_bindgen_generated__shanten__Analysis(second, std::ptr::null_mut()) };
+    assert_eq!(2, shanten);
+
+    // Taking ownership of the value also returns the allocation to the pool.
+    let analysis = unsafe { Analysis::from_abi(second) };
+    assert_eq!(2, analysis.shanten);
+    assert_eq!(1, DROP_COUNT.load(Ordering::SeqCst));
+
+    let third = Analysis { shanten: 3 }.into_abi();
+    assert_eq!(second, third);
+
+    drop(analysis);
+    unsafe {
+        __cs_bindgen_drop__Analysis(third);
+    }
+    assert_eq!(3, DROP_COUNT.load(Ordering::SeqCst));
+}
@@ -0,0 +1,65 @@
+//! Tests verifying that methods marked as property accessors are still exported as
+//! regular bindings.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+pub struct Player {
+    name: String,
+    points: i32,
+}
+
+#[cs_bindgen]
+impl Player {
+    pub fn new(name: String) -> Player {
+        Player {
+            name,
+            points: 25_000,
+        }
+    }
+
+    #[cs_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[cs_bindgen(getter)]
+    pub fn get_points(&self) -> i32 {
+        self.points
+    }
+
+    #[cs_bindgen(setter)]
+    pub fn set_points(&mut self, points: i32) {
+        self.points = points;
+    }
+}
+
+#[test]
+fn accessors_remain_callable() {
+    let mut player = Player::new("Haru".into());
+    player.set_points(18_000);
+    assert_eq!(18_000, player.get_points());
+    assert_eq!("Haru", player.name());
+}
+
+#[test]
+fn accessor_bindings() {
+    let mut player = Player::new("Haru".into());
+    unsafe {
+        __cs_bindgen_generated__set_points__Player(
+            <&mut Player as Abi>::into_abi(&mut player),
+            32_000,
+            ptr::null_mut(),
+        );
+    }
+
+    let points = unsafe {
+        __cs_bindgen_generated__get_points__Player(
+            <&Player as Abi>::into_abi(&player),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(32_000, points);
+}
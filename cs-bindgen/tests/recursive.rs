@@ -0,0 +1,34 @@
+//! Tests verifying that types with boxed, recursive fields can be described and
+//! passed through their raw representation.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tree {
+    Leaf(u32),
+    Node { left: Box<Tree>, right: Box<Tree> },
+}
+
+#[test]
+fn describe_recursive_enum() {
+    // NOTE: Describing the type would overflow the stack if the boxed fields were
+    // described in full.
+    let export = unsafe { (*__cs_bindgen_describe__Tree()).into_string() };
+    assert!(export.contains("cs_bindgen::boxed"), "{}", export);
+}
+
+#[test]
+fn roundtrip_recursive_enum() {
+    let tree = Tree::Node {
+        left: Box::new(Tree::Leaf(1)),
+        right: Box::new(Tree::Node {
+            left: Box::new(Tree::Leaf(2)),
+            right: Box::new(Tree::Leaf(3)),
+        }),
+    };
+
+    let raw = tree.clone().into_abi();
+    let result = unsafe { Tree::from_abi(raw) };
+    assert_eq!(tree, result);
+}
@@ -0,0 +1,96 @@
+//! Tests verifying that `&mut T` arguments write the modified value back to the
+//! caller.
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    prelude::*,
+};
+use std::{mem::MaybeUninit, ptr};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub seat: u8,
+    pub points: i32,
+}
+
+#[cs_bindgen]
+pub fn add_points(total: &mut i32, points: i32) -> bool {
+    *total += points;
+    *total >= 0
+}
+
+#[cs_bindgen]
+pub fn pay(from: &mut Score, to: &mut Score, points: i32) {
+    if points < 0 {
+        panic!("Can't pay a negative amount");
+    }
+
+    from.points -= points;
+    to.points += points;
+}
+
+#[test]
+fn primitive_write_back() {
+    let mut total = 100;
+    let result = unsafe { __cs_bindgen_generated__add_points(&mut total, -300, ptr::null_mut()) };
+    assert_eq!(-200, total);
+    assert_eq!(0, result);
+}
+
+#[test]
+fn value_write_back() {
+    let mut from = Score {
+        seat: 0,
+        points: 25000,
+    }
+    .into_abi();
+    let mut to = Score {
+        seat: 1,
+        points: 25000,
+    }
+    .into_abi();
+
+    unsafe { __cs_bindgen_generated__pay(&mut from, &mut to, 8000, ptr::null_mut()) };
+
+    let from = unsafe { Score::from_abi(from) };
+    let to = unsafe { Score::from_abi(to) };
+    assert_eq!(
+        Score {
+            seat: 0,
+            points: 17000,
+        },
+        from,
+    );
+    assert_eq!(
+        Score {
+            seat: 1,
+            points: 33000,
+        },
+        to,
+    );
+}
+
+#[test]
+fn no_write_back_on_panic() {
+    let mut from = Score {
+        seat: 2,
+        points: 25000,
+    }
+    .into_abi();
+    let mut to = Score {
+        seat: 3,
+        points: 25000,
+    }
+    .into_abi();
+
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__pay(&mut from, &mut to, -1, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert_eq!("Can't pay a negative amount", message);
+
+    let from = unsafe { Score::from_abi(from) };
+    let to = unsafe { Score::from_abi(to) };
+    assert_eq!(25000, from.points);
+    assert_eq!(25000, to.points);
+}
@@ -0,0 +1,100 @@
+//! Tests verifying that renamed items are described with their new names.
+
+use cs_bindgen::{prelude::*, shared::Named};
+use std::ptr;
+
+#[cs_bindgen(rename = "HTTPPort")]
+pub fn http_port() -> u16 {
+    8080
+}
+
+#[cs_bindgen(rename = "TileValue")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    #[cs_bindgen(rename = "Base")]
+    pub base: u8,
+    pub is_red: bool,
+}
+
+#[cs_bindgen(rename = "WallHandle")]
+#[derive(Default)]
+pub struct Wall {
+    tiles: Vec<Tile>,
+}
+
+#[cs_bindgen]
+impl Wall {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cs_bindgen(rename = "Params")]
+    pub fn params(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum Wind {
+    East,
+    #[cs_bindgen(rename = "SouthWind")]
+    South,
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn renamed_fn() {
+    let export = describe(unsafe { __cs_bindgen_describe__http_port() });
+    assert!(export.contains(r#""name":"http_port""#), "{}", export);
+    assert!(export.contains(r#""rename":"HTTPPort""#), "{}", export);
+
+    // The binding function keeps the original name.
+    assert_eq!(8080, unsafe {
+        __cs_bindgen_generated__http_port(ptr::null_mut())
+    });
+}
+
+#[test]
+fn renamed_struct_and_field() {
+    assert_eq!("TileValue", &*<Tile as Named>::type_name().name);
+
+    let export = describe(unsafe { __cs_bindgen_describe__Tile() });
+    assert!(export.contains(r#""TileValue""#), "{}", export);
+    assert!(export.contains(r#""Base""#), "{}", export);
+    assert!(export.contains(r#""is_red""#), "{}", export);
+    assert!(
+        export.contains(r#""renamed_fields":["Base"]"#),
+        "{}",
+        export
+    );
+}
+
+#[test]
+fn renamed_handle() {
+    assert_eq!("WallHandle", &*<Wall as Named>::type_name().name);
+
+    // The drop function keeps the original name of the type.
+    let export = describe(unsafe { __cs_bindgen_describe__Wall() });
+    assert!(
+        export.contains(r#""drop_fn":"__cs_bindgen_drop__Wall""#),
+        "{}",
+        export
+    );
+}
+
+#[test]
+fn renamed_method() {
+    let export = describe(unsafe { __cs_bindgen_describe__params__Wall() });
+    assert!(export.contains(r#""rename":"Params""#), "{}", export);
+}
+
+#[test]
+fn renamed_variant() {
+    let export = describe(unsafe { __cs_bindgen_describe__Wind() });
+    assert!(export.contains(r#""East""#), "{}", export);
+    assert!(export.contains(r#""SouthWind""#), "{}", export);
+}
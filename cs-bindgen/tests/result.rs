@@ -0,0 +1,48 @@
+//! Tests verifying that results can be round-tripped through their raw
+//! representation.
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{mem::MaybeUninit, num::ParseIntError};
+
+#[cs_bindgen(error = "display")]
+pub fn parse_seat(value: String) -> Result<u8, ParseIntError> {
+    value.parse()
+}
+
+#[test]
+fn int_result_round_trip() {
+    for original in vec![Ok(123_u32), Err(7_i32)] {
+        let result: Result<u32, i32> = unsafe { Abi::from_abi(original.into_abi()) };
+        assert_eq!(original, result);
+    }
+}
+
+#[test]
+fn string_error_round_trip() {
+    for original in vec![Ok(()), Err(String::from("Something went wrong"))] {
+        let result: Result<(), String> = unsafe { Abi::from_abi(original.clone().into_abi()) };
+        assert_eq!(original, result);
+    }
+}
+
+#[test]
+fn display_error() {
+    let parse = |value: &str| -> Result<u8, String> {
+        unsafe {
+            let mut panic = MaybeUninit::<RawString>::zeroed().assume_init();
+            let raw = __cs_bindgen_generated__parse_seat(value.to_string().into_abi(), &mut panic);
+            assert!(panic.ptr.is_null());
+            Abi::from_abi(raw)
+        }
+    };
+
+    assert_eq!(Ok(3), parse("3"));
+    assert_eq!(
+        Err(String::from("invalid digit found in string")),
+        parse("East"),
+    );
+}
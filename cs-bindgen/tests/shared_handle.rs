@@ -0,0 +1,51 @@
+//! Tests verifying that shared handles are reference counted.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{ptr, sync::Arc};
+
+#[cs_bindgen(shared)]
+pub struct Table {
+    seats: u32,
+}
+
+#[cs_bindgen]
+impl Table {
+    pub fn new(seats: u32) -> Arc<Self> {
+        Arc::new(Table { seats })
+    }
+
+    pub fn seats(&self) -> u32 {
+        self.seats
+    }
+}
+
+#[test]
+fn clone_increments_ref_count() {
+    let table = Table::new(4);
+    let handle = Arc::clone(&table).into_abi();
+    assert_eq!(2, Arc::strong_count(&table));
+
+    let clone = unsafe { __cs_bindgen_generated_clone__Table(handle, ptr::null_mut()) };
+    assert_eq!(handle, clone);
+    assert_eq!(3, Arc::strong_count(&table));
+
+    unsafe {
+        __cs_bindgen_drop__Table(clone);
+    }
+    assert_eq!(2, Arc::strong_count(&table));
+
+    unsafe {
+        __cs_bindgen_drop__Table(handle);
+    }
+    assert_eq!(1, Arc::strong_count(&table));
+}
+
+#[test]
+fn constructor_returns_shared_handle() {
+    let handle = unsafe { __cs_bindgen_generated__new__Table(4, ptr::null_mut()) };
+    let seats = unsafe { __cs_bindgen_generated__seats__Table(handle, ptr::null_mut()) };
+    assert_eq!(4, seats);
+
+    let table = unsafe { <Arc<Table> as Abi>::from_abi(handle) };
+    assert_eq!(1, Arc::strong_count(&table));
+}
@@ -77,3 +77,44 @@ fn simple_enum_explicit_first_discriminant() {
         assert_eq!(variant, result);
     }
 }
+
+#[test]
+fn simple_enum_repr() {
+    #[cs_bindgen]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+    #[repr(u8)]
+    pub enum Narrow {
+        Zero,
+        One = 200,
+        Two,
+    }
+
+    for variant in Narrow::iter() {
+        let abi: u8 = variant.into_abi();
+        assert_eq!(variant as u8, abi);
+
+        let result = unsafe { Narrow::from_abi(abi) };
+        assert_eq!(variant, result);
+    }
+
+    let export = unsafe { (*__cs_bindgen_describe__Narrow()).into_string() };
+    assert!(export.contains(r#""discriminant":"U8""#), "{}", export);
+}
+
+#[test]
+fn simple_enum_repr_with_hints() {
+    #[cs_bindgen]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+    #[repr(C, i16)]
+    pub enum Signed {
+        Low = -300,
+        Middle = 0,
+        High = 300,
+    }
+
+    for variant in Signed::iter() {
+        let abi: i16 = variant.into_abi();
+        let result = unsafe { Signed::from_abi(abi) };
+        assert_eq!(variant, result);
+    }
+}
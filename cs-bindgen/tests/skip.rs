@@ -0,0 +1,81 @@
+//! Tests verifying that skipped fields and methods are excluded from the bindings.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{marker::PhantomData, ptr};
+
+/// A cache that can't be exported, since it doesn't implement `Abi`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ShantenCache {
+    shanten: Option<u8>,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hand {
+    pub tiles: u8,
+
+    #[cs_bindgen(skip)]
+    pub cache: ShantenCache,
+
+    pub riichi: bool,
+}
+
+#[cs_bindgen]
+pub struct Player {
+    points: i32,
+}
+
+#[cs_bindgen]
+impl Player {
+    pub fn new(points: i32) -> Self {
+        Player { points }
+    }
+
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+
+    // Neither generic methods nor arguments that aren't exportable are supported, so
+    // this would fail to compile without `skip`.
+    #[cs_bindgen(skip)]
+    pub fn score_with<T>(&self, _cache: ShantenCache, _marker: PhantomData<T>) -> i32 {
+        self.points
+    }
+}
+
+#[test]
+fn skipped_field_uses_default() {
+    let hand = Hand {
+        tiles: 13,
+        cache: ShantenCache { shanten: Some(1) },
+        riichi: true,
+    };
+
+    let result = unsafe { Hand::from_abi(hand.into_abi()) };
+    assert_eq!(13, result.tiles);
+    assert_eq!(ShantenCache::default(), result.cache);
+    assert!(result.riichi);
+}
+
+#[test]
+fn skipped_field_not_described() {
+    let export = unsafe { (*__cs_bindgen_describe__Hand()).into_string() };
+    assert!(export.contains(r#""tiles""#), "{}", export);
+    assert!(export.contains(r#""riichi""#), "{}", export);
+    assert!(!export.contains("cache"), "{}", export);
+}
+
+#[test]
+fn exported_methods_unaffected_by_skip() {
+    let player = Player::new(25_000);
+    let points = unsafe {
+        __cs_bindgen_generated__points__Player(<&Player as Abi>::into_abi(&player), ptr::null_mut())
+    };
+    assert_eq!(25_000, points);
+
+    // The skipped method is still a normal method in Rust.
+    assert_eq!(
+        25_000,
+        player.score_with::<u8>(ShantenCache::default(), PhantomData)
+    );
+}
@@ -0,0 +1,41 @@
+//! Tests verifying that `&str` arguments are decoded correctly from UTF-16.
+
+use cs_bindgen::abi::{RawSlice, StrArg};
+use pretty_assertions::assert_eq;
+
+fn decode(value: &str) -> String {
+    let utf16 = value.encode_utf16().collect::<Vec<_>>();
+    let arg = unsafe { StrArg::from_utf16(RawSlice::from(utf16.as_slice())) };
+    arg.as_str().to_owned()
+}
+
+#[test]
+fn empty_str() {
+    assert_eq!("", decode(""));
+}
+
+#[test]
+fn short_str() {
+    assert_eq!("Hello, world!", decode("Hello, world!"));
+}
+
+#[test]
+fn non_ascii_str() {
+    assert_eq!("麻雀🀄", decode("麻雀🀄"));
+}
+
+#[test]
+fn inline_buffer_limit() {
+    // Every character here is 3 bytes of UTF-8 but only one UTF-16 code unit, so it's
+    // the worst case for the inline buffer.
+    for len in 60..70 {
+        let value = "東".repeat(len);
+        assert_eq!(value, decode(&value));
+    }
+}
+
+#[test]
+fn long_str() {
+    let value = "Riichi! ".repeat(100);
+    assert_eq!(value, decode(&value));
+}
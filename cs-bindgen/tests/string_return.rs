@@ -0,0 +1,51 @@
+//! Tests verifying that returned strings are transcoded to UTF-16 correctly.
+
+use cs_bindgen::{
+    abi::{Abi, RawSlice},
+    exports::{__cs_bindgen_string_utf16_len, __cs_bindgen_string_write_utf16},
+};
+use pretty_assertions::assert_eq;
+
+fn transcode(value: &str) -> Vec<u16> {
+    let raw = RawSlice::from(value.as_bytes());
+    let len = unsafe { __cs_bindgen_string_utf16_len(raw) };
+    let mut buffer = vec![0; len];
+    unsafe {
+        __cs_bindgen_string_write_utf16(raw, buffer.as_mut_ptr(), len);
+    }
+    buffer
+}
+
+#[test]
+fn empty_string() {
+    assert_eq!(Vec::<u16>::new(), transcode(""));
+}
+
+#[test]
+fn ascii_string() {
+    let expected = "Tsumo!".encode_utf16().collect::<Vec<_>>();
+    assert_eq!(expected, transcode("Tsumo!"));
+}
+
+#[test]
+fn non_ascii_string() {
+    // Includes a character outside the BMP to verify that surrogate pairs are
+    // counted correctly.
+    let expected = "麻雀🀄".encode_utf16().collect::<Vec<_>>();
+    assert_eq!(4, expected.len());
+    assert_eq!(expected, transcode("麻雀🀄"));
+}
+
+#[test]
+fn interior_nul() {
+    let expected = "東\0南".encode_utf16().collect::<Vec<_>>();
+    assert_eq!(3, expected.len());
+    assert_eq!(expected, transcode("東\0南"));
+}
+
+#[test]
+fn interior_nul_round_trip() {
+    let raw = String::from("riichi\0tsumo\0").into_abi();
+    assert_eq!(13, raw.len);
+    assert_eq!("riichi\0tsumo\0", unsafe { raw.into_string() });
+}
@@ -0,0 +1,44 @@
+//! Tests verifying that the thread-safety of exported types is described correctly.
+
+use cs_bindgen::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+#[cs_bindgen]
+pub struct Scoreboard {
+    _points: Vec<i32>,
+}
+
+#[cs_bindgen]
+pub struct DrawCounter {
+    _draws: Cell<u32>,
+}
+
+#[cs_bindgen]
+pub struct SharedWall {
+    _tiles: Rc<Vec<u8>>,
+}
+
+fn describe(raw: Box<cs_bindgen::abi::RawString>) -> String {
+    unsafe { (*raw).into_string() }
+}
+
+#[test]
+fn send_and_sync() {
+    let export = describe(unsafe { __cs_bindgen_describe__Scoreboard() });
+    assert!(export.contains(r#""is_send":true"#), "{}", export);
+    assert!(export.contains(r#""is_sync":true"#), "{}", export);
+}
+
+#[test]
+fn send_but_not_sync() {
+    let export = describe(unsafe { __cs_bindgen_describe__DrawCounter() });
+    assert!(export.contains(r#""is_send":true"#), "{}", export);
+    assert!(export.contains(r#""is_sync":false"#), "{}", export);
+}
+
+#[test]
+fn neither_send_nor_sync() {
+    let export = describe(unsafe { __cs_bindgen_describe__SharedWall() });
+    assert!(export.contains(r#""is_send":false"#), "{}", export);
+    assert!(export.contains(r#""is_sync":false"#), "{}", export);
+}
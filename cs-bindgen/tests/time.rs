@@ -0,0 +1,83 @@
+//! Tests verifying that `Duration` and `SystemTime` are passed as .NET ticks.
+
+use cs_bindgen::{
+    abi::{Abi, RawString},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{
+    mem::MaybeUninit,
+    ptr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cs_bindgen]
+pub fn extend_turn_timer(remaining: Duration) -> Duration {
+    remaining + Duration::from_secs(5)
+}
+
+#[cs_bindgen]
+pub fn timeout_at(start: SystemTime, timeout: Duration) -> SystemTime {
+    start + timeout
+}
+
+#[test]
+fn duration_as_ticks() {
+    assert_eq!(0, Duration::from_secs(0).into_abi());
+    assert_eq!(15_000_000, Duration::from_millis(1500).into_abi());
+
+    // Precision finer than a tick is truncated.
+    assert_eq!(1, Duration::from_nanos(199).into_abi());
+
+    assert_eq!(Duration::from_millis(1500), unsafe {
+        Duration::from_abi(15_000_000)
+    });
+}
+
+#[test]
+fn system_time_as_ticks_since_epoch() {
+    let time = UNIX_EPOCH + Duration::from_secs(2);
+    assert_eq!(20_000_000, time.into_abi());
+
+    let time = UNIX_EPOCH - Duration::from_secs(2);
+    assert_eq!(-20_000_000, time.into_abi());
+
+    assert_eq!(UNIX_EPOCH - Duration::from_millis(1), unsafe {
+        SystemTime::from_abi(-10_000)
+    });
+}
+
+#[test]
+fn round_trip() {
+    let raw = unsafe {
+        __cs_bindgen_generated__extend_turn_timer(
+            Duration::from_secs(10).into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(Duration::from_secs(15), unsafe { Duration::from_abi(raw) });
+
+    let start = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    let raw = unsafe {
+        __cs_bindgen_generated__timeout_at(
+            start.into_abi(),
+            Duration::from_secs(30).into_abi(),
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(start + Duration::from_secs(30), unsafe {
+        SystemTime::from_abi(raw)
+    });
+}
+
+#[test]
+fn negative_time_span_panics() {
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__extend_turn_timer(-1, &mut panic) };
+    let message = unsafe { panic.into_string() };
+    assert!(
+        message.starts_with("Negative `TimeSpan` can't be converted to a `Duration`"),
+        "{}",
+        message,
+    );
+}
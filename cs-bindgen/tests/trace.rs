@@ -0,0 +1,73 @@
+//! Tests verifying that calls into the binding functions are logged when the `trace`
+//! feature is enabled.
+//!
+//! Only one logger can be installed per process, so everything is tested from a
+//! single test function.
+
+#![cfg(feature = "trace")]
+
+use cs_bindgen::{abi::RawString, exports, prelude::*, trace};
+use std::{cell::RefCell, ffi::c_void, mem::MaybeUninit, ptr};
+
+#[cs_bindgen]
+pub fn count_dora(indicators: u8) -> u8 {
+    indicators * 2
+}
+
+#[cs_bindgen]
+pub fn exhaustive_draw() {
+    panic!("The wall is empty");
+}
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(String, String)>> = RefCell::new(Vec::new());
+}
+
+unsafe extern "C" fn handler(_level: u8, target: RawString, message: RawString) {
+    let record = (target.into_string(), message.into_string());
+    RECORDS.with(|records| records.borrow_mut().push(record));
+}
+
+fn take_messages() -> Vec<String> {
+    RECORDS.with(|records| {
+        records
+            .borrow_mut()
+            .drain(..)
+            .filter(|(target, _)| target == trace::TARGET)
+            .map(|(_, message)| message)
+            .collect()
+    })
+}
+
+#[test]
+fn trace_calls() {
+    let registered = unsafe { exports::__cs_bindgen_set_log_handler(handler as *const c_void, 5) };
+    assert_eq!(1, registered);
+
+    let result = unsafe { __cs_bindgen_generated__count_dora(2, ptr::null_mut()) };
+    assert_eq!(4, result);
+
+    let messages = take_messages();
+    assert_eq!(2, messages.len(), "{:?}", messages);
+    assert_eq!("Calling __cs_bindgen_generated__count_dora", messages[0]);
+    assert!(
+        messages[1].starts_with("__cs_bindgen_generated__count_dora returned after "),
+        "{}",
+        messages[1],
+    );
+
+    // Calls that panic are still reported as returning once the panic is caught.
+    let mut panic = unsafe { MaybeUninit::<RawString>::zeroed().assume_init() };
+    unsafe { __cs_bindgen_generated__exhaustive_draw(&mut panic) };
+    assert!(unsafe { panic.into_string() }.contains("The wall is empty"));
+
+    let messages = take_messages();
+    assert_eq!(2, messages.len(), "{:?}", messages);
+    assert!(
+        messages[1].starts_with("__cs_bindgen_generated__exhaustive_draw returned after "),
+        "{}",
+        messages[1],
+    );
+
+    unsafe { exports::__cs_bindgen_set_log_handler(ptr::null(), 5) };
+}
@@ -0,0 +1,185 @@
+//! Tests verifying that exported traits can be passed as trait objects.
+
+use cs_bindgen::{
+    abi::{Abi, FromManaged, RawManaged, RawString},
+    prelude::*,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::Cell, ffi::c_void, ptr};
+
+#[cs_bindgen]
+pub trait Scorer {
+    fn score(&self, han: u32, fu: u32) -> u32;
+
+    fn name(&self) -> String {
+        "Scorer".into()
+    }
+}
+
+#[cs_bindgen]
+pub struct BasicScorer {
+    pub multiplier: u32,
+}
+
+#[cs_bindgen]
+impl Scorer for BasicScorer {
+    fn score(&self, han: u32, fu: u32) -> u32 {
+        fu * 2u32.pow(han + 2) * self.multiplier
+    }
+}
+
+#[cs_bindgen]
+pub fn score_with_dyn(scorer: &dyn Scorer, han: u32, fu: u32) -> u32 {
+    scorer.score(han, fu)
+}
+
+#[cs_bindgen]
+pub fn score_with_impl(scorer: impl Scorer, han: u32, fu: u32) -> u32 {
+    scorer.score(han, fu)
+}
+
+#[cs_bindgen]
+pub fn score_with_box(scorer: Box<dyn Scorer>, han: u32, fu: u32) -> u32 {
+    scorer.score(han, fu)
+}
+
+#[cs_bindgen]
+pub fn boxed_scorer(multiplier: u32) -> Box<dyn Scorer> {
+    Box::new(BasicScorer { multiplier })
+}
+
+unsafe extern "C" fn release(context: *mut c_void) {
+    let released = &*(context as *const Cell<u32>);
+    released.set(released.get() + 1);
+}
+
+unsafe extern "C" fn managed_score(
+    _context: *mut c_void,
+    han: u32,
+    fu: u32,
+    _threw: *mut u8,
+) -> u32 {
+    han * 100 + fu
+}
+
+unsafe extern "C" fn managed_name(_context: *mut c_void, _threw: *mut u8) -> RawString {
+    String::from("Managed").into()
+}
+
+/// Vtable mimicking the one generated for a C# class deriving from `ScorerBase`.
+static VTABLE: [usize; 3] = [
+    release as usize,
+    managed_score as usize,
+    managed_name as usize,
+];
+
+fn managed_scorer(released: &Cell<u32>) -> RawManaged {
+    RawManaged {
+        vtable: VTABLE.as_ptr() as *const *const c_void,
+        context: released as *const Cell<u32> as *mut c_void,
+    }
+}
+
+#[test]
+fn dyn_round_trip() {
+    let scorer = BasicScorer { multiplier: 2 };
+    let raw = <&dyn Scorer as Abi>::into_abi(&scorer);
+    let result: &dyn Scorer = unsafe { Abi::from_abi(raw) };
+    assert_eq!(1280, result.score(2, 40));
+    assert_eq!("Scorer", result.name());
+}
+
+#[test]
+fn as_dyn_from_handle() {
+    let scorer = BasicScorer { multiplier: 2 };
+    let raw = unsafe { __cs_bindgen_as_dyn__Scorer__BasicScorer(&scorer) };
+    let result: &dyn Scorer = unsafe { Abi::from_abi(raw) };
+    assert_eq!(1280, result.score(2, 40));
+}
+
+#[test]
+fn forwarding_impl() {
+    let scorer = BasicScorer { multiplier: 2 };
+    let scorer: &dyn Scorer = &scorer;
+    assert_eq!(1280, score_with_impl(scorer, 2, 40));
+    assert_eq!(
+        score_with_dyn(scorer, 3, 30),
+        score_with_impl(scorer, 3, 30)
+    );
+}
+
+#[test]
+fn default_method_binding() {
+    let scorer = BasicScorer { multiplier: 2 };
+    let raw = <&dyn Scorer as Abi>::into_abi(&scorer);
+    let name = unsafe { __cs_bindgen_generated__Scorer__name(raw, ptr::null_mut()) };
+    assert_eq!("Scorer", unsafe { name.into_string() });
+}
+
+#[test]
+fn managed_object_forwards_methods() {
+    let released = Cell::new(0);
+    let scorer = unsafe { <dyn Scorer as FromManaged>::from_managed(managed_scorer(&released)) };
+    assert_eq!(240, scorer.score(2, 40));
+    assert_eq!("Managed", scorer.name());
+    assert_eq!(0, released.get());
+
+    drop(scorer);
+    assert_eq!(1, released.get());
+}
+
+#[test]
+fn managed_object_argument() {
+    let released = Cell::new(0);
+    let result = unsafe {
+        __cs_bindgen_generated__score_with_box(managed_scorer(&released), 3, 30, ptr::null_mut())
+    };
+    assert_eq!(330, result);
+    assert_eq!(1, released.get());
+}
+
+#[test]
+fn managed_default_method_binding() {
+    let released = Cell::new(0);
+    let name = unsafe {
+        __cs_bindgen_generated__Scorer__name__default(managed_scorer(&released), ptr::null_mut())
+    };
+    assert_eq!("Scorer", unsafe { name.into_string() });
+
+    // The default binding borrows the object, so it must not be released.
+    assert_eq!(0, released.get());
+}
+
+#[test]
+fn boxed_trait_object_handle() {
+    let handle = unsafe { __cs_bindgen_generated__boxed_scorer(3, ptr::null_mut()) };
+
+    // The handle can be borrowed as a trait object any number of times.
+    for _ in 0..2 {
+        let raw = unsafe { __cs_bindgen_boxed_as_dyn__Scorer(handle) };
+        let scorer: &dyn Scorer = unsafe { Abi::from_abi(raw) };
+        assert_eq!(1920, scorer.score(2, 40));
+    }
+
+    let raw = unsafe { __cs_bindgen_boxed_as_dyn__Scorer(handle) };
+    let name = unsafe { __cs_bindgen_generated__Scorer__name(raw, ptr::null_mut()) };
+    assert_eq!("Scorer", unsafe { name.into_string() });
+
+    unsafe { __cs_bindgen_drop__boxed__Scorer(handle) };
+}
+
+#[test]
+fn boxed_managed_object_is_released() {
+    let released = Cell::new(0);
+    let scorer = unsafe { <dyn Scorer as FromManaged>::from_managed(managed_scorer(&released)) };
+    let handle = scorer.into_abi();
+
+    let raw = unsafe { __cs_bindgen_boxed_as_dyn__Scorer(handle) };
+    let scorer: &dyn Scorer = unsafe { Abi::from_abi(raw) };
+    assert_eq!(330, scorer.score(3, 30));
+    assert_eq!(0, released.get());
+
+    // Dropping the handle drops the box through its vtable.
+    unsafe { __cs_bindgen_drop__boxed__Scorer(handle) };
+    assert_eq!(1, released.get());
+}
@@ -0,0 +1,64 @@
+//! Tests verifying that transparent newtypes are passed as their inner value.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::ptr;
+
+#[cs_bindgen]
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerId(u32);
+
+#[cs_bindgen(transparent, wrapper)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerName {
+    pub name: String,
+}
+
+#[cs_bindgen]
+pub fn next_player(id: PlayerId) -> PlayerId {
+    PlayerId((id.0 + 1) % 4)
+}
+
+#[cs_bindgen]
+pub fn default_name(id: PlayerId) -> PlayerName {
+    PlayerName {
+        name: format!("Player {}", id.0 + 1),
+    }
+}
+
+#[test]
+fn passed_as_inner_value() {
+    assert_eq!(3, PlayerId(3).into_abi());
+    assert_eq!(PlayerId(7), unsafe { PlayerId::from_abi(7) });
+
+    let raw = unsafe { __cs_bindgen_generated__next_player(3, ptr::null_mut()) };
+    assert_eq!(0, raw);
+}
+
+#[test]
+fn round_trip_wrapper() {
+    let raw = unsafe { __cs_bindgen_generated__default_name(1, ptr::null_mut()) };
+    let name = unsafe { PlayerName::from_abi(raw) };
+    assert_eq!("Player 2", name.name);
+
+    let raw = name.clone().into_abi();
+    assert_eq!(name, unsafe { PlayerName::from_abi(raw) });
+}
+
+#[test]
+fn describe_transparent_type() {
+    let export = unsafe { (*__cs_bindgen_describe__PlayerId()).into_string() };
+    assert!(
+        export.contains(r#""binding_style":{"Transparent":{"inner":"U32","wrapper":false}}"#),
+        "{}",
+        export,
+    );
+
+    let export = unsafe { (*__cs_bindgen_describe__PlayerName()).into_string() };
+    assert!(
+        export.contains(r#""binding_style":{"Transparent":{"inner":"String","wrapper":true}}"#),
+        "{}",
+        export,
+    );
+}
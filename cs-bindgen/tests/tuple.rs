@@ -0,0 +1,50 @@
+//! Tests verifying that tuples can be round-tripped through their raw
+//! representation.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructWithTuple {
+    pub pair: (u32, String),
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumWithTuple {
+    Empty,
+    Pair((i8, bool)),
+}
+
+#[test]
+fn pair_round_trip() {
+    let original = (7_i32, String::from("seven"));
+    let result: (i32, String) = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn nested_tuple_round_trip() {
+    let original = ((1_u8, 2_u16), Some(3_u32), vec![4_u64]);
+    let result: ((u8, u16), Option<u32>, Vec<u64>) =
+        unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn struct_tuple_round_trip() {
+    let original = StructWithTuple {
+        pair: (13, String::from("Kokushi musou")),
+    };
+    let result: StructWithTuple = unsafe { Abi::from_abi(original.clone().into_abi()) };
+    assert_eq!(original, result);
+}
+
+#[test]
+fn enum_tuple_round_trip() {
+    for original in vec![EnumWithTuple::Empty, EnumWithTuple::Pair((-1, true))] {
+        let result: EnumWithTuple = unsafe { Abi::from_abi(original.clone().into_abi()) };
+        assert_eq!(original, result);
+    }
+}
@@ -0,0 +1,39 @@
+//! Tests verifying that `Uuid` is passed with the same layout as a C# `Guid`.
+
+#![cfg(feature = "uuid")]
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use pretty_assertions::assert_eq;
+use std::{mem, ptr};
+use uuid::Uuid;
+
+#[cs_bindgen]
+pub fn echo_uuid(id: Uuid) -> Uuid {
+    id
+}
+
+#[test]
+fn guid_layout() {
+    let id = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    let raw = id.into_abi();
+
+    // The first three fields of a `Guid` are stored as little-endian integers.
+    let bytes: [u8; 16] = unsafe { mem::transmute(raw) };
+    assert_eq!(
+        [
+            0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ],
+        bytes,
+    );
+
+    assert_eq!(16, mem::size_of_val(&raw));
+    assert_eq!(4, mem::align_of_val(&raw));
+}
+
+#[test]
+fn round_trip() {
+    let id = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+    let raw = unsafe { __cs_bindgen_generated__echo_uuid(id.into_abi(), ptr::null_mut()) };
+    assert_eq!(id, unsafe { Uuid::from_abi(raw) });
+}
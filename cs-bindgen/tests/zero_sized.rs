@@ -0,0 +1,56 @@
+//! Tests verifying that zero-sized types and fields are marshaled as nothing.
+
+use cs_bindgen::{abi::Abi, prelude::*};
+use std::{marker::PhantomData, mem};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tagged {
+    pub value: u32,
+    pub marker: Marker,
+    pub unit: PhantomData<Marker>,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Seat {
+    East(PhantomData<Marker>),
+    South,
+}
+
+#[test]
+fn zero_sized_struct_has_no_raw_size() {
+    assert_eq!(0, mem::size_of::<<Marker as Abi>::Abi>());
+    assert_eq!(
+        mem::size_of::<u32>(),
+        mem::size_of::<<Tagged as Abi>::Abi>()
+    );
+}
+
+#[test]
+fn phantom_data_is_not_described() {
+    let export = unsafe { (*__cs_bindgen_describe__Tagged()).into_string() };
+    assert!(export.contains("marker"), "{}", export);
+    assert!(!export.contains("unit"), "{}", export);
+}
+
+#[test]
+fn roundtrip_phantom_data() {
+    let tagged = Tagged {
+        value: 7,
+        marker: Marker,
+        unit: PhantomData,
+    };
+    let result = unsafe { Tagged::from_abi(tagged.into_abi()) };
+    assert_eq!(tagged, result);
+
+    // Variants that only contain `PhantomData` don't carry any data, so the enum is
+    // marshaled as its discriminant.
+    let seat = Seat::East(PhantomData);
+    let result = unsafe { Seat::from_abi(seat.into_abi()) };
+    assert_eq!(seat, result);
+}
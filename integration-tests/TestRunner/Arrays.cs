@@ -0,0 +1,49 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Arrays
+    {
+        [Fact]
+        public void IntArrayRoundTrip()
+        {
+            var original = new int[] { 1, 2, 3, 4 };
+            Assert.Equal(original, IntegrationTests.RoundTripIntArray(original));
+        }
+
+        [Fact]
+        public void StringArrayRoundTrip()
+        {
+            var original = new string[] { "East", "South", "West" };
+            Assert.Equal(original, IntegrationTests.RoundTripStringArray(original));
+        }
+
+        [Fact]
+        public void TileArrayRoundTrip()
+        {
+            var original = new SimpleTile[] { new SimpleTile(Suit.Bamboo, 1), new SimpleTile(Suit.Man, 9) };
+            Assert.Equal(original, IntegrationTests.RoundTripTileArray(original));
+        }
+
+        [Fact]
+        public void ArrayArgument()
+        {
+            Assert.Equal(10, IntegrationTests.SumIntArray(new int[] { 1, 2, 3, 4 }));
+        }
+
+        [Fact]
+        public void WrongLength()
+        {
+            Assert.Throws<ArgumentException>(() => IntegrationTests.SumIntArray(new int[] { 1, 2, 3 }));
+        }
+
+        [Fact]
+        public void ArrayFieldRoundTrip()
+        {
+            var original = new SeatScores(new int[] { 25000, 25000, 30000, 20000 });
+            var result = IntegrationTests.RoundTripSeatScores(original);
+            Assert.Equal(original.Scores, result.Scores);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+using System.Threading.Tasks;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Async
+    {
+        [Fact]
+        public async Task ReadyFuture()
+        {
+            Assert.Equal(5, await IntegrationTests.AddAsync(2, 3));
+        }
+
+        [Fact]
+        public async Task PendingFuture()
+        {
+            Assert.Equal("Hello, Riichi!", await IntegrationTests.DelayedGreeting("Riichi", 20));
+        }
+
+        [Fact]
+        public async Task UnitFuture()
+        {
+            Task task = IntegrationTests.AsyncUnit(20);
+            await task;
+            Assert.True(task.IsCompleted);
+        }
+
+        [Fact]
+        public async Task ConcurrentFutures()
+        {
+            var first = IntegrationTests.DelayedGreeting("Tenhou", 50);
+            var second = IntegrationTests.DelayedGreeting("Tsumo", 10);
+            Assert.Equal("Hello, Tsumo!", await second);
+            Assert.Equal("Hello, Tenhou!", await first);
+        }
+
+        [Fact]
+        public async Task ResultFuture()
+        {
+            Assert.Equal(123, await IntegrationTests.ParseNumberAsync("123"));
+
+            var exception = await Assert.ThrowsAsync<RustException<string>>(
+                () => IntegrationTests.ParseNumberAsync("abc"));
+            Assert.Equal("invalid digit found in string", exception.Error);
+        }
+
+        [Fact]
+        public async Task PanicFaultsTask()
+        {
+            var exception = await Assert.ThrowsAsync<RustPanicException>(
+                () => IntegrationTests.AsyncPanic(10));
+            Assert.Equal("Async panic", exception.Message);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Batching
+    {
+        [Fact]
+        public void CallsRunOnSubmit()
+        {
+            IntegrationTests.ResetScore();
+            using (var commands = new CommandBuffer())
+            {
+                IntegrationTests.AdjustScore(commands, 100, false);
+                IntegrationTests.AdjustScore(commands, 250, true);
+                Assert.Equal(2, commands.Count);
+                Assert.Equal(0, IntegrationTests.Score());
+
+                commands.Submit();
+                Assert.Equal(0, commands.Count);
+                Assert.Equal(600, IntegrationTests.Score());
+            }
+        }
+
+        [Fact]
+        public void BatchedMethods()
+        {
+            using (var log = new DiscardLog())
+            using (var commands = new CommandBuffer(16))
+            {
+                // Enough discards for a full hand, which grows the buffer several times.
+                for (byte turn = 0; turn < 72; turn++)
+                {
+                    log.PushDiscard(commands, (byte)(turn % 4), (byte)(turn % 34), turn % 3 == 0);
+                }
+                log.PushDiscard(commands, 0, 5);
+                Assert.Equal(0u, log.Count());
+
+                commands.Submit();
+                Assert.Equal(73u, log.Count());
+                Assert.Equal(24u, log.TsumogiriCount());
+                Assert.Equal(19, log.TilesFor(0).Count);
+                Assert.Equal(5, log.TilesFor(0)[18]);
+            }
+        }
+
+        [Fact]
+        public void PanicSkipsRemainingCalls()
+        {
+            using (var log = new DiscardLog())
+            using (var commands = new CommandBuffer())
+            {
+                log.PushDiscard(commands, 0, 1);
+                log.PushDiscard(commands, 7, 2);
+                log.PushDiscard(commands, 1, 3);
+
+                var exception = Assert.Throws<RustPanicException>(() => commands.Submit());
+                Assert.Contains("Invalid seat 7", exception.Message);
+                Assert.Equal(1u, log.Count());
+
+                // The buffer is cleared even if a call panics.
+                Assert.Equal(0, commands.Count);
+                log.PushDiscard(commands, 1, 3);
+                commands.Submit();
+                Assert.Equal(new List<byte> { 3 }, log.TilesFor(1));
+            }
+        }
+
+        [Fact]
+        public void ClearDiscardsCalls()
+        {
+            using (var log = new DiscardLog())
+            using (var commands = new CommandBuffer())
+            {
+                log.PushDiscard(commands, 0, 1);
+                commands.Clear();
+                commands.Submit();
+                Assert.Equal(0u, log.Count());
+            }
+        }
+
+        [Fact]
+        public void DisposedObjectRetainedUntilSubmit()
+        {
+            var log = new DiscardLog();
+            using (var commands = new CommandBuffer())
+            {
+                log.PushDiscard(commands, 0, 1);
+                log.Dispose();
+
+                // The value isn't dropped until the recorded call has run.
+                commands.Submit();
+                Assert.Throws<ObjectDisposedException>(() => log.PushDiscard(commands, 0, 2));
+                Assert.Equal(0, commands.Count);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Borrowed
+    {
+        private static List<SimpleTile> StartingTiles()
+        {
+            return new List<SimpleTile>
+            {
+                new SimpleTile(Suit.Man, 1),
+                new SimpleTile(Suit.Circles, 5),
+                new SimpleTile(Suit.Bamboo, 9),
+            };
+        }
+
+        [Fact]
+        public void BorrowedSlice()
+        {
+            using (var hand = new ClosedHand(StartingTiles()))
+            {
+                var tiles = hand.Tiles();
+                Assert.Equal(3, tiles.Count);
+                Assert.Equal(new SimpleTile(Suit.Circles, 5), tiles[1]);
+                Assert.Equal(StartingTiles(), tiles.ToList());
+                Assert.Throws<ArgumentOutOfRangeException>(() => tiles[3]);
+
+                var values = hand.Values();
+                Assert.Equal(new byte[] { 1, 5, 9 }, values.ToArray());
+            }
+        }
+
+        [Fact]
+        public void BorrowedSliceInvalidatedOnDispose()
+        {
+            IReadOnlyList<SimpleTile> tiles;
+            using (var hand = new ClosedHand(StartingTiles()))
+            {
+                tiles = hand.Tiles();
+            }
+
+            Assert.Throws<ObjectDisposedException>(() => tiles.Count);
+            Assert.Throws<ObjectDisposedException>(() => tiles[0]);
+        }
+
+        [Fact]
+        public void BorrowedSliceInvalidatedOnMutation()
+        {
+            using (var hand = new ClosedHand(StartingTiles()))
+            {
+                var tiles = hand.Tiles();
+                hand.Draw(new SimpleTile(Suit.Man, 2));
+
+                // Drawing may reallocate the tiles, so the old view can't be used.
+                Assert.Throws<InvalidOperationException>(() => tiles.Count);
+                Assert.Throws<InvalidOperationException>(() => tiles[0]);
+                Assert.Equal(4, hand.Tiles().Count);
+            }
+        }
+
+        [Fact]
+        public void BorrowedHandle()
+        {
+            using (var seat = new Seat(StartingTiles()))
+            {
+                var hand = seat.Hand();
+                Assert.Equal(3, hand.Tiles().Count);
+
+                // Disposing the borrowed object doesn't drop the value owned by the seat.
+                hand.Dispose();
+                Assert.Equal(new SimpleTile(Suit.Man, 1), seat.Hand().Tiles()[0]);
+            }
+        }
+
+        [Fact]
+        public void BorrowedHandleInvalidatedOnDispose()
+        {
+            ClosedHand hand;
+            IReadOnlyList<SimpleTile> tiles;
+            using (var seat = new Seat(StartingTiles()))
+            {
+                hand = seat.Hand();
+                tiles = hand.Tiles();
+            }
+
+            Assert.Throws<ObjectDisposedException>(() => hand.Tiles());
+            Assert.Throws<ObjectDisposedException>(() => tiles.Count);
+        }
+
+        [Fact]
+        public void BorrowedHandleInvalidatedOnMutation()
+        {
+            using (var seat = new Seat(StartingTiles()))
+            {
+                var hand = seat.Hand();
+                var tiles = hand.Tiles();
+                seat.Draw(new SimpleTile(Suit.Bamboo, 3));
+
+                Assert.Throws<InvalidOperationException>(() => hand.Tiles());
+                Assert.Throws<InvalidOperationException>(() => tiles.Count);
+                Assert.Equal(4, seat.Hand().Tiles().Count);
+            }
+        }
+
+        [Fact]
+        public void BorrowedHandleIsReadOnly()
+        {
+            using (var seat = new Seat(StartingTiles()))
+            {
+                var hand = seat.Hand();
+                Assert.Throws<InvalidOperationException>(
+                    () => hand.Draw(new SimpleTile(Suit.Man, 2)));
+                Assert.Throws<InvalidOperationException>(() => IntegrationTests.HandSize(hand));
+                Assert.Equal(3, seat.Hand().Tiles().Count);
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Builders
+    {
+        [Fact]
+        public void ChainConsumingMethods()
+        {
+            using (var builder = new TableRulesBuilder())
+            {
+                var chained = builder.WithPlayers(3).WithRedFives(true);
+
+                // The builder takes ownership of the value returned by each call, so the
+                // chain returns the original object.
+                Assert.Same(builder, chained);
+
+                using (var rules = builder.Build())
+                {
+                    Assert.Equal(3u, rules.Players());
+                    Assert.True(rules.RedFives());
+                    Assert.Equal(25000u, rules.StartingPoints());
+                }
+            }
+        }
+
+        [Fact]
+        public void ChainMutatingMethods()
+        {
+            using (var builder = new TableRulesBuilder())
+            {
+                Assert.Same(builder, builder.StartingPoints(30000).StartingPoints(35000));
+
+                using (var rules = builder.WithPlayers(3).Build())
+                {
+                    Assert.Equal(35000u, rules.StartingPoints());
+                }
+            }
+        }
+
+        [Fact]
+        public void ConsumedBuilderIsDisposed()
+        {
+            var builder = new TableRulesBuilder();
+            using (var rules = builder.Build())
+            {
+                Assert.Equal(4u, rules.Players());
+            }
+
+            Assert.Throws<ObjectDisposedException>(() => builder.WithPlayers(3));
+
+            // Disposing a consumed builder doesn't free the value a second time.
+            builder.Dispose();
+        }
+    }
+}
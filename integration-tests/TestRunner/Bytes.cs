@@ -0,0 +1,44 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Bytes
+    {
+        [Fact]
+        public void ReturnBytes()
+        {
+            Assert.Equal(new byte[] { 1, 2, 3, 4 }, IntegrationTests.ReturnVecU8());
+        }
+
+        [Fact]
+        public void BytesRoundTrip()
+        {
+            var original = new byte[] { 0, 1, 127, 128, 255 };
+            Assert.Equal(original, IntegrationTests.RoundTripBytes(original));
+        }
+
+        [Fact]
+        public void EmptyBytesRoundTrip()
+        {
+            Assert.Empty(IntegrationTests.RoundTripBytes(new byte[0]));
+        }
+
+        [Fact]
+        public void ByteSliceArgument()
+        {
+            var data = new byte[] { 1, 2, 3, 250 };
+            Assert.Equal(256u, IntegrationTests.SumBytes(data));
+            Assert.Equal(5u, IntegrationTests.SumBytes(new ReadOnlySpan<byte>(data, 1, 2)));
+            Assert.Equal(0u, IntegrationTests.SumBytes(ReadOnlySpan<byte>.Empty));
+        }
+
+        [Fact]
+        public void CopyByteSlice()
+        {
+            var data = new byte[1024];
+            new Random(1234).NextBytes(data);
+            Assert.Equal(data, IntegrationTests.CopyBytes(data));
+        }
+    }
+}
@@ -0,0 +1,66 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Callbacks
+    {
+        [Fact]
+        public void FuncCallback()
+        {
+            Assert.Equal(14, IntegrationTests.ApplyCallback(7, value => value * 2));
+        }
+
+        [Fact]
+        public void ActionCallback()
+        {
+            var count = 0;
+            IntegrationTests.InvokeNTimes(3, () => count += 1);
+            Assert.Equal(3, count);
+        }
+
+        [Fact]
+        public void MutableCallback()
+        {
+            var events = new List<(uint, string)>();
+            IntegrationTests.EmitEvents(2, (index, message) => events.Add((index, message)));
+
+            Assert.Equal(new List<(uint, string)> { (0, "Event 0"), (1, "Event 1") }, events);
+        }
+
+        [Fact]
+        public void StructArgumentCallback()
+        {
+            var tiles = new List<SimpleTile>
+            {
+                new SimpleTile(Suit.Man, 3),
+                new SimpleTile(Suit.Bamboo, 9),
+                new SimpleTile(Suit.Man, 1),
+            };
+
+            Assert.Equal(2u, IntegrationTests.FilterTiles(tiles, tile => tile.Suit == Suit.Man));
+        }
+
+        [Fact]
+        public void StringReturnCallback()
+        {
+            Assert.Equal("Ron!", IntegrationTests.CallbackResultOrDefault(() => "Ron!"));
+            Assert.Equal("default", IntegrationTests.CallbackResultOrDefault(() => ""));
+        }
+
+        [Fact]
+        public void CallbackException()
+        {
+            var exception = Assert.Throws<InvalidOperationException>(
+                () => IntegrationTests.ApplyCallback(7, value => throw new InvalidOperationException("Chombo!")));
+            Assert.Equal("Chombo!", exception.Message);
+        }
+
+        [Fact]
+        public void NullCallback()
+        {
+            Assert.Throws<ArgumentNullException>(() => IntegrationTests.ApplyCallback(7, null));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+using System.Threading;
+using System.Threading.Tasks;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Cancellation
+    {
+        [Fact]
+        public void CompletesWithoutCancellation()
+        {
+            Assert.Equal(5u, IntegrationTests.CountUntilCancelled(5, CancellationToken.None));
+        }
+
+        [Fact]
+        public void AlreadyCancelled()
+        {
+            var token = new CancellationToken(true);
+            Assert.Null(IntegrationTests.CountUntilCancelled(10_000, token));
+        }
+
+        [Fact]
+        public void CancelFromAnotherThread()
+        {
+            using (var source = new CancellationTokenSource())
+            {
+                source.CancelAfter(20);
+                Assert.Null(IntegrationTests.CountUntilCancelled(10_000, source.Token));
+            }
+        }
+
+        [Fact]
+        public async Task CancelAsyncCall()
+        {
+            using (var source = new CancellationTokenSource())
+            {
+                var task = IntegrationTests.CountUntilCancelledAsync(10_000, source.Token);
+                source.Cancel();
+                Assert.Null(await task);
+            }
+        }
+    }
+}
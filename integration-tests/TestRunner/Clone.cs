@@ -0,0 +1,43 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Clone
+    {
+        [Fact]
+        public void CloneHandle()
+        {
+            using (var state = new GameState())
+            {
+                state.Discard("1m");
+
+                using (var snapshot = state.Clone())
+                {
+                    Assert.NotSame(state, snapshot);
+                    Assert.Equal(1u, snapshot.Turn());
+
+                    // Modifying the clone doesn't affect the original.
+                    snapshot.Discard("9p");
+                    Assert.Equal(2u, snapshot.Turn());
+                    Assert.Equal(1u, state.Turn());
+                    Assert.Equal(new[] { "1m" }, state.Discards());
+                }
+
+                // The original is still valid after the clone is disposed.
+                Assert.Equal(new[] { "1m" }, state.Discards());
+            }
+        }
+
+        [Fact]
+        public void CloneAsICloneable()
+        {
+            using (var state = new GameState())
+            {
+                var clone = ((ICloneable)state).Clone();
+                Assert.IsType<GameState>(clone);
+                ((GameState)clone).Dispose();
+            }
+        }
+    }
+}
@@ -99,5 +99,35 @@ namespace TestRunner
                 ReturnDataEnumList();
             }
         }
+
+        [Fact]
+        public void StringListRoundTrip()
+        {
+            var expected = new List<string>() { "foo", "bar", "" };
+            var actual = IntegrationTests.RoundTripStringVec(expected);
+            Assert.Equal(expected, actual);
+        }
+
+        [Fact]
+        public void NestedListRoundTrip()
+        {
+            var expected = new List<List<string>>()
+            {
+                new List<string>() { "foo", "bar" },
+                new List<string>(),
+                new List<string>() { "baz" },
+            };
+            var actual = IntegrationTests.RoundTripNestedVec(expected);
+            Assert.Equal(expected, actual);
+        }
+
+        [Fact]
+        public void NestedListManyTimes()
+        {
+            for (var count = 0; count < 100_000; count += 1)
+            {
+                NestedListRoundTrip();
+            }
+        }
     }
 }
@@ -0,0 +1,40 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Constants
+    {
+        [Fact]
+        public void PrimitiveConstants()
+        {
+            Assert.Equal(14, IntegrationTests.MaxHandSize);
+            Assert.Equal(136u, IntegrationTests.TilesetSize);
+            Assert.Equal(25000, IntegrationTests.StartingPoints);
+            Assert.Equal(0.04f, IntegrationTests.RiichiDepositRatio);
+            Assert.True(IntegrationTests.AllowOpenTanyao);
+        }
+
+        [Fact]
+        public void StringConstant()
+        {
+            Assert.Equal("Tenhou \"default\"", IntegrationTests.RulesetName);
+        }
+
+        [Fact]
+        public void StructConstant()
+        {
+            var wallBreak = IntegrationTests.DefaultWallBreak;
+            Assert.Equal(1, wallBreak.Wall);
+            Assert.Equal(7, wallBreak.Offset);
+        }
+
+        [Fact]
+        public void AssociatedConstants()
+        {
+            Assert.Equal(14, WallBreak.DeadWallSize);
+            Assert.Equal(0, WallBreak.First.Wall);
+            Assert.Equal(0, WallBreak.First.Offset);
+            Assert.Equal(4u, RuleSet.MaxRedFives);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Conversions
+    {
+        [Fact]
+        public void ExplicitTryFromConversion()
+        {
+            var tile = (CompactTile)(byte)10;
+            Assert.Equal(1, tile.Suit);
+            Assert.Equal(2, tile.Rank);
+        }
+
+        [Fact]
+        public void FailedTryFromConversionThrows()
+        {
+            Assert.Throws<InvalidCastException>(() => (CompactTile)(byte)34);
+        }
+
+        [Fact]
+        public void ImplicitFromConversionIntoPrimitive()
+        {
+            byte id = new CompactTile { Suit = 3, Rank = 7 };
+            Assert.Equal(33, id);
+        }
+
+        [Fact]
+        public void ImplicitFromConversionIntoHandle()
+        {
+            Points points = 8000;
+            Assert.Equal(8000, points.Value());
+        }
+    }
+}
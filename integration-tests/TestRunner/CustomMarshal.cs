@@ -0,0 +1,43 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+// Converts the hex string that game IDs are passed as into a `Guid`.
+public static class GameIdConverter
+{
+    public static Guid FromRaw(string raw) => Guid.ParseExact(raw, "N");
+
+    public static string IntoRaw(Guid value) => value.ToString("N");
+}
+
+namespace TestRunner
+{
+    public class CustomMarshal
+    {
+        [Fact]
+        public void ReturnCustomType()
+        {
+            var id = IntegrationTests.FirstGameId();
+            Assert.Equal(Guid.Parse("00000000000000000000000000000001"), id);
+        }
+
+        [Fact]
+        public void PassCustomType()
+        {
+            var id = Guid.Parse("000000000000000000000000000000ff");
+            Assert.Equal(
+                Guid.Parse("00000000000000000000000000000100"),
+                IntegrationTests.NextGameId(id));
+        }
+
+        [Fact]
+        public void ListOfCustomType()
+        {
+            var ids = IntegrationTests.GameIds(3);
+            Assert.Equal(3, ids.Count);
+            Assert.Equal(Guid.Parse("00000000000000000000000000000003"), ids[2]);
+
+            Assert.Equal(ids[2], IntegrationTests.NewestGameId(ids));
+        }
+    }
+}
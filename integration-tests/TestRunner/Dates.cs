@@ -0,0 +1,49 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Dates
+    {
+        [Fact]
+        public void ReturnDate()
+        {
+            // The test project targets a framework without `DateOnly`, so dates are
+            // exposed as a `DateTime` at midnight.
+            Assert.Equal(new DateTime(2020, 9, 13), IntegrationTests.TournamentStart());
+        }
+
+        [Fact]
+        public void PassDate()
+        {
+            Assert.Equal(19, IntegrationTests.DaysUntil(new DateTime(2020, 9, 13), new DateTime(2020, 10, 2)));
+            Assert.Equal(-1, IntegrationTests.DaysUntil(new DateTime(2000, 1, 1), new DateTime(1999, 12, 31)));
+
+            Assert.True(IntegrationTests.IsWeekend(new DateTime(2020, 9, 13)));
+            Assert.False(IntegrationTests.IsWeekend(new DateTime(2020, 9, 14)));
+        }
+
+        [Fact]
+        public void ReturnDateTime()
+        {
+            var expected = new DateTimeOffset(2020, 9, 13, 12, 30, 0, TimeSpan.Zero);
+            Assert.Equal(expected, IntegrationTests.MatchScheduledAt());
+        }
+
+        [Fact]
+        public void PassDateTimeAndDuration()
+        {
+            var scheduled = new DateTimeOffset(2020, 9, 13, 21, 30, 0, TimeSpan.FromHours(9));
+            Assert.Equal(
+                scheduled.AddMinutes(15),
+                IntegrationTests.DelayMatch(scheduled, TimeSpan.FromMinutes(15)));
+        }
+
+        [Fact]
+        public void NegativeDuration()
+        {
+            Assert.Equal(TimeSpan.FromSeconds(-90), IntegrationTests.PenaltyTime(TimeSpan.FromSeconds(90)));
+            Assert.Equal(TimeSpan.FromSeconds(3), IntegrationTests.PenaltyTime(TimeSpan.FromSeconds(-3)));
+        }
+    }
+}
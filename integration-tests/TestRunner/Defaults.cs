@@ -0,0 +1,28 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Defaults
+    {
+        [Fact]
+        public void DefaultHandleConstructor()
+        {
+            using (var state = new RoundState())
+            {
+                Assert.Equal(0u, state.Round());
+                Assert.Equal(0u, state.Honba());
+
+                state.AddHonba();
+                Assert.Equal(1u, state.Honba());
+            }
+        }
+
+        [Fact]
+        public void DefaultValueProperty()
+        {
+            var points = StartingPoints.Default;
+            Assert.Equal(25000, points.Points);
+            Assert.Equal(30000, points.Target);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Diagnostics
+    {
+        private static long LiveDiscardPiles()
+        {
+            return RustDiagnostics.LiveHandles()
+                .Where(count => count.TypeName.EndsWith("::DiscardPile"))
+                .Select(count => count.Live)
+                .SingleOrDefault();
+        }
+
+        [Fact]
+        public void CountLiveHandles()
+        {
+            var before = LiveDiscardPiles();
+
+            using (var pile = new DiscardPile())
+            {
+                pile.Discard("9s");
+                Assert.Equal(before + 1, LiveDiscardPiles());
+            }
+
+            Assert.Equal(before, LiveDiscardPiles());
+        }
+
+        [Fact]
+        public void CaptureCreationStackTraces()
+        {
+            RustDiagnostics.CaptureStackTraces = true;
+            try
+            {
+                using (var pile = new DiscardPile())
+                {
+                    var undisposed = RustDiagnostics.UndisposedHandles();
+                    Assert.Contains(
+                        undisposed,
+                        handle => handle.Key == "DiscardPile"
+                            && handle.Value.ToString().Contains(nameof(CaptureCreationStackTraces)));
+                }
+
+                Assert.DoesNotContain(RustDiagnostics.UndisposedHandles(), handle => handle.Key == "DiscardPile");
+            }
+            finally
+            {
+                RustDiagnostics.CaptureStackTraces = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Display
+    {
+        [Fact]
+        public void HandleToString()
+        {
+            using (var score = new Score(3, 30))
+            {
+                Assert.Equal("3 han 30 fu", score.ToString());
+                Assert.Equal("Score: 3 han 30 fu", $"Score: {score}");
+            }
+        }
+
+        [Fact]
+        public void ValueToString()
+        {
+            var tile = new DisplayTile(1, 5);
+            Assert.Equal("5p", tile.ToString());
+        }
+    }
+}
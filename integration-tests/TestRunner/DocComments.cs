@@ -0,0 +1,41 @@
+using Xunit;
+
+namespace TestRunner
+{
+    // NOTE: These tests mainly verify that the generated doc comments don't break
+    // compilation of the generated bindings.
+    public class DocComments
+    {
+        [Fact]
+        public void DocumentedFunction()
+        {
+            Assert.Equal(136u, IntegrationTests.FullTileCount());
+        }
+
+        [Fact]
+        public void DocumentedStruct()
+        {
+            var score = new HandScore(2, 30);
+            Assert.Equal(480u, IntegrationTests.ScorePoints(score));
+        }
+
+        [Fact]
+        public void DocumentedHandle()
+        {
+            using (var pond = new Pond())
+            {
+                pond.Discard(5);
+                pond.Discard(7);
+                Assert.Equal(2u, pond.Count);
+            }
+        }
+
+        [Fact]
+        public void DocumentedEnums()
+        {
+            Assert.NotEqual(CallKind.Chi, CallKind.Kan);
+            Assert.Equal(5, IntegrationTests.ClaimedTile(new Call.Open(5)));
+            Assert.Equal(0, IntegrationTests.ClaimedTile(new Call.Closed()));
+        }
+    }
+}
@@ -26,6 +26,32 @@ namespace TestRunner
             }
         }
 
+        [Fact]
+        public void ReprEnumUnderlyingType()
+        {
+            Assert.Equal(typeof(byte), Enum.GetUnderlyingType(typeof(SeatWind)));
+            Assert.Equal(typeof(short), Enum.GetUnderlyingType(typeof(ScoreAdjustment)));
+            Assert.Equal(1, (byte)SeatWind.East);
+            Assert.Equal(-8000, (short)ScoreAdjustment.Chombo);
+        }
+
+        [Fact]
+        public void ReprEnumRoundTrip()
+        {
+            Assert.Equal(SeatWind.South, IntegrationTests.NextSeatWind(SeatWind.East));
+            Assert.Equal(SeatWind.East, IntegrationTests.NextSeatWind(SeatWind.North));
+        }
+
+        [Fact]
+        public void ReprEnumInStruct()
+        {
+            var score = new SeatScore(SeatWind.West, ScoreAdjustment.Chombo, 25000);
+            var result = IntegrationTests.ApplyAdjustment(score);
+            Assert.Equal(SeatWind.West, result.Wind);
+            Assert.Equal(ScoreAdjustment.Chombo, result.Adjustment);
+            Assert.Equal(17000, result.Points);
+        }
+
         [Fact]
         public void GenerateDataEnum()
         {
@@ -59,5 +85,50 @@ namespace TestRunner
                 Assert.Equal(orig.Value, result.Value);
             }
         }
+
+        [Fact]
+        public void MatchDataEnum()
+        {
+            IDataEnum value = IntegrationTests.GenerateDataEnum();
+            var description = value.Match(
+                onFoo: foo => "foo",
+                onBar: bar => bar.Element0,
+                onBaz: baz => baz.Name + " " + baz.Value,
+                onCoolness: coolness => "coolness",
+                onNestedStruct: nested => "nested");
+            Assert.Equal("Randal 11", description);
+        }
+
+        [Fact]
+        public void MatchDataEnumWithActions()
+        {
+            IDataEnum value = new DataEnum.Bar() { Element0 = "bar" };
+            string matched = null;
+            value.Match(
+                foo => { matched = "foo"; },
+                bar => { matched = bar.Element0; },
+                baz => { matched = "baz"; },
+                coolness => { matched = "coolness"; },
+                nested => { matched = "nested"; });
+            Assert.Equal("bar", matched);
+        }
+
+        [Fact]
+        public void VisitDataEnum()
+        {
+            var visitor = new DescribeVisitor();
+            Assert.Equal("Foo", new DataEnum.Foo().Accept(visitor));
+            Assert.Equal("Bar(cool)", new DataEnum.Bar() { Element0 = "cool" }.Accept(visitor));
+            Assert.Equal("Baz(Randal)", IntegrationTests.GenerateDataEnum().Accept(visitor));
+        }
+
+        private class DescribeVisitor : IDataEnumVisitor<string>
+        {
+            public string VisitFoo(DataEnum.Foo value) => "Foo";
+            public string VisitBar(DataEnum.Bar value) => "Bar(" + value.Element0 + ")";
+            public string VisitBaz(DataEnum.Baz value) => "Baz(" + value.Name + ")";
+            public string VisitCoolness(DataEnum.Coolness value) => "Coolness";
+            public string VisitNestedStruct(DataEnum.NestedStruct value) => "NestedStruct";
+        }
     }
 }
@@ -0,0 +1,66 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Equality
+    {
+        [Fact]
+        public void HandleEquality()
+        {
+            using (var first = new Yaku("riichi", 1))
+            using (var second = new Yaku("riichi", 1))
+            using (var third = new Yaku("pinfu", 1))
+            {
+                Assert.NotSame(first, second);
+                Assert.Equal(first, second);
+                Assert.True(first == second);
+                Assert.False(first != second);
+                Assert.NotEqual(first, third);
+                Assert.True(first != third);
+                Assert.False(first == null);
+                Assert.False(first.Equals(null));
+            }
+        }
+
+        [Fact]
+        public void HandleHashing()
+        {
+            using (var first = new Yaku("riichi", 1))
+            using (var second = new Yaku("riichi", 1))
+            {
+                Assert.Equal(first.GetHashCode(), second.GetHashCode());
+
+                var set = new HashSet<Yaku> { first };
+                Assert.Contains(second, set);
+            }
+        }
+
+        [Fact]
+        public void ValueEquality()
+        {
+            var first = new Position(1, 2);
+            var second = new Position(1, 2);
+            var third = new Position(2, 1);
+
+            Assert.Equal(first, second);
+            Assert.True(first == second);
+            Assert.True(first != third);
+            Assert.Equal(first.GetHashCode(), second.GetHashCode());
+
+            var counts = new Dictionary<Position, int> { { first, 1 } };
+            Assert.Equal(1, counts[second]);
+        }
+
+        [Fact]
+        public void CustomValueEquality()
+        {
+            var discard = new Discard(5, false);
+            var riichiDiscard = new Discard(5, true);
+
+            Assert.Equal(discard, riichiDiscard);
+            Assert.NotEqual(discard, new Discard(6, false));
+            Assert.Equal(discard.GetHashCode(), riichiDiscard.GetHashCode());
+        }
+    }
+}
@@ -0,0 +1,39 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class ErrorHook
+    {
+        [Fact]
+        public void ReportPanics()
+        {
+            var reports = new List<(string, string)>();
+
+            // NOTE: Tests in other classes may panic in parallel, so the handler may also
+            // receive their reports.
+            RustErrorHook.SetHandler((message, backtrace) =>
+            {
+                lock (reports)
+                {
+                    reports.Add((message, backtrace));
+                }
+            });
+
+            try
+            {
+                var exception = Assert.Throws<RustPanicException>(() => IntegrationTests.CallInvalidKan("1z"));
+                Assert.Equal("Can't call kan on 1z", exception.Message);
+
+                lock (reports)
+                {
+                    Assert.Contains(reports, report => report.Item1 == "Can't call kan on 1z" && report.Item2 != "");
+                }
+            }
+            finally
+            {
+                RustErrorHook.SetHandler(null);
+            }
+        }
+    }
+}
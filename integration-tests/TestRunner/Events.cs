@@ -0,0 +1,62 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Events
+    {
+        [Fact]
+        public void PollRaisesReceived()
+        {
+            using (var game = new EventfulGame())
+            using (var events = game.Events())
+            {
+                var received = new List<IGameEvent>();
+                events.Received += (sender, e) => received.Add(e);
+
+                Assert.Equal(0, events.Poll());
+
+                game.Discard("5m");
+                Assert.Equal(2, events.Poll());
+
+                var discarded = (GameEvent.Discarded)received[0];
+                Assert.Equal(0, discarded.Seat);
+                Assert.Equal("5m", discarded.Tile);
+
+                var turn = (GameEvent.TurnStarted)received[1];
+                Assert.Equal(1, turn.Seat);
+            }
+        }
+
+        [Fact]
+        public void DrainPendingEvents()
+        {
+            using (var game = new EventfulGame())
+            using (var events = game.Events())
+            {
+                game.Discard("East");
+                game.End();
+
+                var pending = events.Drain();
+                Assert.Equal(3, pending.Count);
+                Assert.IsType<GameEvent.Ended>(pending[2]);
+                Assert.Empty(events.Drain());
+            }
+        }
+
+        [Fact]
+        public void QueuesShareEvents()
+        {
+            using (var game = new EventfulGame())
+            using (var first = game.Events())
+            using (var second = game.Events())
+            {
+                game.End();
+
+                Assert.True(second.TryNext(out IGameEvent e));
+                Assert.IsType<GameEvent.Ended>(e);
+                Assert.False(first.TryNext(out _));
+            }
+        }
+    }
+}
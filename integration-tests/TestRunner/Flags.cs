@@ -0,0 +1,58 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Flags
+    {
+        [Fact]
+        public void FlagsMembers()
+        {
+            Assert.Equal(0u, (uint)RuleFlags.None);
+            Assert.Equal(1u, (uint)RuleFlags.RedFives);
+            Assert.Equal(2u, (uint)RuleFlags.OpenTanyao);
+            Assert.Equal(4u, (uint)RuleFlags.DoubleRon);
+            Assert.Equal(RuleFlags.RedFives | RuleFlags.OpenTanyao, RuleFlags.Tenhou);
+            Assert.Equal(typeof(uint), Enum.GetUnderlyingType(typeof(RuleFlags)));
+            Assert.True(Attribute.IsDefined(typeof(RuleFlags), typeof(FlagsAttribute)));
+        }
+
+        [Fact]
+        public void ExplicitNoneMember()
+        {
+            Assert.Equal(0, (byte)WaitFlags.None);
+            Assert.Equal(typeof(byte), Enum.GetUnderlyingType(typeof(WaitFlags)));
+        }
+
+        [Fact]
+        public void FlagsConstant()
+        {
+            Assert.Equal(RuleFlags.Tenhou, IntegrationTests.DefaultRules);
+        }
+
+        [Fact]
+        public void PassFlags()
+        {
+            Assert.True(IntegrationTests.HasRedFives(RuleFlags.Tenhou));
+            Assert.False(IntegrationTests.HasRedFives(RuleFlags.OpenTanyao));
+            Assert.Equal(
+                RuleFlags.Tenhou | RuleFlags.DoubleRon,
+                IntegrationTests.EnableDoubleRon(RuleFlags.Tenhou));
+            Assert.Equal(
+                RuleFlags.DoubleRon,
+                IntegrationTests.EnableDoubleRon(RuleFlags.None));
+        }
+
+        [Fact]
+        public void FlagsList()
+        {
+            var waits = new List<WaitFlags>
+            {
+                WaitFlags.Ryanmen | WaitFlags.Kanchan,
+                WaitFlags.Penchan,
+            };
+            Assert.Equal(3u, IntegrationTests.WaitCount(waits));
+        }
+    }
+}
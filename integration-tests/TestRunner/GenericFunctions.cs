@@ -0,0 +1,30 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class GenericFunctions
+    {
+        [Fact]
+        public void Overloads()
+        {
+            Assert.Equal("[7]", IntegrationTests.FormatValue(7));
+            Assert.Equal("[Chun]", IntegrationTests.FormatValue("Chun"));
+            Assert.Equal("[true]", IntegrationTests.FormatValue(true));
+        }
+
+        [Fact]
+        public void SequenceOverloads()
+        {
+            Assert.Equal(6u, IntegrationTests.SumValues(new List<uint> { 1, 2, 3 }));
+            Assert.Equal(1.5, IntegrationTests.SumValues(new List<double> { 0.5, 1.0 }));
+        }
+
+        [Fact]
+        public void MultipleTypeParameters()
+        {
+            Assert.Equal("Dora: 3", IntegrationTests.FormatPair("Dora", 3u));
+            Assert.Equal("3: Dora", IntegrationTests.FormatPair(3u, "Dora"));
+        }
+    }
+}
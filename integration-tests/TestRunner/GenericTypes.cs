@@ -0,0 +1,41 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class GenericTypes
+    {
+        [Fact]
+        public void Instantiations()
+        {
+            using (var draws = new HistoryU32(5))
+            using (var calls = new HistoryString("chi"))
+            {
+                Assert.Equal(5u, draws.Last());
+                draws.Push(7);
+                Assert.Equal(2u, draws.Count());
+                Assert.Equal(7u, draws.Last());
+
+                calls.Push("pon");
+                Assert.Equal(2u, calls.Count());
+                Assert.Equal("pon", calls.Last());
+            }
+        }
+
+        [Fact]
+        public void EnumInstantiations()
+        {
+            Assert.Equal(new List<uint>(), IntegrationTests.ClaimedTiles(new ClaimU32.Pass()));
+            Assert.Equal(
+                new List<uint> { 4, 4, 4 },
+                IntegrationTests.ClaimedTiles(new ClaimU32.Pon { Element0 = 4 }));
+            Assert.Equal(
+                new List<string> { "2m", "3m", "4m" },
+                IntegrationTests.ClaimedTiles(
+                    new ClaimString.Chi { Tiles = new List<string> { "2m", "3m", "4m" } }));
+
+            var pon = (ClaimString.Pon)IntegrationTests.PonClaim("7p");
+            Assert.Equal("7p", pon.Element0);
+        }
+    }
+}
@@ -35,6 +35,43 @@ namespace TestRunner
             Assert.Equal("Hello, Test!", result);
         }
 
+        [Fact]
+        public void StrArg()
+        {
+            Assert.Equal("Hello, Test!", IntegrationTests.StrArg("Test"));
+            Assert.Equal("Hello, !", IntegrationTests.StrArg(""));
+        }
+
+        [Fact]
+        public void StrArgNonAscii()
+        {
+            Assert.Equal("Hello, 麻雀🀄!", IntegrationTests.StrArg("麻雀🀄"));
+            Assert.Equal(3u, IntegrationTests.StrCharCount("麻雀🀄"));
+        }
+
+        [Fact]
+        public void StrArgLong()
+        {
+            var value = new string('東', 1000);
+            Assert.Equal(1000u, IntegrationTests.StrCharCount(value));
+            Assert.Equal($"Hello, {value}!", IntegrationTests.StrArg(value));
+        }
+
+        [Fact]
+        public void EmptyStringReturn()
+        {
+            Assert.Equal("", IntegrationTests.EmptyString());
+            Assert.Equal("", IntegrationTests.EmptyString());
+        }
+
+        [Fact]
+        public void InteriorNulRoundTrip()
+        {
+            Assert.Equal("East\0South", IntegrationTests.JoinWithNul("East", "South"));
+            Assert.Equal("\0\0\0", IntegrationTests.JoinWithNul("\0", "\0"));
+            Assert.Equal(5u, IntegrationTests.StrCharCount("a\0b\0c"));
+        }
+
         [Fact]
         public void StringArgRepeated()
         {
@@ -62,6 +99,28 @@ namespace TestRunner
             }
         }
 
+        [Fact]
+        public void ExplicitConstructor()
+        {
+            using (PersonInfo info = new PersonInfo("David", 12, 7, "Riichi Road"))
+            using (Address address = info.Address())
+            {
+                Assert.Equal("David", info.Name());
+                Assert.Equal(7u, address.StreetNumber());
+                Assert.Equal("Riichi Road", address.StreetName());
+            }
+        }
+
+        [Fact]
+        public void StaticFactoryMethod()
+        {
+            using (PersonInfo info = PersonInfo.Anonymous())
+            {
+                Assert.Equal("Anonymous", info.Name());
+                Assert.Equal(30, info.Age());
+            }
+        }
+
         [Fact]
         public void CreateManyPersonInfo()
         {
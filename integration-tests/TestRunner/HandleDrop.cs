@@ -0,0 +1,74 @@
+using System;
+using System.Runtime.CompilerServices;
+using Xunit;
+
+namespace TestRunner
+{
+    public class HandleDrop
+    {
+        [Fact]
+        public void DisposeDropsOnce()
+        {
+            var before = IntegrationTests.RiichiDropCount();
+
+            var riichi = new Riichi(0);
+            riichi.Dispose();
+            riichi.Dispose();
+
+            Assert.Equal(before + 1, IntegrationTests.RiichiDropCount());
+        }
+
+        [Fact]
+        public void PassingByValueTransfersOwnership()
+        {
+            var before = IntegrationTests.RiichiDropCount();
+
+            var riichi = new Riichi(1);
+            IntegrationTests.ConsumeRiichi(riichi);
+            Assert.Equal(before + 1, IntegrationTests.RiichiDropCount());
+
+            // The object no longer owns the value, so disposing it doesn't drop it again.
+            riichi.Dispose();
+            Assert.Equal(before + 1, IntegrationTests.RiichiDropCount());
+            Assert.Throws<ObjectDisposedException>(() => IntegrationTests.ConsumeRiichi(riichi));
+        }
+
+        [Fact]
+        public void FinalizerDropsForgottenHandle()
+        {
+            var before = IntegrationTests.RiichiDropCount();
+
+            CreateAndForget();
+            GC.Collect();
+            GC.WaitForPendingFinalizers();
+
+            Assert.True(IntegrationTests.RiichiDropCount() > before);
+        }
+
+        [Fact]
+        public void ReceiverKeptAliveDuringCall()
+        {
+            Assert.False(CallOnTemporary());
+        }
+
+        // NOTE: Nothing references the object once the call has started, so the GC
+        // would finalize it during the callback if the wrapper didn't keep it alive.
+        [MethodImpl(MethodImplOptions.NoInlining)]
+        private static bool CallOnTemporary()
+        {
+            return new Riichi(3).DroppedDuring(() =>
+            {
+                GC.Collect();
+                GC.WaitForPendingFinalizers();
+            });
+        }
+
+        // NOTE: Creating the object in a separate, non-inlined method ensures that no
+        // reference to it remains on the stack when the GC runs.
+        [MethodImpl(MethodImplOptions.NoInlining)]
+        private static void CreateAndForget()
+        {
+            new Riichi(2);
+        }
+    }
+}
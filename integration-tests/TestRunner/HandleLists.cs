@@ -0,0 +1,46 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class HandleLists
+    {
+        [Fact]
+        public void ReturnList()
+        {
+            var players = IntegrationTests.SeatPlayers(new List<string> { "East", "South", "West" });
+
+            Assert.Equal(new[] { "East", "South", "West" }, players.Select(player => player.Name()));
+            Assert.All(players, player => Assert.Equal(25000, player.Points()));
+
+            foreach (var player in players)
+            {
+                player.Dispose();
+            }
+        }
+
+        [Fact]
+        public void EmptyList()
+        {
+            var players = IntegrationTests.SeatPlayers(new List<string>());
+            Assert.Empty(players);
+        }
+
+        [Fact]
+        public void PassingListTransfersOwnership()
+        {
+            var players = new List<TablePlayer> { new TablePlayer("East"), new TablePlayer("North") };
+            var result = IntegrationTests.CollectRiichiSticks(players);
+
+            Assert.Equal(new[] { "East", "North" }, result.Select(player => player.Name()));
+            Assert.All(result, player => Assert.Equal(24000, player.Points()));
+
+            // The original objects no longer own their values.
+            Assert.All(players, player => Assert.Throws<ObjectDisposedException>(() => player.Points()));
+
+            Assert.Equal(48000, IntegrationTests.TotalPoints(result));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Indexers
+    {
+        [Fact]
+        public void ReadWriteIndexer()
+        {
+            using (var hand = new Hand(new byte[] { 1, 2, 3 }))
+            {
+                Assert.Equal(2, hand[1]);
+                hand[1] = 7;
+                Assert.Equal(7, hand[1]);
+            }
+        }
+
+        [Fact]
+        public void NegativeIndexThrows()
+        {
+            using (var hand = new Hand(new byte[] { 1, 2, 3 }))
+            {
+                Assert.Throws<ArgumentOutOfRangeException>(() => hand[-1]);
+            }
+        }
+
+        [Fact]
+        public void ReadOnlyIndexer()
+        {
+            using (var pile = new DiscardPile(new List<string> { "1m", "9p", "east" }))
+            {
+                Assert.Equal("1m", pile[0]);
+                Assert.Equal("east", pile[2u]);
+            }
+        }
+    }
+}
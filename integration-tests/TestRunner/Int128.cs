@@ -0,0 +1,49 @@
+using System;
+using System.Numerics;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Int128
+    {
+        private static readonly BigInteger MaxU128 = (BigInteger.One << 128) - 1;
+
+        [Fact]
+        public void ReturnU128()
+        {
+            Assert.Equal(MaxU128, IntegrationTests.MaxU128());
+        }
+
+        [Fact]
+        public void PassI128()
+        {
+            var value = BigInteger.One << 100;
+            Assert.Equal(-value, IntegrationTests.NegateI128(value));
+            Assert.Equal(value, IntegrationTests.NegateI128(-value));
+            Assert.Equal(BigInteger.One, IntegrationTests.NegateI128(BigInteger.MinusOne));
+        }
+
+        [Fact]
+        public void OptionalU128()
+        {
+            Assert.Equal(BigInteger.One << 100, IntegrationTests.CheckedDoubleU128(BigInteger.One << 99));
+            Assert.Null(IntegrationTests.CheckedDoubleU128(MaxU128));
+        }
+
+        [Fact]
+        public void OutOfRangeThrows()
+        {
+            Assert.Throws<OverflowException>(() => IntegrationTests.CheckedDoubleU128(BigInteger.MinusOne));
+            Assert.Throws<OverflowException>(() => IntegrationTests.NegateI128(BigInteger.One << 127));
+        }
+
+        [Fact]
+        public void StructField()
+        {
+            var wager = new Wager(-(BigInteger.One << 70), 2);
+            var doubled = IntegrationTests.DoubleStake(wager);
+            Assert.Equal(-(BigInteger.One << 71), doubled.Stake);
+            Assert.Equal(2, doubled.Seat);
+        }
+    }
+}
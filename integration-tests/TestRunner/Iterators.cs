@@ -0,0 +1,77 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Iterators
+    {
+        [Fact]
+        public void EnumeratePrimitives()
+        {
+            var items = new List<uint>();
+            foreach (var item in IntegrationTests.CountUp(4))
+            {
+                items.Add(item);
+            }
+
+            Assert.Equal(new uint[] { 0, 1, 2, 3 }, items);
+        }
+
+        [Fact]
+        public void EnumerateStrings()
+        {
+            Assert.Equal(
+                new string[] { "East", "South", "West", "North" },
+                IntegrationTests.WindNames().ToArray());
+        }
+
+        [Fact]
+        public void EmptyIterator()
+        {
+            Assert.Empty(IntegrationTests.CountUp(0));
+        }
+
+        [Fact]
+        public void StopEarly()
+        {
+            // The enumerator is disposed by `Take` before the Rust iterator is
+            // exhausted, which drops the iterator.
+            Assert.Equal(new uint[] { 0, 1 }, IntegrationTests.CountUp(100).Take(2).ToArray());
+        }
+
+        [Fact]
+        public void EnumerateOnce()
+        {
+            var items = IntegrationTests.CountUp(3);
+            Assert.Equal(3, items.Count());
+            Assert.Throws<InvalidOperationException>(() => items.Count());
+        }
+
+        [Fact]
+        public void MethodIterator()
+        {
+            using (var wall = new Wall(4))
+            {
+                Assert.Equal(new byte[] { 3, 2, 1, 0 }, wall.Draws().ToArray());
+            }
+        }
+
+        [Fact]
+        public void PanicInIterator()
+        {
+            var items = new List<uint>();
+            var exception = Assert.Throws<RustPanicException>(() =>
+            {
+                foreach (var item in IntegrationTests.PanickingIterator(2))
+                {
+                    items.Add(item);
+                }
+            });
+
+            Assert.Equal("Iterated too far", exception.Message);
+            Assert.Equal(new uint[] { 0, 1 }, items);
+        }
+    }
+}
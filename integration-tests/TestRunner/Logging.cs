@@ -0,0 +1,86 @@
+using System.Collections.Generic;
+using System.Threading;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Logging
+    {
+        [Fact]
+        public void ForwardRecords()
+        {
+            var records = new List<(RustLogLevel, string, string)>();
+            Assert.True(RustLogger.SetHandler(
+                (level, target, message) => records.Add((level, target, message)),
+                RustLogLevel.Debug));
+
+            try
+            {
+                IntegrationTests.LogDiscard(2, "7p");
+
+                var record = Assert.Single(records);
+                Assert.Equal((RustLogLevel.Info, "mahjong", "Seat 2 discarded 7p"), record);
+            }
+            finally
+            {
+                RustLogger.SetHandler(null);
+            }
+        }
+
+        [Fact]
+        public void HandlerExceptionsAreDiscarded()
+        {
+            Assert.True(RustLogger.SetHandler((level, target, message) => throw new System.Exception("Oops")));
+
+            try
+            {
+                IntegrationTests.LogDiscard(0, "East");
+            }
+            finally
+            {
+                RustLogger.SetHandler(null);
+            }
+        }
+
+        [Fact]
+        public void DispatchRecordsToContext()
+        {
+            var context = new InlineContext();
+            var contexts = new List<SynchronizationContext>();
+            Assert.True(RustLogger.SetHandler((level, target, message) => contexts.Add(SynchronizationContext.Current)));
+            RustCallbacks.Context = context;
+
+            try
+            {
+                IntegrationTests.LogDiscard(1, "Red dragon");
+
+                var recordContext = Assert.Single(contexts);
+                Assert.Same(context, recordContext);
+            }
+            finally
+            {
+                RustCallbacks.Context = null;
+                RustLogger.SetHandler(null);
+            }
+        }
+
+        // Runs posted callbacks immediately, with the context set as the current context
+        // so that the test can tell that the callback was dispatched.
+        private class InlineContext : SynchronizationContext
+        {
+            public override void Post(SendOrPostCallback callback, object state)
+            {
+                var previous = Current;
+                SetSynchronizationContext(this);
+                try
+                {
+                    callback(state);
+                }
+                finally
+                {
+                    SetSynchronizationContext(previous);
+                }
+            }
+        }
+    }
+}
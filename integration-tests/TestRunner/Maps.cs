@@ -0,0 +1,74 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Maps
+    {
+        [Fact]
+        public void StringMapRoundTrip()
+        {
+            var original = new Dictionary<string, int>
+            {
+                { "East", 25000 },
+                { "South", -8000 },
+                { "West", 32000 },
+            };
+
+            Assert.Equal(original, IntegrationTests.RoundTripStringMap(original));
+        }
+
+        [Fact]
+        public void EmptyMapRoundTrip()
+        {
+            Assert.Empty(IntegrationTests.RoundTripStringMap(new Dictionary<string, int>()));
+        }
+
+        [Fact]
+        public void BTreeMapRoundTrip()
+        {
+            var original = new Dictionary<uint, string>
+            {
+                { 1, "Riichi" },
+                { 13, "Kokushi musou" },
+            };
+
+            Assert.Equal(original, IntegrationTests.RoundTripBtreeMap(original));
+        }
+
+        [Fact]
+        public void MapArgument()
+        {
+            var scores = new Dictionary<string, int>
+            {
+                { "East", 1 },
+                { "South", 2 },
+                { "West", 3 },
+            };
+
+            Assert.Equal(6, IntegrationTests.SumMapValues(scores));
+        }
+
+        [Fact]
+        public void MapWithStructKeys()
+        {
+            var bamboo = new SimpleTile(Suit.Bamboo, 1);
+            var coins = new SimpleTile(Suit.Man, 5);
+            var counts = IntegrationTests.TileCounts(new List<SimpleTile>() { bamboo, coins, bamboo });
+
+            Assert.Equal(2, counts.Count);
+            Assert.Equal(2u, counts[bamboo]);
+            Assert.Equal(1u, counts[coins]);
+        }
+
+        [Fact]
+        public void MapRoundTripManyTimes()
+        {
+            var original = new Dictionary<string, int> { { "North", 0 } };
+            for (var count = 0; count < 10_000; count += 1)
+            {
+                Assert.Equal(original, IntegrationTests.RoundTripStringMap(original));
+            }
+        }
+    }
+}
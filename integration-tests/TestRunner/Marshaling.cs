@@ -0,0 +1,113 @@
+using System.Collections.Generic;
+using System.Text.Json.Serialization;
+using Xunit;
+
+// `Wind` is serialized as the name of the variant, which doesn't have a generated
+// equivalent in C#, so the type is provided here instead.
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum Wind
+{
+    East,
+    South,
+    West,
+    North,
+}
+
+namespace TestRunner
+{
+    public class Marshaling
+    {
+        [Fact]
+        public void ReturnMarshaledType()
+        {
+            var snapshot = IntegrationTests.StartingSnapshot(new List<string>() { "Ron", "Tsumo" });
+
+            Assert.Equal(Wind.East, snapshot.RoundWind);
+            Assert.Equal(0, snapshot.Honba);
+            Assert.Null(snapshot.RiichiSticks);
+            Assert.Equal(new List<string>() { "5m" }, snapshot.DoraIndicators);
+
+            Assert.Equal(2, snapshot.Players.Count);
+            Assert.Equal("Tsumo", snapshot.Players[1].Name);
+            Assert.Equal(Wind.South, snapshot.Players[1].Seat);
+            Assert.Equal(new List<byte>() { 1, 1, 9 }, snapshot.Players[1].Hand);
+
+            Assert.Equal(25000, snapshot.Scores["Ron"]);
+        }
+
+        [Fact]
+        public void PassMarshaledType()
+        {
+            var snapshot = IntegrationTests.StartingSnapshot(new List<string>() { "Ron", "Tsumo" });
+            snapshot.Players[0].Points = 33000;
+            snapshot.RiichiSticks = 2;
+
+            Assert.Equal(58000, IntegrationTests.TotalPoints(snapshot));
+
+            var next = IntegrationTests.NextHonba(snapshot);
+            Assert.Equal(1, next.Honba);
+            Assert.Equal(2u, next.RiichiSticks);
+            Assert.Equal(33000, next.Players[0].Points);
+        }
+
+        [Fact]
+        public void ConstructMarshaledType()
+        {
+            var snapshot = new GameSnapshot()
+            {
+                RoundWind = Wind.South,
+                Players = new List<PlayerState>()
+                {
+                    new PlayerState()
+                    {
+                        Name = "Ron",
+                        Seat = Wind.West,
+                        Points = 12000,
+                        Hand = new List<byte>(),
+                    },
+                },
+                DoraIndicators = new List<string>(),
+                Scores = new Dictionary<string, int>(),
+            };
+
+            Assert.Equal(12000, IntegrationTests.TotalPoints(snapshot));
+            Assert.Null(IntegrationTests.Dealer(snapshot));
+        }
+
+        [Fact]
+        public void OptionalMarshaledType()
+        {
+            var snapshot = IntegrationTests.StartingSnapshot(new List<string>() { "Ron" });
+            var dealer = IntegrationTests.Dealer(snapshot);
+            Assert.NotNull(dealer);
+            Assert.Equal("Ron", dealer.Name);
+        }
+
+        [Fact]
+        public void ReturnMessagePackType()
+        {
+            var record = IntegrationTests.RecordGame(3);
+
+            Assert.Equal(3, record.Rounds.Count);
+            Assert.Equal(2, record.Rounds[2].Dealer);
+            Assert.Equal((byte?)0, record.Rounds[0].Winner);
+            Assert.Null(record.Rounds[1].Winner);
+            Assert.Equal("9s", record.Rounds[1].Discards[0][1]);
+            Assert.Equal(25000, record.FinalScores[3]);
+        }
+
+        [Fact]
+        public void PassMessagePackType()
+        {
+            var record = IntegrationTests.RecordGame(2);
+            Assert.Equal(6u, IntegrationTests.CountDiscards(record));
+
+            record.Rounds.Add(new RoundRecord()
+            {
+                Dealer = 2,
+                Discards = new List<List<string>>() { new List<string>() { "5p" } },
+            });
+            Assert.Equal(7u, IntegrationTests.CountDiscards(record));
+        }
+    }
+}
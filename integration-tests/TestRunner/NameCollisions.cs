@@ -0,0 +1,37 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class NameCollisions
+    {
+        [Fact]
+        public void DuplicateConstructorIsFactory()
+        {
+            using (var wall = new LiveWall())
+            using (var empty = LiveWall.Empty())
+            {
+                Assert.Equal(4u, wall.Count());
+                Assert.Equal(0u, empty.Count());
+            }
+        }
+
+        [Fact]
+        public void MethodNamedAfterClassIsSuffixed()
+        {
+            using (var wall = new LiveWall())
+            {
+                Assert.Equal(4u, wall.LiveWall2());
+            }
+        }
+
+        [Fact]
+        public void DifferentParametersAreOverloads()
+        {
+            using (var wall = new LiveWall())
+            {
+                Assert.Equal(4u, wall.Count());
+                Assert.Equal(2u, wall.Count(1));
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class NonExhaustive
+    {
+        [Fact]
+        public void RoundTripSimpleEnum()
+        {
+            Assert.Equal(Call.Pon, IntegrationTests.RoundtripCall(Call.Pon));
+            Assert.Equal(Call.Kan, IntegrationTests.RoundtripCall(Call.Kan));
+        }
+
+        [Fact]
+        public void UnknownValueIsDistinct()
+        {
+            Assert.NotEqual(Call.Chi, Call.Unknown);
+            Assert.NotEqual(Call.Pon, Call.Unknown);
+            Assert.NotEqual(Call.Kan, Call.Unknown);
+        }
+
+        [Fact]
+        public void UnknownValueCantBePassedToRust()
+        {
+            Assert.Throws<ArgumentException>(() => IntegrationTests.RoundtripCall(Call.Unknown));
+        }
+
+        [Fact]
+        public void RoundTripDataEnum()
+        {
+            var result = IntegrationTests.RoundtripDiscard(new Discard.Riichi { Tile = 7 });
+            var tile = result.Match(
+                onTile: discard => -1,
+                onRiichi: discard => (int)discard.Tile,
+                onUnknownVariant: discard => -2);
+            Assert.Equal(7, tile);
+        }
+
+        [Fact]
+        public void UnknownVariantCantBePassedToRust()
+        {
+            Assert.ThrowsAny<Exception>(
+                () => IntegrationTests.RoundtripDiscard(new Discard.UnknownVariant()));
+        }
+    }
+}
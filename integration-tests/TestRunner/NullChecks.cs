@@ -0,0 +1,39 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class NullChecks
+    {
+        [Fact]
+        public void NullStringArg()
+        {
+            var error = Assert.Throws<ArgumentNullException>(() => IntegrationTests.StringArg(null));
+            Assert.Equal("arg", error.ParamName);
+        }
+
+        [Fact]
+        public void NullStrArg()
+        {
+            var error = Assert.Throws<ArgumentNullException>(() => IntegrationTests.StrArg(null));
+            Assert.Equal("arg", error.ParamName);
+        }
+
+        [Fact]
+        public void NullHandleRef()
+        {
+            var error = Assert.Throws<ArgumentNullException>(() => IntegrationTests.EventKind(null));
+            Assert.Equal("gameEvent", error.ParamName);
+        }
+
+        [Fact]
+        public void NullConstructorArg()
+        {
+            using (var gameEvent = new Event("discard"))
+            {
+                Assert.Throws<ArgumentNullException>(() => new Event(null));
+                Assert.Equal("discard", IntegrationTests.EventKind(gameEvent));
+            }
+        }
+    }
+}
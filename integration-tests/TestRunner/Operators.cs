@@ -0,0 +1,38 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Operators
+    {
+        [Fact]
+        public void BinaryOperators()
+        {
+            var left = new Payout { Points = 8000 };
+            var right = new Payout { Points = 2000 };
+            Assert.Equal(10000, (left + right).Points);
+            Assert.Equal(6000, (left - right).Points);
+        }
+
+        [Fact]
+        public void UnaryOperator()
+        {
+            var score = new Payout { Points = 1000 };
+            Assert.Equal(-1000, (-score).Points);
+        }
+
+        [Fact]
+        public void OperatorDeclaredOnRightOperand()
+        {
+            var score = new Payout { Points = 2000 };
+            Assert.Equal(6000, (3 * score).Points);
+        }
+
+        [Fact]
+        public void CompoundAssignment()
+        {
+            var score = new Payout { Points = 1000 };
+            score += new Payout { Points = 500 };
+            Assert.Equal(1500, score.Points);
+        }
+    }
+}
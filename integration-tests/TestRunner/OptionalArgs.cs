@@ -0,0 +1,57 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class OptionalArgs
+    {
+        private static readonly byte[] Hand = new byte[] { 1, 2, 3 };
+
+        [Fact]
+        public void DefaultValuesUsed()
+        {
+            Assert.Equal(
+                "3 tiles, 1000 iterations, riichi, aka true, seed None",
+                IntegrationTests.Analyze(Hand));
+        }
+
+        [Fact]
+        public void NamedOptionalArgument()
+        {
+            Assert.Equal(
+                "3 tiles, 50 iterations, riichi, aka false, seed Some(7)",
+                IntegrationTests.Analyze(Hand, iterations: 50, useAka: false, seed: 7));
+            Assert.Equal(
+                "3 tiles, 1000 iterations, sanma, aka true, seed None",
+                IntegrationTests.Analyze(Hand, ruleset: "sanma"));
+        }
+
+        [Fact]
+        public void FloatDefault()
+        {
+            Assert.Equal(12000, IntegrationTests.ScaledPoints(8000));
+            Assert.Equal(16000, IntegrationTests.ScaledPoints(8000, 2f));
+        }
+
+        [Fact]
+        public void OnlyTrailingOptionIsOptional()
+        {
+            Assert.Equal("None 5 None", IntegrationTests.DescribeWait(null, 5));
+            Assert.Equal("Some(4) 5 Some(6)", IntegrationTests.DescribeWait(4, 5, 6));
+        }
+
+        [Fact]
+        public void ConstructorAndMethodDefaults()
+        {
+            using (var simulator = new Simulator())
+            {
+                Assert.Equal("100 iterations, seed None", simulator.Summary());
+                Assert.Equal("East: 100 iterations, seed None", simulator.Summary("East: "));
+            }
+
+            using (var simulator = new Simulator(seed: 42))
+            {
+                Assert.Equal("100 iterations, seed Some(42)", simulator.Summary());
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Options
+    {
+        [Fact]
+        public void OptionIntRoundTrip()
+        {
+            Assert.Equal(7, IntegrationTests.RoundTripOptionI32(7));
+            Assert.Null(IntegrationTests.RoundTripOptionI32(null));
+        }
+
+        [Fact]
+        public void OptionBoolRoundTrip()
+        {
+            Assert.Equal(true, IntegrationTests.RoundTripOptionBool(true));
+            Assert.Equal(false, IntegrationTests.RoundTripOptionBool(false));
+            Assert.Null(IntegrationTests.RoundTripOptionBool(null));
+        }
+
+        [Fact]
+        public void OptionStringRoundTrip()
+        {
+            Assert.Equal("Hello, Option!", IntegrationTests.RoundTripOptionString("Hello, Option!"));
+            Assert.Null(IntegrationTests.RoundTripOptionString(null));
+        }
+
+        [Fact]
+        public void OptionStringRoundTripManyTimes()
+        {
+            for (var count = 0; count < 100_000; count += 1)
+            {
+                OptionStringRoundTrip();
+            }
+        }
+
+        [Fact]
+        public void OptionStructRoundTrip()
+        {
+            var tile = new SimpleTile(Suit.Man, 5);
+            Assert.Equal(tile, IntegrationTests.RoundTripOptionTile(tile));
+            Assert.Null(IntegrationTests.RoundTripOptionTile(null));
+        }
+
+        [Fact]
+        public void OptionHandleReturn()
+        {
+            using (var address = IntegrationTests.FindAddress(123))
+            {
+                Assert.NotNull(address);
+                Assert.Equal(123u, address.StreetNumber());
+            }
+
+            Assert.Null(IntegrationTests.FindAddress(7));
+        }
+
+        [Fact]
+        public void OptionalFieldsRoundTrip()
+        {
+            var original = new OptionalFields(12, null);
+            var result = IntegrationTests.RoundTripOptionalFields(original);
+            Assert.Equal(original, result);
+            Assert.Equal(12, result.Value);
+            Assert.Null(result.Tile);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+using System.Collections.Generic;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Ordering
+    {
+        [Fact]
+        public void HandleComparison()
+        {
+            using (var low = new PlayerScore(100))
+            using (var high = new PlayerScore(25000))
+            {
+                Assert.True(low < high);
+                Assert.True(low <= high);
+                Assert.True(high > low);
+                Assert.True(high >= low);
+                Assert.False(low > high);
+                Assert.Equal(0, low.CompareTo(low));
+                Assert.Equal(1, low.CompareTo(null));
+            }
+        }
+
+        [Fact]
+        public void SortHandles()
+        {
+            var scores = new List<PlayerScore>
+            {
+                new PlayerScore(300),
+                new PlayerScore(-100),
+                new PlayerScore(50),
+            };
+            scores.Sort();
+
+            Assert.Equal(new[] { -100, 50, 300 }, scores.Select(score => score.Points()));
+
+            foreach (var score in scores)
+            {
+                score.Dispose();
+            }
+        }
+
+        [Fact]
+        public void ValueComparison()
+        {
+            var first = new OrderedTile(0, 9);
+            var second = new OrderedTile(1, 1);
+
+            Assert.True(first < second);
+            Assert.True(second >= first);
+            Assert.True(first <= new OrderedTile(0, 9));
+
+            var sorted = new SortedSet<OrderedTile> { second, first };
+            Assert.Equal(new[] { first, second }, sorted);
+        }
+    }
+}
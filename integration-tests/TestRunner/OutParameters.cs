@@ -0,0 +1,41 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class OutParameters
+    {
+        [Fact]
+        public void TrailingElementAsOutParameter()
+        {
+            var tiles = new List<SimpleTile>() { new SimpleTile(Suit.Man, 1), new SimpleTile(Suit.Bamboo, 9) };
+            var shanten = IntegrationTests.ShantenAndUkeire(tiles, out List<SimpleTile> ukeire);
+
+            Assert.Equal(1, shanten);
+            Assert.Equal(new List<SimpleTile>() { new SimpleTile(Suit.Man, 2), new SimpleTile(Suit.Bamboo, 1) }, ukeire);
+        }
+
+        [Fact]
+        public void AllElementsAsOutParameters()
+        {
+            var tiles = new List<SimpleTile>() { new SimpleTile(Suit.Man, 7), new SimpleTile(Suit.Circles, 3) };
+            IntegrationTests.TileRange(tiles, out byte min, out byte max);
+
+            Assert.Equal(3, min);
+            Assert.Equal(7, max);
+        }
+
+        [Fact]
+        public void MethodReturnsRemainingTuple()
+        {
+            using (var pile = new DiscardPile(new List<SimpleTile>() { new SimpleTile(Suit.Man, 5) }))
+            {
+                var (count, isFull) = pile.Counts(out SimpleTile? last);
+
+                Assert.Equal(1u, count);
+                Assert.False(isFull);
+                Assert.Equal(new SimpleTile(Suit.Man, 5), last);
+            }
+        }
+    }
+}
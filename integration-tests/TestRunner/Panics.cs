@@ -0,0 +1,21 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Panics
+    {
+        [Fact]
+        public void PanicMessage()
+        {
+            var exception = Assert.Throws<RustPanicException>(() => IntegrationTests.PanicWithMessage("Oh no!"));
+            Assert.Equal("Oh no!", exception.Message);
+        }
+
+        [Fact]
+        public void RecoverAfterPanic()
+        {
+            Assert.Throws<RustPanicException>(() => IntegrationTests.PanicIf(true));
+            Assert.Equal("Didn't panic", IntegrationTests.PanicIf(false));
+        }
+    }
+}
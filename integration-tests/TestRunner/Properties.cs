@@ -0,0 +1,39 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Properties
+    {
+        [Fact]
+        public void ReadOnlyProperty()
+        {
+            using (var seat = new Seat("East"))
+            {
+                Assert.Equal("East", seat.Wind);
+            }
+        }
+
+        [Fact]
+        public void ReadWriteProperty()
+        {
+            using (var seat = new Seat("South"))
+            {
+                Assert.Equal(25000, seat.Points);
+                seat.Points = 32000;
+                Assert.Equal(32000, seat.Points);
+            }
+        }
+
+        [Fact]
+        public void PrefixedAccessors()
+        {
+            using (var seat = new Seat("West"))
+            {
+                Assert.False(seat.Riichi);
+                seat.Riichi = true;
+                Assert.True(seat.Riichi);
+                Assert.Equal(24000, seat.Points);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Recursive
+    {
+        [Fact]
+        public void PassRecursiveEnum()
+        {
+            // -(1 + -(2)) == 1
+            IExpr expr = new Expr.Neg
+            {
+                Element0 = new Expr.Add
+                {
+                    Lhs = new Expr.Lit { Element0 = 1 },
+                    Rhs = new Expr.Neg { Element0 = new Expr.Lit { Element0 = 2 } },
+                },
+            };
+
+            Assert.Equal(1, IntegrationTests.EvalExpr(expr));
+        }
+
+        [Fact]
+        public void ReturnRecursiveEnum()
+        {
+            var result = (Expr.Neg)IntegrationTests.NegateExpr(new Expr.Lit { Element0 = 7 });
+            var inner = (Expr.Lit)result.Element0;
+            Assert.Equal(7, inner.Element0);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class RefParameters
+    {
+        [Fact]
+        public void PrimitiveRef()
+        {
+            uint remaining = 70;
+            Assert.True(IntegrationTests.DrawFromWall(ref remaining, 4));
+            Assert.Equal(66u, remaining);
+
+            Assert.False(IntegrationTests.DrawFromWall(ref remaining, 100));
+            Assert.Equal(66u, remaining);
+
+            var riichi = false;
+            IntegrationTests.ToggleRiichi(ref riichi);
+            Assert.True(riichi);
+        }
+
+        [Fact]
+        public void EnumRef()
+        {
+            var wind = SeatWind.North;
+            IntegrationTests.RotateSeat(ref wind);
+            Assert.Equal(SeatWind.East, wind);
+        }
+
+        [Fact]
+        public void StructRef()
+        {
+            var counts = new TileCounts(1, 2, 3, 4);
+            Assert.Equal(5, IntegrationTests.AddHonor(ref counts));
+            Assert.Equal(new TileCounts(1, 2, 3, 5), counts);
+        }
+
+        [Fact]
+        public void MultipleRefs()
+        {
+            var payer = new SeatScore(SeatWind.East, ScoreAdjustment.Neutral, 25000);
+            var payee = new SeatScore(SeatWind.South, ScoreAdjustment.Riichi, 25000);
+            IntegrationTests.TransferPoints(ref payer, ref payee, 8000);
+            Assert.Equal(17000, payer.Points);
+            Assert.Equal(33000, payee.Points);
+            Assert.Equal(SeatWind.South, payee.Wind);
+        }
+
+        [Fact]
+        public void RefUnchangedAfterPanic()
+        {
+            var payer = new SeatScore(SeatWind.East, ScoreAdjustment.Neutral, 25000);
+            var payee = new SeatScore(SeatWind.South, ScoreAdjustment.Neutral, 25000);
+            Assert.Throws<RustPanicException>(
+                () => IntegrationTests.TransferPoints(ref payer, ref payee, -1));
+            Assert.Equal(25000, payer.Points);
+            Assert.Equal(25000, payee.Points);
+        }
+
+        [Fact]
+        public void RefInMethod()
+        {
+            using (var wall = new Wall(2))
+            {
+                var counts = new TileCounts(0, 0, 0, 0);
+                wall.DrawInto(ref counts);
+                wall.DrawInto(ref counts);
+                wall.DrawInto(ref counts);
+                Assert.Equal(2, counts.Man);
+                Assert.Equal(0u, wall.Remaining());
+
+                IntegrationTests.ResetWall(wall);
+                Assert.Equal(136u, wall.Remaining());
+            }
+        }
+    }
+}
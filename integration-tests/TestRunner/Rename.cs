@@ -0,0 +1,49 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Rename
+    {
+        [Fact]
+        public void RenamedFunction()
+        {
+            Assert.Equal(8080, IntegrationTests.GetHTTPPort());
+        }
+
+        [Fact]
+        public void RenamedStructAndField()
+        {
+            var roll = new DiceRoll(3, 4);
+            Assert.Equal(3, roll.Base);
+            Assert.Equal(4, roll.Bonus);
+            Assert.Equal(7u, IntegrationTests.RollTotal(roll));
+        }
+
+        [Fact]
+        public void RenamedHandleAndMethod()
+        {
+            using (var gameEvent = new Event("discard"))
+            {
+                Assert.Equal("kind=discard", gameEvent.Params());
+                Assert.Equal("discard", IntegrationTests.EventKind(gameEvent));
+            }
+        }
+
+        [Fact]
+        public void KeywordArgumentName()
+        {
+            using (var gameEvent = new Event("riichi"))
+            {
+                // The Rust argument is named `event`, which is a C# keyword.
+                Assert.Equal("riichi", IntegrationTests.EventKind(@event: gameEvent));
+            }
+        }
+
+        [Fact]
+        public void RenamedVariant()
+        {
+            Assert.Equal(Side.RightSide, IntegrationTests.FlipSide(Side.Left));
+            Assert.Equal(Side.Left, IntegrationTests.FlipSide(Side.RightSide));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Results
+    {
+        [Fact]
+        public void OkResult()
+        {
+            Assert.Equal(123, IntegrationTests.ParseNumber("123"));
+        }
+
+        [Fact]
+        public void ErrResult()
+        {
+            var exception = Assert.Throws<RustException<string>>(() => IntegrationTests.ParseNumber("abc"));
+            Assert.Equal("invalid digit found in string", exception.Error);
+            Assert.Equal("invalid digit found in string", exception.Message);
+        }
+
+        [Fact]
+        public void UnitResult()
+        {
+            IntegrationTests.CheckPositive(5);
+
+            var exception = Assert.Throws<RustException<string>>(() => IntegrationTests.CheckPositive(-5));
+            Assert.Equal("-5 is not positive", exception.Error);
+        }
+
+        [Fact]
+        public void ExportedErrorType()
+        {
+            Assert.Equal("Success!", IntegrationTests.ErrorEnum(false));
+
+            var exception = Assert.Throws<RustException<SimpleCEnum>>(() => IntegrationTests.ErrorEnum(true));
+            Assert.Equal(SimpleCEnum.Baz, exception.Error);
+        }
+
+        [Fact]
+        public void DisplayError()
+        {
+            Assert.Equal(3, IntegrationTests.ParseSeat("3"));
+
+            var exception = Assert.Throws<RustException<string>>(() => IntegrationTests.ParseSeat("East"));
+            Assert.Equal("invalid digit found in string", exception.Error);
+        }
+
+        [Fact]
+        public void ErrResultManyTimes()
+        {
+            for (var count = 0; count < 10_000; count += 1)
+            {
+                Assert.ThrowsAny<RustException>(() => IntegrationTests.ParseNumber("abc"));
+            }
+        }
+    }
+}
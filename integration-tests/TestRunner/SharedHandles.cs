@@ -0,0 +1,60 @@
+using System.Threading.Tasks;
+using Xunit;
+
+namespace TestRunner
+{
+    public class SharedHandles
+    {
+        [Fact]
+        public void CloneSharesValue()
+        {
+            using (var state = new SharedState())
+            using (var shared = state.Clone())
+            {
+                Assert.NotSame(state, shared);
+
+                state.Discard("1m");
+                Assert.Equal(1u, shared.DiscardCount());
+            }
+        }
+
+        [Fact]
+        public void CloneOutlivesOriginal()
+        {
+            var state = new SharedState();
+            var shared = state.Clone();
+
+            state.Discard("9p");
+            state.Dispose();
+
+            Assert.Equal(1u, shared.DiscardCount());
+            shared.Dispose();
+        }
+
+        [Fact]
+        public void ShareAcrossThreads()
+        {
+            using (var state = new SharedState())
+            {
+                var tasks = new Task[4];
+                for (var index = 0; index < tasks.Length; index++)
+                {
+                    var shared = state.Clone();
+                    tasks[index] = Task.Run(() =>
+                    {
+                        using (shared)
+                        {
+                            for (var count = 0; count < 10; count++)
+                            {
+                                shared.Discard("east");
+                            }
+                        }
+                    });
+                }
+
+                Task.WaitAll(tasks);
+                Assert.Equal(40u, state.DiscardCount());
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Skip
+    {
+        [Fact]
+        public void SkippedFieldIsNotExported()
+        {
+            var tenpai = IntegrationTests.MakeTenpai(2, 30);
+            Assert.Equal(2, tenpai.Han);
+            Assert.Equal(30, tenpai.Fu);
+
+            // The skipped field is reset to its default value when passed back to Rust.
+            Assert.Equal(0, IntegrationTests.CachedWaits(tenpai));
+            Assert.Null(typeof(Tenpai).GetField("Cache"));
+        }
+
+        [Fact]
+        public void SkippedMethodIsNotExported()
+        {
+            using (var dealer = new Dealer(3))
+            {
+                Assert.Equal(3u, dealer.Round());
+                Assert.Null(typeof(Dealer).GetMethod("Cache"));
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+using System;
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Slices
+    {
+        [Fact]
+        public void PrimitiveSlice()
+        {
+            Assert.Equal(10, IntegrationTests.SumI32Slice(new int[] { 1, 2, 3, 4 }));
+            Assert.Equal(6.5, IntegrationTests.SumF64Slice(new double[] { 1.5, 2.0, 3.0 }));
+        }
+
+        [Fact]
+        public void EmptySlice()
+        {
+            Assert.Equal(0, IntegrationTests.SumI32Slice(new int[0]));
+            Assert.Equal(0u, IntegrationTests.CountTrue(ReadOnlySpan<bool>.Empty));
+        }
+
+        [Fact]
+        public void PartialSlice()
+        {
+            var values = new int[] { 1, 2, 3, 4 };
+            Assert.Equal(5, IntegrationTests.SumI32Slice(new ReadOnlySpan<int>(values, 1, 2)));
+        }
+
+        [Fact]
+        public void BoolSlice()
+        {
+            Assert.Equal(2u, IntegrationTests.CountTrue(new bool[] { true, false, true }));
+        }
+
+        [Fact]
+        public void StringSlice()
+        {
+            Assert.Equal("East, South", IntegrationTests.JoinStrings(new string[] { "East", "South" }));
+        }
+
+        [Fact]
+        public void StructSlice()
+        {
+            var tiles = new SimpleTile[]
+            {
+                new SimpleTile(Suit.Man, 3),
+                new SimpleTile(Suit.Bamboo, 9),
+                new SimpleTile(Suit.Circles, 1),
+            };
+
+            Assert.Equal(new SimpleTile(Suit.Bamboo, 9), IntegrationTests.HighestTile(tiles));
+            Assert.Null(IntegrationTests.HighestTile(new SimpleTile[0]));
+        }
+
+        [Fact]
+        public void DataEnumSlice()
+        {
+            var values = new IDataEnum[]
+            {
+                new DataEnum.Foo(),
+                new DataEnum.Bar("Cool string"),
+            };
+
+            Assert.Equal(2u, IntegrationTests.CountDataEnums(values));
+        }
+    }
+}
@@ -0,0 +1,31 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class StaticMethods
+    {
+        [Fact]
+        public void StructStaticMethods()
+        {
+            var dice = Dice.FromSeed(13);
+            Assert.Equal(2, dice.First);
+            Assert.Equal(3, dice.Second);
+            Assert.Equal(12, Dice.MaxTotal());
+        }
+
+        [Fact]
+        public void SimpleEnumStaticMethods()
+        {
+            var expected = new List<Dragon>() { Dragon.White, Dragon.Green, Dragon.Red };
+            Assert.Equal(expected, DragonExtensions.All());
+        }
+
+        [Fact]
+        public void DataEnumStaticMethods()
+        {
+            var meld = Assert.IsType<Meld.Pon>(Meld.PonOf(5));
+            Assert.Equal(5, meld.Element0);
+        }
+    }
+}
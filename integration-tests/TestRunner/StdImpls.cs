@@ -0,0 +1,40 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class StdImpls
+    {
+        [Fact]
+        public void HandleImpls()
+        {
+            using (var wall = new DeadWall())
+            {
+                Assert.Equal(14u, wall.Remaining());
+                Assert.Equal("14 tiles remaining", wall.ToString());
+
+                using (var clone = wall.Clone())
+                {
+                    wall.Draw();
+                    Assert.Equal(13u, wall.Remaining());
+                    Assert.Equal(14u, clone.Remaining());
+                }
+            }
+        }
+
+        [Fact]
+        public void ValueImpls()
+        {
+            var first = new Honba(1);
+            var second = new Honba(2);
+
+            Assert.Equal(first, new Honba(1));
+            Assert.NotEqual(first, second);
+            Assert.Equal(first.GetHashCode(), new Honba(1).GetHashCode());
+            Assert.True(first.CompareTo(second) < 0);
+
+            var sorted = new SortedSet<Honba> { second, first };
+            Assert.Equal(new[] { first, second }, sorted);
+        }
+    }
+}
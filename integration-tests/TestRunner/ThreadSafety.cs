@@ -0,0 +1,43 @@
+using System;
+using System.Threading.Tasks;
+using Xunit;
+
+namespace TestRunner
+{
+    public class ThreadSafety
+    {
+        [Fact]
+        public void NonSyncHandleOnOwnerThread()
+        {
+            using (var counter = new TurnCounter())
+            {
+                Assert.Equal(1u, counter.Advance());
+                Assert.Equal(2u, counter.Advance());
+            }
+        }
+
+        [Fact]
+        public void NonSyncHandleOnOtherThreadThrows()
+        {
+            using (var counter = new TurnCounter())
+            {
+                var task = Task.Factory.StartNew(
+                    () => counter.Advance(),
+                    TaskCreationOptions.LongRunning);
+
+                var exception = Assert.Throws<AggregateException>(() => task.Wait());
+                Assert.IsType<InvalidOperationException>(exception.InnerException);
+            }
+        }
+
+        [Fact]
+        public void SyncHandleOnOtherThread()
+        {
+            using (var log = new TurnLog())
+            {
+                Task.Factory.StartNew(() => log.Record(1), TaskCreationOptions.LongRunning).Wait();
+                Assert.Equal(1u, log.TurnCount());
+            }
+        }
+    }
+}
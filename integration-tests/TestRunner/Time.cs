@@ -0,0 +1,49 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Time
+    {
+        [Fact]
+        public void ReturnDuration()
+        {
+            Assert.Equal(TimeSpan.FromMilliseconds(7500), IntegrationTests.TurnTimeLimit());
+        }
+
+        [Fact]
+        public void PassDuration()
+        {
+            Assert.Equal(
+                TimeSpan.FromSeconds(15),
+                IntegrationTests.AddOvertime(TimeSpan.FromSeconds(10), TimeSpan.FromSeconds(5)));
+            Assert.Equal(
+                TimeSpan.FromSeconds(10),
+                IntegrationTests.AddOvertime(TimeSpan.FromSeconds(10), null));
+        }
+
+        [Fact]
+        public void NegativeDurationThrows()
+        {
+            Assert.Throws<RustPanicException>(
+                () => IntegrationTests.AddOvertime(TimeSpan.FromSeconds(-1), null));
+        }
+
+        [Fact]
+        public void ReturnSystemTime()
+        {
+            var expected = DateTimeOffset.FromUnixTimeSeconds(1_600_000_000);
+            Assert.Equal(expected, IntegrationTests.GameStartedAt());
+        }
+
+        [Fact]
+        public void PassSystemTime()
+        {
+            var time = new DateTimeOffset(2020, 9, 13, 21, 26, 40, TimeSpan.FromHours(9));
+            Assert.Equal(time.ToUnixTimeSeconds(), IntegrationTests.SecondsSinceEpoch(time));
+
+            var beforeEpoch = DateTimeOffset.FromUnixTimeSeconds(-60);
+            Assert.Equal(-60, IntegrationTests.SecondsSinceEpoch(beforeEpoch));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+using System;
+using System.Collections.Concurrent;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Tracing
+    {
+        [Fact]
+        public void RecordCalls()
+        {
+            // NOTE: Other tests may be making calls at the same time, so only the records
+            // for the function called by this test are checked.
+            var records = new ConcurrentQueue<(string, string, TimeSpan)>();
+            RustTrace.SetHandler((binding, args, elapsed) => records.Enqueue((binding, args, elapsed)));
+
+            try
+            {
+                Assert.True(IntegrationTests.DeclareRiichi(2, "Nan", new byte[] { 1, 2, 3 }));
+            }
+            finally
+            {
+                RustTrace.SetHandler(null);
+            }
+
+            var (binding, args, duration) = Assert.Single(
+                records.Where(record => record.Item1 == "__cs_bindgen_generated__declare_riichi"));
+            Assert.Equal("seat=2, player=\"Nan\", discards=3 items", args);
+            Assert.True(duration >= TimeSpan.Zero);
+        }
+
+        [Fact]
+        public void HandlerExceptionsAreDiscarded()
+        {
+            RustTrace.SetHandler((binding, args, elapsed) => throw new Exception("Oops"));
+
+            try
+            {
+                Assert.False(IntegrationTests.DeclareRiichi(4, "Pei", new byte[] { 1 }));
+            }
+            finally
+            {
+                RustTrace.SetHandler(null);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Traits
+    {
+        [Fact]
+        public void InterfaceMethods()
+        {
+            using (var bot = new HighestTileBot("Akagi"))
+            {
+                IBot asInterface = bot;
+                Assert.Equal("Akagi", asInterface.Name());
+                Assert.Equal((byte)9, asInterface.ChooseDiscard(new byte[] { 3, 9, 1 }));
+                Assert.Equal("Akagi is ready", asInterface.Greeting());
+            }
+        }
+
+        [Fact]
+        public void DefaultMethod()
+        {
+            using (var bot = new TsumogiriBot())
+            {
+                IBot asInterface = bot;
+                Assert.Equal("Tsumogiri joins the table", asInterface.Greeting());
+            }
+        }
+
+        [Fact]
+        public void InherentMethodWithSameName()
+        {
+            using (var bot = new HighestTileBot("Akagi"))
+            {
+                Assert.Equal("Inherent Akagi", bot.Name());
+                Assert.Equal("Akagi", ((IBot)bot).Name());
+            }
+        }
+
+        [Fact]
+        public void DynArgument()
+        {
+            using (var tsumogiri = new TsumogiriBot())
+            using (var highest = new HighestTileBot("Akagi"))
+            {
+                Assert.Equal("Tsumogiri joins the table", IntegrationTests.BotGreeting(tsumogiri));
+                Assert.Equal("Akagi is ready", IntegrationTests.BotGreeting(highest));
+            }
+        }
+
+        [Fact]
+        public void ImplTraitArgument()
+        {
+            var hand = new byte[] { 3, 9, 1 };
+            using (var tsumogiri = new TsumogiriBot())
+            using (var highest = new HighestTileBot("Akagi"))
+            {
+                Assert.Equal((byte)1, IntegrationTests.BotDiscard(tsumogiri, hand));
+                Assert.Equal((byte)9, IntegrationTests.BotDiscard(highest, hand));
+            }
+        }
+
+        [Fact]
+        public void ReturnedTraitObject()
+        {
+            var hand = new byte[] { 3, 9, 1 };
+            IBot tsumogiri = IntegrationTests.ChooseBot("tsumogiri");
+            IBot highest = IntegrationTests.ChooseBot("Akagi");
+
+            Assert.Equal("Tsumogiri", tsumogiri.Name());
+            Assert.Equal((byte)1, tsumogiri.ChooseDiscard(hand));
+            Assert.Equal("Tsumogiri joins the table", tsumogiri.Greeting());
+
+            Assert.Equal("Akagi", highest.Name());
+            Assert.Equal((byte)9, highest.ChooseDiscard(hand));
+            Assert.Equal("Akagi is ready", highest.Greeting());
+
+            // Returned objects can be passed back to Rust as trait objects.
+            Assert.Equal("Akagi is ready", IntegrationTests.BotGreeting(highest));
+
+            ((IDisposable)tsumogiri).Dispose();
+            ((IDisposable)highest).Dispose();
+        }
+
+        [Fact]
+        public void DisposedTraitObjectThrows()
+        {
+            var bot = IntegrationTests.ChooseBot("tsumogiri");
+            ((IDisposable)bot).Dispose();
+            Assert.Throws<ObjectDisposedException>(() => bot.Name());
+        }
+
+        private class ManagedBot : IBot
+        {
+            public string Name() { return "Managed"; }
+            public byte ChooseDiscard(byte[] hand) { return 0; }
+            public string Greeting() { return "Hello"; }
+        }
+
+        [Fact]
+        public void ManagedImplementationRejected()
+        {
+            Assert.Throws<ArgumentException>(() => IntegrationTests.BotGreeting(new ManagedBot()));
+        }
+
+        private class LowestTileBot : BotBase
+        {
+            public override string Name() { return "Lowest"; }
+            public override byte ChooseDiscard(byte[] hand) { return hand.Min(); }
+        }
+
+        private class PoliteBot : BotBase
+        {
+            public override string Name() { return "Polite"; }
+            public override byte ChooseDiscard(byte[] hand) { return hand[0]; }
+            public override string Greeting() { return "Onegaishimasu"; }
+        }
+
+        private class ThrowingBot : BotBase
+        {
+            public override string Name() { return "Throwing"; }
+
+            public override byte ChooseDiscard(byte[] hand)
+            {
+                throw new InvalidOperationException("No discard");
+            }
+        }
+
+        [Fact]
+        public void ManagedBoxArgument()
+        {
+            var hand = new byte[] { 3, 9, 1 };
+            Assert.Equal((byte)1, IntegrationTests.BoxedBotDiscard(new LowestTileBot(), hand));
+            Assert.Equal((byte)3, IntegrationTests.BoxedBotDiscard(new PoliteBot(), hand));
+        }
+
+        [Fact]
+        public void ManagedDefaultMethod()
+        {
+            Assert.Equal("Lowest joins the table", new LowestTileBot().Greeting());
+            Assert.Equal("Onegaishimasu", new PoliteBot().Greeting());
+        }
+
+        [Fact]
+        public void ManagedObjectsStoredInRust()
+        {
+            using (var table = new Table())
+            {
+                table.AddBot(new LowestTileBot());
+                table.AddBot(new PoliteBot());
+
+                // Collect garbage to verify that Rust keeps the objects alive.
+                GC.Collect();
+                GC.WaitForPendingFinalizers();
+
+                Assert.Equal(
+                    new List<string> { "Lowest joins the table", "Onegaishimasu" },
+                    table.Greetings());
+                Assert.Equal(new byte[] { 1, 3 }, table.Discards(new byte[] { 3, 9, 1 }));
+            }
+        }
+
+        [Fact]
+        public void ManagedExceptionRethrown()
+        {
+            var exception = Assert.Throws<InvalidOperationException>(
+                () => IntegrationTests.BoxedBotDiscard(new ThrowingBot(), new byte[] { 1 }));
+            Assert.Equal("No discard", exception.Message);
+        }
+    }
+}
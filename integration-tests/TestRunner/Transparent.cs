@@ -0,0 +1,38 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Transparent
+    {
+        [Fact]
+        public void PassedAsInnerType()
+        {
+            byte count = IntegrationTests.AddTiles(13, 1);
+            Assert.Equal(14, count);
+        }
+
+        [Fact]
+        public void ReturnWrapper()
+        {
+            var name = IntegrationTests.SeatName(0);
+            Assert.Equal("East", name.Value);
+            Assert.Equal("East", name.ToString());
+            Assert.Equal(new SeatName("East"), name);
+        }
+
+        [Fact]
+        public void PassWrapper()
+        {
+            var name = IntegrationTests.ShoutSeatName(new SeatName("West"));
+            Assert.Equal("WEST", name.Value);
+        }
+
+        [Fact]
+        public void WrapperList()
+        {
+            var names = IntegrationTests.SeatNames();
+            Assert.Equal(4, names.Count);
+            Assert.Equal(new SeatName("North"), names[3]);
+        }
+    }
+}
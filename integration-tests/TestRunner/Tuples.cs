@@ -0,0 +1,36 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class Tuples
+    {
+        [Fact]
+        public void PairRoundTrip()
+        {
+            Assert.Equal((7, "seven"), IntegrationTests.RoundTripPair((7, "seven")));
+        }
+
+        [Fact]
+        public void SwapPair()
+        {
+            Assert.Equal((true, 12), IntegrationTests.SwapPair((12, true)));
+        }
+
+        [Fact]
+        public void TileTripleRoundTrip()
+        {
+            var triple = (new SimpleTile(Suit.Man, 1), new SimpleTile(Suit.Man, 2), new SimpleTile(Suit.Man, 3));
+            Assert.Equal(triple, IntegrationTests.RoundTripTileTriple(triple));
+        }
+
+        [Fact]
+        public void TupleFieldsRoundTrip()
+        {
+            var original = new TupleFields((1.5f, -2.0f), (3, 7));
+            var result = IntegrationTests.RoundTripTupleFields(original);
+
+            Assert.Equal((1.5f, -2.0f), result.Position);
+            Assert.Equal((3u, 7u), result.Range);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+using System.Collections.Generic;
+using Xunit;
+
+namespace TestRunner
+{
+    public class TypeAliases
+    {
+        [Fact]
+        public void PrimitiveAlias()
+        {
+            Assert.Equal(0, IntegrationTests.NextSeatIndex(3));
+            Assert.Equal(2, IntegrationTests.NextSeatIndex(1));
+        }
+
+        [Fact]
+        public void ListAlias()
+        {
+            var tiles = new List<SimpleTile>() { new SimpleTile(Suit.Man, 3), new SimpleTile(Suit.Bamboo, 7), new SimpleTile(Suit.Bamboo, 2) };
+            var sorted = IntegrationTests.SortTiles(tiles);
+
+            Assert.Equal(new List<SimpleTile>() { new SimpleTile(Suit.Bamboo, 2), new SimpleTile(Suit.Bamboo, 7), new SimpleTile(Suit.Man, 3) }, sorted);
+        }
+
+        [Fact]
+        public void OptionAlias()
+        {
+            Assert.Equal((byte)2, IntegrationTests.FindWinner(new List<int>() { 25000, 18000, 32000, 25000 }));
+            Assert.Null(IntegrationTests.FindWinner(new List<int>() { 25000, 25000, 25000, 25000 }));
+        }
+    }
+}
@@ -0,0 +1,29 @@
+using System;
+using Xunit;
+
+namespace TestRunner
+{
+    public class Uuids
+    {
+        [Fact]
+        public void ReturnUuid()
+        {
+            Assert.Equal(Guid.Parse("936da01f-9abd-4d9d-80c7-02af85c822a8"), IntegrationTests.MatchId());
+        }
+
+        [Fact]
+        public void PassUuid()
+        {
+            var id = Guid.Parse("00112233-4455-6677-8899-aabbccddeeff");
+            Assert.Equal("00112233-4455-6677-8899-aabbccddeeff", IntegrationTests.FormatPlayerId(id));
+        }
+
+        [Fact]
+        public void OptionalUuid()
+        {
+            Assert.True(IntegrationTests.IsNilId(null));
+            Assert.True(IntegrationTests.IsNilId(Guid.Empty));
+            Assert.False(IntegrationTests.IsNilId(Guid.NewGuid()));
+        }
+    }
+}
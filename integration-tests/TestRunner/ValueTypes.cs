@@ -1,3 +1,4 @@
+using System.Runtime.InteropServices;
 using Xunit;
 
 namespace TestRunner
@@ -16,5 +17,34 @@ namespace TestRunner
             Assert.Equal(tile.Suit, result.Suit);
             Assert.Equal(tile.Value, result.Value);
         }
+
+        [Fact]
+        public void BlittableStructs()
+        {
+            // Blittable structs are passed to Rust directly, so no raw struct is generated
+            // for them.
+            Assert.True(typeof(SimpleTile).IsLayoutSequential);
+            Assert.True(typeof(HandShape).IsLayoutSequential);
+            Assert.Null(typeof(HandShape).Assembly.GetType(
+                "__integration_tests__copy_types__HandShape__Raw"));
+            Assert.Equal(6, Marshal.SizeOf<TileCounts>());
+        }
+
+        [Fact]
+        public void NestedBlittableStructs()
+        {
+            var shape = new HandShape(
+                new TileCounts(3, 4, 5, 2),
+                new SimpleTile(Suit.Man, 7),
+                2,
+                1.5f);
+            Assert.Equal(14u, IntegrationTests.CountTiles(shape));
+
+            var result = IntegrationTests.ImproveShape(shape);
+            Assert.Equal(shape.Counts, result.Counts);
+            Assert.Equal(shape.LastTile, result.LastTile);
+            Assert.Equal(1, result.Shanten);
+            Assert.Equal(3.0f, result.Ukeire);
+        }
     }
 }
@@ -0,0 +1,28 @@
+using Xunit;
+
+namespace TestRunner
+{
+    public class ZeroSized
+    {
+        [Fact]
+        public void RoundTripStructWithZeroSizedFields()
+        {
+            var meld = new DoraMeld { FirstTile = 3, LastTile = 5 };
+            var result = IntegrationTests.RoundtripDoraMeld(meld);
+            Assert.Equal(3, result.FirstTile);
+            Assert.Equal(5, result.LastTile);
+        }
+
+        [Fact]
+        public void ZeroSizedArgumentsAreSkipped()
+        {
+            Assert.Equal(7u, IntegrationTests.CountAfterDora(3, new Dora(), 4));
+        }
+
+        [Fact]
+        public void ReturnZeroSizedValue()
+        {
+            Assert.Equal(new Dora(), IntegrationTests.MakeDora());
+        }
+    }
+}
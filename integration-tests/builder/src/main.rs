@@ -1,77 +1,118 @@
-use regex::Regex;
-use std::{env, fs, path::Path, process::Command};
+//! Builds the integration test library and generates the C# bindings for the
+//! `TestRunner` project.
+//!
+//! With the `test` argument, the `TestRunner` project is then built and run with
+//! `dotnet test`, which checks that the generated bindings compile and that both the
+//! hand-written and the generated C# tests pass against the native library. Any
+//! further arguments are passed through to `dotnet test`, e.g. to run a subset of the
+//! tests:
+//!
+//! ```text
+//! cargo run -p builder -- test --filter HandleLists
+//! ```
+//!
+//! The test results are written to `target/test-results` in the TRX format, so that
+//! CI can report them, and the process exits with a non-zero status if any step fails.
+
+use std::{
+    env,
+    path::Path,
+    process::{self, Command},
+};
 
 fn main() {
+    let mut args = env::args().skip(1);
+    let run_tests = match args.next().as_deref() {
+        None => false,
+        Some("test") => true,
+        Some(arg) => {
+            eprintln!("Unknown argument `{}`, expected `test`", arg);
+            process::exit(1);
+        }
+    };
+    let dotnet_args = args.collect::<Vec<_>>();
+
     // Get the environment variables set by cargo so that we can put together the right
     // paths regardless of where in the directory structure this is invoked.
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
 
     let wasm_module_path =
         manifest_dir.join("../../target/wasm32-unknown-unknown/debug/integration_tests.wasm");
-    let bindings_path = manifest_dir.join("../TestRunner/Bindings.cs");
+    let test_runner_dir = manifest_dir.join("../TestRunner");
+    let bindings_path = test_runner_dir.join("Bindings.cs");
+    let generated_tests_path = test_runner_dir.join("GeneratedTests.cs");
+    let results_dir = manifest_dir.join("../../target/test-results");
 
     // Build the WASM module for the test project so that we can run it through the
     // cs-bindgen CLI tool.
     println!("Building WASM module for integration-tests:");
-
-    let mut child = Command::new("cargo")
-        .args(&[
+    run(
+        Command::new("cargo").args(&[
             "build",
             "--target=wasm32-unknown-unknown",
             "-p=integration-tests",
-        ])
-        .spawn()
-        .expect("Failed to spawn the build process");
+        ]),
+        "Building the WASM module",
+    );
 
-    let status = child.wait().expect("Failed to finish build process");
-    if !status.success() {
-        panic!("Build process exited with an error");
-    }
+    // Run the code generation script. Along with the bindings, this generates tests
+    // that round trip each value type through its raw representation, which catches
+    // layout mismatches for types that the hand-written tests don't cover.
+    run(
+        Command::new("cargo")
+            .arg("run")
+            .arg("-p=cs-bindgen-cli")
+            .arg("--")
+            .arg(wasm_module_path)
+            .arg("-o")
+            .arg(&bindings_path)
+            // NOTE: This must match the target framework in `TestRunner.csproj`.
+            .arg("--target-framework=netcoreapp2.0")
+            .arg("--thread-checks")
+            .arg("--trace")
+            .arg("--tests")
+            .arg(&generated_tests_path),
+        "Generating the bindings",
+    );
 
-    // Run the code generation script.
-    let mut child = Command::new("cargo")
-        .arg("run")
-        .arg("-p=cs-bindgen-cli")
-        .arg("--")
-        .arg(wasm_module_path)
-        .arg("-o")
-        .arg(&bindings_path)
-        .spawn()
-        .expect("Failed to spawn cs-bindgen process");
+    // Build the actual DLL for the project.
+    run(
+        Command::new("cargo").args(&["build", "-p=integration-tests"]),
+        "Building the dylib",
+    );
 
-    let status = child.wait().expect("Failed to finish codegen process");
-    if !status.success() {
-        panic!("Codegen process finished with an error");
+    if !run_tests {
+        return;
     }
 
-    // Build the actual DLL for the project.
-    let mut child = Command::new("cargo")
-        .args(&["build", "-p=integration-tests"])
-        .spawn()
-        .expect("Failed to spawn the build process");
+    // Compile the generated bindings along with the C# tests and run them against the
+    // dylib, which `TestRunner.csproj` copies into the build directory.
+    println!("Running the C# tests:");
+    run(
+        Command::new("dotnet")
+            .arg("test")
+            .arg("--logger")
+            .arg("trx;LogFileName=integration-tests.trx")
+            .arg("--results-directory")
+            .arg(&results_dir)
+            .args(&dotnet_args)
+            .current_dir(&test_runner_dir),
+        "Running the C# tests",
+    );
+}
+
+/// Runs `command` to completion, exiting with the command's status if it fails.
+fn run(command: &mut Command, step: &str) {
+    let status = match command.status() {
+        Ok(status) => status,
+        Err(err) => {
+            eprintln!("{} failed to start: {}", step, err);
+            process::exit(1);
+        }
+    };
 
-    let status = child.wait().expect("Failed to finish building the dylib");
     if !status.success() {
-        panic!("Dylib build process finished with an error");
+        eprintln!("{} exited with {}", step, status);
+        process::exit(status.code().unwrap_or(1));
     }
-
-    // HACK: Manually insert some new lines into the generated code in order to improve
-    // the formatter's output. For some inexplicable reason the C# formatter won't break
-    // up long lines in all cases, which means that even after running the generated
-    // code through the formatter it's still often unreadable. Notably:
-    //
-    // * If you have multiple statements on the same line, the formatter will leave them
-    //   on the same line.
-    // * It won't break attributes up onto multiple lines, and it won't put a new line
-    //   between an attribute than the item the attribute is attached to, so all of our
-    //   `[DllImport]` declarations are left on one big line even after formatting.
-    //
-    // Fortunately, if we manually insert new lines into those places, the formatter
-    // keeps those new lines in place. So to force the formatter to break up the code
-    // more, we manually insert '/n' after ';' (to break up statements) and ']' (to
-    // break up attributes).
-    let regex = Regex::new(r"(]|;)").unwrap();
-    let file = fs::read_to_string(&bindings_path).unwrap();
-    let result = regex.replace_all(&file, "$1\n");
-    fs::write(&bindings_path, &*result).unwrap();
 }
@@ -0,0 +1,33 @@
+//! Tests verifying that exported type aliases can be used in function signatures.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+/// The index of a player's seat at the table, starting from the dealer.
+#[cs_bindgen]
+pub type SeatIndex = u8;
+
+#[cs_bindgen]
+pub type TileList = Vec<SimpleTile>;
+
+#[cs_bindgen(rename = "WinningSeat")]
+pub type Winner = Option<SeatIndex>;
+
+#[cs_bindgen]
+pub fn next_seat_index(seat: SeatIndex) -> SeatIndex {
+    (seat + 1) % 4
+}
+
+#[cs_bindgen]
+pub fn sort_tiles(mut tiles: TileList) -> TileList {
+    tiles.sort_by_key(|tile| (tile.suit as u8, tile.value));
+    tiles
+}
+
+#[cs_bindgen]
+pub fn find_winner(scores: Vec<i32>) -> Winner {
+    scores
+        .iter()
+        .position(|&score| score >= 30000)
+        .map(|seat| seat as SeatIndex)
+}
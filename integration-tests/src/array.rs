@@ -0,0 +1,35 @@
+//! Tests verifying that fixed-size arrays can be passed to and returned from C#.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn round_trip_int_array(value: [i32; 4]) -> [i32; 4] {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_string_array(value: [String; 3]) -> [String; 3] {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_tile_array(value: [SimpleTile; 2]) -> [SimpleTile; 2] {
+    value
+}
+
+#[cs_bindgen]
+pub fn sum_int_array(value: [i32; 4]) -> i32 {
+    value.iter().sum()
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeatScores {
+    pub scores: [i32; 4],
+}
+
+#[cs_bindgen]
+pub fn round_trip_seat_scores(value: SeatScores) -> SeatScores {
+    value
+}
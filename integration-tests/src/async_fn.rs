@@ -0,0 +1,81 @@
+//! Tests verifying that async functions can be awaited from C#.
+
+use cs_bindgen::prelude::*;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+#[cs_bindgen]
+pub async fn add_async(left: i32, right: i32) -> i32 {
+    left + right
+}
+
+#[cs_bindgen]
+pub async fn delayed_greeting(name: String, delay_ms: u64) -> String {
+    Delay::new(Duration::from_millis(delay_ms)).await;
+    format!("Hello, {}!", name)
+}
+
+#[cs_bindgen]
+pub async fn async_unit(delay_ms: u64) {
+    Delay::new(Duration::from_millis(delay_ms)).await;
+}
+
+#[cs_bindgen]
+pub async fn parse_number_async(value: String) -> Result<i32, String> {
+    value.parse().map_err(|err| format!("{}", err))
+}
+
+#[cs_bindgen]
+pub async fn async_panic(delay_ms: u64) -> u32 {
+    Delay::new(Duration::from_millis(delay_ms)).await;
+    panic!("Async panic");
+}
+
+/// A future that completes after a delay, using a separate thread to wake the task
+/// so that the future is pending when first polled.
+struct Delay {
+    state: Arc<Mutex<DelayState>>,
+}
+
+#[derive(Default)]
+struct DelayState {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        let state = Arc::new(Mutex::new(DelayState::default()));
+        let thread_state = state.clone();
+        thread::spawn(move || {
+            thread::sleep(duration);
+            let mut state = thread_state.lock().unwrap();
+            state.done = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Delay { state }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.done {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
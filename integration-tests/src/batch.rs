@@ -0,0 +1,69 @@
+//! Tests verifying that batched functions can be recorded in a `CommandBuffer` and
+//! submitted with a single call.
+
+use cs_bindgen::prelude::*;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+static SCORE: AtomicI64 = AtomicI64::new(0);
+
+#[cs_bindgen(batch)]
+pub fn adjust_score(delta: i32, doubled: bool) {
+    let delta = if doubled { delta * 2 } else { delta };
+    SCORE.fetch_add(delta as i64, Ordering::SeqCst);
+}
+
+#[cs_bindgen]
+pub fn score() -> i64 {
+    SCORE.load(Ordering::SeqCst)
+}
+
+#[cs_bindgen]
+pub fn reset_score() {
+    SCORE.store(0, Ordering::SeqCst);
+}
+
+/// Records the tiles discarded by each player over the course of a hand.
+#[cs_bindgen]
+pub struct DiscardLog {
+    discards: Vec<(u8, u8, bool)>,
+}
+
+#[cs_bindgen]
+impl DiscardLog {
+    pub fn new() -> Self {
+        DiscardLog {
+            discards: Vec::new(),
+        }
+    }
+
+    /// Records a discard by the player in `seat`.
+    #[cs_bindgen(batch)]
+    pub fn push_discard(
+        &mut self,
+        seat: u8,
+        tile: u8,
+        #[cs_bindgen(default = false)] tsumogiri: bool,
+    ) {
+        assert!(seat < 4, "Invalid seat {}", seat);
+        self.discards.push((seat, tile, tsumogiri));
+    }
+
+    pub fn count(&self) -> u32 {
+        self.discards.len() as u32
+    }
+
+    pub fn tiles_for(&self, seat: u8) -> Vec<u8> {
+        self.discards
+            .iter()
+            .filter(|(discard_seat, _, _)| *discard_seat == seat)
+            .map(|(_, tile, _)| *tile)
+            .collect()
+    }
+
+    pub fn tsumogiri_count(&self) -> u32 {
+        self.discards
+            .iter()
+            .filter(|(_, _, tsumogiri)| *tsumogiri)
+            .count() as u32
+    }
+}
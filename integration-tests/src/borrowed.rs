@@ -0,0 +1,59 @@
+//! Methods returning references that borrow from `self`, which are exposed as views of
+//! the value owned by the C# object.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub struct ClosedHand {
+    tiles: Vec<SimpleTile>,
+    values: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl ClosedHand {
+    pub fn new(tiles: Vec<SimpleTile>) -> ClosedHand {
+        let values = tiles.iter().map(|tile| tile.value).collect();
+        ClosedHand { tiles, values }
+    }
+
+    pub fn tiles(&self) -> &[SimpleTile] {
+        &self.tiles
+    }
+
+    pub fn values(&self) -> &[u8] {
+        &self.values
+    }
+
+    pub fn draw(&mut self, tile: SimpleTile) {
+        self.tiles.push(tile);
+        self.values.push(tile.value);
+    }
+}
+
+#[cs_bindgen]
+pub struct Seat {
+    hand: ClosedHand,
+}
+
+#[cs_bindgen]
+impl Seat {
+    pub fn new(tiles: Vec<SimpleTile>) -> Seat {
+        Seat {
+            hand: ClosedHand::new(tiles),
+        }
+    }
+
+    pub fn hand(&self) -> &ClosedHand {
+        &self.hand
+    }
+
+    pub fn draw(&mut self, tile: SimpleTile) {
+        self.hand.draw(tile);
+    }
+}
+
+#[cs_bindgen]
+pub fn hand_size(hand: ClosedHand) -> u32 {
+    hand.tiles.len() as u32
+}
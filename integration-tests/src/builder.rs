@@ -0,0 +1,70 @@
+//! Tests verifying that builder-style methods generate chainable C# methods.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub struct TableRules {
+    players: u32,
+    starting_points: u32,
+    red_fives: bool,
+}
+
+#[cs_bindgen]
+impl TableRules {
+    pub fn players(&self) -> u32 {
+        self.players
+    }
+
+    pub fn starting_points(&self) -> u32 {
+        self.starting_points
+    }
+
+    pub fn red_fives(&self) -> bool {
+        self.red_fives
+    }
+}
+
+#[cs_bindgen]
+pub struct TableRulesBuilder {
+    players: u32,
+    starting_points: u32,
+    red_fives: bool,
+}
+
+impl Default for TableRulesBuilder {
+    fn default() -> Self {
+        TableRulesBuilder {
+            players: 4,
+            starting_points: 25_000,
+            red_fives: false,
+        }
+    }
+}
+
+#[cs_bindgen]
+impl TableRulesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_players(self, players: u32) -> Self {
+        TableRulesBuilder { players, ..self }
+    }
+
+    pub fn with_red_fives(self, red_fives: bool) -> Self {
+        TableRulesBuilder { red_fives, ..self }
+    }
+
+    pub fn starting_points(&mut self, points: u32) -> &mut Self {
+        self.starting_points = points;
+        self
+    }
+
+    pub fn build(self) -> TableRules {
+        TableRules {
+            players: self.players,
+            starting_points: self.starting_points,
+            red_fives: self.red_fives,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Tests verifying that byte buffers can be passed to and returned from C# without
+//! per-element conversion.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn round_trip_bytes(data: Vec<u8>) -> Vec<u8> {
+    data
+}
+
+#[cs_bindgen]
+pub fn sum_bytes(data: &[u8]) -> u32 {
+    data.iter().map(|&byte| u32::from(byte)).sum()
+}
+
+#[cs_bindgen]
+pub fn copy_bytes(data: &[u8]) -> Vec<u8> {
+    data.to_vec()
+}
@@ -0,0 +1,40 @@
+//! Tests verifying that C# delegates can be passed as callbacks.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn apply_callback(value: i32, callback: impl Fn(i32) -> i32) -> i32 {
+    callback(value)
+}
+
+#[cs_bindgen]
+pub fn invoke_n_times(count: u32, callback: &dyn Fn()) {
+    for _ in 0..count {
+        callback();
+    }
+}
+
+#[cs_bindgen]
+pub fn emit_events(count: u32, on_event: &mut dyn FnMut(u32, String)) {
+    for index in 0..count {
+        on_event(index, format!("Event {}", index));
+    }
+}
+
+#[cs_bindgen]
+pub fn filter_tiles(tiles: Vec<SimpleTile>, predicate: impl Fn(SimpleTile) -> bool) -> u32 {
+    tiles.into_iter().filter(|&tile| predicate(tile)).count() as u32
+}
+
+#[cs_bindgen]
+pub fn callback_result_or_default(callback: impl Fn() -> String) -> String {
+    // NOTE: If the callback throws, the panic is propagated back to C# and the default
+    // value is never returned.
+    let result = callback();
+    if result.is_empty() {
+        "default".into()
+    } else {
+        result
+    }
+}
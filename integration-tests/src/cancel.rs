@@ -0,0 +1,24 @@
+//! Tests verifying that long-running functions can be cancelled from C#.
+
+use cs_bindgen::{cancel::CancelFlag, prelude::*};
+use std::{thread, time::Duration};
+
+/// Counts up until the count is reached or the call is cancelled, returning `None`
+/// if it was cancelled.
+#[cs_bindgen]
+pub fn count_until_cancelled(count: u32, cancel: CancelFlag) -> Option<u32> {
+    for _ in 0..count {
+        if cancel.is_cancelled() {
+            return None;
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    Some(count)
+}
+
+#[cs_bindgen]
+pub async fn count_until_cancelled_async(count: u32, cancel: CancelFlag) -> Option<u32> {
+    count_until_cancelled(count, cancel)
+}
@@ -0,0 +1,30 @@
+//! Tests verifying that handle types implementing `Clone` can be cloned from C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Clone, Default)]
+pub struct GameState {
+    turn: u32,
+    discards: Vec<String>,
+}
+
+#[cs_bindgen]
+impl GameState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn discard(&mut self, tile: String) {
+        self.discards.push(tile);
+        self.turn += 1;
+    }
+
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    pub fn discards(&self) -> Vec<String> {
+        self.discards.clone()
+    }
+}
@@ -84,3 +84,13 @@ pub fn round_trip_data_enum_vec(val: Vec<DataEnum>) -> Vec<DataEnum> {
 pub enum ValueTypeWithCollection {
     Foo { values: Vec<u32> },
 }
+
+#[cs_bindgen]
+pub fn round_trip_string_vec(val: Vec<String>) -> Vec<String> {
+    val
+}
+
+#[cs_bindgen]
+pub fn round_trip_nested_vec(val: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    val
+}
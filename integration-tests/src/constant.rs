@@ -0,0 +1,53 @@
+//! Tests verifying that `const` and `static` items are exported as C# constants.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub const MAX_HAND_SIZE: usize = 14;
+
+#[cs_bindgen]
+pub static TILESET_SIZE: u32 = 136;
+
+#[cs_bindgen]
+pub const STARTING_POINTS: i32 = 25_000;
+
+#[cs_bindgen]
+pub const RIICHI_DEPOSIT_RATIO: f32 = 0.04;
+
+#[cs_bindgen]
+pub const ALLOW_OPEN_TANYAO: bool = true;
+
+#[cs_bindgen]
+pub const RULESET_NAME: &str = "Tenhou \"default\"";
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct WallBreak {
+    pub wall: u8,
+    pub offset: u8,
+}
+
+#[cs_bindgen]
+pub const DEFAULT_WALL_BREAK: WallBreak = WallBreak { wall: 1, offset: 7 };
+
+#[cs_bindgen]
+impl WallBreak {
+    pub const DEAD_WALL_SIZE: u8 = 14;
+    pub const FIRST: Self = WallBreak { wall: 0, offset: 0 };
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub name: String,
+    pub red_fives: u32,
+}
+
+#[cs_bindgen]
+impl RuleSet {
+    pub const MAX_RED_FIVES: u32 = 4;
+
+    pub fn red_fives(&self) -> u32 {
+        self.red_fives
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests verifying that `From` and `TryFrom` impls tagged with `#[cs_bindgen]` are
+//! generated as C# conversion operators.
+
+use cs_bindgen::prelude::*;
+use std::convert::TryFrom;
+
+/// A tile identified by its suit and rank, which can be converted to and from its
+/// compact tile ID, i.e. its index in the list of the 34 distinct tiles.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactTile {
+    pub suit: u8,
+    pub rank: u8,
+}
+
+#[cs_bindgen]
+impl TryFrom<u8> for CompactTile {
+    type Error = ();
+
+    fn try_from(id: u8) -> Result<Self, ()> {
+        if id >= 34 {
+            return Err(());
+        }
+
+        Ok(CompactTile {
+            suit: id / 9,
+            rank: id % 9 + 1,
+        })
+    }
+}
+
+#[cs_bindgen]
+impl From<CompactTile> for u8 {
+    fn from(tile: CompactTile) -> u8 {
+        tile.suit * 9 + tile.rank - 1
+    }
+}
+
+#[cs_bindgen]
+pub struct Points {
+    value: i32,
+}
+
+#[cs_bindgen]
+impl Points {
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[cs_bindgen]
+impl From<i32> for Points {
+    fn from(value: i32) -> Self {
+        Points { value }
+    }
+}
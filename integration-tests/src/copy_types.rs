@@ -11,6 +11,7 @@ pub struct SimpleTile {
 
 #[cs_bindgen]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
 pub enum Suit {
     Bamboo,
     Circles,
@@ -21,3 +22,36 @@ pub enum Suit {
 pub fn roundtrip_simple_tile(tile: SimpleTile) -> SimpleTile {
     tile
 }
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCounts {
+    pub man: u8,
+    pub pin: u8,
+    pub sou: u8,
+    pub honors: u16,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandShape {
+    pub counts: TileCounts,
+    pub last_tile: SimpleTile,
+    pub shanten: i8,
+    pub ukeire: f32,
+}
+
+#[cs_bindgen]
+pub fn count_tiles(shape: HandShape) -> u32 {
+    let counts = shape.counts;
+    u32::from(counts.man) + u32::from(counts.pin) + u32::from(counts.sou) + u32::from(counts.honors)
+}
+
+#[cs_bindgen]
+pub fn improve_shape(shape: HandShape) -> HandShape {
+    HandShape {
+        shanten: shape.shanten - 1,
+        ukeire: shape.ukeire * 2.0,
+        ..shape
+    }
+}
@@ -0,0 +1,42 @@
+use cs_bindgen::prelude::*;
+
+/// A unique identifier for a game, exposed to C# as a `Guid`.
+#[cs_bindgen(
+    raw = "String",
+    into_raw_with = "GameId::to_hex",
+    from_raw_with = "GameId::from_hex",
+    converter = "GameIdConverter",
+    cs_type = "System.Guid"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameId(u128);
+
+impl GameId {
+    fn to_hex(&self) -> String {
+        format!("{:032x}", self.0)
+    }
+
+    fn from_hex(raw: String) -> Self {
+        GameId(u128::from_str_radix(&raw, 16).expect("Invalid game ID"))
+    }
+}
+
+#[cs_bindgen]
+pub fn first_game_id() -> GameId {
+    GameId(1)
+}
+
+#[cs_bindgen]
+pub fn next_game_id(id: GameId) -> GameId {
+    GameId(id.0 + 1)
+}
+
+#[cs_bindgen]
+pub fn game_ids(count: u8) -> Vec<GameId> {
+    (1..=count as u128).map(GameId).collect()
+}
+
+#[cs_bindgen]
+pub fn newest_game_id(ids: Vec<GameId>) -> GameId {
+    ids.into_iter().max_by_key(|id| id.0).unwrap_or(GameId(0))
+}
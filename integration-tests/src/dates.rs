@@ -0,0 +1,33 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use cs_bindgen::prelude::*;
+
+/// The day the tournament's first match is played.
+#[cs_bindgen]
+pub fn tournament_start() -> NaiveDate {
+    NaiveDate::from_ymd(2020, 9, 13)
+}
+
+#[cs_bindgen]
+pub fn days_until(from: NaiveDate, to: NaiveDate) -> i64 {
+    (to - from).num_days()
+}
+
+#[cs_bindgen]
+pub fn is_weekend(date: NaiveDate) -> bool {
+    date.weekday().number_from_monday() > 5
+}
+
+#[cs_bindgen]
+pub fn match_scheduled_at() -> DateTime<Utc> {
+    Utc.ymd(2020, 9, 13).and_hms(12, 30, 0)
+}
+
+#[cs_bindgen]
+pub fn delay_match(scheduled: DateTime<Utc>, delay: Duration) -> DateTime<Utc> {
+    scheduled + delay
+}
+
+#[cs_bindgen]
+pub fn penalty_time(late_by: Duration) -> Duration {
+    -late_by
+}
@@ -0,0 +1,43 @@
+//! Tests verifying that `Default` impls are exposed as parameterless constructors.
+
+use cs_bindgen::prelude::*;
+
+/// A handle type with a derived `Default` impl.
+#[cs_bindgen]
+#[derive(Debug, Default)]
+pub struct RoundState {
+    round: u32,
+    honba: u32,
+}
+
+#[cs_bindgen]
+impl RoundState {
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn honba(&self) -> u32 {
+        self.honba
+    }
+
+    pub fn add_honba(&mut self) {
+        self.honba += 1;
+    }
+}
+
+/// A value type with a manual `Default` impl.
+#[cs_bindgen(default)]
+#[derive(Debug, Clone, Copy)]
+pub struct StartingPoints {
+    pub points: i32,
+    pub target: i32,
+}
+
+impl Default for StartingPoints {
+    fn default() -> Self {
+        StartingPoints {
+            points: 25_000,
+            target: 30_000,
+        }
+    }
+}
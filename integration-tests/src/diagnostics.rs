@@ -0,0 +1,18 @@
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Default)]
+pub struct DiscardPile {
+    tiles: Vec<String>,
+}
+
+#[cs_bindgen]
+impl DiscardPile {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn discard(&mut self, tile: String) {
+        self.tiles.push(tile);
+    }
+}
@@ -0,0 +1,41 @@
+//! Tests verifying that `Display` impls are used for `ToString` in C#.
+
+use cs_bindgen::prelude::*;
+use std::fmt;
+
+#[cs_bindgen(display)]
+pub struct Score {
+    han: u32,
+    fu: u32,
+}
+
+#[cs_bindgen]
+impl Score {
+    pub fn new(han: u32, fu: u32) -> Self {
+        Score { han, fu }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} han {} fu", self.han, self.fu)
+    }
+}
+
+#[cs_bindgen(display)]
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayTile {
+    pub suit: u8,
+    pub value: u8,
+}
+
+impl fmt::Display for DisplayTile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suit = match self.suit {
+            0 => 'm',
+            1 => 'p',
+            _ => 's',
+        };
+        write!(f, "{}{}", self.value, suit)
+    }
+}
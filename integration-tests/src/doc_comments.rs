@@ -0,0 +1,82 @@
+//! Tests verifying that documented items generate valid C# doc comments.
+//!
+//! The doc comments here deliberately contain characters that need to be escaped in
+//! XML docs, as well as the sequence that ends a C# comment.
+
+use cs_bindgen::prelude::*;
+
+/// Returns the number of tiles in a full set.
+///
+/// Doesn't include flower tiles, e.g. `count < 144 && count */ 4`.
+#[cs_bindgen]
+pub fn full_tile_count() -> u32 {
+    136
+}
+
+/// The score for a winning hand.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct HandScore {
+    pub han: u8,
+    pub fu: u8,
+}
+
+/// Tracks the discards for a player.
+#[cs_bindgen]
+#[derive(Default)]
+pub struct Pond {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Pond {
+    /// Creates an empty pond.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tile to the <pond>.
+    pub fn discard(&mut self, tile: u8) {
+        self.tiles.push(tile);
+    }
+
+    /// The number of discarded tiles.
+    #[cs_bindgen(getter)]
+    pub fn count(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+}
+
+/// The kinds of calls a player can make.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    /// Claims a discard to complete a sequence.
+    Chi,
+    Pon,
+    /// Claims a discard to complete a quad & draws a replacement tile.
+    Kan,
+}
+
+/// A call made by a player.
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum Call {
+    /// A call with no claimed tile.
+    Closed,
+    /// A call that claims a discarded tile.
+    Open { tile: u8 },
+}
+
+#[cs_bindgen]
+pub fn score_points(score: HandScore) -> u32 {
+    score.fu as u32 * 2u32.pow(2 + score.han as u32)
+}
+
+#[cs_bindgen]
+pub fn claimed_tile(call: Call) -> u8 {
+    match call {
+        Call::Closed => 0,
+        Call::Open { tile } => tile,
+    }
+}
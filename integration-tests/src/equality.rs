@@ -0,0 +1,39 @@
+//! Tests verifying that `PartialEq` and `Hash` impls are used for equality and
+//! hashing in C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(PartialEq, Eq, Hash)]
+pub struct Yaku {
+    name: String,
+    han: u32,
+}
+
+#[cs_bindgen]
+impl Yaku {
+    pub fn new(name: String, han: u32) -> Self {
+        Yaku { name, han }
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub row: u8,
+    pub column: u8,
+}
+
+/// Compares discards by tile only, ignoring whether the tile was discarded sideways.
+#[cs_bindgen(eq)]
+#[derive(Debug, Clone, Copy)]
+pub struct Discard {
+    pub tile: u8,
+    pub riichi: bool,
+}
+
+impl PartialEq for Discard {
+    fn eq(&self, other: &Self) -> bool {
+        self.tile == other.tile
+    }
+}
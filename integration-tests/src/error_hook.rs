@@ -0,0 +1,6 @@
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn call_invalid_kan(tile: String) {
+    panic!("Can't call kan on {}", tile);
+}
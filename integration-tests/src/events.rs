@@ -0,0 +1,41 @@
+use cs_bindgen::{event::EventQueue, prelude::*};
+
+#[cs_bindgen]
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    TurnStarted { seat: u8 },
+    Discarded { seat: u8, tile: String },
+    Ended,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Default)]
+pub struct EventfulGame {
+    events: EventQueue<GameEvent>,
+    seat: u8,
+}
+
+#[cs_bindgen]
+impl EventfulGame {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn events(&self) -> EventQueue<GameEvent> {
+        self.events.clone()
+    }
+
+    pub fn discard(&mut self, tile: String) {
+        self.events.push(GameEvent::Discarded {
+            seat: self.seat,
+            tile,
+        });
+
+        self.seat = (self.seat + 1) % 4;
+        self.events.push(GameEvent::TurnStarted { seat: self.seat });
+    }
+
+    pub fn end(&mut self) {
+        self.events.push(GameEvent::Ended);
+    }
+}
@@ -0,0 +1,52 @@
+//! Tests verifying that flags types are exported as C# `[Flags]` enums.
+
+use cs_bindgen::prelude::*;
+
+/// The rules that are enabled for a game.
+#[cs_bindgen(flags)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleFlags {
+    bits: u32,
+}
+
+#[cs_bindgen]
+impl RuleFlags {
+    pub const RED_FIVES: Self = RuleFlags { bits: 1 << 0 };
+    pub const OPEN_TANYAO: Self = RuleFlags { bits: 1 << 1 };
+    pub const DOUBLE_RON: Self = RuleFlags { bits: 1 << 2 };
+    pub const TENHOU: Self = RuleFlags {
+        bits: Self::RED_FIVES.bits | Self::OPEN_TANYAO.bits,
+    };
+}
+
+#[cs_bindgen(flags)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaitFlags(u8);
+
+#[cs_bindgen]
+impl WaitFlags {
+    pub const NONE: Self = WaitFlags(0);
+    pub const RYANMEN: Self = WaitFlags(1);
+    pub const KANCHAN: Self = WaitFlags(2);
+    pub const PENCHAN: Self = WaitFlags(4);
+}
+
+#[cs_bindgen]
+pub const DEFAULT_RULES: RuleFlags = RuleFlags::TENHOU;
+
+#[cs_bindgen]
+pub fn enable_double_ron(rules: RuleFlags) -> RuleFlags {
+    RuleFlags {
+        bits: rules.bits | RuleFlags::DOUBLE_RON.bits,
+    }
+}
+
+#[cs_bindgen]
+pub fn has_red_fives(rules: RuleFlags) -> bool {
+    rules.bits & RuleFlags::RED_FIVES.bits != 0
+}
+
+#[cs_bindgen]
+pub fn wait_count(waits: Vec<WaitFlags>) -> u32 {
+    waits.iter().map(|waits| waits.0.count_ones()).sum()
+}
@@ -15,6 +15,26 @@ pub fn string_arg(arg: String) -> String {
     format!("Hello, {}!", arg)
 }
 
+#[cs_bindgen]
+pub fn str_arg(arg: &str) -> String {
+    format!("Hello, {}!", arg)
+}
+
+#[cs_bindgen]
+pub fn empty_string() -> String {
+    String::new()
+}
+
+#[cs_bindgen]
+pub fn join_with_nul(first: &str, second: &str) -> String {
+    format!("{}\0{}", first, second)
+}
+
+#[cs_bindgen]
+pub fn str_char_count(arg: &str) -> u32 {
+    arg.chars().count() as u32
+}
+
 #[cs_bindgen]
 pub fn is_seven(value: i32) -> bool {
     value == 7
@@ -0,0 +1,20 @@
+//! Tests verifying that instantiations of generic functions are exposed as C#
+//! overloads.
+
+use cs_bindgen::prelude::*;
+use std::fmt::Display;
+
+#[cs_bindgen(instantiate(T = i32, T = String, T = bool))]
+pub fn format_value<T: Display>(value: T) -> String {
+    format!("[{}]", value)
+}
+
+#[cs_bindgen(instantiate(T = u32, T = f64))]
+pub fn sum_values<T: Copy + std::iter::Sum<T>>(values: Vec<T>) -> T {
+    values.into_iter().sum()
+}
+
+#[cs_bindgen(instantiate(K = String, V = u32, K = u32, V = String))]
+pub fn format_pair<K: Display, V: Display>(key: K, value: V) -> String {
+    format!("{}: {}", key, value)
+}
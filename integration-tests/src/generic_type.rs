@@ -0,0 +1,54 @@
+//! Tests verifying that instantiations of generic structs, enums, and impl blocks are
+//! exported as separate C# types.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen(instantiate(T = u32, T = String))]
+pub struct History<T> {
+    entries: Vec<T>,
+}
+
+#[cs_bindgen(instantiate(T = u32, T = String))]
+impl<T: Clone> History<T> {
+    pub fn new(first: T) -> Self {
+        History {
+            entries: vec![first],
+        }
+    }
+
+    pub fn push(&mut self, entry: T) {
+        self.entries.push(entry);
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.entries.last().cloned()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.entries.len() as u32
+    }
+}
+
+/// A claim on a discarded tile, used to verify that instantiations of generic enums
+/// are exported as separate types.
+#[cs_bindgen(instantiate(T = u32, T = String))]
+#[derive(Debug, Clone)]
+pub enum Claim<T> {
+    Pass,
+    Pon(T),
+    Chi { tiles: Vec<T> },
+}
+
+#[cs_bindgen(instantiate(T = u32, T = String))]
+pub fn claimed_tiles<T: Clone>(claim: Claim<T>) -> Vec<T> {
+    match claim {
+        Claim::Pass => Vec::new(),
+        Claim::Pon(tile) => vec![tile.clone(), tile.clone(), tile],
+        Claim::Chi { tiles } => tiles,
+    }
+}
+
+#[cs_bindgen]
+pub fn pon_claim(tile: String) -> Claim<String> {
+    Claim::Pon(tile)
+}
@@ -0,0 +1,51 @@
+//! Tests verifying that handle types are dropped exactly once from C#.
+
+use cs_bindgen::prelude::*;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+static DROP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+#[cs_bindgen]
+pub struct Riichi {
+    _player: u32,
+    dropped: Arc<AtomicBool>,
+}
+
+#[cs_bindgen]
+impl Riichi {
+    pub fn new(player: u32) -> Self {
+        Riichi {
+            _player: player,
+            dropped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Invokes `callback` while the declaration is borrowed, returning `true` if the
+    /// declaration was dropped before the callback returned.
+    pub fn dropped_during(&self, callback: &dyn Fn()) -> bool {
+        let dropped = self.dropped.clone();
+        callback();
+        dropped.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Riichi {
+    fn drop(&mut self) {
+        self.dropped.store(true, Ordering::SeqCst);
+        DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cs_bindgen]
+pub fn riichi_drop_count() -> u32 {
+    DROP_COUNT.load(Ordering::SeqCst)
+}
+
+/// Takes ownership of the riichi declaration, dropping it before returning.
+#[cs_bindgen]
+pub fn consume_riichi(riichi: Riichi) {
+    drop(riichi);
+}
@@ -0,0 +1,51 @@
+//! Tests verifying that lists of handle types can be passed to and returned from Rust.
+
+use cs_bindgen::prelude::*;
+
+/// A player at the table, which is exposed to C# as a handle.
+#[cs_bindgen]
+pub struct TablePlayer {
+    name: String,
+    points: i32,
+}
+
+#[cs_bindgen]
+impl TablePlayer {
+    pub fn new(name: String) -> Self {
+        TablePlayer {
+            name,
+            points: 25_000,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+}
+
+/// Seats a player for each of the names, in order.
+#[cs_bindgen]
+pub fn seat_players(names: Vec<String>) -> Vec<TablePlayer> {
+    names.into_iter().map(TablePlayer::new).collect()
+}
+
+/// Pays the riichi stick from every player, returning the players in the same order.
+#[cs_bindgen]
+pub fn collect_riichi_sticks(players: Vec<TablePlayer>) -> Vec<TablePlayer> {
+    players
+        .into_iter()
+        .map(|mut player| {
+            player.points -= 1_000;
+            player
+        })
+        .collect()
+}
+
+#[cs_bindgen]
+pub fn total_points(players: Vec<TablePlayer>) -> i32 {
+    players.iter().map(|player| player.points).sum()
+}
@@ -0,0 +1,58 @@
+//! Tests verifying that index accessors are exposed as C# indexers.
+
+use cs_bindgen::prelude::*;
+use std::ops::{Index, IndexMut};
+
+#[cs_bindgen]
+pub struct Hand {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Hand {
+    pub fn new(tiles: Vec<u8>) -> Hand {
+        Hand { tiles }
+    }
+
+    #[cs_bindgen(index)]
+    pub fn get(&self, index: usize) -> u8 {
+        self[index]
+    }
+
+    #[cs_bindgen(index_mut)]
+    pub fn set(&mut self, index: usize, tile: u8) {
+        self[index] = tile;
+    }
+}
+
+impl Index<usize> for Hand {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        &self.tiles[index]
+    }
+}
+
+impl IndexMut<usize> for Hand {
+    fn index_mut(&mut self, index: usize) -> &mut u8 {
+        &mut self.tiles[index]
+    }
+}
+
+/// A discard pile indexed by turn number, which is read-only.
+#[cs_bindgen]
+pub struct DiscardPile {
+    discards: Vec<String>,
+}
+
+#[cs_bindgen]
+impl DiscardPile {
+    pub fn new(discards: Vec<String>) -> DiscardPile {
+        DiscardPile { discards }
+    }
+
+    #[cs_bindgen(index)]
+    pub fn discard(&self, turn: u32) -> String {
+        self.discards[turn as usize].clone()
+    }
+}
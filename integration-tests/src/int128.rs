@@ -0,0 +1,31 @@
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn max_u128() -> u128 {
+    u128::MAX
+}
+
+#[cs_bindgen]
+pub fn negate_i128(value: i128) -> i128 {
+    -value
+}
+
+#[cs_bindgen]
+pub fn checked_double_u128(value: u128) -> Option<u128> {
+    value.checked_mul(2)
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Wager {
+    pub stake: i128,
+    pub seat: u8,
+}
+
+#[cs_bindgen]
+pub fn double_stake(wager: Wager) -> Wager {
+    Wager {
+        stake: wager.stake * 2,
+        ..wager
+    }
+}
@@ -0,0 +1,45 @@
+//! Tests verifying that iterators can be enumerated from C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn count_up(count: u32) -> impl Iterator<Item = u32> {
+    0..count
+}
+
+#[cs_bindgen]
+pub fn wind_names() -> impl Iterator<Item = String> {
+    vec!["East", "South", "West", "North"]
+        .into_iter()
+        .map(String::from)
+}
+
+#[cs_bindgen]
+pub fn panicking_iterator(count: u32) -> impl Iterator<Item = u32> {
+    (0..).map(move |value| {
+        if value == count {
+            panic!("Iterated too far");
+        }
+
+        value
+    })
+}
+
+/// A wall of tiles, used to verify that methods can return iterators.
+#[cs_bindgen]
+pub struct Wall {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl Wall {
+    pub fn new(count: u8) -> Wall {
+        Wall {
+            tiles: (0..count).collect(),
+        }
+    }
+
+    pub fn draws(&self) -> impl Iterator<Item = u8> {
+        self.tiles.clone().into_iter().rev()
+    }
+}
@@ -1,11 +1,68 @@
+pub mod alias;
+pub mod array;
+pub mod async_fn;
+pub mod batch;
+pub mod borrowed;
+pub mod builder;
+pub mod bytes;
+pub mod callback;
+pub mod cancel;
+pub mod clone;
 pub mod collections;
+pub mod constant;
+pub mod conversion;
 pub mod copy_types;
+pub mod custom_marshal;
 pub mod data_enum;
+pub mod dates;
+pub mod default;
+pub mod diagnostics;
+pub mod display;
+pub mod doc_comments;
+pub mod equality;
+pub mod error_hook;
+pub mod events;
+pub mod flags;
 pub mod function;
+pub mod generic_fn;
+pub mod generic_type;
+pub mod handle_drop;
+pub mod handle_list;
+pub mod indexer;
+pub mod int128;
+pub mod iterator;
+pub mod logging;
+pub mod map;
+pub mod marshal;
 pub mod method;
 pub mod name_collision;
+pub mod non_exhaustive;
+pub mod operator;
+pub mod option;
+pub mod optional_args;
+pub mod ordering;
+pub mod out_param;
+pub mod panics;
+pub mod property;
+pub mod recursive;
+pub mod ref_mut;
+pub mod rename;
+pub mod result;
+pub mod shared_handle;
 pub mod simple_enum;
+pub mod skip;
+pub mod slice;
+pub mod static_fn;
+pub mod std_impl;
 pub mod structs;
+pub mod thread_safety;
+pub mod time;
+pub mod trace;
+pub mod traits;
+pub mod transparent;
+pub mod tuple;
+pub mod uuids;
+pub mod zero_sized;
 
 // Re-export core cs_bindgen functionality. Required in order for the generated Wasm module.
 cs_bindgen::export!();
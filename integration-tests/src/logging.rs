@@ -0,0 +1,7 @@
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn log_discard(seat: u8, tile: String) {
+    log::info!(target: "mahjong", "Seat {} discarded {}", seat, tile);
+    log::trace!(target: "mahjong", "Checking calls on {}", tile);
+}
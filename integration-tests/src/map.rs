@@ -0,0 +1,30 @@
+//! Tests verifying that map types can be passed to and returned from C#.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+#[cs_bindgen]
+pub fn round_trip_string_map(value: HashMap<String, i32>) -> HashMap<String, i32> {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_btree_map(value: BTreeMap<u32, String>) -> BTreeMap<u32, String> {
+    value
+}
+
+#[cs_bindgen]
+pub fn sum_map_values(value: HashMap<String, i32>) -> i32 {
+    value.values().sum()
+}
+
+#[cs_bindgen]
+pub fn tile_counts(tiles: Vec<SimpleTile>) -> HashMap<SimpleTile, u32> {
+    let mut counts = HashMap::new();
+    for tile in tiles {
+        *counts.entry(tile).or_insert(0) += 1;
+    }
+
+    counts
+}
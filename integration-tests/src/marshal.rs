@@ -0,0 +1,129 @@
+use cs_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The state of a game at the start of a hand.
+#[cs_bindgen(marshal = "json")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub round_wind: Wind,
+    pub honba: u8,
+    pub players: Vec<PlayerState>,
+    pub dora_indicators: Vec<String>,
+    pub riichi_sticks: Option<u32>,
+    pub scores: HashMap<String, i32>,
+}
+
+#[cs_bindgen(marshal = "json")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub name: String,
+    pub seat: Wind,
+    pub points: i32,
+    pub hand: Vec<u8>,
+}
+
+/// Serialized as the name of the variant, so the C# enum is provided by the test
+/// runner instead of being generated.
+#[cs_bindgen(marshal = "json")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Wind {
+    East,
+    South,
+    West,
+    North,
+}
+
+#[cs_bindgen]
+pub fn starting_snapshot(names: Vec<String>) -> GameSnapshot {
+    let seats = [Wind::East, Wind::South, Wind::West, Wind::North];
+    let players = names
+        .iter()
+        .zip(seats.iter())
+        .map(|(name, &seat)| PlayerState {
+            name: name.clone(),
+            seat,
+            points: 25000,
+            hand: vec![1, 1, 9],
+        })
+        .collect::<Vec<_>>();
+
+    GameSnapshot {
+        round_wind: Wind::East,
+        honba: 0,
+        scores: players
+            .iter()
+            .map(|player| (player.name.clone(), player.points))
+            .collect(),
+        players,
+        dora_indicators: vec!["5m".into()],
+        riichi_sticks: None,
+    }
+}
+
+#[cs_bindgen]
+pub fn next_honba(snapshot: GameSnapshot) -> GameSnapshot {
+    GameSnapshot {
+        honba: snapshot.honba + 1,
+        ..snapshot
+    }
+}
+
+#[cs_bindgen]
+pub fn total_points(snapshot: GameSnapshot) -> i32 {
+    snapshot.players.iter().map(|player| player.points).sum()
+}
+
+#[cs_bindgen]
+pub fn dealer(snapshot: GameSnapshot) -> Option<PlayerState> {
+    snapshot
+        .players
+        .into_iter()
+        .find(|player| player.seat == Wind::East)
+}
+
+/// A full record of a game, which is large enough that it's marshaled as MessagePack.
+#[cs_bindgen(marshal = "msgpack")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub rounds: Vec<RoundRecord>,
+    pub final_scores: HashMap<u8, i32>,
+}
+
+#[cs_bindgen(marshal = "msgpack")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundRecord {
+    pub dealer: u8,
+    pub discards: Vec<Vec<String>>,
+    pub winner: Option<u8>,
+}
+
+#[cs_bindgen]
+pub fn record_game(round_count: u8) -> GameRecord {
+    let rounds = (0..round_count)
+        .map(|round| RoundRecord {
+            dealer: round % 4,
+            discards: vec![vec!["1m".into(), "9s".into()], vec!["7z".into()]],
+            winner: if round % 2 == 0 {
+                Some(round % 4)
+            } else {
+                None
+            },
+        })
+        .collect();
+
+    GameRecord {
+        rounds,
+        final_scores: (0..4).map(|seat| (seat, 25000)).collect(),
+    }
+}
+
+#[cs_bindgen]
+pub fn count_discards(record: GameRecord) -> u32 {
+    record
+        .rounds
+        .iter()
+        .flat_map(|round| &round.discards)
+        .map(|discards| discards.len() as u32)
+        .sum()
+}
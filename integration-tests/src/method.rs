@@ -12,8 +12,7 @@ pub struct PersonInfo {
 // getters, setters, and methods that operate on the internal state of the object.
 #[cs_bindgen]
 impl PersonInfo {
-    // TODO: Change the return type back to `Self` once that's supported.
-    pub fn new(name: String, age: i32) -> PersonInfo {
+    pub fn new(name: String, age: i32) -> Self {
         Self {
             name,
             age,
@@ -24,6 +23,23 @@ impl PersonInfo {
         }
     }
 
+    #[cs_bindgen(constructor)]
+    pub fn with_address(name: String, age: i32, street_number: u32, street: String) -> Self {
+        Self {
+            name,
+            age,
+            address: Address {
+                street_number,
+                street,
+            },
+        }
+    }
+
+    // Returns `Self` but isn't named `new`, so it's exported as a static method.
+    pub fn anonymous() -> Self {
+        Self::new("Anonymous".into(), 30)
+    }
+
     // TODO: Change this to return `&str` once that's supported.
     pub fn name(&self) -> String {
         self.name.clone()
@@ -13,3 +13,41 @@ pub struct Test {
 pub enum TestEnum {
     Test(Test),
 }
+
+/// A type whose methods collide with each other and with the generated members once
+/// their names are converted to C#.
+#[cs_bindgen]
+#[derive(Default)]
+pub struct LiveWall {
+    tiles: Vec<u8>,
+}
+
+#[cs_bindgen]
+impl LiveWall {
+    pub fn new() -> Self {
+        LiveWall {
+            tiles: vec![1, 1, 2, 3],
+        }
+    }
+
+    /// Has the same parameters as `new`, so it's generated as a static factory method.
+    #[cs_bindgen(constructor)]
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Would have the same name as the class, so it's generated as `LiveWall2`.
+    pub fn live_wall(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    pub fn count(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    /// Has different parameters than `count`, so it's generated as an overload.
+    #[cs_bindgen(rename = "Count")]
+    pub fn count_value(&self, value: u8) -> u32 {
+        self.tiles.iter().filter(|&&tile| tile == value).count() as u32
+    }
+}
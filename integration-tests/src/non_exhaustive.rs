@@ -0,0 +1,31 @@
+//! Tests verifying that `#[non_exhaustive]` enums tolerate variants that the bindings
+//! don't know about.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Call {
+    Chi,
+    Pon,
+    Kan = 5,
+}
+
+#[cs_bindgen]
+pub fn roundtrip_call(call: Call) -> Call {
+    call
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Discard {
+    Tile(u8),
+    Riichi { tile: u8 },
+}
+
+#[cs_bindgen]
+pub fn roundtrip_discard(discard: Discard) -> Discard {
+    discard
+}
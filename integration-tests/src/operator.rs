@@ -0,0 +1,56 @@
+//! Tests verifying that impls of the operator traits tagged with `#[cs_bindgen]` are
+//! generated as C# operator overloads.
+
+use cs_bindgen::prelude::*;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payout {
+    pub points: i32,
+}
+
+#[cs_bindgen]
+impl Add for Payout {
+    type Output = Payout;
+
+    fn add(self, other: Payout) -> Payout {
+        Payout {
+            points: self.points + other.points,
+        }
+    }
+}
+
+#[cs_bindgen]
+impl Sub for Payout {
+    type Output = Payout;
+
+    fn sub(self, other: Payout) -> Payout {
+        Payout {
+            points: self.points - other.points,
+        }
+    }
+}
+
+#[cs_bindgen]
+impl Neg for Payout {
+    type Output = Payout;
+
+    fn neg(self) -> Payout {
+        Payout {
+            points: -self.points,
+        }
+    }
+}
+
+/// Scales a payout by a factor, e.g. for a dealer win.
+#[cs_bindgen]
+impl Mul<Payout> for i32 {
+    type Output = Payout;
+
+    fn mul(self, payout: Payout) -> Payout {
+        Payout {
+            points: self * payout.points,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Tests verifying that optional values can be passed to and returned from C#.
+
+use crate::{
+    copy_types::SimpleTile,
+    method::{Address, PersonInfo},
+};
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn round_trip_option_i32(value: Option<i32>) -> Option<i32> {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_option_bool(value: Option<bool>) -> Option<bool> {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_option_string(value: Option<String>) -> Option<String> {
+    value
+}
+
+#[cs_bindgen]
+pub fn round_trip_option_tile(value: Option<SimpleTile>) -> Option<SimpleTile> {
+    value
+}
+
+#[cs_bindgen]
+pub fn find_address(street_number: u32) -> Option<Address> {
+    if street_number == 123 {
+        Some(PersonInfo::new("David".into(), 12).address())
+    } else {
+        None
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct OptionalFields {
+    pub value: Option<i32>,
+    pub tile: Option<SimpleTile>,
+}
+
+#[cs_bindgen]
+pub fn round_trip_optional_fields(value: OptionalFields) -> OptionalFields {
+    value
+}
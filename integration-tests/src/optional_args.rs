@@ -0,0 +1,56 @@
+//! Tests verifying that default argument values are exposed as C# optional parameters.
+
+use cs_bindgen::prelude::*;
+
+/// The default number of iterations used when analyzing a hand.
+pub const DEFAULT_ITERATIONS: u32 = 1000;
+
+#[cs_bindgen]
+pub fn analyze(
+    hand: Vec<u8>,
+    #[cs_bindgen(default = DEFAULT_ITERATIONS)] iterations: u32,
+    #[cs_bindgen(default = "riichi")] ruleset: &str,
+    #[cs_bindgen(default = true)] use_aka: bool,
+    seed: Option<u64>,
+) -> String {
+    format!(
+        "{} tiles, {} iterations, {}, aka {}, seed {:?}",
+        hand.len(),
+        iterations,
+        ruleset,
+        use_aka,
+        seed,
+    )
+}
+
+#[cs_bindgen]
+pub fn scaled_points(points: i32, #[cs_bindgen(default = 1.5)] multiplier: f32) -> i32 {
+    (points as f32 * multiplier) as i32
+}
+
+/// Only the trailing `Option` is optional, since C# requires optional parameters to
+/// come last.
+#[cs_bindgen]
+pub fn describe_wait(first: Option<u8>, second: u8, third: Option<u8>) -> String {
+    format!("{:?} {} {:?}", first, second, third)
+}
+
+#[cs_bindgen]
+pub struct Simulator {
+    iterations: u32,
+    seed: Option<u64>,
+}
+
+#[cs_bindgen]
+impl Simulator {
+    pub fn new(#[cs_bindgen(default = 100)] iterations: u32, seed: Option<u64>) -> Self {
+        Simulator { iterations, seed }
+    }
+
+    pub fn summary(&self, #[cs_bindgen(default = "")] prefix: String) -> String {
+        format!(
+            "{}{} iterations, seed {:?}",
+            prefix, self.iterations, self.seed
+        )
+    }
+}
@@ -0,0 +1,27 @@
+//! Tests verifying that `PartialOrd` impls are used for comparisons in C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlayerScore {
+    points: i32,
+}
+
+#[cs_bindgen]
+impl PlayerScore {
+    pub fn new(points: i32) -> Self {
+        PlayerScore { points }
+    }
+
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OrderedTile {
+    pub suit: u8,
+    pub value: u8,
+}
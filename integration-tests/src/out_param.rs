@@ -0,0 +1,41 @@
+//! Tests verifying that trailing tuple elements can be returned through C# `out`
+//! parameters.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen(out(ukeire))]
+pub fn shanten_and_ukeire(tiles: Vec<SimpleTile>) -> (i8, Vec<SimpleTile>) {
+    let ukeire = tiles
+        .iter()
+        .map(|tile| SimpleTile {
+            suit: tile.suit,
+            value: tile.value % 9 + 1,
+        })
+        .collect();
+    (tiles.len() as i8 - 1, ukeire)
+}
+
+#[cs_bindgen(out(min, max))]
+pub fn tile_range(tiles: Vec<SimpleTile>) -> (u8, u8) {
+    let values = tiles.iter().map(|tile| tile.value);
+    (values.clone().min().unwrap_or(0), values.max().unwrap_or(0))
+}
+
+#[cs_bindgen]
+pub struct DiscardPile {
+    tiles: Vec<SimpleTile>,
+}
+
+#[cs_bindgen]
+impl DiscardPile {
+    pub fn new(tiles: Vec<SimpleTile>) -> Self {
+        DiscardPile { tiles }
+    }
+
+    #[cs_bindgen(out(last))]
+    pub fn counts(&self) -> (u32, bool, Option<SimpleTile>) {
+        let len = self.tiles.len() as u32;
+        (len, len >= 6, self.tiles.last().copied())
+    }
+}
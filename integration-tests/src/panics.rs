@@ -0,0 +1,17 @@
+//! Tests verifying that panics in Rust code are rethrown as exceptions in C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn panic_with_message(message: String) -> i32 {
+    panic!("{}", message)
+}
+
+#[cs_bindgen]
+pub fn panic_if(should_panic: bool) -> String {
+    if should_panic {
+        panic!("Asked to panic");
+    }
+
+    "Didn't panic".into()
+}
@@ -0,0 +1,50 @@
+//! Tests verifying that getters and setters are exposed as C# properties.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub struct Seat {
+    wind: String,
+    points: i32,
+    riichi: bool,
+}
+
+#[cs_bindgen]
+impl Seat {
+    pub fn new(wind: String) -> Seat {
+        Seat {
+            wind,
+            points: 25_000,
+            riichi: false,
+        }
+    }
+
+    #[cs_bindgen(getter)]
+    pub fn wind(&self) -> String {
+        self.wind.clone()
+    }
+
+    #[cs_bindgen(getter)]
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+
+    #[cs_bindgen(setter)]
+    pub fn set_points(&mut self, points: i32) {
+        self.points = points;
+    }
+
+    #[cs_bindgen(getter)]
+    pub fn get_riichi(&self) -> bool {
+        self.riichi
+    }
+
+    #[cs_bindgen(setter)]
+    pub fn set_riichi(&mut self, riichi: bool) {
+        if riichi && !self.riichi {
+            self.points -= 1000;
+        }
+
+        self.riichi = riichi;
+    }
+}
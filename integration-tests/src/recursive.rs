@@ -0,0 +1,25 @@
+//! Tests verifying that types with boxed, recursive fields can be passed by value.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Lit(i32),
+    Neg(Box<Expr>),
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+#[cs_bindgen]
+pub fn eval_expr(expr: Expr) -> i32 {
+    match expr {
+        Expr::Lit(value) => value,
+        Expr::Neg(inner) => -eval_expr(*inner),
+        Expr::Add { lhs, rhs } => eval_expr(*lhs) + eval_expr(*rhs),
+    }
+}
+
+#[cs_bindgen]
+pub fn negate_expr(expr: Expr) -> Expr {
+    Expr::Neg(Box::new(expr))
+}
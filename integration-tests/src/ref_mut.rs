@@ -0,0 +1,72 @@
+//! Functions taking `&mut` arguments, which are exposed as `ref` parameters in C#.
+
+use crate::{
+    copy_types::TileCounts,
+    simple_enum::{next_seat_wind, SeatScore, SeatWind},
+};
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn draw_from_wall(remaining: &mut u32, count: u32) -> bool {
+    if *remaining < count {
+        return false;
+    }
+
+    *remaining -= count;
+    true
+}
+
+#[cs_bindgen]
+pub fn toggle_riichi(riichi: &mut bool) {
+    *riichi = !*riichi;
+}
+
+#[cs_bindgen]
+pub fn rotate_seat(wind: &mut SeatWind) {
+    *wind = next_seat_wind(*wind);
+}
+
+#[cs_bindgen]
+pub fn add_honor(counts: &mut TileCounts) -> u16 {
+    counts.honors += 1;
+    counts.honors
+}
+
+#[cs_bindgen]
+pub fn transfer_points(payer: &mut SeatScore, payee: &mut SeatScore, points: i32) {
+    if points < 0 {
+        panic!("Can't transfer a negative number of points");
+    }
+
+    payer.points -= points;
+    payee.points += points;
+}
+
+#[cs_bindgen]
+pub struct Wall {
+    remaining: u32,
+}
+
+#[cs_bindgen]
+impl Wall {
+    pub fn new(remaining: u32) -> Self {
+        Self { remaining }
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    pub fn draw_into(&mut self, counts: &mut TileCounts) {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            counts.man += 1;
+        }
+    }
+}
+
+// Mutable references to handle types still borrow the handle directly.
+#[cs_bindgen]
+pub fn reset_wall(wall: &mut Wall) {
+    wall.remaining = 136;
+}
@@ -0,0 +1,62 @@
+//! Tests verifying that items renamed with `#[cs_bindgen(rename = "...")]` use their
+//! new names in C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen(rename = "GetHTTPPort")]
+pub fn http_port() -> u16 {
+    8080
+}
+
+#[cs_bindgen(rename = "DiceRoll")]
+#[derive(Debug, Clone, Copy)]
+pub struct Roll {
+    #[cs_bindgen(rename = "Base")]
+    pub base: u8,
+    pub bonus: u8,
+}
+
+#[cs_bindgen]
+pub fn roll_total(roll: Roll) -> u32 {
+    roll.base as u32 + roll.bonus as u32
+}
+
+#[cs_bindgen(rename = "Event")]
+pub struct GameEvent {
+    kind: String,
+}
+
+#[cs_bindgen]
+impl GameEvent {
+    pub fn new(kind: String) -> Self {
+        GameEvent { kind }
+    }
+
+    #[cs_bindgen(rename = "Params")]
+    pub fn params(&self) -> String {
+        format!("kind={}", self.kind)
+    }
+}
+
+// NOTE: `event` is a C# keyword, so the generated argument has to be escaped.
+#[cs_bindgen]
+pub fn event_kind(event: &GameEvent) -> String {
+    event.kind.clone()
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Left,
+
+    #[cs_bindgen(rename = "RightSide")]
+    Right,
+}
+
+#[cs_bindgen]
+pub fn flip_side(side: Side) -> Side {
+    match side {
+        Side::Left => Side::Right,
+        Side::Right => Side::Left,
+    }
+}
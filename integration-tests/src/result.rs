@@ -0,0 +1,33 @@
+//! Tests verifying that errors returned from Rust are rethrown as exceptions in C#.
+
+use crate::simple_enum::SimpleCEnum;
+use cs_bindgen::prelude::*;
+use std::num::ParseIntError;
+
+#[cs_bindgen]
+pub fn parse_number(value: String) -> Result<i32, String> {
+    value.parse().map_err(|err| format!("{}", err))
+}
+
+#[cs_bindgen]
+pub fn check_positive(value: i32) -> Result<(), String> {
+    if value > 0 {
+        Ok(())
+    } else {
+        Err(format!("{} is not positive", value))
+    }
+}
+
+#[cs_bindgen]
+pub fn error_enum(fail: bool) -> Result<String, SimpleCEnum> {
+    if fail {
+        Err(SimpleCEnum::Baz)
+    } else {
+        Ok("Success!".into())
+    }
+}
+
+#[cs_bindgen(error = "display")]
+pub fn parse_seat(value: String) -> Result<u8, ParseIntError> {
+    value.parse()
+}
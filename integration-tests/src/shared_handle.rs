@@ -0,0 +1,28 @@
+//! Tests verifying that shared handles can be used from multiple C# objects.
+
+use cs_bindgen::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Game state shared between multiple C# objects, e.g. on a render thread and a logic
+/// thread.
+#[cs_bindgen(shared)]
+pub struct SharedState {
+    discards: Mutex<Vec<String>>,
+}
+
+#[cs_bindgen]
+impl SharedState {
+    pub fn new() -> Arc<Self> {
+        Arc::new(SharedState {
+            discards: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn discard(&self, tile: String) {
+        self.discards.lock().unwrap().push(tile);
+    }
+
+    pub fn discard_count(&self) -> u32 {
+        self.discards.lock().unwrap().len() as u32
+    }
+}
@@ -29,3 +29,48 @@ pub fn roundtrip_simple_enum_with_discriminants(
 ) -> EnumWithDiscriminants {
     val
 }
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SeatWind {
+    East = 1,
+    South,
+    West,
+    North,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i16)]
+pub enum ScoreAdjustment {
+    Chombo = -8000,
+    Neutral = 0,
+    Riichi = 1000,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeatScore {
+    pub wind: SeatWind,
+    pub adjustment: ScoreAdjustment,
+    pub points: i32,
+}
+
+#[cs_bindgen]
+pub fn next_seat_wind(wind: SeatWind) -> SeatWind {
+    match wind {
+        SeatWind::East => SeatWind::South,
+        SeatWind::South => SeatWind::West,
+        SeatWind::West => SeatWind::North,
+        SeatWind::North => SeatWind::East,
+    }
+}
+
+#[cs_bindgen]
+pub fn apply_adjustment(score: SeatScore) -> SeatScore {
+    SeatScore {
+        points: score.points + score.adjustment as i32,
+        ..score
+    }
+}
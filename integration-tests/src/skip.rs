@@ -0,0 +1,56 @@
+//! Tests verifying that skipped fields and methods are excluded from the bindings.
+
+use cs_bindgen::prelude::*;
+
+/// Cached state that isn't exported to C#.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WaitCache {
+    waits: Option<u8>,
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Tenpai {
+    pub han: u8,
+    pub fu: u8,
+
+    #[cs_bindgen(skip)]
+    pub cache: WaitCache,
+}
+
+/// Returns the number of waits in the cache, which is always 0 for a `Tenpai` created
+/// in C#.
+#[cs_bindgen]
+pub fn cached_waits(tenpai: Tenpai) -> u8 {
+    tenpai.cache.waits.unwrap_or_default()
+}
+
+#[cs_bindgen]
+pub fn make_tenpai(han: u8, fu: u8) -> Tenpai {
+    Tenpai {
+        han,
+        fu,
+        cache: WaitCache { waits: Some(3) },
+    }
+}
+
+#[cs_bindgen]
+pub struct Dealer {
+    round: u32,
+}
+
+#[cs_bindgen]
+impl Dealer {
+    pub fn new(round: u32) -> Self {
+        Dealer { round }
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    #[cs_bindgen(skip)]
+    pub fn cache(&self) -> WaitCache {
+        WaitCache::default()
+    }
+}
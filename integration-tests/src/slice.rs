@@ -0,0 +1,34 @@
+//! Tests verifying that slices can be passed as arguments from C#.
+
+use crate::{copy_types::SimpleTile, data_enum::DataEnum};
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn sum_i32_slice(values: &[i32]) -> i32 {
+    values.iter().sum()
+}
+
+#[cs_bindgen]
+pub fn sum_f64_slice(values: &[f64]) -> f64 {
+    values.iter().sum()
+}
+
+#[cs_bindgen]
+pub fn count_true(values: &[bool]) -> u32 {
+    values.iter().filter(|&&value| value).count() as u32
+}
+
+#[cs_bindgen]
+pub fn join_strings(values: &[String]) -> String {
+    values.join(", ")
+}
+
+#[cs_bindgen]
+pub fn highest_tile(tiles: &[SimpleTile]) -> Option<SimpleTile> {
+    tiles.iter().max_by_key(|tile| tile.value).copied()
+}
+
+#[cs_bindgen]
+pub fn count_data_enums(values: &[DataEnum]) -> u32 {
+    values.len() as u32
+}
@@ -0,0 +1,53 @@
+//! Associated functions without a receiver on types that aren't handles.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Dice {
+    pub first: u8,
+    pub second: u8,
+}
+
+#[cs_bindgen]
+impl Dice {
+    pub fn from_seed(seed: u32) -> Dice {
+        Dice {
+            first: (seed % 6) as u8 + 1,
+            second: (seed / 6 % 6) as u8 + 1,
+        }
+    }
+
+    pub fn max_total() -> u8 {
+        12
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dragon {
+    White,
+    Green,
+    Red,
+}
+
+#[cs_bindgen]
+impl Dragon {
+    pub fn all() -> Vec<Dragon> {
+        vec![Dragon::White, Dragon::Green, Dragon::Red]
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone)]
+pub enum Meld {
+    Chii(u8),
+    Pon(u8),
+}
+
+#[cs_bindgen]
+impl Meld {
+    pub fn pon_of(tile: u8) -> Meld {
+        Meld::Pon(tile)
+    }
+}
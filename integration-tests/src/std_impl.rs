@@ -0,0 +1,71 @@
+//! Tests verifying that impls of standard traits tagged with `#[cs_bindgen]` are used
+//! by the C# types, the same as if the trait was specified on the type.
+
+use cs_bindgen::prelude::*;
+use std::{cmp::Ordering, fmt};
+
+#[cs_bindgen]
+pub struct DeadWall {
+    remaining: u32,
+}
+
+#[cs_bindgen]
+impl DeadWall {
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    pub fn draw(&mut self) {
+        self.remaining -= 1;
+    }
+}
+
+#[cs_bindgen]
+impl Default for DeadWall {
+    fn default() -> Self {
+        DeadWall { remaining: 14 }
+    }
+}
+
+#[cs_bindgen]
+impl fmt::Display for DeadWall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} tiles remaining", self.remaining)
+    }
+}
+
+#[cs_bindgen]
+impl Clone for DeadWall {
+    fn clone(&self) -> Self {
+        DeadWall {
+            remaining: self.remaining,
+        }
+    }
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Honba {
+    pub count: u8,
+}
+
+#[cs_bindgen]
+impl PartialEq for Honba {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+    }
+}
+
+#[cs_bindgen]
+impl PartialOrd for Honba {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.count.partial_cmp(&other.count)
+    }
+}
+
+#[cs_bindgen]
+impl std::hash::Hash for Honba {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.count.hash(state);
+    }
+}
@@ -0,0 +1,44 @@
+//! Tests verifying that handles for types that aren't `Sync` are guarded against use
+//! from other threads.
+
+use cs_bindgen::prelude::*;
+use std::cell::Cell;
+
+#[cs_bindgen]
+#[derive(Default)]
+pub struct TurnCounter {
+    turns: Cell<u32>,
+}
+
+#[cs_bindgen]
+impl TurnCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self) -> u32 {
+        self.turns.set(self.turns.get() + 1);
+        self.turns.get()
+    }
+}
+
+#[cs_bindgen]
+#[derive(Default)]
+pub struct TurnLog {
+    turns: Vec<u32>,
+}
+
+#[cs_bindgen]
+impl TurnLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, turn: u32) {
+        self.turns.push(turn);
+    }
+
+    pub fn turn_count(&self) -> u32 {
+        self.turns.len() as u32
+    }
+}
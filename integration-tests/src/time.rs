@@ -0,0 +1,26 @@
+use cs_bindgen::prelude::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The time each player has to make a move.
+#[cs_bindgen]
+pub fn turn_time_limit() -> Duration {
+    Duration::from_millis(7500)
+}
+
+#[cs_bindgen]
+pub fn add_overtime(remaining: Duration, overtime: Option<Duration>) -> Duration {
+    remaining + overtime.unwrap_or_default()
+}
+
+#[cs_bindgen]
+pub fn game_started_at() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_600_000_000)
+}
+
+#[cs_bindgen]
+pub fn seconds_since_epoch(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(err) => -(err.duration().as_secs() as i64),
+    }
+}
@@ -0,0 +1,9 @@
+//! Tests verifying that calls through the generated bindings are traced when the
+//! bindings are generated with `--trace`.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn declare_riichi(seat: u8, player: String, discards: &[u8]) -> bool {
+    seat < 4 && !player.is_empty() && !discards.is_empty()
+}
@@ -0,0 +1,119 @@
+//! Tests verifying that exported traits can be used from C#.
+
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub trait Bot {
+    fn name(&self) -> String;
+
+    fn choose_discard(&self, hand: Vec<u8>) -> u8;
+
+    fn greeting(&self) -> String {
+        format!("{} joins the table", self.name())
+    }
+}
+
+#[cs_bindgen]
+pub struct TsumogiriBot;
+
+#[cs_bindgen]
+impl TsumogiriBot {
+    pub fn new() -> TsumogiriBot {
+        TsumogiriBot
+    }
+}
+
+#[cs_bindgen]
+impl Bot for TsumogiriBot {
+    fn name(&self) -> String {
+        "Tsumogiri".into()
+    }
+
+    fn choose_discard(&self, hand: Vec<u8>) -> u8 {
+        hand.last().copied().unwrap_or_default()
+    }
+}
+
+#[cs_bindgen]
+pub struct HighestTileBot {
+    name: String,
+}
+
+#[cs_bindgen]
+impl HighestTileBot {
+    pub fn new(name: String) -> HighestTileBot {
+        HighestTileBot { name }
+    }
+
+    // NOTE: Has the same name as a trait method to verify that the two don't conflict.
+    pub fn name(&self) -> String {
+        format!("Inherent {}", self.name)
+    }
+}
+
+#[cs_bindgen]
+impl Bot for HighestTileBot {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn choose_discard(&self, hand: Vec<u8>) -> u8 {
+        hand.into_iter().max().unwrap_or_default()
+    }
+
+    fn greeting(&self) -> String {
+        format!("{} is ready", self.name)
+    }
+}
+
+#[cs_bindgen]
+pub fn bot_greeting(bot: &dyn Bot) -> String {
+    bot.greeting()
+}
+
+#[cs_bindgen]
+pub fn bot_discard(bot: impl Bot, hand: Vec<u8>) -> u8 {
+    bot.choose_discard(hand)
+}
+
+#[cs_bindgen]
+pub fn boxed_bot_discard(bot: Box<dyn Bot>, hand: Vec<u8>) -> u8 {
+    bot.choose_discard(hand)
+}
+
+/// Returns a bot chosen by name, used to verify that trait objects can be returned.
+#[cs_bindgen]
+pub fn choose_bot(style: String) -> Box<dyn Bot> {
+    match &*style {
+        "tsumogiri" => Box::new(TsumogiriBot),
+        _ => Box::new(HighestTileBot { name: style }),
+    }
+}
+
+/// A table of bots, used to verify that C# objects can be stored in Rust.
+#[cs_bindgen]
+pub struct Table {
+    bots: Vec<Box<dyn Bot>>,
+}
+
+#[cs_bindgen]
+impl Table {
+    pub fn new() -> Table {
+        Table { bots: Vec::new() }
+    }
+
+    pub fn add_bot(&mut self, bot: Box<dyn Bot>) {
+        self.bots.push(bot);
+    }
+
+    pub fn greetings(&self) -> Vec<String> {
+        self.bots.iter().map(|bot| bot.greeting()).collect()
+    }
+
+    pub fn discards(&self, hand: Vec<u8>) -> Vec<u8> {
+        self.bots
+            .iter()
+            .map(|bot| bot.choose_discard(hand.clone()))
+            .collect()
+    }
+}
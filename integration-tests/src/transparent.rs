@@ -0,0 +1,36 @@
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct TileCount(u8);
+
+#[cs_bindgen(transparent, wrapper)]
+#[derive(Debug, Clone)]
+pub struct SeatName(String);
+
+#[cs_bindgen]
+pub fn add_tiles(count: TileCount, extra: u8) -> TileCount {
+    TileCount(count.0 + extra)
+}
+
+#[cs_bindgen]
+pub fn seat_name(seat: u8) -> SeatName {
+    let name = match seat {
+        0 => "East",
+        1 => "South",
+        2 => "West",
+        _ => "North",
+    };
+    SeatName(name.into())
+}
+
+#[cs_bindgen]
+pub fn shout_seat_name(name: SeatName) -> SeatName {
+    SeatName(name.0.to_uppercase())
+}
+
+#[cs_bindgen]
+pub fn seat_names() -> Vec<SeatName> {
+    (0..4).map(seat_name).collect()
+}
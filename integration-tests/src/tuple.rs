@@ -0,0 +1,33 @@
+//! Tests verifying that tuples can be passed to and returned from C#.
+
+use crate::copy_types::SimpleTile;
+use cs_bindgen::prelude::*;
+
+#[cs_bindgen]
+pub fn round_trip_pair(value: (i32, String)) -> (i32, String) {
+    value
+}
+
+#[cs_bindgen]
+pub fn swap_pair(value: (i32, bool)) -> (bool, i32) {
+    (value.1, value.0)
+}
+
+#[cs_bindgen]
+pub fn round_trip_tile_triple(
+    value: (SimpleTile, SimpleTile, SimpleTile),
+) -> (SimpleTile, SimpleTile, SimpleTile) {
+    value
+}
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TupleFields {
+    pub position: (f32, f32),
+    pub range: (u32, u32),
+}
+
+#[cs_bindgen]
+pub fn round_trip_tuple_fields(value: TupleFields) -> TupleFields {
+    value
+}
@@ -0,0 +1,17 @@
+use cs_bindgen::prelude::*;
+use uuid::Uuid;
+
+#[cs_bindgen]
+pub fn match_id() -> Uuid {
+    Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap()
+}
+
+#[cs_bindgen]
+pub fn format_player_id(id: Uuid) -> String {
+    id.to_hyphenated().to_string()
+}
+
+#[cs_bindgen]
+pub fn is_nil_id(id: Option<Uuid>) -> bool {
+    id.map(|id| id.is_nil()).unwrap_or(true)
+}
@@ -0,0 +1,32 @@
+//! Tests verifying that zero-sized types and fields are marshaled as nothing.
+
+use cs_bindgen::prelude::*;
+use std::marker::PhantomData;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Dora;
+
+#[cs_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct DoraMeld {
+    pub first_tile: u8,
+    pub dora: Dora,
+    pub last_tile: u8,
+    pub kind: PhantomData<Dora>,
+}
+
+#[cs_bindgen]
+pub fn roundtrip_dora_meld(meld: DoraMeld) -> DoraMeld {
+    meld
+}
+
+#[cs_bindgen]
+pub fn count_after_dora(before: u32, _dora: Dora, after: u32) -> u32 {
+    before + after
+}
+
+#[cs_bindgen]
+pub fn make_dora() -> Dora {
+    Dora
+}